@@ -0,0 +1,168 @@
+//! Benchmarks of calling AVM2 methods, as done by movies calling small methods in loops.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use ruffle_core::limits::ExecutionLimit;
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::PlayerBuilder;
+use swf::avm2::types::{
+    AbcFile, ConstantPool, Index, Method, MethodBody, MethodFlags, MethodParam, Multiname,
+    Namespace, Op, Script, Trait, TraitKind,
+};
+use swf::avm2::write::Writer;
+
+/// How many times the method is called by the movie.
+const CALLS: i32 = 100_000;
+
+fn write_code(ops: &[Op]) -> Vec<u8> {
+    let mut code = Vec::new();
+    let mut writer = Writer::new(&mut code);
+    for op in ops {
+        writer.write_op(op).unwrap();
+    }
+    code
+}
+
+/// Builds a movie whose script calls an empty method with `num_locals` locals in a loop.
+fn call_loop_movie(num_locals: u32) -> SwfMovie {
+    let callee = Index::new(1);
+
+    let mut code = write_code(&[
+        Op::GetLocal { index: 0 },
+        Op::PushScope,
+        Op::PushByte { value: 0 },
+        Op::SetLocal { index: 1 },
+    ]);
+    let loop_start = code.len() as i32;
+    code.extend(write_code(&[
+        Op::Label,
+        Op::GetLocal { index: 0 },
+        Op::GetLocal { index: 1 },
+        Op::CallPropVoid {
+            index: callee,
+            num_args: 1,
+        },
+        Op::IncLocalI { index: 1 },
+        Op::GetLocal { index: 1 },
+        Op::PushInt {
+            value: Index::new(1),
+        },
+    ]));
+    // Branches are relative to the end of the branch, which is 4 bytes long.
+    let offset = loop_start - (code.len() as i32 + 4);
+    code.extend(write_code(&[Op::IfLt { offset }, Op::ReturnVoid]));
+
+    let abc = AbcFile {
+        major_version: 46,
+        minor_version: 16,
+        constant_pool: ConstantPool {
+            ints: vec![CALLS],
+            uints: vec![],
+            doubles: vec![],
+            strings: vec![b"".to_vec(), b"callee".to_vec()],
+            namespaces: vec![Namespace::Package(Index::new(1))],
+            namespace_sets: vec![],
+            multinames: vec![Multiname::QName {
+                namespace: Index::new(1),
+                name: Index::new(2),
+            }],
+        },
+        methods: vec![
+            Method {
+                name: Index::new(0),
+                params: vec![],
+                return_type: Index::new(0),
+                flags: MethodFlags::empty(),
+                body: Some(Index::new(0)),
+            },
+            Method {
+                name: Index::new(0),
+                params: vec![MethodParam {
+                    name: None,
+                    kind: Index::new(0),
+                    default_value: None,
+                }],
+                return_type: Index::new(0),
+                flags: MethodFlags::empty(),
+                body: Some(Index::new(1)),
+            },
+        ],
+        metadata: vec![],
+        instances: vec![],
+        classes: vec![],
+        scripts: vec![Script {
+            init_method: Index::new(0),
+            traits: vec![Trait {
+                name: callee,
+                kind: TraitKind::Method {
+                    disp_id: 0,
+                    method: Index::new(1),
+                },
+                metadata: vec![],
+                is_final: false,
+                is_override: false,
+            }],
+        }],
+        method_bodies: vec![
+            MethodBody {
+                method: Index::new(0),
+                max_stack: 3,
+                num_locals: 2,
+                init_scope_depth: 0,
+                max_scope_depth: 1,
+                code,
+                exceptions: vec![],
+                traits: vec![],
+            },
+            MethodBody {
+                method: Index::new(1),
+                max_stack: 0,
+                num_locals,
+                init_scope_depth: 0,
+                max_scope_depth: 0,
+                code: write_code(&[Op::ReturnVoid]),
+                exceptions: vec![],
+                traits: vec![],
+            },
+        ],
+    };
+    let mut abc_data = Vec::new();
+    Writer::new(&mut abc_data).write(abc).unwrap();
+
+    let header = swf::Header::default_with_swf_version(10);
+    let tags = [
+        swf::Tag::FileAttributes(swf::FileAttributes::IS_ACTION_SCRIPT_3),
+        swf::Tag::DoAbc2(swf::DoAbc2 {
+            flags: swf::DoAbc2Flag::empty(),
+            name: swf::SwfStr::from_bytes(b""),
+            data: &abc_data,
+        }),
+        swf::Tag::ShowFrame,
+    ];
+    let mut swf_data = Vec::new();
+    swf::write_swf(&header, &tags, &mut swf_data).unwrap();
+    SwfMovie::from_data(&swf_data, "file:///calls.swf".to_string(), None).unwrap()
+}
+
+/// Calls a method many times, with few locals (stored inline) or many (allocated, and pooled).
+fn call_loop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("call_loop");
+    group.sample_size(20);
+    for (name, num_locals) in [("few_locals", 4), ("many_locals", 32)] {
+        let movie = call_loop_movie(num_locals);
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || {
+                    let player = PlayerBuilder::new().with_movie(movie.clone()).build();
+                    player.lock().unwrap().preload(&mut ExecutionLimit::none());
+                    player
+                },
+                |player| player.lock().unwrap().run_frame(),
+                BatchSize::PerIteration,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, call_loop);
+criterion_main!(benches);
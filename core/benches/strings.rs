@@ -0,0 +1,83 @@
+//! Benchmarks of building strings, as done by string-heavy movies.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use gc_arena::{Arena, Rootable};
+use ruffle_core::string::{AvmString, AvmStringInterner, StringContext, WStr};
+
+type StringArena = Arena<Rootable![AvmStringInterner<'_>]>;
+
+fn with_strings(f: impl for<'gc> FnOnce(&mut StringContext<'gc>)) {
+    let mut arena = StringArena::new(AvmStringInterner::new);
+    arena.mutate_root(|mc, interner| f(&mut StringContext::from_parts(mc, interner)));
+}
+
+/// Appends many small pieces to a single string, like a JSON serializer.
+fn append_pieces(c: &mut Criterion) {
+    let mut group = c.benchmark_group("append_pieces");
+    for (name, start) in [("narrow", "{"), ("wide", "{\u{2603}")] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                with_strings(|strings| {
+                    let piece = AvmString::from("\"key\":1234,");
+                    let mut out = AvmString::new_utf8(strings.gc(), start);
+                    for _ in 0..10_000 {
+                        out = strings.concat(out, piece);
+                    }
+                    black_box(out);
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Joins many small pieces at once, like `String.concat` with many arguments.
+fn concat_all_pieces(c: &mut Criterion) {
+    c.bench_function("concat_all_pieces", |b| {
+        b.iter(|| {
+            with_strings(|strings| {
+                let parts = vec![AvmString::from("\"key\":1234,"); 10_000];
+                black_box(strings.concat_all(&parts));
+            })
+        })
+    });
+}
+
+/// Builds the same short strings repeatedly, like property names in a loop.
+fn build_names(c: &mut Criterion) {
+    c.bench_function("build_names", |b| {
+        b.iter(|| {
+            with_strings(|strings| {
+                let prefix = AvmString::from("item");
+                for _ in 0..100 {
+                    for i in 0..100 {
+                        let index = AvmString::new_utf8(strings.gc(), i.to_string());
+                        black_box(strings.concat(prefix, index));
+                    }
+                }
+            })
+        })
+    });
+}
+
+/// Interns strings that were already interned.
+fn intern_existing(c: &mut Criterion) {
+    c.bench_function("intern_existing", |b| {
+        b.iter(|| {
+            with_strings(|strings| {
+                for _ in 0..10_000 {
+                    black_box(strings.intern_wstr(WStr::from_units(b"onEnterFrame")));
+                }
+            })
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    append_pieces,
+    concat_all_pieces,
+    build_names,
+    intern_existing
+);
+criterion_main!(benches);
@@ -0,0 +1,310 @@
+//! Process-wide cache of decoded SWF assets.
+//!
+//! Assets are keyed by the content hash of the movie that defines them, so
+//! the same movie opened in several players, or loaded repeatedly by a
+//! container movie, only has to decode each asset once.
+
+use crate::character::CompressedBitmap;
+use crate::tag_utils::{ContentHash, SwfMovie};
+use lru::LruCache;
+use ruffle_render::backend::{RenderBackend, ShapeHandle};
+use ruffle_render::bitmap::{Bitmap, BitmapSource};
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::{Mesh, ShapeTessellator, Vertex};
+use std::mem::size_of;
+use std::sync::{Arc, Mutex, OnceLock};
+use swf::CharacterId;
+
+/// The default maximum total size of the cached assets, in bytes.
+#[cfg(not(target_family = "wasm"))]
+pub const DEFAULT_MAX_SIZE: usize = 256 * 1024 * 1024;
+
+/// The default maximum total size of the cached assets, in bytes.
+///
+/// A web page rarely shows the same movie twice, so the cache is kept small.
+#[cfg(target_family = "wasm")]
+pub const DEFAULT_MAX_SIZE: usize = 32 * 1024 * 1024;
+
+/// Identifies a shape defined by a movie, so that its tessellation can be
+/// shared between players.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShapeKey {
+    movie_hash: ContentHash,
+    id: CharacterId,
+
+    /// The index of the glyph, if this is a glyph of the font `id`.
+    glyph: Option<usize>,
+}
+
+impl ShapeKey {
+    /// The shape defined by a `DefineShape` tag.
+    pub fn shape(movie: &SwfMovie, id: CharacterId) -> Self {
+        Self {
+            movie_hash: movie.content_hash(),
+            id,
+            glyph: None,
+        }
+    }
+
+    /// A glyph of the font defined by a `DefineFont` tag.
+    pub fn glyph(movie: &SwfMovie, font_id: CharacterId, index: usize) -> Self {
+        Self {
+            movie_hash: movie.content_hash(),
+            id: font_id,
+            glyph: Some(index),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum AssetKey {
+    Bitmap {
+        movie_hash: ContentHash,
+        id: CharacterId,
+    },
+    Shape(ShapeKey),
+}
+
+#[derive(Clone)]
+enum Asset {
+    Bitmap(Arc<Bitmap>),
+    Mesh(Arc<Mesh>),
+}
+
+impl Asset {
+    /// The memory taken by the asset, in bytes.
+    fn size(&self) -> usize {
+        match self {
+            Asset::Bitmap(bitmap) => bitmap.data().len(),
+            Asset::Mesh(mesh) => mesh_size(mesh),
+        }
+    }
+}
+
+/// The size of the vertices and indices of a mesh, in bytes.
+fn mesh_size(mesh: &Mesh) -> usize {
+    mesh.draws
+        .iter()
+        .map(|draw| {
+            draw.vertices.len() * size_of::<Vertex>() + draw.indices.len() * size_of::<u32>()
+        })
+        .sum()
+}
+
+/// The size of the texture renderers make for each gradient of a mesh, in bytes.
+const GRADIENT_TEXTURE_SIZE: usize = 256 * 4;
+
+/// Cached assets, evicted least recently used first.
+struct AssetCache {
+    assets: LruCache<AssetKey, Asset>,
+    size: usize,
+    max_size: usize,
+}
+
+impl AssetCache {
+    fn new(max_size: usize) -> Self {
+        Self {
+            assets: LruCache::unbounded(),
+            size: 0,
+            max_size,
+        }
+    }
+
+    fn get(&mut self, key: &AssetKey) -> Option<Asset> {
+        self.assets.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: AssetKey, asset: Asset) {
+        let size = asset.size();
+        if size > self.max_size || self.assets.contains(&key) {
+            return;
+        }
+
+        self.shrink_to(self.max_size - size);
+        self.size += size;
+        self.assets.put(key, asset);
+    }
+
+    fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size;
+        self.shrink_to(max_size);
+    }
+
+    /// Evicts assets until they take at most `size` bytes.
+    fn shrink_to(&mut self, size: usize) {
+        while self.size > size {
+            let Some((_, evicted)) = self.assets.pop_lru() else {
+                break;
+            };
+            self.size -= evicted.size();
+        }
+    }
+}
+
+fn asset_cache() -> &'static Mutex<AssetCache> {
+    static CACHE: OnceLock<Mutex<AssetCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(AssetCache::new(DEFAULT_MAX_SIZE)))
+}
+
+/// Sets the maximum total size of the cached assets in bytes, where 0 turns
+/// the cache off.
+///
+/// The cache is shared by every player in the process.
+pub fn set_max_size(max_size: usize) {
+    asset_cache()
+        .lock()
+        .expect("Cache not poisoned")
+        .set_max_size(max_size);
+}
+
+/// Decode a bitmap character, reusing the result of any previous decode of
+/// the same character in an identical movie.
+pub fn decode_bitmap(
+    movie: &SwfMovie,
+    id: CharacterId,
+    compressed: &CompressedBitmap,
+) -> Result<Arc<Bitmap>, ruffle_render::error::Error> {
+    let key = AssetKey::Bitmap {
+        movie_hash: movie.content_hash(),
+        id,
+    };
+
+    let cached = asset_cache().lock().expect("Cache not poisoned").get(&key);
+    if let Some(Asset::Bitmap(bitmap)) = cached {
+        return Ok(bitmap);
+    }
+
+    // Decode without holding the lock, so other players aren't blocked.
+    let bitmap = Arc::new(compressed.decode()?);
+    asset_cache()
+        .lock()
+        .expect("Cache not poisoned")
+        .insert(key, Asset::Bitmap(bitmap.clone()));
+    Ok(bitmap)
+}
+
+/// Register a shape with the renderer, reusing the tessellation of the same
+/// shape in an identical movie.
+///
+/// Renderers that don't draw tessellated meshes get the shape from `shape`.
+pub fn register_shape<'a>(
+    renderer: &mut dyn RenderBackend,
+    key: ShapeKey,
+    shape: impl Fn() -> DistilledShape<'a>,
+    bitmap_source: &dyn BitmapSource,
+) -> ShapeHandle {
+    register_shape_with_size(renderer, key, shape, bitmap_source).0
+}
+
+/// Like [`register_shape`], also returning an estimate of the GPU memory taken by the
+/// buffers and gradient textures of the shape, in bytes.
+///
+/// The size is 0 for renderers that don't draw tessellated meshes.
+pub fn register_shape_with_size<'a>(
+    renderer: &mut dyn RenderBackend,
+    key: ShapeKey,
+    shape: impl Fn() -> DistilledShape<'a>,
+    bitmap_source: &dyn BitmapSource,
+) -> (ShapeHandle, usize) {
+    if !renderer.supports_meshes() {
+        return (renderer.register_shape(shape(), bitmap_source), 0);
+    }
+
+    let cache_key = AssetKey::Shape(key);
+    let cached = asset_cache()
+        .lock()
+        .expect("Cache not poisoned")
+        .get(&cache_key);
+    let mesh = if let Some(Asset::Mesh(mesh)) = cached {
+        mesh
+    } else {
+        // Tessellate without holding the lock, so other players aren't blocked.
+        let mesh = Arc::new(ShapeTessellator::new().tessellate_shape(shape(), bitmap_source));
+        asset_cache()
+            .lock()
+            .expect("Cache not poisoned")
+            .insert(cache_key, Asset::Mesh(mesh.clone()));
+        mesh
+    };
+    match renderer.register_mesh(key.id, Mesh::clone(&mesh), bitmap_source) {
+        Some(handle) => {
+            let size = mesh_size(&mesh) + mesh.gradients.len() * GRADIENT_TEXTURE_SIZE;
+            (handle, size)
+        }
+        None => (renderer.register_shape(shape(), bitmap_source), 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::bitmap::BitmapFormat;
+
+    fn key(id: CharacterId) -> AssetKey {
+        AssetKey::Bitmap {
+            movie_hash: [1; 32],
+            id,
+        }
+    }
+
+    fn bitmap(width: u32) -> Asset {
+        Asset::Bitmap(Arc::new(Bitmap::new(
+            width,
+            1,
+            BitmapFormat::Rgba,
+            vec![0; width as usize * 4],
+        )))
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = AssetCache::new(12);
+        cache.insert(key(1), bitmap(1));
+        cache.insert(key(2), bitmap(1));
+        cache.insert(key(3), bitmap(1));
+        assert!(cache.get(&key(1)).is_some());
+
+        cache.insert(key(4), bitmap(1));
+        assert!(cache.get(&key(2)).is_none());
+        assert!(cache.get(&key(1)).is_some());
+        assert!(cache.get(&key(3)).is_some());
+        assert!(cache.get(&key(4)).is_some());
+        assert_eq!(cache.size, 12);
+
+        // Bitmaps larger than the whole cache are never stored.
+        cache.insert(key(5), bitmap(4));
+        assert!(cache.get(&key(5)).is_none());
+        assert_eq!(cache.size, 12);
+    }
+
+    #[test]
+    fn shares_cached_bitmaps() {
+        let mut cache = AssetCache::new(12);
+        cache.insert(key(1), bitmap(1));
+        let (Some(Asset::Bitmap(a)), Some(Asset::Bitmap(b))) =
+            (cache.get(&key(1)), cache.get(&key(1)))
+        else {
+            panic!("Bitmap should be cached");
+        };
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn lowering_max_size_evicts() {
+        let mut cache = AssetCache::new(12);
+        cache.insert(key(1), bitmap(1));
+        cache.insert(key(2), bitmap(1));
+        cache.insert(key(3), bitmap(1));
+        assert!(cache.get(&key(1)).is_some());
+
+        cache.set_max_size(8);
+        assert!(cache.get(&key(2)).is_none());
+        assert_eq!(cache.size, 8);
+
+        // A size of 0 turns the cache off.
+        cache.set_max_size(0);
+        cache.insert(key(4), bitmap(1));
+        assert!(cache.get(&key(4)).is_none());
+        assert_eq!(cache.size, 0);
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 #[macro_use]
-mod test_utils;
+pub(crate) mod test_utils;
 
 #[macro_use]
 mod function;
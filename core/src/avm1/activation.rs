@@ -6,12 +6,14 @@ use crate::avm1::property::Attribute;
 use crate::avm1::runtime::skip_actions;
 use crate::avm1::scope::{Scope, ScopeClass};
 use crate::avm1::{fscommand, globals, scope, ArrayObject, ScriptObject, Value};
+use crate::backend::log::AvmType;
 use crate::backend::navigator::{NavigationMethod, Request};
 use crate::context::UpdateContext;
 use crate::display_object::{
     DisplayObject, DisplayObjectContainer, MovieClip, TDisplayObject, TDisplayObjectContainer,
 };
 use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
+use crate::form_encoding::encode_form;
 use crate::loader::MovieLoaderVMData;
 use crate::string::{AvmString, StringContext, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::SwfSlice;
@@ -26,8 +28,6 @@ use std::cmp::min;
 use std::fmt;
 use swf::avm1::read::Reader;
 use swf::avm1::types::*;
-use url::form_urlencoded;
-use web_time::Instant;
 
 use super::object_reference::MovieClipReference;
 
@@ -451,7 +451,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         *self.context.actions_since_timeout_check += 1;
         if *self.context.actions_since_timeout_check >= 2000 {
             *self.context.actions_since_timeout_check = 0;
-            if self.context.update_start.elapsed() >= self.context.max_execution_duration {
+            if self.context.is_script_timed_out() {
                 return Err(Error::ExecutionTimeout);
             }
         }
@@ -460,6 +460,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             //Executing beyond the end of a function constitutes an implicit return.
             Ok(FrameControl::Return(ReturnType::Implicit))
         } else {
+            if self.context.opcode_counters.is_enabled() {
+                if let Some(&action_code) = reader.get_ref().first() {
+                    self.context.opcode_counters.count_avm1(action_code);
+                }
+            }
             let action = reader.read_action()?;
             avm_debug!(
                 self.context.avm1,
@@ -605,14 +610,14 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let b = self.context.avm1.pop().to_primitive(self)?;
 
         let result: Value<'_> = match (a, b) {
-            (Value::String(a), Value::String(b)) => {
-                AvmString::concat(self.context.gc_context, b, a).into()
-            }
+            (Value::String(a), Value::String(b)) => self.strings().concat(b, a).into(),
             (Value::String(a), b) => {
-                AvmString::concat(self.context.gc_context, b.coerce_to_string(self)?, a).into()
+                let b = b.coerce_to_string(self)?;
+                self.strings().concat(b, a).into()
             }
             (a, Value::String(b)) => {
-                AvmString::concat(self.context.gc_context, b, a.coerce_to_string(self)?).into()
+                let a = a.coerce_to_string(self)?;
+                self.strings().concat(b, a).into()
             }
             _ => (b.coerce_to_f64(self)? + a.coerce_to_f64(self)?).into(),
         };
@@ -1082,11 +1087,19 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         // Version >=5 equality
         let a = self.context.avm1.pop();
         let b = self.context.avm1.pop();
+        self.note_sitelock_comparison(&a, &b);
         let result = b.abstract_eq(a, self)?;
         self.context.avm1.push(result.into());
         Ok(FrameControl::Continue)
     }
 
+    /// Lets the sitelock detector know about a comparison of two strings.
+    fn note_sitelock_comparison(&mut self, a: &Value<'gc>, b: &Value<'gc>) {
+        if let (Value::String(a), Value::String(b)) = (a, b) {
+            self.context.sitelock_detector.note_comparison(a, b);
+        }
+    }
+
     fn action_extends(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let superclass = self.context.avm1.pop().coerce_to_object(self);
         let subclass = self.context.avm1.pop().coerce_to_object(self);
@@ -1176,9 +1189,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             *self.context.time_offset += 1;
         }
 
-        let time = Instant::now()
-            .duration_since(self.context.start_time)
-            .as_millis() as u32;
+        let time = self.context.time_source.elapsed().as_millis() as u32;
         let result = time.wrapping_add(*self.context.time_offset);
         self.context.avm1.push(result.into());
         Ok(FrameControl::Continue)
@@ -1199,8 +1210,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let target = action.target.decode(self.encoding());
         let url = action.url.decode(self.encoding());
         // TODO: Use `StageObject::get_level_by_path`.
-        if target.starts_with(WStr::from_units(b"_level")) && target.len() > 6 {
-            match target[6..].parse::<i32>() {
+        if let Some(level_id) = self.strip_level_prefix(&target).filter(|id| !id.is_empty()) {
+            match level_id.parse::<i32>() {
                 Ok(level_id) => {
                     if url.is_empty() {
                         let level = self.get_level(level_id);
@@ -1258,11 +1269,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
 
         // TODO: Use `StageObject::get_level_by_path`.
-        let level_target = if target.starts_with(WStr::from_units(b"_level")) && target.len() >= 6 {
-            match target[6..].parse::<f64>() {
+        let level_target = if let Some(level_id) = self.strip_level_prefix(&target) {
+            match level_id.parse::<f64>() {
                 Ok(level_id) => level_id as i32,
                 Err(_) => {
-                    if target.len() == 6 {
+                    if level_id.is_empty() {
                         0
                     } else {
                         -1
@@ -1957,6 +1968,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         // The same as normal equality but types must match
         let a = self.context.avm1.pop();
         let b = self.context.avm1.pop();
+        self.note_sitelock_comparison(&a, &b);
         let result = a == b;
         self.context.avm1.push(result.into());
         Ok(FrameControl::Continue)
@@ -2101,7 +2113,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         // TODO(Herschel): Result with non-string operands?
         let a = self.context.avm1.pop().coerce_to_string(self)?;
         let b = self.context.avm1.pop().coerce_to_string(self)?;
-        let s = AvmString::concat(self.context.gc_context, b, a);
+        let s = self.strings().concat(b, a);
         self.context.avm1.push(s.into());
         Ok(FrameControl::Continue)
     }
@@ -2110,6 +2122,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         // AS1 strcmp
         let a = self.context.avm1.pop().coerce_to_string(self)?;
         let b = self.context.avm1.pop().coerce_to_string(self)?;
+        self.context.sitelock_detector.note_comparison(&a, &b);
         let result = b == a;
         self.context.avm1.push(result.into()); // Diverges from spec: returns a boolean even in SWF 4
         Ok(FrameControl::Continue)
@@ -2248,7 +2261,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         } else {
             val.coerce_to_string(self)?
         };
-        self.context.avm_trace(&out.to_utf8_lossy());
+        self.avm_trace(&out.to_utf8_lossy());
         Ok(FrameControl::Continue)
     }
 
@@ -2390,7 +2403,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             // Undefined/null with is ignored.
             Value::Undefined | Value::Null => {
                 // Mimic Flash's error output.
-                self.context.avm_trace(
+                self.avm_trace(
                     "Error: A 'with' action failed because the specified object did not exist.\n",
                 );
                 Ok(FrameControl::Continue)
@@ -2486,16 +2499,18 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         match method {
             Some(method) => {
                 let vars = self.object_into_form_values(object);
-                let qstring = form_urlencoded::Serializer::new(String::new())
-                    .extend_pairs(vars.iter())
-                    .finish();
+                let qstring = encode_form(
+                    vars.iter()
+                        .map(|(name, value)| (name.as_str(), value.as_str())),
+                );
 
                 match method {
                     NavigationMethod::Get if !url.contains(b'?') => {
                         Request::get(format!("{url}?{qstring}"))
                     }
                     NavigationMethod::Get => Request::get(format!("{url}&{qstring}")),
-                    NavigationMethod::Post => Request::post(
+                    method => Request::request(
+                        method,
                         url.to_utf8_lossy().into_owned(),
                         Some((
                             qstring.as_bytes().to_owned(),
@@ -2650,9 +2665,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 let name = &path[..pos];
                 path = path.slice(pos + 1..).unwrap_or_default();
 
-                if first_element && name == b"this" {
+                // Like other identifiers, these are case insensitive before SWF7,
+                // so `tellTarget("_ROOT/clip")` works in SWF4 and SWF5 content.
+                if first_element && name.eq_with_case(b"this", case_sensitive) {
                     self.this_cell()
-                } else if first_element && name == b"_root" {
+                } else if first_element && name.eq_with_case(b"_root", case_sensitive) {
                     self.root_object()
                 } else {
                     // Get the value from the object.
@@ -2952,6 +2969,19 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.swf_version() > 6
     }
 
+    /// Strips the `_level` prefix from a `getURL` target such as `_level1`,
+    /// returning the remaining level number.
+    ///
+    /// Like property keys, the prefix is case insensitive before SWF7.
+    fn strip_level_prefix<'s>(&self, target: &'s WStr) -> Option<&'s WStr> {
+        let prefix = target.slice(..6)?;
+        if prefix.eq_with_case(b"_level", self.is_case_sensitive()) {
+            Some(&target[6..])
+        } else {
+            None
+        }
+    }
+
     /// Resolve a particular named local variable within this activation.
     ///
     /// Because scopes are object chains, the same rules for `Object::get`
@@ -3022,6 +3052,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.base_clip
     }
 
+    /// Traces a message from the movie of the executing bytecode.
+    pub fn avm_trace(&self, message: &str) {
+        self.context
+            .movie_trace(AvmType::Avm1, &self.base_clip.movie(), message);
+    }
+
     /// Gets the current target clip of this stack frame.
     /// This is the movie clip to which `GotoFrame` and other actions apply.
     /// Changed via `ActionSetTarget`/`ActionSetTarget2`.
@@ -3153,7 +3189,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                     None => WStr::from_units(b"?"),
                 }
             );
-            self.context.avm_trace(&message);
+            self.avm_trace(&message);
 
             // When SetTarget has an invalid target, subsequent GetVariables act
             // as if they are targeting root, but subsequent Play/Stop/etc.
@@ -95,7 +95,7 @@ pub fn trace<'gc>(
         .get(0)
         .unwrap_or(&Value::Undefined)
         .coerce_to_string(activation)?;
-    activation.context.avm_trace(&out.to_utf8_lossy());
+    activation.avm_trace(&out.to_utf8_lossy());
     Ok(Value::Undefined)
 }
 
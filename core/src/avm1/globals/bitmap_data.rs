@@ -1,12 +1,15 @@
 //! flash.display.BitmapData object
 
 use super::matrix::object_to_matrix;
+use super::movie_clip::{new_rectangle, object_to_rectangle};
+use crate::asset_cache;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::globals::bitmap_filter;
 use crate::avm1::globals::color_transform::ColorTransformObject;
 use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Attribute, Error, Object, ScriptObject, TObject, Value};
+use crate::avm_error;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
 use crate::bitmap::bitmap_data::{ChannelOptions, ThresholdOperation};
@@ -15,7 +18,6 @@ use crate::character::Character;
 use crate::display_object::DisplayObject;
 use crate::string::StringContext;
 use crate::swf::BlendMode;
-use crate::{avm1_stub, avm_error};
 use gc_arena::{GcCell, Mutation};
 use ruffle_render::transform::Transform;
 
@@ -532,9 +534,10 @@ fn draw<'gc>(
                 }
             }
 
-            if args.get(4).is_some() {
-                avm1_stub!(activation, "BitmapData", "draw", "with clip rect");
-            }
+            let clip_rect = match args.get(4) {
+                Some(Value::Object(clip_rect)) => object_to_rectangle(activation, *clip_rect)?,
+                _ => None,
+            };
             let smoothing = args
                 .get(5)
                 .unwrap_or(&false.into())
@@ -571,7 +574,7 @@ fn draw<'gc>(
                 },
                 smoothing,
                 blend_mode,
-                None,
+                clip_rect,
                 quality,
             ) {
                 Ok(()) => {}
@@ -663,12 +666,29 @@ fn apply_filter<'gc>(
 fn generate_filter_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let NativeObject::BitmapData(bitmap_data) = this.native() {
         if !bitmap_data.disposed() {
-            avm1_stub!(activation, "BitmapData", "generateFilterRect");
-            return Ok(Value::Undefined);
+            let source_rect = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let Some(source_rect) = object_to_rectangle(activation, source_rect)? else {
+                return Ok(Value::Undefined);
+            };
+
+            let filter_object = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let Some(filter) = bitmap_filter::avm1_to_filter(filter_object, activation.context)
+            else {
+                return Ok(Value::Undefined);
+            };
+
+            let rect = operations::generate_filter_rect(bitmap_data, source_rect, &filter);
+            return new_rectangle(activation, rect);
         }
     }
 
@@ -1495,13 +1515,13 @@ fn load_bitmap<'gc>(
     );
 
     let character = library
-        .library_for_movie(movie)
+        .library_for_movie(movie.clone())
         .and_then(|l| l.character_by_export_name(name));
 
-    let Some((_id, Character::Bitmap { compressed, .. })) = character else {
+    let Some((id, Character::Bitmap { compressed, .. })) = character else {
         return Ok(Value::Undefined);
     };
-    let bitmap = compressed.decode().unwrap();
+    let bitmap = asset_cache::decode_bitmap(&movie, id, compressed).unwrap();
 
     let transparency = true;
     let bitmap_data = BitmapData::new_with_pixels(
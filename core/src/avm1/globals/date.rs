@@ -3,7 +3,7 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, ScriptObject, TObject, Value};
-use crate::locale::{get_current_date_time, get_timezone};
+use crate::locale::get_timezone;
 use crate::string::{AvmString, StringContext};
 use gc_arena::Gc;
 use std::cell::Cell;
@@ -61,8 +61,8 @@ impl Date {
     }
 
     /// Create from current date and time.
-    fn now() -> Self {
-        Self(get_current_date_time().timestamp_millis() as f64)
+    fn now(activation: &Activation<'_, '_>) -> Self {
+        Self(activation.context.time_source.now().timestamp_millis() as f64)
     }
 
     /// Get milliseconds since epoch.
@@ -352,7 +352,7 @@ fn constructor<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let date = match args[..] {
         [] => {
-            let date = Date::now();
+            let date = Date::now(activation);
             if activation.swf_version() > 7 {
                 Date(date.time().round())
             } else {
@@ -382,7 +382,7 @@ fn function<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(AvmString::new_utf8(activation.gc(), Date::now().local().to_string()).into())
+    Ok(AvmString::new_utf8(activation.gc(), Date::now(activation).local().to_string()).into())
 }
 
 /// ECMA-262 Date.UTC
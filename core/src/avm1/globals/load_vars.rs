@@ -9,6 +9,7 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, ScriptObject, TObject, Value};
 use crate::avm1_stub;
 use crate::backend::navigator::{NavigationMethod, Request};
+use crate::form_encoding::encode_form;
 use crate::string::{AvmString, StringContext};
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
@@ -241,9 +242,11 @@ fn to_string<'gc>(
         );
     }
 
-    let query_string = url::form_urlencoded::Serializer::new(String::new())
-        .extend_pairs(form_values.iter())
-        .finish();
+    let query_string = encode_form(
+        form_values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str())),
+    );
 
     Ok(AvmString::new_utf8(activation.context.gc_context, query_string).into())
 }
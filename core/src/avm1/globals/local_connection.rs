@@ -11,6 +11,7 @@ use crate::avm1::{
 use crate::context::UpdateContext;
 use crate::display_object::TDisplayObject;
 use crate::local_connection::{LocalConnectionHandle, LocalConnections};
+use crate::sitelock::SitelockCheck;
 use crate::string::{AvmString, StringContext};
 use flash_lso::types::Value as AmfValue;
 use gc_arena::{Collect, Gc};
@@ -143,12 +144,16 @@ pub fn domain<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let movie = activation.base_clip().movie();
-    let domain = LocalConnections::get_domain(movie.url());
-
-    Ok(Value::String(AvmString::new_utf8(
+    let domain = AvmString::new_utf8(
         activation.context.gc_context,
-        domain,
-    )))
+        LocalConnections::get_domain(movie.url()),
+    );
+    activation
+        .context
+        .sitelock_detector
+        .note_url_read(SitelockCheck::LocalConnectionDomain, &domain);
+
+    Ok(Value::String(domain))
 }
 
 pub fn connect<'gc>(
@@ -9,7 +9,7 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{self, ArrayObject, Object, ScriptObject, TObject, Value};
 use crate::backend::navigator::NavigationMethod;
 use crate::context::UpdateContext;
-use crate::display_object::{Bitmap, EditText, MovieClip, TInteractiveObject};
+use crate::display_object::{hit_test, Bitmap, EditText, MovieClip, TInteractiveObject};
 use crate::ecma_conversions::f64_to_wrapping_i32;
 use crate::prelude::*;
 use crate::string::{AvmString, StringContext};
@@ -239,11 +239,7 @@ pub fn hit_test<'gc>(
             // root can be moved via _root._x etc., so we actually have to transform from root to world space.
             let local = Point::from_pixels(x, y);
             let point = movie_clip.avm1_root_no_lock().local_to_global(local);
-            let ret = if shape {
-                movie_clip.hit_test_shape(activation.context, point, HitTestOptions::AVM_HIT_TEST)
-            } else {
-                movie_clip.hit_test_bounds(point)
-            };
+            let ret = hit_test::hit_test_point(movie_clip.into(), activation.context, point, shape);
             return Ok(ret.into());
         }
     } else if args.len() == 1 {
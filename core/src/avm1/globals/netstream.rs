@@ -2,7 +2,6 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::{NativeObject, Object, TObject};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, ScriptObject, Value};
-use crate::avm1_stub;
 use crate::streams::NetStream;
 use crate::string::StringContext;
 
@@ -33,14 +32,12 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
 };
 
 fn get_buffer_length<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let NativeObject::NetStream(ns) = this.native() {
-        avm1_stub!(activation, "NetStream", "bufferLength");
-
-        return Ok(ns.buffer_time().into());
+        return Ok(ns.buffer_length().into());
     }
 
     Ok(Value::Undefined)
@@ -110,11 +107,11 @@ fn pause<'gc>(
         let is_pause = action.as_bool(activation.swf_version());
 
         if matches!(action, Value::Undefined) {
-            ns.toggle_paused(activation.context);
+            ns.toggle_paused(activation.context, true);
         } else if is_pause {
             ns.pause(activation.context, true);
         } else {
-            ns.resume(activation.context);
+            ns.resume(activation.context, true);
         }
     }
 
@@ -145,8 +142,6 @@ fn set_buffer_time<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let NativeObject::NetStream(ns) = this.native() {
-        avm1_stub!(activation, "NetStream", "setBufferTime");
-
         let buffer_time = args
             .get(0)
             .cloned()
@@ -5,6 +5,7 @@ use crate::avm1::{
 };
 use crate::avm1_stub;
 use crate::display_object::TDisplayObject;
+use crate::shared_object::FlushResult;
 use crate::string::{AvmString, StringContext};
 use flash_lso::amf0::read::AMF0Decoder;
 use flash_lso::amf0::writer::{Amf0Writer, CacheKey, ObjWriter};
@@ -344,7 +345,11 @@ fn get_local<'gc>(
         }
         "localhost"
     } else {
-        movie_url.host_str().unwrap_or_default()
+        let swf_version = activation.context.swf.version();
+        activation
+            .context
+            .system
+            .settings_domain(movie_url.host_str().unwrap_or_default(), swf_version)
     };
 
     let local_path = if let Some(Value::String(local_path)) = args.get(1) {
@@ -505,11 +510,15 @@ fn connect<'gc>(
 pub(crate) fn flush<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let NativeObject::SharedObject(shared_object) = this.native() else {
         return Ok(Value::Undefined);
     };
+    let min_disk_space = match args.get(0) {
+        Some(value) => value.coerce_to_i32(activation)?.max(0) as usize,
+        None => 0,
+    };
     let name = shared_object.read().name();
     let data = this.get("data", activation)?.coerce_to_object(activation);
     let mut lso = new_lso(activation, &name, data);
@@ -519,7 +528,13 @@ pub(crate) fn flush<'gc>(
         Ok(true.into())
     } else {
         let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
-        Ok(activation.context.storage.put(&name, &bytes).into())
+        Ok(
+            match crate::shared_object::flush(activation.context, &name, bytes, min_disk_space) {
+                FlushResult::Flushed => true.into(),
+                FlushResult::Pending => "pending".into(),
+                FlushResult::Failed => false.into(),
+            },
+        )
     }
 }
 
@@ -1,5 +1,5 @@
 //! AVM1 Sound object
-//! TODO: Sound position, transform, loadSound
+//! TODO: Sound transform, loadSound
 
 use std::cell::Cell;
 use std::fmt;
@@ -365,6 +365,14 @@ fn position<'gc>(
     // Return undefined for player <6 if we ever add player version emulation.
     if let NativeObject::Sound(sound) = this.native() {
         if sound.sound().is_some() {
+            // Ask the audio backend for the position of a playing sound, as the stored position
+            // is only updated once per frame.
+            let playing_position = sound
+                .sound_instance()
+                .and_then(|instance| activation.context.audio.get_sound_position(instance));
+            if let Some(position) = playing_position {
+                return Ok((position.round() as u32).into());
+            }
             return Ok(sound.position().into());
         }
     } else {
@@ -491,8 +499,10 @@ pub fn start<'gc>(
                 sound_handle,
                 &SoundInfo {
                     event: SoundEvent::Start,
+                    // The offset is in seconds, and sample positions are always given at 44.1KHz.
+                    // Round it so that offsets like 0.3 don't start a sample frame early.
                     in_sample: if start_offset > 0.0 {
-                        Some((start_offset * 44100.0) as u32)
+                        Some((start_offset * 44100.0).round() as u32)
                     } else {
                         None
                     },
@@ -505,6 +515,11 @@ pub fn start<'gc>(
             );
             if sound_instance.is_some() {
                 sound.set_sound_instance(sound_instance);
+                // Scripts reading `position` right after starting the sound see the offset,
+                // rather than the position of the last played sound.
+                let offset_ms = (start_offset.max(0.0) * 1000.0).round();
+                let offset_ms = offset_ms.min(sound.duration().unwrap_or(u32::MAX).into());
+                sound.set_position(offset_ms as u32);
             }
         } else {
             avm_warn!(activation, "Sound.start: No sound is attached");
@@ -148,15 +148,12 @@ fn concat<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let mut ret: WString = Value::from(this)
-        .coerce_to_string(activation)?
-        .as_wstr()
-        .into();
+    let mut parts = Vec::with_capacity(args.len() + 1);
+    parts.push(Value::from(this).coerce_to_string(activation)?);
     for arg in args {
-        let s = arg.coerce_to_string(activation)?;
-        ret.push_str(&s);
+        parts.push(arg.coerce_to_string(activation)?);
     }
-    Ok(AvmString::new(activation.context.gc_context, ret).into())
+    Ok(activation.strings().concat_all(&parts).into())
 }
 
 fn from_char_code<'gc>(
@@ -164,6 +161,9 @@ fn from_char_code<'gc>(
     _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    // Like the `chr` action, this operates on UTF-16 code units in SWF6+, which can't
+    // express unpaired surrogates, and on raw code units in SWF5 and below.
+    let is_swf6 = activation.swf_version() >= 6;
     let mut out = WString::with_capacity(args.len(), false);
     for arg in args {
         let i = arg.coerce_to_u16(activation)?;
@@ -171,7 +171,11 @@ fn from_char_code<'gc>(
             // Stop at a null-terminator.
             break;
         }
-        out.push(i);
+        if is_swf6 && char::try_from(u32::from(i)).is_err() {
+            out.push(char::REPLACEMENT_CHARACTER as u16);
+        } else {
+            out.push(i);
+        }
     }
     Ok(AvmString::new(activation.context.gc_context, out).into())
 }
@@ -186,6 +190,10 @@ fn index_of<'gc>(
         None => return Ok(Value::Undefined),
         Some(s) => s.clone().coerce_to_string(activation)?,
     };
+    activation
+        .context
+        .sitelock_detector
+        .note_comparison(&this, &pattern);
 
     let start_index = match args.get(1) {
         None | Some(Value::Undefined) => 0,
@@ -208,6 +216,10 @@ fn last_index_of<'gc>(
         None => return Ok(Value::Undefined),
         Some(s) => s.clone().coerce_to_string(activation)?,
     };
+    activation
+        .context
+        .sitelock_detector
+        .note_comparison(&this, &pattern);
 
     let start_index = match args.get(1) {
         None | Some(Value::Undefined) => this.len(),
@@ -369,11 +381,14 @@ fn to_lower_case<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this_val = Value::from(this);
     let this = this_val.coerce_to_string(activation)?;
+    let to_lowercase = if activation.swf_version() >= 6 {
+        string_utils::swf_to_lowercase
+    } else {
+        ascii_to_lowercase
+    };
     Ok(AvmString::new(
         activation.context.gc_context,
-        this.iter()
-            .map(string_utils::swf_to_lowercase)
-            .collect::<WString>(),
+        this.iter().map(to_lowercase).collect::<WString>(),
     )
     .into())
 }
@@ -403,15 +418,30 @@ fn to_upper_case<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this_val = Value::from(this);
     let this = this_val.coerce_to_string(activation)?;
+    let to_uppercase = if activation.swf_version() >= 6 {
+        string_utils::swf_to_uppercase
+    } else {
+        ascii_to_uppercase
+    };
     Ok(AvmString::new(
         activation.context.gc_context,
-        this.iter()
-            .map(string_utils::swf_to_uppercase)
-            .collect::<WString>(),
+        this.iter().map(to_uppercase).collect::<WString>(),
     )
     .into())
 }
 
+/// Maps a code unit to lowercase as SWF5 and below do.
+///
+/// Strings in these versions are in the locale encoding, and only ASCII letters change case.
+fn ascii_to_lowercase(c: u16) -> u16 {
+    u8::try_from(c).map_or(c, |c| c.to_ascii_lowercase().into())
+}
+
+/// Maps a code unit to uppercase as SWF5 and below do. See [`ascii_to_lowercase`].
+fn ascii_to_uppercase(c: u16) -> u16 {
+    u8::try_from(c).map_or(c, |c| c.to_ascii_uppercase().into())
+}
+
 /// Normalizes an  index parameter used in `String` functions such as `substring`.
 /// The returned index will be within the range of `[0, len]`.
 fn string_index(i: i32, len: usize) -> usize {
@@ -433,3 +463,72 @@ fn string_wrapping_index(i: i32, len: usize) -> usize {
         (i as usize).min(len)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    /// Calls a method of `String`, or of a string if `this` is given, and returns the result.
+    fn call<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        this: Option<&str>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<WString, Error<'gc>> {
+        let object = match this {
+            Some(this) => {
+                Value::from(AvmString::new_utf8(activation.gc(), this)).coerce_to_object(activation)
+            }
+            None => activation
+                .context
+                .avm1
+                .global_object()
+                .get("String", activation)?
+                .coerce_to_object(activation),
+        };
+        let result = object.call_method(
+            name.into(),
+            args,
+            activation,
+            crate::avm1::function::ExecutionReason::Special,
+        )?;
+        Ok(result.coerce_to_string(activation)?.as_wstr().into())
+    }
+
+    #[test]
+    fn from_char_code_surrogates() {
+        fn args<'gc>() -> [Value<'gc>; 3] {
+            [0xD800.into(), 0x263A.into(), 0x1F600.into()]
+        }
+        with_avm(5, |activation, _| -> Result<(), Error> {
+            let result = call(activation, None, "fromCharCode", &args())?;
+            assert_eq!(result, WString::from_buf(vec![0xD800u16, 0x263A, 0xF600]));
+            Ok(())
+        });
+        with_avm(6, |activation, _| -> Result<(), Error> {
+            let result = call(activation, None, "fromCharCode", &args())?;
+            assert_eq!(result, WString::from_buf(vec![0xFFFDu16, 0x263A, 0xF600]));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn change_case_per_swf_version() {
+        with_avm(5, |activation, _| -> Result<(), Error> {
+            let upper = call(activation, Some("straße éa"), "toUpperCase", &[])?;
+            assert_eq!(upper, WString::from_utf8("STRAßE éA"));
+            let lower = call(activation, Some("ÉCOLE A"), "toLowerCase", &[])?;
+            assert_eq!(lower, WString::from_utf8("École a"));
+            Ok(())
+        });
+        with_avm(6, |activation, _| -> Result<(), Error> {
+            // Flash Player doesn't expand `ß` into `SS`, as Unicode does.
+            let upper = call(activation, Some("straße éa"), "toUpperCase", &[])?;
+            assert_eq!(upper, WString::from_utf8("STRAßE ÉA"));
+            let lower = call(activation, Some("ÉCOLE A"), "toLowerCase", &[])?;
+            assert_eq!(lower, WString::from_utf8("école a"));
+            Ok(())
+        });
+    }
+}
@@ -6,10 +6,13 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::runtime::Avm1;
 use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm1_stub;
+use crate::backend::ui::SettingsPanel;
+use crate::config::{ScreenInfo, SpoofedCapabilities};
 use crate::context::UpdateContext;
 use crate::string::StringContext;
 use bitflags::bitflags;
 use core::fmt;
+use ruffle_render::backend::ViewportDimensions;
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "exactSettings" => property(get_exact_settings, set_exact_settings);
@@ -202,20 +205,6 @@ impl fmt::Display for PlayerType {
     }
 }
 
-#[derive(Debug, Copy, Clone, FromPrimitive)]
-enum SettingsPanel {
-    Privacy = 0,
-    LocalStorage = 1,
-    Microphone = 2,
-    Camera = 3,
-}
-
-impl SettingsPanel {
-    pub fn from_u8(n: u8) -> Option<Self> {
-        num_traits::FromPrimitive::from_u8(n)
-    }
-}
-
 bitflags! {
     pub struct SystemCapabilities: u32 {
         const AV_HARDWARE      = 1 << 0;
@@ -245,7 +234,8 @@ bitflags! {
 pub struct SystemProperties {
     /// If true then settings should be saved and read from the exact same domain of the player
     /// If false then they should be saved to the super domain
-    pub exact_settings: bool,
+    /// If unset, this depends on the SWF version, see `exact_settings()`
+    pub exact_settings: Option<bool>,
     /// If true, the system codepage should be used for text files
     /// If false, UTF-8 should be used for SWF version >= 6 and ISO Latin-1 for SWF version <= 5
     pub use_codepage: bool,
@@ -257,8 +247,10 @@ pub struct SystemProperties {
     pub screen_color: ScreenColor,
     /// The aspect ratio of the screens pixels
     pub pixel_aspect_ratio: f32,
-    /// The dpi of the screen
+    /// The dpi of the screen, unless the frontend reported the actual screen
     pub dpi: f32,
+    /// The screen the player is shown on, if known
+    pub screen: Option<ScreenInfo>,
     /// The language of the host os
     pub language: Language,
     /// The manufacturer of the player
@@ -269,19 +261,20 @@ pub struct SystemProperties {
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
     pub idc_level: String,
+    /// Values to report instead of the ones above, as configured by the user
+    pub spoofed: SpoofedCapabilities,
 }
 
 impl Default for SystemProperties {
     fn default() -> Self {
-        Self::new()
+        Self::new(SpoofedCapabilities::default())
     }
 }
 
 impl SystemProperties {
-    pub fn new() -> Self {
+    pub fn new(spoofed: SpoofedCapabilities) -> Self {
         SystemProperties {
-            //TODO: default to true on fp>=7, false <= 6
-            exact_settings: true,
+            exact_settings: None,
             //TODO: default to false on fp>=7, true <= 6
             use_codepage: false,
             capabilities: SystemCapabilities::empty(),
@@ -293,13 +286,39 @@ impl SystemProperties {
             pixel_aspect_ratio: 1_f32,
             // source: https://tracker.adobe.com/#/view/FP-3949775
             dpi: 72_f32,
+            screen: None,
             manufacturer: Manufacturer::Linux,
             os: OperatingSystem::Linux,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
+            spoofed,
+        }
+    }
+    /// Whether settings are stored for the exact domain of the movie, rather than its super domain.
+    ///
+    /// Unless set by the movie, this is only the case for SWF7 and later.
+    pub fn exact_settings(&self, swf_version: u8) -> bool {
+        self.exact_settings.unwrap_or(swf_version >= 7)
+    }
+
+    /// The domain that settings and shared objects of a movie served from `host` are stored under.
+    ///
+    /// Without exact settings, all subdomains share the settings of their super domain,
+    /// e.g. `www.example.com` and `games.example.com` both use `example.com`.
+    pub fn settings_domain<'a>(&self, host: &'a str, swf_version: u8) -> &'a str {
+        if self.exact_settings(swf_version) || host.parse::<std::net::IpAddr>().is_ok() {
+            return host;
+        }
+        match host.rmatch_indices('.').nth(1) {
+            Some((index, _)) => &host[index + 1..],
+            None => host,
         }
     }
+
     pub fn get_version_string(&self, avm: &mut Avm1) -> String {
+        if let Some(version) = &self.spoofed.version {
+            return version.clone();
+        }
         format!(
             "{} {},0,0,0",
             self.manufacturer.get_platform_name(),
@@ -307,6 +326,60 @@ impl SystemProperties {
         )
     }
 
+    pub fn get_os_name(&self) -> String {
+        self.spoofed
+            .os
+            .clone()
+            .unwrap_or_else(|| self.os.to_string())
+    }
+
+    pub fn get_language_code(&self, player_version: u8) -> &str {
+        self.spoofed
+            .language
+            .as_deref()
+            .unwrap_or_else(|| self.language.get_language_code(player_version))
+    }
+
+    pub fn get_player_type_name(&self) -> String {
+        self.spoofed
+            .player_type
+            .clone()
+            .unwrap_or_else(|| self.player_type.to_string())
+    }
+
+    pub fn is_debugger(&self) -> bool {
+        self.spoofed
+            .is_debugger
+            .unwrap_or_else(|| self.has_capability(SystemCapabilities::DEBUGGER))
+    }
+
+    /// The screen resolution to report, given the size of the viewport in physical pixels.
+    ///
+    /// Without a spoofed resolution, this is the resolution of the screen the player is shown on,
+    /// or the size of the viewport if the frontend doesn't know it.
+    pub fn get_screen_resolution(&self, viewport_dimensions: ViewportDimensions) -> (f64, f64) {
+        if let Some(resolution) = self
+            .spoofed
+            .screen_resolution
+            .or(self.screen.map(|screen| screen.resolution))
+        {
+            return (resolution.width.into(), resolution.height.into());
+        }
+        // Viewport size is adjusted for HiDPI.
+        let scale_factor = viewport_dimensions.scale_factor;
+        (
+            (f64::from(viewport_dimensions.width) / scale_factor).round(),
+            (f64::from(viewport_dimensions.height) / scale_factor).round(),
+        )
+    }
+
+    /// The dpi of the screen the player is shown on.
+    pub fn screen_dpi(&self) -> f64 {
+        self.screen
+            .map(|screen| screen.dpi)
+            .unwrap_or(self.dpi.into())
+    }
+
     pub fn has_capability(&self, cap: SystemCapabilities) -> bool {
         self.capabilities.contains(cap)
     }
@@ -369,7 +442,7 @@ impl SystemProperties {
                 "SB",
                 self.encode_capability(SystemCapabilities::SCREEN_BROADCAST),
             )
-            .append_pair("DEB", self.encode_capability(SystemCapabilities::DEBUGGER))
+            .append_pair("DEB", if self.is_debugger() { "t" } else { "f" })
             .append_pair(
                 "M",
                 &self.encode_string(
@@ -378,23 +451,16 @@ impl SystemProperties {
                         .as_str(),
                 ),
             )
-            .append_pair(
-                "R",
-                &format!(
-                    "{}x{}",
-                    viewport_dimensions.width, viewport_dimensions.height
-                ),
-            )
+            .append_pair("R", &{
+                let (width, height) = self.get_screen_resolution(viewport_dimensions);
+                format!("{width}x{height}")
+            })
             .append_pair("COL", &self.screen_color.to_string())
             .append_pair("AR", &self.pixel_aspect_ratio.to_string())
-            .append_pair("OS", &self.encode_string(&self.os.to_string()))
-            .append_pair(
-                "L",
-                self.language
-                    .get_language_code(context.avm1.player_version()),
-            )
+            .append_pair("OS", &self.encode_string(&self.get_os_name()))
+            .append_pair("L", self.get_language_code(context.avm1.player_version()))
             .append_pair("IME", self.encode_capability(SystemCapabilities::IME))
-            .append_pair("PT", &self.player_type.to_string())
+            .append_pair("PT", &self.get_player_type_name())
             .append_pair(
                 "AVD",
                 self.encode_not_capability(SystemCapabilities::AV_HARDWARE),
@@ -403,7 +469,7 @@ impl SystemProperties {
                 "LFD",
                 self.encode_not_capability(SystemCapabilities::LOCAL_FILE_READ),
             )
-            .append_pair("DP", &self.dpi.to_string())
+            .append_pair("DP", &self.screen_dpi().to_string())
             .finish()
     }
 }
@@ -429,17 +495,18 @@ pub fn show_settings<'gc>(
     _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    //TODO: should default to the last panel displayed
-    let last_panel_pos = 0;
-
-    let panel_pos = args
-        .get(0)
-        .unwrap_or(&last_panel_pos.into())
-        .coerce_to_i32(activation)?;
-
-    let _panel = SettingsPanel::from_u8(panel_pos as u8).unwrap_or(SettingsPanel::Privacy);
-
-    avm1_stub!(activation, "System", "showSettings");
+    let panel = match args.get(0) {
+        Some(panel) => match panel.coerce_to_i32(activation)? {
+            0 => SettingsPanel::Privacy,
+            1 => SettingsPanel::LocalStorage,
+            2 => SettingsPanel::Microphone,
+            3 => SettingsPanel::Camera,
+            _ => SettingsPanel::Default,
+        },
+        None => SettingsPanel::Default,
+    };
+
+    activation.context.ui.display_settings_panel(panel);
     Ok(Value::Undefined)
 }
 
@@ -478,7 +545,7 @@ pub fn set_exact_settings<'gc>(
         .to_owned()
         .as_bool(activation.swf_version());
 
-    activation.context.system.exact_settings = value;
+    activation.context.system.exact_settings = Some(value);
 
     Ok(Value::Undefined)
 }
@@ -488,7 +555,8 @@ pub fn get_exact_settings<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(activation.context.system.exact_settings.into())
+    let swf_version = activation.context.swf.version();
+    Ok(activation.context.system.exact_settings(swf_version).into())
 }
 
 pub fn on_status<'gc>(
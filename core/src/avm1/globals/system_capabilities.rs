@@ -89,7 +89,6 @@ capabilities_func!(get_has_screen_playback, SystemCapabilities::SCREEN_PLAYBACK)
 capabilities_func!(get_has_streaming_audio, SystemCapabilities::STREAMING_AUDIO);
 capabilities_func!(get_has_streaming_video, SystemCapabilities::STREAMING_VIDEO);
 capabilities_func!(get_has_video_encoder, SystemCapabilities::VIDEO_ENCODER);
-capabilities_func!(get_is_debugger, SystemCapabilities::DEBUGGER);
 inverse_capabilities_func!(
     get_is_local_file_read_disabled,
     SystemCapabilities::LOCAL_FILE_READ
@@ -97,6 +96,14 @@ inverse_capabilities_func!(
 inverse_capabilities_func!(get_is_av_hardware_disabled, SystemCapabilities::AV_HARDWARE);
 inverse_capabilities_func!(get_is_windowless_disabled, SystemCapabilities::WINDOW_LESS);
 
+pub fn get_is_debugger<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.system.is_debugger().into())
+}
+
 pub fn get_player_type<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
@@ -104,7 +111,7 @@ pub fn get_player_type<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(AvmString::new_utf8(
         activation.context.gc_context,
-        activation.context.system.player_type.to_string(),
+        activation.context.system.get_player_type_name(),
     )
     .into())
 }
@@ -131,7 +138,6 @@ pub fn get_language<'gc>(
         activation
             .context
             .system
-            .language
             .get_language_code(activation.context.avm1.player_version()),
     )
     .into())
@@ -143,9 +149,11 @@ pub fn get_screen_resolution_x<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let viewport_dimensions = activation.context.renderer.viewport_dimensions();
-    // Viewport size is adjusted for HiDPI.
-    let adjusted_width = f64::from(viewport_dimensions.width) / viewport_dimensions.scale_factor;
-    Ok(adjusted_width.round().into())
+    let (width, _) = activation
+        .context
+        .system
+        .get_screen_resolution(viewport_dimensions);
+    Ok(width.into())
 }
 
 pub fn get_screen_resolution_y<'gc>(
@@ -154,9 +162,11 @@ pub fn get_screen_resolution_y<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let viewport_dimensions = activation.context.renderer.viewport_dimensions();
-    // Viewport size is adjusted for HiDPI.
-    let adjusted_height = f64::from(viewport_dimensions.height) / viewport_dimensions.scale_factor;
-    Ok(adjusted_height.round().into())
+    let (_, height) = activation
+        .context
+        .system
+        .get_screen_resolution(viewport_dimensions);
+    Ok(height.into())
 }
 
 pub fn get_pixel_aspect_ratio<'gc>(
@@ -172,7 +182,7 @@ pub fn get_screen_dpi<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(activation.context.system.dpi.into())
+    Ok(activation.context.system.screen_dpi().into())
 }
 
 pub fn get_manufacturer<'gc>(
@@ -198,7 +208,7 @@ pub fn get_os_name<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(AvmString::new_utf8(
         activation.context.gc_context,
-        activation.context.system.os.to_string(),
+        activation.context.system.get_os_name(),
     )
     .into())
 }
@@ -4,8 +4,10 @@ use crate::avm1::object::Object;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{ScriptObject, Value};
 use crate::avm1_stub;
+use crate::policy_file;
 use crate::prelude::TDisplayObject;
 use crate::sandbox::SandboxType;
+use crate::sitelock::SitelockCheck;
 use crate::string::{AvmString, StringContext};
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
@@ -21,27 +23,49 @@ const OBJECT_DECLS: &[Declaration] = declare_properties! {
 fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "allowDomain");
+    allow_domains(activation, args, false)?;
     Ok(Value::Undefined)
 }
 
 fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "allowInsecureDomain");
+    allow_domains(activation, args, true)?;
     Ok(Value::Undefined)
 }
 
+/// Grants every domain in `domains` permission to script the calling movie.
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    domains: &[Value<'gc>],
+    insecure: bool,
+) -> Result<(), Error<'gc>> {
+    let movie = activation.base_clip().movie();
+    for domain in domains {
+        let domain = domain.coerce_to_string(activation)?.to_string();
+        activation
+            .context
+            .domain_permissions
+            .allow_domain(&movie, &domain, insecure);
+    }
+    Ok(())
+}
+
 fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "loadPolicyFile");
+    let url = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+    policy_file::load_policy_file(activation.context, &url);
     Ok(Value::Undefined)
 }
 
@@ -59,6 +83,10 @@ fn get_sandbox_type<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .sitelock_detector
+        .note_check(SitelockCheck::SandboxType);
     let movie = activation.base_clip().movie();
     Ok(AvmString::new_utf8(
         activation.context.gc_context,
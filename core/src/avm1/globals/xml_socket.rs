@@ -140,11 +140,17 @@ pub fn connect<'gc>(
             .unwrap_or(&Value::Undefined)
             .coerce_to_u16(activation)?;
 
+        let host = host.to_utf8_lossy().into_owned();
+        let movie = activation.base_clip().movie();
         let UpdateContext {
-            sockets, navigator, ..
+            sockets,
+            navigator,
+            policy_files,
+            ..
         } = activation.context;
 
-        sockets.connect_avm1(*navigator, this, host.to_utf8_lossy().into_owned(), port);
+        policy_files.grant_socket(*navigator, &movie, &host, port);
+        sockets.connect_avm1(*navigator, this, host, port);
 
         // NOTE: At this point we do not know if the connection will succeed
         //       because connecting is an asynchronous process, so we just return true.
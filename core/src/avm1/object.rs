@@ -111,12 +111,16 @@ pub trait TObject<'gc>: 'gc + Collect + Into<Object<'gc>> + Clone + Copy {
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
         // TODO: Extract logic to a `lookup` function.
-        let (this, proto) = if let Some(super_object) = self.as_super_object() {
-            (super_object.this(), super_object.proto(activation))
+        let (this, proto, proto_depth) = if let Some(super_object) = self.as_super_object() {
+            (
+                super_object.this(),
+                super_object.proto(activation),
+                super_object.proto_depth(),
+            )
         } else {
-            ((*self).into(), Value::Object((*self).into()))
+            ((*self).into(), Value::Object((*self).into()), 0)
         };
-        match search_prototype(proto, name.into(), activation, this, false)? {
+        match search_prototype(proto, proto_depth, name.into(), activation, this, false)? {
             Some((value, _depth)) => Ok(value),
             None => Ok(Value::Undefined),
         }
@@ -129,12 +133,16 @@ pub trait TObject<'gc>: 'gc + Collect + Into<Object<'gc>> + Clone + Copy {
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
         // TODO: Extract logic to a `lookup` function.
-        let (this, proto) = if let Some(super_object) = self.as_super_object() {
-            (super_object.this(), super_object.proto(activation))
+        let (this, proto, proto_depth) = if let Some(super_object) = self.as_super_object() {
+            (
+                super_object.this(),
+                super_object.proto(activation),
+                super_object.proto_depth(),
+            )
         } else {
-            ((*self).into(), Value::Object((*self).into()))
+            ((*self).into(), Value::Object((*self).into()), 0)
         };
-        match search_prototype(proto, name.into(), activation, this, true)? {
+        match search_prototype(proto, proto_depth, name.into(), activation, this, true)? {
             Some((value, _depth)) => Ok(value),
             None => Ok(Value::Undefined),
         }
@@ -191,38 +199,51 @@ pub trait TObject<'gc>: 'gc + Collect + Into<Object<'gc>> + Clone + Copy {
         }
 
         let mut value = value;
-        let (this, mut proto) = if let Some(super_object) = self.as_super_object() {
-            (super_object.this(), super_object.proto(activation))
+        let (this, mut proto, mut depth) = if let Some(super_object) = self.as_super_object() {
+            (
+                super_object.this(),
+                super_object.proto(activation),
+                super_object.proto_depth(),
+            )
         } else {
-            ((*self).into(), Value::Object((*self).into()))
+            ((*self).into(), Value::Object((*self).into()), 0)
         };
-        let watcher_result = self.call_watcher(activation, name, &mut value, this);
+
+        // Getter/setter properties can't be watched, so their setter is called directly.
+        if self.has_own_virtual(activation, name) {
+            return self.set_local(name, value, activation, this);
+        }
 
         if !self.has_own_property(activation, name) {
             // Before actually inserting a new property, we need to crawl the
             // prototype chain for virtual setters.
             while let Value::Object(this_proto) = proto {
                 if this_proto.has_own_virtual(activation, name) {
+                    // An inherited getter without a setter makes the property read-only.
                     if let Some(setter) = this_proto.setter(name, activation) {
                         if let Some(exec) = setter.as_executable() {
-                            let _ = exec.exec(
+                            if let Err(Error::ThrownValue(e)) = exec.exec(
                                 ExecutionName::Static("[Setter]"),
                                 activation,
                                 this.into(),
-                                1,
+                                depth.max(1),
                                 &[value],
                                 ExecutionReason::Special,
                                 setter,
-                            );
+                            ) {
+                                return Err(Error::ThrownValue(e));
+                            }
                         }
                     }
                     return Ok(());
                 }
 
                 proto = this_proto.proto(activation);
+                depth = depth.saturating_add(1);
             }
         }
 
+        let watcher_result = self.call_watcher(activation, name, &mut value, this);
         let result = self.set_local(name, value, activation, this);
         watcher_result.and(result)
     }
@@ -287,7 +308,7 @@ pub trait TObject<'gc>: 'gc + Collect + Into<Object<'gc>> + Clone + Copy {
         }
 
         let (method, depth) =
-            match search_prototype(Value::Object(this), name, activation, this, false)? {
+            match search_prototype(Value::Object(this), 0, name, activation, this, false)? {
                 Some((Value::Object(method), depth)) => (method, depth),
                 _ => return Ok(Value::Undefined),
             };
@@ -678,34 +699,42 @@ impl<'gc> Object<'gc> {
 
 /// Perform a prototype lookup of a given object.
 ///
-/// This function returns both the `Value` and the prototype depth from which
-/// it was grabbed from. If the property did not resolve, then it returns
-/// `Ok(None)`.
+/// `proto_depth` is the prototype depth of `proto` itself, relative to `this`;
+/// that is, 0 when searching from `this`, or the depth of the prototype that a
+/// `super` refers to.
+///
+/// This function returns both the `Value` and the prototype depth (relative to
+/// `this`) from which it was grabbed from. If the property did not resolve,
+/// then it returns `Ok(None)`.
 ///
 /// The prototype depth can and should be used to populate the `depth`
 /// parameter necessary to make `super` work.
 pub fn search_prototype<'gc>(
     mut proto: Value<'gc>,
+    proto_depth: u8,
     name: AvmString<'gc>,
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     is_slash_path: bool,
 ) -> Result<Option<(Value<'gc>, u8)>, Error<'gc>> {
-    let mut depth = 0;
+    let mut searched = 0;
+    let mut depth = proto_depth;
     let orig_proto = proto;
 
     while let Value::Object(p) = proto {
-        if depth == 255 {
+        if searched == 255 {
             return Err(Error::PrototypeRecursionLimit);
         }
 
         if let Some(getter) = p.getter(name, activation) {
             if let Some(exec) = getter.as_executable() {
+                // As with methods, a getter found on the object itself
+                // behaves as if it was found on its prototype.
                 let result = exec.exec(
                     ExecutionName::Static("[Getter]"),
                     activation,
                     this.into(),
-                    1,
+                    depth.max(1),
                     &[],
                     ExecutionReason::Special,
                     getter,
@@ -724,12 +753,13 @@ pub fn search_prototype<'gc>(
         }
 
         proto = p.proto(activation);
-        depth += 1;
+        searched += 1;
+        depth = depth.saturating_add(1);
     }
 
     if let Some(resolve) = find_resolve_method(orig_proto, activation)? {
         let result = resolve.call("__resolve".into(), activation, this.into(), &[name.into()])?;
-        return Ok(Some((result, 0)));
+        return Ok(Some((result, proto_depth)));
     }
 
     Ok(None)
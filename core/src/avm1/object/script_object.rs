@@ -14,6 +14,10 @@ use gc_arena::{Collect, GcCell, Mutation};
 pub struct Watcher<'gc> {
     callback: Object<'gc>,
     user_data: Value<'gc>,
+
+    /// Whether the callback is currently running. Assigning to the watched
+    /// property from inside its own watcher doesn't call the watcher again.
+    is_running: bool,
 }
 
 impl<'gc> Watcher<'gc> {
@@ -21,6 +25,7 @@ impl<'gc> Watcher<'gc> {
         Self {
             callback,
             user_data,
+            is_running: false,
         }
     }
 
@@ -316,23 +321,40 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         value: &mut Value<'gc>,
         this: Object<'gc>,
     ) -> Result<(), Error<'gc>> {
-        let mut result = Ok(());
-        let watcher = self
+        let is_case_sensitive = activation.is_case_sensitive();
+        let watcher = match self
             .0
-            .read()
+            .write(activation.context.gc_context)
             .watchers
-            .get(name, activation.is_case_sensitive())
-            .cloned();
-        if let Some(watcher) = watcher {
-            let old_value = self.get_stored(name, activation)?;
+            .get_mut(name, is_case_sensitive)
+        {
+            Some(watcher) if !watcher.is_running => {
+                watcher.is_running = true;
+                watcher.clone()
+            }
+            _ => return Ok(()),
+        };
+
+        let result = self.get_stored(name, activation).and_then(|old_value| {
             match watcher.call(activation, name, old_value, *value, this) {
                 Ok(v) => *value = v,
                 Err(Error::ThrownValue(e)) => {
                     *value = Value::Undefined;
-                    result = Err(Error::ThrownValue(e));
+                    return Err(Error::ThrownValue(e));
                 }
                 Err(_) => *value = Value::Undefined,
             };
+            Ok(())
+        });
+
+        // The callback may have removed its own watcher.
+        if let Some(watcher) = self
+            .0
+            .write(activation.context.gc_context)
+            .watchers
+            .get_mut(name, is_case_sensitive)
+        {
+            watcher.is_running = false;
         }
 
         result
@@ -10,6 +10,7 @@ use crate::context::UpdateContext;
 use crate::display_object::{
     DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
 };
+use crate::sitelock::SitelockCheck;
 use crate::string::{AvmString, WStr};
 use crate::types::Percent;
 use gc_arena::{Collect, GcCell, GcWeakCell, Mutation};
@@ -686,7 +687,14 @@ fn drop_target<'gc>(activation: &mut Activation<'_, 'gc>, this: DisplayObject<'g
 
 fn url<'gc>(activation: &mut Activation<'_, 'gc>, this: DisplayObject<'gc>) -> Value<'gc> {
     match this.as_movie_clip() {
-        Some(mc) => AvmString::new_utf8(activation.gc(), mc.movie().url()).into(),
+        Some(mc) => {
+            let url = AvmString::new_utf8(activation.gc(), mc.movie().url());
+            activation
+                .context
+                .sitelock_detector
+                .note_url_read(SitelockCheck::Avm1Url, &url);
+            url.into()
+        }
         None => activation.strings().empty().into(),
     }
 }
@@ -52,6 +52,15 @@ impl<'gc> SuperObject<'gc> {
         self.0.this
     }
 
+    /// The prototype depth of `super.__proto__`, relative to `this`.
+    ///
+    /// Anything found while searching from there must be executed with its
+    /// own depth, so that a further `super` inside of it keeps climbing the
+    /// prototype chain of `this` instead of looping back on itself.
+    pub fn proto_depth(&self) -> u8 {
+        self.0.depth.saturating_add(1)
+    }
+
     fn base_proto(&self, activation: &mut Activation<'_, 'gc>) -> Object<'gc> {
         let depth = self.0.depth;
         let mut proto = self.0.this;
@@ -98,16 +107,27 @@ impl<'gc> TObject<'gc> for SuperObject<'gc> {
         _this: Value<'gc>,
         args: &[Value<'gc>],
     ) -> Result<Value<'gc>, Error<'gc>> {
-        let constructor = self
-            .base_proto(activation)
-            .get("__constructor__", activation)?
-            .coerce_to_object(activation);
+        // `__constructor__` may be inherited when an `extends` chain was built
+        // at runtime, in which case the constructor runs at the depth of the
+        // prototype it was found on, not the one `super` started from.
+        let base_proto = self.base_proto(activation);
+        let (constructor, depth) = match search_prototype(
+            Value::Object(base_proto),
+            self.0.depth,
+            "__constructor__".into(),
+            activation,
+            self.0.this,
+            false,
+        )? {
+            Some((constructor, depth)) => (constructor.coerce_to_object(activation), depth),
+            None => return Ok(Value::Undefined),
+        };
         match constructor.as_executable() {
             Some(exec) => exec.exec(
                 ExecutionName::Dynamic(name),
                 activation,
                 self.0.this.into(),
-                self.0.depth + 1,
+                depth.saturating_add(1),
                 args,
                 ExecutionReason::FunctionCall,
                 constructor,
@@ -124,18 +144,24 @@ impl<'gc> TObject<'gc> for SuperObject<'gc> {
         reason: ExecutionReason,
     ) -> Result<Value<'gc>, Error<'gc>> {
         let this = self.0.this;
-        let (method, depth) =
-            match search_prototype(self.proto(activation), name, activation, this, false)? {
-                Some((Value::Object(method), depth)) => (method, depth),
-                _ => return Ok(Value::Undefined),
-            };
+        let (method, depth) = match search_prototype(
+            self.proto(activation),
+            self.proto_depth(),
+            name,
+            activation,
+            this,
+            false,
+        )? {
+            Some((Value::Object(method), depth)) => (method, depth),
+            _ => return Ok(Value::Undefined),
+        };
 
         match method.as_executable() {
             Some(exec) => exec.exec(
                 ExecutionName::Dynamic(name),
                 activation,
                 this.into(),
-                self.0.depth + depth + 1,
+                depth,
                 args,
                 reason,
                 method,
@@ -588,7 +588,7 @@ pub fn root_error_handler<'gc>(activation: &mut Activation<'_, 'gc>, error: Erro
             let message = value
                 .coerce_to_string(activation)
                 .unwrap_or_else(|_| "undefined".into());
-            activation.context.avm_trace(&message.to_utf8_lossy());
+            activation.avm_trace(&message.to_utf8_lossy());
             // Continue execution without halting.
             return;
         }
@@ -5,7 +5,7 @@ use crate::avm1::{
 };
 use crate::display_object::TDisplayObject;
 
-pub fn with_avm<F>(swf_version: u8, test: F)
+pub(crate) fn with_avm<F>(swf_version: u8, test: F)
 where
     F: for<'a, 'gc> FnOnce(&mut Activation<'_, 'gc>, Object<'gc>) -> Result<(), Error<'gc>>,
 {
@@ -2,6 +2,7 @@
 
 use std::rc::Rc;
 
+use crate::avm2::activation::RegisterPool;
 use crate::avm2::class::AllocatorFn;
 use crate::avm2::error::make_error_1107;
 use crate::avm2::globals::{
@@ -12,7 +13,7 @@ use crate::avm2::scope::ScopeChain;
 use crate::avm2::script::{Script, TranslationUnit};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, DisplayObjectWeak, TDisplayObject};
-use crate::string::{AvmString, StringContext};
+use crate::string::{AvmAtom, AvmString, StringContext};
 use crate::tag_utils::SwfMovie;
 use crate::PlayerRuntime;
 
@@ -33,7 +34,7 @@ macro_rules! avm_debug {
 }
 
 pub mod activation;
-mod amf;
+pub mod amf;
 pub mod api_version;
 mod array;
 pub mod bytearray;
@@ -52,6 +53,7 @@ mod metadata;
 mod method;
 mod multiname;
 mod namespace;
+mod number_format;
 pub mod object;
 mod op;
 mod optimize;
@@ -115,6 +117,9 @@ pub struct Avm2<'gc> {
     /// Scopes currently present of the scope stack.
     scope_stack: Vec<Scope<'gc>>,
 
+    /// Register sets kept to be reused by the next method calls.
+    register_pool: RegisterPool<'gc>,
+
     /// The current call stack of the player.
     call_stack: GcRefLock<'gc, CallStack<'gc>>,
 
@@ -156,8 +161,9 @@ pub struct Avm2<'gc> {
     /// A list of objects which are capable of receiving broadcasts.
     ///
     /// Certain types of events are "broadcast events" that are emitted on all
-    /// constructed objects in order of their creation, whether or not they are
-    /// currently present on the display list. This list keeps track of that.
+    /// objects listening to them, in the order they started listening, whether
+    /// or not they are currently present on the display list. This list keeps
+    /// track of that.
     broadcast_list: FnvHashMap<AvmString<'gc>, Vec<WeakObject<'gc>>>,
 
     /// The list of 'orphan' objects - these objects have no parent,
@@ -182,10 +188,25 @@ pub struct Avm2<'gc> {
     #[collect(require_static)]
     pub root_api_version: ApiVersion,
 
+    /// Whether an uncaught error is being dispatched to `uncaughtErrorEvents`, so that errors
+    /// thrown by the handlers of such an error aren't dispatched again.
+    handling_uncaught_error: bool,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 
     pub optimizer_enabled: bool,
+
+    /// Whether every method is verified when its ABC file is loaded, rather than when
+    /// it's first called.
+    ///
+    /// This is off by default: Flash Player only reports VerifyErrors for code that runs,
+    /// and many movies contain broken code that never does.
+    pub strict_verification: bool,
+
+    /// Whether the name of each frame event is traced before it's broadcast, so that
+    /// tests can check the order in which frame event handlers run.
+    pub log_frame_events: bool,
 }
 
 impl<'gc> Avm2<'gc> {
@@ -208,6 +229,7 @@ impl<'gc> Avm2<'gc> {
             player_runtime,
             stack: Vec::with_capacity(PREALLOCATED_STACK_SIZE),
             scope_stack: Vec::new(),
+            register_pool: RegisterPool::default(),
             call_stack: GcRefLock::new(mc, CallStack::new().into()),
             playerglobals_domain,
             stage_domain,
@@ -232,10 +254,14 @@ impl<'gc> Avm2<'gc> {
             // Set the lowest version for now - this will be overridden when we set our movie
             root_api_version: ApiVersion::AllVersions,
 
+            handling_uncaught_error: false,
+
             #[cfg(feature = "avm_debug")]
             debug_output: false,
 
             optimizer_enabled: true,
+            strict_verification: false,
+            log_frame_events: false,
         }
     }
 
@@ -447,12 +473,12 @@ impl<'gc> Avm2<'gc> {
         let mut activation = Activation::from_nothing(context);
         match events::dispatch_event(&mut activation, target, event, simulate_dispatch) {
             Err(err) => {
-                tracing::error!(
-                    "Encountered AVM2 error when dispatching `{}` event: {:?}",
-                    event_name,
+                Self::uncaught_error(
+                    &mut activation,
+                    target.as_display_object(),
                     err,
+                    &format!("Encountered AVM2 error when dispatching `{event_name}` event"),
                 );
-                // TODO: push the error onto `loaderInfo.uncaughtErrorEvents`
                 false
             }
             Ok(handled) => handled,
@@ -493,6 +519,34 @@ impl<'gc> Avm2<'gc> {
         bucket.push(object.downgrade());
     }
 
+    /// Whether any live object listens to an event broadcast on every frame, like `enterFrame`.
+    pub fn has_broadcast_listeners(context: &UpdateContext<'gc>) -> bool {
+        context.avm2.broadcast_list.values().any(|bucket| {
+            bucket
+                .iter()
+                .any(|entry| entry.upgrade(context.gc_context).is_some())
+        })
+    }
+
+    /// Remove an object from the broadcast list of an event.
+    ///
+    /// This must be called once an object no longer has any handlers for the
+    /// event, so that it's broadcast to in the order it registered again if it
+    /// starts listening to it later, like Flash Player does.
+    pub fn unregister_broadcast_listener(
+        context: &mut UpdateContext<'gc>,
+        object: Object<'gc>,
+        event_name: AvmString<'gc>,
+    ) {
+        if let Some(bucket) = context.avm2.broadcast_list.get_mut(&event_name) {
+            bucket.retain(|entry| {
+                entry
+                    .upgrade(context.gc_context)
+                    .is_some_and(|obj| !Object::ptr_eq(obj, object))
+            });
+        }
+    }
+
     /// Dispatch an event on all objects in the current execution list.
     ///
     /// `on_type` specifies a class or interface constructor whose instances,
@@ -521,34 +575,30 @@ impl<'gc> Avm2<'gc> {
             return;
         }
 
-        let el_length = context
+        // Objects that start listening during the broadcast only receive the next one,
+        // while objects that stop listening simply have no handlers left to run.
+        let listeners = context
             .avm2
             .broadcast_list
-            .entry(event_name)
-            .or_default()
-            .len();
-
-        for i in 0..el_length {
-            let object = context
-                .avm2
-                .broadcast_list
-                .get(&event_name)
-                .unwrap()
-                .get(i)
-                .copied();
-
-            if let Some(object) = object.and_then(|obj| obj.upgrade(context.gc_context)) {
+            .get(&event_name)
+            .cloned()
+            .unwrap_or_default();
+
+        for object in listeners {
+            if let Some(object) = object.upgrade(context.gc_context) {
                 let mut activation = Activation::from_nothing(context);
 
                 if object.is_of_type(on_type.inner_class_definition()) {
                     if let Err(err) = events::dispatch_event(&mut activation, object, event, false)
                     {
-                        tracing::error!(
-                            "Encountered AVM2 error when broadcasting `{}` event: {:?}",
-                            event_name,
+                        Self::uncaught_error(
+                            &mut activation,
+                            object.as_display_object(),
                             err,
+                            &format!(
+                                "Encountered AVM2 error when broadcasting `{event_name}` event"
+                            ),
                         );
-                        // TODO: push the error onto `loaderInfo.uncaughtErrorEvents`
                     }
                 }
             }
@@ -562,19 +612,77 @@ impl<'gc> Avm2<'gc> {
             .retain(|x| x.upgrade(context.gc_context).is_some());
     }
 
-    pub fn run_stack_frame_for_callable(
-        callable: Object<'gc>,
-        receiver: Value<'gc>,
-        args: &[Value<'gc>],
-        domain: Domain<'gc>,
-        context: &mut UpdateContext<'gc>,
-    ) -> Result<(), String> {
-        let mut evt_activation = Activation::from_domain(context, domain);
-        callable
-            .call(receiver, args, &mut evt_activation)
-            .map_err(|e| format!("{e:?}"))?;
+    /// Handles an error that wasn't caught by ActionScript code.
+    ///
+    /// Thrown values are dispatched as `uncaughtError` events to the `uncaughtErrorEvents`
+    /// of the movie `source` belongs to, and then to those of each movie that loaded it,
+    /// until a handler stops the propagation of the event. Errors that no handler saw are
+    /// logged with the given description, as are internal errors.
+    pub fn uncaught_error(
+        activation: &mut Activation<'_, 'gc>,
+        source: Option<DisplayObject<'gc>>,
+        error: Error<'gc>,
+        description: &str,
+    ) {
+        if let Error::AvmError(value) = error {
+            if !activation.context.avm2.handling_uncaught_error {
+                activation.context.avm2.handling_uncaught_error = true;
+                let handled = Self::dispatch_uncaught_error(activation, source, value);
+                activation.context.avm2.handling_uncaught_error = false;
+                if handled {
+                    return;
+                }
+            }
+        }
+        tracing::error!("{description}: {error:?}");
+    }
 
-        Ok(())
+    fn dispatch_uncaught_error(
+        activation: &mut Activation<'_, 'gc>,
+        source: Option<DisplayObject<'gc>>,
+        value: Value<'gc>,
+    ) -> bool {
+        let mut root = source
+            .and_then(|source| source.avm2_root())
+            .or_else(|| activation.context.stage.root_clip());
+        let mut handled = false;
+
+        while let Some(loader_info) = root
+            .and_then(|root| root.loader_info())
+            .and_then(|loader_info| loader_info.as_loader_info_object().copied())
+        {
+            let event_class = activation.avm2().classes().uncaughterrorevent;
+            let args = &["uncaughtError".into(), true.into(), true.into(), value];
+            let event = match event_class.construct(activation, args) {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::error!("Couldn't construct `uncaughtError` event: {e:?}");
+                    return handled;
+                }
+            };
+
+            match events::dispatch_event(
+                activation,
+                loader_info.uncaught_error_events(),
+                event,
+                false,
+            ) {
+                Ok(dispatched) => handled |= dispatched,
+                Err(e) => tracing::error!(
+                    "Encountered AVM2 error when dispatching `uncaughtError` event: {e:?}"
+                ),
+            }
+            if event.as_event().unwrap().is_propagation_stopped() {
+                break;
+            }
+
+            root = loader_info
+                .loader()
+                .and_then(|loader| loader.as_display_object())
+                .and_then(|loader| loader.avm2_root());
+        }
+
+        handled
     }
 
     /// Load an ABC file embedded in a `DoAbc` or `DoAbc2` tag.
@@ -607,6 +715,10 @@ impl<'gc> Avm2<'gc> {
             tunit.load_script(i as u32, &mut activation)?;
         }
 
+        if activation.avm2().strict_verification() {
+            tunit.verify_methods(&mut activation)?;
+        }
+
         if !flags.contains(DoAbc2Flag::LAZY_INITIALIZE) {
             return Ok(Some(tunit.get_script(num_scripts - 1).unwrap()));
         }
@@ -670,6 +782,24 @@ impl<'gc> Avm2<'gc> {
         self.call_stack.borrow_mut(mc).pop()
     }
 
+    /// Whether errors capture the call stack, so that `Error.getStackTrace` returns it.
+    ///
+    /// As in the release build of Flash Player, stack traces are unavailable unless
+    /// we're debugging.
+    pub fn stack_traces_enabled() -> bool {
+        tracing::enabled!(tracing::Level::INFO) || cfg!(feature = "avm_debug")
+    }
+
+    /// Records the source file of the executing call, for stack traces.
+    pub fn set_debug_file(&self, mc: &Mutation<'gc>, file: AvmAtom<'gc>) {
+        self.call_stack.borrow_mut(mc).set_debug_file(file)
+    }
+
+    /// Records the source line of the executing call, for stack traces.
+    pub fn set_debug_line(&self, mc: &Mutation<'gc>, line: u32) {
+        self.call_stack.borrow_mut(mc).set_debug_line(line)
+    }
+
     pub fn call_stack(&self) -> GcRefLock<'gc, CallStack<'gc>> {
         self.call_stack
     }
@@ -789,6 +919,22 @@ impl<'gc> Avm2<'gc> {
     pub fn set_optimizer_enabled(&mut self, value: bool) {
         self.optimizer_enabled = value;
     }
+
+    pub fn strict_verification(&self) -> bool {
+        self.strict_verification
+    }
+
+    pub fn set_strict_verification(&mut self, value: bool) {
+        self.strict_verification = value;
+    }
+
+    pub fn log_frame_events(&self) -> bool {
+        self.log_frame_events
+    }
+
+    pub fn set_log_frame_events(&mut self, value: bool) {
+        self.log_frame_events = value;
+    }
 }
 
 /// If the provided `DisplayObjectWeak` should have frames run, returns
@@ -5,7 +5,8 @@ use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
 use crate::avm2::e4x::{escape_attribute_value, escape_element_value};
 use crate::avm2::error::{
-    make_error_1065, make_error_1127, make_error_1506, make_null_or_undefined_error, type_error,
+    make_error_1001, make_error_1065, make_error_1127, make_error_1502, make_error_1506,
+    make_null_or_undefined_error, type_error,
 };
 use crate::avm2::method::{BytecodeMethod, Method, ResolvedParamConfig};
 use crate::avm2::object::{
@@ -20,16 +21,18 @@ use crate::avm2::value::Value;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::{Avm2, Error};
+use crate::backend::log::AvmType;
 use crate::context::UpdateContext;
 use crate::string::{AvmAtom, AvmString, StringContext};
 use crate::tag_utils::SwfMovie;
-use gc_arena::Gc;
+use gc_arena::{Collect, Gc};
 use smallvec::SmallVec;
 use std::cmp::{min, Ordering};
 use std::sync::Arc;
 use swf::avm2::types::{
     Exception, Index, Method as AbcMethod, MethodFlags as AbcMethodFlags, Namespace as AbcNamespace,
 };
+use web_time::Instant;
 
 use super::error::make_mismatch_error;
 
@@ -37,7 +40,10 @@ use super::error::make_mismatch_error;
 ///
 /// This type exists primarily because SmallVec isn't garbage-collectable.
 
-pub struct RegisterSet<'gc>(SmallVec<[Value<'gc>; 8]>);
+pub struct RegisterSet<'gc>(SmallVec<[Value<'gc>; INLINE_REGISTERS]>);
+
+/// The number of registers a [`RegisterSet`] holds without allocating.
+const INLINE_REGISTERS: usize = 8;
 
 unsafe impl<'gc> gc_arena::Collect for RegisterSet<'gc> {
     #[inline]
@@ -65,6 +71,43 @@ impl<'gc> RegisterSet<'gc> {
     }
 }
 
+/// The most register sets kept by a [`RegisterPool`].
+const MAX_POOLED_REGISTER_SETS: usize = 64;
+
+/// Register sets of methods that have returned, kept to be reused by later calls.
+///
+/// Register sets of up to 8 registers are stored inline, but bigger ones would
+/// otherwise be allocated and freed on every call, which adds up when a loop
+/// calls a method with many locals. Only those are pooled, and they are
+/// emptied before being pooled, so the pool never keeps values alive.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct RegisterPool<'gc>(Vec<RegisterSet<'gc>>);
+
+impl<'gc> RegisterPool<'gc> {
+    /// Take a register set with a given number of registers from the pool,
+    /// or create one if no pooled set can be used.
+    ///
+    /// The given registers will be set to `undefined`.
+    pub fn take(&mut self, num: u32) -> RegisterSet<'gc> {
+        if num as usize > INLINE_REGISTERS {
+            if let Some(mut registers) = self.0.pop() {
+                registers.0.resize(num as usize, Value::Undefined);
+                return registers;
+            }
+        }
+        RegisterSet::new(num)
+    }
+
+    /// Return a register set that is no longer used to the pool.
+    pub fn give_back(&mut self, mut registers: RegisterSet<'gc>) {
+        if registers.0.spilled() && self.0.len() < MAX_POOLED_REGISTER_SETS {
+            registers.0.clear();
+            self.0.push(registers);
+        }
+    }
+}
+
 #[derive(Clone)]
 enum FrameControl<'gc> {
     Continue,
@@ -399,14 +442,15 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         bound_class: Option<Class<'gc>>,
         callee: Object<'gc>,
     ) -> Result<(), Error<'gc>> {
-        let body: Result<_, Error<'gc>> = method
-            .body()
-            .ok_or_else(|| "Cannot execute non-native method without body".into());
-        let body = body?;
+        // Bodies are only looked up on first call, so a method whose body is
+        // missing (or was discarded as malformed) only fails if it's actually used.
+        let Some(body) = method.body() else {
+            return Err(make_error_1001(self, &method.method_name()));
+        };
         let num_locals = body.num_locals;
         let has_rest_or_args = method.is_variadic();
 
-        let mut local_registers = RegisterSet::new(num_locals + 1);
+        let mut local_registers = self.context.avm2.register_pool.take(num_locals + 1);
         *local_registers.get_unchecked_mut(0) = this.into();
 
         let activation_class =
@@ -511,6 +555,13 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(())
     }
 
+    /// Return the registers of this activation to the register pool, once the
+    /// method it was initialized for with `init_from_method` has returned.
+    pub fn release_registers(&mut self) {
+        let registers = std::mem::replace(&mut self.local_registers, RegisterSet::new(0));
+        self.context.avm2.register_pool.give_back(registers);
+    }
+
     /// Construct an activation for the execution of a builtin method.
     ///
     /// It is a logic error to attempt to execute builtins within the same
@@ -603,6 +654,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.caller_movie().unwrap_or(self.context.swf.clone())
     }
 
+    /// Traces a message from the movie of the original AS3 caller.
+    pub fn avm_trace(&self, message: &str) {
+        self.context
+            .movie_trace(AvmType::Avm2, &self.caller_movie_or_root(), message);
+    }
+
     /// Returns the global scope of this activation.
     ///
     /// The global scope refers to scope at the bottom of the
@@ -804,17 +861,30 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.actions_since_timeout_check += 1;
         if self.actions_since_timeout_check >= 64000 {
             self.actions_since_timeout_check = 0;
-            if self.context.update_start.elapsed() >= self.context.max_execution_duration {
-                return Err(
-                    "A script in this movie has taken too long to execute and has been terminated."
-                        .into(),
-                );
+            if self.context.script_timed_out {
+                // The script caught Error #1502 and kept going, so it's terminated for good.
+                let timeout = self.context.max_execution_duration;
+                if self.context.update_start.elapsed() >= timeout {
+                    // It has run for one timeout period before #1502, and another one since.
+                    let total = timeout.saturating_mul(2).as_secs_f64();
+                    return Err(format!(
+                        "Error #1503: A script failed to exit after {total} seconds and was terminated."
+                    )
+                    .into());
+                }
+            } else if self.context.is_script_timed_out() {
+                self.context.script_timed_out = true;
+                self.context.update_start = Instant::now();
+                return Err(make_error_1502(self));
             }
         }
 
         let op = &opcodes[self.ip as usize];
         self.ip += 1;
         avm_debug!(self.avm2(), "Opcode: {op:?}");
+        if self.context.opcode_counters.is_enabled() {
+            self.context.opcode_counters.count_avm2(op.name());
+        }
 
         {
             let result = match op {
@@ -2019,16 +2089,14 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             // note: with not-yet-guaranteed assumption that Integer < 1<<28, this won't overflow.
             (Value::Integer(n1), Value::Integer(n2)) => (n1 + n2).into(),
             (Value::Number(n1), Value::Number(n2)) => (n1 + n2).into(),
-            (Value::String(s), value2) => Value::String(AvmString::concat(
-                self.context.gc_context,
-                s,
-                value2.coerce_to_string(self)?,
-            )),
-            (value1, Value::String(s)) => Value::String(AvmString::concat(
-                self.context.gc_context,
-                value1.coerce_to_string(self)?,
-                s,
-            )),
+            (Value::String(s), value2) => {
+                let s2 = value2.coerce_to_string(self)?;
+                Value::String(self.strings().concat(s, s2))
+            }
+            (value1, Value::String(s)) => {
+                let s1 = value1.coerce_to_string(self)?;
+                Value::String(self.strings().concat(s1, s))
+            }
             (Value::Object(value1), Value::Object(value2))
                 if (value1.as_xml_list_object().is_some() || value1.as_xml_object().is_some())
                     && (value2.as_xml_list_object().is_some()
@@ -2045,16 +2113,14 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 let prim_value2 = value2.coerce_to_primitive(None, self)?;
 
                 match (prim_value1, prim_value2) {
-                    (Value::String(s), value2) => Value::String(AvmString::concat(
-                        self.context.gc_context,
-                        s,
-                        value2.coerce_to_string(self)?,
-                    )),
-                    (value1, Value::String(s)) => Value::String(AvmString::concat(
-                        self.context.gc_context,
-                        value1.coerce_to_string(self)?,
-                        s,
-                    )),
+                    (Value::String(s), value2) => {
+                        let s2 = value2.coerce_to_string(self)?;
+                        Value::String(self.strings().concat(s, s2))
+                    }
+                    (value1, Value::String(s)) => {
+                        let s1 = value1.coerce_to_string(self)?;
+                        Value::String(self.strings().concat(s1, s))
+                    }
                     (value1, value2) => Value::Number(
                         value1.coerce_to_number(self)? + value2.coerce_to_number(self)?,
                     ),
@@ -2319,9 +2385,17 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    /// Lets the sitelock detector know about a comparison of two strings.
+    fn note_sitelock_comparison(&mut self, value1: &Value<'gc>, value2: &Value<'gc>) {
+        if let (Value::String(a), Value::String(b)) = (value1, value2) {
+            self.context.sitelock_detector.note_comparison(a, b);
+        }
+    }
+
     fn op_if_strict_eq(&mut self, offset: i32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
+        self.note_sitelock_comparison(&value1, &value2);
 
         if value1.strict_eq(&value2) {
             self.ip += offset;
@@ -2333,6 +2407,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_if_strict_ne(&mut self, offset: i32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
+        self.note_sitelock_comparison(&value1, &value2);
 
         if !value1.strict_eq(&value2) {
             self.ip += offset;
@@ -2344,6 +2419,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_if_eq(&mut self, offset: i32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
+        self.note_sitelock_comparison(&value1, &value2);
 
         if value1.abstract_eq(&value2, self)? {
             self.ip += offset;
@@ -2355,6 +2431,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_if_ne(&mut self, offset: i32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
+        self.note_sitelock_comparison(&value1, &value2);
 
         if !value1.abstract_eq(&value2, self)? {
             self.ip += offset;
@@ -2454,6 +2531,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_strict_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
+        self.note_sitelock_comparison(&value1, &value2);
         self.push_raw(value1.strict_eq(&value2));
 
         Ok(FrameControl::Continue)
@@ -2462,6 +2540,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
+        self.note_sitelock_comparison(&value1, &value2);
 
         let result = value1.abstract_eq(&value2, self)?;
 
@@ -3083,20 +3162,24 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    #[cfg(feature = "avm_debug")]
     fn op_debug_file(&mut self, file_name: AvmAtom<'gc>) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "File: {file_name}");
+        if Avm2::stack_traces_enabled() {
+            self.context
+                .avm2
+                .set_debug_file(self.context.gc_context, file_name);
+        }
 
         Ok(FrameControl::Continue)
     }
 
-    #[cfg(not(feature = "avm_debug"))]
-    fn op_debug_file(&mut self, _file_name: AvmAtom<'gc>) -> Result<FrameControl<'gc>, Error<'gc>> {
-        Ok(FrameControl::Continue)
-    }
-
     fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {line_num}");
+        if Avm2::stack_traces_enabled() {
+            self.context
+                .avm2
+                .set_debug_line(self.context.gc_context, line_num);
+        }
 
         Ok(FrameControl::Continue)
     }
@@ -1,7 +1,7 @@
 use crate::avm2::class::Class;
 use crate::avm2::function::display_function;
 use crate::avm2::method::Method;
-use crate::string::WString;
+use crate::string::{AvmAtom, WStr, WString};
 use gc_arena::Collect;
 
 use super::script::Script;
@@ -16,10 +16,23 @@ pub enum CallNode<'gc> {
     },
 }
 
+/// A call on the stack, with the position in the source code it was last at.
+#[derive(Collect, Clone)]
+#[collect(no_drop)]
+struct CallFrame<'gc> {
+    node: CallNode<'gc>,
+
+    /// The source file, as reported by the last `debugfile` opcode of this call.
+    file: Option<AvmAtom<'gc>>,
+
+    /// The source line, as reported by the last `debugline` opcode of this call.
+    line: Option<u32>,
+}
+
 #[derive(Collect, Clone)]
 #[collect(no_drop)]
 pub struct CallStack<'gc> {
-    stack: Vec<CallNode<'gc>>,
+    stack: Vec<CallFrame<'gc>>,
 }
 
 impl<'gc> CallStack<'gc> {
@@ -27,22 +40,44 @@ impl<'gc> CallStack<'gc> {
         Self { stack: Vec::new() }
     }
 
+    fn push_node(&mut self, node: CallNode<'gc>) {
+        self.stack.push(CallFrame {
+            node,
+            file: None,
+            line: None,
+        })
+    }
+
     pub fn push(&mut self, method: Method<'gc>, class: Option<Class<'gc>>) {
-        self.stack.push(CallNode::Method { method, class })
+        self.push_node(CallNode::Method { method, class })
     }
 
     pub fn push_global_init(&mut self, script: Script<'gc>) {
-        self.stack.push(CallNode::GlobalInit(script))
+        self.push_node(CallNode::GlobalInit(script))
     }
 
     pub fn pop(&mut self) -> Option<CallNode<'gc>> {
-        self.stack.pop()
+        self.stack.pop().map(|frame| frame.node)
+    }
+
+    /// Records the source file the innermost call is executing, from debug information.
+    pub fn set_debug_file(&mut self, file: AvmAtom<'gc>) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.file = Some(file);
+        }
+    }
+
+    /// Records the source line the innermost call is executing, from debug information.
+    pub fn set_debug_line(&mut self, line: u32) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.line = Some(line);
+        }
     }
 
     pub fn display(&self, output: &mut WString) {
-        for call in self.stack.iter().rev() {
+        for frame in self.stack.iter().rev() {
             output.push_utf8("\n\tat ");
-            match call {
+            match &frame.node {
                 CallNode::GlobalInit(script) => {
                     let name = if let Some(tuint) = script.translation_unit() {
                         if let Some(name) = tuint.name() {
@@ -61,6 +96,13 @@ impl<'gc> CallStack<'gc> {
                 }
                 CallNode::Method { method, class } => display_function(output, method, *class),
             }
+            if let Some(line) = frame.line {
+                output.push_char('[');
+                if let Some(file) = frame.file {
+                    display_file(output, file.as_wstr());
+                }
+                output.push_utf8(&format!(":{line}]"));
+            }
         }
     }
 
@@ -69,6 +111,22 @@ impl<'gc> CallStack<'gc> {
     }
 }
 
+/// Writes a source file name as Flash Player does.
+///
+/// Compilers emit the name as the source path, the package path and the file name
+/// separated by `;`, e.g. `C:\project\src;com\example;Main.as`.
+fn display_file(output: &mut WString, file: &WStr) {
+    let separator = if file.contains(b'\\') { '\\' } else { '/' };
+    let mut first = true;
+    for part in file.split(b';').filter(|part| !part.is_empty()) {
+        if !first {
+            output.push_char(separator);
+        }
+        output.push_str(part);
+        first = false;
+    }
+}
+
 impl<'gc> Default for CallStack<'gc> {
     fn default() -> Self {
         Self::new()
@@ -82,3 +140,33 @@ impl<'gc> std::fmt::Display for CallStack<'gc> {
         write!(f, "{output}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str) -> String {
+        let mut output = WString::new();
+        display_file(&mut output, &WString::from_utf8(name));
+        output.to_string()
+    }
+
+    #[test]
+    fn display_file_joins_path_parts() {
+        assert_eq!(
+            file(r"C:\project\src;com\example;Main.as"),
+            r"C:\project\src\com\example\Main.as"
+        );
+        assert_eq!(
+            file("/home/user/src;com/example;Main.as"),
+            "/home/user/src/com/example/Main.as"
+        );
+    }
+
+    #[test]
+    fn display_file_skips_empty_parts() {
+        assert_eq!(file(r"C:\project\src;;Main.as"), r"C:\project\src\Main.as");
+        assert_eq!(file("Main.as"), "Main.as");
+        assert_eq!(file(""), "");
+    }
+}
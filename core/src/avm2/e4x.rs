@@ -3,6 +3,7 @@ use std::{
     fmt::{self, Debug},
 };
 
+use fnv::FnvHashMap;
 use gc_arena::{Collect, GcCell, Mutation};
 use quick_xml::{
     errors::{IllFormedError, SyntaxError as XmlSyntaxError},
@@ -40,6 +41,32 @@ pub struct E4XNodeData<'gc> {
     local_name: Option<AvmString<'gc>>,
     kind: E4XNodeKind<'gc>,
     notification: Option<FunctionObject<'gc>>,
+
+    /// The name index of the document this node is the root of, once it has been used.
+    ///
+    /// It's kept in a cell of its own, so that changes to the document can clear it
+    /// while the root node is borrowed.
+    name_index: Option<GcCell<'gc, NameIndex<'gc>>>,
+}
+
+/// The children and descendants of the root of a document by local name, in document order.
+///
+/// These are built on demand, so that looking up nodes by name in large documents
+/// doesn't need to visit every node each time, and are cleared when the document changes.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+struct NameIndex<'gc> {
+    children: Option<FnvHashMap<AvmString<'gc>, Vec<E4XNode<'gc>>>>,
+    descendants: Option<FnvHashMap<AvmString<'gc>, Vec<E4XNode<'gc>>>>,
+}
+
+/// Returns the local name to look up a name by in a `NameIndex`,
+/// if it names children that could be in one.
+fn indexed_local_name<'gc>(name: &Multiname<'gc>) -> Option<AvmString<'gc>> {
+    if name.is_attribute() || name.is_any_name() {
+        return None;
+    }
+    name.local_name()
 }
 
 impl<'gc> Debug for E4XNodeData<'gc> {
@@ -53,6 +80,15 @@ impl<'gc> Debug for E4XNodeData<'gc> {
     }
 }
 
+/// Interns a name, prefix or namespace URI of a document, as these are repeated by many
+/// of its nodes, and interned strings are faster to compare.
+fn intern_utf8<'gc>(activation: &mut Activation<'_, 'gc>, bytes: &[u8]) -> AvmString<'gc> {
+    activation
+        .strings()
+        .intern_wstr(ruffle_wstr::from_utf8_bytes(bytes))
+        .into()
+}
+
 fn make_xml_error<'gc>(activation: &mut Activation<'_, 'gc>, err: XmlError) -> Error<'gc> {
     let error = match err {
         XmlError::InvalidAttr(XmlAttrError::Duplicated(_, _)) => type_error(
@@ -171,6 +207,7 @@ impl<'gc> E4XNode<'gc> {
                     namespaces: vec![],
                 },
                 notification: None,
+                name_index: None,
             },
         ))
     }
@@ -184,6 +221,7 @@ impl<'gc> E4XNode<'gc> {
                 local_name: None,
                 kind: E4XNodeKind::Text(text),
                 notification: None,
+                name_index: None,
             },
         ))
     }
@@ -206,6 +244,7 @@ impl<'gc> E4XNode<'gc> {
                     namespaces: vec![],
                 },
                 notification: None,
+                name_index: None,
             },
         ))
     }
@@ -225,6 +264,7 @@ impl<'gc> E4XNode<'gc> {
                 local_name: Some(name),
                 kind: E4XNodeKind::Attribute(value),
                 notification: None,
+                name_index: None,
             },
         ))
     }
@@ -343,6 +383,7 @@ impl<'gc> E4XNode<'gc> {
                 local_name: this.local_name,
                 kind,
                 notification: None,
+                name_index: None,
             },
         ));
 
@@ -418,17 +459,20 @@ impl<'gc> E4XNode<'gc> {
     }
 
     pub fn remove_all_children(&self, gc_context: &Mutation<'gc>) {
+        self.invalidate_name_index(gc_context);
         let mut this = self.0.write(gc_context);
         if let E4XNodeKind::Element { children, .. } = &mut this.kind {
             for child in children.iter_mut() {
                 let mut child_data = child.0.write(gc_context);
                 child_data.parent = None;
+                child_data.name_index = None;
             }
             children.clear()
         }
     }
 
     pub fn remove_child(&self, gc_context: &Mutation<'gc>, child: &Self) {
+        self.invalidate_name_index(gc_context);
         let mut this = self.0.write(gc_context);
         if let E4XNodeKind::Element { children, .. } = &mut this.kind {
             children.retain(|c| !GcCell::ptr_eq(c.0, child.0));
@@ -443,6 +487,10 @@ impl<'gc> E4XNode<'gc> {
     }
 
     pub fn append_child(&self, gc_context: &Mutation<'gc>, child: Self) -> Result<(), Error<'gc>> {
+        self.invalidate_name_index(gc_context);
+        if let Some(old_parent) = child.0.try_read().ok().and_then(|data| data.parent) {
+            old_parent.invalidate_name_index(gc_context);
+        }
         let mut this = self.0.write(gc_context);
         let mut child_data = match child.0.try_write(gc_context) {
             Ok(data) => data,
@@ -458,6 +506,7 @@ impl<'gc> E4XNode<'gc> {
         };
 
         child_data.parent = Some(*self);
+        child_data.name_index = None;
 
         match &mut this.kind {
             E4XNodeKind::Element { children, .. } => {
@@ -846,6 +895,7 @@ impl<'gc> E4XNode<'gc> {
                             E4XNodeKind::CData(text)
                         },
                         notification: None,
+                        name_index: None,
                     },
                 ));
                 push_childless_node(node, open_tags, top_level, activation)?;
@@ -943,6 +993,7 @@ impl<'gc> E4XNode<'gc> {
                             local_name: None,
                             kind: E4XNodeKind::Comment(text),
                             notification: None,
+                            name_index: None,
                         },
                     ));
 
@@ -982,6 +1033,7 @@ impl<'gc> E4XNode<'gc> {
                             local_name: Some(name),
                             kind: E4XNodeKind::ProcessingInstruction(value),
                             notification: None,
+                            name_index: None,
                         },
                     ));
 
@@ -1056,8 +1108,7 @@ impl<'gc> E4XNode<'gc> {
 
             let (ns, local_name) = parser.resolve_attribute(attribute.key);
 
-            let local_name = ruffle_wstr::from_utf8_bytes(local_name.into_inner());
-            let name = activation.strings().intern_wstr(local_name).into();
+            let name = intern_utf8(activation, local_name.into_inner());
 
             let namespace = match ns {
                 ResolveResult::Bound(ns) if ns.into_inner() == b"http://www.w3.org/2000/xmlns/" => {
@@ -1068,10 +1119,11 @@ impl<'gc> E4XNode<'gc> {
                     continue;
                 }
                 ResolveResult::Bound(ns) => {
-                    let prefix = attribute.key.prefix().map(|prefix| {
-                        AvmString::new_utf8_bytes(activation.gc(), prefix.into_inner())
-                    });
-                    let uri = AvmString::new_utf8_bytes(activation.gc(), ns.into_inner());
+                    let prefix = attribute
+                        .key
+                        .prefix()
+                        .map(|prefix| intern_utf8(activation, prefix.into_inner()));
+                    let uri = intern_utf8(activation, ns.into_inner());
                     Some(E4XNamespace { prefix, uri })
                 }
                 ResolveResult::Unknown(ns) => {
@@ -1096,6 +1148,7 @@ impl<'gc> E4XNode<'gc> {
                 local_name: Some(name),
                 kind: E4XNodeKind::Attribute(value),
                 notification: None,
+                name_index: None,
             };
             let attribute = E4XNode(GcCell::new(activation.context.gc_context, attribute_data));
             attribute_nodes.push(attribute);
@@ -1103,16 +1156,15 @@ impl<'gc> E4XNode<'gc> {
 
         let (ns, local_name) = parser.resolve_element(bs.name());
 
-        let local_name = ruffle_wstr::from_utf8_bytes(local_name.into_inner());
-        let name = activation.strings().intern_wstr(local_name).into();
+        let name = intern_utf8(activation, local_name.into_inner());
 
         let namespace = match ns {
             ResolveResult::Bound(ns) => {
                 let prefix = bs
                     .name()
                     .prefix()
-                    .map(|prefix| AvmString::new_utf8_bytes(activation.gc(), prefix.into_inner()));
-                let uri = AvmString::new_utf8_bytes(activation.gc(), ns.into_inner());
+                    .map(|prefix| intern_utf8(activation, prefix.into_inner()));
+                let uri = intern_utf8(activation, ns.into_inner());
                 Some(E4XNamespace { prefix, uri })
             }
             ResolveResult::Unknown(ns) => {
@@ -1131,6 +1183,7 @@ impl<'gc> E4XNode<'gc> {
                 namespaces,
             },
             notification: None,
+            name_index: None,
         };
 
         let result = E4XNode(GcCell::new(activation.context.gc_context, data));
@@ -1154,6 +1207,7 @@ impl<'gc> E4XNode<'gc> {
     }
 
     pub fn set_local_name(&self, name: AvmString<'gc>, mc: &Mutation<'gc>) {
+        self.invalidate_name_index(mc);
         self.0.write(mc).local_name = Some(name);
     }
 
@@ -1162,7 +1216,13 @@ impl<'gc> E4XNode<'gc> {
     }
 
     pub fn set_parent(&self, parent: Option<E4XNode<'gc>>, mc: &Mutation<'gc>) {
-        self.0.write(mc).parent = parent;
+        if let Some(old_parent) = self.parent() {
+            old_parent.invalidate_name_index(mc);
+        }
+        let mut data = self.0.write(mc);
+        data.parent = parent;
+        // Only the root of a document has a name index.
+        data.name_index = None;
     }
 
     pub fn parent(&self) -> Option<E4XNode<'gc>> {
@@ -1333,7 +1393,137 @@ impl<'gc> E4XNode<'gc> {
             .any(|ns| ns.as_uri_opt().expect("NS set cannot contain Any") == self_ns)
     }
 
-    pub fn descendants(&self, name: &Multiname<'gc>, out: &mut Vec<E4XOrXml<'gc>>) {
+    /// Returns the children of this element that match a name, in order.
+    pub fn matching_children(
+        &self,
+        mc: &Mutation<'gc>,
+        name: &Multiname<'gc>,
+    ) -> Vec<E4XNode<'gc>> {
+        if let Some(local_name) = indexed_local_name(name) {
+            if self
+                .length()
+                .is_some_and(|len| len >= Self::MIN_INDEXED_CHILDREN)
+            {
+                if let Some(nodes) = self.lookup_name_index(mc, local_name, false) {
+                    return nodes
+                        .into_iter()
+                        .filter(|node| node.matches_name(name))
+                        .collect();
+                }
+            }
+        }
+
+        if let E4XNodeKind::Element { children, .. } = &*self.kind() {
+            children
+                .iter()
+                .filter(|node| node.matches_name(name))
+                .copied()
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Elements with fewer children than this are searched without building an index.
+    const MIN_INDEXED_CHILDREN: usize = 8;
+
+    /// Looks up the children or descendants of this element with a local name,
+    /// building the index for them first if needed.
+    ///
+    /// Returns `None` if this isn't the root element of a document, or if the index
+    /// can't be updated because this node is borrowed.
+    fn lookup_name_index(
+        &self,
+        mc: &Mutation<'gc>,
+        local_name: AvmString<'gc>,
+        descendants: bool,
+    ) -> Option<Vec<E4XNode<'gc>>> {
+        let mut data = self.0.try_write(mc).ok()?;
+        let data = &mut *data;
+        if data.parent.is_some() {
+            return None;
+        }
+        let E4XNodeKind::Element { children, .. } = &data.kind else {
+            return None;
+        };
+
+        let index = *data
+            .name_index
+            .get_or_insert_with(|| GcCell::new(mc, Default::default()));
+        let mut index = index.try_write(mc).ok()?;
+        let nodes = if descendants {
+            index.descendants.get_or_insert_with(|| {
+                let mut nodes = FnvHashMap::default();
+                for child in children {
+                    child.index_subtree(&mut nodes);
+                }
+                nodes
+            })
+        } else {
+            index.children.get_or_insert_with(|| {
+                let mut nodes: FnvHashMap<_, Vec<_>> = FnvHashMap::default();
+                for child in children {
+                    if let Some(name) = child.local_name() {
+                        nodes.entry(name).or_default().push(*child);
+                    }
+                }
+                nodes
+            })
+        };
+        Some(nodes.get(&local_name).cloned().unwrap_or_default())
+    }
+
+    /// Clears the name index of the document this node belongs to.
+    ///
+    /// This must be called before changing a node. An ancestor that is mutably borrowed
+    /// is being changed itself, and so has already cleared the index.
+    fn invalidate_name_index(&self, mc: &Mutation<'gc>) {
+        let mut node = *self;
+        loop {
+            let Ok(data) = node.0.try_read() else {
+                return;
+            };
+            let Some(parent) = data.parent else {
+                if let Some(index) = data.name_index {
+                    *index.write(mc) = NameIndex::default();
+                }
+                return;
+            };
+            drop(data);
+            node = parent;
+        }
+    }
+
+    /// Adds this node and its descendants (but not attributes) to a name index, in document order.
+    fn index_subtree(&self, nodes: &mut FnvHashMap<AvmString<'gc>, Vec<E4XNode<'gc>>>) {
+        if let Some(name) = self.local_name() {
+            nodes.entry(name).or_default().push(*self);
+        }
+        if let E4XNodeKind::Element { children, .. } = &*self.kind() {
+            for child in children {
+                child.index_subtree(nodes);
+            }
+        }
+    }
+
+    pub fn descendants(
+        &self,
+        mc: &Mutation<'gc>,
+        name: &Multiname<'gc>,
+        out: &mut Vec<E4XOrXml<'gc>>,
+    ) {
+        if let Some(local_name) = indexed_local_name(name) {
+            if let Some(nodes) = self.lookup_name_index(mc, local_name, true) {
+                out.extend(
+                    nodes
+                        .into_iter()
+                        .filter(|node| node.matches_name(name))
+                        .map(E4XOrXml::E4X),
+                );
+                return;
+            }
+        }
+
         if let E4XNodeKind::Element {
             children,
             attributes,
@@ -1351,7 +1541,7 @@ impl<'gc> E4XNode<'gc> {
                 if child.matches_name(name) {
                     out.push(E4XOrXml::E4X(*child));
                 }
-                child.descendants(name, out)
+                child.descendants(mc, name, out)
             }
         }
     }
@@ -1409,6 +1599,7 @@ impl<'gc> E4XNode<'gc> {
     }
 
     pub fn kind_mut(&self, mc: &Mutation<'gc>) -> RefMut<'_, E4XNodeKind<'gc>> {
+        self.invalidate_name_index(mc);
         RefMut::map(self.0.write(mc), |r| &mut r.kind)
     }
 
@@ -1421,7 +1612,7 @@ pub fn simple_content_to_string<'gc>(
     children: impl Iterator<Item = E4XOrXml<'gc>>,
     activation: &mut Activation<'_, 'gc>,
 ) -> AvmString<'gc> {
-    let mut out = AvmString::default();
+    let mut parts = Vec::new();
     for child in children {
         if matches!(
             &*child.node().kind(),
@@ -1429,10 +1620,9 @@ pub fn simple_content_to_string<'gc>(
         ) {
             continue;
         }
-        let child_str = child.node().xml_to_string(activation);
-        out = AvmString::concat(activation.context.gc_context, out, child_str);
+        parts.push(child.node().xml_to_string(activation));
     }
-    out
+    activation.strings().concat_all(&parts)
 }
 
 // Implementation of `EscapeAttributeValue` from ECMA-357 (10.2.1.2)
@@ -125,6 +125,20 @@ pub fn make_reference_error<'gc>(
     }
 }
 
+#[inline(never)]
+#[cold]
+pub fn make_error_1001<'gc>(activation: &mut Activation<'_, 'gc>, method_name: &str) -> Error<'gc> {
+    let err = verify_error(
+        activation,
+        &format!("Error #1001: The method {method_name} is not implemented."),
+        1001,
+    );
+    match err {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
 #[inline(never)]
 #[cold]
 pub fn make_error_1002<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
@@ -461,6 +475,23 @@ pub fn make_error_1127<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc>
     }
 }
 
+#[inline(never)]
+#[cold]
+pub fn make_error_1502<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    let timeout = activation.context.max_execution_duration.as_secs_f64();
+    let err = error(
+        activation,
+        &format!(
+            "Error #1502: A script has executed for longer than the default timeout period of {timeout} seconds."
+        ),
+        1502,
+    );
+    match err {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
 #[inline(never)]
 #[cold]
 pub fn make_error_1506<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
@@ -624,6 +655,23 @@ pub fn make_error_2085<'gc>(activation: &mut Activation<'_, 'gc>, param_name: &s
     }
 }
 
+#[inline(never)]
+#[cold]
+pub fn make_error_2096<'gc>(activation: &mut Activation<'_, 'gc>, header_name: &str) -> Error<'gc> {
+    let err = argument_error(
+        activation,
+        &format!(
+            "Error #2096: The HTTP request header {} cannot be set via ActionScript.",
+            header_name
+        ),
+        2096,
+    );
+    match err {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
 #[inline(never)]
 #[cold]
 pub fn make_error_2097<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
@@ -638,6 +686,27 @@ pub fn make_error_2097<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc>
     }
 }
 
+#[inline(never)]
+#[cold]
+pub fn make_error_2121<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    accessor_url: &str,
+    target_url: &str,
+) -> Error<'gc> {
+    let err = security_error(
+        activation,
+        &format!(
+            "Error #2121: Security sandbox violation: {operation}: {accessor_url} cannot access {target_url}. This may be worked around by calling Security.allowDomain."
+        ),
+        2121,
+    );
+    match err {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
 #[inline(never)]
 #[cold]
 pub fn make_error_2126<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
@@ -652,6 +721,40 @@ pub fn make_error_2126<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc>
     }
 }
 
+#[inline(never)]
+#[cold]
+pub fn make_error_2142<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    accessor_url: &str,
+    target_url: &str,
+) -> Error<'gc> {
+    let err = security_error(
+        activation,
+        &format!(
+            "Error #2142: Security sandbox violation: local SWF files cannot use the LoaderContext.securityDomain property. {accessor_url} was attempting to load {target_url}."
+        ),
+        2142,
+    );
+    match err {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
+#[inline(never)]
+#[cold]
+pub fn make_error_2179<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    let err = security_error(
+        activation,
+        "Error #2179: The Clipboard.generalClipboard object may only be read while processing a flash.events.Event.PASTE event.",
+        2179,
+    );
+    match err {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
 #[inline(never)]
 #[cold]
 pub fn range_error<'gc>(
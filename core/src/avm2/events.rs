@@ -4,14 +4,14 @@ use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2007;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
-use crate::avm2::Error;
 use crate::avm2::Multiname;
+use crate::avm2::{Avm2, Error};
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
 use fnv::FnvHashMap;
 use gc_arena::Collect;
-use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
+use std::rc::Rc;
 
 /// Which phase of event dispatch is currently occurring.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -180,7 +180,7 @@ impl<'gc> Event<'gc> {
 /// A set of handlers organized by event type, priority, and order added.
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
-pub struct DispatchList<'gc>(FnvHashMap<AvmString<'gc>, BTreeMap<i32, Vec<EventHandler<'gc>>>>);
+pub struct DispatchList<'gc>(FnvHashMap<AvmString<'gc>, EventHandlers<'gc>>);
 
 impl<'gc> DispatchList<'gc> {
     /// Construct a new dispatch list.
@@ -188,65 +188,28 @@ impl<'gc> DispatchList<'gc> {
         Self(Default::default())
     }
 
-    /// Get all of the event handlers for a given event type, if such a type
-    /// exists.
-    fn get_event(
-        &self,
-        event: impl Into<AvmString<'gc>>,
-    ) -> Option<&BTreeMap<i32, Vec<EventHandler<'gc>>>> {
-        self.0.get(&event.into())
-    }
-
-    /// Get all of the event handlers for a given event type, for mutation.
-    ///
-    /// If the event type does not exist, it will be added to the dispatch
-    /// list.
-    fn get_event_mut(
-        &mut self,
-        event: impl Into<AvmString<'gc>>,
-    ) -> &mut BTreeMap<i32, Vec<EventHandler<'gc>>> {
-        self.0.entry(event.into()).or_default()
-    }
-
-    /// Get a single priority level of event handlers for a given event type,
-    /// for mutation.
-    fn get_event_priority_mut(
-        &mut self,
-        event: impl Into<AvmString<'gc>>,
-        priority: i32,
-    ) -> &mut Vec<EventHandler<'gc>> {
-        self.0
-            .entry(event.into())
-            .or_default()
-            .entry(priority)
-            .or_default()
-    }
-
     /// Add an event handler to this dispatch list.
     ///
     /// This enforces the invariant that an `EventHandler` must not appear at
-    /// more than one priority (since we can't enforce that with clever-er data
-    /// structure selection). If an event handler already exists, it will not
+    /// more than one priority. If an event handler already exists, it will not
     /// be added again, and this function will silently fail.
     pub fn add_event_listener(
         &mut self,
-        event: impl Into<AvmString<'gc>> + Clone,
+        event: impl Into<AvmString<'gc>>,
         priority: i32,
         handler: Object<'gc>,
         use_capture: bool,
     ) {
-        let new_handler = EventHandler::new(handler, use_capture);
-
-        if let Some(event_sheaf) = self.get_event(event.clone()) {
-            for (_other_prio, other_set) in event_sheaf.iter() {
-                if other_set.contains(&new_handler) {
-                    return;
-                }
-            }
+        let new_handler = EventHandler::new(handler, priority, use_capture);
+        let handlers = self.0.entry(event.into()).or_default();
+        if handlers.0.contains(&new_handler) {
+            return;
         }
 
-        self.get_event_priority_mut(event, priority)
-            .push(new_handler);
+        // Handlers are kept sorted by descending priority, and then by the order they
+        // were added in, so that dispatching never has to sort them.
+        let position = handlers.0.partition_point(|h| h.priority >= priority);
+        Rc::make_mut(&mut handlers.0).insert(position, new_handler);
     }
 
     /// Remove an event handler from this dispatch list.
@@ -259,56 +222,66 @@ impl<'gc> DispatchList<'gc> {
         handler: Object<'gc>,
         use_capture: bool,
     ) {
-        let old_handler = EventHandler::new(handler, use_capture);
-
-        for (_prio, set) in self.get_event_mut(event).iter_mut() {
-            if let Some(pos) = set.iter().position(|h| *h == old_handler) {
-                set.remove(pos);
-            }
+        let event = event.into();
+        let Some(handlers) = self.0.get_mut(&event) else {
+            return;
+        };
+
+        let old_handler = EventHandler::new(handler, 0, use_capture);
+        if let Some(pos) = handlers.0.iter().position(|h| *h == old_handler) {
+            Rc::make_mut(&mut handlers.0).remove(pos);
+        }
+        if handlers.0.is_empty() {
+            self.0.remove(&event);
         }
     }
 
     /// Determine if there are any event listeners in this dispatch list.
     pub fn has_event_listener(&self, event: impl Into<AvmString<'gc>>) -> bool {
-        if let Some(event_sheaf) = self.get_event(event) {
-            for (_prio, set) in event_sheaf.iter() {
-                if !set.is_empty() {
-                    return true;
-                }
-            }
-        }
-
-        false
+        self.0
+            .get(&event.into())
+            .is_some_and(|handlers| !handlers.0.is_empty())
     }
 
-    /// Yield the event handlers on this dispatch list for a given event.
+    /// Get a snapshot of the event handlers on this dispatch list for a given event.
     ///
-    /// Event handlers will be yielded in the order they are intended to be
-    /// executed.
+    /// The snapshot is shared with the dispatch list until either of them is
+    /// modified, so taking one is cheap, and handlers that are added or removed
+    /// while an event is being dispatched don't affect that dispatch.
+    pub fn event_handlers(&self, event: impl Into<AvmString<'gc>>) -> EventHandlers<'gc> {
+        self.0.get(&event.into()).cloned().unwrap_or_default()
+    }
+}
+
+impl<'gc> Default for DispatchList<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The handlers of a single event type, in the order they are intended to be executed.
+#[derive(Clone, Collect, Default)]
+#[collect(no_drop)]
+pub struct EventHandlers<'gc>(Rc<Vec<EventHandler<'gc>>>);
+
+impl<'gc> EventHandlers<'gc> {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Yield the event handlers in the order they are intended to be executed.
     ///
     /// `use_capture` indicates if you want handlers that execute during the
     /// capture phase, or handlers that execute during the bubble and target
     /// phases.
-    pub fn iter_event_handlers<'a>(
-        &'a mut self,
-        event: impl Into<AvmString<'gc>>,
-        use_capture: bool,
-    ) -> impl 'a + Iterator<Item = Object<'gc>> {
-        self.get_event_mut(event)
+    pub fn iter(&self, use_capture: bool) -> impl '_ + Iterator<Item = Object<'gc>> {
+        self.0
             .iter()
-            .rev()
-            .flat_map(|(_p, v)| v.iter())
             .filter(move |eh| eh.use_capture == use_capture)
             .map(|eh| eh.handler)
     }
 }
 
-impl<'gc> Default for DispatchList<'gc> {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 /// A single instance of an event handler.
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
@@ -316,6 +289,9 @@ struct EventHandler<'gc> {
     /// The event handler to call.
     handler: Object<'gc>,
 
+    /// The priority the handler was added with. Handlers with a higher priority are called first.
+    priority: i32,
+
     /// Indicates if this handler should only be called for capturing events
     /// (when `true`), or if it should only be called for bubbling and
     /// at-target events (when `false`).
@@ -323,9 +299,10 @@ struct EventHandler<'gc> {
 }
 
 impl<'gc> EventHandler<'gc> {
-    fn new(handler: Object<'gc>, use_capture: bool) -> Self {
+    fn new(handler: Object<'gc>, priority: i32, use_capture: bool) -> Self {
         Self {
             handler,
+            priority,
             use_capture,
         }
     }
@@ -399,15 +376,14 @@ fn dispatch_event_to_target<'gc>(
     let name = evtmut.event_type();
     let use_capture = evtmut.phase() == EventPhase::Capturing;
 
-    let handlers: Vec<Object<'gc>> = dispatch_list
-        .as_dispatch_mut(activation.context.gc_context)
+    let handlers = dispatch_list
+        .as_dispatch()
         .ok_or_else(|| Error::from("Internal dispatch list is missing during dispatch!"))?
-        .iter_event_handlers(name, use_capture)
-        .collect();
+        .event_handlers(name);
 
     evtmut.set_current_target(target);
 
-    if !handlers.is_empty() {
+    if handlers.iter(use_capture).next().is_some() {
         evtmut.dispatched = true;
     }
 
@@ -417,7 +393,7 @@ fn dispatch_event_to_target<'gc>(
         return Ok(());
     }
 
-    for handler in handlers.iter() {
+    for handler in handlers.iter(use_capture) {
         if event
             .as_event()
             .unwrap()
@@ -429,11 +405,11 @@ fn dispatch_event_to_target<'gc>(
         let global = activation.context.avm2.toplevel_global_object().unwrap();
 
         if let Err(err) = handler.call(global.into(), &[event.into()], activation) {
-            tracing::error!(
-                "Error dispatching event {:?} to handler {:?} : {:?}",
-                event,
-                handler,
+            Avm2::uncaught_error(
+                activation,
+                target.as_display_object(),
                 err,
+                &format!("Error dispatching event {event:?} to handler {handler:?}"),
             );
         }
     }
@@ -243,7 +243,9 @@ pub fn exec<'gc>(
                 .context
                 .avm2
                 .push_call(activation.context.gc_context, method, bound_class);
-            activation.run_actions(bm)
+            let ret = activation.run_actions(bm);
+            activation.release_registers();
+            ret
         }
     };
     activation
@@ -128,6 +128,7 @@ pub struct SystemClasses<'gc> {
     pub urierror: ClassObject<'gc>,
     pub securityerror: ClassObject<'gc>,
     pub error: ClassObject<'gc>,
+    pub uncaughterrorevent: ClassObject<'gc>,
     pub uncaughterrorevents: ClassObject<'gc>,
     pub statictext: ClassObject<'gc>,
     pub textlinemetrics: ClassObject<'gc>,
@@ -137,6 +138,7 @@ pub struct SystemClasses<'gc> {
     pub vertexbuffer3d: ClassObject<'gc>,
     pub program3d: ClassObject<'gc>,
     pub urlvariables: ClassObject<'gc>,
+    pub urlrequestheader: ClassObject<'gc>,
     pub bevelfilter: ClassObject<'gc>,
     pub bitmapfilter: ClassObject<'gc>,
     pub blurfilter: ClassObject<'gc>,
@@ -169,6 +171,7 @@ pub struct SystemClasses<'gc> {
     pub dictionary: ClassObject<'gc>,
     pub id3info: ClassObject<'gc>,
     pub textrun: ClassObject<'gc>,
+    pub gameinput: ClassObject<'gc>,
 }
 
 #[derive(Clone, Collect)]
@@ -284,6 +287,7 @@ impl<'gc> SystemClasses<'gc> {
             urierror: object,
             securityerror: object,
             error: object,
+            uncaughterrorevent: object,
             uncaughterrorevents: object,
             statictext: object,
             textlinemetrics: object,
@@ -293,6 +297,7 @@ impl<'gc> SystemClasses<'gc> {
             vertexbuffer3d: object,
             program3d: object,
             urlvariables: object,
+            urlrequestheader: object,
             bevelfilter: object,
             bitmapfilter: object,
             blurfilter: object,
@@ -325,6 +330,7 @@ impl<'gc> SystemClasses<'gc> {
             dictionary: object,
             id3info: object,
             textrun: object,
+            gameinput: object,
         }
     }
 }
@@ -897,6 +903,7 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.events", "IOErrorEvent", ioerrorevent),
             ("flash.events", "MouseEvent", mouseevent),
             ("flash.events", "FullScreenEvent", fullscreenevent),
+            ("flash.events", "UncaughtErrorEvent", uncaughterrorevent),
             ("flash.events", "UncaughtErrorEvents", uncaughterrorevents),
             ("flash.events", "NetStatusEvent", netstatusevent),
             ("flash.events", "StatusEvent", statusevent),
@@ -913,6 +920,7 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.media", "SoundTransform", soundtransform),
             ("flash.media", "Video", video),
             ("flash.net", "URLVariables", urlvariables),
+            ("flash.net", "URLRequestHeader", urlrequestheader),
             ("flash.net", "FileReference", filereference),
             ("flash.net", "FileFilter", filefilter),
             ("flash.utils", "ByteArray", bytearray),
@@ -941,6 +949,7 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.filters", "GradientGlowFilter", gradientglowfilter),
             ("flash.filters", "ShaderFilter", shaderfilter),
             ("flash.events", "SampleDataEvent", sampledataevent),
+            ("flash.ui", "GameInput", gameinput),
         ]
     );
 
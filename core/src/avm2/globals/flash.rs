@@ -1,11 +1,13 @@
 //! `flash` namespace
 
 pub mod crypto;
+pub mod desktop;
 pub mod display;
 #[allow(non_snake_case)]
 pub mod display3D;
 pub mod events;
 pub mod external;
+pub mod filesystem;
 pub mod geom;
 pub mod media;
 pub mod net;
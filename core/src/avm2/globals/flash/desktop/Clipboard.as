@@ -1,11 +1,14 @@
 package flash.desktop {
-    import __ruffle__.stub_getter;
     import __ruffle__.stub_method;
     import flash.system.System;
 
     public class Clipboard {
         private static var _generalClipboard = new Clipboard();
 
+        // Data stored in this clipboard, keyed by format.
+        // The general clipboard keeps text in the OS clipboard instead.
+        private var _data:Object = {};
+
         public static function get generalClipboard(): Clipboard {
             return Clipboard._generalClipboard;
         }
@@ -14,35 +17,66 @@ package flash.desktop {
             // TODO: This should only be callable in AIR
         }
 
+        private function get isGeneral(): Boolean {
+            return this === Clipboard._generalClipboard;
+        }
+
         public function get formats(): Array {
-            stub_getter("flash.desktop.Clipboard", "formats");
-            return new Array();
+            var result = new Array();
+            if (this.isGeneral && Clipboard.hasSystemText()) {
+                result.push(ClipboardFormats.TEXT_FORMAT);
+            }
+            for (var format in this._data) {
+                if (result.indexOf(format) == -1) {
+                    result.push(format);
+                }
+            }
+            return result;
         }
 
         public function clear(): void {
-            stub_method("flash.desktop.Clipboard", "clear");
+            if (this.isGeneral) {
+                System.setClipboard("");
+            }
+            this._data = {};
         }
 
         public function clearData(format: String): void {
-            stub_method("flash.desktop.Clipboard", "clearData");
+            if (this.isGeneral && format == ClipboardFormats.TEXT_FORMAT) {
+                System.setClipboard("");
+            }
+            delete this._data[format];
         }
 
         public function getData(format: String, transferMode: String = ClipboardTransferMode.ORIGINAL_PREFERRED): Object {
-            stub_method("flash.desktop.Clipboard", "getData");
+            if (this.isGeneral && format == ClipboardFormats.TEXT_FORMAT) {
+                return Clipboard.getSystemText();
+            }
+            if (format in this._data) {
+                return this._data[format];
+            }
             return null;
         }
 
         public function hasFormat(format: String): Boolean {
-            stub_method("flash.desktop.Clipboard", "hasFormat");
-            return false;
+            if (this.isGeneral && format == ClipboardFormats.TEXT_FORMAT) {
+                return Clipboard.hasSystemText();
+            }
+            return format in this._data;
         }
 
         public function setData(format: String, data: Object, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setData");
-            if (format == ClipboardFormats.TEXT_FORMAT) {
+            if (this.isGeneral && format == ClipboardFormats.TEXT_FORMAT) {
                 System.setClipboard(data);
                 return true;
             }
+            if (format == ClipboardFormats.TEXT_FORMAT
+                || format == ClipboardFormats.HTML_FORMAT
+                || format == ClipboardFormats.RICH_TEXT_FORMAT
+                || format == ClipboardFormats.URL_FORMAT) {
+                this._data[format] = String(data);
+                return true;
+            }
             return false;
         }
 
@@ -50,5 +84,9 @@ package flash.desktop {
             stub_method("flash.desktop.Clipboard", "setDataHandler");
             return false;
         }
+
+        private static native function getSystemText(): String;
+
+        private static native function hasSystemText(): Boolean;
     }
 }
@@ -0,0 +1,37 @@
+//! `flash.desktop.Clipboard` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2179;
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+use crate::PlayerRuntime;
+
+/// Implements `Clipboard.getSystemText`, reading text from the OS clipboard.
+///
+/// Flash Player only allows this while handling a paste event,
+/// so outside of AIR we ask the user instead.
+pub fn get_system_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let allowed = activation.avm2().player_runtime == PlayerRuntime::AIR
+        || activation.context.ui.request_clipboard_read_permission();
+    if !allowed {
+        return Err(make_error_2179(activation));
+    }
+
+    let content = activation.context.ui.clipboard_content();
+    Ok(AvmString::new_utf8(activation.gc(), content).into())
+}
+
+/// Implements `Clipboard.hasSystemText`.
+pub fn has_system_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.clipboard_available().into())
+}
@@ -4,7 +4,6 @@ package flash.display {
     import flash.geom.Point;
     import flash.geom.Matrix;
     import flash.filters.BitmapFilter;
-    import flash.filters.ShaderFilter;
     import flash.utils.ByteArray;
     import __ruffle__.stub_method;
 
@@ -96,13 +95,32 @@ package flash.display {
         public native function merge(
             sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, redMultiplier:uint, greenMultiplier:uint, blueMultiplier:uint, alphaMultiplier:uint
         ):void 
-        public function generateFilterRect(sourceRect:Rectangle, filter:BitmapFilter):Rectangle {
-            // Flash always reports that a ShaderFilter affects the entire BitampData, ignoring SourceRect.
-            if (filter is ShaderFilter) {
-                return this.rect.clone();
+        public native function generateFilterRect(sourceRect:Rectangle, filter:BitmapFilter):Rectangle;
+
+        [API("680")]
+        public function encode(rect:Rectangle, compressor:Object, byteArray:ByteArray = null):ByteArray {
+            if (compressor == null) {
+                throw new TypeError("Error #2007: Parameter compressor must be non-null.", 2007);
+            }
+            if (byteArray == null) {
+                byteArray = new ByteArray();
             }
-            stub_method("flash.display.BitmapData", "generateFilterRect");
-            return sourceRect.clone();
+
+            if (compressor is PNGEncoderOptions) {
+                this.encodePng(rect, PNGEncoderOptions(compressor).fastCompression, byteArray);
+            } else if (compressor is JPEGEncoderOptions) {
+                this.encodeJpeg(rect, JPEGEncoderOptions(compressor).quality, byteArray);
+            } else if (compressor is JPEGXREncoderOptions) {
+                // JPEG XR can be lossless, so PNG is the closest we can get.
+                stub_method("flash.display.BitmapData", "encode", "with JPEGXREncoderOptions");
+                this.encodePng(rect, false, byteArray);
+            } else {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            return byteArray;
         }
+
+        private native function encodePng(rect:Rectangle, fastCompression:Boolean, byteArray:ByteArray):void;
+        private native function encodeJpeg(rect:Rectangle, quality:uint, byteArray:ByteArray):void;
     }
 }
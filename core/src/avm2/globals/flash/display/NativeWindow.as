@@ -18,10 +18,10 @@ package flash.display
     public const systemMinSize:Point = new Point(1, 1);
     public var minSize:Point = systemMinSize;
     public var maxSize:Point = systemMaxSize;
-    public var title:String;
     public var alwaysInFront:Boolean = true;
-    public var visible:Boolean = true;
 
+    private var _title:String;
+    private var _visible:Boolean;
     private var _bounds:Rectangle;
     private var _maximizable:Boolean;
     private var _minimizable:Boolean;
@@ -34,16 +34,24 @@ package flash.display
 
     public function NativeWindow(initOptions:NativeWindowInitOptions, _stage:Stage = null)
     {
-      stub_constructor("flash.display.NativeWindow");
       NativeApplication.nativeApplication.openedWindows.push(this);
       if (_stage)
       {
         this._stage = _stage;
+        this._visible = true;
         _stage.addEventListener(Event.RESIZE, function(e:Event):void
         {
           dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.RESIZE, false, false, _bounds, _bounds = new Rectangle(x, y, width, height)));
         });
       }
+      else
+      {
+        // Windows created by content have no stage of their own and are never shown;
+        // only their state is tracked, so that content relying on it keeps working.
+        stub_constructor("flash.display.NativeWindow", "windows created by content");
+        this._visible = false;
+        this._bounds = new Rectangle(100, 100, 550, 400);
+      }
 
       _maximizable = initOptions.maximizable;
       _minimizable = initOptions.minimizable;
@@ -53,62 +61,151 @@ package flash.display
       _type = initOptions.type;
     }
 
+    public function get title():String
+    {
+      return _title;
+    }
+
+    public function set title(value:String):void
+    {
+      _title = value;
+      if (_stage && !_closed)
+      {
+        NativeWindow.setWindowTitle(value === null ? "" : value);
+      }
+    }
+
+    public function get visible():Boolean
+    {
+      return _visible;
+    }
+
+    public function set visible(value:Boolean):void
+    {
+      if (_closed || _visible == value)
+      {
+        return;
+      }
+      _visible = value;
+    }
+
     public function get width():Number
     {
-      stub_getter("flash.display.NativeWindow", "width");
-      return _stage.stageWidth;
+      if (_stage)
+      {
+        return _stage.stageWidth;
+      }
+      return _bounds.width;
     }
 
     public function set width(value:Number):void
     {
-      stub_setter("flash.display.NativeWindow", "width");
-      _stage.stageWidth = value;
+      if (_stage)
+      {
+        stub_setter("flash.display.NativeWindow", "width");
+        _stage.stageWidth = value;
+        return;
+      }
+      this.bounds = new Rectangle(_bounds.x, _bounds.y, value, _bounds.height);
     }
 
     public function get height():Number
     {
-      stub_getter("flash.display.NativeWindow", "height");
-      return _stage.stageHeight;
+      if (_stage)
+      {
+        return _stage.stageHeight;
+      }
+      return _bounds.height;
     }
 
     public function set height(value:Number):void
     {
-      stub_setter("flash.display.NativeWindow", "height");
-      _stage.stageHeight = value;
+      if (_stage)
+      {
+        stub_setter("flash.display.NativeWindow", "height");
+        _stage.stageHeight = value;
+        return;
+      }
+      this.bounds = new Rectangle(_bounds.x, _bounds.y, _bounds.width, value);
     }
 
     public function get x():Number
     {
-      stub_getter("flash.display.NativeWindow", "x");
-      return _stage.x;
+      if (_stage)
+      {
+        stub_getter("flash.display.NativeWindow", "x");
+        return _stage.x;
+      }
+      return _bounds.x;
     }
 
     public function set x(value:Number):void
     {
-      stub_setter("flash.display.NativeWindow", "x");
+      if (_stage)
+      {
+        stub_setter("flash.display.NativeWindow", "x");
+        return;
+      }
+      this.bounds = new Rectangle(value, _bounds.y, _bounds.width, _bounds.height);
     }
 
     public function get y():Number
     {
-      stub_getter("flash.display.NativeWindow", "y");
-      return _stage.y;
+      if (_stage)
+      {
+        stub_getter("flash.display.NativeWindow", "y");
+        return _stage.y;
+      }
+      return _bounds.y;
     }
 
     public function set y(value:Number):void
     {
-      stub_setter("flash.display.NativeWindow", "y");
+      if (_stage)
+      {
+        stub_setter("flash.display.NativeWindow", "y");
+        return;
+      }
+      this.bounds = new Rectangle(_bounds.x, value, _bounds.width, _bounds.height);
     }
 
     public function get bounds():Rectangle
     {
-      stub_getter("flash.display.NativeWindow", "bounds");
-      return _bounds;
+      if (_stage)
+      {
+        stub_getter("flash.display.NativeWindow", "bounds");
+        return _bounds;
+      }
+      return _bounds.clone();
     }
 
     public function set bounds(value:Rectangle):void
     {
-      stub_setter("flash.display.NativeWindow", "bounds");
-      _bounds = value;
+      if (_stage)
+      {
+        stub_setter("flash.display.NativeWindow", "bounds");
+        _bounds = value;
+        return;
+      }
+
+      var newBounds:Rectangle = new Rectangle(value.x, value.y,
+        Math.max(minSize.x, Math.min(maxSize.x, value.width)),
+        Math.max(minSize.y, Math.min(maxSize.y, value.height)));
+      if (_closed || newBounds.equals(_bounds))
+      {
+        return;
+      }
+
+      var oldBounds:Rectangle = _bounds;
+      _bounds = newBounds;
+      if (oldBounds.x != newBounds.x || oldBounds.y != newBounds.y)
+      {
+        dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.MOVE, false, false, oldBounds, newBounds));
+      }
+      if (oldBounds.width != newBounds.width || oldBounds.height != newBounds.height)
+      {
+        dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.RESIZE, false, false, oldBounds, newBounds));
+      }
     }
 
     public function get maximizable():Boolean
@@ -155,17 +252,37 @@ package flash.display
     // Activates this window.
     public function activate():void
     {
-      stub_method("flash.display.NativeWindow", "activate");
+      if (_closed)
+      {
+        return;
+      }
+      this.visible = true;
       dispatchEvent(new Event(Event.ACTIVATE));
     }
 
     // Closes this window.
     public function close():void
     {
-      stub_method("flash.display.NativeWindow", "close");
+      if (_closed)
+      {
+        return;
+      }
+      if (_stage)
+      {
+        stub_method("flash.display.NativeWindow", "close", "with the main window");
+      }
       if (dispatchEvent(new Event(Event.CLOSING, false, true)))
       {
         _closed = true;
+        _visible = false;
+
+        var openedWindows:Array = NativeApplication.nativeApplication.openedWindows;
+        var index:int = openedWindows.indexOf(this);
+        if (index != -1)
+        {
+          openedWindows.splice(index, 1);
+        }
+
         dispatchEvent(new Event(Event.CLOSE));
         dispatchEvent(new Event(Event.DEACTIVATE));
       }
@@ -308,5 +425,7 @@ package flash.display
       stub_getter("flash.display.NativeWindow", "supportsTransparency");
       return false;
     }
+
+    private static native function setWindowTitle(title:String):void;
   }
 }
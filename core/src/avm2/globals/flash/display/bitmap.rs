@@ -51,11 +51,12 @@ pub fn bitmap_allocator<'gc>(
             }) = activation
                 .context
                 .library
-                .library_for_movie_mut(movie)
+                .library_for_movie_mut(movie.clone())
                 .character_by_id(symbol)
                 .cloned()
             {
-                let new_bitmap_data = fill_bitmap_data_from_symbol(activation, &compressed);
+                let new_bitmap_data =
+                    fill_bitmap_data_from_symbol(activation, &movie, symbol, &compressed);
                 let bitmap_data_obj = BitmapDataObject::from_bitmap_data_internal(
                     activation,
                     BitmapDataWrapper::dummy(activation.context.gc_context),
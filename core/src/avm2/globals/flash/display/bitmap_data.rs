@@ -1,5 +1,6 @@
 //! `flash.display.BitmapData` builtin/prototype
 
+use crate::asset_cache;
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::error::{
@@ -22,11 +23,12 @@ use crate::character::{Character, CompressedBitmap};
 use crate::display_object::TDisplayObject;
 use crate::ecma_conversions::round_to_even;
 use crate::swf::BlendMode;
+use crate::tag_utils::SwfMovie;
 use gc_arena::GcCell;
 use ruffle_render::filters::Filter;
 use ruffle_render::transform::Transform;
 use std::str::FromStr;
-use swf::{Rectangle, Twips};
+use swf::{CharacterId, Rectangle, Twips};
 
 // Computes the integer x,y,width,height values from
 // the given `Rectangle`. This method performs `x + width`
@@ -68,9 +70,11 @@ fn get_rectangle_x_y_width_height<'gc>(
 /// class named by `name`.
 pub fn fill_bitmap_data_from_symbol<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    movie: &SwfMovie,
+    id: CharacterId,
     bd: &CompressedBitmap,
 ) -> BitmapDataWrapper<'gc> {
-    let bitmap = bd.decode().expect("Failed to decode BitmapData");
+    let bitmap = asset_cache::decode_bitmap(movie, id, bd).expect("Failed to decode BitmapData");
     let new_bitmap_data = GcCell::new(
         activation.context.gc_context,
         BitmapData::new_with_pixels(
@@ -101,22 +105,27 @@ pub fn init<'gc>(
         .avm2_class_registry()
         .class_symbol(this.instance_class())
         .and_then(|(movie, chara_id)| {
-            activation
+            let character = activation
                 .context
                 .library
-                .library_for_movie_mut(movie)
+                .library_for_movie_mut(movie.clone())
                 .character_by_id(chara_id)
-                .cloned()
+                .cloned()?;
+            Some((movie, chara_id, character))
         });
 
-    let new_bitmap_data = if let Some(Character::Bitmap {
-        compressed,
-        avm2_bitmapdata_class: _,
-        handle: _,
-    }) = character
+    let new_bitmap_data = if let Some((
+        movie,
+        chara_id,
+        Character::Bitmap {
+            compressed,
+            avm2_bitmapdata_class: _,
+            handle: _,
+        },
+    )) = &character
     {
         // Instantiating BitmapData from an Animate-style bitmap asset
-        fill_bitmap_data_from_symbol(activation, &compressed)
+        fill_bitmap_data_from_symbol(activation, movie, *chara_id, compressed)
     } else {
         if character.is_some() {
             //TODO: Determine if mismatched symbols will still work as a
@@ -351,6 +360,70 @@ pub fn copy_pixels_to_byte_array<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements the PNG encoding of `BitmapData.encode`.
+pub fn encode_png<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let fast_compression = args.get_bool(1);
+    encode(
+        activation,
+        this,
+        args,
+        operations::EncodeFormat::Png { fast_compression },
+    )
+}
+
+/// Implements the JPEG encoding of `BitmapData.encode`.
+pub fn encode_jpeg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let quality = args.get_u32(activation, 1)?.min(100) as u8;
+    encode(
+        activation,
+        this,
+        args,
+        operations::EncodeFormat::Jpeg { quality },
+    )
+}
+
+fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    format: operations::EncodeFormat,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let rectangle = args.get_object(activation, 0, "rect")?;
+        let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+        let byte_array = args.get_object(activation, 2, "byteArray")?;
+
+        let encoded = operations::encode(
+            bitmap_data,
+            activation.context.renderer,
+            x,
+            y,
+            width,
+            height,
+            format,
+        )
+        .map_err(|e| {
+            tracing::warn!("Couldn't encode BitmapData: {e}");
+            make_error_2004(activation, Error2004Type::ArgumentError)
+        })?;
+        let mut storage = byte_array.as_bytearray_mut().unwrap();
+        storage
+            .write_bytes(&encoded)
+            .map_err(|e| e.to_avm(activation))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_vector<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -1113,6 +1186,39 @@ pub fn get_rect<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `BitmapData.generateFilterRect`
+pub fn generate_filter_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let source_rect = args.get_object(activation, 0, "sourceRect")?;
+        let source_rect = super::display_object::object_to_rectangle(activation, source_rect)?;
+        let filter = args.get_object(activation, 1, "filter")?;
+        let filter = Filter::from_avm2_object(activation, filter)?;
+
+        let rect = operations::generate_filter_rect(bitmap_data, source_rect, &filter);
+        return Ok(activation
+            .avm2()
+            .classes()
+            .rectangle
+            .construct(
+                activation,
+                &[
+                    rect.x_min.to_pixels().into(),
+                    rect.y_min.to_pixels().into(),
+                    rect.width().to_pixels().into(),
+                    rect.height().to_pixels().into(),
+                ],
+            )?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implement `BitmapData.applyFilter`
 pub fn apply_filter<'gc>(
     activation: &mut Activation<'_, 'gc>,
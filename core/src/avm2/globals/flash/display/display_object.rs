@@ -2,15 +2,19 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::{
-    argument_error, illegal_operation_error, make_error_2007, make_error_2008,
+    argument_error, illegal_operation_error, make_error_2004, make_error_2007, make_error_2008,
+    Error2004Type,
 };
 use crate::avm2::filters::FilterAvm2Ext;
+use crate::avm2::globals::flash::display::shader_job::get_shader_data_args;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::StageObject;
 use crate::avm2::{ArrayObject, ArrayStorage};
 use crate::avm2::{ClassObject, Error};
+use crate::display_object::hit_test;
+use crate::display_object::transform_3d::Transform3D;
 use crate::ecma_conversions::round_to_even;
 use crate::prelude::*;
 use crate::string::AvmString;
@@ -18,7 +22,9 @@ use crate::types::{Degrees, Percent};
 use crate::vminterface::Instantiator;
 use crate::{avm2_stub_getter, avm2_stub_setter};
 use ruffle_render::blend::ExtendedBlendMode;
+use ruffle_render::commands::BlendShader;
 use ruffle_render::filters::Filter;
+use ruffle_render::pixel_bender::PixelBenderShaderArgument;
 use std::str::FromStr;
 
 pub fn display_object_allocator<'gc>(
@@ -386,96 +392,177 @@ pub fn set_y<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `z`'s getter.
 pub fn get_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "z");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        let z = dobj
+            .transform_3d()
+            .map_or(0.0, |transform_3d| transform_3d.z);
+        return Ok(z.into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `z`'s setter.
 pub fn set_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "z");
+    if let Some(dobj) = this.as_display_object() {
+        let z = args.get_f64(activation, 0)?;
+        update_transform_3d(activation, dobj, |transform_3d| transform_3d.z = z);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s getter.
 pub fn get_rotation_x<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationX");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        let rotation = dobj
+            .transform_3d()
+            .map_or(0.0, |transform_3d| transform_3d.rotation_x.into());
+        return Ok(normalize_rotation(rotation).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s setter.
 pub fn set_rotation_x<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationX");
+    if let Some(dobj) = this.as_display_object() {
+        let rotation = Degrees::from(args.get_f64(activation, 0)?);
+        update_transform_3d(activation, dobj, |transform_3d| {
+            transform_3d.rotation_x = rotation
+        });
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s getter.
 pub fn get_rotation_y<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationY");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        let rotation = dobj
+            .transform_3d()
+            .map_or(0.0, |transform_3d| transform_3d.rotation_y.into());
+        return Ok(normalize_rotation(rotation).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s setter.
 pub fn set_rotation_y<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationY");
+    if let Some(dobj) = this.as_display_object() {
+        let rotation = Degrees::from(args.get_f64(activation, 0)?);
+        update_transform_3d(activation, dobj, |transform_3d| {
+            transform_3d.rotation_y = rotation
+        });
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationZ`'s getter.
+///
+/// This is the same rotation as `rotation`, as display objects are rotated around the z axis
+/// by their 2D matrix.
 pub fn get_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(0.into())
+    get_rotation(activation, this, args)
 }
 
+/// Implements `rotationZ`'s setter.
 pub fn set_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(Value::Undefined)
+    if let Some(dobj) = this.as_display_object() {
+        update_transform_3d(activation, dobj, |_| ());
+    }
+    set_rotation(activation, this, args)
 }
 
+/// Implements `scaleZ`'s getter.
 pub fn get_scale_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "scaleZ");
-    Ok(1.into())
+    if let Some(dobj) = this.as_display_object() {
+        let scale = dobj
+            .transform_3d()
+            .map_or(1.0, |transform_3d| transform_3d.scale_z);
+        return Ok(scale.into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `scaleZ`'s setter.
 pub fn set_scale_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "scaleZ");
+    if let Some(dobj) = this.as_display_object() {
+        let scale = args.get_f64(activation, 0)?;
+        update_transform_3d(activation, dobj, |transform_3d| {
+            transform_3d.scale_z = scale
+        });
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Changes the 3D transform of a display object, placing it in 3D space if it wasn't yet.
+fn update_transform_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    dobj: DisplayObject<'gc>,
+    update: impl FnOnce(&mut Transform3D),
+) {
+    let mut transform_3d = dobj.transform_3d().unwrap_or_default();
+    update(&mut transform_3d);
+    dobj.set_transform_3d(activation.context.gc_context, Some(transform_3d));
+}
+
+/// Wraps a rotation in degrees into the range that the rotation properties report.
+fn normalize_rotation(degrees: f64) -> f64 {
+    let rem = degrees % 360.0;
+    if rem <= 180.0 {
+        rem
+    } else {
+        rem - 360.0
+    }
+}
+
 /// Implements `rotation`'s getter.
 pub fn get_rotation<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -484,13 +571,7 @@ pub fn get_rotation<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.as_display_object() {
         let rot: f64 = dobj.rotation(activation.context.gc_context).into();
-        let rem = rot % 360.0;
-
-        if rem <= 180.0 {
-            return Ok(rem.into());
-        } else {
-            return Ok((rem - 360.0).into());
-        }
+        return Ok(normalize_rotation(rot).into());
     }
 
     Ok(Value::Undefined)
@@ -625,11 +706,10 @@ pub fn set_visible<'gc>(
 
 /// Implements `metaData`'s getter.
 pub fn get_meta_data<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "metaData");
     if let Some(dobj) = this.as_display_object() {
         return Ok(dobj.meta_data().map_or(Value::Null, Value::Object));
     }
@@ -696,17 +776,11 @@ pub fn hit_test_point<'gc>(
             .avm2_root()
             .map_or(local, |root| root.local_to_global(local));
 
-        if shape_flag {
-            if !dobj.is_on_stage(activation.context) {
-                return Ok(false.into());
-            }
-
-            return Ok(dobj
-                .hit_test_shape(activation.context, global, HitTestOptions::AVM_HIT_TEST)
-                .into());
-        } else {
-            return Ok(dobj.hit_test_bounds(global).into());
+        if shape_flag && !dobj.is_on_stage(activation.context) {
+            return Ok(false.into());
         }
+
+        return Ok(hit_test::hit_test_point(dobj, activation.context, global, shape_flag).into());
     }
 
     Ok(Value::Undefined)
@@ -768,16 +842,27 @@ pub fn set_transform<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let transform = args.get_object(activation, 0, "transform")?;
 
-    // FIXME - consider 3D matrix and pixel bounds
-    let matrix = transform
-        .get_public_property("matrix", activation)?
-        .coerce_to_object(activation)?;
     let color_transform = transform
         .get_public_property("colorTransform", activation)?
         .coerce_to_object(activation)?;
 
-    let matrix =
-        crate::avm2::globals::flash::geom::transform::object_to_matrix(matrix, activation)?;
+    // The matrix of an object placed in 3D space is null, so copy its 3D transform as well.
+    let (matrix, transform_3d) = match transform.get_public_property("matrix", activation)? {
+        Value::Null => {
+            let source = crate::avm2::globals::flash::geom::transform::get_display_object(
+                transform, activation,
+            )?;
+            let matrix = *source.base().matrix();
+            (matrix, source.transform_3d())
+        }
+        matrix => {
+            let matrix = crate::avm2::globals::flash::geom::transform::object_to_matrix(
+                matrix.coerce_to_object(activation)?,
+                activation,
+            )?;
+            (matrix, None)
+        }
+    };
     let color_transform = crate::avm2::globals::flash::geom::transform::object_to_color_transform(
         color_transform,
         activation,
@@ -788,6 +873,7 @@ pub fn set_transform<'gc>(
     write.set_matrix(matrix);
     write.set_color_transform(color_transform);
     drop(write);
+    dobj.set_transform_3d(activation.context.gc_context, transform_3d);
     if let Some(parent) = dobj.parent() {
         // Self-transform changes are automatically handled,
         // we only want to inform ancestors to avoid unnecessary invalidations for tx/ty
@@ -1114,14 +1200,29 @@ pub fn set_blend_shader<'gc>(
         else {
             return Err(make_error_2007(activation, "data"));
         };
-
-        let shader_handle = shader_data
+        let Some(shader) = shader_data
             .as_shader_data()
-            .expect("Shader.data is not a ShaderData instance")
-            .pixel_bender_shader()
-            .expect("Missing compiled PixelBender shader");
-
-        dobj.set_blend_shader(activation.context.gc_context, Some(shader_handle));
+            .and_then(|data| data.pixel_bender_shader())
+        else {
+            return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+        };
+        // Unlike the arguments of a `ShaderJob`, these are never rejected.
+        let mut arguments = get_shader_data_args(activation, shader_data, &shader, true)?;
+        // The first two inputs are the background and this object, which are filled in
+        // when rendering.
+        arguments
+            .iter_mut()
+            .filter_map(|argument| match argument {
+                PixelBenderShaderArgument::ImageInput { texture, .. } => Some(texture),
+                PixelBenderShaderArgument::ValueInput { .. } => None,
+            })
+            .take(2)
+            .for_each(|texture| *texture = None);
+
+        dobj.set_blend_shader(
+            activation.context.gc_context,
+            Some(BlendShader { shader, arguments }),
+        );
     }
     Ok(Value::Undefined)
 }
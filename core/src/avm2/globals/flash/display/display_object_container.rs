@@ -5,10 +5,10 @@ use swf::Twips;
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::{argument_error, make_error_2025, range_error};
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{EventObject, Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
-use crate::avm2::{ArrayObject, ArrayStorage, Error};
+use crate::avm2::{ArrayObject, ArrayStorage, Avm2, Error};
 use crate::avm2_stub_method;
 use crate::context::UpdateContext;
 use crate::display_object::HitTestOptions;
@@ -656,7 +656,14 @@ pub fn set_tab_children<'gc>(
         .and_then(|this| this.as_container())
     {
         let value = args.get_bool(0);
+        let changed = obj.is_tab_children(activation.context) != value;
         obj.set_tab_children(activation.context, value);
+
+        if changed {
+            let event =
+                EventObject::bare_event(activation.context, "tabChildrenChange", true, false);
+            Avm2::dispatch_event(activation.context, event, this);
+        }
     }
 
     Ok(Value::Undefined)
@@ -2,10 +2,10 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2027;
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{EventObject, Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Avm2, Error};
 use crate::display_object::{TDisplayObject, TInteractiveObject};
 
 /// Implements `flash.display.InteractiveObject`'s native instance constructor.
@@ -140,7 +140,14 @@ pub fn set_tab_enabled<'gc>(
         .and_then(|this| this.as_interactive())
     {
         let value = args.get_bool(0);
+        let changed = obj.tab_enabled(activation.context) != value;
         obj.set_tab_enabled(activation.context, value);
+
+        if changed {
+            let event =
+                EventObject::bare_event(activation.context, "tabEnabledChange", true, false);
+            Avm2::dispatch_event(activation.context, event, this);
+        }
     }
 
     Ok(Value::Undefined)
@@ -173,7 +180,13 @@ pub fn set_tab_index<'gc>(
         if value < -1 {
             return Err(make_error_2027(activation, value));
         }
+        let changed = obj.tab_index().unwrap_or(-1) != value;
         obj.set_tab_index(activation.context, Some(value));
+
+        if changed {
+            let event = EventObject::bare_event(activation.context, "tabIndexChange", true, false);
+            Avm2::dispatch_event(activation.context, event, this);
+        }
     }
 
     Ok(Value::Undefined)
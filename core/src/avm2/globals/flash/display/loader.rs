@@ -3,7 +3,7 @@
 use indexmap::IndexMap;
 
 use crate::avm2::activation::Activation;
-use crate::avm2::error::make_error_2007;
+use crate::avm2::error::{make_error_2007, make_error_2096, make_error_2142};
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
 use crate::avm2::object::LoaderInfoObject;
 use crate::avm2::object::LoaderStream;
@@ -19,6 +19,7 @@ use crate::display_object::LoaderDisplay;
 use crate::display_object::MovieClip;
 use crate::loader::LoadManager;
 use crate::loader::MovieLoaderVMData;
+use crate::sandbox::SandboxType;
 use crate::tag_utils::SwfMovie;
 use std::sync::Arc;
 
@@ -30,7 +31,7 @@ pub fn loader_allocator<'gc>(
 
     // Loader does not have an associated `Character` variant, and can never be
     // instantiated from the timeline.
-    let display_object = LoaderDisplay::empty(activation, activation.context.swf.clone()).into();
+    let display_object = LoaderDisplay::empty(activation, activation.caller_movie_or_root()).into();
     let loader = initialize_for_allocator(activation, display_object, class)?;
 
     // Note that the initialization of `_contentLoaderInfo` is intentionally done here,
@@ -111,24 +112,97 @@ pub fn load<'gc>(
     let request = request_from_url_request(activation, url_request)?;
 
     let url = request.url().to_string();
-    let future = activation.context.load_manager.load_movie_into_clip(
-        activation.context.player.clone(),
-        content.into(),
-        request,
-        Some(url),
-        MovieLoaderVMData::Avm2 {
-            loader_info,
-            context,
-            default_domain: activation
-                .caller_domain()
-                .expect("Missing caller domain in Loader.load"),
-        },
-    );
+
+    let security_domain = match context {
+        Some(context) => context.get_public_property("securityDomain", activation)?,
+        None => Value::Null,
+    };
+    let security_domain_importer = if matches!(security_domain, Value::Null | Value::Undefined) {
+        None
+    } else {
+        let caller_movie = activation.caller_movie_or_root();
+        if caller_movie.sandbox_type() != SandboxType::Remote {
+            return Err(make_error_2142(activation, caller_movie.url(), &url));
+        }
+        Some(caller_movie)
+    };
+    let future = activation
+        .context
+        .load_manager
+        .load_movie_into_security_domain(
+            activation.context.player.clone(),
+            content.into(),
+            request,
+            Some(url),
+            MovieLoaderVMData::Avm2 {
+                loader_info,
+                context,
+                default_domain: activation
+                    .caller_domain()
+                    .expect("Missing caller domain in Loader.load"),
+            },
+            security_domain_importer,
+        );
     activation.context.navigator.spawn_future(future);
 
     Ok(Value::Undefined)
 }
 
+/// Request headers that Flash Player refuses to let content set.
+const BLOCKED_REQUEST_HEADERS: &[&str] = &[
+    "Accept-Charset",
+    "Accept-Encoding",
+    "Accept-Ranges",
+    "Age",
+    "Allow",
+    "Allowed",
+    "Authorization",
+    "Charge-To",
+    "Connect",
+    "Connection",
+    "Content-Length",
+    "Content-Location",
+    "Content-Range",
+    "Cookie",
+    "Date",
+    "Delete",
+    "ETag",
+    "Expect",
+    "Get",
+    "Head",
+    "Host",
+    "If-Modified-Since",
+    "Keep-Alive",
+    "Last-Modified",
+    "Location",
+    "Max-Forwards",
+    "Options",
+    "Origin",
+    "Post",
+    "Proxy-Authenticate",
+    "Proxy-Authorization",
+    "Proxy-Connection",
+    "Public",
+    "Put",
+    "Range",
+    "Referer",
+    "Request-Range",
+    "Retry-After",
+    "Server",
+    "TE",
+    "Trace",
+    "Trailer",
+    "Transfer-Encoding",
+    "Upgrade",
+    "URI",
+    "User-Agent",
+    "Vary",
+    "Via",
+    "Warning",
+    "WWW-Authenticate",
+    "x-flash-version",
+];
+
 pub fn request_from_url_request<'gc>(
     activation: &mut Activation<'_, 'gc>,
     url_request: Object<'gc>,
@@ -167,18 +241,28 @@ pub fn request_from_url_request<'gc>(
             .coerce_to_string(activation)?
             .to_string();
 
+        if BLOCKED_REQUEST_HEADERS
+            .iter()
+            .any(|blocked| blocked.eq_ignore_ascii_case(name.trim()))
+        {
+            return Err(make_error_2096(activation, &name));
+        }
+
         // Note - testing with Flash Player shows that later entries in the array
         // overwrite earlier ones with the same name. Flash Player never sends an HTTP
-        // request with duplicate headers
+        // request with duplicate headers.
+        // Methods other than GET and POST are commonly tunneled through a POST
+        // with an `X-HTTP-Method-Override` header, which is passed through as-is.
         string_headers.insert(name, value);
     }
 
-    let method =
-        NavigationMethod::from_method_str(&method).expect("URLRequest should have a valid method");
+    // `URLRequest.method` only accepts the AIR-only methods when running as AIR.
+    let method = NavigationMethod::from_air_method_str(&method)
+        .expect("URLRequest should have a valid method");
     let data = url_request.get_public_property("data", activation)?;
     let body = match (method, data) {
         (_, Value::Null | Value::Undefined) => None,
-        (NavigationMethod::Get, data) => {
+        (method, data) if !method.sends_data_in_body() => {
             // This looks "wrong" but it's Flash-correct.
             // It simply appends the data to the URL if there's already a query,
             // otherwise it adds ?data.
@@ -190,7 +274,7 @@ pub fn request_from_url_request<'gc>(
             url.push_str(&data.coerce_to_string(activation)?.to_string());
             None
         }
-        (NavigationMethod::Post, data) => {
+        (_, data) => {
             let content_type = url_request
                 .get_public_property("contentType", activation)?
                 .coerce_to_string(activation)?
@@ -2,13 +2,16 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::Endian;
-use crate::avm2::error::error;
+use crate::avm2::error::{error, make_error_2121};
 use crate::avm2::object::{DomainObject, LoaderStream, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::{AvmString, Error};
 use crate::display_object::TDisplayObject;
 use crate::loader::ContentType;
+use crate::sitelock::SitelockCheck;
+use crate::tag_utils::SwfMovie;
 use crate::{avm2_stub_getter, avm2_stub_method};
+use std::sync::Arc;
 use swf::{write_swf, Compression};
 
 pub use crate::avm2::object::loader_info_allocator;
@@ -154,6 +157,21 @@ pub fn get_content<'gc>(
     if let Some(loader_stream) = loader_info.as_loader_stream() {
         match &*loader_stream {
             LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => {
+                if let Some(caller_movie) = activation.caller_movie() {
+                    let movie = root.movie();
+                    if !activation
+                        .context
+                        .domain_permissions
+                        .can_script(&caller_movie, &movie)
+                    {
+                        return Err(make_error_2121(
+                            activation,
+                            "LoaderInfo.content",
+                            caller_movie.url(),
+                            movie.url(),
+                        ));
+                    }
+                }
                 if root.movie().is_action_script_3() || !root.movie().is_movie() {
                     return Ok(root.object2());
                 } else {
@@ -251,6 +269,14 @@ pub fn get_is_url_inaccessible<'gc>(
     Ok(false.into())
 }
 
+/// The movie containing the `Loader` that loaded this content, if any.
+fn loader_movie(this: Object<'_>) -> Option<Arc<SwfMovie>> {
+    this.as_loader_info_object()?
+        .loader()?
+        .as_display_object()
+        .map(|loader| loader.movie())
+}
+
 /// `sameDomain` getter
 pub fn get_same_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -265,9 +291,12 @@ pub fn get_same_domain<'gc>(
             LoaderStream::NotYetLoaded(_, _, _) => {
                 return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
             }
-            LoaderStream::Swf(_root, _) => {
-                avm2_stub_getter!(activation, "flash.display.LoaderInfo", "sameDomain");
-                return Ok(false.into());
+            LoaderStream::Swf(root, _) => {
+                let Some(loader_movie) = loader_movie(this) else {
+                    return Ok(true.into());
+                };
+                let permissions = &activation.context.domain_permissions;
+                return Ok(permissions.same_domain(&loader_movie, root).into());
             }
         }
     }
@@ -289,9 +318,12 @@ pub fn get_child_allows_parent<'gc>(
             LoaderStream::NotYetLoaded(_, _, _) => {
                 return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
             }
-            LoaderStream::Swf(_root, _) => {
-                avm2_stub_getter!(activation, "flash.display.LoaderInfo", "childAllowsParent");
-                return Ok(false.into());
+            LoaderStream::Swf(root, _) => {
+                let Some(loader_movie) = loader_movie(this) else {
+                    return Ok(true.into());
+                };
+                let permissions = &activation.context.domain_permissions;
+                return Ok(permissions.can_script(&loader_movie, root).into());
             }
         }
     }
@@ -313,9 +345,12 @@ pub fn get_parent_allows_child<'gc>(
             LoaderStream::NotYetLoaded(_, _, _) => {
                 return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
             }
-            LoaderStream::Swf(_root, _) => {
-                avm2_stub_getter!(activation, "flash.display.LoaderInfo", "parentAllowsChild");
-                return Ok(false.into());
+            LoaderStream::Swf(root, _) => {
+                let Some(loader_movie) = loader_movie(this) else {
+                    return Ok(true.into());
+                };
+                let permissions = &activation.context.domain_permissions;
+                return Ok(permissions.can_script(root, &loader_movie).into());
             }
         }
     }
@@ -360,7 +395,12 @@ pub fn get_url<'gc>(
             let root = match &*loader_stream {
                 LoaderStream::NotYetLoaded(root, _, _) | LoaderStream::Swf(root, _) => root,
             };
-            return Ok(AvmString::new_utf8(activation.context.gc_context, root.url()).into());
+            let url = AvmString::new_utf8(activation.context.gc_context, root.url());
+            activation
+                .context
+                .sitelock_detector
+                .note_url_read(SitelockCheck::LoaderInfoUrl, &url);
+            return Ok(url.into());
         }
     }
 
@@ -0,0 +1,27 @@
+//! `flash.display.NativeWindow` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::player::PlayerRuntime;
+
+/// Implements `NativeWindow.setWindowTitle`
+pub fn set_window_title<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let title = args.get_string(activation, 0)?;
+
+    // Only AIR applications own the window they're running in.
+    if activation.avm2().player_runtime == PlayerRuntime::AIR {
+        activation
+            .context
+            .ui
+            .set_window_title(&title.to_utf8_lossy());
+    }
+
+    Ok(Value::Undefined)
+}
@@ -31,147 +31,176 @@ pub fn get_shader_args<'gc>(
     let shader_data = shader_obj
         .get_public_property("data", activation)?
         .as_object()
-        .expect("Missing ShaderData object")
-        .as_shader_data()
-        .expect("ShaderData object is not a ShaderData instance");
+        .expect("Missing ShaderData object");
 
-    let shader_handle = shader_data.pixel_bender_shader();
+    let shader_handle = shader_data
+        .as_shader_data()
+        .expect("ShaderData object is not a ShaderData instance")
+        .pixel_bender_shader();
     let shader_handle = shader_handle
         .as_ref()
         .expect("ShaderData object has no shader");
-    let shader = shader_handle.0.parsed_shader();
 
-    let args = shader
-        .params
-        .iter()
-        .enumerate()
-        .flat_map(|(index, param)| {
-            match param {
-                PixelBenderParam::Normal {
-                    qualifier,
-                    param_type,
-                    name,
-                    ..
-                } => {
-                    if matches!(qualifier, PixelBenderParamQualifier::Output) {
-                        return None;
-                    }
+    let args = get_shader_data_args(activation, shader_data, shader_handle, false)?;
+    Ok((shader_handle.clone(), args))
+}
 
-                    if name == OUT_COORD_NAME {
-                        // Pass in a dummy value - this will be ignored in favor of the actual pixel coordinate
-                        return Some(PixelBenderShaderArgument::ValueInput {
-                            index: index as u8,
-                            value: PixelBenderType::TFloat2(f32::NAN, f32::NAN),
-                        });
+/// Reads the arguments of a shader from the properties of its `ShaderData`.
+///
+/// With `use_defaults`, as for blend shaders, parameters that are missing or can't be
+/// converted use their `defaultValue` (or are left out without one), and inputs that can't
+/// be read are left empty. Otherwise, these panic.
+pub fn get_shader_data_args<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    shader_data: Object<'gc>,
+    shader: &PixelBenderShaderHandle,
+    use_defaults: bool,
+) -> Result<Vec<PixelBenderShaderArgument<'static>>, Error<'gc>> {
+    let mut args = Vec::new();
+    for (index, param) in shader.0.parsed_shader().params.iter().enumerate() {
+        match param {
+            PixelBenderParam::Normal {
+                qualifier: PixelBenderParamQualifier::Output,
+                ..
+            } => {}
+            PixelBenderParam::Normal {
+                param_type,
+                name,
+                metadata,
+                ..
+            } => {
+                let value = if name == OUT_COORD_NAME {
+                    // Pass in a dummy value - this will be ignored in favor of the actual pixel coordinate
+                    Some(PixelBenderType::TFloat2(f32::NAN, f32::NAN))
+                } else {
+                    let name = AvmString::new_utf8(activation.context.gc_context, name);
+                    let shader_param = shader_data.get_public_property(name, activation)?;
+                    match shader_param.as_object() {
+                        Some(shader_param) => {
+                            let value = shader_param.get_public_property("value", activation)?;
+                            let value =
+                                PixelBenderType::from_avm2_value(activation, value, param_type);
+                            if use_defaults {
+                                value.ok()
+                            } else {
+                                Some(
+                                    value.expect("Failed to convert AVM2 value to PixelBenderType"),
+                                )
+                            }
+                        }
+                        None if use_defaults => None,
+                        None => panic!("Shader property is not an object"),
                     }
-                    let shader_param = shader_data
-                        .get_public_property(
-                            AvmString::new_utf8(activation.context.gc_context, name),
-                            activation,
-                        )
-                        .expect("Missing normal property");
-
-                    let shader_param = shader_param
-                        .as_object()
-                        .expect("Shader property is not an object");
+                };
+                let value = value.or_else(|| {
+                    metadata
+                        .iter()
+                        .find(|metadata| metadata.key == "defaultValue")
+                        .map(|metadata| metadata.value.clone())
+                });
 
-                    let value = shader_param
-                        .get_public_property("value", activation)
-                        .expect("Missing value property");
-                    let pb_val = PixelBenderType::from_avm2_value(activation, value, param_type)
-                        .expect("Failed to convert AVM2 value to PixelBenderType");
-
-                    Some(PixelBenderShaderArgument::ValueInput {
+                if let Some(value) = value {
+                    args.push(PixelBenderShaderArgument::ValueInput {
                         index: index as u8,
-                        value: pb_val,
-                    })
+                        value,
+                    });
                 }
-                PixelBenderParam::Texture {
-                    index,
-                    channels,
-                    name,
-                } => {
-                    let shader_input = shader_data
-                        .get_public_property(
-                            AvmString::new_utf8(activation.context.gc_context, name),
-                            activation,
-                        )
-                        .expect("Missing property")
-                        .as_object()
-                        .expect("Shader input is not an object");
-
-                    let input = shader_input
-                        .get_public_property("input", activation)
-                        .expect("Missing input property");
+            }
+            PixelBenderParam::Texture {
+                index,
+                channels,
+                name,
+            } => {
+                let name_string = AvmString::new_utf8(activation.context.gc_context, name);
+                let shader_input = shader_data
+                    .get_public_property(name_string, activation)?
+                    .as_object();
+                let texture = match shader_input {
+                    Some(shader_input) => {
+                        get_input_texture(activation, shader_input, *channels, use_defaults)?
+                    }
+                    None if use_defaults => None,
+                    None => panic!("Shader input is not an object"),
+                };
 
-                    let width = shader_input
-                        .get_public_property("width", activation)
-                        .unwrap()
-                        .as_u32();
-                    let height = shader_input
-                        .get_public_property("height", activation)
-                        .unwrap()
-                        .as_u32();
+                args.push(PixelBenderShaderArgument::ImageInput {
+                    index: *index,
+                    channels: *channels,
+                    name: name.clone(),
+                    texture,
+                });
+            }
+        }
+    }
+    Ok(args)
+}
 
-                    let input_channels = shader_input
-                        .get_public_property("channels", activation)
-                        .unwrap()
-                        .as_u32();
+/// Reads the texture of a `ShaderInput`, see [`get_shader_data_args`].
+fn get_input_texture<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    shader_input: Object<'gc>,
+    channels: u8,
+    use_defaults: bool,
+) -> Result<Option<ImageInputTexture<'static>>, Error<'gc>> {
+    let input = shader_input.get_public_property("input", activation)?;
+    if use_defaults {
+        let bitmap = input.as_object().and_then(|input| input.as_bitmap_data());
+        return Ok(bitmap.map(|bitmap| {
+            ImageInputTexture::Bitmap(
+                bitmap.bitmap_handle(activation.context.gc_context, activation.context.renderer),
+            )
+        }));
+    }
 
-                    assert_eq!(*channels as u32, input_channels);
+    let width = shader_input
+        .get_public_property("width", activation)?
+        .as_u32();
+    let height = shader_input
+        .get_public_property("height", activation)?
+        .as_u32();
+    let input_channels = shader_input
+        .get_public_property("channels", activation)?
+        .as_u32();
 
-                    let texture = if let Value::Null = input {
-                        None
-                    } else {
-                        let input = input
-                            .as_object()
-                            .expect("ShaderInput.input is not an object");
+    assert_eq!(channels as u32, input_channels);
 
-                        let input_texture = if let Some(bitmap) = input.as_bitmap_data() {
-                            ImageInputTexture::Bitmap(bitmap.bitmap_handle(
-                                activation.context.gc_context,
-                                activation.context.renderer,
-                            ))
-                        } else if let Some(byte_array) = input.as_bytearray() {
-                            let expected_len = (width * height * input_channels) as usize
-                                * std::mem::size_of::<f32>();
-                            assert_eq!(byte_array.len(), expected_len);
-                            assert_eq!(byte_array.endian(), Endian::Little);
-                            ImageInputTexture::Bytes {
-                                width,
-                                height,
-                                channels: input_channels,
-                                bytes: byte_array.read_at(0, byte_array.len()).unwrap().to_vec(),
-                            }
-                        } else if let Some(vector) = input.as_vector_storage() {
-                            let expected_len = (width * height * input_channels) as usize;
-                            assert_eq!(vector.length(), expected_len);
-                            ImageInputTexture::Bytes {
-                                width,
-                                height,
-                                channels: input_channels,
-                                bytes: vector
-                                    .iter()
-                                    .flat_map(|val| (val.as_f64() as f32).to_le_bytes())
-                                    .collect(),
-                            }
-                        } else {
-                            panic!("Unexpected input object {input:?}");
-                        };
-                        Some(input_texture)
-                    };
+    if let Value::Null = input {
+        return Ok(None);
+    }
+    let input = input
+        .as_object()
+        .expect("ShaderInput.input is not an object");
 
-                    Some(PixelBenderShaderArgument::ImageInput {
-                        index: *index,
-                        channels: *channels,
-                        name: name.clone(),
-                        texture,
-                    })
-                }
-            }
-        })
-        .collect();
-    Ok((shader_handle.clone(), args))
+    let texture = if let Some(bitmap) = input.as_bitmap_data() {
+        ImageInputTexture::Bitmap(
+            bitmap.bitmap_handle(activation.context.gc_context, activation.context.renderer),
+        )
+    } else if let Some(byte_array) = input.as_bytearray() {
+        let expected_len = (width * height * input_channels) as usize * std::mem::size_of::<f32>();
+        assert_eq!(byte_array.len(), expected_len);
+        assert_eq!(byte_array.endian(), Endian::Little);
+        ImageInputTexture::Bytes {
+            width,
+            height,
+            channels: input_channels,
+            bytes: byte_array.read_at(0, byte_array.len()).unwrap().to_vec(),
+        }
+    } else if let Some(vector) = input.as_vector_storage() {
+        let expected_len = (width * height * input_channels) as usize;
+        assert_eq!(vector.length(), expected_len);
+        ImageInputTexture::Bytes {
+            width,
+            height,
+            channels: input_channels,
+            bytes: vector
+                .iter()
+                .flat_map(|val| (val.as_f64() as f32).to_le_bytes())
+                .collect(),
+        }
+    } else {
+        panic!("Unexpected input object {input:?}");
+    };
+    Ok(Some(texture))
 }
 
 /// Implements `ShaderJob.start`.
@@ -449,8 +449,8 @@ pub fn get_full_screen_height<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.Stage", "fullScreenHeight");
-    Ok(768.into())
+    let (_, height) = full_screen_size(activation);
+    Ok(height.into())
 }
 
 /// Stage.fullScreenWidth's getter
@@ -459,8 +459,17 @@ pub fn get_full_screen_width<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.Stage", "fullScreenWidth");
-    Ok(1024.into())
+    let (width, _) = full_screen_size(activation);
+    Ok(width.into())
+}
+
+/// The size the stage would have in fullscreen mode, which is the resolution of the screen it's on.
+fn full_screen_size(activation: &mut Activation<'_, '_>) -> (f64, f64) {
+    let viewport_dimensions = activation.context.renderer.viewport_dimensions();
+    activation
+        .context
+        .system
+        .get_screen_resolution(viewport_dimensions)
 }
 
 pub fn set_tab_children<'gc>(
@@ -71,10 +71,16 @@ pub fn remove_event_listener<'gc>(
         .as_callable(activation, None, None, false)?;
     let use_capture = args.get_bool(2);
 
-    dispatch_list
+    let mut dispatch_list = dispatch_list
         .as_dispatch_mut(activation.context.gc_context)
-        .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
-        .remove_event_listener(event_type, listener, use_capture);
+        .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?;
+    dispatch_list.remove_event_listener(event_type, listener, use_capture);
+    let has_listeners = dispatch_list.has_event_listener(event_type);
+    drop(dispatch_list);
+
+    if !has_listeners {
+        Avm2::unregister_broadcast_listener(activation.context, this, event_type);
+    }
 
     Ok(Value::Undefined)
 }
@@ -1,36 +1,323 @@
 package flash.filesystem {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-
+    import flash.errors.IOError;
     import flash.net.FileReference;
+    import flash.utils.ByteArray;
 
+    // Ruffle only exposes a sandboxed filesystem to AIR content.
+    // Every path lives inside the sandbox configured by the embedder,
+    // with the well-known AIR directories mapped to top-level folders.
     [API("661")]
     public class File extends FileReference {
-        private static var _applicationDirectory:File = null;
+        public static const separator:String = "/";
+        public static const lineEnding:String = "\n";
+
+        // The sandbox-relative path of this file, without a leading separator.
+        // An empty string refers to the root of the sandbox.
+        private var _path:String = null;
 
         public function File(path:String = null) {
-            stub_constructor("flash.filesystem.File");
+            if (path !== null) {
+                this.nativePath = File.pathFromUrl(path);
+            }
         }
 
         public static function get applicationDirectory():File {
-            if (_applicationDirectory === null) {
-                _applicationDirectory = new File();
+            return File.fromSandboxPath("app");
+        }
+
+        public static function get applicationStorageDirectory():File {
+            return File.fromSandboxPath("app-storage");
+        }
+
+        public static function get desktopDirectory():File {
+            return File.fromSandboxPath("desktop");
+        }
+
+        public static function get documentsDirectory():File {
+            return File.fromSandboxPath("documents");
+        }
+
+        public static function get userDirectory():File {
+            return File.fromSandboxPath("user");
+        }
+
+        public static function get cacheDirectory():File {
+            return File.fromSandboxPath("cache");
+        }
+
+        private static function fromSandboxPath(path:String):File {
+            var file:File = new File();
+            file._path = path;
+            return file;
+        }
+
+        private static function pathFromUrl(url:String):String {
+            if (url.indexOf("app:/") == 0) {
+                return "/app/" + url.substr(5);
             }
+            if (url.indexOf("app-storage:/") == 0) {
+                return "/app-storage/" + url.substr(13);
+            }
+            if (url.indexOf("file://") == 0) {
+                return decodeURI(url.substr(7));
+            }
+            return url;
+        }
 
-            return _applicationDirectory;
+        // Resolves `.` and `..` segments. `..` never leaves the sandbox root.
+        private static function normalize(path:String):String {
+            var result:Array = [];
+            var segments:Array = path.split("\\").join("/").split("/");
+            for each (var segment:String in segments) {
+                if (segment == "" || segment == ".") {
+                    continue;
+                }
+                if (segment == "..") {
+                    result.pop();
+                } else {
+                    result.push(segment);
+                }
+            }
+            return result.join("/");
         }
 
-        public function resolvePath(path:String):File {
-            stub_method("flash.filesystem.File", "resolvePath");
+        private function get path():String {
+            if (this._path === null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            return this._path;
+        }
+
+        public function get nativePath():String {
+            if (this._path === null) {
+                return null;
+            }
+            return "/" + this._path;
+        }
+
+        public function set nativePath(value:String):void {
+            if (value === null || value.charAt(0) != "/") {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._path = File.normalize(value);
+        }
+
+        public function get url():String {
+            if (this._path === null) {
+                return null;
+            }
+            if (this._path == "app" || this._path.indexOf("app/") == 0) {
+                return "app:/" + encodeURI(this._path.substr(4));
+            }
+            if (this._path == "app-storage" || this._path.indexOf("app-storage/") == 0) {
+                return "app-storage:/" + encodeURI(this._path.substr(12));
+            }
+            return "file:///" + encodeURI(this._path);
+        }
+
+        public function set url(value:String):void {
+            this.nativePath = File.pathFromUrl(value);
+        }
+
+        override public function get name():String {
+            var path:String = this.path;
+            return path.substr(path.lastIndexOf("/") + 1);
+        }
 
-            return new File();
+        [API("661")]
+        override public function get extension():String {
+            var name:String = this.name;
+            var index:int = name.lastIndexOf(".");
+            return index == -1 ? null : name.substr(index + 1);
+        }
+
+        override public function get type():String {
+            var extension:String = this.extension;
+            return extension === null ? null : "." + extension;
+        }
+
+        override public function get size():Number {
+            if (!this.exists) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+            return File.fileSize(this.path);
         }
 
         public function get exists():Boolean {
-            stub_getter("flash.filesystem.File", "exists");
+            return File.fileExists(this.path);
+        }
+
+        public function get isDirectory():Boolean {
+            return File.fileIsDirectory(this.path);
+        }
+
+        public function get isHidden():Boolean {
+            return this.name.charAt(0) == ".";
+        }
+
+        public function get isPackage():Boolean {
+            return false;
+        }
 
+        public function get isSymbolicLink():Boolean {
             return false;
         }
+
+        public function get parent():File {
+            var path:String = this.path;
+            if (path == "") {
+                return null;
+            }
+            var index:int = path.lastIndexOf("/");
+            return File.fromSandboxPath(index == -1 ? "" : path.substr(0, index));
+        }
+
+        public function resolvePath(path:String):File {
+            if (path.charAt(0) == "/") {
+                return File.fromSandboxPath(File.normalize(path));
+            }
+            return File.fromSandboxPath(File.normalize(this.path + "/" + path));
+        }
+
+        public function getRelativePath(ref:FileReference, useDotDot:Boolean = false):String {
+            var other:File = ref as File;
+            if (other === null) {
+                return null;
+            }
+            var from:Array = this.path == "" ? [] : this.path.split("/");
+            var to:Array = other.path == "" ? [] : other.path.split("/");
+            var common:int = 0;
+            while (common < from.length && common < to.length && from[common] == to[common]) {
+                common++;
+            }
+            if (common < from.length && !useDotDot) {
+                return null;
+            }
+            var result:Array = [];
+            for (var i:int = common; i < from.length; i++) {
+                result.push("..");
+            }
+            return result.concat(to.slice(common)).join("/");
+        }
+
+        public function clone():File {
+            return File.fromSandboxPath(this._path);
+        }
+
+        public function getDirectoryListing():Array {
+            var names:Array = File.listDirectory(this.path);
+            if (names === null) {
+                throw new IOError("Error #3007: The specified file is not a directory.", 3007);
+            }
+            var result:Array = [];
+            for each (var name:String in names) {
+                result.push(this.resolvePath(name));
+            }
+            return result;
+        }
+
+        public function createDirectory():void {
+            if (!File.createDirectoryAt(this.path)) {
+                throw new IOError("Error #3001: File or directory access denied.", 3001);
+            }
+        }
+
+        public function deleteFile():void {
+            if (!this.exists) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+            if (this.isDirectory) {
+                throw new IOError("Error #3008: The specified file is a directory.", 3008);
+            }
+            if (!File.deletePath(this.path, false)) {
+                throw new IOError("Error #3001: File or directory access denied.", 3001);
+            }
+        }
+
+        public function deleteDirectory(deleteDirectoryContents:Boolean = false):void {
+            if (!this.exists) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+            if (!this.isDirectory) {
+                throw new IOError("Error #3007: The specified file is not a directory.", 3007);
+            }
+            if (!File.deletePath(this.path, deleteDirectoryContents)) {
+                throw new IOError("Error #3001: File or directory access denied.", 3001);
+            }
+        }
+
+        public function copyTo(newLocation:FileReference, overwrite:Boolean = false):void {
+            var destination:File = this.prepareDestination(newLocation, overwrite);
+            if (this.isDirectory) {
+                this.copyDirectoryTo(destination);
+            } else {
+                this.copyFileTo(destination);
+            }
+        }
+
+        public function moveTo(newLocation:FileReference, overwrite:Boolean = false):void {
+            var destination:File = this.prepareDestination(newLocation, overwrite);
+            if (!File.movePath(this.path, destination.path)) {
+                throw new IOError("Error #3001: File or directory access denied.", 3001);
+            }
+        }
+
+        // Checks that this file can be copied or moved to `newLocation`,
+        // removing whatever is already there if `overwrite` is set.
+        private function prepareDestination(newLocation:FileReference, overwrite:Boolean):File {
+            var destination:File = newLocation as File;
+            if (destination === null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (!this.exists) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+            if (destination.exists) {
+                if (!overwrite) {
+                    throw new IOError("Error #3002: File or directory exists.", 3002);
+                }
+                if (!File.deletePath(destination.path, true)) {
+                    throw new IOError("Error #3001: File or directory access denied.", 3001);
+                }
+            }
+            return destination;
+        }
+
+        private function copyDirectoryTo(destination:File):void {
+            destination.createDirectory();
+            for each (var child:File in this.getDirectoryListing()) {
+                var target:File = destination.resolvePath(child.name);
+                if (child.isDirectory) {
+                    child.copyDirectoryTo(target);
+                } else {
+                    child.copyFileTo(target);
+                }
+            }
+        }
+
+        private function copyFileTo(destination:File):void {
+            var bytes:ByteArray = new ByteArray();
+            var stream:FileStream = new FileStream();
+            stream.open(this, FileMode.READ);
+            stream.readBytes(bytes);
+            stream.close();
+
+            stream.open(destination, FileMode.WRITE);
+            stream.writeBytes(bytes);
+            stream.close();
+        }
+
+        private static native function fileExists(path:String):Boolean;
+        private static native function fileIsDirectory(path:String):Boolean;
+        private static native function fileSize(path:String):Number;
+        private static native function listDirectory(path:String):Array;
+        private static native function createDirectoryAt(path:String):Boolean;
+        private static native function deletePath(path:String, recursive:Boolean):Boolean;
+        private static native function movePath(from:String, to:String):Boolean;
+
+        // Used by FileStream, which has no access to `_path` itself.
+        internal function get sandboxPath():String {
+            return this.path;
+        }
     }
 }
@@ -0,0 +1,229 @@
+package flash.filesystem {
+    import __ruffle__.stub_method;
+
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.utils.ByteArray;
+    import flash.utils.Endian;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+
+    // The whole file is buffered in memory while the stream is open,
+    // and written back to the sandbox when the stream is closed.
+    [API("661")]
+    public class FileStream extends EventDispatcher implements IDataInput, IDataOutput {
+        private var _buffer:ByteArray = null;
+        private var _path:String = null;
+        private var _mode:String = null;
+        private var _dirty:Boolean = false;
+        private var _endian:String = Endian.BIG_ENDIAN;
+        private var _objectEncoding:uint = 3;
+
+        public function FileStream() {
+        }
+
+        public function open(file:File, fileMode:String):void {
+            if (this._buffer !== null) {
+                this.close();
+            }
+
+            var path:String = file.sandboxPath;
+            var contents:ByteArray = null;
+            if (fileMode != FileMode.WRITE) {
+                contents = FileStream.readFile(path);
+            }
+
+            if (fileMode == FileMode.READ) {
+                if (contents === null) {
+                    throw new IOError("Error #3003: File or directory does not exist.", 3003);
+                }
+            } else if (fileMode == FileMode.WRITE || fileMode == FileMode.APPEND || fileMode == FileMode.UPDATE) {
+                if (contents === null) {
+                    contents = new ByteArray();
+                    // Creates the file, as AIR does when opening it for writing.
+                    if (!FileStream.writeFile(path, contents)) {
+                        throw new IOError("Error #3001: File or directory access denied.", 3001);
+                    }
+                }
+            } else {
+                throw new ArgumentError("Error #2008: Parameter fileMode must be one of the accepted values.", 2008);
+            }
+
+            contents.endian = this._endian;
+            contents.objectEncoding = this._objectEncoding;
+            contents.position = fileMode == FileMode.APPEND ? contents.length : 0;
+
+            this._buffer = contents;
+            this._path = path;
+            this._mode = fileMode;
+            this._dirty = false;
+        }
+
+        public function openAsync(file:File, fileMode:String):void {
+            stub_method("flash.filesystem.FileStream", "openAsync", "asynchronous reads");
+            this.open(file, fileMode);
+            this.dispatchEvent(new Event(Event.COMPLETE));
+        }
+
+        public function close():void {
+            if (this._buffer === null) {
+                return;
+            }
+            var flushed:Boolean = !this._dirty || FileStream.writeFile(this._path, this._buffer);
+            this._buffer = null;
+            this._path = null;
+            this._mode = null;
+            this._dirty = false;
+            this.dispatchEvent(new Event(Event.CLOSE));
+            if (!flushed) {
+                throw new IOError("Error #3013: File or directory is in use.", 3013);
+            }
+        }
+
+        private function get readable():ByteArray {
+            if (this._buffer === null || this._mode == FileMode.WRITE || this._mode == FileMode.APPEND) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+            return this._buffer;
+        }
+
+        private function get writable():ByteArray {
+            if (this._buffer === null || this._mode == FileMode.READ) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+            this._dirty = true;
+            if (this._mode == FileMode.APPEND) {
+                this._buffer.position = this._buffer.length;
+            }
+            return this._buffer;
+        }
+
+        public function get bytesAvailable():uint {
+            return this._buffer === null ? 0 : this._buffer.bytesAvailable;
+        }
+
+        public function get position():Number {
+            return this._buffer === null ? 0 : this._buffer.position;
+        }
+
+        public function set position(value:Number):void {
+            if (this._buffer !== null) {
+                this._buffer.position = value;
+            }
+        }
+
+        public function get endian():String {
+            return this._endian;
+        }
+
+        public function set endian(value:String):void {
+            if (value !== Endian.BIG_ENDIAN && value !== Endian.LITTLE_ENDIAN) {
+                throw new ArgumentError("Error #2008: Parameter endian must be one of the accepted values.", 2008);
+            }
+            this._endian = value;
+            if (this._buffer !== null) {
+                this._buffer.endian = value;
+            }
+        }
+
+        public function get objectEncoding():uint {
+            return this._objectEncoding;
+        }
+
+        public function set objectEncoding(value:uint):void {
+            this._objectEncoding = value;
+            if (this._buffer !== null) {
+                this._buffer.objectEncoding = value;
+            }
+        }
+
+        public function truncate():void {
+            var buffer:ByteArray = this.writable;
+            buffer.length = buffer.position;
+        }
+
+        public function readBoolean():Boolean {
+            return this.readable.readBoolean();
+        }
+        public function readByte():int {
+            return this.readable.readByte();
+        }
+        public function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.readable.readBytes(bytes, offset, length);
+        }
+        public function readDouble():Number {
+            return this.readable.readDouble();
+        }
+        public function readFloat():Number {
+            return this.readable.readFloat();
+        }
+        public function readInt():int {
+            return this.readable.readInt();
+        }
+        public function readMultiByte(length:uint, charSet:String):String {
+            return this.readable.readMultiByte(length, charSet);
+        }
+        public function readObject():* {
+            return this.readable.readObject();
+        }
+        public function readShort():int {
+            return this.readable.readShort();
+        }
+        public function readUnsignedByte():uint {
+            return this.readable.readUnsignedByte();
+        }
+        public function readUnsignedInt():uint {
+            return this.readable.readUnsignedInt();
+        }
+        public function readUnsignedShort():uint {
+            return this.readable.readUnsignedShort();
+        }
+        public function readUTF():String {
+            return this.readable.readUTF();
+        }
+        public function readUTFBytes(length:uint):String {
+            return this.readable.readUTFBytes(length);
+        }
+
+        public function writeBoolean(value:Boolean):void {
+            this.writable.writeBoolean(value);
+        }
+        public function writeByte(value:int):void {
+            this.writable.writeByte(value);
+        }
+        public function writeBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.writable.writeBytes(bytes, offset, length);
+        }
+        public function writeDouble(value:Number):void {
+            this.writable.writeDouble(value);
+        }
+        public function writeFloat(value:Number):void {
+            this.writable.writeFloat(value);
+        }
+        public function writeInt(value:int):void {
+            this.writable.writeInt(value);
+        }
+        public function writeMultiByte(value:String, charSet:String):void {
+            this.writable.writeMultiByte(value, charSet);
+        }
+        public function writeObject(object:*):void {
+            this.writable.writeObject(object);
+        }
+        public function writeShort(value:int):void {
+            this.writable.writeShort(value);
+        }
+        public function writeUnsignedInt(value:uint):void {
+            this.writable.writeUnsignedInt(value);
+        }
+        public function writeUTF(value:String):void {
+            this.writable.writeUTF(value);
+        }
+        public function writeUTFBytes(value:String):void {
+            this.writable.writeUTFBytes(value);
+        }
+
+        private static native function readFile(path:String):ByteArray;
+        private static native function writeFile(path:String, data:ByteArray):Boolean;
+    }
+}
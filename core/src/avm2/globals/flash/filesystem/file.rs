@@ -0,0 +1,113 @@
+//! `flash.filesystem.File` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayObject, ArrayStorage, Error};
+use crate::string::AvmString;
+
+/// Implements `File.fileExists`
+pub fn file_exists<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+
+    // The sandbox root and the well-known directories always exist,
+    // even if the backend hasn't created them on disk yet.
+    Ok((path.is_empty() || activation.context.filesystem.exists(&path)).into())
+}
+
+/// Implements `File.fileIsDirectory`
+pub fn file_is_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+
+    Ok((path.is_empty() || activation.context.filesystem.is_directory(&path)).into())
+}
+
+/// Implements `File.fileSize`
+pub fn file_size<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+
+    let size = activation.context.filesystem.size(&path).unwrap_or(0);
+    Ok((size as f64).into())
+}
+
+/// Implements `File.listDirectory`, returning `null` if the path isn't a directory.
+pub fn list_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+
+    let Some(mut names) = activation.context.filesystem.list_directory(&path) else {
+        return Ok(Value::Null);
+    };
+    names.sort();
+
+    let names = names
+        .into_iter()
+        .map(|name| Some(AvmString::new_utf8(activation.gc(), name).into()))
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(names))?.into())
+}
+
+/// Implements `File.createDirectoryAt`
+pub fn create_directory_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+
+    Ok(activation.context.filesystem.create_directory(&path).into())
+}
+
+/// Implements `File.deletePath`
+pub fn delete_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+    let recursive = args.get_bool(1);
+
+    // Never allow wiping out the whole sandbox.
+    if path.is_empty() {
+        return Ok(false.into());
+    }
+
+    Ok(activation
+        .context
+        .filesystem
+        .delete(&path, recursive)
+        .into())
+}
+
+/// Implements `File.movePath`
+pub fn move_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let from = args.get_string(activation, 0)?.to_string();
+    let to = args.get_string(activation, 1)?.to_string();
+
+    // The sandbox root can't be moved, nor can anything be moved over it.
+    if from.is_empty() || to.is_empty() {
+        return Ok(false.into());
+    }
+
+    Ok(activation.context.filesystem.rename(&from, &to).into())
+}
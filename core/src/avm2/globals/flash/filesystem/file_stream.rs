@@ -0,0 +1,46 @@
+//! `flash.filesystem.FileStream` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::{ByteArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Implements `FileStream.readFile`, returning `null` if the file can't be read.
+pub fn read_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+
+    if activation.context.filesystem.is_directory(&path) {
+        return Ok(Value::Null);
+    }
+    let Some(data) = activation.context.filesystem.read(&path) else {
+        return Ok(Value::Null);
+    };
+
+    let storage = ByteArrayStorage::from_vec(data);
+    Ok(ByteArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements `FileStream.writeFile`
+pub fn write_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+    let data = args.get_object(activation, 1, "data")?;
+
+    let Some(bytearray) = data.as_bytearray() else {
+        return Ok(false.into());
+    };
+    let written = activation
+        .context
+        .filesystem
+        .write(&path, bytearray.bytes());
+    Ok(written.into())
+}
@@ -93,15 +93,9 @@ package flash.geom {
             }
 
             this._fieldOfView = value;
-            //this._focalLength = stage.stageWidth * (1.0 / Math.tan(this._fieldOfView * TO_RADIAN * 0.5));
 
-            // influenced by both the field of view and the aspect ratio of the stage. 
-            // the aspect ratio is the ratio of width to height (i.e., stageWidth / stageHeight)
-            //var effectiveHeight:Number = 2 * this._znear * Math.tan(this._fieldOfView * TO_RADIAN * 0.5);
-            //var effectiveWidth:Number = effectiveHeight * (stage.stageWidth / stage.stageHeight);
-            //this._focalLength = effectiveWidth;
-
-            this._focalLength = 2 * this._znear * Math.tan(this._fieldOfView * TO_RADIAN * 0.5) * (stage.stageWidth / stage.stageHeight);
+            // The distance at which half the stage width spans half the field of view.
+            this._focalLength = stage.stageWidth / 2 / Math.tan(this._fieldOfView * TO_RADIAN * 0.5);
 
             return this._fieldOfView; // return degree
         }
@@ -2,9 +2,7 @@ package flash.geom {
 	import flash.display.DisplayObject;
 	import flash.geom.Matrix3D;
 	import flash.geom.PerspectiveProjection;
-	import __ruffle__.stub_getter;
-	import __ruffle__.stub_method;
-	import __ruffle__.stub_setter;
+	import flash.geom.Vector3D;
 
 	public class Transform {
 		internal var _displayObject:DisplayObject;
@@ -23,27 +21,92 @@ package flash.geom {
 		public native function get concatenatedMatrix():Matrix;
 		public native function get pixelBounds():Rectangle;
 
+		private native function get is3D():Boolean;
+		private native function flatten3D():void;
+
+		private static const DEGREES_TO_RADIANS:Number = Math.PI / 180;
+
+		// Objects only have a 3D matrix once they were placed in 3D space.
 		public function get matrix3D():Matrix3D {
-			stub_getter("flash.geom.Transform", "matrix3D");
-			return new Matrix3D();
+			if (!this.is3D) {
+				return null;
+			}
+			var obj:DisplayObject = this._displayObject;
+			var m:Matrix3D = new Matrix3D();
+			m.recompose(new <Vector3D>[
+				new Vector3D(obj.x, obj.y, obj.z),
+				new Vector3D(
+					obj.rotationX * DEGREES_TO_RADIANS,
+					obj.rotationY * DEGREES_TO_RADIANS,
+					obj.rotationZ * DEGREES_TO_RADIANS
+				),
+				new Vector3D(obj.scaleX, obj.scaleY, obj.scaleZ)
+			]);
+			return m;
 		}
 
 		public function set matrix3D(m:Matrix3D):void {
-			stub_setter("flash.geom.Transform", "matrix3D");
+			if (m == null) {
+				this.flatten3D();
+				return;
+			}
+			var components:Vector.<Vector3D> = m.decompose();
+			var obj:DisplayObject = this._displayObject;
+			obj.x = components[0].x;
+			obj.y = components[0].y;
+			obj.z = components[0].z;
+			obj.scaleX = components[2].x;
+			obj.scaleY = components[2].y;
+			obj.scaleZ = components[2].z;
+			obj.rotationX = components[1].x / DEGREES_TO_RADIANS;
+			obj.rotationY = components[1].y / DEGREES_TO_RADIANS;
+			obj.rotationZ = components[1].z / DEGREES_TO_RADIANS;
 		}
 
+		private native function readPerspectiveProjection(projection:PerspectiveProjection):Boolean;
+
 		public function get perspectiveProjection():PerspectiveProjection {
-			stub_getter("flash.geom.Transform", "perspectiveProjection");
-			return new PerspectiveProjection();
+			var projection:PerspectiveProjection = new PerspectiveProjection();
+			if (!this.readPerspectiveProjection(projection)) {
+				return null;
+			}
+			return projection;
 		}
 
-		public function set perspectiveProjection(val: PerspectiveProjection):void {
-			stub_setter("flash.geom.Transform", "perspectiveProjection");
-		}
+		public native function set perspectiveProjection(val: PerspectiveProjection):void;
 
 		public function getRelativeMatrix3D(relativeTo:DisplayObject):Matrix3D {
-			stub_method("flash.geom.Transform", "getRelativeMatrix3D");
-			return new Matrix3D();
+			if (relativeTo == null) {
+				throw new TypeError("Error #2007: Parameter relativeTo must be non-null.", 2007);
+			}
+			if (!this.is3D) {
+				return null;
+			}
+			var m:Matrix3D = concatenatedMatrix3D(this._displayObject);
+			var relativeMatrix:Matrix3D = concatenatedMatrix3D(relativeTo);
+			relativeMatrix.invert();
+			m.append(relativeMatrix);
+			return m;
+		}
+
+		// The matrix from the local space of `obj` to the space of the stage, without projection.
+		private static function concatenatedMatrix3D(obj:DisplayObject):Matrix3D {
+			var result:Matrix3D = new Matrix3D();
+			while (obj != null && obj.parent != null) {
+				var local:Matrix3D = obj.transform.matrix3D;
+				if (local == null) {
+					var m:Matrix = obj.transform.matrix;
+					local = new Matrix3D(new <Number>[
+						m.a, m.b, 0, 0,
+						m.c, m.d, 0, 0,
+						0, 0, 1, 0,
+						m.tx, m.ty, 0, 1
+					]);
+				}
+				result.append(local);
+				obj = obj.parent;
+			}
+			return result;
 		}
 	}
 }
@@ -1,13 +1,13 @@
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
-use crate::avm2_stub_getter;
+use crate::display_object::transform_3d::PerspectiveProjection;
 use crate::display_object::TDisplayObject;
-use crate::prelude::{DisplayObject, Matrix, Twips};
+use crate::prelude::{DisplayObject, Matrix, Point, Twips};
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8, Rectangle};
 
-fn get_display_object<'gc>(
+pub fn get_display_object<'gc>(
     this: Object<'gc>,
     activation: &mut Activation<'_, 'gc>,
 ) -> Result<DisplayObject<'gc>, Error<'gc>> {
@@ -68,7 +68,12 @@ pub fn get_matrix<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let matrix = *get_display_object(this, activation)?.base().matrix();
+    let dobj = get_display_object(this, activation)?;
+    // Objects placed in 3D space only have a `matrix3D`.
+    if dobj.transform_3d().is_some() {
+        return Ok(Value::Null);
+    }
+    let matrix = *dobj.base().matrix();
     matrix_to_object(matrix, activation)
 }
 
@@ -80,6 +85,8 @@ pub fn set_matrix<'gc>(
     let matrix = object_to_matrix(args.get_object(activation, 0, "value")?, activation)?;
     let dobj = get_display_object(this, activation)?;
     dobj.set_matrix(activation.context.gc_context, matrix);
+    // Setting a 2D matrix makes the object flat again.
+    dobj.set_transform_3d(activation.context.gc_context, None);
     if let Some(parent) = dobj.parent() {
         // Self-transform changes are automatically handled,
         // we only want to inform ancestors to avoid unnecessary invalidations for tx/ty
@@ -129,14 +136,101 @@ pub fn get_concatenated_matrix<'gc>(
 
 pub fn get_concatenated_color_transform<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let mut color_transform = *dobj.base().color_transform();
+    let mut node = dobj.parent();
+    while let Some(obj) = node {
+        color_transform = *obj.base().color_transform() * color_transform;
+        node = obj.parent();
+    }
+    color_transform_to_object(&color_transform, activation)
+}
+
+pub fn get_is_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(
+    let dobj = get_display_object(this, activation)?;
+    Ok(dobj.transform_3d().is_some().into())
+}
+
+pub fn flatten_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    dobj.set_transform_3d(activation.context.gc_context, None);
+    Ok(Value::Undefined)
+}
+
+/// Copies the perspective projection of the display object into the given
+/// `PerspectiveProjection`, returning false if it has none.
+pub fn read_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let object = args.get_object(activation, 0, "projection")?;
+
+    // The stage and the root of a movie always have a projection, centered on the stage.
+    let is_root = dobj.as_stage().is_some()
+        || dobj
+            .parent()
+            .is_some_and(|parent| parent.as_stage().is_some());
+    let projection = match dobj.perspective_projection() {
+        Some(projection) => projection,
+        None if is_root => {
+            PerspectiveProjection::default_for_stage(activation.context.stage.stage_size())
+        }
+        None => return Ok(false.into()),
+    };
+
+    let center = activation.avm2().classes().point.construct(
         activation,
-        "flash.geom.Transform",
-        "concatenatedColorTransform"
-    );
+        &[
+            projection.projection_center.x.to_pixels().into(),
+            projection.projection_center.y.to_pixels().into(),
+        ],
+    )?;
+    object.set_public_property("fieldOfView", projection.field_of_view.into(), activation)?;
+    object.set_public_property("projectionCenter", center.into(), activation)?;
+    Ok(true.into())
+}
+
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = match args.try_get_object(activation, 0) {
+        Some(object) => {
+            let field_of_view = object
+                .get_public_property("fieldOfView", activation)?
+                .coerce_to_number(activation)?;
+            let center = object
+                .get_public_property("projectionCenter", activation)?
+                .coerce_to_object(activation)?;
+            let x = center
+                .get_public_property("x", activation)?
+                .coerce_to_number(activation)?;
+            let y = center
+                .get_public_property("y", activation)?
+                .coerce_to_number(activation)?;
+            Some(PerspectiveProjection {
+                field_of_view,
+                projection_center: Point::new(Twips::from_pixels(x), Twips::from_pixels(y)),
+            })
+        }
+        None => None,
+    };
+    dobj.set_perspective_projection(activation.context.gc_context, projection);
     Ok(Value::Undefined)
 }
 
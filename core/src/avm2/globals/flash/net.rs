@@ -16,6 +16,8 @@ pub mod responder;
 pub mod shared_object;
 pub mod socket;
 pub mod url_loader;
+pub mod url_stream;
+pub mod url_variables;
 pub mod xml_socket;
 
 fn object_to_index_map<'gc>(
@@ -89,7 +91,7 @@ pub fn navigate_to_url<'gc>(
             let method = request
                 .get_public_property("method", activation)?
                 .coerce_to_string(activation)?;
-            let method = NavigationMethod::from_method_str(&method).unwrap();
+            let method = NavigationMethod::from_air_method_str(&method).unwrap();
             let data: Value<'gc> = request.get_public_property("data", activation)?;
             let (url, vars) = parse_data(activation, &url, &data)?;
             activation.context.navigator.navigate_to_url(
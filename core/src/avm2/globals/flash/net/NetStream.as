@@ -135,19 +135,11 @@ package flash.net {
             stub_setter("flash.net.NetStream", "backBufferTime");
         }
 
-        public function get bufferLength():Number {
-            stub_getter("flash.net.NetStream", "bufferLength");
-            return 0.0;
-        }
+        public native function get bufferLength():Number;
 
-        public function get bufferTime():Number {
-            stub_getter("flash.net.NetStream", "bufferTime");
-            return 0.0;
-        }
+        public native function get bufferTime():Number;
 
-        public function set bufferTime(time:Number) {
-            stub_setter("flash.net.NetStream", "bufferTime");
-        }
+        public native function set bufferTime(time:Number);
 
         public function get bufferTimeMax():Number {
             stub_getter("flash.net.NetStream", "bufferTimeMax");
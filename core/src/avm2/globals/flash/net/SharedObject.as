@@ -5,20 +5,26 @@ package flash.net {
     namespace ruffle = "__ruffle__";
 
     public class SharedObject extends EventDispatcher {
+        public static var defaultObjectEncoding:uint = 3;
+
         public function SharedObject() {
            this.data = {};
         }
 
-        // NOTE: We currently always use AMF3 serialization.
-        // If you implement the `defaultObjectEncoding` or `objectEncoding`,
-        // you will need to adjust the serialization and deserialization code
-        // to work with AMF0.
-
         public static native function getLocal(name:String, localPath:String = null, secure:Boolean = false): SharedObject;
 
         public native function get size() : uint;
-        public native function get objectEncoding() : uint;
-        public native function set objectEncoding(value:uint) : void;
+
+        public function get objectEncoding() : uint {
+            return this.ruffle::_ruffleObjectEncoding;
+        }
+
+        public function set objectEncoding(value:uint) : void {
+            if (value != ObjectEncoding.AMF0 && value != ObjectEncoding.AMF3) {
+                throw new ArgumentError("Error #2008: Parameter objectEncoding must be one of the accepted values.", 2008);
+            }
+            this.ruffle::_ruffleObjectEncoding = value;
+        }
 
         public native function flush(minDiskSpace:int = 0) : String;
         public native function close() : void;
@@ -34,5 +40,9 @@ package flash.net {
         public var data: Object;
 
         ruffle var _ruffleName: String;
+
+        // The encoding the shared object is written with, which is also
+        // the encoding of the `.sol` file it was read from.
+        ruffle var _ruffleObjectEncoding: uint = SharedObject.defaultObjectEncoding;
     }
 }
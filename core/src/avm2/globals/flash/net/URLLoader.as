@@ -7,25 +7,16 @@ package flash.net {
 		public var data: *;
 		public var dataFormat: String = "text";
 
+		// Updated by the loader as data arrives.
+		public var bytesLoaded: uint = 0;
+		public var bytesTotal: uint = 0;
+
 		public function URLLoader(request:URLRequest = null) {
 			if (request != null) {
 				this.load(request);
 			}
 		}
 
-		// FIXME - this should be a normal property for consistency with Flash
-		public function get bytesTotal():uint {
-			if (this.data) {
-				return this.data.length;
-			}
-			return 0;
-		}
-
-		// FIXME - this should be a normal property for consistency with Flash
-		public function get bytesLoaded():uint {
-			// TODO - update this as the download progresses
-			return this.bytesTotal
-		}
 		public native function load(request:URLRequest):void;
 
 		public function close():void {
@@ -1,5 +1,6 @@
 package flash.net {
 
+	import flash.system.Capabilities;
 	import __ruffle__.stub_getter;
 	import __ruffle__.stub_setter;
 
@@ -9,7 +10,7 @@ package flash.net {
 
 		// FIXME - this should be a getter/setter for consistency with Flash
 		public var url:String;
-		private var _contentType: String = "application/x-www-form-urlencoded"; // ignored
+		private var _contentType: String = "application/x-www-form-urlencoded";
 		private var _requestHeaders: Array = []; 
 
 		public var digest:String;
@@ -26,11 +27,24 @@ package flash.net {
 
 		public function set method(value: String):void {
 			// The method can apparently either be all upper or lower case, but not mixed.
-			if (value !== "GET" && value !== "get" && value !== "POST" && value !== "post") {
+			if (value === null || (value !== value.toUpperCase() && value !== value.toLowerCase())) {
+				throw new ArgumentError("Error #2008: Parameter method must be one of the accepted values.", 2008);
+			}
+
+			var method:String = value.toUpperCase();
+			var allowed:Boolean = method === URLRequestMethod.GET || method === URLRequestMethod.POST;
+
+			// AIR also supports methods that Flash Player can only send as a POST
+			// with an `X-HTTP-Method-Override` header.
+			if (Capabilities.playerType === "Desktop") {
+				allowed = allowed || method === URLRequestMethod.PUT || method === URLRequestMethod.DELETE ||
+					method === URLRequestMethod.HEAD || method === URLRequestMethod.OPTIONS;
+			}
+
+			if (!allowed) {
 				throw new ArgumentError("Error #2008: Parameter method must be one of the accepted values.", 2008);
 			}
 
-			// TODO: AIR is supposed to support other methods like PUT or DELETE.
 			this._method = value;
 		}
 
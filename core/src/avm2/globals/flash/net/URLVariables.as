@@ -1,6 +1,4 @@
 package flash.net {
-    import flash.utils.escapeMultiByte;
-    import flash.utils.unescapeMultiByte;
     public dynamic class URLVariables {
         public function URLVariables(str: String = null) {
             if (str) {
@@ -14,9 +12,8 @@ package flash.net {
                 if (splitIndex === -1) {
                     throw new Error("Error #2101: The String passed to URLVariables.decode() must be a URL-encoded query string containing name/value pairs.", 2101);
                 }
-                pair = pair.AS3::replace("+", " ");
-                var prop = unescapeMultiByte(pair.AS3::slice(0, splitIndex));
-                var val = unescapeMultiByte(pair.AS3::slice(splitIndex + 1));
+                var prop = URLVariables.decodeComponent(pair.AS3::slice(0, splitIndex));
+                var val = URLVariables.decodeComponent(pair.AS3::slice(splitIndex + 1));
                 if (this[prop] == null) {
                     this[prop] = val;
                 } else if (this[prop] instanceof Array) {
@@ -31,14 +28,14 @@ package flash.net {
             var acc : String = ""
             var sep :String = ""
             for (p in this) {
-                var pe : String = escapeMultiByte(p);
+                var pe : String = URLVariables.encodeComponent(p);
                 var val = this[p];
                 if (val is Array) {
                     for (i in val) {
                         acc += sep;
                         acc += pe;
                         acc += "=";
-                        acc += escapeMultiByte(val[i]);
+                        acc += URLVariables.encodeComponent(val[i]);
                         sep = "&";
                     }
                     continue;
@@ -46,10 +43,17 @@ package flash.net {
                 acc += sep;
                 acc += pe;
                 acc += "=";
-                acc += escapeMultiByte(val);
+                acc += URLVariables.encodeComponent(val);
                 sep="&";
             }
             return acc;
         }
+
+        // Like `escapeMultiByte`, but doesn't stop at the first null character,
+        // so that binary data survives being sent as a variable.
+        private static native function encodeComponent(value:String):String;
+
+        // Like `unescapeMultiByte`, but also turns every `+` into a space.
+        private static native function decodeComponent(value:String):String;
     }
 }
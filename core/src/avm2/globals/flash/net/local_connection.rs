@@ -8,6 +8,7 @@ use flash_lso::types::{AMFVersion, Value as AmfValue};
 
 pub use crate::avm2::object::local_connection_allocator;
 use crate::local_connection::LocalConnections;
+use crate::sitelock::SitelockCheck;
 
 /// Implements `domain` getter
 pub fn get_domain<'gc>(
@@ -16,12 +17,16 @@ pub fn get_domain<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let movie = &activation.context.swf;
-    let domain = LocalConnections::get_domain(movie.url());
-
-    Ok(Value::String(AvmString::new_utf8(
+    let domain = AvmString::new_utf8(
         activation.context.gc_context,
-        domain,
-    )))
+        LocalConnections::get_domain(movie.url()),
+    );
+    activation
+        .context
+        .sitelock_detector
+        .note_url_read(SitelockCheck::LocalConnectionDomain, &domain);
+
+    Ok(Value::String(domain))
 }
 
 /// Implements `LocalConnection.send`
@@ -65,7 +65,7 @@ pub fn resume<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(ns) = this.as_netstream() {
-        ns.resume(activation.context);
+        ns.resume(activation.context, true);
     }
 
     Ok(Value::Undefined)
@@ -77,7 +77,44 @@ pub fn toggle_pause<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(ns) = this.as_netstream() {
-        ns.toggle_paused(activation.context);
+        ns.toggle_paused(activation.context, true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.buffer_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.buffer_time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let buffer_time = args.get_f64(activation, 0)?;
+        ns.set_buffer_time(activation.context.gc_context, buffer_time);
     }
 
     Ok(Value::Undefined)
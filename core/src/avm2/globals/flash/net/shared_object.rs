@@ -2,26 +2,49 @@
 
 use crate::avm2::error::error;
 use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Error::AvmError;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, Value};
+use crate::avm2_stub_method;
+use crate::shared_object::FlushResult;
 use crate::string::AvmString;
-use crate::{avm2_stub_getter, avm2_stub_method, avm2_stub_setter};
 use flash_lso::types::{AMFVersion, Lso};
 use std::borrow::Cow;
 
+/// The AMF version that the shared object is written with, according to its `objectEncoding`.
+fn object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<AMFVersion, Error<'gc>> {
+    let encoding_name = Multiname::new(
+        activation.avm2().namespaces.__ruffle__,
+        "_ruffleObjectEncoding",
+    );
+    let encoding = this
+        .get_property(&encoding_name, activation)?
+        .coerce_to_u32(activation)?;
+    Ok(if encoding == 0 {
+        AMFVersion::AMF0
+    } else {
+        AMFVersion::AMF3
+    })
+}
+
 fn new_lso<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     name: &str,
     data: Object<'gc>,
 ) -> Result<Lso, Error<'gc>> {
+    let amf_version = object_encoding(activation, this)?;
     let mut elements = Vec::new();
     crate::avm2::amf::recursive_serialize(
         activation,
         data,
         &mut elements,
         None,
-        AMFVersion::AMF3,
+        amf_version,
         &mut Default::default(),
     )?;
     Ok(Lso::new(
@@ -30,7 +53,7 @@ fn new_lso<'gc>(
             .last()
             .map(|e| e.to_string())
             .unwrap_or_else(|| "<unknown>".to_string()),
-        AMFVersion::AMF3,
+        amf_version,
     ))
 }
 
@@ -87,7 +110,11 @@ pub fn get_local<'gc>(
         }
         "localhost"
     } else {
-        movie_url.host_str().unwrap_or_default()
+        let swf_version = activation.context.swf.version();
+        activation
+            .context
+            .system
+            .settings_domain(movie_url.host_str().unwrap_or_default(), swf_version)
     };
 
     let local_path = if let Some(Value::String(local_path)) = args.get(1) {
@@ -166,6 +193,18 @@ pub fn get_local<'gc>(
     if let Some(saved) = activation.context.storage.get(&full_name) {
         if let Ok(lso) = flash_lso::read::Reader::default().parse(&saved) {
             data = crate::avm2::amf::deserialize_lso(activation, &lso)?.into();
+
+            // Keep writing the file in the encoding it was saved with, so that saves
+            // imported from Flash Player stay readable by the movie that made them.
+            let encoding = match lso.header.format_version {
+                AMFVersion::AMF0 => 0,
+                AMFVersion::AMF3 => 3,
+            };
+            let encoding_name = Multiname::new(
+                activation.avm2().namespaces.__ruffle__,
+                "_ruffleObjectEncoding",
+            );
+            this.set_property(&encoding_name, encoding.into(), activation)?;
         }
     }
 
@@ -191,8 +230,10 @@ pub fn get_local<'gc>(
 pub fn flush<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let min_disk_space = args.get_i32(activation, 0)?.max(0) as usize;
+
     let data = this
         .get_public_property("data", activation)?
         .coerce_to_object(activation)?;
@@ -203,23 +244,22 @@ pub fn flush<'gc>(
         .coerce_to_string(activation)?;
     let name = name.to_utf8_lossy();
 
-    let mut lso = new_lso(activation, &name, data)?;
+    let mut lso = new_lso(activation, this, &name, data)?;
     // Flash does not write empty LSOs to disk
     if lso.body.is_empty() {
         Ok("flushed".into())
     } else {
         let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
-        if activation.context.storage.put(&name, &bytes) {
-            Ok("flushed".into())
-        } else {
-            Err(AvmError(error(
+        match crate::shared_object::flush(activation.context, &name, bytes, min_disk_space) {
+            FlushResult::Flushed => Ok("flushed".into()),
+            FlushResult::Pending => Ok("pending".into()),
+            FlushResult::Failed => Err(AvmError(error(
                 activation,
                 "Error #2130: Unable to flush SharedObject.",
                 2130,
-            )?))
+            )?)),
         }
     }
-    // FIXME - We should dispatch a NetStatusEvent after this function returns
 }
 
 pub fn get_size<'gc>(
@@ -237,7 +277,7 @@ pub fn get_size<'gc>(
         .coerce_to_string(activation)?;
     let name = name.to_utf8_lossy();
 
-    let mut lso = new_lso(activation, &name, data)?;
+    let mut lso = new_lso(activation, this, &name, data)?;
     // Flash returns 0 for empty LSOs, but the actual number of bytes (including the header) otherwise
     if lso.body.is_empty() {
         Ok(0.into())
@@ -280,21 +320,3 @@ pub fn clear<'gc>(
 
     Ok(Value::Undefined)
 }
-
-pub fn get_object_encoding<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.net.SharedObject", "objectEncoding");
-    Ok(0.into())
-}
-
-pub fn set_object_encoding<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.net.SharedObject", "objectEncoding");
-    Ok(Value::Undefined)
-}
@@ -41,11 +41,21 @@ pub fn connect<'gc>(
         .try_into()
         .map_err(|_| invalid_port_number(activation))?;
 
+    let host = host.to_utf8_lossy().into_owned();
+    if let Some(movie) = activation.caller_movie() {
+        let UpdateContext {
+            policy_files,
+            navigator,
+            ..
+        } = activation.context;
+        policy_files.grant_socket(*navigator, &movie, &host, port);
+    }
+
     let UpdateContext {
         sockets, navigator, ..
     } = activation.context;
 
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    sockets.connect_avm2(*navigator, socket, host, port);
 
     Ok(Value::Undefined)
 }
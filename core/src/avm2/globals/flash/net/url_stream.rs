@@ -0,0 +1,75 @@
+//! `flash.net.URLStream` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname, Object};
+
+/// Native function definition for `URLStream.beginLoad`
+pub fn begin_load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url_request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, url_request)?;
+    let buffer = buffer(activation, this)?;
+
+    let future = activation.context.load_manager.load_data_into_url_stream(
+        activation.context.player.clone(),
+        this,
+        buffer,
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// The `ByteArray` that a `URLStream` is currently reading from.
+fn buffer<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let namespaces = activation.avm2().namespaces;
+    this.get_property(
+        &Multiname::new(namespaces.flash_net_internal, "_buffer"),
+        activation,
+    )?
+    .as_object()
+    .ok_or_else(|| "URLStream buffer should be a ByteArray".into())
+}
+
+/// Whether `buffer` is still the buffer of an open `URLStream`.
+///
+/// A stream that was closed, or reopened with a new request, no longer
+/// accepts data from its previous load.
+pub fn is_loading_into<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    buffer: Object<'gc>,
+) -> Result<bool, Error<'gc>> {
+    let namespaces = activation.avm2().namespaces;
+    let connected = this
+        .get_property(
+            &Multiname::new(namespaces.flash_net_internal, "_connected"),
+            activation,
+        )?
+        .coerce_to_boolean();
+
+    Ok(connected && Object::ptr_eq(self::buffer(activation, this)?, buffer))
+}
+
+/// Marks a `URLStream` as no longer connected once its load ends.
+pub fn disconnect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let namespaces = activation.avm2().namespaces;
+    this.set_property(
+        &Multiname::new(namespaces.flash_net_internal, "_connected"),
+        false.into(),
+        activation,
+    )
+}
@@ -0,0 +1,35 @@
+//! `flash.net.URLVariables` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Object};
+use crate::form_encoding::{escape_form_value, unescape_form_value};
+use crate::string::AvmString;
+
+/// Implements `URLVariables.encodeComponent`
+pub fn encode_component<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let escaped = escape_form_value(&value.as_wstr().to_utf8_lossy());
+    Ok(AvmString::new_utf8(activation.context.gc_context, escaped).into())
+}
+
+/// Implements `URLVariables.decodeComponent`
+pub fn decode_component<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let unescaped = unescape_form_value(&value.as_wstr().to_utf8_lossy());
+    Ok(AvmString::new_utf8(activation.context.gc_context, unescaped).into())
+}
@@ -9,6 +9,10 @@ pub fn get_os<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(os) = &activation.context.system.spoofed.os {
+        return Ok(AvmString::new_utf8(activation.gc(), os).into());
+    }
+
     let os = match activation.avm2().player_runtime {
         // For most normal Flash Player usage, the OS should not matter,
         // so let's pretend it's Windows for the broadest possible compatibility.
@@ -32,6 +36,10 @@ pub fn get_version<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(version) = &activation.context.system.spoofed.version {
+        return Ok(AvmString::new_utf8(activation.gc(), version).into());
+    }
+
     let os = match activation.avm2().player_runtime {
         PlayerRuntime::FlashPlayer => "WIN",
         PlayerRuntime::AIR => {
@@ -57,6 +65,10 @@ pub fn get_player_type<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(player_type) = &activation.context.system.spoofed.player_type {
+        return Ok(AvmString::new_utf8(activation.gc(), player_type).into());
+    }
+
     // TODO: When should "External" be returned?
     let player_type = if cfg!(target_family = "wasm") {
         "PlugIn"
@@ -77,9 +89,11 @@ pub fn get_screen_resolution_x<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let viewport_dimensions = activation.context.renderer.viewport_dimensions();
-    // Viewport size is adjusted for HiDPI.
-    let adjusted_width = f64::from(viewport_dimensions.width) / viewport_dimensions.scale_factor;
-    Ok(adjusted_width.round().into())
+    let (width, _) = activation
+        .context
+        .system
+        .get_screen_resolution(viewport_dimensions);
+    Ok(width.into())
 }
 
 /// Implements `flash.system.Capabilities.screenResolutionY`
@@ -89,9 +103,11 @@ pub fn get_screen_resolution_y<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let viewport_dimensions = activation.context.renderer.viewport_dimensions();
-    // Viewport size is adjusted for HiDPI.
-    let adjusted_height = f64::from(viewport_dimensions.height) / viewport_dimensions.scale_factor;
-    Ok(adjusted_height.round().into())
+    let (_, height) = activation
+        .context
+        .system
+        .get_screen_resolution(viewport_dimensions);
+    Ok(height.into())
 }
 
 /// Implements `flash.system.Capabilities.pixelAspectRatio`
@@ -106,10 +122,40 @@ pub fn get_pixel_aspect_ratio<'gc>(
 
 /// Implements `flash.system.Capabilities.screenDPI`
 pub fn get_screen_dpi<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.system.screen_dpi().into())
+}
+
+/// Implements `flash.system.Capabilities.language`
+pub fn get_language<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let language = activation
+        .context
+        .system
+        .spoofed
+        .language
+        .as_deref()
+        .unwrap_or("en");
+    Ok(AvmString::new_utf8(activation.context.gc_context, language).into())
+}
+
+/// Implements `flash.system.Capabilities.isDebugger`
+pub fn get_is_debugger<'gc>(
+    activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // source: https://tracker.adobe.com/#/view/FP-3949775
-    Ok(72.into())
+    Ok(activation
+        .context
+        .system
+        .spoofed
+        .is_debugger
+        .unwrap_or(false)
+        .into())
 }
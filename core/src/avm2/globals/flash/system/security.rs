@@ -2,10 +2,13 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::avm2_stub_method;
+use crate::backend::ui::SettingsPanel;
+use crate::policy_file;
 use crate::sandbox::SandboxType;
+use crate::sitelock::SitelockCheck;
 use crate::string::AvmString;
 use url::Url;
 
@@ -39,6 +42,10 @@ pub fn get_sandbox_type<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .sitelock_detector
+        .note_check(SitelockCheck::SandboxType);
     let movie = activation
         .caller_movie()
         .expect("Caller movie expected for sandboxType");
@@ -55,35 +62,89 @@ pub fn get_sandbox_type<'gc>(
 pub fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowDomain");
+    allow_domains(activation, args, false)?;
     Ok(Value::Undefined)
 }
 
 pub fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowInsecureDomain");
+    allow_domains(activation, args, true)?;
     Ok(Value::Undefined)
 }
 
+/// Grants every domain in `domains` permission to script the calling movie.
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    domains: &[Value<'gc>],
+    insecure: bool,
+) -> Result<(), Error<'gc>> {
+    let Some(movie) = activation.caller_movie() else {
+        return Ok(());
+    };
+    for domain in domains {
+        let domain = domain.coerce_to_string(activation)?.to_string();
+        activation
+            .context
+            .domain_permissions
+            .allow_domain(&movie, &domain, insecure);
+    }
+    Ok(())
+}
+
 pub fn load_policy_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+    policy_file::load_policy_file(activation.context, &url);
+    Ok(Value::Undefined)
+}
+
+pub fn get_exact_settings<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "loadPolicyFile");
+    let swf_version = activation.context.swf.version();
+    Ok(activation.context.system.exact_settings(swf_version).into())
+}
+
+pub fn set_exact_settings<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.system.exact_settings = Some(args.get_bool(0));
     Ok(Value::Undefined)
 }
 
 pub fn show_settings<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "showSettings");
+    let panel = args.get_string(activation, 0)?;
+    let panel = if &panel == b"privacy" {
+        SettingsPanel::Privacy
+    } else if &panel == b"localStorage" {
+        SettingsPanel::LocalStorage
+    } else if &panel == b"microphone" {
+        SettingsPanel::Microphone
+    } else if &panel == b"camera" {
+        SettingsPanel::Camera
+    } else if &panel == b"display" {
+        SettingsPanel::Display
+    } else {
+        // "settingsManager" opens a web page in Flash Player, which we have no equivalent for.
+        SettingsPanel::Default
+    };
+
+    activation.context.ui.display_settings_panel(panel);
     Ok(Value::Undefined)
 }
@@ -1223,6 +1223,25 @@ pub fn get_line_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_image_reference<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    else {
+        return Ok(Value::Null);
+    };
+
+    let id = args.get_string(activation, 0)?;
+    Ok(this
+        .image_reference(&id)
+        .map(|image| image.object2())
+        .unwrap_or(Value::Null))
+}
+
 pub fn get_line_offset<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
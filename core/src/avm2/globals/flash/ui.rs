@@ -1,5 +1,8 @@
 //! `flash.ui` namespace
 
 pub mod context_menu;
+pub mod game_input;
+pub mod game_input_control;
+pub mod game_input_device;
 pub mod keyboard;
 pub mod mouse;
@@ -1,18 +1,59 @@
 package flash.ui {
     import flash.events.EventDispatcher;
+    import flash.events.GameInputEvent;
+
+    namespace ruffle = "__ruffle__";
 
     [API("688")]
     public final class GameInput extends EventDispatcher {
-        public static function get isSupported():Boolean {
-            return false;
+        // Every `GameInput` receives the events of every device.
+        private static var _instances:Array = [];
+
+        // The `GameInputDevice` objects that the movie has seen, by device ID.
+        private static var _devices:Object = {};
+
+        public function GameInput() {
+            _instances.push(this);
         }
 
-        public static function get numDevices():int {
-            return 0;
+        public static function get isSupported():Boolean {
+            return true;
         }
 
+        public static native function get numDevices():int;
+
         public static function getDeviceAt(index:int):GameInputDevice {
-            throw new RangeError("Error #1506: The specified range is invalid.", 1506);
+            return getDevice(deviceIdAt(index));
+        }
+
+        private static native function deviceIdAt(index:int):String;
+
+        private static function getDevice(id:String):GameInputDevice {
+            var device:GameInputDevice = _devices[id];
+            if (!device) {
+                device = GameInputDevice.create(id);
+                _devices[id] = device;
+            }
+            return device;
+        }
+
+        // Called by the player when a device was connected, or is about to be disconnected.
+        ruffle static function dispatchDeviceEvent(type:String, id:String):void {
+            var device:GameInputDevice = getDevice(id);
+            if (type == GameInputEvent.DEVICE_REMOVED) {
+                delete _devices[id];
+            }
+            for each (var gameInput:GameInput in _instances) {
+                gameInput.dispatchEvent(new GameInputEvent(type, false, false, device));
+            }
+        }
+
+        // Called by the player when a control of a device changed its value.
+        ruffle static function dispatchControlChange(id:String, index:int):void {
+            var device:GameInputDevice = _devices[id];
+            if (device) {
+                device.controlChanged(index);
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -3,8 +3,37 @@ package flash.ui {
 
     [API("688")]
     public dynamic class GameInputControl extends EventDispatcher {
+        private static var _creating:Boolean = false;
+
+        private var _device:GameInputDevice;
+
+        ruffle var _ruffleIndex:int;
+
         public function GameInputControl() {
-            throw new ArgumentError("Error #2012: GameInputControl$ class cannot be instantiated.", 2012)
+            if (!_creating) {
+                throw new ArgumentError("Error #2012: GameInputControl$ class cannot be instantiated.", 2012);
+            }
+        }
+
+        internal static function create(device:GameInputDevice, index:int):GameInputControl {
+            _creating = true;
+            try {
+                var control:GameInputControl = new GameInputControl();
+            } finally {
+                _creating = false;
+            }
+            control._device = device;
+            control.ruffle::_ruffleIndex = index;
+            return control;
+        }
+
+        public function get device():GameInputDevice {
+            return this._device;
         }
+
+        public native function get id():String;
+        public native function get minValue():Number;
+        public native function get maxValue():Number;
+        public native function get value():Number;
     }
-}
\ No newline at end of file
+}
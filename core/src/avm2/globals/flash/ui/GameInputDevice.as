@@ -1,4 +1,8 @@
 package flash.ui {
+    import flash.events.Event;
+    import flash.utils.ByteArray;
+    import __ruffle__.stub_method;
+
     // The AS3 docs say this is only available in AIR 3.7.
     // That was determined to be a lie.
     [API("688")]
@@ -7,5 +11,73 @@ package flash.ui {
         // control values. If `startCachingSamples` returns samples that
         // require more memory than you specify, it throws a memory error.
         public static const MAX_BUFFER_SIZE:int = 32000;
+
+        private static var _creating:Boolean = false;
+
+        ruffle var _ruffleId:String;
+
+        // The `GameInputControl` objects that the movie has seen, by index.
+        private var _controls:Array = [];
+
+        // Controls only dispatch `change` events while their device is enabled.
+        public var enabled:Boolean = false;
+
+        public var sampleInterval:int = 0;
+
+        public function GameInputDevice() {
+            if (!_creating) {
+                throw new ArgumentError("Error #2012: GameInputDevice$ class cannot be instantiated.", 2012);
+            }
+        }
+
+        internal static function create(id:String):GameInputDevice {
+            _creating = true;
+            try {
+                var device:GameInputDevice = new GameInputDevice();
+            } finally {
+                _creating = false;
+            }
+            device.ruffle::_ruffleId = id;
+            return device;
+        }
+
+        public function get id():String {
+            return this.ruffle::_ruffleId;
+        }
+
+        public native function get name():String;
+        public native function get numControls():int;
+
+        public function getControlAt(index:int):GameInputControl {
+            if (index < 0 || index >= this.numControls) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var control:GameInputControl = this._controls[index];
+            if (!control) {
+                control = GameInputControl.create(this, index);
+                this._controls[index] = control;
+            }
+            return control;
+        }
+
+        internal function controlChanged(index:int):void {
+            var control:GameInputControl = this._controls[index];
+            if (this.enabled && control) {
+                control.dispatchEvent(new Event(Event.CHANGE));
+            }
+        }
+
+        public function getCachedSamples(data:ByteArray, append:Boolean = false):int {
+            stub_method("flash.ui.GameInputDevice", "getCachedSamples");
+            return 0;
+        }
+
+        public function startCachingSamples(numSamples:int, controls:Vector.<String>):void {
+            stub_method("flash.ui.GameInputDevice", "startCachingSamples");
+        }
+
+        public function stopCachingSamples():void {
+            stub_method("flash.ui.GameInputDevice", "stopCachingSamples");
+        }
     }
 }
@@ -0,0 +1,68 @@
+//! `flash.ui.GameInput` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::range_error;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+
+pub fn get_num_devices<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.input.game_input_devices().len().into())
+}
+
+pub fn device_id_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let devices = activation.context.input.game_input_devices();
+    let Some(device) = usize::try_from(index).ok().and_then(|i| devices.get(i)) else {
+        return Err(Error::AvmError(range_error(
+            activation,
+            "Error #1506: The specified range is invalid.",
+            1506,
+        )?));
+    };
+    let id = device.id.clone();
+    Ok(AvmString::new_utf8(activation.context.gc_context, id).into())
+}
+
+/// Announces a device to every `GameInput` object, with a `GameInputEvent` of the given type.
+pub fn dispatch_device_event(context: &mut UpdateContext<'_>, event_type: &'static str, id: &str) {
+    let mut activation = Activation::from_nothing(context);
+    let args = [
+        event_type.into(),
+        AvmString::new_utf8(activation.context.gc_context, id).into(),
+    ];
+    call_game_input(&mut activation, "dispatchDeviceEvent", &args);
+}
+
+/// Lets the `GameInputControl` of a device know that its value changed.
+pub fn dispatch_control_change(context: &mut UpdateContext<'_>, id: &str, control: usize) {
+    let mut activation = Activation::from_nothing(context);
+    let args = [
+        AvmString::new_utf8(activation.context.gc_context, id).into(),
+        control.into(),
+    ];
+    call_game_input(&mut activation, "dispatchControlChange", &args);
+}
+
+fn call_game_input<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: &'static str,
+    args: &[Value<'gc>],
+) {
+    let game_input = activation.avm2().classes().gameinput;
+    let name = Multiname::new(activation.avm2().namespaces.__ruffle__, name);
+    if let Err(e) = game_input.call_property(&name, args, activation) {
+        tracing::error!("Unhandled error dispatching GameInput event: {e:?}");
+    }
+}
@@ -0,0 +1,66 @@
+//! `flash.ui.GameInputControl` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::ui::game_input_device::device;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname};
+use crate::input::GameInputControl;
+use crate::string::AvmString;
+
+/// Looks up the button or axis that a `GameInputControl` object stands for.
+fn control<'a, 'gc>(
+    activation: &'a mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Option<&'a GameInputControl>, Error<'gc>> {
+    let index_name = Multiname::new(activation.avm2().namespaces.__ruffle__, "_ruffleIndex");
+    let index = this
+        .get_property(&index_name, activation)?
+        .coerce_to_i32(activation)?;
+    let device_object = this
+        .get_public_property("device", activation)?
+        .coerce_to_object(activation)?;
+    Ok(device(activation, device_object)?
+        .and_then(|device| device.controls.get(usize::try_from(index).ok()?)))
+}
+
+pub fn get_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = control(activation, this)?.map(|control| control.id.clone()) else {
+        return Ok(Value::Null);
+    };
+    Ok(AvmString::new_utf8(activation.context.gc_context, id).into())
+}
+
+pub fn get_min_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(control(activation, this)?
+        .map_or(0.0, |control| control.min_value)
+        .into())
+}
+
+pub fn get_max_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(control(activation, this)?
+        .map_or(0.0, |control| control.max_value)
+        .into())
+}
+
+pub fn get_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(control(activation, this)?
+        .map_or(0.0, |control| control.value)
+        .into())
+}
@@ -0,0 +1,43 @@
+//! `flash.ui.GameInputDevice` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname};
+use crate::input::GameInputDevice;
+use crate::string::AvmString;
+
+/// Looks up the device that a `GameInputDevice` object stands for.
+///
+/// Returns `None` once the device has been disconnected.
+pub fn device<'a, 'gc>(
+    activation: &'a mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Option<&'a GameInputDevice>, Error<'gc>> {
+    let id_name = Multiname::new(activation.avm2().namespaces.__ruffle__, "_ruffleId");
+    let id = this
+        .get_property(&id_name, activation)?
+        .coerce_to_string(activation)?;
+    let id = id.to_utf8_lossy();
+    Ok(activation.context.input.game_input_device(&id))
+}
+
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(name) = device(activation, this)?.map(|device| device.name.clone()) else {
+        return Ok(Value::Null);
+    };
+    Ok(AvmString::new_utf8(activation.context.gc_context, name).into())
+}
+
+pub fn get_num_controls<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let num_controls = device(activation, this)?.map_or(0, |device| device.controls.len());
+    Ok(num_controls.into())
+}
@@ -5,7 +5,6 @@ use crate::avm2::{Activation, Error, Object, Value};
 use crate::string::AvmString;
 use crate::string::WString;
 use std::fmt::Write;
-use web_time::Instant;
 
 pub mod byte_array;
 pub mod dictionary;
@@ -18,10 +17,7 @@ pub fn get_timer<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok((Instant::now()
-        .duration_since(activation.context.start_time)
-        .as_millis() as u32)
-        .into())
+    Ok((activation.context.time_source.elapsed().as_millis() as u32).into())
 }
 
 /// Implements `flash.utils.setInterval`
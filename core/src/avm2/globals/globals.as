@@ -306,6 +306,8 @@ include "flash/net/URLVariables.as"
 include "flash/net/XMLSocket.as"
 
 include "flash/filesystem/File.as" // File extends FileReference
+include "flash/filesystem/FileMode.as"
+include "flash/filesystem/FileStream.as"
 
 include "flash/net/drm/AuthenticationMethod.as"
 include "flash/net/drm/LoadVoucherSetting.as"
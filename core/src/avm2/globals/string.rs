@@ -168,15 +168,13 @@ fn concat<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let lhs = Value::from(this).coerce_to_string(activation)?;
-
-    let mut ret = lhs;
+    let mut parts = Vec::with_capacity(args.len() + 1);
+    parts.push(Value::from(this).coerce_to_string(activation)?);
     for arg in args {
-        let s = arg.coerce_to_string(activation)?;
-        ret = AvmString::concat(activation.context.gc_context, ret, s);
+        parts.push(arg.coerce_to_string(activation)?);
     }
 
-    Ok(ret.into())
+    Ok(activation.strings().concat_all(&parts).into())
 }
 
 /// Implements `String.fromCharCode`
@@ -204,6 +202,10 @@ fn index_of<'gc>(
         None => return Ok(Value::Integer(-1)),
         Some(s) => s.clone().coerce_to_string(activation)?,
     };
+    activation
+        .context
+        .sitelock_detector
+        .note_comparison(&this, &pattern);
 
     let start_index = match args.get(1) {
         None | Some(Value::Undefined) => 0,
@@ -228,6 +230,10 @@ fn last_index_of<'gc>(
         None => return Ok(Value::Undefined),
         Some(s) => s.clone().coerce_to_string(activation)?,
     };
+    activation
+        .context
+        .sitelock_detector
+        .note_comparison(&this, &pattern);
 
     let start_index = match args.get(1) {
         None | Some(Value::Undefined) => this.len(),
@@ -17,10 +17,10 @@ pub fn trace<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     match args {
-        [] => activation.context.avm_trace(""),
+        [] => activation.avm_trace(""),
         [arg] => {
             let msg = arg.coerce_to_string(activation)?;
-            activation.context.avm_trace(&msg.to_utf8_lossy());
+            activation.avm_trace(&msg.to_utf8_lossy());
         }
         args => {
             let strings = args
@@ -28,7 +28,7 @@ pub fn trace<'gc>(
                 .map(|a| a.coerce_to_string(activation))
                 .collect::<Result<Vec<_>, _>>()?;
             let msg = crate::string::join(&strings, &WStr::from_units(b" "));
-            activation.context.avm_trace(&msg.to_utf8_lossy());
+            activation.avm_trace(&msg.to_utf8_lossy());
         }
     }
 
@@ -25,34 +25,45 @@ pub struct Metadata<'gc> {
 
 impl<'gc> Metadata<'gc> {
     // Converts an AbcMetadata into a Metadata by resolving all the indexes.
+    //
+    // Flash Player never validates metadata, and obfuscators like to fill it
+    // with junk, so entries that can't be resolved are silently dropped.
     pub fn from_abc_index(
         activation: &mut Activation<'_, 'gc>,
         translation_unit: TranslationUnit<'gc>,
         metadata: &[AbcIndex<AbcMetadata>],
-    ) -> Result<Option<Box<[Metadata<'gc>]>>, Error<'gc>> {
+    ) -> Option<Box<[Metadata<'gc>]>> {
         if metadata.is_empty() {
-            return Ok(None);
+            return None;
         }
 
         let abc = translation_unit.abc();
         let mut trait_metadata_list = vec![];
         for single_metadata in metadata.iter() {
             // Lookup the Index<Metadata> to convert it into a Metadata.
-            let single_metadata = abc
-                .metadata
-                .get(single_metadata.0 as usize)
-                .ok_or_else(|| format!("Unknown metadata {}", single_metadata.0))?;
+            let Some(single_metadata) = abc.metadata.get(single_metadata.0 as usize) else {
+                continue;
+            };
 
-            let name =
-                translation_unit.pool_string(single_metadata.name.0, activation.strings())?;
+            let Ok(name) =
+                translation_unit.pool_string(single_metadata.name.0, activation.strings())
+            else {
+                continue;
+            };
 
             let mut current_metadata_items = vec![];
             for metadata_item in single_metadata.items.iter() {
-                let key =
-                    translation_unit.pool_string(metadata_item.key.0, activation.strings())?;
+                let Ok(key) =
+                    translation_unit.pool_string(metadata_item.key.0, activation.strings())
+                else {
+                    continue;
+                };
 
-                let value =
-                    translation_unit.pool_string(metadata_item.value.0, activation.strings())?;
+                let Ok(value) =
+                    translation_unit.pool_string(metadata_item.value.0, activation.strings())
+                else {
+                    continue;
+                };
 
                 let item = MetadataItem {
                     key: key.into(),
@@ -69,7 +80,7 @@ impl<'gc> Metadata<'gc> {
             trait_metadata_list.push(single_metadata_result);
         }
 
-        Ok(Some(trait_metadata_list.into_boxed_slice()))
+        Some(trait_metadata_list.into_boxed_slice())
     }
 
     // Converts the Metadata to an Object of the form used in avmplus:describeTypeJSON().
@@ -0,0 +1,486 @@
+//! Formatting of numbers as strings, as done by `Number`'s `toString`, `toFixed`,
+//! `toExponential` and `toPrecision`, and when coercing numbers to strings.
+//!
+//! Coerced numbers use the fewest digits that read back as the same number, like ECMA-262
+//! specifies. The other methods work on the exact binary value of the number, and mostly round
+//! it like ECMA-262 specifies too, with ties rounded up (so `(0.125).toFixed(2)` is `"0.13"`).
+//! Where Flash Player differs from the spec is noted on each method.
+
+/// The decimal digits of a positive finite number, which is `0.<digits> * 10^point`.
+struct Decimal {
+    /// The significant digits as ASCII, without leading zeros.
+    digits: Vec<u8>,
+
+    /// Where the decimal point goes, relative to the first digit.
+    point: i32,
+}
+
+impl Decimal {
+    /// The fewest digits that read back as `n`.
+    fn shortest(n: f64) -> Self {
+        Self::from_exponential(&format!("{n:e}"))
+    }
+
+    /// All the digits of the exact value of `n`, which every `f64` has few enough of.
+    fn exact(n: f64) -> Self {
+        // The longest exact expansion of an `f64` has 767 significant digits.
+        Self::from_exponential(&format!("{n:.767e}"))
+    }
+
+    fn from_exponential(formatted: &str) -> Self {
+        let (mantissa, exponent) = formatted
+            .split_once('e')
+            .expect("Floats formatted as exponential have an exponent");
+        let exponent: i32 = exponent.parse().expect("Exponents are integers");
+        let mut decimal = Self {
+            digits: mantissa.bytes().filter(u8::is_ascii_digit).collect(),
+            point: exponent + 1,
+        };
+        decimal.trim_zeros();
+        decimal
+    }
+
+    fn trim_zeros(&mut self) {
+        while self.digits.len() > 1 && self.digits.last() == Some(&b'0') {
+            self.digits.pop();
+        }
+    }
+
+    /// Rounds to the first `len` digits, with ties rounded up, and pads with zeros to get
+    /// exactly `len` digits. A `len` of 0 or less leaves no digits, unless rounding up adds one.
+    fn round(mut self, len: i32) -> Self {
+        if len < 0 {
+            self.digits.clear();
+            return self;
+        }
+        let len = len as usize;
+        let round_up = self.digits.get(len).is_some_and(|digit| *digit >= b'5');
+        self.digits.resize(len.max(self.digits.len()), b'0');
+        self.digits.truncate(len);
+        if round_up {
+            match self.digits.iter().rposition(|digit| *digit != b'9') {
+                Some(last) => {
+                    self.digits[last] += 1;
+                    self.digits[last + 1..].fill(b'0');
+                }
+                None => {
+                    // All nines (or no digits at all) carry over to a new leading 1.
+                    self.digits.fill(b'0');
+                    match self.digits.first_mut() {
+                        Some(first) => *first = b'1',
+                        None => self.digits.push(b'1'),
+                    }
+                    self.point += 1;
+                }
+            }
+        }
+        self
+    }
+
+    /// Drops everything after the first `len` digits, and pads with zeros to get exactly
+    /// `len` digits.
+    fn truncate(mut self, len: usize) -> Self {
+        self.digits.resize(len, b'0');
+        self
+    }
+
+    /// Writes the digits with an exponent, like `1.5e+2`.
+    fn write_exponential(&self, out: &mut String) {
+        out.push(self.digits[0] as char);
+        if self.digits.len() > 1 {
+            out.push('.');
+            out.extend(self.digits[1..].iter().map(|digit| *digit as char));
+        }
+        let exponent = self.point - 1;
+        out.push('e');
+        out.push(if exponent < 0 { '-' } else { '+' });
+        out.push_str(&exponent.abs().to_string());
+    }
+
+    /// Writes the digits with a decimal point and no exponent, like `150` or `0.015`.
+    fn write_fixed(&self, out: &mut String) {
+        let point = self.point;
+        if point <= 0 {
+            out.push_str("0.");
+            out.push_str(&"0".repeat(-point as usize));
+            out.extend(self.digits.iter().map(|digit| *digit as char));
+        } else if point as usize >= self.digits.len() {
+            out.extend(self.digits.iter().map(|digit| *digit as char));
+            out.push_str(&"0".repeat(point as usize - self.digits.len()));
+        } else {
+            let (whole, fraction) = self.digits.split_at(point as usize);
+            out.extend(whole.iter().map(|digit| *digit as char));
+            out.push('.');
+            out.extend(fraction.iter().map(|digit| *digit as char));
+        }
+    }
+}
+
+/// Formats NaN and infinities, or returns `None` for finite numbers.
+fn non_finite_to_string(n: f64) -> Option<String> {
+    if n.is_nan() {
+        Some("NaN".to_string())
+    } else if n == f64::INFINITY {
+        Some("Infinity".to_string())
+    } else if n == f64::NEG_INFINITY {
+        Some("-Infinity".to_string())
+    } else {
+        None
+    }
+}
+
+/// Formats a number as when it's coerced to a string, using exponents for numbers of
+/// 1e21 or more and below 1e-6.
+///
+/// Unlike in ECMA-262, Flash Player keeps at most 15 digits of numbers of 1e21 or more,
+/// without rounding them.
+pub fn number_to_string(n: f64) -> String {
+    if let Some(string) = non_finite_to_string(n) {
+        return string;
+    }
+    if n == 0.0 {
+        return "0".to_string();
+    }
+
+    let mut out = String::new();
+    if n < 0.0 {
+        out.push('-');
+    }
+    let mut decimal = Decimal::shortest(n.abs());
+    if decimal.point > 21 {
+        decimal.digits.truncate(15);
+        decimal.trim_zeros();
+        decimal.write_exponential(&mut out);
+    } else if decimal.point < -5 {
+        decimal.write_exponential(&mut out);
+    } else {
+        decimal.write_fixed(&mut out);
+    }
+    out
+}
+
+/// Formats a number with exactly `fraction_digits` digits after the decimal point,
+/// as done by `toFixed`.
+///
+/// Unlike in ECMA-262, Flash Player never uses an exponent here, even for numbers of 1e21
+/// or more. It also stops at the fewest digits that are within half of the gap to the next
+/// number divided by `10^fraction_digits`, and pads with zeros, which only matters for
+/// numbers too large to have a fraction.
+pub fn number_to_fixed(n: f64, fraction_digits: usize) -> String {
+    if let Some(string) = non_finite_to_string(n) {
+        return string;
+    }
+
+    let mut out = String::new();
+    if n < 0.0 {
+        out.push('-');
+    }
+    let decimal = if n == 0.0 {
+        Decimal {
+            digits: vec![],
+            point: 0,
+        }
+    } else {
+        let decimal = Decimal::exact(n.abs());
+        let len = match whole_number_len(&decimal, n.abs(), fraction_digits) {
+            Some(len) => len,
+            None => decimal.point + fraction_digits as i32,
+        };
+        decimal.round(len)
+    };
+
+    // Rounding left at most the digits up to `fraction_digits` after the point.
+    let (whole, fraction) = if decimal.point <= 0 {
+        let mut fraction = vec![b'0'; -decimal.point as usize];
+        fraction.extend(decimal.digits);
+        (vec![b'0'], fraction)
+    } else {
+        let mut whole = decimal.digits;
+        let point = decimal.point as usize;
+        whole.resize(point.max(whole.len()), b'0');
+        let fraction = whole.split_off(point);
+        (whole, fraction)
+    };
+    out.extend(whole.iter().map(|digit| *digit as char));
+    if fraction_digits > 0 {
+        out.push('.');
+        out.extend(
+            fraction
+                .iter()
+                .map(|digit| *digit as char)
+                .chain(std::iter::repeat('0'))
+                .take(fraction_digits),
+        );
+    }
+    out
+}
+
+/// How many digits `toFixed` keeps of a number too large to have a fraction, or `None` for
+/// numbers that can have one, which are rounded after `fraction_digits` digits past the point.
+fn whole_number_len(decimal: &Decimal, n: f64, fraction_digits: usize) -> Option<i32> {
+    if n < 2f64.powi(53) {
+        return None;
+    }
+
+    // Half of the gap to the next number, which is a whole power of two.
+    let exponent = ((n.to_bits() >> 52) & 0x7ff) as i32 - 1075;
+    let margin = format!("{:.0}", 2f64.powi(exponent - 1)).into_bytes();
+
+    let len = decimal.point as usize;
+    let mut digits = decimal.digits.clone();
+    digits.resize(len, b'0');
+    for kept in 1..len {
+        // How far rounding to `kept` digits moves the number.
+        let tail = &digits[kept..];
+        let mut error = if tail[0] >= b'5' {
+            ten_power_minus(tail)
+        } else {
+            tail.to_vec()
+        };
+        error.resize(error.len() + fraction_digits, b'0');
+        if compare_decimal(&error, &margin).is_le() {
+            return Some(kept as i32);
+        }
+    }
+    Some(len as i32)
+}
+
+/// Subtracts `digits` from the power of ten with one more digit, such as `1000 - 995`.
+fn ten_power_minus(digits: &[u8]) -> Vec<u8> {
+    let mut result = vec![b'0'; digits.len()];
+    let mut borrow = 0;
+    for (out, digit) in result.iter_mut().zip(digits).rev() {
+        let difference = 10 - (digit - b'0') - borrow;
+        *out = b'0' + difference % 10;
+        borrow = if difference == 10 { 0 } else { 1 };
+    }
+    result
+}
+
+/// Compares two whole numbers written as decimal digits.
+fn compare_decimal(a: &[u8], b: &[u8]) -> std::cmp::Ordering {
+    let trim = |digits: &[u8]| -> Vec<u8> {
+        let start = digits
+            .iter()
+            .position(|digit| *digit != b'0')
+            .unwrap_or(digits.len());
+        digits[start..].to_vec()
+    };
+    let (a, b) = (trim(a), trim(b));
+    a.len().cmp(&b.len()).then_with(|| a.cmp(&b))
+}
+
+/// Formats a number with one digit before the decimal point, `fraction_digits` digits
+/// after it and an exponent, as done by `toExponential`.
+///
+/// Unlike in ECMA-262, Flash Player truncates the digits instead of rounding them.
+pub fn number_to_exponential(n: f64, fraction_digits: usize) -> String {
+    if let Some(string) = non_finite_to_string(n) {
+        return string;
+    }
+
+    let mut out = String::new();
+    if n < 0.0 {
+        out.push('-');
+    }
+    let decimal = if n == 0.0 {
+        Decimal {
+            digits: vec![b'0'; fraction_digits + 1],
+            point: 1,
+        }
+    } else {
+        Decimal::exact(n.abs()).truncate(fraction_digits + 1)
+    };
+    decimal.write_exponential(&mut out);
+    out
+}
+
+/// Formats a number with `precision` significant digits, as done by `toPrecision`.
+///
+/// Exponents are only used when the digits don't reach the decimal point. Unlike in ECMA-262,
+/// Flash Player doesn't use them for numbers below 1e-6.
+pub fn number_to_precision(n: f64, precision: usize) -> String {
+    if let Some(string) = non_finite_to_string(n) {
+        return string;
+    }
+
+    let mut out = String::new();
+    if n < 0.0 {
+        out.push('-');
+    }
+    let decimal = if n == 0.0 {
+        Decimal {
+            digits: vec![b'0'; precision],
+            point: 1,
+        }
+    } else {
+        Decimal::exact(n.abs()).round(precision as i32)
+    };
+    let exponent = decimal.point - 1;
+    if exponent >= precision as i32 {
+        decimal.write_exponential(&mut out);
+    } else {
+        decimal.write_fixed(&mut out);
+    }
+    out
+}
+
+/// Formats the integer part of a number in the given radix, with lowercase letters for
+/// digits above 9, as done by `toString` with a radix other than 10.
+///
+/// Flash Player drops the fractional part of numbers formatted in other radixes.
+/// Numbers of 2^53 or more are formatted exactly here, while Flash Player only gets their
+/// last digits right in radixes that are powers of two.
+pub fn number_to_radix_string(n: f64, radix: u32) -> String {
+    debug_assert!((2..=36).contains(&radix));
+    if radix == 10 {
+        return number_to_string(n);
+    }
+    if let Some(string) = non_finite_to_string(n) {
+        return string;
+    }
+
+    let whole = n.abs().trunc();
+    if whole == 0.0 {
+        return "0".to_string();
+    }
+
+    // The whole part is `mantissa * 2^exponent`, which is split into 32-bit limbs
+    // (least significant first) to be divided exactly.
+    let bits = whole.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32 - 1075;
+    let mantissa = (bits & ((1 << 52) - 1)) | (1 << 52);
+    let mut limbs: Vec<u32> = if exponent < 0 {
+        // The number is a whole number below 2^53, so the shift drops only zeros.
+        let value = mantissa >> -exponent;
+        vec![value as u32, (value >> 32) as u32]
+    } else {
+        let mut limbs = vec![0; exponent as usize / 32];
+        let shifted = u128::from(mantissa) << (exponent % 32);
+        limbs.extend([
+            shifted as u32,
+            (shifted >> 32) as u32,
+            (shifted >> 64) as u32,
+        ]);
+        limbs
+    };
+
+    let mut digits = Vec::new();
+    while limbs.iter().any(|limb| *limb != 0) {
+        let mut remainder = 0u64;
+        for limb in limbs.iter_mut().rev() {
+            let value = (remainder << 32) | u64::from(*limb);
+            *limb = (value / u64::from(radix)) as u32;
+            remainder = value % u64::from(radix);
+        }
+        digits.push(std::char::from_digit(remainder as u32, radix).expect("Remainders are digits"));
+    }
+
+    if n < 0.0 {
+        digits.push('-');
+    }
+    digits.into_iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coerced_numbers_use_the_shortest_digits() {
+        assert_eq!(number_to_string(0.1 + 0.2), "0.30000000000000004");
+        assert_eq!(number_to_string(1.0 / 3.0), "0.3333333333333333");
+        assert_eq!(number_to_string(-123.0), "-123");
+        assert_eq!(number_to_string(-0.0), "0");
+        assert_eq!(
+            number_to_string(123456789012345680000.0),
+            "123456789012345680000"
+        );
+        assert_eq!(number_to_string(1e21), "1e+21");
+        assert_eq!(
+            number_to_string(1.2315987654321987e21),
+            "1.23159876543219e+21"
+        );
+        assert_eq!(
+            number_to_string(1.2315987654321988e-8),
+            "1.2315987654321988e-8"
+        );
+        assert_eq!(number_to_string(0.000001), "0.000001");
+        assert_eq!(number_to_string(1.5e-7), "1.5e-7");
+        assert_eq!(number_to_string(f64::NAN), "NaN");
+        assert_eq!(number_to_string(f64::NEG_INFINITY), "-Infinity");
+    }
+
+    #[test]
+    fn to_fixed_rounds_ties_up() {
+        assert_eq!(number_to_fixed(0.125, 2), "0.13");
+        assert_eq!(number_to_fixed(2.5, 0), "3");
+        assert_eq!(number_to_fixed(-2.5, 0), "-3");
+        // 1.005 is actually slightly below 1.005, so this isn't a tie.
+        assert_eq!(number_to_fixed(1.005, 2), "1.00");
+        assert_eq!(number_to_fixed(9.995, 2), "9.99");
+        assert_eq!(number_to_fixed(99.996, 2), "100.00");
+        assert_eq!(number_to_fixed(0.0004, 2), "0.00");
+        assert_eq!(number_to_fixed(0.006, 2), "0.01");
+        assert_eq!(number_to_fixed(0.5, 0), "1");
+        assert_eq!(number_to_fixed(0.4, 0), "0");
+        assert_eq!(number_to_fixed(-0.0, 2), "0.00");
+        assert_eq!(number_to_fixed(123.0, 3), "123.000");
+        assert_eq!(number_to_fixed(1.2315e21, 0), "1231500000000000000000");
+        assert_eq!(
+            number_to_fixed(1.2315e21, 5),
+            "1231500000000000131072.00000"
+        );
+        assert_eq!(
+            number_to_fixed(1.2315987654321988e19, 2),
+            "12315987654321987580.00"
+        );
+        assert_eq!(number_to_fixed(f64::INFINITY, 2), "Infinity");
+    }
+
+    #[test]
+    fn to_exponential_truncates() {
+        assert_eq!(number_to_exponential(1.5, 1), "1.5e+0");
+        assert_eq!(number_to_exponential(12.5, 1), "1.2e+1");
+        assert_eq!(number_to_exponential(123456.0, 2), "1.23e+5");
+        assert_eq!(number_to_exponential(123.15, 3), "1.231e+2");
+        assert_eq!(number_to_exponential(0.00015, 0), "1e-4");
+        assert_eq!(number_to_exponential(9.99, 1), "9.9e+0");
+        assert_eq!(number_to_exponential(0.0, 2), "0.00e+0");
+        assert_eq!(number_to_exponential(-5.0, 0), "-5e+0");
+        assert_eq!(number_to_exponential(f64::NEG_INFINITY, 0), "-Infinity");
+    }
+
+    #[test]
+    fn to_precision_rounds_instead_of_truncating() {
+        assert_eq!(number_to_precision(123.456, 4), "123.5");
+        assert_eq!(number_to_precision(123.456, 2), "1.2e+2");
+        assert_eq!(number_to_precision(0.000123, 2), "0.00012");
+        assert_eq!(number_to_precision(0.000000012315, 5), "0.000000012315");
+        assert_eq!(number_to_precision(99.99, 3), "100");
+        assert_eq!(number_to_precision(99.99, 2), "1.0e+2");
+        assert_eq!(number_to_precision(5.0, 3), "5.00");
+        assert_eq!(number_to_precision(0.0, 3), "0.00");
+        assert_eq!(number_to_precision(-1.25, 2), "-1.3");
+    }
+
+    #[test]
+    fn radix_strings_are_exact_and_lowercase() {
+        assert_eq!(number_to_radix_string(255.0, 16), "ff");
+        assert_eq!(number_to_radix_string(-255.0, 16), "-ff");
+        assert_eq!(number_to_radix_string(255.9, 16), "ff");
+        assert_eq!(number_to_radix_string(-0.0, 2), "0");
+        assert_eq!(number_to_radix_string(-0.5, 2), "0");
+        assert_eq!(number_to_radix_string(35.0, 36), "z");
+        assert_eq!(
+            number_to_radix_string(9007199254740993.0, 3),
+            "1121202011211211122211100012101112"
+        );
+        assert_eq!(
+            number_to_radix_string(2f64.powi(100), 16),
+            "10000000000000000000000000"
+        );
+        assert_eq!(number_to_radix_string(4294967295.0, 36), "1z141z3");
+        assert_eq!(number_to_radix_string(f64::NAN, 16), "NaN");
+    }
+}
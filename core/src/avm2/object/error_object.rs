@@ -5,12 +5,11 @@ use crate::avm2::call_stack::CallStack;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Avm2, Error};
 use crate::string::WString;
 use core::fmt;
 use gc_arena::{Collect, Gc, GcWeak};
 use std::fmt::Debug;
-use tracing::{enabled, Level};
 
 /// A class instance allocator that allocates Error objects.
 pub fn error_allocator<'gc>(
@@ -19,7 +18,7 @@ pub fn error_allocator<'gc>(
 ) -> Result<Object<'gc>, Error<'gc>> {
     let base = ScriptObjectData::new(class);
 
-    let call_stack = (enabled!(Level::INFO) || cfg!(feature = "avm_debug"))
+    let call_stack = Avm2::stack_traces_enabled()
         .then(|| activation.avm2().call_stack().borrow().clone())
         .unwrap_or_default();
 
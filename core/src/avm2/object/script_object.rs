@@ -85,6 +85,14 @@ fn maybe_int_property(name: AvmString<'_>) -> DynamicKey<'_> {
     }
 }
 
+fn dynamic_key_to_value<'gc>(key: &DynamicKey<'gc>) -> Value<'gc> {
+    match key {
+        DynamicKey::String(name) => Value::String(*name),
+        DynamicKey::Object(obj) => Value::Object(*obj),
+        DynamicKey::Uint(val) => Value::Number(*val as f64),
+    }
+}
+
 impl<'gc> ScriptObject<'gc> {
     /// Construct an instance with a possibly-none class and proto chain.
     /// NOTE: this is a low-level function.
@@ -372,11 +380,18 @@ impl<'gc> ScriptObjectWrapper<'gc> {
     }
 
     pub fn get_enumerant_name(&self, index: u32) -> Option<Value<'gc>> {
-        self.values().key_at(index as usize).map(|key| match key {
-            DynamicKey::String(name) => Value::String(*name),
-            DynamicKey::Object(obj) => Value::Object(*obj),
-            DynamicKey::Uint(val) => Value::Number(*val as f64),
-        })
+        self.values()
+            .key_at(index as usize)
+            .map(dynamic_key_to_value)
+    }
+
+    /// The names and values of all dynamic properties of this object, enumerable or not.
+    pub fn dynamic_properties(&self) -> Vec<(Value<'gc>, Value<'gc>)> {
+        self.values()
+            .as_hashmap()
+            .iter()
+            .map(|(key, property)| (dynamic_key_to_value(key), property.value))
+            .collect()
     }
 
     pub fn property_is_enumerable(&self, name: AvmString<'gc>) -> bool {
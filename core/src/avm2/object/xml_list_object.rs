@@ -498,7 +498,9 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
     ) -> Option<XmlListObject<'gc>> {
         let mut descendants = Vec::new();
         for child in self.0.children.borrow().iter() {
-            child.node().descendants(multiname, &mut descendants);
+            child
+                .node()
+                .descendants(activation.gc(), multiname, &mut descendants);
         }
 
         // NOTE: The way avmplus implemented this means we do not need to set target_dirty flag.
@@ -82,7 +82,7 @@ impl<'gc> XmlObject<'gc> {
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> XmlListObject<'gc> {
-        let children = if let E4XNodeKind::Element { children, .. } = &*self.node().kind() {
+        if let E4XNodeKind::Element { children, .. } = &*self.node().kind() {
             if let Some(local_name) = name.local_name() {
                 if let Ok(index) = local_name.parse::<usize>() {
                     let children = if let Some(node) = children.get(index) {
@@ -101,15 +101,14 @@ impl<'gc> XmlObject<'gc> {
                     return list;
                 }
             }
+        }
 
-            children
-                .iter()
-                .filter(|node| node.matches_name(name))
-                .map(|node| E4XOrXml::E4X(*node))
-                .collect()
-        } else {
-            Vec::new()
-        };
+        let children = self
+            .node()
+            .matching_children(activation.gc(), name)
+            .into_iter()
+            .map(E4XOrXml::E4X)
+            .collect();
 
         // FIXME: If name is not a number index, then we should call [[Get]] (get_property_local) with the name.
         XmlListObject::new_with_children(
@@ -125,15 +124,13 @@ impl<'gc> XmlObject<'gc> {
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> XmlListObject<'gc> {
-        let children = if let E4XNodeKind::Element { children, .. } = &*self.node().kind() {
-            children
-                .iter()
-                .filter(|node| node.is_element() && node.matches_name(name))
-                .map(|node| E4XOrXml::E4X(*node))
-                .collect()
-        } else {
-            Vec::new()
-        };
+        let children = self
+            .node()
+            .matching_children(activation.gc(), name)
+            .into_iter()
+            .filter(|node| node.is_element())
+            .map(E4XOrXml::E4X)
+            .collect();
 
         let list = XmlListObject::new_with_children(
             activation,
@@ -273,7 +270,10 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
         multiname: &Multiname<'gc>,
     ) -> Option<XmlListObject<'gc>> {
         let mut descendants = Vec::new();
-        self.0.node.get().descendants(multiname, &mut descendants);
+        self.0
+            .node
+            .get()
+            .descendants(activation.gc(), multiname, &mut descendants);
 
         let list = XmlListObject::new_with_children(activation, descendants, None, None);
         // NOTE: avmplus does not set a target property/object here, but if there was at least one child
@@ -307,28 +307,18 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
 
         let name = handle_input_multiname(name.clone(), activation);
 
-        let matched_children = if let E4XNodeKind::Element {
-            children,
-            attributes,
-            ..
-        } = &*self.0.node.get().kind()
-        {
-            let search_children = if name.is_attribute() {
-                attributes
-            } else {
-                children
-            };
-
-            search_children
+        let node = self.0.node.get();
+        let matched_children = if !name.is_attribute() {
+            node.matching_children(activation.gc(), &name)
+                .into_iter()
+                .map(E4XOrXml::E4X)
+                .collect()
+        } else if let E4XNodeKind::Element { attributes, .. } = &*node.kind() {
+            attributes
                 .iter()
-                .filter_map(|child| {
-                    if child.matches_name(&name) {
-                        Some(E4XOrXml::E4X(*child))
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
+                .filter(|attr| attr.matches_name(&name))
+                .map(|attr| E4XOrXml::E4X(*attr))
+                .collect()
         } else {
             Vec::new()
         };
@@ -347,6 +347,172 @@ pub enum Op<'gc> {
 }
 
 impl<'gc> Op<'gc> {
+    /// The name of this opcode, as shown in opcode counts.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Op::Add => "Add",
+            Op::AddI => "AddI",
+            Op::ApplyType { .. } => "ApplyType",
+            Op::AsType { .. } => "AsType",
+            Op::AsTypeLate => "AsTypeLate",
+            Op::BitAnd => "BitAnd",
+            Op::BitNot => "BitNot",
+            Op::BitOr => "BitOr",
+            Op::BitXor => "BitXor",
+            Op::Bkpt => "Bkpt",
+            Op::BkptLine { .. } => "BkptLine",
+            Op::Call { .. } => "Call",
+            Op::CallMethod { .. } => "CallMethod",
+            Op::CallProperty { .. } => "CallProperty",
+            Op::CallPropLex { .. } => "CallPropLex",
+            Op::CallPropVoid { .. } => "CallPropVoid",
+            Op::CallStatic { .. } => "CallStatic",
+            Op::CallSuper { .. } => "CallSuper",
+            Op::CallSuperVoid { .. } => "CallSuperVoid",
+            Op::CheckFilter => "CheckFilter",
+            Op::Coerce { .. } => "Coerce",
+            Op::CoerceSwapPop { .. } => "CoerceSwapPop",
+            Op::CoerceA => "CoerceA",
+            Op::CoerceB => "CoerceB",
+            Op::CoerceD => "CoerceD",
+            Op::CoerceDSwapPop => "CoerceDSwapPop",
+            Op::CoerceI => "CoerceI",
+            Op::CoerceISwapPop => "CoerceISwapPop",
+            Op::CoerceO => "CoerceO",
+            Op::CoerceS => "CoerceS",
+            Op::CoerceU => "CoerceU",
+            Op::CoerceUSwapPop => "CoerceUSwapPop",
+            Op::Construct { .. } => "Construct",
+            Op::ConstructProp { .. } => "ConstructProp",
+            Op::ConstructSuper { .. } => "ConstructSuper",
+            Op::ConvertO => "ConvertO",
+            Op::ConvertS => "ConvertS",
+            Op::Debug { .. } => "Debug",
+            Op::DebugFile { .. } => "DebugFile",
+            Op::DebugLine { .. } => "DebugLine",
+            Op::DecLocal { .. } => "DecLocal",
+            Op::DecLocalI { .. } => "DecLocalI",
+            Op::Decrement => "Decrement",
+            Op::DecrementI => "DecrementI",
+            Op::DeleteProperty { .. } => "DeleteProperty",
+            Op::Divide => "Divide",
+            Op::Dup => "Dup",
+            Op::Dxns { .. } => "Dxns",
+            Op::DxnsLate => "DxnsLate",
+            Op::Equals => "Equals",
+            Op::EscXAttr => "EscXAttr",
+            Op::EscXElem => "EscXElem",
+            Op::FindDef { .. } => "FindDef",
+            Op::FindProperty { .. } => "FindProperty",
+            Op::FindPropStrict { .. } => "FindPropStrict",
+            Op::GetDescendants { .. } => "GetDescendants",
+            Op::GetGlobalScope => "GetGlobalScope",
+            Op::GetGlobalSlot { .. } => "GetGlobalSlot",
+            Op::GetLocal { .. } => "GetLocal",
+            Op::GetOuterScope { .. } => "GetOuterScope",
+            Op::GetProperty { .. } => "GetProperty",
+            Op::GetScopeObject { .. } => "GetScopeObject",
+            Op::GetScriptGlobals { .. } => "GetScriptGlobals",
+            Op::GetSlot { .. } => "GetSlot",
+            Op::GetSuper { .. } => "GetSuper",
+            Op::GreaterEquals => "GreaterEquals",
+            Op::GreaterThan => "GreaterThan",
+            Op::HasNext => "HasNext",
+            Op::HasNext2 { .. } => "HasNext2",
+            Op::IfEq { .. } => "IfEq",
+            Op::IfFalse { .. } => "IfFalse",
+            Op::IfGe { .. } => "IfGe",
+            Op::IfGt { .. } => "IfGt",
+            Op::IfLe { .. } => "IfLe",
+            Op::IfLt { .. } => "IfLt",
+            Op::IfNe { .. } => "IfNe",
+            Op::IfNge { .. } => "IfNge",
+            Op::IfNgt { .. } => "IfNgt",
+            Op::IfNle { .. } => "IfNle",
+            Op::IfNlt { .. } => "IfNlt",
+            Op::IfStrictEq { .. } => "IfStrictEq",
+            Op::IfStrictNe { .. } => "IfStrictNe",
+            Op::IfTrue { .. } => "IfTrue",
+            Op::In => "In",
+            Op::IncLocal { .. } => "IncLocal",
+            Op::IncLocalI { .. } => "IncLocalI",
+            Op::Increment => "Increment",
+            Op::IncrementI => "IncrementI",
+            Op::InitProperty { .. } => "InitProperty",
+            Op::InstanceOf => "InstanceOf",
+            Op::IsType { .. } => "IsType",
+            Op::IsTypeLate => "IsTypeLate",
+            Op::Jump { .. } => "Jump",
+            Op::Kill { .. } => "Kill",
+            Op::LessEquals => "LessEquals",
+            Op::LessThan => "LessThan",
+            Op::Lf32 => "Lf32",
+            Op::Lf64 => "Lf64",
+            Op::Li16 => "Li16",
+            Op::Li32 => "Li32",
+            Op::Li8 => "Li8",
+            Op::LookupSwitch { .. } => "LookupSwitch",
+            Op::LShift => "LShift",
+            Op::Modulo => "Modulo",
+            Op::Multiply => "Multiply",
+            Op::MultiplyI => "MultiplyI",
+            Op::Negate => "Negate",
+            Op::NegateI => "NegateI",
+            Op::NewActivation => "NewActivation",
+            Op::NewArray { .. } => "NewArray",
+            Op::NewCatch { .. } => "NewCatch",
+            Op::NewClass { .. } => "NewClass",
+            Op::NewFunction { .. } => "NewFunction",
+            Op::NewObject { .. } => "NewObject",
+            Op::NextName => "NextName",
+            Op::NextValue => "NextValue",
+            Op::Nop => "Nop",
+            Op::Not => "Not",
+            Op::Pop => "Pop",
+            Op::PopScope => "PopScope",
+            Op::PushByte { .. } => "PushByte",
+            Op::PushDouble { .. } => "PushDouble",
+            Op::PushFalse => "PushFalse",
+            Op::PushInt { .. } => "PushInt",
+            Op::PushNamespace { .. } => "PushNamespace",
+            Op::PushNaN => "PushNaN",
+            Op::PushNull => "PushNull",
+            Op::PushScope => "PushScope",
+            Op::PushShort { .. } => "PushShort",
+            Op::PushString { .. } => "PushString",
+            Op::PushTrue => "PushTrue",
+            Op::PushUint { .. } => "PushUint",
+            Op::PushUndefined => "PushUndefined",
+            Op::PushWith => "PushWith",
+            Op::ReturnValue => "ReturnValue",
+            Op::ReturnValueNoCoerce => "ReturnValueNoCoerce",
+            Op::ReturnVoid => "ReturnVoid",
+            Op::RShift => "RShift",
+            Op::SetGlobalSlot { .. } => "SetGlobalSlot",
+            Op::SetLocal { .. } => "SetLocal",
+            Op::SetProperty { .. } => "SetProperty",
+            Op::SetSlot { .. } => "SetSlot",
+            Op::SetSlotNoCoerce { .. } => "SetSlotNoCoerce",
+            Op::SetSuper { .. } => "SetSuper",
+            Op::Sf32 => "Sf32",
+            Op::Sf64 => "Sf64",
+            Op::Si16 => "Si16",
+            Op::Si32 => "Si32",
+            Op::Si8 => "Si8",
+            Op::StrictEquals => "StrictEquals",
+            Op::Subtract => "Subtract",
+            Op::SubtractI => "SubtractI",
+            Op::Swap => "Swap",
+            Op::Sxi1 => "Sxi1",
+            Op::Sxi16 => "Sxi16",
+            Op::Sxi8 => "Sxi8",
+            Op::Throw => "Throw",
+            Op::TypeOf => "TypeOf",
+            Op::Timestamp => "Timestamp",
+            Op::URShift => "URShift",
+        }
+    }
+
     pub fn is_block_terminating(&self) -> bool {
         matches!(
             self,
@@ -262,6 +262,30 @@ impl<'gc> TranslationUnit<'gc> {
         Ok(script)
     }
 
+    /// Verifies every method with a body in the ABC file, failing on the first VerifyError.
+    ///
+    /// Methods are normally verified when they're first called. This is done when
+    /// strict verification is enabled, to find errors in code that never runs.
+    pub fn verify_methods(self, activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
+        let abc = self.abc();
+        for body in &abc.method_bodies {
+            // This method isn't cached, as we don't know whether it will be loaded as a function.
+            let method = BytecodeMethod::from_method_index(self, body.method, false, activation)?;
+            let method = Gc::new(activation.gc(), method);
+
+            if let Err(e) = crate::avm2::verify::verify_method_early(activation, method) {
+                tracing::warn!(
+                    "Method #{} ({}) failed strict verification",
+                    body.method.0,
+                    method.method_name()
+                );
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Gets a script in the ABC file by index.
     pub fn get_script(&self, index: usize) -> Option<Script<'gc>> {
         self.0.read().scripts.get(index).copied().flatten()
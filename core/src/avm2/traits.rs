@@ -215,7 +215,7 @@ impl<'gc> Trait<'gc> {
                         default_value,
                         unit: Some(unit),
                     },
-                    metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata)?,
+                    metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata),
                 }
             }
             AbcTraitKind::Method { disp_id, method } => Trait {
@@ -225,7 +225,7 @@ impl<'gc> Trait<'gc> {
                     disp_id: *disp_id,
                     method: unit.load_method(*method, false, activation)?,
                 },
-                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata)?,
+                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata),
             },
             AbcTraitKind::Getter { disp_id, method } => Trait {
                 name,
@@ -234,7 +234,7 @@ impl<'gc> Trait<'gc> {
                     disp_id: *disp_id,
                     method: unit.load_method(*method, false, activation)?,
                 },
-                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata)?,
+                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata),
             },
             AbcTraitKind::Setter { disp_id, method } => Trait {
                 name,
@@ -243,7 +243,7 @@ impl<'gc> Trait<'gc> {
                     disp_id: *disp_id,
                     method: unit.load_method(*method, false, activation)?,
                 },
-                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata)?,
+                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata),
             },
             AbcTraitKind::Class { slot_id, class } => Trait {
                 name,
@@ -252,7 +252,7 @@ impl<'gc> Trait<'gc> {
                     slot_id: *slot_id,
                     class: unit.load_class(class.0, activation)?,
                 },
-                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata)?,
+                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata),
             },
             AbcTraitKind::Function { slot_id, function } => Trait {
                 name,
@@ -261,7 +261,7 @@ impl<'gc> Trait<'gc> {
                     slot_id: *slot_id,
                     function: unit.load_method(*function, true, activation)?,
                 },
-                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata)?,
+                metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata),
             },
             AbcTraitKind::Const {
                 slot_id,
@@ -279,7 +279,7 @@ impl<'gc> Trait<'gc> {
                         default_value,
                         unit: Some(unit),
                     },
-                    metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata)?,
+                    metadata: Metadata::from_abc_index(activation, unit, &abc_trait.metadata),
                 }
             }
         })
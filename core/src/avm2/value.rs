@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::error;
 use crate::avm2::error::type_error;
+use crate::avm2::number_format::number_to_string;
 use crate::avm2::object::{NamespaceObject, Object, PrimitiveObject, TObject};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::Error;
@@ -749,32 +750,15 @@ impl<'gc> Value<'gc> {
         })
     }
 
-    /// Minimum number of digits after which numbers are formatted as
-    /// exponential strings.
-    const MIN_DIGITS: f64 = -6.0;
-
-    /// Maximum number of digits before numbers are formatted as exponential
-    /// strings.
-    const MAX_DIGITS: f64 = 21.0;
-
-    /// Maximum number of significant digits renderable within coerced numbers.
-    ///
-    /// Any precision beyond this point will be discarded and replaced with
-    /// zeroes (for whole parts) or not rendered (for decimal parts).
-    const MAX_PRECISION: f64 = 15.0;
-
     /// Coerce the value to a String.
     ///
     /// This function returns the resulting String directly; or a TypeError if
     /// the value is an `Object` that cannot be converted to a primitive value.
     ///
     /// String conversions generally occur according to ECMA-262 3rd Edition's
-    /// ToString algorithm. The conversion of numbers to strings appears to be
-    /// somewhat underspecified; there are several formatting modes which
-    /// change at specific digit count cutoffs, but the spec allows
-    /// implementations to limit how much precision is displayed on coerced
-    /// numbers, even if that precision would result in rounding the whole part
-    /// of the number. (This is confusingly expressed in ECMA-262.)
+    /// ToString algorithm. Numbers are formatted with the fewest digits that
+    /// read back as the same number, with exponents for numbers of 1e21 or
+    /// more and below 1e-6 (see `number_format`).
     ///
     /// TODO: The cutoffs change based on SWF/ABC version. Targeting FP10.3 in
     /// Animate CC 2020 significantly reduces them (towards zero).
@@ -789,32 +773,8 @@ impl<'gc> Value<'gc> {
             Value::Bool(false) => "false".into(),
             Value::Number(n) if n.is_nan() => "NaN".into(),
             Value::Number(n) if *n == 0.0 => "0".into(),
-            Value::Number(n) if *n < 0.0 => AvmString::new_utf8(
-                activation.context.gc_context,
-                format!("-{}", Value::Number(-n).coerce_to_string(activation)?),
-            ),
-            Value::Number(n) if n.is_infinite() => "Infinity".into(),
             Value::Number(n) => {
-                let digits = n.log10().floor();
-
-                // TODO: This needs to limit precision in the resulting decimal
-                // output, not in binary.
-                let precision = (n * 10.0_f64.powf(Self::MAX_PRECISION - digits)).floor()
-                    / 10.0_f64.powf(Self::MAX_PRECISION - digits);
-
-                if digits < Self::MIN_DIGITS || digits >= Self::MAX_DIGITS {
-                    AvmString::new_utf8(
-                        activation.context.gc_context,
-                        format!(
-                            "{}e{}{}",
-                            precision / 10.0_f64.powf(digits),
-                            if digits < 0.0 { "-" } else { "+" },
-                            digits.abs()
-                        ),
-                    )
-                } else {
-                    AvmString::new_utf8(activation.context.gc_context, n.to_string())
-                }
+                AvmString::new_utf8(activation.context.gc_context, number_to_string(*n))
             }
             Value::Integer(i) => {
                 if *i >= 0 && *i < 10 {
@@ -64,14 +64,27 @@ pub enum JumpSource {
     ExceptionTarget,
 }
 
-pub fn verify_method<'gc>(
+/// What [`check_code`] learned about the method it checked.
+struct CheckedCode<'gc> {
+    /// The resolved parameter and return types, which are only known when the method is called.
+    signature: Option<(Vec<ResolvedParamConfig<'gc>>, Option<Class<'gc>>)>,
+    byte_info: Vec<ByteInfo>,
+    seen_exception_indices: HashSet<usize>,
+}
+
+/// Walks the reachable code of a method, checking its ops and jumps.
+///
+/// When `at_call` is false, the method is checked as its ABC file is loaded. Neither
+/// the scope it will run in nor the classes defined by later ABC files are known then,
+/// so `getouterscope` and type references are only checked once it's called.
+fn check_code<'gc>(
     activation: &mut Activation<'_, 'gc>,
     method: Gc<'gc, BytecodeMethod<'gc>>,
-) -> Result<VerifiedMethodInfo<'gc>, Error<'gc>> {
+    at_call: bool,
+) -> Result<CheckedCode<'gc>, Error<'gc>> {
     let body = method
         .body()
         .expect("Cannot verify non-native method without body!");
-    let translation_unit = method.translation_unit();
 
     let param_count = method.method().params.len();
     let max_locals = body.num_locals;
@@ -97,8 +110,14 @@ pub fn verify_method<'gc>(
         )?));
     }
 
-    let resolved_param_config = resolve_param_config(activation, method.signature())?;
-    let resolved_return_type = resolve_return_type(activation, method.return_type)?;
+    let signature = if at_call {
+        Some((
+            resolve_param_config(activation, method.signature())?,
+            resolve_return_type(activation, method.return_type)?,
+        ))
+    } else {
+        None
+    };
 
     let mut seen_exception_indices = HashSet::new();
 
@@ -355,7 +374,7 @@ pub fn verify_method<'gc>(
                 }
 
                 AbcOp::GetOuterScope { index } => {
-                    if activation.outer().get(index as usize).is_none() {
+                    if at_call && activation.outer().get(index as usize).is_none() {
                         return Err(Error::AvmError(verify_error(
                             activation,
                             "Error #1019: Getscopeobject  is out of bounds.",
@@ -378,15 +397,18 @@ pub fn verify_method<'gc>(
                         return Err(make_error_1014(activation, "[]".into()));
                     }
 
-                    activation
-                        .domain()
-                        .get_class(activation.context, &multiname)
-                        .ok_or_else(|| {
-                            make_error_1014(
-                                activation,
-                                multiname.to_qualified_name(activation.context.gc_context),
-                            )
-                        })?;
+                    // Classes from later ABC files can't be looked up until the method is called.
+                    if at_call {
+                        activation
+                            .domain()
+                            .get_class(activation.context, &multiname)
+                            .ok_or_else(|| {
+                                make_error_1014(
+                                    activation,
+                                    multiname.to_qualified_name(activation.context.gc_context),
+                                )
+                            })?;
+                    }
                 }
 
                 _ => {}
@@ -394,6 +416,30 @@ pub fn verify_method<'gc>(
         }
     }
 
+    Ok(CheckedCode {
+        signature,
+        byte_info,
+        seen_exception_indices,
+    })
+}
+
+pub fn verify_method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+) -> Result<VerifiedMethodInfo<'gc>, Error<'gc>> {
+    let CheckedCode {
+        signature,
+        byte_info,
+        seen_exception_indices,
+    } = check_code(activation, method, true)?;
+    let (resolved_param_config, resolved_return_type) =
+        signature.expect("Types are resolved when the method is called");
+
+    let body = method
+        .body()
+        .expect("Cannot verify non-native method without body!");
+    let translation_unit = method.translation_unit();
+
     let mut byte_offset_to_idx = HashMap::new();
     let mut idx_to_byte_offset = Vec::new();
 
@@ -666,6 +712,34 @@ pub fn verify_method<'gc>(
     })
 }
 
+/// Verifies a method when its ABC file is loaded, rather than when it's first called.
+///
+/// This is used by strict verification, and reports the same errors as Flash Player's
+/// verifier for methods that are never called. Unlike [`verify_method`], every exception
+/// handler is checked, even those that no code can reach. The scope the method will run in
+/// and the classes of later ABC files aren't known yet, so `getouterscope` and the types the
+/// method refers to are only checked once it's called.
+pub fn verify_method_early<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+) -> Result<(), Error<'gc>> {
+    check_code(activation, method, false)?;
+
+    let body = method
+        .body()
+        .expect("Cannot verify non-native method without body!");
+    for exception in &body.exceptions {
+        if exception.from_offset > exception.to_offset
+            || exception.target_offset < exception.to_offset
+            || exception.target_offset as usize >= body.code.len()
+        {
+            return Err(make_error_1054(activation));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn resolve_param_config<'gc>(
     activation: &mut Activation<'_, 'gc>,
     param_config: &[ParamConfig<'gc>],
@@ -1,4 +1,5 @@
 pub mod audio;
+pub mod filesystem;
 pub mod log;
 pub mod navigator;
 pub mod storage;
@@ -18,8 +18,10 @@ pub mod swf {
     };
 }
 
+mod effects;
 #[cfg(feature = "audio")]
 mod mixer;
+mod time_stretch;
 #[cfg(feature = "audio")]
 pub use mixer::*;
 
@@ -32,6 +34,9 @@ mod decoders {
     }
 }
 
+pub use effects::AudioEffects;
+pub use time_stretch::FastForwardAudio;
+
 use crate::swf::{CharacterId, SoundInfo};
 use thiserror::Error;
 use web_time::Duration;
@@ -184,6 +189,22 @@ pub trait AudioBackend: Downcast {
     /// Sets the master volume of the audio backend.
     fn set_volume(&mut self, volume: f32);
 
+    /// Returns the effects that are applied to the audio output.
+    fn effects(&self) -> AudioEffects {
+        AudioEffects::NONE
+    }
+
+    /// Sets the effects that are applied to the audio output.
+    ///
+    /// Backends that don't support effects ignore this.
+    fn set_effects(&mut self, _effects: AudioEffects) {}
+
+    /// Sets how many times faster than normal sounds are played, as done while the movie is
+    /// fast-forwarded, and whether they are time-stretched or muted when not at normal speed.
+    ///
+    /// Backends that can't change the speed of sounds ignore this.
+    fn set_playback_speed(&mut self, _speed: f64, _fast_forward_audio: FastForwardAudio) {}
+
     /// Returns the last whole window of output samples.
     fn get_sample_history(&self) -> [[f32; 2]; 1024];
 
@@ -377,6 +398,11 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Whether no sounds are playing.
+    pub fn is_empty(&self) -> bool {
+        self.sounds.is_empty()
+    }
+
     /// Update state of active sounds. Should be called once per frame.
     pub fn update_sounds(context: &mut UpdateContext<'gc>) {
         // We can't use 'context' to construct an event inside the
@@ -403,8 +429,12 @@ impl<'gc> AudioManager<'gc> {
                     .and_then(|sound| context.audio.get_sound_duration(sound))
                     .unwrap_or_default();
                 if let Some(object) = sound.avm1_object {
-                    if let NativeObject::Sound(sound) = object.native() {
-                        sound.set_position(duration.round() as u32);
+                    if let NativeObject::Sound(avm1_sound) = object.native() {
+                        avm1_sound.set_position(duration.round() as u32);
+                        // Forget the instance, unless the sound has been started again since.
+                        if avm1_sound.sound_instance() == Some(sound.instance) {
+                            avm1_sound.set_sound_instance(None);
+                        }
                     }
 
                     // Fire soundComplete event.
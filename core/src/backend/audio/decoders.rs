@@ -1,5 +1,7 @@
 //! Audio decoders.
 
+#[cfg(feature = "aac")]
+mod aac;
 mod adpcm;
 #[cfg(feature = "mp3")]
 mod mp3;
@@ -7,9 +9,11 @@ mod mp3;
 mod nellymoser;
 mod pcm;
 
+#[cfg(feature = "aac")]
+pub use aac::AacDecoder;
 pub use adpcm::AdpcmDecoder;
 #[cfg(feature = "mp3")]
-pub use mp3::{mp3_metadata, Mp3Decoder};
+pub use mp3::{gapless_info, mp3_metadata, GaplessInfo, Mp3Decoder};
 #[cfg(feature = "nellymoser")]
 pub use nellymoser::NellymoserDecoder;
 pub use pcm::PcmDecoder;
@@ -18,6 +22,7 @@ use crate::backend::audio::{SoundStreamInfo, SoundStreamWrapping};
 use crate::buffer::{Slice, SliceCursor, Substream, SubstreamChunksIter};
 use crate::tag_utils::{ControlFlow, SwfSlice};
 use std::io::{Cursor, Read};
+use std::sync::Arc;
 use swf::{AudioCompression, SoundFormat, TagCode};
 use thiserror::Error;
 
@@ -30,6 +35,10 @@ pub enum Error {
     #[error("Couldn't decode ADPCM: {0}")]
     InvalidAdpcm(#[from] adpcm::Error),
 
+    #[cfg(feature = "aac")]
+    #[error("Couldn't decode AAC: {0}")]
+    InvalidAac(#[from] aac::Error),
+
     #[error("Unhandled compression {0:?}")]
     UnhandledCompression(AudioCompression),
 
@@ -47,42 +56,82 @@ pub trait Decoder: Iterator<Item = [i16; 2]> + Send + Sync {
     fn sample_rate(&self) -> u16;
 }
 
+/// The decoders of a codec.
+///
+/// Every codec lives in its own module, and those that need extra dependencies are behind a
+/// feature flag. A codec is supported by listing it in [`CODECS`].
+struct Codec {
+    /// The compression formats that this codec decodes.
+    compressions: &'static [AudioCompression],
+
+    /// Makes a decoder that reads the sound data as it's needed.
+    make_decoder: fn(&SoundFormat, Box<dyn Read + Send + Sync>) -> Result<Box<dyn Decoder>, Error>,
+
+    /// Makes a decoder that can seek through sound data held in memory.
+    make_seekable_decoder:
+        fn(&SoundFormat, Cursor<ArcAsRef>) -> Result<Box<dyn SeekableDecoder>, Error>,
+}
+
+/// The codecs that sounds can be decoded with.
+///
+/// AAC is missing, as it's only streamed from FLVs, which needs its own decoder setup (see
+/// `make_substream_decoder`).
+///
+/// TODO: Speex. No Speex decoder crate is available to us yet; one would be added as its own
+/// module behind a `speex` feature, like Nellymoser. Until then, Speex sounds fail with
+/// `Error::UnhandledCompression`.
+const CODECS: &[Codec] = &[
+    pcm::CODEC,
+    adpcm::CODEC,
+    #[cfg(feature = "mp3")]
+    mp3::CODEC,
+    #[cfg(feature = "nellymoser")]
+    nellymoser::CODEC,
+];
+
+/// Finds the codec that decodes sounds in the given format.
+fn find_codec(format: &SoundFormat) -> Result<&'static Codec, Error> {
+    CODECS
+        .iter()
+        .find(|codec| codec.compressions.contains(&format.compression))
+        .ok_or(Error::UnhandledCompression(format.compression))
+}
+
 /// Instantiate a decoder for the compression that the sound data uses.
 pub fn make_decoder<R: 'static + Read + Send + Sync>(
     format: &SoundFormat,
     data: R,
 ) -> Result<Box<dyn Decoder>, Error> {
-    let decoder: Box<dyn Decoder> = match format.compression {
-        AudioCompression::UncompressedUnknownEndian => {
-            // Cross fingers that it's little endian.
-            tracing::warn!("make_decoder: PCM sound is unknown endian; assuming little endian");
-            Box::new(PcmDecoder::new(
-                data,
-                format.is_stereo,
-                format.sample_rate,
-                format.is_16_bit,
-            ))
-        }
-        AudioCompression::Uncompressed => Box::new(PcmDecoder::new(
-            data,
-            format.is_stereo,
-            format.sample_rate,
-            format.is_16_bit,
-        )),
-        AudioCompression::Adpcm => Box::new(AdpcmDecoder::new(
-            data,
-            format.is_stereo,
-            format.sample_rate,
-        )?),
-        #[cfg(feature = "mp3")]
-        AudioCompression::Mp3 => Box::new(Mp3Decoder::new(data)?),
-        #[cfg(feature = "nellymoser")]
-        AudioCompression::Nellymoser => {
-            Box::new(NellymoserDecoder::new(data, format.sample_rate.into()))
-        }
-        _ => return Err(Error::UnhandledCompression(format.compression)),
-    };
-    Ok(decoder)
+    (find_codec(format)?.make_decoder)(format, Box::new(data))
+}
+
+/// Instantiate a seekable decoder for the compression that the sound data uses.
+///
+/// A seekable decoder is used for:
+///  * "Event" sounds on the timeline with custom start/loop settings
+///  * ActionScript sounds that may have a custom start and loop setting
+pub(super) fn make_seekable_decoder(
+    format: &SoundFormat,
+    data: Cursor<ArcAsRef>,
+) -> Result<Box<dyn SeekableDecoder>, Error> {
+    (find_codec(format)?.make_seekable_decoder)(format, data)
+}
+
+/// A dummy wrapper struct to implement `AsRef<[u8]>` for `Arc<Vec<u8>>`.
+/// Not having this trait causes problems when trying to use `Cursor<Vec<u8>>`.
+pub(super) struct ArcAsRef(pub(super) Arc<[u8]>);
+
+impl AsRef<[u8]> for ArcAsRef {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Default for ArcAsRef {
+    fn default() -> Self {
+        ArcAsRef(Arc::new([]))
+    }
 }
 
 impl<T: Decoder + ?Sized> Decoder for Box<T> {
@@ -496,12 +545,20 @@ pub fn make_substream_decoder(
     stream_info: &SoundStreamInfo,
     data_stream: Substream,
 ) -> Result<Box<dyn Decoder + Send>, Error> {
-    let decoder: Box<dyn Decoder + Send> =
-        if stream_info.stream_format.compression == AudioCompression::Adpcm {
-            Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?)
-        } else {
-            Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?)
-        };
+    let decoder: Box<dyn Decoder + Send> = match stream_info.stream_format.compression {
+        AudioCompression::Adpcm => Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?),
+        #[cfg(feature = "aac")]
+        AudioCompression::Aac => {
+            // The first chunk of an AAC stream is its decoder configuration,
+            // followed by one raw frame per chunk.
+            let empty_buffer = data_stream.buffer().to_empty_slice();
+            let mut tag_reader = SubstreamTagReader::new(stream_info, data_stream);
+            let config = tag_reader.next().unwrap_or(empty_buffer).data().to_vec();
+            let frames = tag_reader.map(|frame| frame.data().to_vec());
+            Box::new(AacDecoder::new(&config, Box::new(frames))?)
+        }
+        _ => Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?),
+    };
     Ok(decoder)
 }
 
@@ -607,3 +664,37 @@ impl Iterator for AdpcmSubstreamDecoder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(compression: AudioCompression) -> SoundFormat {
+        SoundFormat {
+            compression,
+            sample_rate: 44100,
+            is_stereo: false,
+            is_16_bit: true,
+        }
+    }
+
+    #[test]
+    fn decoders_are_made_by_the_codec_of_the_sound() {
+        let data = Arc::from(&[0x01, 0x00, 0xff, 0x7f][..]);
+        let decoder = make_seekable_decoder(
+            &format(AudioCompression::Uncompressed),
+            Cursor::new(ArcAsRef(data)),
+        )
+        .unwrap();
+        assert_eq!(decoder.sample_rate(), 44100);
+        assert_eq!(decoder.collect::<Vec<_>>(), vec![[1, 1], [32767, 32767]]);
+
+        assert!(matches!(
+            make_decoder(
+                &format(AudioCompression::Speex),
+                Cursor::new(Vec::<u8>::new())
+            ),
+            Err(Error::UnhandledCompression(AudioCompression::Speex))
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::backend::audio::decoders::Decoder;
+use symphonia::core::{audio, codecs, errors, formats::Packet};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Couldn't create AAC decoder: {0}")]
+    Decoder(#[from] errors::Error),
+
+    #[error("Invalid AudioSpecificConfig")]
+    InvalidConfig,
+
+    #[error("Unsupported channel configuration {0}")]
+    InvalidChannels(u8),
+
+    #[error("Unsupported sample rate {0}")]
+    InvalidSampleRate(u32),
+}
+
+/// The sample rates addressed by a sampling frequency index.
+const SAMPLE_RATES: [u32; 13] = [
+    96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+
+const OBJECT_TYPE_LC: u8 = 2;
+const OBJECT_TYPE_SBR: u8 = 5;
+const OBJECT_TYPE_PS: u8 = 29;
+
+/// Decodes raw AAC frames, such as the ones found in FLV audio tags.
+///
+/// Unlike MP3, raw AAC frames can't be told apart in a byte stream, so this
+/// decoder reads whole frames, one per item of `frames`.
+///
+/// HE-AAC streams are played back as their AAC-LC core, without the
+/// reconstructed high frequencies.
+pub struct AacDecoder {
+    frames: Box<dyn Iterator<Item = Vec<u8>> + Send + Sync>,
+    decoder: Box<dyn codecs::Decoder>,
+    sample_buf: audio::SampleBuffer<i16>,
+    cur_sample: usize,
+    sample_rate: u16,
+    num_channels: u8,
+}
+
+impl AacDecoder {
+    /// Create a decoder from an `AudioSpecificConfig` (as found in the FLV
+    /// AAC sequence header) and the raw frames that follow it.
+    pub fn new(
+        config: &[u8],
+        frames: Box<dyn Iterator<Item = Vec<u8>> + Send + Sync>,
+    ) -> Result<Self, Error> {
+        let (sample_rate_index, num_channels) = parse_config(config)?;
+        let sample_rate = SAMPLE_RATES[sample_rate_index as usize];
+        let channels = match num_channels {
+            1 => audio::Channels::FRONT_LEFT,
+            2 => audio::Channels::FRONT_LEFT | audio::Channels::FRONT_RIGHT,
+            n => return Err(Error::InvalidChannels(n)),
+        };
+
+        // Symphonia only decodes AAC-LC, so describe the stream as such.
+        let lc_config = ((OBJECT_TYPE_LC as u16) << 11)
+            | ((sample_rate_index as u16) << 7)
+            | ((num_channels as u16) << 3);
+
+        let mut codec_params = codecs::CodecParameters::new();
+        codec_params
+            .for_codec(codecs::CODEC_TYPE_AAC)
+            .with_sample_rate(sample_rate)
+            .with_channels(channels)
+            .with_extra_data(Box::new(lc_config.to_be_bytes()));
+        let decoder = symphonia::default::get_codecs().make(&codec_params, &Default::default())?;
+
+        Ok(Self {
+            frames,
+            decoder,
+            sample_buf: audio::SampleBuffer::new(0, audio::SignalSpec::new(sample_rate, channels)),
+            cur_sample: 0,
+            sample_rate: sample_rate
+                .try_into()
+                .map_err(|_| Error::InvalidSampleRate(sample_rate))?,
+            num_channels,
+        })
+    }
+
+    /// Decode the next frame into `sample_buf`, returning false at the end of
+    /// the stream.
+    fn next_frame(&mut self) -> bool {
+        self.cur_sample = 0;
+        for frame in self.frames.by_ref() {
+            let packet = Packet::new_from_boxed_slice(0, 0, 0, frame.into_boxed_slice());
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    if self.sample_buf.capacity() < decoded.capacity() {
+                        self.sample_buf = audio::SampleBuffer::new(
+                            decoded.capacity() as symphonia::core::units::Duration,
+                            *decoded.spec(),
+                        );
+                    }
+                    self.sample_buf.copy_interleaved_ref(decoded);
+                    return true;
+                }
+                // Decode errors are not fatal.
+                Err(errors::Error::DecodeError(_)) => (),
+                Err(_) => break,
+            }
+        }
+        false
+    }
+}
+
+/// Read the sampling frequency index and channel count from an
+/// `AudioSpecificConfig`, looking through HE-AAC signalling to the AAC-LC
+/// core of the stream.
+fn parse_config(config: &[u8]) -> Result<(u8, u8), Error> {
+    let [first, second, ..] = *config else {
+        return Err(Error::InvalidConfig);
+    };
+
+    let object_type = first >> 3;
+    let sample_rate_index = ((first & 0x07) << 1) | (second >> 7);
+    let num_channels = (second >> 3) & 0x0F;
+
+    if sample_rate_index as usize >= SAMPLE_RATES.len() {
+        return Err(Error::InvalidConfig);
+    }
+
+    match object_type {
+        // Explicit HE-AAC signalling is followed by the extension sample
+        // rate and the core object type; the core runs at the base rate.
+        OBJECT_TYPE_LC | OBJECT_TYPE_SBR | OBJECT_TYPE_PS => {
+            // Parametric stereo is coded as mono in the core.
+            let num_channels = if object_type == OBJECT_TYPE_PS {
+                1
+            } else {
+                num_channels
+            };
+            Ok((sample_rate_index, num_channels))
+        }
+        _ => Err(Error::InvalidConfig),
+    }
+}
+
+impl Iterator for AacDecoder {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cur_sample >= self.sample_buf.len() {
+            if !self.next_frame() {
+                return None;
+            }
+        }
+
+        let sample_buf = self.sample_buf.samples();
+        if self.num_channels == 2 {
+            let samples: [i16; 2] = [sample_buf[self.cur_sample], sample_buf[self.cur_sample + 1]];
+            self.cur_sample += 2;
+            Some(samples)
+        } else {
+            let sample = sample_buf[self.cur_sample];
+            self.cur_sample += 1;
+            Some([sample, sample])
+        }
+    }
+}
+
+impl Decoder for AacDecoder {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        self.num_channels
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_audio_specific_config() {
+        // AAC-LC, 44.1kHz, stereo.
+        assert_eq!(parse_config(&[0x12, 0x10]).unwrap(), (4, 2));
+        // HE-AAC, 24kHz core, stereo, 48kHz extension.
+        assert_eq!(parse_config(&[0x2B, 0x11, 0x88, 0x00]).unwrap(), (6, 2));
+        // HE-AACv2, 24kHz core with parametric stereo.
+        assert_eq!(parse_config(&[0xEB, 0x09, 0x88]).unwrap(), (6, 1));
+        assert!(parse_config(&[0x12]).is_err());
+    }
+}
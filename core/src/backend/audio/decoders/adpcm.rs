@@ -1,8 +1,27 @@
-use super::{Decoder, SeekableDecoder};
+use super::{Codec, Decoder, SeekableDecoder};
 use bitstream_io::{BigEndian, BitRead, BitReader};
 use std::io::{Cursor, Read};
+use swf::AudioCompression;
 use thiserror::Error;
 
+pub(super) const CODEC: Codec = Codec {
+    compressions: &[AudioCompression::Adpcm],
+    make_decoder: |format, data| {
+        Ok(Box::new(AdpcmDecoder::new(
+            data,
+            format.is_stereo,
+            format.sample_rate,
+        )?))
+    },
+    make_seekable_decoder: |format, data| {
+        Ok(Box::new(AdpcmDecoder::new(
+            data,
+            format.is_stereo,
+            format.sample_rate,
+        )?))
+    },
+};
+
 const INDEX_TABLE: [&[i16]; 4] = [
     &[-1, 2],
     &[-1, -1, 2, 4],
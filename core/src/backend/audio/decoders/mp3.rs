@@ -1,5 +1,6 @@
-use crate::backend::audio::decoders::{Decoder, Mp3Metadata, SeekableDecoder};
+use crate::backend::audio::decoders::{Codec, Decoder, Mp3Metadata, SeekableDecoder};
 use std::io::{Cursor, Read};
+use swf::AudioCompression;
 use symphonia::{
     core::{
         self, audio, codecs, errors,
@@ -10,6 +11,12 @@ use symphonia::{
 };
 use thiserror::Error;
 
+pub(super) const CODEC: Codec = Codec {
+    compressions: &[AudioCompression::Mp3],
+    make_decoder: |_format, data| Ok(Box::new(Mp3Decoder::new(data)?)),
+    make_seekable_decoder: |_format, data| Ok(Box::new(Mp3Decoder::new_seekable(data)?)),
+};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Couldn't decode MP3 frame: {0}")]
@@ -199,3 +206,134 @@ pub fn mp3_metadata(data: &std::sync::Arc<[u8]>) -> Result<Mp3Metadata, Error> {
         sample_rate,
     })
 }
+
+/// The encoder delay and padding of an MP3, as stored in its LAME tag.
+///
+/// Encoders add silence at both ends of the audio, so that it fills whole MP3 frames.
+/// Trimming it lets MP3s that were authored as seamless loops play without gaps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GaplessInfo {
+    /// The number of sample frames of silence that the encoder added at the start.
+    pub encoder_delay: u32,
+
+    /// The number of sample frames of silence that the encoder added at the end.
+    pub padding: u32,
+}
+
+impl GaplessInfo {
+    /// The decoder itself delays the audio by this many sample frames.
+    pub const DECODER_DELAY: u32 = 529;
+
+    /// The number of decoded sample frames to skip to reach the start of the audio.
+    pub fn skip_sample_frames(&self) -> u32 {
+        self.encoder_delay + Self::DECODER_DELAY
+    }
+
+    /// The number of sample frames of audio, out of `total` decoded ones.
+    pub fn num_sample_frames(&self, total: u32) -> u32 {
+        total.saturating_sub(self.encoder_delay + self.padding)
+    }
+}
+
+/// Reads the encoder delay and padding from the LAME tag in the first frame of an MP3,
+/// which is written by LAME and FFmpeg.
+pub fn gapless_info(data: &[u8]) -> Option<GaplessInfo> {
+    let mut data = data;
+    // Skip over an ID3v2 tag.
+    if let [b'I', b'D', b'3', _, _, flags, size @ ..] = data {
+        let size = size
+            .get(..4)?
+            .iter()
+            .fold(0, |size, byte| (size << 7) | usize::from(byte & 0x7f));
+        let footer = if flags & 0x10 != 0 { 10 } else { 0 };
+        data = data.get(10 + size + footer..)?;
+    }
+
+    // The Xing/Info tag comes after the frame header and the side information.
+    let &[0xff, version_layer, _, mode, ..] = data else {
+        return None;
+    };
+    if version_layer & 0xe0 != 0xe0 || (version_layer >> 1) & 0x3 != 1 {
+        // Not a Layer III frame.
+        return None;
+    }
+    let is_mpeg1 = (version_layer >> 3) & 0x3 == 3;
+    let is_mono = mode >> 6 == 3;
+    let side_info_len = match (is_mpeg1, is_mono) {
+        (true, false) => 32,
+        (true, true) | (false, false) => 17,
+        (false, true) => 9,
+    };
+    let xing = data.get(4 + side_info_len..)?;
+    if !xing.starts_with(b"Xing") && !xing.starts_with(b"Info") {
+        return None;
+    }
+
+    // The LAME tag follows the optional fields of the Xing tag.
+    let flags = u32::from_be_bytes(xing.get(4..8)?.try_into().ok()?);
+    let lame_offset = 8
+        + if flags & 0x1 != 0 { 4 } else { 0 }
+        + if flags & 0x2 != 0 { 4 } else { 0 }
+        + if flags & 0x4 != 0 { 100 } else { 0 }
+        + if flags & 0x8 != 0 { 4 } else { 0 };
+    let lame = xing.get(lame_offset..lame_offset + 24)?;
+    if !lame.starts_with(b"LAME") && !lame.starts_with(b"Lavc") && !lame.starts_with(b"Lavf") {
+        return None;
+    }
+
+    // 12 bits of delay followed by 12 bits of padding.
+    let delay_padding = &lame[21..24];
+    Some(GaplessInfo {
+        encoder_delay: (u32::from(delay_padding[0]) << 4) | (u32::from(delay_padding[1]) >> 4),
+        padding: (u32::from(delay_padding[1] & 0xf) << 8) | u32::from(delay_padding[2]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the first frame of a stereo MPEG-1 MP3 with a Xing tag that has the frame count,
+    /// followed by a LAME tag.
+    fn info_frame(encoder: &[u8; 4], delay_padding: [u8; 3]) -> Vec<u8> {
+        let mut frame = vec![0xff, 0xfb, 0x90, 0x00];
+        frame.extend([0; 32]);
+        frame.extend(b"Info");
+        frame.extend(1u32.to_be_bytes());
+        frame.extend(1000u32.to_be_bytes());
+        frame.extend(encoder);
+        frame.extend([0; 17]);
+        frame.extend(delay_padding);
+        frame.extend([0; 12]);
+        frame
+    }
+
+    #[test]
+    fn reads_lame_tag() {
+        // 576 sample frames of delay and 1260 of padding.
+        let frame = info_frame(b"LAME", [0x24, 0x04, 0xec]);
+        let info = gapless_info(&frame).unwrap();
+        assert_eq!(
+            info,
+            GaplessInfo {
+                encoder_delay: 576,
+                padding: 1260
+            }
+        );
+        assert_eq!(info.skip_sample_frames(), 1105);
+        assert_eq!(info.num_sample_frames(1152 * 1000), 1152 * 1000 - 1836);
+
+        let mut tagged = b"ID3\x04\x00\x00\x00\x00\x00\x05".to_vec();
+        tagged.extend([0; 5]);
+        tagged.extend(&frame);
+        assert_eq!(gapless_info(&tagged), Some(info));
+    }
+
+    #[test]
+    fn ignores_mp3s_without_lame_tag() {
+        assert_eq!(gapless_info(&info_frame(b"XXXX", [0x24, 0x04, 0xec])), None);
+        assert_eq!(gapless_info(&[0xff, 0xfb, 0x90, 0x00, 0x00]), None);
+        assert_eq!(gapless_info(b"ID3"), None);
+        assert_eq!(gapless_info(&[]), None);
+    }
+}
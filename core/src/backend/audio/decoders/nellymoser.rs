@@ -1,5 +1,38 @@
-use super::{Decoder, SeekableDecoder};
+use super::{Codec, Decoder, SeekableDecoder};
 use std::io::{Cursor, Read};
+use swf::{AudioCompression, SoundFormat};
+
+pub(super) const CODEC: Codec = Codec {
+    compressions: &[
+        AudioCompression::Nellymoser,
+        AudioCompression::Nellymoser8Khz,
+        AudioCompression::Nellymoser16Khz,
+    ],
+    make_decoder: |format, data| {
+        Ok(Box::new(NellymoserDecoder::new(
+            data,
+            nellymoser_sample_rate(format),
+        )))
+    },
+    make_seekable_decoder: |format, data| {
+        Ok(Box::new(NellymoserDecoder::new(
+            data,
+            nellymoser_sample_rate(format),
+        )))
+    },
+};
+
+/// The sample rate that Nellymoser audio in the given format plays at.
+///
+/// The 8kHz and 16kHz variants (mostly found in FLV voice recordings) have a
+/// fixed sample rate that the rate flags of the sound can't express.
+fn nellymoser_sample_rate(format: &SoundFormat) -> u32 {
+    match format.compression {
+        AudioCompression::Nellymoser8Khz => 8000,
+        AudioCompression::Nellymoser16Khz => 16000,
+        _ => format.sample_rate.into(),
+    }
+}
 
 pub struct NellymoserDecoder<R: Read> {
     decoder: nellymoser_rs::Decoder<R>,
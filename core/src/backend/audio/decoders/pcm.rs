@@ -1,6 +1,16 @@
-use super::{Decoder, SeekableDecoder};
+use super::{Codec, Decoder, SeekableDecoder};
 use byteorder::{LittleEndian, ReadBytesExt};
 use std::io::{Cursor, Read};
+use swf::{AudioCompression, SoundFormat};
+
+pub(super) const CODEC: Codec = Codec {
+    compressions: &[
+        AudioCompression::Uncompressed,
+        AudioCompression::UncompressedUnknownEndian,
+    ],
+    make_decoder: |format, data| Ok(Box::new(PcmDecoder::with_format(format, data))),
+    make_seekable_decoder: |format, data| Ok(Box::new(PcmDecoder::with_format(format, data))),
+};
 
 /// Decoder for PCM audio data in a Flash file.
 /// Flash exports this when you use the "Raw" compression setting.
@@ -22,6 +32,19 @@ impl<R: Read> PcmDecoder<R> {
         }
     }
 
+    fn with_format(format: &SoundFormat, inner: R) -> Self {
+        if format.compression == AudioCompression::UncompressedUnknownEndian {
+            // Cross fingers that it's little endian.
+            tracing::warn!("make_decoder: PCM sound is unknown endian; assuming little endian");
+        }
+        Self::new(
+            inner,
+            format.is_stereo,
+            format.sample_rate,
+            format.is_16_bit,
+        )
+    }
+
     #[inline]
     fn read_sample(&mut self) -> Option<i16> {
         let sample = if self.is_16_bit {
@@ -0,0 +1,355 @@
+//! An optional effects bus for the mixed audio output.
+//!
+//! Flash Player plays sounds exactly as they were authored, and so does Ruffle by default.
+//! Many old movies only have 11 or 22kHz audio though, which some users prefer to hear
+//! with a little equalization and reverb on top.
+
+#[cfg(feature = "audio")]
+use std::f32::consts::{PI, SQRT_2};
+
+/// The settings of the effects bus.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioEffects {
+    /// The gain of the low shelf equalizer band, in decibels.
+    pub bass_gain: f32,
+
+    /// The gain of the high shelf equalizer band, in decibels.
+    pub treble_gain: f32,
+
+    /// How much reverb is mixed into the output, from 0.0 (none) to 1.0.
+    pub reverb_mix: f32,
+
+    /// The size of the room that the reverb simulates, from 0.0 to 1.0.
+    pub reverb_room_size: f32,
+}
+
+impl AudioEffects {
+    /// No effects at all, which is the default.
+    pub const NONE: Self = Self {
+        bass_gain: 0.0,
+        treble_gain: 0.0,
+        reverb_mix: 0.0,
+        reverb_room_size: 0.5,
+    };
+
+    /// A subtle preset that livens up low quality audio.
+    pub const ENHANCED: Self = Self {
+        bass_gain: 3.0,
+        treble_gain: 4.0,
+        reverb_mix: 0.12,
+        reverb_room_size: 0.4,
+    };
+
+    /// Whether these settings change the audio at all.
+    pub fn is_enabled(&self) -> bool {
+        self.bass_gain != 0.0 || self.treble_gain != 0.0 || self.reverb_mix > 0.0
+    }
+}
+
+impl Default for AudioEffects {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Applies `AudioEffects` to stereo sample frames.
+#[cfg(feature = "audio")]
+pub(crate) struct EffectsBus {
+    effects: AudioEffects,
+    sample_rate: u32,
+    bass: [Biquad; 2],
+    treble: [Biquad; 2],
+    reverb: [Reverb; 2],
+}
+
+#[cfg(feature = "audio")]
+impl EffectsBus {
+    /// The corner frequency of the bass band, in Hz.
+    const BASS_FREQUENCY: f32 = 150.0;
+
+    /// The corner frequency of the treble band, in Hz.
+    const TREBLE_FREQUENCY: f32 = 6000.0;
+
+    pub fn new(sample_rate: u32) -> Self {
+        let mut bus = Self {
+            effects: AudioEffects::NONE,
+            sample_rate,
+            bass: Default::default(),
+            treble: Default::default(),
+            reverb: [
+                Reverb::new(sample_rate, 0),
+                Reverb::new(sample_rate, Reverb::STEREO_SPREAD),
+            ],
+        };
+        bus.set_effects(AudioEffects::NONE);
+        bus
+    }
+
+    pub fn effects(&self) -> AudioEffects {
+        self.effects
+    }
+
+    pub fn set_effects(&mut self, effects: AudioEffects) {
+        let sample_rate = self.sample_rate as f32;
+        // Keep the treble band below the Nyquist frequency of low output rates.
+        let treble_frequency = Self::TREBLE_FREQUENCY.min(sample_rate * 0.4);
+        for channel in 0..2 {
+            self.bass[channel] =
+                Biquad::low_shelf(Self::BASS_FREQUENCY, effects.bass_gain, sample_rate);
+            self.treble[channel] =
+                Biquad::high_shelf(treble_frequency, effects.treble_gain, sample_rate);
+            self.reverb[channel].set_room_size(effects.reverb_room_size);
+        }
+        self.effects = effects;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.effects.is_enabled()
+    }
+
+    pub fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        let mut out = frame;
+        for (channel, sample) in out.iter_mut().enumerate() {
+            let mut value = *sample;
+            if self.effects.bass_gain != 0.0 {
+                value = self.bass[channel].process(value);
+            }
+            if self.effects.treble_gain != 0.0 {
+                value = self.treble[channel].process(value);
+            }
+            if self.effects.reverb_mix > 0.0 {
+                value += self.reverb[channel].process(value) * self.effects.reverb_mix;
+            }
+            *sample = value.clamp(-1.0, 1.0);
+        }
+        out
+    }
+}
+
+/// A second-order IIR filter, used for the shelf bands of the equalizer.
+///
+/// The coefficients follow the "Audio EQ Cookbook" by Robert Bristow-Johnson.
+#[cfg(feature = "audio")]
+#[derive(Clone, Copy, Default)]
+struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+#[cfg(feature = "audio")]
+impl Biquad {
+    fn low_shelf(frequency: f32, gain: f32, sample_rate: f32) -> Self {
+        let (a, cos, alpha) = Self::shelf_parameters(frequency, gain, sample_rate);
+        let sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+        Self::new(
+            a * ((a + 1.0) - (a - 1.0) * cos + sqrt_a_alpha),
+            2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+            a * ((a + 1.0) - (a - 1.0) * cos - sqrt_a_alpha),
+            (a + 1.0) + (a - 1.0) * cos + sqrt_a_alpha,
+            -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+            (a + 1.0) + (a - 1.0) * cos - sqrt_a_alpha,
+        )
+    }
+
+    fn high_shelf(frequency: f32, gain: f32, sample_rate: f32) -> Self {
+        let (a, cos, alpha) = Self::shelf_parameters(frequency, gain, sample_rate);
+        let sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+        Self::new(
+            a * ((a + 1.0) + (a - 1.0) * cos + sqrt_a_alpha),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+            a * ((a + 1.0) + (a - 1.0) * cos - sqrt_a_alpha),
+            (a + 1.0) - (a - 1.0) * cos + sqrt_a_alpha,
+            2.0 * ((a - 1.0) - (a + 1.0) * cos),
+            (a + 1.0) - (a - 1.0) * cos - sqrt_a_alpha,
+        )
+    }
+
+    /// Returns the amplitude, the cosine of the angular frequency and the alpha of a shelf
+    /// filter with a slope of 1.
+    fn shelf_parameters(frequency: f32, gain: f32, sample_rate: f32) -> (f32, f32, f32) {
+        let a = 10f32.powf(gain / 40.0);
+        let w0 = 2.0 * PI * frequency / sample_rate;
+        (a, w0.cos(), w0.sin() / 2.0 * SQRT_2)
+    }
+
+    fn new(b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) -> Self {
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+            ..Default::default()
+        }
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// A small Schroeder reverb: parallel damped comb filters followed by allpass filters,
+/// with the delay lengths of Freeverb.
+#[cfg(feature = "audio")]
+struct Reverb {
+    combs: Vec<Comb>,
+    allpasses: Vec<Allpass>,
+}
+
+#[cfg(feature = "audio")]
+impl Reverb {
+    /// The delay lengths at 44.1kHz.
+    const COMB_LENGTHS: [usize; 4] = [1116, 1188, 1277, 1356];
+    const ALLPASS_LENGTHS: [usize; 2] = [556, 441];
+
+    /// How many samples longer the delays of the right channel are, to widen the stereo image.
+    const STEREO_SPREAD: usize = 23;
+
+    const INPUT_GAIN: f32 = 0.03;
+    const DAMPING: f32 = 0.2;
+
+    fn new(sample_rate: u32, spread: usize) -> Self {
+        let scale = |length: usize| {
+            (((length + spread) as u64 * u64::from(sample_rate)) / 44100).max(1) as usize
+        };
+        Self {
+            combs: Self::COMB_LENGTHS
+                .iter()
+                .map(|&length| Comb::new(scale(length)))
+                .collect(),
+            allpasses: Self::ALLPASS_LENGTHS
+                .iter()
+                .map(|&length| Allpass::new(scale(length)))
+                .collect(),
+        }
+    }
+
+    fn set_room_size(&mut self, room_size: f32) {
+        let feedback = 0.7 + 0.28 * room_size.clamp(0.0, 1.0);
+        for comb in &mut self.combs {
+            comb.feedback = feedback;
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let input = input * Self::INPUT_GAIN;
+        let mut out = self.combs.iter_mut().map(|comb| comb.process(input)).sum();
+        for allpass in &mut self.allpasses {
+            out = allpass.process(out);
+        }
+        out
+    }
+}
+
+#[cfg(feature = "audio")]
+struct Comb {
+    buffer: Vec<f32>,
+    pos: usize,
+    feedback: f32,
+    filter_state: f32,
+}
+
+#[cfg(feature = "audio")]
+impl Comb {
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length],
+            pos: 0,
+            feedback: 0.0,
+            filter_state: 0.0,
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let out = self.buffer[self.pos];
+        self.filter_state = out * (1.0 - Reverb::DAMPING) + self.filter_state * Reverb::DAMPING;
+        self.buffer[self.pos] = input + self.filter_state * self.feedback;
+        self.pos = (self.pos + 1) % self.buffer.len();
+        out
+    }
+}
+
+#[cfg(feature = "audio")]
+struct Allpass {
+    buffer: Vec<f32>,
+    pos: usize,
+}
+
+#[cfg(feature = "audio")]
+impl Allpass {
+    const FEEDBACK: f32 = 0.5;
+
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length],
+            pos: 0,
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let delayed = self.buffer[self.pos];
+        self.buffer[self.pos] = input + delayed * Self::FEEDBACK;
+        self.pos = (self.pos + 1) % self.buffer.len();
+        delayed - input
+    }
+}
+
+#[cfg(all(test, feature = "audio"))]
+mod tests {
+    use super::*;
+
+    /// Returns the peak amplitude of a sine wave after it went through the effects bus.
+    fn peak_of_sine(effects: AudioEffects, frequency: f32) -> f32 {
+        const SAMPLE_RATE: u32 = 44100;
+        let mut bus = EffectsBus::new(SAMPLE_RATE);
+        bus.set_effects(effects);
+        (0..SAMPLE_RATE)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                let sample = (2.0 * PI * frequency * t).sin() * 0.25;
+                bus.process([sample, sample])[0]
+            })
+            // Let the filters settle first.
+            .skip(SAMPLE_RATE as usize / 2)
+            .fold(0.0, |peak: f32, sample| peak.max(sample.abs()))
+    }
+
+    #[test]
+    fn no_effects_by_default() {
+        assert!(!AudioEffects::default().is_enabled());
+        assert!(AudioEffects::ENHANCED.is_enabled());
+        assert!((peak_of_sine(AudioEffects::NONE, 440.0) - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn shelves_boost_their_band() {
+        let bass = AudioEffects {
+            bass_gain: 6.0,
+            ..AudioEffects::NONE
+        };
+        // A 6dB boost doubles the amplitude.
+        assert!((peak_of_sine(bass, 40.0) - 0.5).abs() < 0.05);
+        assert!((peak_of_sine(bass, 10000.0) - 0.25).abs() < 0.02);
+
+        let treble = AudioEffects {
+            treble_gain: 6.0,
+            ..AudioEffects::NONE
+        };
+        assert!((peak_of_sine(treble, 40.0) - 0.25).abs() < 0.02);
+        assert!(peak_of_sine(treble, 15000.0) > 0.4);
+    }
+}
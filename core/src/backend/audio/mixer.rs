@@ -1,4 +1,7 @@
-use super::decoders::{self, AdpcmDecoder, Decoder, PcmDecoder, SeekableDecoder};
+use super::decoders::{self, ArcAsRef, Decoder, SeekableDecoder};
+use super::effects::EffectsBus;
+use super::time_stretch::{FastForwardAudio, PlaybackSpeed, TimeStretcher};
+use super::AudioEffects;
 use super::{SoundHandle, SoundInstanceHandle, SoundStreamInfo, SoundTransform};
 use crate::backend::audio::{DecodeError, RegisterError};
 use crate::buffer::Substream;
@@ -62,6 +65,12 @@ pub struct AudioMixer {
     /// The master volume of the audio from [0.0, 1.0].
     volume: Arc<RwLock<f32>>,
 
+    /// The effects applied to the mixed output.
+    effects: Arc<Mutex<EffectsBus>>,
+
+    /// How fast sounds are played, and how they keep their pitch when played faster.
+    playback_speed: Arc<RwLock<PlaybackSpeed>>,
+
     /// The number of channels in the output stream. Must be 1 or 2.
     num_output_channels: u8,
 
@@ -159,6 +168,26 @@ struct Sound {
     /// `skip_sample_frames` indicates how many sample frames to skip to bypass the delay.
     /// This is `0` unless `format.compression` is `AudioCompression::Mp3`.
     skip_sample_frames: u16,
+
+    /// The number of sample frames that ActionScript reports as the length of this sound.
+    ///
+    /// This differs from `num_sample_frames` for MP3s that have gapless playback metadata,
+    /// as Flash Player counts the encoder delay and padding too.
+    length_sample_frames: u32,
+}
+
+impl Sound {
+    /// Trims the encoder delay and padding of an MP3 according to its LAME tag, if it has one.
+    #[cfg(feature = "mp3")]
+    fn with_gapless_info(mut self) -> Self {
+        if let Some(info) = decoders::gapless_info(&self.data) {
+            if let Ok(skip_sample_frames) = info.skip_sample_frames().try_into() {
+                self.skip_sample_frames = skip_sample_frames;
+                self.num_sample_frames = info.num_sample_frames(self.num_sample_frames);
+            }
+        }
+        self
+    }
 }
 
 /// An actively playing instance of a sound.
@@ -195,6 +224,15 @@ struct SoundInstance {
     /// (respectively) of this sound over the buffer currently being
     /// mixed. Used to compute `peak`, and is reset after every time.
     range: ([f32; 2], [f32; 2]),
+
+    /// The remaining and total number of output frames over which a stopped sound fades out.
+    fade_out: Option<(u32, u32)>,
+
+    /// Time-stretches this sound while it's played faster or slower than normal.
+    stretcher: Option<TimeStretcher>,
+
+    /// The fraction of a frame that this sound fell short of skipping while muted.
+    muted_skip_remainder: f64,
 }
 
 impl SoundInstance {
@@ -208,6 +246,9 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]),
+            fade_out: None,
+            stretcher: None,
+            muted_skip_remainder: 0.0,
         }
     }
 
@@ -223,9 +264,53 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]),
+            fade_out: None,
+            stretcher: None,
+            muted_skip_remainder: 0.0,
+        }
+    }
+
+    /// Returns the next sample frame of this sound, played at the given speed.
+    ///
+    /// Frames buffered by the time-stretcher are dropped when going back to the normal speed.
+    fn next_frame(&mut self, playback_speed: PlaybackSpeed) -> [i16; 2] {
+        if playback_speed.is_normal() {
+            self.stretcher = None;
+            return self.stream.next();
+        }
+        match playback_speed.fast_forward_audio {
+            FastForwardAudio::PreservePitch => {
+                let stream = &mut self.stream;
+                self.stretcher
+                    .get_or_insert_with(TimeStretcher::new)
+                    .next(playback_speed.speed, || {
+                        (!stream.is_exhausted()).then(|| stream.next())
+                    })
+            }
+            FastForwardAudio::Mute => {
+                self.stretcher = None;
+                let skip = playback_speed.speed + self.muted_skip_remainder;
+                self.muted_skip_remainder = skip.fract();
+                for _ in 0..skip as usize {
+                    if self.stream.is_exhausted() {
+                        break;
+                    }
+                    self.stream.next();
+                }
+                [0, 0]
+            }
         }
     }
 
+    /// Whether this sound has played completely.
+    fn is_exhausted(&self) -> bool {
+        self.stream.is_exhausted()
+            && self
+                .stretcher
+                .as_ref()
+                .map_or(true, |stretcher| stretcher.is_drained())
+    }
+
     /// Updates `peak` from `range`, and resets the latter to default.
     fn update_peak(&mut self) {
         self.peak[0] = (self.range.1[0] - self.range.0[0]) / 2.0;
@@ -236,12 +321,17 @@ impl SoundInstance {
 }
 
 impl AudioMixer {
+    /// Stopped sounds fade out over 1/200th of a second.
+    const FADE_OUT_DIVISOR: u32 = 200;
+
     /// Creates a new `AudioMixer` with the given number of channels and sample rate.
     pub fn new(num_output_channels: u8, output_sample_rate: u32) -> Self {
         Self {
             sounds: SlotMap::with_key(),
             sound_instances: Arc::new(Mutex::new(SlotMap::with_key())),
             volume: Arc::new(RwLock::new(1.0)),
+            effects: Arc::new(Mutex::new(EffectsBus::new(output_sample_rate))),
+            playback_speed: Arc::new(RwLock::new(PlaybackSpeed::NORMAL)),
             num_output_channels,
             output_sample_rate,
             output_memory: Arc::new(RwLock::new(CircBuf::new())),
@@ -253,6 +343,8 @@ impl AudioMixer {
         AudioMixerProxy {
             sound_instances: Arc::clone(&self.sound_instances),
             volume: Arc::clone(&self.volume),
+            effects: Arc::clone(&self.effects),
+            playback_speed: Arc::clone(&self.playback_speed),
             num_output_channels: self.num_output_channels,
             output_memory: Arc::clone(&self.output_memory),
         }
@@ -268,13 +360,19 @@ impl AudioMixer {
             + Default
             + dasp::Sample<Signed = T>
             + dasp::sample::ToSample<f32>
-            + dasp::sample::FromSample<i16>,
+            + dasp::sample::FromSample<i16>
+            + dasp::sample::FromSample<f32>,
     {
         let mut sound_instances = self
             .sound_instances
             .lock()
             .expect("Cannot be called reentrant");
         let volume = *self.volume.read().expect("Cannot be called reentrant");
+        let mut effects = self.effects.lock().expect("Cannot be called reentrant");
+        let playback_speed = *self
+            .playback_speed
+            .read()
+            .expect("Cannot be called reentrant");
         let mut output_memory = self
             .output_memory
             .write()
@@ -282,55 +380,14 @@ impl AudioMixer {
         Self::mix_audio::<T>(
             &mut sound_instances,
             volume,
+            &mut effects,
+            playback_speed,
             self.num_output_channels,
             output_buffer,
             &mut output_memory,
         );
     }
 
-    /// Instantiate a seekable decoder for audio data with the given format.
-    ///
-    /// A seekable decoder is used for:
-    ///  * "Event" sounds on the timeline with custom start/loop settings
-    ///  * ActionScript sounds that may have a custom start and loop setting
-    fn make_seekable_decoder(
-        format: &swf::SoundFormat,
-        data: Cursor<ArcAsRef>,
-    ) -> Result<Box<dyn SeekableDecoder>, decoders::Error> {
-        let decoder: Box<dyn SeekableDecoder> = match format.compression {
-            AudioCompression::UncompressedUnknownEndian => {
-                // Cross fingers that it's little endian.
-                tracing::warn!("make_decoder: PCM sound is unknown endian; assuming little endian");
-                Box::new(PcmDecoder::new(
-                    data,
-                    format.is_stereo,
-                    format.sample_rate,
-                    format.is_16_bit,
-                ))
-            }
-            AudioCompression::Uncompressed => Box::new(PcmDecoder::new(
-                data,
-                format.is_stereo,
-                format.sample_rate,
-                format.is_16_bit,
-            )),
-            AudioCompression::Adpcm => Box::new(AdpcmDecoder::new(
-                data,
-                format.is_stereo,
-                format.sample_rate,
-            )?),
-            #[cfg(feature = "mp3")]
-            AudioCompression::Mp3 => Box::new(decoders::Mp3Decoder::new_seekable(data)?),
-            #[cfg(feature = "nellymoser")]
-            AudioCompression::Nellymoser => Box::new(decoders::NellymoserDecoder::new(
-                data,
-                format.sample_rate.into(),
-            )),
-            _ => return Err(decoders::Error::UnhandledCompression(format.compression)),
-        };
-        Ok(decoder)
-    }
-
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
     fn make_resampler(&self, mut stream: impl Stream) -> impl Stream {
         // TODO: Allow interpolator to be user-configurable?
@@ -357,7 +414,7 @@ impl AudioMixer {
         data: Cursor<ArcAsRef>,
     ) -> Result<Box<dyn Stream>, DecodeError> {
         // Instantiate a decoder for the compression that the sound data uses.
-        let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+        let decoder = decoders::make_seekable_decoder(&sound.format, data)?;
 
         // Wrap the decoder into an event sound stream (controls looping/envelope)
         let stream = EventSoundStream::new_with_settings(
@@ -430,6 +487,8 @@ impl AudioMixer {
     fn mix_audio<'a, T>(
         sound_instances: &mut SlotMap<SoundInstanceHandle, SoundInstance>,
         volume: f32,
+        effects: &mut EffectsBus,
+        playback_speed: PlaybackSpeed,
         num_channels: u8,
         mut output_buffer: &mut [T],
         output_memory: &mut CircBuf,
@@ -438,7 +497,8 @@ impl AudioMixer {
             + Default
             + dasp::Sample<Signed = T>
             + dasp::sample::ToSample<f32>
-            + dasp::sample::FromSample<i16>,
+            + dasp::sample::FromSample<i16>
+            + dasp::sample::FromSample<f32>,
     {
         use dasp::{
             frame::{Frame, Stereo},
@@ -456,8 +516,8 @@ impl AudioMixer {
         {
             let mut output_frame = Stereo::<T::Signed>::EQUILIBRIUM;
             for (_, sound) in sound_instances.iter_mut() {
-                if sound.active && !sound.stream.is_exhausted() {
-                    let sound_frame = sound.stream.next();
+                if sound.active && !sound.is_exhausted() {
+                    let sound_frame = sound.next_frame(playback_speed);
                     let [left_0, left_1] = sound_frame.mul_amp(sound.left_transform);
                     let [right_0, right_1] = sound_frame.mul_amp(sound.right_transform);
                     let mut sound_frame: Stereo<T> = [
@@ -466,6 +526,15 @@ impl AudioMixer {
                     ];
                     sound_frame = sound_frame.scale_amp(volume);
 
+                    if let Some((remaining, total)) = &mut sound.fade_out {
+                        let fade = *remaining as f32 / *total as f32;
+                        sound_frame = sound_frame.scale_amp(fade.to_sample());
+                        *remaining = remaining.saturating_sub(1);
+                        if *remaining == 0 {
+                            sound.active = false;
+                        }
+                    }
+
                     sound.range.0[0] = sound.range.0[0].min(sound_frame[0].to_sample());
                     sound.range.0[1] = sound.range.0[1].min(sound_frame[1].to_sample());
 
@@ -478,6 +547,12 @@ impl AudioMixer {
                 }
             }
 
+            if effects.is_enabled() {
+                let frame =
+                    effects.process([output_frame[0].to_sample(), output_frame[1].to_sample()]);
+                output_frame = [frame[0].to_sample(), frame[1].to_sample()];
+            }
+
             output_memory.push([output_frame[0].to_sample(), output_frame[1].to_sample()]);
 
             if output_memory.pos == 0 || output_memory.pos == 1024 {
@@ -522,6 +597,16 @@ impl AudioMixer {
             data: Arc::from(data),
             num_sample_frames: swf_sound.num_samples,
             skip_sample_frames,
+            length_sample_frames: swf_sound.num_samples,
+        };
+        // MP3s embedded as-is, rather than encoded by the authoring tool, may have a LAME tag
+        // instead of a latency seek.
+        #[cfg(feature = "mp3")]
+        let sound = if sound.format.compression == AudioCompression::Mp3 && skip_sample_frames == 0
+        {
+            sound.with_gapless_info()
+        } else {
+            sound
         };
         Ok(self.sounds.insert(sound))
     }
@@ -542,8 +627,9 @@ impl AudioMixer {
             data,
             num_sample_frames: metadata.num_sample_frames,
             skip_sample_frames: 0,
+            length_sample_frames: metadata.num_sample_frames,
         };
-        Ok(self.sounds.insert(sound))
+        Ok(self.sounds.insert(sound.with_gapless_info()))
     }
 
     #[cfg(not(feature = "mp3"))]
@@ -623,12 +709,23 @@ impl AudioMixer {
     }
 
     /// Stops a playing sound instance.
+    /// Stops a sound instance.
+    ///
+    /// Cutting a sound off mid-waveform pops audibly, which is especially noticeable when
+    /// a looping timeline restarts its stream sound. Instead, the sound keeps playing for a
+    /// few milliseconds under a new handle while it fades out.
     pub fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self
             .sound_instances
             .lock()
             .expect("Cannot be called reentrant");
-        sound_instances.remove(sound);
+        if let Some(mut instance) = sound_instances.remove(sound) {
+            if instance.active && instance.fade_out.is_none() {
+                let fade_out_frames = (self.output_sample_rate / Self::FADE_OUT_DIVISOR).max(1);
+                instance.fade_out = Some((fade_out_frames, fade_out_frames));
+                sound_instances.insert(instance);
+            }
+        }
     }
 
     pub fn stop_all_sounds(&mut self) {
@@ -669,7 +766,7 @@ impl AudioMixer {
     pub fn get_sound_duration(&self, sound: SoundHandle) -> Option<f64> {
         if let Some(sound) = self.sounds.get(sound) {
             // AS duration does not subtract `skip_sample_frames`.
-            let num_sample_frames: f64 = sound.num_sample_frames.into();
+            let num_sample_frames: f64 = sound.length_sample_frames.into();
             let sample_rate: f64 = sound.format.sample_rate.into();
             let ms = num_sample_frames * 1000.0 / sample_rate;
             Some(ms)
@@ -709,6 +806,30 @@ impl AudioMixer {
     pub fn set_volume(&mut self, volume: f32) {
         *self.volume.write().expect("Cannot be called reentrant") = volume
     }
+
+    pub fn effects(&self) -> AudioEffects {
+        self.effects
+            .lock()
+            .expect("Cannot be called reentrant")
+            .effects()
+    }
+
+    pub fn set_effects(&mut self, effects: AudioEffects) {
+        self.effects
+            .lock()
+            .expect("Cannot be called reentrant")
+            .set_effects(effects)
+    }
+
+    pub fn set_playback_speed(&mut self, speed: f64, fast_forward_audio: FastForwardAudio) {
+        *self
+            .playback_speed
+            .write()
+            .expect("Cannot be called reentrant") = PlaybackSpeed {
+            speed,
+            fast_forward_audio,
+        };
+    }
 }
 
 /// A thread-safe proxy to the main `AudioMixer`, allowing for mixing audio from a different thread.
@@ -722,6 +843,12 @@ pub struct AudioMixerProxy {
     /// The master volume of the audio from [0.0, 1.0].
     volume: Arc<RwLock<f32>>,
 
+    /// The effects applied to the mixed output.
+    effects: Arc<Mutex<EffectsBus>>,
+
+    /// How fast sounds are played, and how they keep their pitch when played faster.
+    playback_speed: Arc<RwLock<PlaybackSpeed>>,
+
     /// The number of channels in the output stream. Must be 1 or 2.
     num_output_channels: u8,
 
@@ -739,13 +866,19 @@ impl AudioMixerProxy {
             + Default
             + dasp::Sample<Signed = T>
             + dasp::sample::ToSample<f32>
-            + dasp::sample::FromSample<i16>,
+            + dasp::sample::FromSample<i16>
+            + dasp::sample::FromSample<f32>,
     {
         let mut sound_instances = self
             .sound_instances
             .lock()
             .expect("Cannot be called reentrant");
         let volume = *self.volume.read().expect("Cannot be called reentrant");
+        let mut effects = self.effects.lock().expect("Cannot be called reentrant");
+        let playback_speed = *self
+            .playback_speed
+            .read()
+            .expect("Cannot be called reentrant");
         let mut output_memory = self
             .output_memory
             .write()
@@ -753,6 +886,8 @@ impl AudioMixerProxy {
         AudioMixer::mix_audio::<T>(
             &mut sound_instances,
             volume,
+            &mut effects,
+            playback_speed,
             self.num_output_channels,
             output_buffer,
             &mut output_memory,
@@ -760,35 +895,35 @@ impl AudioMixerProxy {
     }
 }
 
-/// A dummy wrapper struct to implement `AsRef<[u8]>` for `Arc<Vec<u8>>`.
-/// Not having this trait causes problems when trying to use `Cursor<Vec<u8>>`.
-struct ArcAsRef(Arc<[u8]>);
-
-impl AsRef<[u8]> for ArcAsRef {
-    #[inline]
-    fn as_ref(&self) -> &[u8] {
-        &self.0
-    }
-}
-
-impl Default for ArcAsRef {
-    fn default() -> Self {
-        ArcAsRef(Arc::new([]))
-    }
-}
-
 /// A stream for event sound instances with custom envelopes, start/end point, or loop settings.
 struct EventSoundStream {
     decoder: Box<dyn SeekableDecoder>,
     num_loops: u16,
     start_sample_frame: u32,
-    end_sample_frame: Option<u32>,
+    /// The sample frame at which each loop ends, exclusive.
+    end_sample_frame: u32,
     cur_sample_frame: u32,
     skip_sample_frames: u32,
+    loop_source: LoopSource,
     is_exhausted: bool,
 }
 
+/// Where the sample frames of the current loop come from.
+///
+/// Seeking the decoder back to the start point isn't sample-accurate for every format
+/// (MP3 frames depend on the ones before them), which makes loops click. So the first
+/// loop is recorded, and later loops replay exactly the same sample frames.
+enum LoopSource {
+    Decoder,
+    Recording(Vec<[i16; 2]>),
+    Replaying(Vec<[i16; 2]>),
+}
+
 impl EventSoundStream {
+    /// Loops longer than this many sample frames are decoded again each time,
+    /// rather than kept in memory.
+    const MAX_RECORDED_LOOP_FRAMES: u32 = 44100 * 30;
+
     fn new_with_settings(
         decoder: Box<dyn SeekableDecoder>,
         settings: &swf::SoundInfo,
@@ -800,9 +935,10 @@ impl EventSoundStream {
         let start_sample_frame = (f64::from(settings.in_sample.unwrap_or(0)) / sample_divisor)
             as u32
             + skip_sample_frames;
+        // The out point is the last sample frame that gets played.
         let end_sample_frame = settings
             .out_sample
-            .map(|n| (f64::from(n) / sample_divisor) as u32)
+            .map(|n| (f64::from(n) / sample_divisor) as u32 + 1)
             .unwrap_or(num_sample_frames)
             + skip_sample_frames;
 
@@ -810,21 +946,32 @@ impl EventSoundStream {
             decoder,
             num_loops: settings.num_loops,
             start_sample_frame,
-            end_sample_frame: Some(end_sample_frame),
+            end_sample_frame,
             cur_sample_frame: start_sample_frame,
             skip_sample_frames,
+            loop_source: LoopSource::Decoder,
             is_exhausted: false,
         };
         stream.next_loop();
+
+        let loop_len = end_sample_frame.saturating_sub(start_sample_frame);
+        if stream.num_loops > 0 && loop_len <= Self::MAX_RECORDED_LOOP_FRAMES {
+            stream.loop_source = LoopSource::Recording(Vec::with_capacity(loop_len as usize));
+        }
         stream
     }
 
-    /// Resets the decoder to the start point of the loop.
+    /// Resets the stream to the start point of the loop.
     fn next_loop(&mut self) {
         if self.num_loops > 0 {
             self.num_loops -= 1;
-            self.decoder.seek_to_sample_frame(self.start_sample_frame);
             self.cur_sample_frame = self.start_sample_frame;
+            match std::mem::replace(&mut self.loop_source, LoopSource::Decoder) {
+                LoopSource::Recording(frames) | LoopSource::Replaying(frames) => {
+                    self.loop_source = LoopSource::Replaying(frames);
+                }
+                LoopSource::Decoder => self.decoder.seek_to_sample_frame(self.start_sample_frame),
+            }
         } else {
             self.is_exhausted = true;
         }
@@ -837,22 +984,27 @@ impl dasp::signal::Signal for EventSoundStream {
     #[inline]
     fn next(&mut self) -> Self::Frame {
         // Loop the sound if necessary, and get the next frame.
-        if !self.is_exhausted {
-            if let Some(frame) = self.decoder.next() {
-                self.cur_sample_frame += 1;
-                if let Some(end) = self.end_sample_frame {
-                    if self.cur_sample_frame > end {
-                        self.next_loop();
+        while !self.is_exhausted {
+            if self.cur_sample_frame < self.end_sample_frame {
+                let frame = match &mut self.loop_source {
+                    LoopSource::Decoder => self.decoder.next(),
+                    LoopSource::Recording(frames) => {
+                        let frame = self.decoder.next();
+                        frames.extend(frame);
+                        frame
                     }
+                    LoopSource::Replaying(frames) => frames
+                        .get((self.cur_sample_frame - self.start_sample_frame) as usize)
+                        .copied(),
+                };
+                if let Some(frame) = frame {
+                    self.cur_sample_frame += 1;
+                    return frame;
                 }
-                frame
-            } else {
-                self.next_loop();
-                self.next()
             }
-        } else {
-            [0, 0]
+            self.next_loop();
         }
+        [0, 0]
     }
 
     #[inline]
@@ -1159,6 +1311,25 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.set_volume(volume)
         }
 
+        #[inline]
+        fn effects(&self) -> $crate::backend::audio::AudioEffects {
+            self.$mixer.effects()
+        }
+
+        #[inline]
+        fn set_effects(&mut self, effects: $crate::backend::audio::AudioEffects) {
+            self.$mixer.set_effects(effects)
+        }
+
+        #[inline]
+        fn set_playback_speed(
+            &mut self,
+            speed: f64,
+            fast_forward_audio: $crate::backend::audio::FastForwardAudio,
+        ) {
+            self.$mixer.set_playback_speed(speed, fast_forward_audio)
+        }
+
         fn get_sample_history(&self) -> [[f32; 2]; 1024] {
             self.$mixer.get_sample_history()
         }
@@ -0,0 +1,190 @@
+//! Playing sounds at a different speed than the movie was made for, as done when fast-forwarding.
+//!
+//! Flash Player has no such thing, so sounds are only sped up when the embedder runs the movie
+//! faster than its frame rate. Speeding up sounds by resampling them would also raise their pitch,
+//! so they are either time-stretched or muted instead.
+
+#[cfg(feature = "audio")]
+use std::collections::VecDeque;
+#[cfg(feature = "audio")]
+use std::f32::consts::PI;
+
+/// What happens to sounds while the movie runs faster or slower than normal.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FastForwardAudio {
+    /// Sounds are time-stretched to keep up with the movie, without changing their pitch.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "preserve-pitch"))]
+    PreservePitch,
+
+    /// Sounds keep up with the movie silently.
+    #[cfg_attr(feature = "serde", serde(rename = "mute"))]
+    Mute,
+}
+
+/// The speed at which the mixer plays sounds.
+#[cfg(feature = "audio")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PlaybackSpeed {
+    /// How many times faster than normal sounds play.
+    pub speed: f64,
+
+    pub fast_forward_audio: FastForwardAudio,
+}
+
+#[cfg(feature = "audio")]
+impl PlaybackSpeed {
+    pub const NORMAL: Self = Self {
+        speed: 1.0,
+        fast_forward_audio: FastForwardAudio::PreservePitch,
+    };
+
+    pub fn is_normal(&self) -> bool {
+        self.speed == 1.0
+    }
+}
+
+/// Changes the speed of a sound without changing its pitch, by overlapping and adding windowed
+/// grains of the sound, which are taken further apart than they are played.
+///
+/// This is the simplest kind of time-stretching, which smears transients a little, but is cheap
+/// enough to run on every playing sound.
+#[cfg(feature = "audio")]
+pub(crate) struct TimeStretcher {
+    /// The frames of the sound that the next grain starts with.
+    input: VecDeque<[f32; 2]>,
+
+    /// The grains being added up, of which the first `HOP` frames are complete.
+    overlap: Vec<[f32; 2]>,
+
+    /// The position of the next frame to play in `overlap`.
+    output_pos: usize,
+
+    /// The fraction of a frame that the last grain fell short of skipping.
+    skip_remainder: f64,
+
+    /// The Hann window applied to grains, which adds up to 1 when grains overlap by half.
+    window: Vec<f32>,
+
+    /// Whether the sound has no more frames.
+    source_exhausted: bool,
+}
+
+#[cfg(feature = "audio")]
+impl TimeStretcher {
+    /// The length of the grains, in output frames (about 46ms at 44.1kHz).
+    const GRAIN: usize = 2048;
+
+    /// How many frames are played between the start of two grains.
+    const HOP: usize = Self::GRAIN / 2;
+
+    pub fn new() -> Self {
+        Self {
+            input: VecDeque::with_capacity(Self::GRAIN),
+            overlap: vec![[0.0; 2]; Self::GRAIN],
+            output_pos: Self::HOP,
+            skip_remainder: 0.0,
+            window: (0..Self::GRAIN)
+                .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / Self::GRAIN as f32).cos())
+                .collect(),
+            source_exhausted: false,
+        }
+    }
+
+    /// Returns the next frame of the sound played at `speed`, pulling frames of the sound from
+    /// `source` as needed, which returns `None` once the sound has ended.
+    pub fn next(&mut self, speed: f64, mut source: impl FnMut() -> Option<[i16; 2]>) -> [i16; 2] {
+        if self.output_pos == Self::HOP {
+            self.add_grain(speed, &mut source);
+        }
+        let frame = self.overlap[self.output_pos];
+        self.output_pos += 1;
+        frame.map(|sample| (sample * 32768.0).clamp(-32768.0, 32767.0) as i16)
+    }
+
+    /// Whether everything left of the sound has been played.
+    pub fn is_drained(&self) -> bool {
+        self.source_exhausted
+            && self.input.is_empty()
+            && self.output_pos == Self::HOP
+            && self.overlap[Self::HOP..]
+                .iter()
+                .all(|frame| *frame == [0.0; 2])
+    }
+
+    fn add_grain(&mut self, speed: f64, source: &mut impl FnMut() -> Option<[i16; 2]>) {
+        // The first half has been played, and the second half is completed by the new grain.
+        self.overlap.copy_within(Self::HOP.., 0);
+        self.overlap[Self::HOP..].fill([0.0; 2]);
+
+        while self.input.len() < Self::GRAIN && !self.source_exhausted {
+            match source() {
+                Some(frame) => self
+                    .input
+                    .push_back(frame.map(|sample| sample as f32 / 32768.0)),
+                None => self.source_exhausted = true,
+            }
+        }
+        for ((out, frame), window) in self.overlap.iter_mut().zip(&self.input).zip(&self.window) {
+            out[0] += frame[0] * window;
+            out[1] += frame[1] * window;
+        }
+
+        // The next grain starts `speed` times further into the sound than it's played.
+        let skip = Self::HOP as f64 * speed + self.skip_remainder;
+        self.skip_remainder = skip.fract();
+        let skip = skip as usize;
+        let buffered = skip.min(self.input.len());
+        self.input.drain(..buffered);
+        for _ in buffered..skip {
+            if self.source_exhausted || source().is_none() {
+                self.source_exhausted = true;
+                break;
+            }
+        }
+        self.output_pos = 0;
+    }
+}
+
+#[cfg(all(test, feature = "audio"))]
+mod tests {
+    use super::*;
+
+    /// Stretches a sine wave, returning how many frames it played and how often it crossed zero.
+    fn stretch_sine(speed: f64, frequency: f32) -> (usize, usize) {
+        const SAMPLE_RATE: usize = 44100;
+        let mut sine = (0..SAMPLE_RATE).map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let sample = ((2.0 * PI * frequency * t).sin() * 16384.0) as i16;
+            [sample, sample]
+        });
+        let mut stretcher = TimeStretcher::new();
+        let mut played = Vec::new();
+        while !stretcher.is_drained() {
+            played.push(stretcher.next(speed, || sine.next())[0]);
+        }
+        let crossings = played
+            .windows(2)
+            .filter(|pair| (pair[0] < 0) != (pair[1] < 0))
+            .count();
+        (played.len(), crossings)
+    }
+
+    #[test]
+    fn stretching_keeps_pitch() {
+        let (normal_len, normal_crossings) = stretch_sine(1.0, 440.0);
+        let (fast_len, fast_crossings) = stretch_sine(4.0, 440.0);
+
+        // A second of sound plays in a quarter of a second, give or take a grain.
+        let grain = TimeStretcher::GRAIN;
+        assert!(normal_len.abs_diff(44100) <= grain);
+        assert!(fast_len.abs_diff(44100 / 4) <= grain);
+
+        // Zero crossings happen just as often, so the pitch is the same.
+        let normal_rate = normal_crossings as f64 / normal_len as f64;
+        let fast_rate = fast_crossings as f64 / fast_len as f64;
+        assert!((fast_rate / normal_rate - 1.0).abs() < 0.1);
+    }
+}
@@ -0,0 +1,82 @@
+/// Access to the sandboxed filesystem exposed to AIR content through
+/// `flash.filesystem.File` and `flash.filesystem.FileStream`.
+///
+/// All paths are `/`-separated and relative to the root of the sandbox,
+/// e.g. `app-storage/saves/slot1.dat`. Implementations are responsible for
+/// refusing paths that would escape the sandbox.
+pub trait FilesystemBackend {
+    /// Reads the entire contents of the file at `path`.
+    fn read(&self, path: &str) -> Option<Vec<u8>>;
+
+    /// Replaces the contents of the file at `path`, creating it and any missing
+    /// parent directories if needed.
+    fn write(&mut self, path: &str, data: &[u8]) -> bool;
+
+    fn exists(&self, path: &str) -> bool;
+
+    fn is_directory(&self, path: &str) -> bool;
+
+    /// Returns the size of the file at `path` in bytes.
+    fn size(&self, path: &str) -> Option<u64> {
+        self.read(path).map(|data| data.len() as u64)
+    }
+
+    /// Lists the names of the entries directly inside the directory at `path`.
+    fn list_directory(&self, path: &str) -> Option<Vec<String>>;
+
+    fn create_directory(&mut self, path: &str) -> bool;
+
+    /// Deletes the file or directory at `path`.
+    /// Non-empty directories are only removed if `recursive` is set.
+    fn delete(&mut self, path: &str, recursive: bool) -> bool;
+
+    /// Moves the file or directory at `from` to `to`, creating any missing
+    /// parent directories. Fails if something already exists at `to`.
+    fn rename(&mut self, from: &str, to: &str) -> bool;
+}
+
+/// A filesystem backend that contains nothing and refuses all writes.
+///
+/// This is used when AIR filesystem access hasn't been enabled by the user.
+#[derive(Default)]
+pub struct NullFilesystemBackend;
+
+impl NullFilesystemBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FilesystemBackend for NullFilesystemBackend {
+    fn read(&self, _path: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn write(&mut self, _path: &str, _data: &[u8]) -> bool {
+        false
+    }
+
+    fn exists(&self, _path: &str) -> bool {
+        false
+    }
+
+    fn is_directory(&self, _path: &str) -> bool {
+        false
+    }
+
+    fn list_directory(&self, _path: &str) -> Option<Vec<String>> {
+        None
+    }
+
+    fn create_directory(&mut self, _path: &str) -> bool {
+        false
+    }
+
+    fn delete(&mut self, _path: &str, _recursive: bool) -> bool {
+        false
+    }
+
+    fn rename(&mut self, _from: &str, _to: &str) -> bool {
+        false
+    }
+}
@@ -1,5 +1,41 @@
+use std::fmt;
+
+/// The virtual machine that ran the script which traced a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AvmType {
+    Avm1,
+    Avm2,
+}
+
+impl fmt::Display for AvmType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AvmType::Avm1 => write!(f, "AVM1"),
+            AvmType::Avm2 => write!(f, "AVM2"),
+        }
+    }
+}
+
+/// Where a traced message comes from, so that the traces of different movies can be told apart
+/// when several of them run in the same player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceSource<'a> {
+    pub avm: AvmType,
+
+    /// The URL of the movie whose script traced the message.
+    pub movie_url: &'a str,
+}
+
 pub trait LogBackend {
     fn avm_trace(&self, message: &str);
+
+    /// Traces a message from a script of a movie.
+    ///
+    /// By default, this forgets where the message comes from and calls [`Self::avm_trace`].
+    fn movie_trace(&self, message: &str, source: TraceSource<'_>) {
+        let _ = source;
+        self.avm_trace(message);
+    }
 }
 
 /// Logging backend that just reroutes traces to the log crate
@@ -41,6 +41,26 @@ pub enum NavigationMethod {
 
     /// Indicates that navigation should generate a POST request.
     Post,
+
+    /// Indicates that navigation should generate a PUT request.
+    ///
+    /// Only AIR content may use this method.
+    Put,
+
+    /// Indicates that navigation should generate a DELETE request.
+    ///
+    /// Only AIR content may use this method.
+    Delete,
+
+    /// Indicates that navigation should generate a HEAD request.
+    ///
+    /// Only AIR content may use this method.
+    Head,
+
+    /// Indicates that navigation should generate an OPTIONS request.
+    ///
+    /// Only AIR content may use this method.
+    Options,
 }
 
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
@@ -95,6 +115,29 @@ impl NavigationMethod {
             None
         }
     }
+
+    /// Like `from_method_str`, but also accepts the methods that only
+    /// AIR content may use.
+    pub fn from_air_method_str(method: &WStr) -> Option<Self> {
+        let method = method.to_ascii_lowercase();
+        if &method == b"put" {
+            Some(Self::Put)
+        } else if &method == b"delete" {
+            Some(Self::Delete)
+        } else if &method == b"head" {
+            Some(Self::Head)
+        } else if &method == b"options" {
+            Some(Self::Options)
+        } else {
+            Self::from_method_str(&method)
+        }
+    }
+
+    /// Whether request data is sent in the body, rather than appended
+    /// to the URL as a query string.
+    pub fn sends_data_in_body(self) -> bool {
+        !matches!(self, Self::Get | Self::Head)
+    }
 }
 
 impl fmt::Display for NavigationMethod {
@@ -102,6 +145,10 @@ impl fmt::Display for NavigationMethod {
         let method = match self {
             Self::Get => "GET",
             Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Delete => "DELETE",
+            Self::Head => "HEAD",
+            Self::Options => "OPTIONS",
         };
         f.write_str(method)
     }
@@ -208,6 +255,13 @@ pub trait SuccessResponse {
     /// Indicates if the request has been redirected.
     fn redirected(&self) -> bool;
 
+    /// The HTTP headers of the response, as (header_name, header_value) pairs.
+    ///
+    /// Responses that didn't come from an HTTP server have no headers.
+    fn headers(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
     /// Read the next chunk of the response.
     ///
     /// Repeated calls to `next_chunk` yield further bytes of the response body.
@@ -319,6 +373,19 @@ pub trait NavigatorBackend: Downcast {
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
     );
+
+    /// Notifies the backend that a socket policy file served by `host`
+    /// allows the current movie to connect to it on `port`.
+    ///
+    /// As the server opted in to the connection, backends that ask the user
+    /// before opening a socket may skip asking for this one.
+    fn allow_socket_by_policy(&mut self, _host: &str, _port: u16) {}
+
+    /// Fetches a file in the background that the movie is likely to load soon,
+    /// so that loading it later doesn't have to wait for the network.
+    ///
+    /// Backends without a cache to keep the file in can ignore this.
+    fn prefetch(&mut self, _url: Url) {}
 }
 impl_downcast!(NavigatorBackend);
 
@@ -645,6 +712,9 @@ pub fn fetch_path<NavigatorType: NavigatorBackend>(
         Ok(url) => url,
         Err(e) => return async_return(create_fetch_error(url, e)),
     };
+    if url.scheme() == "data" {
+        return fetch_data_url(&url);
+    }
     let path = if url.scheme() == "file" {
         // Flash supports query parameters with local urls.
         // SwfMovie takes care of exposing those to ActionScript -
@@ -702,3 +772,130 @@ pub fn get_encoding(content_type: &str) -> Option<&'static Encoding> {
         None
     }
 }
+
+/// Fetches the contents of a `data:` URL, as defined by RFC 2397.
+pub fn fetch_data_url(url: &Url) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+    struct DataResponse {
+        url: String,
+        data: Option<Vec<u8>>,
+        text_encoding: Option<&'static Encoding>,
+    }
+
+    impl SuccessResponse for DataResponse {
+        fn url(&self) -> Cow<str> {
+            Cow::Borrowed(&self.url)
+        }
+
+        fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
+            let data = self.data.unwrap_or_default();
+            Box::pin(async move { Ok(data) })
+        }
+
+        fn text_encoding(&self) -> Option<&'static Encoding> {
+            self.text_encoding
+        }
+
+        fn status(&self) -> u16 {
+            0
+        }
+
+        fn redirected(&self) -> bool {
+            false
+        }
+
+        fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+            let data = self.data.take();
+            Box::pin(async move { Ok(data) })
+        }
+
+        fn expected_length(&self) -> Result<Option<u64>, Error> {
+            Ok(self.data.as_ref().map(|data| data.len() as u64))
+        }
+    }
+
+    match decode_data_url(url) {
+        Some((data, media_type)) => {
+            let response: Box<dyn SuccessResponse> = Box::new(DataResponse {
+                url: url.to_string(),
+                data: Some(data),
+                text_encoding: get_encoding(&media_type),
+            });
+            async_return(Ok(response))
+        }
+        None => async_return(create_specific_fetch_error(
+            "Invalid data URL",
+            url.as_str(),
+            "",
+        )),
+    }
+}
+
+/// Decodes a `data:` URL into its contents and media type.
+fn decode_data_url(url: &Url) -> Option<(Vec<u8>, String)> {
+    if url.scheme() != "data" {
+        return None;
+    }
+    // Unlike its fragment, the query of a data URL is part of the data.
+    let (media_type, data) =
+        url[url::Position::BeforePath..url::Position::AfterQuery].split_once(',')?;
+    let data = percent_encoding::percent_decode_str(data).collect::<Vec<u8>>();
+    match media_type.strip_suffix(";base64") {
+        Some(media_type) => Some((decode_base64(&data)?, media_type.to_owned())),
+        None => Some((data, media_type.to_owned())),
+    }
+}
+
+/// Decodes base64 data, ignoring whitespace and padding.
+fn decode_base64(data: &[u8]) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(data.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in data {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' | b' ' | b'\t' | b'\r' | b'\n' => continue,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(url: &str) -> Option<(Vec<u8>, String)> {
+        decode_data_url(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn decodes_data_urls() {
+        assert_eq!(
+            decode("data:text/plain;charset=utf-8,a%20b?c=d#e"),
+            Some((b"a b?c=d".to_vec(), "text/plain;charset=utf-8".to_owned()))
+        );
+        assert_eq!(
+            decode("data:application/x-shockwave-flash;base64,RldTCg=="),
+            Some((
+                b"FWS\n".to_vec(),
+                "application/x-shockwave-flash".to_owned()
+            ))
+        );
+        assert_eq!(
+            decode("data:;base64,SGVs bG8"),
+            Some((b"Hello".to_vec(), String::new()))
+        );
+        assert_eq!(decode("data:;base64,SGV*"), None);
+        assert_eq!(decode("data:no-comma"), None);
+    }
+}
@@ -1,5 +1,27 @@
 use std::collections::HashMap;
 
+/// How many bytes of shared objects Flash Player lets a site store before asking the user for more.
+pub const DEFAULT_QUOTA: usize = 100 * 1024;
+
+/// Whether a shared object fits within the storage quota of its site.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuotaStatus {
+    /// The shared object may be stored.
+    Allowed,
+
+    /// The site would store `required` bytes in total, more than its quota.
+    /// The user may be asked to allow this.
+    Exceeded { required: usize },
+
+    /// The site may not store anything, and the user chose to never be asked about it.
+    Denied,
+}
+
+/// Returns the site that a shared object belongs to, given its full `host/path/name` name.
+pub fn site_of(name: &str) -> &str {
+    name.split('/').next().unwrap_or(name)
+}
+
 pub trait StorageBackend {
     fn get(&self, name: &str) -> Option<Vec<u8>>;
 
@@ -10,6 +32,14 @@ pub trait StorageBackend {
     }
 
     fn remove_key(&mut self, name: &str);
+
+    /// Checks whether `size` bytes may be stored under `name`, taking into account
+    /// everything else its site has already stored.
+    ///
+    /// Backends without quotas allow everything.
+    fn check_quota(&self, _name: &str, _size: usize) -> QuotaStatus {
+        QuotaStatus::Allowed
+    }
 }
 
 #[derive(Default)]
@@ -79,6 +79,22 @@ pub trait UiBackend: Downcast {
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Asks the user whether the movie may read the clipboard through ActionScript.
+    ///
+    /// Backends that are unable to prompt the user should deny the request.
+    fn request_clipboard_read_permission(&mut self) -> bool {
+        false
+    }
+
+    /// Warns the user that a script of the movie is running slowly, and asks whether to abort it.
+    /// This blocks until the user answers, and returns true if the script should be aborted.
+    ///
+    /// Returns `None` if the backend is unable to prompt the user, in which case
+    /// the player decides on its own.
+    fn ask_abort_slow_script(&mut self) -> Option<bool> {
+        None
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
     /// Displays a message about an error during root movie download.
@@ -128,9 +144,59 @@ pub trait UiBackend: Downcast {
 
     /// Mark that any previously open dialog has been closed
     fn close_file_dialog(&mut self);
+
+    /// Changes the title of the main window, as requested by AIR content
+    /// through `stage.nativeWindow.title`.
+    fn set_window_title(&mut self, _title: &str) {}
+
+    /// Shows the Flash Player Settings panel, as requested through
+    /// `System.showSettings` or `Security.showSettings`.
+    fn display_settings_panel(&mut self, _panel: SettingsPanel) {}
+
+    /// Asks the user to let `site` store `required` bytes of shared objects,
+    /// which is more than its storage quota allows.
+    ///
+    /// The returned future resolves to whether the user agreed, after which the quota
+    /// reported by the storage backend must allow the request.
+    /// Backends that are unable to ask the user should return `None`.
+    fn request_storage_quota(
+        &mut self,
+        _site: &str,
+        _required: usize,
+    ) -> Option<OwnedFuture<bool, ()>> {
+        None
+    }
 }
 impl_downcast!(UiBackend);
 
+/// A tab of the Flash Player Settings panel.
+/// Communicated from the core to the UI backend via `UiBackend::display_settings_panel`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SettingsPanel {
+    /// Whichever panel the user last looked at.
+    /// Equivalent to AS3 `SecurityPanel.DEFAULT`.
+    #[default]
+    Default,
+
+    /// Camera and microphone access.
+    /// Equivalent to AS3 `SecurityPanel.PRIVACY`, or panel 0 in AVM1.
+    Privacy,
+
+    /// How much data the site may store in local shared objects.
+    /// Equivalent to AS3 `SecurityPanel.LOCAL_STORAGE`, or panel 1 in AVM1.
+    LocalStorage,
+
+    /// Equivalent to AS3 `SecurityPanel.MICROPHONE`, or panel 2 in AVM1.
+    Microphone,
+
+    /// Equivalent to AS3 `SecurityPanel.CAMERA`, or panel 3 in AVM1.
+    Camera,
+
+    /// Hardware acceleration.
+    /// Equivalent to AS3 `SecurityPanel.DISPLAY`.
+    Display,
+}
+
 /// A mouse cursor icon displayed by the Flash Player.
 /// Communicated from the core to the UI backend via `UiBackend::set_mouse_cursor`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
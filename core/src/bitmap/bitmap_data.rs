@@ -503,6 +503,11 @@ mod wrapper {
             let handle = inner_bitmap_data
                 .bitmap_handle(context.renderer)
                 .expect("Missing bitmap handle");
+            let size = inner_bitmap_data.width() as usize * inner_bitmap_data.height() as usize * 4;
+            drop(inner_bitmap_data);
+            context
+                .texture_budget
+                .touch(context.gc_context, self.0, size);
 
             context.commands.render_bitmap(
                 handle,
@@ -615,6 +620,27 @@ impl<'gc> BitmapData<'gc> {
         self.disposed = true;
     }
 
+    /// Whether this bitmap currently has a texture uploaded to the GPU.
+    pub fn has_texture(&self) -> bool {
+        self.bitmap_handle.is_some()
+    }
+
+    /// Release the GPU texture of this bitmap, unless the GPU holds the only
+    /// up-to-date copy of its pixels.
+    ///
+    /// The texture will be uploaded again the next time it's needed.
+    pub fn release_texture(&mut self) -> bool {
+        match self.dirty_state {
+            DirtyState::GpuModified(_, _) => false,
+            DirtyState::Clean | DirtyState::CpuModified(_) => {
+                self.bitmap_handle = None;
+                // The next upload will contain all of the CPU pixels.
+                self.dirty_state = DirtyState::Clean;
+                true
+            }
+        }
+    }
+
     pub fn bitmap_handle(&mut self, renderer: &mut dyn RenderBackend) -> Option<BitmapHandle> {
         if self.bitmap_handle.is_none() {
             let bitmap = Bitmap::new(
@@ -266,7 +266,7 @@ pub fn perlin_noise<'gc>(
     fractal_noise: bool,
     channel_options: ChannelOptions,
     grayscale: bool,
-    offsets: Vec<(f64, f64)>, // must contain `num_octaves` values
+    offsets: Vec<(f64, f64)>, // missing values default to no offset
 ) {
     let (target, _) = target.overwrite_cpu_pixels_from_gpu(mc);
     let mut write = target.write(mc);
@@ -1201,6 +1201,27 @@ pub fn copy_pixels_with_alpha_source<'gc>(
     write.set_cpu_dirty(context.gc_context, dirty_region);
 }
 
+/// The area of `target` that `apply_filter` changes when given `source_rect` and `filter`.
+///
+/// Filters such as blurs and shadows reach beyond the source rectangle, while the others
+/// only change the pixels within it.
+pub fn generate_filter_rect(
+    target: BitmapDataWrapper<'_>,
+    source_rect: Rectangle<Twips>,
+    filter: &Filter,
+) -> Rectangle<Twips> {
+    if matches!(filter, Filter::ShaderFilter(_)) {
+        // Flash always reports that a shader affects the whole bitmap, ignoring the source rectangle.
+        return Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels_i32(target.width() as i32),
+            y_max: Twips::from_pixels_i32(target.height() as i32),
+        };
+    }
+    filter.calculate_dest_rect(source_rect)
+}
+
 pub fn apply_filter<'gc>(
     context: &mut UpdateContext<'gc>,
     target: BitmapDataWrapper<'gc>,
@@ -1531,6 +1552,8 @@ pub fn draw<'gc>(
         is_offscreen: true,
         use_bitmap_cache: false,
         stage: context.stage,
+        texture_budget: context.texture_budget,
+        glyph_cache: context.glyph_cache,
     };
 
     // Make the screen opacity match the opacity of this bitmap
@@ -1681,6 +1704,73 @@ pub fn set_vector<'gc>(
     Ok(())
 }
 
+/// An image format to encode bitmaps to.
+#[derive(Clone, Copy, Debug)]
+pub enum EncodeFormat {
+    Png { fast_compression: bool },
+    Jpeg { quality: u8 },
+}
+
+/// Encodes an area of a bitmap as an image file.
+///
+/// Transparent bitmaps keep their alpha channel in formats that support it.
+pub fn encode(
+    target: BitmapDataWrapper,
+    renderer: &mut dyn RenderBackend,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    format: EncodeFormat,
+) -> Result<Vec<u8>, image::ImageError> {
+    use image::codecs::jpeg::JpegEncoder;
+    use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+    use image::{ExtendedColorType, ImageEncoder};
+
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+    let with_alpha = target.transparency() && matches!(format, EncodeFormat::Png { .. });
+
+    let read = target.read_area(region, renderer);
+    let mut pixels = Vec::with_capacity((region.width() * region.height() * 4) as usize);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let color = read.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+            pixels.extend_from_slice(&[color.red(), color.green(), color.blue()]);
+            if with_alpha {
+                pixels.push(color.alpha());
+            }
+        }
+    }
+
+    let color_type = if with_alpha {
+        ExtendedColorType::Rgba8
+    } else {
+        ExtendedColorType::Rgb8
+    };
+    let mut output = Vec::new();
+    match format {
+        EncodeFormat::Png { fast_compression } => {
+            let compression = if fast_compression {
+                CompressionType::Fast
+            } else {
+                CompressionType::Default
+            };
+            PngEncoder::new_with_quality(&mut output, compression, FilterType::Adaptive)
+                .write_image(&pixels, region.width(), region.height(), color_type)?;
+        }
+        EncodeFormat::Jpeg { quality } => {
+            JpegEncoder::new_with_quality(&mut output, quality.clamp(1, 100)).write_image(
+                &pixels,
+                region.width(),
+                region.height(),
+                color_type,
+            )?;
+        }
+    }
+    Ok(output)
+}
+
 pub fn get_pixels_as_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
     target: BitmapDataWrapper,
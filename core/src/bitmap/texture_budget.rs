@@ -0,0 +1,178 @@
+//! Limits the GPU memory used by the textures of bitmaps and shapes.
+
+use crate::bitmap::bitmap_data::BitmapData;
+use crate::display_object::GraphicStatic;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, Gc, GcCell, GcWeak, GcWeakCell, Mutation};
+
+/// Tracks the textures of rendered `BitmapData`s and the meshes and gradient
+/// textures of rendered `DefineShape` shapes, releasing the least recently
+/// rendered ones whenever their total size exceeds a budget.
+///
+/// Released textures are transparently re-uploaded from the CPU copy of the
+/// pixels or shape the next time they're used, so movies with more assets than
+/// fit in GPU memory keep working, at the cost of some uploads.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct TextureBudget<'gc>(GcCell<'gc, TextureBudgetData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct TextureBudgetData<'gc> {
+    /// The maximum total size of all textures, in bytes, or `None` for no limit.
+    budget: Option<usize>,
+
+    /// The number of frames rendered so far.
+    frame: u64,
+
+    /// The tracked textures, keyed by the address of their owner.
+    textures: FnvHashMap<usize, TrackedTexture<'gc>>,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct TrackedTexture<'gc> {
+    owner: TextureOwner<'gc>,
+
+    /// The size of the texture, in bytes.
+    size: usize,
+
+    /// The frame this texture was last rendered in.
+    last_rendered: u64,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+enum TextureOwner<'gc> {
+    Bitmap(GcWeakCell<'gc, BitmapData<'gc>>),
+    Shape(GcWeak<'gc, GraphicStatic>),
+}
+
+impl<'gc> TextureOwner<'gc> {
+    /// Whether the owner is still alive and has its texture.
+    fn has_texture(&self, mc: &Mutation<'gc>) -> bool {
+        match self {
+            Self::Bitmap(bitmap_data) => bitmap_data
+                .upgrade(mc)
+                .is_some_and(|bitmap_data| bitmap_data.read().has_texture()),
+            Self::Shape(shape) => shape
+                .upgrade(mc)
+                .is_some_and(|shape| shape.has_render_handle()),
+        }
+    }
+
+    /// Release the texture, returning whether it was released.
+    fn release_texture(&self, mc: &Mutation<'gc>) -> bool {
+        match self {
+            Self::Bitmap(bitmap_data) => bitmap_data
+                .upgrade(mc)
+                .is_some_and(|bitmap_data| bitmap_data.write(mc).release_texture()),
+            Self::Shape(shape) => shape.upgrade(mc).is_some_and(|shape| {
+                shape.release_render_handle();
+                true
+            }),
+        }
+    }
+}
+
+impl<'gc> TextureBudget<'gc> {
+    pub fn new(mc: &Mutation<'gc>, budget: Option<usize>) -> Self {
+        Self(GcCell::new(
+            mc,
+            TextureBudgetData {
+                budget,
+                frame: 0,
+                textures: Default::default(),
+            },
+        ))
+    }
+
+    pub fn set_budget(self, mc: &Mutation<'gc>, budget: Option<usize>) {
+        let mut write = self.0.write(mc);
+        write.budget = budget;
+        if budget.is_none() {
+            write.textures.clear();
+        }
+    }
+
+    /// Record that the texture of a `BitmapData` is being rendered this frame.
+    pub fn touch(self, mc: &Mutation<'gc>, bitmap_data: GcCell<'gc, BitmapData<'gc>>, size: usize) {
+        self.track(
+            mc,
+            bitmap_data.as_ptr() as usize,
+            TextureOwner::Bitmap(GcCell::downgrade(bitmap_data)),
+            size,
+        );
+    }
+
+    /// Record that a shape is being rendered this frame.
+    pub fn touch_shape(self, mc: &Mutation<'gc>, shape: Gc<'gc, GraphicStatic>, size: usize) {
+        self.track(
+            mc,
+            Gc::as_ptr(shape) as usize,
+            TextureOwner::Shape(Gc::downgrade(shape)),
+            size,
+        );
+    }
+
+    fn track(self, mc: &Mutation<'gc>, key: usize, owner: TextureOwner<'gc>, size: usize) {
+        let mut write = self.0.write(mc);
+        if write.budget.is_none() {
+            return;
+        }
+
+        let last_rendered = write.frame;
+        write.textures.insert(
+            key,
+            TrackedTexture {
+                owner,
+                size,
+                last_rendered,
+            },
+        );
+    }
+
+    /// Finish a frame, releasing textures until we're back under budget.
+    ///
+    /// Textures rendered in the frame that just finished are never released,
+    /// as they'd only have to be uploaded again on the next frame.
+    pub fn end_frame(self, mc: &Mutation<'gc>) {
+        let mut write = self.0.write(mc);
+        let frame = write.frame;
+        write.frame += 1;
+        let Some(budget) = write.budget else {
+            return;
+        };
+
+        // Forget about textures that have since been collected or disposed of.
+        write
+            .textures
+            .retain(|_, texture| texture.owner.has_texture(mc));
+
+        let mut used: usize = write.textures.values().map(|texture| texture.size).sum();
+        if used <= budget {
+            return;
+        }
+
+        let mut candidates: Vec<_> = write
+            .textures
+            .iter()
+            .filter(|(_, texture)| texture.last_rendered < frame)
+            .map(|(key, texture)| (*key, texture.last_rendered))
+            .collect();
+        candidates.sort_unstable_by_key(|(_, last_rendered)| *last_rendered);
+
+        for (key, _) in candidates {
+            if used <= budget {
+                break;
+            }
+
+            let texture = &write.textures[&key];
+            let size = texture.size;
+            if texture.owner.release_texture(mc) {
+                write.textures.remove(&key);
+                used -= size;
+            }
+        }
+    }
+}
@@ -52,6 +52,20 @@ fn lerp(t: f64, a: f64, b: f64) -> f64 {
     a + t * (b - a)
 }
 
+/// Rounds a frequency to the nearest one that repeats a whole number of times per tile.
+fn stitch_frequency(base_freq: f64, tile_size: f64) -> f64 {
+    if base_freq == 0.0 {
+        return 0.0;
+    }
+    let lo_freq = (tile_size * base_freq).floor() / tile_size;
+    let hi_freq = (tile_size * base_freq).ceil() / tile_size;
+    if base_freq / lo_freq < hi_freq / base_freq {
+        lo_freq
+    } else {
+        hi_freq
+    }
+}
+
 const B_SIZE: usize = 0x100;
 const BM: i32 = 0xff;
 const PERLIN_N: i32 = 0x1000;
@@ -190,24 +204,8 @@ impl Turbulence {
         if do_stitching {
             // When stitching tiled turbulence, the frequencies must be adjusted
             // so that the tile borders will be continuous.
-            if base_freq.0 != 0.0 {
-                let lo_freq = (tile_size.0 * base_freq.0).floor() / tile_size.0;
-                let hi_freq = (tile_size.0 * base_freq.0).ceil() / tile_size.0;
-                base_freq.0 = if base_freq.0 / lo_freq < hi_freq / base_freq.0 {
-                    lo_freq
-                } else {
-                    hi_freq
-                };
-            }
-            if base_freq.1 != 0.0 {
-                let lo_freq = (tile_size.1 * base_freq.0).floor() / tile_size.1;
-                let hi_freq = (tile_size.1 * base_freq.1).ceil() / tile_size.1;
-                base_freq.1 = if base_freq.1 / lo_freq < hi_freq / base_freq.1 {
-                    lo_freq
-                } else {
-                    hi_freq
-                };
-            }
+            base_freq.0 = stitch_frequency(base_freq.0, tile_size.0);
+            base_freq.1 = stitch_frequency(base_freq.1, tile_size.1);
             // Set up initial stitch values.
             let w = (tile_size.0 * base_freq.0 + 0.5) as i32;
             let h = (tile_size.1 * base_freq.1 + 0.5) as i32;
@@ -221,7 +219,7 @@ impl Turbulence {
         let mut sum = 0.0;
         let mut ratio = 1.0;
         for octave in 0..num_octaves {
-            let offset = octave_offsets.get(octave).unwrap();
+            let offset = octave_offsets.get(octave).unwrap_or(&(0.0, 0.0));
             let vec = (
                 (point.0 + offset.0) * base_freq.0 * ratio,
                 (point.1 + offset.1) * base_freq.1 * ratio,
@@ -241,3 +239,49 @@ impl Turbulence {
         sum
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_matches_reference() {
+        let mut seed = setup_seed(1);
+        for _ in 0..10_000 {
+            seed = random(seed);
+        }
+        assert_eq!(seed, 1043618065);
+    }
+
+    #[test]
+    fn stitch_frequency_rounds_per_axis() {
+        assert_eq!(stitch_frequency(0.1, 32.0), 3.0 / 32.0);
+        assert_eq!(stitch_frequency(0.14, 32.0), 5.0 / 32.0);
+        assert_eq!(stitch_frequency(0.01, 32.0), 1.0 / 32.0);
+        assert_eq!(stitch_frequency(0.0, 32.0), 0.0);
+    }
+
+    #[test]
+    fn stitched_noise_tiles() {
+        let turbulence = Turbulence::from_seed(42);
+        let tile_size = (64.0, 32.0);
+        let noise = |point| {
+            turbulence.turbulence(
+                0,
+                point,
+                (0.05, 0.1),
+                3,
+                true,
+                true,
+                (0.0, 0.0),
+                tile_size,
+                &[],
+            )
+        };
+        for i in 0..16 {
+            let t = f64::from(i) * 3.0;
+            assert_eq!(noise((0.0, t)), noise((tile_size.0, t)));
+            assert_eq!(noise((t, 0.0)), noise((t, tile_size.1)));
+        }
+    }
+}
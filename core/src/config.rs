@@ -72,3 +72,66 @@ pub enum NetworkingAccessMode {
     #[cfg_attr(feature = "serde", serde(rename = "none"))]
     None,
 }
+
+/// Values reported by `System.capabilities` (AVM1) and `flash.system.Capabilities` (AVM2)
+/// in place of the ones Ruffle would normally report.
+///
+/// Many movies gate features, or refuse to run at all, based on the detected player.
+/// Any value left as `None` is reported as usual.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpoofedCapabilities {
+    /// The full version string, such as `WIN 9,0,115,0`.
+    pub version: Option<String>,
+
+    /// The operating system, such as `Windows XP`.
+    pub os: Option<String>,
+
+    /// The language code, such as `en` or `pt-BR`.
+    pub language: Option<String>,
+
+    /// The player type, such as `PlugIn` or `StandAlone`.
+    pub player_type: Option<String>,
+
+    /// Whether the player claims to be a debugger build.
+    pub is_debugger: Option<bool>,
+
+    /// The screen resolution in pixels.
+    pub screen_resolution: Option<ScreenResolution>,
+}
+
+/// A screen resolution in pixels, written as `1024x768`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenResolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl FromStr for ScreenResolution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (width, height) = s
+            .split_once('x')
+            .ok_or_else(|| format!("expected WIDTHxHEIGHT, got {s:?}"))?;
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<u32>()
+                .map_err(|e| format!("invalid dimension {value:?}: {e}"))
+        };
+        Ok(Self {
+            width: parse(width)?,
+            height: parse(height)?,
+        })
+    }
+}
+
+/// The screen that the player is currently shown on, as reported by the frontend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenInfo {
+    /// The resolution of the screen, in physical pixels.
+    pub resolution: ScreenResolution,
+
+    /// The number of physical pixels per inch.
+    pub dpi: f64,
+}
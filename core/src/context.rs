@@ -15,29 +15,38 @@ use crate::avm2::TObject as _;
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
-    log::LogBackend,
+    filesystem::FilesystemBackend,
+    log::{AvmType, LogBackend, TraceSource},
     navigator::NavigatorBackend,
     storage::StorageBackend,
     ui::UiBackend,
 };
+use crate::bitmap::texture_budget::TextureBudget;
 use crate::context_menu::ContextMenuState;
 use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
+use crate::font::glyph_cache::GlyphCache;
 use crate::frame_lifecycle::FramePhase;
 use crate::input::InputManager;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::local_connection::LocalConnections;
 use crate::net_connection::NetConnections;
+use crate::opcode_counts::OpcodeCounters;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
+use crate::policy_file::PolicyFiles;
+use crate::prefetch::{self, PrefetchPolicy};
 use crate::prelude::*;
+use crate::sandbox::DomainPermissions;
+use crate::sitelock::SitelockDetector;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::{AvmString, StringContext};
 use crate::stub::StubCollection;
 use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::time::TimeSource;
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
 use core::fmt;
@@ -72,6 +81,21 @@ pub struct UpdateContext<'gc> {
     /// A collection of stubs encountered during this movie.
     pub stub_tracker: &'gc mut StubCollection,
 
+    /// Collects what the movie does to find out where it is running, if enabled.
+    pub sitelock_detector: &'gc mut SitelockDetector,
+
+    /// Counts the AVM1 actions and AVM2 opcodes that run, if enabled.
+    pub opcode_counters: &'gc mut OpcodeCounters,
+
+    /// Which movies are allowed to script each other.
+    pub domain_permissions: &'gc mut DomainPermissions,
+
+    /// The cross-domain policy files loaded by movies.
+    pub policy_files: &'gc mut PolicyFiles,
+
+    /// Which files referenced by loaded movies are fetched ahead of time.
+    pub prefetch_policy: PrefetchPolicy,
+
     /// The library containing character definitions for this SWF.
     /// Used to instantiate a `DisplayObject` of a given ID.
     pub library: &'gc mut Library<'gc>,
@@ -107,6 +131,9 @@ pub struct UpdateContext<'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'gc mut dyn StorageBackend,
 
+    /// The filesystem backend, used by AIR content to read and write files.
+    pub filesystem: &'gc mut dyn FilesystemBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -175,16 +202,22 @@ pub struct UpdateContext<'gc> {
     /// External interface for (for example) JavaScript <-> ActionScript interaction
     pub external_interface: &'gc mut ExternalInterface<'gc>,
 
-    /// The instant at which the SWF was launched.
-    pub start_time: Instant,
+    /// The clocks `getTimer` and `Date` read the time from.
+    pub time_source: &'gc dyn TimeSource,
 
     /// The instant at which the current update started.
+    ///
+    /// This is moved forward when the user lets a slow script continue,
+    /// so that it gets another `max_execution_duration` to finish.
     pub update_start: Instant,
 
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
     pub max_execution_duration: Duration,
 
+    /// Whether the script that is currently running has already been told that it timed out.
+    pub script_timed_out: bool,
+
     /// A tracker for the current keyboard focused element
     pub focus_tracker: FocusTracker<'gc>,
 
@@ -200,6 +233,9 @@ pub struct UpdateContext<'gc> {
     /// Whether movies are prevented from changing the stage frame rate.
     pub forced_frame_rate: bool,
 
+    /// Whether truncated movies are played up to their last complete frame.
+    pub partial_playback: bool,
+
     /// Amount of actions performed since the last timeout check
     pub actions_since_timeout_check: &'gc mut u16,
 
@@ -221,6 +257,12 @@ pub struct UpdateContext<'gc> {
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     pub dynamic_root: gc_arena::DynamicRootSet<'gc>,
 
+    /// Limits the GPU memory used by the textures of bitmaps and shapes.
+    pub texture_budget: TextureBudget<'gc>,
+
+    /// Rasterized glyphs of text using advanced anti-aliasing.
+    pub glyph_cache: GlyphCache<'gc>,
+
     /// These functions are run at the end of each frame execution.
     /// Currently, this is just used for handling `Loader.loadBytes`
     #[allow(clippy::type_complexity)]
@@ -335,6 +377,9 @@ impl<'gc> UpdateContext<'gc> {
         *self.swf = Arc::new(movie);
         *self.instance_counter = 0;
 
+        let swf = self.swf.clone();
+        prefetch::prefetch_assets(self, &swf);
+
         if self.swf.is_action_script_3() {
             self.avm2.root_api_version =
                 ApiVersion::from_swf_version(self.swf.version(), self.avm2.player_runtime)
@@ -445,6 +490,33 @@ impl<'gc> UpdateContext<'gc> {
     pub fn avm_trace(&self, message: &str) {
         self.log.avm_trace(&message.replace('\r', "\n"));
     }
+
+    /// Traces a message from a script of `movie`.
+    pub fn movie_trace(&self, avm: AvmType, movie: &SwfMovie, message: &str) {
+        let source = TraceSource {
+            avm,
+            movie_url: movie.url(),
+        };
+        self.log.movie_trace(&message.replace('\r', "\n"), source);
+    }
+
+    /// Checks whether the running script has taken longer than `max_execution_duration`,
+    /// and if so asks the user whether to abort it.
+    ///
+    /// Returns true if the script should be stopped. If the user lets it continue,
+    /// it isn't checked again until another `max_execution_duration` has passed.
+    /// When the user can't be asked, the script is always stopped.
+    pub fn is_script_timed_out(&mut self) -> bool {
+        if self.update_start.elapsed() < self.max_execution_duration {
+            return false;
+        }
+
+        if self.ui.ask_abort_slow_script().unwrap_or(true) {
+            return true;
+        }
+        self.update_start = Instant::now();
+        false
+    }
 }
 
 /// A queued ActionScript call.
@@ -479,6 +551,11 @@ impl<'gc> ActionQueue<'gc> {
         Self { action_queue }
     }
 
+    /// Whether no actions are waiting to run.
+    pub fn is_empty(&self) -> bool {
+        self.action_queue.iter().all(VecDeque::is_empty)
+    }
+
     /// Queues an action to run for the given movie clip.
     /// The action will be skipped if the clip is removed before the action runs.
     pub fn queue_action(
@@ -546,6 +623,12 @@ pub struct RenderContext<'a, 'gc> {
 
     /// The current player's stage (including all loaded levels)
     pub stage: Stage<'gc>,
+
+    /// Limits the GPU memory used by the textures of bitmaps and shapes.
+    pub texture_budget: TextureBudget<'gc>,
+
+    /// Rasterized glyphs of text using advanced anti-aliasing.
+    pub glyph_cache: GlyphCache<'gc>,
 }
 
 impl<'a, 'gc> RenderContext<'a, 'gc> {
@@ -143,7 +143,7 @@ impl DisplayObjectWindow {
                         ui.selectable_value(
                             &mut self.open_panel,
                             Panel::TypeSpecific,
-                            display_object_type(object),
+                            object.type_name(),
                         );
                     }
                     if let Some(ctr) = object.as_container() {
@@ -1188,7 +1188,7 @@ fn has_type_specific_tab(object: DisplayObject) -> bool {
 }
 
 fn summary_name(object: DisplayObject) -> Cow<'static, str> {
-    let do_type = display_object_type(object);
+    let do_type = object.type_name();
     let name = object.name();
 
     if name.is_empty() {
@@ -1198,22 +1198,6 @@ fn summary_name(object: DisplayObject) -> Cow<'static, str> {
     }
 }
 
-fn display_object_type(object: DisplayObject) -> &'static str {
-    match object {
-        DisplayObject::Stage(_) => "Stage",
-        DisplayObject::Bitmap(_) => "Bitmap",
-        DisplayObject::Avm1Button(_) => "Avm1Button",
-        DisplayObject::Avm2Button(_) => "Avm2Button",
-        DisplayObject::EditText(_) => "EditText",
-        DisplayObject::Graphic(_) => "Graphic",
-        DisplayObject::MorphShape(_) => "MorphShape",
-        DisplayObject::MovieClip(_) => "MovieClip",
-        DisplayObject::Text(_) => "Text",
-        DisplayObject::Video(_) => "Video",
-        DisplayObject::LoaderDisplay(_) => "LoaderDisplay",
-    }
-}
-
 fn blend_mode_name(mode: ExtendedBlendMode) -> &'static str {
     match mode {
         ExtendedBlendMode::Normal => "Normal",
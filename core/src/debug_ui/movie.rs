@@ -227,6 +227,19 @@ impl MovieWindow {
                 ui.end_row();
             });
 
+        if let Some(metadata) = movie.metadata() {
+            CollapsingHeader::new("XMP Metadata")
+                .id_salt(ui.id().with("metadata"))
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.add(
+                        TextEdit::multiline(&mut metadata.to_string())
+                            .code_editor()
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+        }
+
         if !movie.parameters().is_empty() {
             CollapsingHeader::new("Parameters")
                 .id_salt(ui.id().with("parameters"))
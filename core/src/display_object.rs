@@ -3,12 +3,11 @@ use crate::avm1::{
     Value as Avm1Value,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, Error as Avm2Error, EventObject as Avm2EventObject,
-    Multiname as Avm2Multiname, Object as Avm2Object, TObject as Avm2TObject, Value as Avm2Value,
+    Activation as Avm2Activation, Error as Avm2Error, Multiname as Avm2Multiname,
+    Object as Avm2Object, TObject as Avm2TObject, Value as Avm2Value,
 };
 use crate::context::{RenderContext, UpdateContext};
 use crate::drawing::Drawing;
-use crate::loader::LoadManager;
 use crate::prelude::*;
 use crate::string::{AvmString, WString};
 use crate::tag_utils::SwfMovie;
@@ -17,7 +16,6 @@ use crate::vminterface::Instantiator;
 use bitflags::bitflags;
 use gc_arena::{Collect, Mutation};
 use ruffle_macros::enum_trait_object;
-use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::transform::{Transform, TransformStack};
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
@@ -31,12 +29,14 @@ mod bitmap;
 mod container;
 mod edit_text;
 mod graphic;
+pub mod hit_test;
 mod interactive;
 mod loader_display;
 mod morph_shape;
 mod movie_clip;
 mod stage;
 mod text;
+pub mod transform_3d;
 mod video;
 
 use crate::avm1::Activation;
@@ -52,6 +52,7 @@ pub use bitmap::{Bitmap, BitmapClass};
 pub use edit_text::LayoutDebugBoxesFlag;
 pub use edit_text::{AutoSizeMode, EditText, TextSelection};
 pub use graphic::Graphic;
+pub(crate) use graphic::GraphicStatic;
 pub use interactive::{Avm2MousePick, InteractiveObject, TInteractiveObject};
 pub use loader_display::LoaderDisplay;
 pub use morph_shape::MorphShape;
@@ -59,13 +60,14 @@ pub use movie_clip::{MovieClip, MovieClipWeak, Scene};
 use ruffle_render::backend::{BitmapCacheEntry, RenderBackend};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelSnapping};
 use ruffle_render::blend::ExtendedBlendMode;
-use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::commands::{BlendShader, CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::filters::Filter;
 pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
 pub use text::Text;
 pub use video::Video;
 
 use self::loader_display::LoaderDisplayWeak;
+use self::transform_3d::{PerspectiveProjection, Projection, Transform3D};
 
 /// If a `DisplayObject` is marked `cacheAsBitmap` (via tag or AS),
 /// this struct keeps the information required to uphold that cache.
@@ -236,7 +238,7 @@ pub struct DisplayObjectBase<'gc> {
     blend_mode: ExtendedBlendMode,
 
     #[collect(require_static)]
-    blend_shader: Option<PixelBenderShaderHandle>,
+    blend_shader: Option<BlendShader>,
 
     /// The opaque background color of this display object.
     /// The bounding box of the display object will be filled with the given color. This also
@@ -268,6 +270,15 @@ pub struct DisplayObjectBase<'gc> {
     /// None means not cached, Some means cached.
     #[collect(require_static)]
     cache: Option<BitmapCache>,
+
+    /// The 3D part of the transform, once `z`, `rotationX`, `rotationY`, `scaleZ`
+    /// or `transform.matrix3D` was set.
+    #[collect(require_static)]
+    transform_3d: Option<Transform3D>,
+
+    /// The perspective applied to descendants with a 3D transform (`transform.perspectiveProjection`).
+    #[collect(require_static)]
+    perspective_projection: Option<PerspectiveProjection>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -297,6 +308,8 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             next_scroll_rect: Default::default(),
             scaling_grid: Default::default(),
             cache: None,
+            transform_3d: None,
+            perspective_projection: None,
         }
     }
 }
@@ -644,11 +657,11 @@ impl<'gc> DisplayObjectBase<'gc> {
         changed
     }
 
-    fn blend_shader(&self) -> Option<PixelBenderShaderHandle> {
+    fn blend_shader(&self) -> Option<BlendShader> {
         self.blend_shader.clone()
     }
 
-    fn set_blend_shader(&mut self, value: Option<PixelBenderShaderHandle>) {
+    fn set_blend_shader(&mut self, value: Option<BlendShader>) {
         self.blend_shader = value;
     }
 
@@ -812,8 +825,24 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
+    let transform_3d = this.base().transform_3d;
+    if let Some(transform_3d) = transform_3d {
+        render_projected(this, context, &transform_3d);
+        return;
+    }
     context.transform_stack.push(this.base().transform());
-    let blend_mode = this.blend_mode();
+    let mut blend_mode = this.blend_mode();
+    let blend_shader = if blend_mode == ExtendedBlendMode::Shader {
+        let blend_shader = this.blend_shader();
+        if blend_shader.is_none() {
+            // Flash lets `blendMode` be set to `BlendMode.SHADER` without a `blendShader`,
+            // but the object then renders corrupted. Drawing it normally is the closest we get.
+            blend_mode = ExtendedBlendMode::Normal;
+        }
+        blend_shader
+    } else {
+        None
+    };
     let original_commands = if blend_mode != ExtendedBlendMode::Normal {
         Some(std::mem::take(&mut context.commands))
     } else {
@@ -934,6 +963,8 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                 is_offscreen: true,
                 use_bitmap_cache: true,
                 stage: context.stage,
+                texture_budget: context.texture_budget,
+                glyph_cache: context.glyph_cache,
             };
             this.render_self(&mut offscreen_context);
             offscreen_context.cache_draws.push(BitmapCacheEntry {
@@ -986,12 +1017,8 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         let sub_commands = std::mem::replace(&mut context.commands, original_commands);
         // If there's nothing to draw, throw away the blend entirely.
         if !sub_commands.is_empty() {
-            let render_blend_mode = if let ExtendedBlendMode::Shader = blend_mode {
-                // Note - Flash appears to let you set `dobj.blendMode = BlendMode.SHADER` without
-                // having `dobj.blendShader` result, but the resulting rendered displayobject
-                // seems to be corrupted. For now, let's panic, and see if any swfs actually
-                // rely on this behavior.
-                RenderBlendMode::Shader(this.blend_shader().expect("Missing blend shader"))
+            let render_blend_mode = if let Some(blend_shader) = blend_shader {
+                RenderBlendMode::Shader(blend_shader)
             } else {
                 RenderBlendMode::Builtin(blend_mode.try_into().unwrap())
             };
@@ -1002,6 +1029,53 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     context.transform_stack.pop();
 }
 
+/// Renders an object that was placed in 3D space, with the perspective of its projection.
+///
+/// Renderers only know 2D matrices, so the object is drawn in strips that each get the matrix
+/// that matches the projection of their corners, and are cropped to their strip with a mask.
+/// Blend modes, filters and cacheAsBitmap are not applied to such objects.
+fn render_projected<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    transform_3d: &Transform3D,
+) {
+    let projection = this.projection(context.stage, transform_3d);
+    let bounds =
+        this.render_bounds_with_transform(&Matrix::IDENTITY, true, &context.stage.view_matrix());
+    let strips = projection.strips(&bounds);
+    let needs_mask = strips.len() > 1;
+    let color_transform = *this.base().color_transform();
+
+    for (strip, matrix) in strips {
+        context.transform_stack.push(&Transform {
+            matrix,
+            color_transform,
+        });
+        let strip_matrix = context.transform_stack.transform().matrix
+            * Matrix::create_box(
+                strip.width().to_pixels() as f32,
+                strip.height().to_pixels() as f32,
+                strip.x_min,
+                strip.y_min,
+            );
+        if needs_mask {
+            context.commands.push_mask();
+            // The color doesn't matter, as this is a mask.
+            context.commands.draw_rect(Color::WHITE, strip_matrix);
+            context.commands.activate_mask();
+        }
+
+        apply_standard_mask_and_scroll(this, context, |context| this.render_self(context));
+
+        if needs_mask {
+            context.commands.deactivate_mask();
+            context.commands.draw_rect(Color::WHITE, strip_matrix);
+            context.commands.pop_mask();
+        }
+        context.transform_stack.pop();
+    }
+}
+
 /// This applies the **standard** method of `mask` and `scrollRect`.
 ///
 /// It uses the stencil buffer so that any pixel drawn in the mask will allow the inner contents to show.
@@ -1706,6 +1780,72 @@ pub trait TDisplayObject<'gc>:
         self.base().scroll_rect.clone()
     }
 
+    /// The 3D part of the transform of this object, if it was placed in 3D space.
+    fn transform_3d(&self) -> Option<Transform3D> {
+        self.base().transform_3d
+    }
+
+    /// Sets the 3D part of the transform of this object, or makes it flat again with `None`.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_transform_3d(&self, gc_context: &Mutation<'gc>, transform_3d: Option<Transform3D>) {
+        let mut base = self.base_mut(gc_context);
+        if base.transform_3d == transform_3d {
+            return;
+        }
+        base.transform_3d = transform_3d;
+        base.set_transformed_by_script(true);
+        drop(base);
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// The perspective this object applies to its descendants with a 3D transform, if it has its own.
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.base().perspective_projection
+    }
+
+    fn set_perspective_projection(
+        &self,
+        gc_context: &Mutation<'gc>,
+        perspective_projection: Option<PerspectiveProjection>,
+    ) {
+        self.base_mut(gc_context).perspective_projection = perspective_projection;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// The projection of this object onto its parent when it has a 3D transform,
+    /// with the perspective of its nearest ancestor that has one.
+    fn projection(&self, stage: Stage<'gc>, transform_3d: &Transform3D) -> Projection {
+        let stage_size = stage.stage_size();
+        let mut projection = PerspectiveProjection::default_for_stage(stage_size);
+        let mut projection_center = projection.projection_center;
+
+        let mut node = self.parent();
+        while let Some(ancestor) = node {
+            if let Some(ancestor_projection) = ancestor.perspective_projection() {
+                projection = ancestor_projection;
+                projection_center = ancestor.local_to_global(projection.projection_center);
+                break;
+            }
+            node = ancestor.parent();
+        }
+
+        // The projection center is in stage coordinates now, but we're rendered within our parent.
+        if let Some(parent) = self.parent() {
+            projection_center = parent
+                .global_to_local(projection_center)
+                .unwrap_or(projection_center);
+        }
+
+        Projection::new(
+            self.base().matrix(),
+            transform_3d,
+            projection_center,
+            projection.focal_length(stage_size.0),
+        )
+    }
+
     fn next_scroll_rect(&self) -> Rectangle<Twips> {
         self.base().next_scroll_rect.clone()
     }
@@ -1800,13 +1940,17 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
-    fn blend_shader(&self) -> Option<PixelBenderShaderHandle> {
+    fn blend_shader(&self) -> Option<BlendShader> {
         self.base().blend_shader()
     }
 
-    fn set_blend_shader(&self, gc_context: &Mutation<'gc>, value: Option<PixelBenderShaderHandle>) {
+    fn set_blend_shader(&self, gc_context: &Mutation<'gc>, value: Option<BlendShader>) {
         self.base_mut(gc_context).set_blend_shader(value);
         self.set_blend_mode(gc_context, ExtendedBlendMode::Shader);
+        if let Some(parent) = self.parent() {
+            // A new shader changes how we're rendered, even if the blend mode was already set.
+            parent.invalidate_cached_bitmap(gc_context);
+        }
     }
 
     /// The opaque background color of this display object.
@@ -2052,15 +2196,6 @@ pub trait TDisplayObject<'gc>:
     /// Execute all other timeline actions on this object.
     fn run_frame_avm1(&self, _context: &mut UpdateContext<'gc>) {}
 
-    /// Emit a `frameConstructed` event on this DisplayObject and any children it
-    /// may have.
-    fn frame_constructed(&self, context: &mut UpdateContext<'gc>) {
-        let frame_constructed_evt =
-            Avm2EventObject::bare_default_event(context, "frameConstructed");
-        let dobject_constr = context.avm2.classes().display_object;
-        Avm2::broadcast_event(context, frame_constructed_evt, dobject_constr);
-    }
-
     /// Run any frame scripts (if they exist and this object needs to run them).
     fn run_frame_scripts(self, context: &mut UpdateContext<'gc>) {
         if let Some(container) = self.as_container() {
@@ -2070,15 +2205,6 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
-    /// Emit an `exitFrame` broadcast event.
-    fn exit_frame(&self, context: &mut UpdateContext<'gc>) {
-        let exit_frame_evt = Avm2EventObject::bare_default_event(context, "exitFrame");
-        let dobject_constr = context.avm2.classes().display_object;
-        Avm2::broadcast_event(context, exit_frame_evt, dobject_constr);
-
-        LoadManager::run_exit_frame(context);
-    }
-
     /// Called before the child is about to be rendered.
     /// Note that this happens even if the child is invisible
     /// (as long as the child is still on a render list)
@@ -2285,12 +2411,12 @@ pub trait TDisplayObject<'gc>:
     /// Tests if a given stage position point intersects within this object, considering the art.
     fn hit_test_shape(
         &self,
-        _context: &mut UpdateContext<'gc>,
+        context: &mut UpdateContext<'gc>,
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
         // Default to using bounding box.
-        (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
+        hit_test::is_hit_test_candidate((*self).into(), context, point, options)
             && self.hit_test_bounds(point)
     }
 
@@ -2559,6 +2685,23 @@ impl<'gc> DisplayObject<'gc> {
             _ => panic!("Downgrade not yet implemented for {:?}", self),
         }
     }
+
+    /// The name of the kind of this display object, e.g. `MovieClip`.
+    pub fn type_name(self) -> &'static str {
+        match self {
+            DisplayObject::Stage(_) => "Stage",
+            DisplayObject::Bitmap(_) => "Bitmap",
+            DisplayObject::Avm1Button(_) => "Avm1Button",
+            DisplayObject::Avm2Button(_) => "Avm2Button",
+            DisplayObject::EditText(_) => "EditText",
+            DisplayObject::Graphic(_) => "Graphic",
+            DisplayObject::MorphShape(_) => "MorphShape",
+            DisplayObject::MovieClip(_) => "MovieClip",
+            DisplayObject::Text(_) => "Text",
+            DisplayObject::Video(_) => "Video",
+            DisplayObject::LoaderDisplay(_) => "LoaderDisplay",
+        }
+    }
 }
 
 bitflags! {
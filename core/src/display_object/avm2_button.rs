@@ -16,7 +16,7 @@ use crate::display_object::interactive::{
 };
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, MovieClip};
 use crate::events::{ClipEvent, ClipEventResult};
-use crate::frame_lifecycle::catchup_display_object_to_frame;
+use crate::frame_lifecycle::{broadcast_frame_event, catchup_display_object_to_frame, exit_frame};
 use crate::prelude::*;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::vminterface::Instantiator;
@@ -367,15 +367,15 @@ impl<'gc> Avm2Button<'gc> {
                 dispatch_removed_event(old_state_child, context);
             }
 
-            if let Some(child) = child {
-                child.frame_constructed(context);
+            if child.is_some() {
+                broadcast_frame_event(context, "frameConstructed");
             }
         }
 
         if is_cur_state {
             if let Some(child) = child {
                 child.run_frame_scripts(context);
-                child.exit_frame(context);
+                exit_frame(context);
             }
         }
     }
@@ -544,10 +544,10 @@ impl<'gc> TDisplayObject<'gc> for Avm2Button<'gc> {
 
                     let stage = context.stage;
                     stage.construct_frame(context);
-                    stage.frame_constructed(context);
+                    broadcast_frame_event(context, "frameConstructed");
                     self.set_state(context, ButtonState::Up);
                     stage.run_frame_scripts(context);
-                    stage.exit_frame(context);
+                    exit_frame(context);
                 }
 
                 if let Some(avm2_object) = self.0.object.get() {
@@ -780,23 +780,10 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
         &self,
         context: &mut UpdateContext<'gc>,
         mut point: Point<Twips>,
-        require_button_mode: bool,
+        _require_button_mode: bool,
     ) -> Avm2MousePick<'gc> {
-        // The button is hovered if the mouse is over any child nodes.
+        // Only the hit state is clicked on, whatever the button currently displays.
         if self.visible() && self.mouse_enabled() {
-            let state_child = self.get_state_child(self.0.state.get().into());
-
-            if let Some(state_child) = state_child {
-                let mouse_pick = state_child
-                    .as_interactive()
-                    .map(|c| c.mouse_pick_avm2(context, point, require_button_mode));
-                match mouse_pick {
-                    None | Some(Avm2MousePick::Miss) => {}
-                    // Selecting a child of a button is equivalent to selecting the button itself
-                    _ => return Avm2MousePick::Hit((*self).into()),
-                };
-            }
-
             if let Some(hit_area) = self.0.hit_area.get() {
                 //TODO: the if below should probably always be taken, why does the hit area
                 // sometimes have a parent?
@@ -12,18 +12,22 @@ use crate::avm2::{
     Activation as Avm2Activation, EventObject as Avm2EventObject, Object as Avm2Object,
     StageObject as Avm2StageObject, TObject as _,
 };
+use crate::backend::navigator::Request;
 use crate::backend::ui::MouseCursor;
-use crate::context::{RenderContext, UpdateContext};
+use crate::context::{ActionType, RenderContext, UpdateContext};
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
+use crate::display_object::MovieClip;
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
-use crate::events::{ClipEvent, ClipEventResult, TextControlCode};
+use crate::events::{ClipEvent, ClipEventResult, MouseWheelDelta, TextControlCode};
 use crate::font::{FontType, Glyph, TextRenderSettings};
 use crate::html;
 use crate::html::{
-    FormatSpans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics, Position, TextFormat,
+    FormatSpans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics, Position, Size,
+    TextFormat, TextImage,
 };
+use crate::loader::MovieLoaderVMData;
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::SwfMovie;
@@ -44,6 +48,9 @@ use unic_segment::WordBoundIndices;
 
 use super::interactive::Avm2MousePick;
 
+#[cfg(test)]
+mod test;
+
 /// The kind of autosizing behavior an `EditText` should have, if any
 #[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
 #[collect(no_drop)]
@@ -176,6 +183,29 @@ pub struct EditTextData<'gc> {
     /// Information related to the last click event inside this text field.
     #[collect(require_static)]
     last_click: Option<ClickEventData>,
+
+    /// The display objects showing the images embedded with `<img>` tags.
+    ///
+    /// They are created on the next frame after the tags were set,
+    /// in the same order as `FormatSpans::images`.
+    images: Vec<EmbeddedImage<'gc>>,
+
+    /// The sizes of the images that the current layout was calculated with.
+    #[collect(require_static)]
+    image_sizes: Vec<Size<Twips>>,
+}
+
+/// A display object showing an image embedded in the text with an `<img>` tag.
+///
+/// This is either an instance of a library symbol, or a clip the image is loaded into.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct EmbeddedImage<'gc> {
+    /// The `src` attribute of the tag this object was created for.
+    #[collect(require_static)]
+    src: WString,
+
+    object: DisplayObject<'gc>,
 }
 
 impl<'gc> EditTextData<'gc> {
@@ -243,6 +273,7 @@ impl<'gc> EditText<'gc> {
             swf_tag.bounds().width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0),
             swf_tag.is_word_wrap(),
             font_type,
+            &[],
         );
 
         let mut base = InteractiveObjectBase::default();
@@ -309,6 +340,8 @@ impl<'gc> EditText<'gc> {
                 restrict: EditTextRestrict::allow_all(),
                 last_click: None,
                 layout_debug_boxes_flags: LayoutDebugBoxesFlag::empty(),
+                images: Vec::new(),
+                image_sizes: Vec::new(),
             },
         ));
 
@@ -755,6 +788,10 @@ impl<'gc> EditText<'gc> {
         let is_word_wrap = edit_text.flags.contains(EditTextFlag::WORD_WRAP);
         let movie = edit_text.static_data.swf.clone();
         let padding = Twips::from_pixels(EditText::INTERNAL_PADDING) * 2;
+        drop(edit_text);
+
+        let old_scroll_state = self.scroll_state();
+        let mut edit_text = self.0.write(context.gc_context);
 
         if edit_text.flags.contains(EditTextFlag::PASSWORD) {
             // If the text is a password, hide the text
@@ -779,19 +816,51 @@ impl<'gc> EditText<'gc> {
             FontType::Embedded
         };
 
-        let new_layout = html::lower_from_text_spans(
+        let image_sizes: Vec<_> = edit_text
+            .text_spans
+            .images()
+            .iter()
+            .enumerate()
+            .map(|(index, image)| {
+                let object = edit_text
+                    .images
+                    .get(index)
+                    .filter(|embedded| embedded.src == image.src)
+                    .map(|embedded| embedded.object);
+                Self::embedded_image_size(image, object)
+            })
+            .collect();
+
+        let mut new_layout = html::lower_from_text_spans(
             &edit_text.text_spans,
             context,
-            movie,
+            movie.clone(),
             content_width,
             is_word_wrap,
             font_type,
+            &image_sizes,
         );
 
+        // Unwrapped autosized text is aligned within its widest line, which is only known
+        // after laying it out. Otherwise centered or right aligned text would be offset by
+        // the width the field had before.
+        if autosize != AutoSizeMode::None && !is_word_wrap {
+            let text_width = new_layout.exterior_bounds().width();
+            if text_width != content_width {
+                new_layout = html::lower_from_text_spans(
+                    &edit_text.text_spans,
+                    context,
+                    movie,
+                    text_width,
+                    is_word_wrap,
+                    font_type,
+                    &image_sizes,
+                );
+            }
+        }
+
         edit_text.layout = new_layout;
-        // reset scroll
-        edit_text.hscroll = 0.0;
-        edit_text.scroll = 1;
+        edit_text.image_sizes = image_sizes;
 
         let layout_exterior_bounds = edit_text.layout.exterior_bounds();
 
@@ -822,7 +891,184 @@ impl<'gc> EditText<'gc> {
             edit_text.bounds.set_height(height);
         }
         drop(edit_text);
+
+        // Keep the scroll position, as long as it's still in range for the new text.
+        let scroll = self.scroll().clamp(1, self.maxscroll());
+        let hscroll = self.hscroll().clamp(0.0, self.maxhscroll());
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.scroll = scroll;
+        edit_text.hscroll = hscroll;
+        drop(edit_text);
+
         self.invalidate_cached_bitmap(context.gc_context);
+
+        // `onScroller` is also sent when only the limits changed, e.g. when appending a line.
+        if self.scroll_state() != old_scroll_state {
+            self.queue_on_scroller(context);
+        }
+    }
+
+    /// The current and maximum scroll positions, in both directions.
+    fn scroll_state(self) -> (usize, usize, f64, f64) {
+        (
+            self.scroll(),
+            self.maxscroll(),
+            self.hscroll(),
+            self.maxhscroll(),
+        )
+    }
+
+    /// The size that an embedded image takes in the text.
+    ///
+    /// This is the size given by the `<img>` tag, or otherwise the size of the
+    /// image's content, scaled proportionally if only one dimension was given.
+    fn embedded_image_size(image: &TextImage, object: Option<DisplayObject<'gc>>) -> Size<Twips> {
+        let content_bounds = object
+            .map(|object| object.bounds_with_transform(&Matrix::IDENTITY))
+            .filter(|bounds| bounds.is_valid())
+            .unwrap_or(Rectangle::ZERO);
+        let content_width = content_bounds.width().to_pixels();
+        let content_height = content_bounds.height().to_pixels();
+
+        let (width, height) = match (image.width, image.height) {
+            (Some(width), Some(height)) => (width, height),
+            (Some(width), None) if content_width > 0.0 => {
+                (width, content_height * width / content_width)
+            }
+            (None, Some(height)) if content_height > 0.0 => {
+                (content_width * height / content_height, height)
+            }
+            _ => (content_width, content_height),
+        };
+        Size::from((Twips::from_pixels(width), Twips::from_pixels(height)))
+    }
+
+    /// Creates the display objects of newly embedded images, and lays out the
+    /// text again when the size of an image changed, e.g. because it finished loading.
+    fn update_images(self, context: &mut UpdateContext<'gc>) {
+        let text_images = self.0.read().text_spans.images().to_vec();
+        let is_up_to_date = {
+            let edit_text = self.0.read();
+            edit_text.images.len() == text_images.len()
+                && edit_text
+                    .images
+                    .iter()
+                    .zip(&text_images)
+                    .all(|(embedded, image)| embedded.src == image.src)
+        };
+
+        if !is_up_to_date {
+            let old_images = std::mem::take(&mut self.0.write(context.gc_context).images);
+            let mut images = Vec::with_capacity(text_images.len());
+            for image in &text_images {
+                // Reuse the objects of images that are still embedded.
+                let object = match old_images.iter().find(|old| old.src == image.src) {
+                    Some(old) => old.object,
+                    None => self.create_embedded_image(context, &image.src),
+                };
+                images.push(EmbeddedImage {
+                    src: image.src.clone(),
+                    object,
+                });
+            }
+            for old in old_images {
+                if !images
+                    .iter()
+                    .any(|image| DisplayObject::ptr_eq(image.object, old.object))
+                {
+                    old.object.set_parent(context, None);
+                }
+            }
+            self.0.write(context.gc_context).images = images;
+        }
+
+        let needs_relayout = {
+            let edit_text = self.0.read();
+            text_images
+                .iter()
+                .zip(&edit_text.images)
+                .map(|(image, embedded)| Self::embedded_image_size(image, Some(embedded.object)))
+                .ne(edit_text.image_sizes.iter().copied())
+        };
+        if needs_relayout {
+            self.relayout(context);
+        }
+    }
+
+    /// Creates the display object of an embedded image.
+    ///
+    /// `src` is first looked up as the linkage name of a library symbol,
+    /// and otherwise loaded as the URL of an image or SWF.
+    fn create_embedded_image(
+        self,
+        context: &mut UpdateContext<'gc>,
+        src: &WStr,
+    ) -> DisplayObject<'gc> {
+        let movie = self.movie();
+        let object = match self.instantiate_library_symbol(context, src) {
+            Some(object) => object,
+            None => {
+                let clip: DisplayObject<'gc> =
+                    MovieClip::new(movie.clone(), context.gc_context).into();
+                let future = context.load_manager.load_movie_into_clip(
+                    context.player.clone(),
+                    clip,
+                    Request::get(src.to_utf8_lossy().into_owned()),
+                    None,
+                    MovieLoaderVMData::Avm1 { broadcaster: None },
+                );
+                context.navigator.spawn_future(future);
+                clip
+            }
+        };
+
+        object.set_parent(context, Some(self.into()));
+        object.post_instantiation(context, None, Instantiator::Movie, false);
+        if movie.is_action_script_3() {
+            object.construct_frame(context);
+        }
+        object
+    }
+
+    /// Instantiates the library symbol with the given linkage name, if there is one.
+    fn instantiate_library_symbol(
+        self,
+        context: &mut UpdateContext<'gc>,
+        name: &WStr,
+    ) -> Option<DisplayObject<'gc>> {
+        let movie = self.movie();
+        let library = context.library.library_for_movie(movie.clone())?;
+
+        if !movie.is_action_script_3() {
+            let name = AvmString::new(context.gc_context, name);
+            let (id, _) = library.character_by_export_name(name)?;
+            return library.instantiate_by_id(id, context.gc_context).ok();
+        }
+
+        let domain = library.avm2_domain();
+        let mut activation = Avm2Activation::from_domain(context, domain);
+        let name = AvmString::new(activation.context.gc_context, name);
+        let class = domain
+            .get_defined_value_handling_vector(&mut activation, name)
+            .ok()?
+            .as_object()?
+            .as_class_object()?;
+        class
+            .construct(&mut activation, &[])
+            .ok()?
+            .as_display_object()
+    }
+
+    /// Returns the display object of the image embedded with the given `id`.
+    pub fn image_reference(self, id: &WStr) -> Option<DisplayObject<'gc>> {
+        let edit_text = self.0.read();
+        edit_text
+            .text_spans
+            .images()
+            .iter()
+            .zip(&edit_text.images)
+            .find(|(image, _)| image.id.as_deref() == Some(id))
+            .map(|(_, embedded)| embedded.object)
     }
 
     /// Measure the width and height of the `EditText`'s current text load.
@@ -1027,7 +1273,7 @@ impl<'gc> EditText<'gc> {
                 self.text_transform(color, baseline_adjustment),
                 params,
                 |pos, transform, glyph: &Glyph, advance, x| {
-                    if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
+                    if glyph.shape_handle(context.renderer).is_some() {
                         // If it's highlighted, override the color.
                         if matches!(visible_selection, Some(visible_selection) if visible_selection.contains(start + pos)) {
                             // Draw selection rect
@@ -1043,9 +1289,7 @@ impl<'gc> EditText<'gc> {
                         }
 
                         // Render glyph.
-                        context
-                            .commands
-                            .render_shape(glyph_shape_handle, context.transform_stack.transform());
+                        glyph.render(context, &edit_text.render_settings);
                         context.transform_stack.pop();
                     }
 
@@ -1305,7 +1549,24 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn set_hscroll(self, hscroll: f64, context: &mut UpdateContext<'gc>) {
-        self.0.write(context.gc_context).hscroll = hscroll;
+        let old_hscroll = std::mem::replace(&mut self.0.write(context.gc()).hscroll, hscroll);
+        if old_hscroll != hscroll {
+            self.invalidate_cached_bitmap(context.gc());
+            self.dispatch_scroll_event(context);
+        }
+    }
+
+    /// Scrolls this text field sideways in response to a horizontal wheel event.
+    pub fn scroll_horizontally(self, delta: MouseWheelDelta, context: &mut UpdateContext<'gc>) {
+        /// How many pixels a single wheel line moves the text.
+        const HSCROLL_PIXELS_PER_LINE: f64 = 20.0;
+
+        if !self.is_mouse_wheel_enabled() {
+            return;
+        }
+
+        let new_hscroll = self.hscroll() - delta.lines() * HSCROLL_PIXELS_PER_LINE;
+        self.set_hscroll(new_hscroll.clamp(0.0, self.maxhscroll()), context);
     }
 
     pub fn scroll(self) -> usize {
@@ -1322,8 +1583,41 @@ impl<'gc> EditText<'gc> {
             scroll as usize
         };
         let clamped = scroll_lines.clamp(1, self.maxscroll());
-        self.0.write(context.gc()).scroll = clamped;
-        self.invalidate_cached_bitmap(context.gc());
+        let old_scroll = std::mem::replace(&mut self.0.write(context.gc()).scroll, clamped);
+        if old_scroll != clamped {
+            self.invalidate_cached_bitmap(context.gc());
+            self.dispatch_scroll_event(context);
+        }
+    }
+
+    /// Dispatches `Event.SCROLL` after the text was scrolled in either direction.
+    ///
+    /// AVM1 text fields are sent `onScroller` instead.
+    fn dispatch_scroll_event(self, context: &mut UpdateContext<'gc>) {
+        if let Avm2Value::Object(object) = self.object2() {
+            let scroll_evt = Avm2EventObject::bare_default_event(context, "scroll");
+            Avm2::dispatch_event(context, scroll_evt, object);
+        } else {
+            self.queue_on_scroller(context);
+        }
+    }
+
+    /// Queues `onScroller` to be broadcast to the listeners of an AVM1 text field.
+    ///
+    /// Like in Flash Player, the listeners run after the script changing the text or the
+    /// scroll position, so they see the final values of all scroll properties.
+    fn queue_on_scroller(self, context: &mut UpdateContext<'gc>) {
+        if let Avm1Value::Object(object) = self.object() {
+            context.action_queue.queue_action(
+                self.into(),
+                ActionType::Method {
+                    object,
+                    name: "broadcastMessage",
+                    args: vec!["onScroller".into(), object.into()],
+                },
+                false,
+            );
+        }
     }
 
     pub fn max_chars(self) -> i32 {
@@ -1552,11 +1846,20 @@ impl<'gc> EditText<'gc> {
                 let text = WString::from_utf8(&text);
                 let mut text = text.as_wstr();
 
-                if text.len() > self.available_chars() && self.available_chars() > 0 {
-                    text = &text[0..self.available_chars()];
+                let available_chars = self.available_chars();
+                if text.len() > available_chars && available_chars > 0 {
+                    // Don't split a surrogate pair when cutting the text at maxChars.
+                    let mut end = available_chars;
+                    if text
+                        .get(end)
+                        .is_some_and(|unit| (0xDC00..0xE000).contains(&unit))
+                    {
+                        end -= 1;
+                    }
+                    text = &text[0..end];
                 }
 
-                if text.len() <= self.available_chars() {
+                if text.len() <= available_chars {
                     self.replace_text(selection.start(), selection.end(), text, context);
                     let new_pos = selection.start() + text.len();
                     if is_selectable {
@@ -1683,6 +1986,10 @@ impl<'gc> EditText<'gc> {
     ///
     /// This algorithm is based on [UAX #29](https://unicode.org/reports/tr29/).
     fn find_prev_word_boundary(self, pos: usize, stop_on_space: bool) -> usize {
+        if self.is_password() {
+            // Masked text has no visible words, so it shouldn't reveal where they are.
+            return 0;
+        }
         let head = &self.text()[..pos];
         if stop_on_space && head.ends_with(ruffle_wstr::utils::swf_is_whitespace) {
             return pos;
@@ -1703,6 +2010,9 @@ impl<'gc> EditText<'gc> {
     ///
     /// This algorithm is based on [UAX #29](https://unicode.org/reports/tr29/).
     fn find_next_word_boundary(self, pos: usize, stop_on_space: bool) -> usize {
+        if self.is_password() {
+            return self.text().len();
+        }
         let tail = &self.text()[pos..];
         if stop_on_space && tail.starts_with(ruffle_wstr::utils::swf_is_whitespace) {
             return pos;
@@ -1771,6 +2081,10 @@ impl<'gc> EditText<'gc> {
             return;
         };
 
+        if character.len_utf16() > self.available_chars() {
+            return;
+        }
+
         if let Avm2Value::Object(target) = self.object2() {
             let character_string = AvmString::new_utf8(context.gc_context, character.to_string());
 
@@ -1795,7 +2109,7 @@ impl<'gc> EditText<'gc> {
             &WString::from_char(character),
             context,
         );
-        let new_pos = selection.start() + character.len_utf8();
+        let new_pos = selection.start() + character.len_utf16();
         self.set_selection(
             Some(TextSelection::for_position(new_pos)),
             context.gc_context,
@@ -1850,18 +2164,6 @@ impl<'gc> EditText<'gc> {
         }
     }
 
-    fn on_scroller(&self, activation: &mut Avm1Activation<'_, 'gc>) {
-        if let Avm1Value::Object(object) = self.object() {
-            let _ = object.call_method(
-                "broadcastMessage".into(),
-                &["onScroller".into(), object.into()],
-                activation,
-                ExecutionReason::Special,
-            );
-        }
-        //TODO: Implement this for Avm2
-    }
-
     /// Construct the text field's AVM1 representation.
     fn construct_as_avm1_object(&self, context: &mut UpdateContext<'gc>, run_frame: bool) {
         let mut text = self.0.write(context.gc_context);
@@ -2183,8 +2485,17 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         }
     }
 
-    fn run_frame_avm1(&self, _context: &mut UpdateContext) {
-        // Noop
+    fn enter_frame(&self, context: &mut UpdateContext<'gc>) {
+        self.update_images(context);
+
+        let images: Vec<_> = self.0.read().images.iter().map(|i| i.object).collect();
+        for image in images {
+            image.enter_frame(context);
+        }
+    }
+
+    fn run_frame_avm1(&self, context: &mut UpdateContext<'gc>) {
+        self.update_images(context);
     }
 
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
@@ -2382,6 +2693,12 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             self.render_layout_line(context, line);
         }
 
+        for layout_image in edit_text.layout.images() {
+            if let Some(embedded) = edit_text.images.get(layout_image.index()) {
+                Self::render_embedded_image(context, embedded.object, layout_image.bounds());
+            }
+        }
+
         self.render_debug_boxes(
             context,
             edit_text.layout_debug_boxes_flags,
@@ -2439,6 +2756,32 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 }
 
 impl<'gc> EditText<'gc> {
+    /// Render an embedded image, scaled to fill the given bounds.
+    fn render_embedded_image(
+        context: &mut RenderContext<'_, 'gc>,
+        object: DisplayObject<'gc>,
+        bounds: html::BoxBounds<Twips>,
+    ) {
+        let content_bounds = object.bounds_with_transform(&Matrix::IDENTITY);
+        if !content_bounds.is_valid()
+            || content_bounds.width() == Twips::ZERO
+            || content_bounds.height() == Twips::ZERO
+        {
+            return;
+        }
+
+        let scale_x = bounds.width().get() as f32 / content_bounds.width().get() as f32;
+        let scale_y = bounds.height().get() as f32 / content_bounds.height().get() as f32;
+        context.transform_stack.push(&Transform {
+            matrix: Matrix::translate(bounds.offset_x(), bounds.offset_y())
+                * Matrix::scale(scale_x, scale_y)
+                * Matrix::translate(-content_bounds.x_min, -content_bounds.y_min),
+            ..Default::default()
+        });
+        object.render(context);
+        context.transform_stack.pop();
+    }
+
     /// Draw the box (border + background) for EditText with device fonts.
     ///
     /// Notes on FP's behavior:
@@ -2603,13 +2946,6 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
             if self.is_mouse_wheel_enabled() {
                 let new_scroll = self.scroll() as f64 - delta.lines();
                 self.set_scroll(new_scroll, context);
-
-                let mut activation = Avm1Activation::from_nothing(
-                    context,
-                    ActivationIdentifier::root("[On Scroller]"),
-                    self.into(),
-                );
-                self.on_scroller(&mut activation);
             }
             return ClipEventResult::Handled;
         }
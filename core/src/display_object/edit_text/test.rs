@@ -0,0 +1,195 @@
+//! Tests for the layout, scrolling and input restrictions of text fields.
+
+use super::{AutoSizeMode, EditText, EditTextRestrict, TextSelectionMode};
+use crate::backend::ui::FontDefinition;
+use crate::context::UpdateContext;
+use crate::font::DefaultFont;
+use crate::player::PlayerBuilder;
+use crate::string::{WStr, WString};
+use crate::tag_utils::SwfMovie;
+use std::sync::Arc;
+use swf::Twips;
+
+const DEVICE_FONT_TAG: &[u8] = include_bytes!("../../../assets/noto-sans-definefont3.bin");
+
+/// Runs a test with a multiline text field of the given size, using Noto Sans as the device font.
+fn with_text_field<F>(width: f64, height: f64, test: F)
+where
+    F: for<'gc> FnOnce(&mut UpdateContext<'gc>, EditText<'gc>),
+{
+    let movie = SwfMovie::empty(10);
+    let player = PlayerBuilder::new().with_movie(movie.clone()).build();
+    let mut player = player.lock().unwrap();
+
+    let mut font_reader = swf::read::Reader::new(DEVICE_FONT_TAG, 8);
+    let font_tag = font_reader
+        .read_define_font_2(3)
+        .expect("Built-in font should compile");
+    player.register_device_font(FontDefinition::SwfTag(font_tag, font_reader.encoding()));
+    player.set_default_font(DefaultFont::Sans, vec!["Noto Sans".to_string()]);
+    player.set_default_font(DefaultFont::Serif, vec!["Noto Sans".to_string()]);
+
+    player.mutate_with_update_context(|context| {
+        let text = EditText::new(context, Arc::new(movie), 0.0, 0.0, width, height);
+        text.set_multiline(true, context);
+        test(context, text);
+    });
+}
+
+fn lines(count: usize) -> WString {
+    let text: Vec<_> = (1..=count).map(|i| format!("line {i}")).collect();
+    WString::from_utf8(&text.join("\r"))
+}
+
+#[test]
+fn scroll_is_kept_when_text_changes() {
+    with_text_field(100.0, 50.0, |context, text| {
+        text.set_text(&lines(20), context);
+        assert!(text.maxscroll() > 5);
+        text.set_scroll(5.0, context);
+
+        // Appending to a chat log keeps the lines in view.
+        text.set_text(&lines(30), context);
+        assert_eq!(text.scroll(), 5);
+
+        // ...unless there aren't enough of them left.
+        text.set_text(&lines(1), context);
+        assert_eq!(text.scroll(), 1);
+    });
+}
+
+#[test]
+fn maxscroll_shows_the_last_line() {
+    with_text_field(100.0, 50.0, |context, text| {
+        text.set_text(&lines(20), context);
+        text.set_scroll(1000.0, context);
+        assert_eq!(text.scroll(), text.maxscroll());
+        assert_eq!(text.bottom_scroll(), 20);
+
+        text.set_scroll(1.0, context);
+        assert!(text.bottom_scroll() < 20);
+    });
+}
+
+#[test]
+fn hscroll_is_clamped_when_text_shrinks() {
+    with_text_field(50.0, 50.0, |context, text| {
+        text.set_text(
+            &WString::from_utf8("a single line that is too wide"),
+            context,
+        );
+        assert!(text.maxhscroll() > 0.0);
+        text.set_hscroll(text.maxhscroll(), context);
+
+        text.set_text(&WString::from_utf8("short"), context);
+        assert_eq!(text.hscroll(), 0.0);
+    });
+}
+
+#[test]
+fn word_wrapped_text_does_not_scroll_horizontally() {
+    with_text_field(50.0, 50.0, |context, text| {
+        text.set_word_wrap(true, context);
+        text.set_text(
+            &WString::from_utf8("a single line that is too wide"),
+            context,
+        );
+        assert_eq!(text.maxhscroll(), 0.0);
+    });
+}
+
+#[test]
+fn autosize_with_word_wrap_only_changes_height() {
+    with_text_field(100.0, 50.0, |context, text| {
+        text.set_word_wrap(true, context);
+        text.set_autosize(AutoSizeMode::Left, context);
+        text.set_text(&lines(10), context);
+
+        let bounds = text.0.read().bounds.clone();
+        assert_eq!(bounds.width(), Twips::from_pixels(100.0));
+        assert!(bounds.height() > Twips::from_pixels(50.0));
+    });
+}
+
+#[test]
+fn autosize_keeps_aligned_text_in_bounds() {
+    for align in [swf::TextAlign::Center, swf::TextAlign::Right] {
+        with_text_field(100.0, 50.0, |context, text| {
+            let mut format = text.new_text_format();
+            format.align = Some(align);
+            text.set_new_text_format(format, context);
+            text.set_autosize(AutoSizeMode::Left, context);
+
+            text.set_text(&WString::from_utf8("a much longer line of text"), context);
+            text.set_text(&WString::from_utf8("short"), context);
+
+            let edit_text = text.0.read();
+            let exterior_bounds = edit_text.layout.exterior_bounds();
+            assert_eq!(exterior_bounds.offset_x(), Twips::ZERO);
+            assert_eq!(
+                edit_text.bounds.width(),
+                exterior_bounds.width() + Twips::from_pixels(EditText::INTERNAL_PADDING) * 2
+            );
+        });
+    }
+}
+
+fn restrict(value: &str) -> EditTextRestrict {
+    EditTextRestrict::from(Some(WString::from_utf8(value).as_wstr()))
+}
+
+#[test]
+fn restrict_ranges_and_negation() {
+    let digits = restrict("0-9");
+    assert!(digits.is_allowed('5'));
+    assert!(!digits.is_allowed('a'));
+
+    let no_digits = restrict("^0-9");
+    assert!(no_digits.is_allowed('a'));
+    assert!(!no_digits.is_allowed('5'));
+
+    let letters_but_m = restrict("a-z^m");
+    assert!(letters_but_m.is_allowed('a'));
+    assert!(!letters_but_m.is_allowed('m'));
+
+    // Inverted and truncated ranges only allow their first character.
+    let inverted = restrict("z-a");
+    assert!(inverted.is_allowed('z'));
+    assert!(!inverted.is_allowed('m'));
+    assert!(restrict("a-").is_allowed('a'));
+    assert!(!restrict("a-").is_allowed('-'));
+}
+
+#[test]
+fn restrict_escapes() {
+    let escaped = restrict("\\^\\-\\\\");
+    assert!(escaped.is_allowed('^'));
+    assert!(escaped.is_allowed('-'));
+    assert!(escaped.is_allowed('\\'));
+    assert!(!escaped.is_allowed('a'));
+}
+
+#[test]
+fn restrict_changes_case_of_input() {
+    let upper = restrict("A-Z");
+    assert_eq!(upper.to_allowed('q'), Some('Q'));
+    assert_eq!(upper.to_allowed('1'), None);
+    assert_eq!(upper.filter_allowed("a1b2"), "AB");
+}
+
+#[test]
+fn restrict_empty_and_null() {
+    assert!(!EditTextRestrict::from(Some(WStr::empty())).is_allowed('a'));
+    assert!(EditTextRestrict::from(None).is_allowed('a'));
+}
+
+#[test]
+fn password_is_selected_as_a_single_word() {
+    with_text_field(100.0, 50.0, |context, text| {
+        text.set_password(true, context);
+        text.set_text(&WString::from_utf8("two words"), context);
+
+        let selection = text.calculate_selection_at(1, TextSelectionMode::Word);
+        assert_eq!((selection.start(), selection.end()), (0, 9));
+    });
+}
@@ -1,19 +1,20 @@
+use crate::asset_cache::{self, ShapeKey};
 use crate::avm1::Object as Avm1Object;
 use crate::avm2::{
     Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
 };
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
+use crate::display_object::{hit_test, DisplayObjectBase, DisplayObjectPtr};
 use crate::drawing::Drawing;
-use crate::library::MovieLibrarySource;
+use crate::library::{Library, MovieLibrarySource};
 use crate::prelude::*;
-use crate::tag_utils::SwfMovie;
+use crate::tag_utils::{Error, SwfMovie, SwfSlice};
 use crate::vminterface::Instantiator;
 use core::fmt;
 use gc_arena::{Collect, GcCell, Mutation};
-use ruffle_render::backend::ShapeHandle;
+use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::commands::CommandHandler;
-use std::cell::{Ref, RefMut};
+use std::cell::{OnceCell, Ref, RefCell, RefMut};
 use std::sync::Arc;
 
 #[derive(Clone, Collect, Copy)]
@@ -40,26 +41,29 @@ pub struct GraphicData<'gc> {
 }
 
 impl<'gc> Graphic<'gc> {
-    /// Construct a `Graphic` from it's associated `Shape` tag.
+    /// Construct a `Graphic` from the body of its associated `DefineShape` tag.
+    ///
+    /// Only the character ID and bounds are read here. The rest of the tag is
+    /// parsed and tessellated the first time the shape is needed.
     pub fn from_swf_tag(
         context: &mut UpdateContext<'gc>,
-        swf_shape: swf::Shape,
-        movie: Arc<SwfMovie>,
-    ) -> Self {
-        let library = context.library.library_for_movie(movie.clone()).unwrap();
+        tag: SwfSlice,
+        version: u8,
+    ) -> Result<Self, Error> {
+        let mut reader = tag.read_from(0);
+        let id = reader.read_character_id()?;
+        let bounds = reader.read_rectangle()?;
         let static_data = GraphicStatic {
-            id: swf_shape.id,
-            bounds: swf_shape.shape_bounds.clone(),
-            render_handle: Some(
-                context
-                    .renderer
-                    .register_shape((&swf_shape).into(), &MovieLibrarySource { library }),
-            ),
-            shape: swf_shape,
-            movie,
+            id,
+            bounds,
+            movie: tag.movie.clone(),
+            tag,
+            version,
+            shape: OnceCell::new(),
+            render_handle: RefCell::new(None),
         };
 
-        Graphic(GcCell::new(
+        Ok(Graphic(GcCell::new(
             context.gc_context,
             GraphicData {
                 base: Default::default(),
@@ -67,7 +71,7 @@ impl<'gc> Graphic<'gc> {
                 avm2_object: None,
                 drawing: None,
             },
-        ))
+        )))
     }
 
     /// Construct an empty `Graphic`.
@@ -75,19 +79,10 @@ impl<'gc> Graphic<'gc> {
         let static_data = GraphicStatic {
             id: 0,
             bounds: Default::default(),
-            render_handle: None,
-            shape: swf::Shape {
-                version: 32,
-                id: 0,
-                shape_bounds: Default::default(),
-                edge_bounds: Default::default(),
-                flags: swf::ShapeFlag::empty(),
-                styles: swf::ShapeStyles {
-                    fill_styles: Vec::new(),
-                    line_styles: Vec::new(),
-                },
-                shape: Vec::new(),
-            },
+            tag: SwfSlice::empty(context.swf.clone()),
+            version: 1,
+            shape: OnceCell::from(empty_shape(0)),
+            render_handle: RefCell::new(None),
             movie: context.swf.clone(),
         };
 
@@ -179,7 +174,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         // Noop
     }
 
-    fn render_self(&self, context: &mut RenderContext) {
+    fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
         if !context.is_offscreen && !self.world_bounds().intersects(&context.stage.view_bounds()) {
             // Off-screen; culled
             return;
@@ -187,7 +182,16 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 
         if let Some(drawing) = &self.0.read().drawing {
             drawing.render(context);
-        } else if let Some(render_handle) = self.0.read().static_data.render_handle.clone() {
+            return;
+        }
+
+        let static_data = self.0.read().static_data;
+        if let Some((render_handle, size)) =
+            static_data.render_handle(context.renderer, context.library)
+        {
+            context
+                .texture_budget
+                .touch_shape(context.gc_context, static_data, size);
             context
                 .commands
                 .render_shape(render_handle, context.transform_stack.transform())
@@ -196,13 +200,13 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 
     fn hit_test_shape(
         &self,
-        _context: &mut UpdateContext<'gc>,
+        context: &mut UpdateContext<'gc>,
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
         // Transform point to local coordinates and test.
-        if (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
-            && self.world_bounds().contains(point)
+        if self.world_bounds().contains(point)
+            && hit_test::is_hit_test_candidate((*self).into(), context, point, options)
         {
             let Some(local_matrix) = self.global_to_local_matrix() else {
                 return false;
@@ -213,7 +217,8 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
                     return true;
                 }
             } else {
-                let shape = &self.0.read().static_data.shape;
+                let read = self.0.read();
+                let shape = read.static_data.shape();
                 return ruffle_render::shape_utils::shape_hit_test(shape, point, &local_matrix);
             }
         }
@@ -266,10 +271,90 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 #[allow(dead_code)]
 #[derive(Collect)]
 #[collect(require_static)]
-struct GraphicStatic {
+pub struct GraphicStatic {
     id: CharacterId,
-    shape: swf::Shape,
-    render_handle: Option<ShapeHandle>,
     bounds: Rectangle<Twips>,
+
+    /// The body of the `DefineShape` tag that defines this graphic.
+    tag: SwfSlice,
+
+    /// The version of the `DefineShape` tag.
+    version: u8,
+
+    /// The parsed shape, decoded from `tag` on first use.
+    shape: OnceCell<swf::Shape>,
+
+    /// The tessellated shape, registered with the renderer on first use, and the
+    /// GPU memory it takes in bytes.
+    ///
+    /// This is released when over the texture budget, and registered again when next used.
+    render_handle: RefCell<Option<(ShapeHandle, usize)>>,
     movie: Arc<SwfMovie>,
 }
+
+impl GraphicStatic {
+    fn shape(&self) -> &swf::Shape {
+        self.shape.get_or_init(|| {
+            self.tag
+                .read_from(0)
+                .read_define_shape(self.version)
+                .unwrap_or_else(|e| {
+                    tracing::error!("Couldn't parse DefineShape {}: {}", self.id, e);
+                    empty_shape(self.id)
+                })
+        })
+    }
+
+    fn render_handle(
+        &self,
+        renderer: &mut dyn RenderBackend,
+        library: &Library<'_>,
+    ) -> Option<(ShapeHandle, usize)> {
+        if let Some(handle) = &*self.render_handle.borrow() {
+            return Some(handle.clone());
+        }
+
+        // Shapes without any records have nothing to draw.
+        if self.shape().shape.is_empty() {
+            return None;
+        }
+
+        // The movie's library may not be registered yet, so a missing library
+        // isn't cached; the shape is registered once it's available.
+        let library = library.library_for_movie(self.movie.clone())?;
+        let handle = asset_cache::register_shape_with_size(
+            renderer,
+            ShapeKey::shape(&self.movie, self.id),
+            || self.shape().into(),
+            &MovieLibrarySource { library },
+        );
+        *self.render_handle.borrow_mut() = Some(handle.clone());
+        Some(handle)
+    }
+
+    /// Whether this shape is currently registered with the renderer.
+    pub fn has_render_handle(&self) -> bool {
+        self.render_handle.borrow().is_some()
+    }
+
+    /// Release the renderer's copy of this shape, which is registered again the next
+    /// time it's rendered.
+    pub fn release_render_handle(&self) {
+        *self.render_handle.borrow_mut() = None;
+    }
+}
+
+fn empty_shape(id: CharacterId) -> swf::Shape {
+    swf::Shape {
+        version: 32,
+        id,
+        shape_bounds: Default::default(),
+        edge_bounds: Default::default(),
+        flags: swf::ShapeFlag::empty(),
+        styles: swf::ShapeStyles {
+            fill_styles: Vec::new(),
+            line_styles: Vec::new(),
+        },
+        shape: Vec::new(),
+    }
+}
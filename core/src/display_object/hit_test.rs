@@ -0,0 +1,84 @@
+//! Hit testing rules that are shared by every kind of display object,
+//! and by both ActionScript's `hitTest`/`hitTestPoint` and mouse picking.
+
+use crate::context::UpdateContext;
+use crate::prelude::*;
+
+/// Checks whether an object may be hit at the given stage position at all,
+/// before its content gets tested.
+///
+/// Invisible objects can't be hit (unless they are a mask, as masks are always invisible),
+/// masks can't be hit when `SKIP_MASK` is set, and the position must be inside the mask
+/// and the scroll rect of the object, as nothing of it is drawn outside of them.
+pub fn is_hit_test_candidate<'gc>(
+    object: DisplayObject<'gc>,
+    context: &mut UpdateContext<'gc>,
+    point: Point<Twips>,
+    options: HitTestOptions,
+) -> bool {
+    if options.contains(HitTestOptions::SKIP_INVISIBLE)
+        && !object.visible()
+        && object.maskee().is_none()
+    {
+        return false;
+    }
+
+    if options.contains(HitTestOptions::SKIP_MASK) && object.maskee().is_some() {
+        return false;
+    }
+
+    if !is_within_scroll_rect(object, point) {
+        return false;
+    }
+
+    if let Some(masker) = object.masker() {
+        if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Checks whether a stage position is inside the scroll rect of an object, if it has one.
+pub fn is_within_scroll_rect(object: DisplayObject<'_>, point: Point<Twips>) -> bool {
+    let Some(scroll_rect) = object.scroll_rect() else {
+        return true;
+    };
+    object
+        .global_to_local_matrix()
+        .is_some_and(|matrix| scroll_rect.contains(matrix * point))
+}
+
+/// Tests a stage position against an object, as `hitTest` in AVM1 and `hitTestPoint` in AVM2 do.
+///
+/// When testing against the shape, the masks and scroll rects of the ancestors of the object
+/// are taken into account too, as they hide the object outside of them.
+pub fn hit_test_point<'gc>(
+    object: DisplayObject<'gc>,
+    context: &mut UpdateContext<'gc>,
+    point: Point<Twips>,
+    shape_flag: bool,
+) -> bool {
+    if !shape_flag {
+        return object.hit_test_bounds(point);
+    }
+
+    let mut ancestor = object.parent();
+    while let Some(parent) = ancestor {
+        if parent.as_stage().is_some() {
+            break;
+        }
+        if !is_within_scroll_rect(parent, point) {
+            return false;
+        }
+        if let Some(masker) = parent.masker() {
+            if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+                return false;
+            }
+        }
+        ancestor = parent.parent();
+    }
+
+    object.hit_test_shape(context, point, HitTestOptions::AVM_HIT_TEST)
+}
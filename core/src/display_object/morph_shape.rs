@@ -2,7 +2,7 @@ use crate::avm2::{
     Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
 };
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
+use crate::display_object::{hit_test, DisplayObjectBase, DisplayObjectPtr};
 use crate::library::{Library, MovieLibrarySource};
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
@@ -37,12 +37,17 @@ pub struct MorphShapeData<'gc> {
 }
 
 impl<'gc> MorphShape<'gc> {
+    /// The number of intermediate frames each morph shape keeps by default.
+    pub const DEFAULT_CACHE_SIZE: usize = 64;
+
+    /// `cache_size` is the number of intermediate frames to keep, see `Library::morph_shape_cache_size`.
     pub fn from_swf_tag(
         gc_context: &Mutation<'gc>,
         tag: swf::DefineMorphShape,
         movie: Arc<SwfMovie>,
+        cache_size: usize,
     ) -> Self {
-        let static_data = MorphShapeStatic::from_swf_tag(&tag, movie);
+        let static_data = MorphShapeStatic::from_swf_tag(&tag, movie, cache_size);
         MorphShape(GcCell::new(
             gc_context,
             MorphShapeData {
@@ -137,7 +142,7 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
 
     fn render_self(&self, context: &mut RenderContext) {
         let this = self.0.read();
-        let ratio = this.ratio;
+        let ratio = MorphShapeStatic::quantize_ratio(this.ratio);
         let static_data = this.static_data;
         let shape_handle = static_data.get_shape(context, context.library, ratio);
         context
@@ -147,7 +152,7 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
 
     fn self_bounds(&self) -> Rectangle<Twips> {
         let this = self.0.read();
-        let ratio = this.ratio;
+        let ratio = MorphShapeStatic::quantize_ratio(this.ratio);
         let static_data = this.static_data;
         let frame = static_data.get_frame(ratio);
         frame.bounds.clone()
@@ -155,25 +160,23 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
 
     fn hit_test_shape(
         &self,
-        _context: &mut UpdateContext<'gc>,
+        context: &mut UpdateContext<'gc>,
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
-        if (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
-            && self.world_bounds().contains(point)
+        if self.world_bounds().contains(point)
+            && hit_test::is_hit_test_candidate((*self).into(), context, point, options)
         {
-            if let Some(frame) = self.0.read().static_data.frames.borrow().get(&self.ratio()) {
-                let Some(local_matrix) = self.global_to_local_matrix() else {
-                    return false;
-                };
-                return ruffle_render::shape_utils::shape_hit_test(
-                    &frame.shape,
-                    local_matrix * point,
-                    &local_matrix,
-                );
-            } else {
-                tracing::warn!("Missing ratio for morph shape");
-            }
+            let Some(local_matrix) = self.global_to_local_matrix() else {
+                return false;
+            };
+            let static_data = self.0.read().static_data;
+            let frame = static_data.get_frame(MorphShapeStatic::quantize_ratio(self.ratio()));
+            return ruffle_render::shape_utils::shape_hit_test(
+                &frame.shape,
+                local_matrix * point,
+                &local_matrix,
+            );
         }
 
         false
@@ -189,6 +192,19 @@ struct Frame {
     shape_handle: Option<ShapeHandle>,
     shape: swf::Shape,
     bounds: Rectangle<Twips>,
+
+    /// When this frame was last used, to find the least recently used frame to evict.
+    last_used: u64,
+}
+
+/// The intermediate frames of a morph shape, keyed by their ratio.
+///
+/// Only up to `capacity` frames are kept, as movies that tween shapes through many ratios
+/// would otherwise keep every frame and its tessellation around forever.
+struct FrameCache {
+    frames: fnv::FnvHashMap<u16, Frame>,
+    capacity: usize,
+    uses: u64,
 }
 
 /// Static data shared between all instances of a morph shape.
@@ -199,29 +215,74 @@ pub struct MorphShapeStatic {
     id: CharacterId,
     start: swf::MorphShape,
     end: swf::MorphShape,
-    frames: RefCell<fnv::FnvHashMap<u16, Frame>>,
+    frames: RefCell<FrameCache>,
     movie: Arc<SwfMovie>,
 }
 
 impl MorphShapeStatic {
-    pub fn from_swf_tag(swf_tag: &swf::DefineMorphShape, movie: Arc<SwfMovie>) -> Self {
+    /// Ratios are rendered in steps of this size, so that tweens share tessellations
+    /// between nearby ratios instead of tessellating the shape again for every one of them.
+    /// With 1024 steps across a tween, the difference can't be seen.
+    const RATIO_STEP: u16 = 64;
+
+    pub fn from_swf_tag(
+        swf_tag: &swf::DefineMorphShape,
+        movie: Arc<SwfMovie>,
+        cache_size: usize,
+    ) -> Self {
         Self {
             id: swf_tag.id,
             start: swf_tag.start.clone(),
             end: swf_tag.end.clone(),
-            frames: RefCell::new(fnv::FnvHashMap::default()),
+            frames: RefCell::new(FrameCache {
+                frames: Default::default(),
+                // Keep at least the frame being rendered and the one being hit tested.
+                capacity: cache_size.max(2),
+                uses: 0,
+            }),
             movie,
         }
     }
 
+    /// Rounds a ratio to the nearest step that gets rendered.
+    /// The start and end shapes are always rendered exactly, so the last step
+    /// snaps to the end shape rather than stopping just short of it.
+    fn quantize_ratio(ratio: u16) -> u16 {
+        let step = u32::from(Self::RATIO_STEP);
+        let quantized = (u32::from(ratio) + step / 2) / step * step;
+        if quantized > u32::from(u16::MAX - Self::RATIO_STEP) {
+            u16::MAX
+        } else {
+            quantized as u16
+        }
+    }
+
     /// Retrieves the `Frame` for the given ratio.
-    /// Lazily initializes the frame if it does not yet exist.
+    /// Lazily initializes the frame if it does not yet exist,
+    /// evicting the least recently used frame if the cache is full.
     fn get_frame(&self, ratio: u16) -> RefMut<'_, Frame> {
-        let frames = self.frames.borrow_mut();
-        RefMut::map(frames, |frames| {
-            frames
+        let mut cache = self.frames.borrow_mut();
+        cache.uses += 1;
+        let last_used = cache.uses;
+
+        if !cache.frames.contains_key(&ratio) && cache.frames.len() >= cache.capacity {
+            let least_recently_used = cache
+                .frames
+                .iter()
+                .min_by_key(|(_, frame)| frame.last_used)
+                .map(|(ratio, _)| *ratio);
+            if let Some(least_recently_used) = least_recently_used {
+                cache.frames.remove(&least_recently_used);
+            }
+        }
+
+        RefMut::map(cache, |cache| {
+            let frame = cache
+                .frames
                 .entry(ratio)
-                .or_insert_with(|| self.build_morph_frame(ratio))
+                .or_insert_with(|| self.build_morph_frame(ratio));
+            frame.last_used = last_used;
+            frame
         })
     }
 
@@ -375,6 +436,7 @@ impl MorphShapeStatic {
             shape_handle: None,
             shape,
             bounds,
+            last_used: 0,
         }
     }
 
@@ -642,4 +704,16 @@ mod test {
             Twips::new(-7)
         );
     }
+
+    #[test]
+    fn test_quantize_ratio() {
+        assert_eq!(MorphShapeStatic::quantize_ratio(0), 0);
+        assert_eq!(MorphShapeStatic::quantize_ratio(31), 0);
+        assert_eq!(MorphShapeStatic::quantize_ratio(32), 64);
+        assert_eq!(MorphShapeStatic::quantize_ratio(32767), 32768);
+        assert_eq!(MorphShapeStatic::quantize_ratio(65439), 65408);
+        assert_eq!(MorphShapeStatic::quantize_ratio(65440), 65535);
+        assert_eq!(MorphShapeStatic::quantize_ratio(65500), 65535);
+        assert_eq!(MorphShapeStatic::quantize_ratio(65535), 65535);
+    }
 }
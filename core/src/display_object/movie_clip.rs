@@ -9,7 +9,7 @@ use crate::avm2::{
     QName as Avm2QName, StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
 };
 use crate::backend::audio::{AudioManager, SoundHandle, SoundInstanceHandle};
-use crate::backend::navigator::Request;
+use crate::backend::navigator::{url_from_relative_url, Request};
 use crate::backend::ui::MouseCursor;
 use crate::frame_lifecycle::run_inner_goto_frame;
 use bitflags::bitflags;
@@ -24,8 +24,8 @@ use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
 use crate::display_object::{
-    Avm1Button, Avm2Button, DisplayObjectBase, DisplayObjectPtr, EditText, Graphic, MorphShape,
-    Text, Video,
+    hit_test, Avm1Button, Avm2Button, DisplayObjectBase, DisplayObjectPtr, EditText, Graphic,
+    MorphShape, Text, Video,
 };
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
@@ -39,12 +39,15 @@ use crate::string::{AvmString, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::{self, ControlFlow, DecodeResult, Error, SwfMovie, SwfSlice, SwfStream};
 use crate::vminterface::{AvmObject, Instantiator};
 use core::fmt;
+use flash_lso::amf3::read::AMF3Decoder;
 use gc_arena::{Collect, Gc, GcCell, GcWeakCell, Mutation};
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::transform::Transform;
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cell::{Ref, RefCell, RefMut};
 use std::cmp::max;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use swf::extensions::ReadSwfExt;
 use swf::{ClipEventFlag, DefineBitsLossless, FrameLabelData, TagCode, UTF_8};
@@ -545,6 +548,14 @@ impl<'gc> MovieClip<'gc> {
             }
         }
 
+        if context
+            .library
+            .library_for_movie(self.movie())
+            .is_some_and(|library| library.has_pending_imports())
+        {
+            return false;
+        }
+
         // TODO: Re-creating static data because preload step occurs after construction.
         // Should be able to hoist this up somewhere, or use MaybeUninit.
         let mut static_data = (*self.0.read().static_data).clone();
@@ -689,19 +700,19 @@ impl<'gc> MovieClip<'gc> {
                 TagCode::DefineShape => self
                     .0
                     .write(context.gc_context)
-                    .define_shape(context, reader, 1),
+                    .define_shape(context, reader, tag_len, 1),
                 TagCode::DefineShape2 => self
                     .0
                     .write(context.gc_context)
-                    .define_shape(context, reader, 2),
+                    .define_shape(context, reader, tag_len, 2),
                 TagCode::DefineShape3 => self
                     .0
                     .write(context.gc_context)
-                    .define_shape(context, reader, 3),
+                    .define_shape(context, reader, tag_len, 3),
                 TagCode::DefineShape4 => self
                     .0
                     .write(context.gc_context)
-                    .define_shape(context, reader, 4),
+                    .define_shape(context, reader, tag_len, 4),
                 TagCode::DefineSound => self
                     .0
                     .write(context.gc_context)
@@ -744,12 +755,19 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .jpeg_tables(context, reader),
-                TagCode::ShowFrame => self.0.write(context.gc_context).show_frame(
-                    reader,
-                    tag_len,
-                    &mut cur_frame,
-                    &mut start_pos,
-                ),
+                TagCode::ShowFrame => {
+                    // The next frame starts after the body of this tag, which
+                    // should be empty, but might not be in obfuscated movies.
+                    let next_frame_start = (reader.get_ref().as_ptr() as u64 + tag_len as u64)
+                        .saturating_sub(data.data().as_ptr() as u64);
+                    static_data.frame_offsets.push(next_frame_start);
+                    self.0.write(context.gc_context).show_frame(
+                        reader,
+                        tag_len,
+                        &mut cur_frame,
+                        &mut start_pos,
+                    )
+                }
                 TagCode::ScriptLimits => self
                     .0
                     .write(context.gc_context)
@@ -775,12 +793,17 @@ impl<'gc> MovieClip<'gc> {
                 TagCode::ImportAssets => {
                     self.0
                         .write(context.gc_context)
-                        .import_assets(context, reader, chunk_limit)
+                        .import_assets(context, reader, chunk_limit)?;
+                    // Wait for the imported movie before loading the rest of the frame.
+                    return Ok(ControlFlow::Exit);
                 }
                 TagCode::ImportAssets2 => {
-                    self.0
-                        .write(context.gc_context)
-                        .import_assets_2(context, reader, chunk_limit)
+                    self.0.write(context.gc_context).import_assets_2(
+                        context,
+                        reader,
+                        chunk_limit,
+                    )?;
+                    return Ok(ControlFlow::Exit);
                 }
                 TagCode::DoAbc | TagCode::DoAbc2 => self.preload_bytecode_tag(
                     tag_code,
@@ -814,6 +837,23 @@ impl<'gc> MovieClip<'gc> {
         };
         let is_finished = end_tag_found || result.is_err() || !result.unwrap_or_default();
 
+        // Without an `End` tag, the tag stream was cut off. If frames are missing, only keep
+        // the complete ones, as the tags after the last `ShowFrame` are an incomplete frame.
+        if is_finished
+            && !end_tag_found
+            && context.partial_playback
+            && cur_frame > 1
+            && cur_frame - 1 < static_data.total_frames
+        {
+            tracing::warn!(
+                "Movie is truncated, playing {} of {} frames",
+                cur_frame - 1,
+                static_data.total_frames
+            );
+            static_data.total_frames = cur_frame - 1;
+            static_data.is_shortened = true;
+        }
+
         self.0
             .write(context.gc_context)
             .import_exports_of_importer(context);
@@ -1008,7 +1048,6 @@ impl<'gc> MovieClip<'gc> {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn playing(self) -> bool {
         self.0.read().playing()
     }
@@ -1266,6 +1305,12 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().total_frames()
     }
 
+    /// Whether this clip stops at its last complete frame because its movie is truncated.
+    /// See [`crate::PlayerBuilder::with_partial_playback`].
+    pub fn is_shortened(self) -> bool {
+        self.0.read().static_data.is_shortened
+    }
+
     #[allow(dead_code)]
     pub fn has_frame_script(self, frame: FrameNumber) -> bool {
         self.0
@@ -1296,6 +1341,99 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().frames_loaded()
     }
 
+    fn render_contents(self, context: &mut RenderContext<'_, 'gc>) {
+        if let Some(drawing) = self.drawing() {
+            drawing.render(context);
+        }
+        self.render_children(context);
+    }
+
+    /// Renders this clip with 9-slice scaling, as set by `DefineScalingGrid` or `scale9Grid`:
+    /// the corners around the grid keep their size when the clip is scaled, the edges only
+    /// stretch along the grid, and the center stretches in both directions.
+    ///
+    /// Each of the 9 slices is rendered separately, masked to where it ends up. Children
+    /// are sliced along with the drawing of the clip. Returns false, without rendering
+    /// anything, if the grid doesn't apply, e.g. because the clip isn't scaled or is rotated.
+    fn render_with_scaling_grid(self, context: &mut RenderContext<'_, 'gc>) -> bool {
+        let grid = self.scaling_grid();
+        let matrix = *self.base().matrix();
+        if !grid.is_valid()
+            || matrix.b != 0.0
+            || matrix.c != 0.0
+            || matrix.a <= 0.0
+            || matrix.d <= 0.0
+            || (matrix.a == 1.0 && matrix.d == 1.0)
+        {
+            return false;
+        }
+
+        let bounds = self.bounds();
+        if !bounds.is_valid()
+            || grid.x_min <= bounds.x_min
+            || grid.x_max >= bounds.x_max
+            || grid.y_min <= bounds.y_min
+            || grid.y_max >= bounds.y_max
+        {
+            return false;
+        }
+
+        let columns = scaling_grid_slices(
+            bounds.x_min.to_pixels(),
+            grid.x_min.to_pixels(),
+            grid.x_max.to_pixels(),
+            bounds.x_max.to_pixels(),
+            matrix.a.into(),
+        );
+        let rows = scaling_grid_slices(
+            bounds.y_min.to_pixels(),
+            grid.y_min.to_pixels(),
+            grid.y_max.to_pixels(),
+            bounds.y_max.to_pixels(),
+            matrix.d.into(),
+        );
+        let base_matrix = context.transform_stack.transform().matrix;
+        for (x_source, x_dest) in columns {
+            for (y_source, y_dest) in rows {
+                let width = x_dest.1 - x_dest.0;
+                let height = y_dest.1 - y_dest.0;
+                if width <= 0.0 || height <= 0.0 {
+                    continue;
+                }
+                let scale_x = width / (x_source.1 - x_source.0);
+                let scale_y = height / (y_source.1 - y_source.0);
+                let slice_matrix = Matrix::create_box(
+                    scale_x as f32,
+                    scale_y as f32,
+                    Twips::from_pixels(x_dest.0 - x_source.0 * scale_x),
+                    Twips::from_pixels(y_dest.0 - y_source.0 * scale_y),
+                );
+                let mask_matrix = base_matrix
+                    * Matrix::create_box(
+                        width as f32,
+                        height as f32,
+                        Twips::from_pixels(x_dest.0),
+                        Twips::from_pixels(y_dest.0),
+                    );
+
+                // The color doesn't matter, as this is a mask.
+                context.commands.push_mask();
+                context.commands.draw_rect(Color::WHITE, mask_matrix);
+                context.commands.activate_mask();
+                context.transform_stack.push(&Transform {
+                    matrix: slice_matrix,
+                    color_transform: Default::default(),
+                });
+                self.render_contents(context);
+                context.transform_stack.pop();
+                context.commands.deactivate_mask();
+                context.commands.draw_rect(Color::WHITE, mask_matrix);
+                context.commands.pop_mask();
+            }
+        }
+        true
+    }
+
     pub fn total_bytes(self) -> i32 {
         // For a loaded SWF, returns the uncompressed size of the SWF.
         // Otherwise, returns the size of the tag list in the clip's DefineSprite tag.
@@ -1488,7 +1626,7 @@ impl<'gc> MovieClip<'gc> {
     fn determine_next_frame(self) -> NextFrame {
         if self.current_frame() < self.total_frames() {
             NextFrame::Next
-        } else if self.total_frames() > 1 {
+        } else if self.total_frames() > 1 && !self.is_shortened() {
             NextFrame::First
         } else {
             NextFrame::Same
@@ -1518,7 +1656,14 @@ impl<'gc> MovieClip<'gc> {
                 }
             }
             NextFrame::First => return self.run_goto(context, 1, true),
-            NextFrame::Same => self.stop(context),
+            NextFrame::Same => {
+                self.stop(context);
+
+                // The rest of a shortened clip's tag stream is an incomplete frame.
+                if self.is_shortened() {
+                    return;
+                }
+            }
         }
 
         let mc = self.0.read();
@@ -1671,7 +1816,11 @@ impl<'gc> MovieClip<'gc> {
                                 .collect(),
                         );
                     }
-                    // TODO: Missing PlaceObject property: amf_data
+                    if let Some(amf_data) = place_object.amf_data {
+                        if movie.is_action_script_3() {
+                            set_meta_data_from_amf(context, child, amf_data);
+                        }
+                    }
 
                     // Run first frame.
                     child.post_instantiation(context, None, Instantiator::Movie, false);
@@ -1832,8 +1981,28 @@ impl<'gc> MovieClip<'gc> {
 
         // Sanity; let's make sure we don't seek way too far.
         let clamped_frame = frame.min(max(mc.frames_loaded(), 0) as FrameNumber);
+        let timeline_snapshots = mc.static_data.timeline_snapshots;
+        let static_data = mc.static_data;
         drop(mc);
 
+        // Rewinds can skip ahead to the latest snapshot before the target
+        // frame. The target frame itself is always read from the tag stream,
+        // as we need to know where it starts.
+        if is_rewind {
+            if let Some((&snapshot_frame, snapshot)) =
+                timeline_snapshots.read().range(..clamped_frame).next_back()
+            {
+                match static_data.frame_offsets.get(usize::from(snapshot_frame)) {
+                    Some(&snapshot_pos) if snapshot.restore(&data, &mut goto_commands) => {
+                        self.0.write(context.gc_context).current_frame = snapshot_frame;
+                        frame_pos = snapshot_pos;
+                        index = snapshot.next_index;
+                    }
+                    _ => goto_commands.clear(),
+                }
+            }
+        }
+
         let mut removed_frame_scripts: Vec<DisplayObject<'gc>> = vec![];
 
         let mut reader = data.read_from(frame_pos);
@@ -1887,10 +2056,19 @@ impl<'gc> MovieClip<'gc> {
 
                 Ok(ControlFlow::Continue)
             };
-            let _ = tag_utils::decode_tags(&mut reader, tag_callback);
+            let frame_complete =
+                matches!(tag_utils::decode_tags(&mut reader, tag_callback), Ok(true));
             if let Err(e) = self.run_abc_and_symbol_tags(context, self.current_frame() - 1) {
                 tracing::error!("Error running abc/symbols in goto: {e:?}");
             }
+
+            let current_frame = self.current_frame();
+            if is_rewind && frame_complete && current_frame % TIMELINE_SNAPSHOT_INTERVAL == 0 {
+                timeline_snapshots
+                    .write(context.gc_context)
+                    .entry(current_frame)
+                    .or_insert_with(|| TimelineSnapshot::new(index, &goto_commands));
+            }
         }
         let hit_target_frame = self.0.read().current_frame == frame;
 
@@ -2339,6 +2517,28 @@ impl<'gc> MovieClip<'gc> {
         self.0.write(context.gc_context).hit_area = hit_area;
     }
 
+    /// The object hit tested by the mouse instead of this clip, if any.
+    ///
+    /// In AVM1, this is whichever movie clip the `hitArea` property is set to.
+    fn mouse_hit_area(self, context: &mut UpdateContext<'gc>) -> Option<DisplayObject<'gc>> {
+        if self.movie().is_action_script_3() {
+            return self.hit_area();
+        }
+
+        let Avm1Value::Object(object) = self.object() else {
+            return None;
+        };
+        let mut activation = Avm1Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[Mouse Pick]"),
+            self.avm1_root(),
+        );
+        match object.get("hitArea", &mut activation) {
+            Ok(Avm1Value::Object(hit_area)) => hit_area.as_display_object(),
+            _ => None,
+        }
+    }
+
     pub fn tag_stream_len(&self) -> usize {
         self.0.read().tag_stream_len()
     }
@@ -2364,6 +2564,29 @@ impl<'gc> MovieClip<'gc> {
         Ref::filter_map(read, Option::as_deref).ok()
     }
 
+    /// Whether this clip runs AVM1 code on every frame, with `onClipEvent(enterFrame)`
+    /// or an `onEnterFrame` method.
+    pub fn has_enter_frame_handler(self, context: &mut UpdateContext<'gc>) -> bool {
+        if self
+            .0
+            .read()
+            .clip_event_flags
+            .contains(ClipEventFlag::ENTER_FRAME)
+        {
+            return true;
+        }
+        if let Avm1Value::Object(object) = self.object() {
+            let mut activation = Avm1Activation::from_nothing(
+                context,
+                ActivationIdentifier::root("[Idle Check]"),
+                self.avm1_root(),
+            );
+            object.has_property(&mut activation, "onEnterFrame".into())
+        } else {
+            false
+        }
+    }
+
     pub fn is_button_mode(&self, context: &mut UpdateContext<'gc>) -> bool {
         if self.forced_button_mode()
             || self
@@ -2712,16 +2935,14 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                                 .unwrap()
                                 .avm2_domain();
 
-                            if let Err(e) = Avm2::run_stack_frame_for_callable(
-                                callable,
-                                avm2_object.into(),
-                                &[],
-                                domain,
-                                context,
-                            ) {
-                                tracing::error!(
-                                    "Error occurred when running AVM2 frame script: {}",
-                                    e
+                            let mut activation = Avm2Activation::from_domain(context, domain);
+                            if let Err(e) = callable.call(avm2_object.into(), &[], &mut activation)
+                            {
+                                Avm2::uncaught_error(
+                                    &mut activation,
+                                    Some(self.into()),
+                                    e,
+                                    "Error occurred when running AVM2 frame script",
                                 );
                             }
                             write = self.0.write(context.gc_context);
@@ -2749,10 +2970,9 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
-        if let Some(drawing) = self.drawing() {
-            drawing.render(context);
+        if !self.render_with_scaling_grid(context) {
+            self.render_contents(context);
         }
-        self.render_children(context);
     }
 
     fn self_bounds(&self) -> Rectangle<Twips> {
@@ -2767,26 +2987,12 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
-        if options.contains(HitTestOptions::SKIP_INVISIBLE)
-            && !self.visible()
-            && self.maskee().is_none()
+        if self.world_bounds().contains(point)
+            && hit_test::is_hit_test_candidate((*self).into(), context, point, options)
         {
-            return false;
-        }
-
-        if options.contains(HitTestOptions::SKIP_MASK) && self.maskee().is_some() {
-            return false;
-        }
-
-        if self.world_bounds().contains(point) {
             let Some(local_matrix) = self.global_to_local_matrix() else {
                 return false;
             };
-            if let Some(masker) = self.masker() {
-                if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
-                    return false;
-                }
-            }
 
             let mut clip_depth = 0;
 
@@ -3076,7 +3282,8 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
             return None;
         }
 
-        if self.visible() {
+        // Nothing is drawn outside of the scroll rect, so nothing can be clicked there either.
+        if self.visible() && hit_test::is_within_scroll_rect((*self).into(), point) {
             let this: InteractiveObject<'gc> = (*self).into();
             let local_matrix = self.global_to_local_matrix()?;
 
@@ -3092,15 +3299,22 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
             // true.
             // InteractiveObject.mouseEnabled:
             // "Any children of this instance on the display list are not affected."
-            if self.mouse_enabled() && self.world_bounds().contains(point) {
+            if self.mouse_enabled() {
+                // The hit area can be anywhere, and is clickable even when invisible.
+                let hit_area = self.mouse_hit_area(context);
+                let bounds = hit_area.map_or_else(|| self.world_bounds(), |o| o.world_bounds());
+
                 // This MovieClip operates in "button mode" if it has a mouse handler,
                 // either via on(..) or via property mc.onRelease, etc.
-                let is_button_mode = self.is_button_mode(context);
-
-                if is_button_mode {
-                    let mut options = HitTestOptions::SKIP_INVISIBLE;
-                    options.set(HitTestOptions::SKIP_MASK, self.maskee().is_none());
-                    if self.hit_test_shape(context, point, options) {
+                if bounds.contains(point) && self.is_button_mode(context) {
+                    let is_hit = if let Some(hit_area) = hit_area {
+                        hit_area.hit_test_shape(context, point, HitTestOptions::SKIP_MASK)
+                    } else {
+                        let mut options = HitTestOptions::SKIP_INVISIBLE;
+                        options.set(HitTestOptions::SKIP_MASK, self.maskee().is_none());
+                        self.hit_test_shape(context, point, options)
+                    };
+                    if is_hit {
                         return Some(this);
                     }
                 }
@@ -3179,7 +3393,8 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
             return Avm2MousePick::Miss;
         }
 
-        if self.visible() {
+        // Nothing is drawn outside of the scroll rect, so nothing can be clicked there either.
+        if self.visible() && hit_test::is_within_scroll_rect((*self).into(), point) {
             let this: InteractiveObject<'gc> = (*self).into();
             let Some(local_matrix) = self.global_to_local_matrix() else {
                 return Avm2MousePick::Miss;
@@ -3203,6 +3418,10 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
 
             let mut found_propagate = None;
 
+            // A sprite with a hit area is only hit through it, instead of through its own
+            // graphics and those of its non-interactive children.
+            let hit_area = self.hit_area();
+
             let mut clip_layers = self
                 .iter_render_list()
                 .flat_map(|child| {
@@ -3236,6 +3455,11 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                     continue;
                 }
 
+                // The hit area stands in for this sprite, so it isn't picked by itself.
+                if DisplayObject::option_ptr_eq(Some(child), hit_area) {
+                    continue;
+                }
+
                 let mut res = if let Some(child) = child.as_interactive() {
                     if child.as_displayobject().movie().is_action_script_3() {
                         child.mouse_pick_avm2(context, point, require_button_mode)
@@ -3248,9 +3472,7 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                             Avm2MousePick::Miss
                         }
                     }
-                } else if child.as_interactive().is_none()
-                    && child.hit_test_shape(context, point, options)
-                {
+                } else if hit_area.is_none() && child.hit_test_shape(context, point, options) {
                     if self.mouse_enabled() {
                         Avm2MousePick::Hit(this)
                     } else {
@@ -3296,6 +3518,18 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 return propagate.combine_with_parent((*self).into());
             }
 
+            if let Some(hit_area) = hit_area {
+                // Unlike the sprite itself, the hit area is clickable even when invisible.
+                if hit_area.hit_test_shape(context, point, HitTestOptions::SKIP_MASK) {
+                    return if self.mouse_enabled() {
+                        Avm2MousePick::Hit((*self).into())
+                    } else {
+                        Avm2MousePick::PropagateToParent
+                    };
+                }
+                return Avm2MousePick::Miss;
+            }
+
             // Check drawing, because this selects the current clip, it must have mouse enabled
             if self.world_bounds().contains(point) {
                 let point = local_matrix * point;
@@ -3317,10 +3551,21 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
 
     fn mouse_cursor(self, context: &mut UpdateContext<'gc>) -> MouseCursor {
         if self.is_button_mode(context) && self.use_hand_cursor(context) && self.enabled(context) {
-            MouseCursor::Hand
-        } else {
-            MouseCursor::Arrow
+            return MouseCursor::Hand;
         }
+
+        // In AVM2, the children of a sprite in button mode show its cursor too.
+        if self.movie().is_action_script_3() {
+            let mut parent = self.parent();
+            while let Some(clip) = parent.and_then(|p| p.as_movie_clip()) {
+                if clip.forced_button_mode() && clip.avm2_use_hand_cursor() && clip.avm2_enabled() {
+                    return MouseCursor::Hand;
+                }
+                parent = clip.parent();
+            }
+        }
+
+        MouseCursor::Arrow
     }
 
     fn is_focusable(&self, context: &mut UpdateContext<'gc>) -> bool {
@@ -3569,7 +3814,12 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let movie = self.movie();
         let tag = reader.read_define_morph_shape(version)?;
         let id = tag.id;
-        let morph_shape = MorphShape::from_swf_tag(context.gc_context, tag, movie.clone());
+        let morph_shape = MorphShape::from_swf_tag(
+            context.gc_context,
+            tag,
+            movie.clone(),
+            context.library.morph_shape_cache_size(),
+        );
         context
             .library
             .library_for_movie_mut(movie)
@@ -3582,12 +3832,13 @@ impl<'gc, 'a> MovieClipData<'gc> {
         &mut self,
         context: &mut UpdateContext<'gc>,
         reader: &mut SwfStream<'a>,
+        tag_len: usize,
         version: u8,
     ) -> Result<(), Error> {
-        let movie = self.movie();
-        let swf_shape = reader.read_define_shape(version)?;
-        let id = swf_shape.id;
-        let graphic = Graphic::from_swf_tag(context, swf_shape, movie);
+        // Shapes are only parsed when they are first displayed.
+        let tag = self.static_data.swf.resize_to_reader(reader, tag_len);
+        let graphic = Graphic::from_swf_tag(context, tag, version)?;
+        let id = graphic.id();
         context
             .library
             .library_for_movie_mut(self.movie())
@@ -3913,6 +4164,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             font,
             reader.encoding(),
             FontType::Embedded,
+            Some(&self.movie()),
         );
         context
             .library
@@ -3935,6 +4187,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             font,
             reader.encoding(),
             FontType::Embedded,
+            Some(&self.movie()),
         );
         context
             .library
@@ -3957,6 +4210,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             font,
             reader.encoding(),
             FontType::Embedded,
+            Some(&self.movie()),
         );
         context
             .library
@@ -4177,9 +4431,15 @@ impl<'gc, 'a> MovieClipData<'gc> {
         exported_assets: Vec<swf::ExportedAsset>,
         _chunk_limit: &mut ExecutionLimit,
     ) -> Result<(), Error> {
-        let library = context.library.library_for_movie_mut(self.movie());
+        let movie = self.movie();
+        let library = context.library.library_for_movie_mut(movie.clone());
 
+        // Imported movies are found relative to the importer, wherever it was loaded from.
         let asset_url = url.to_string_lossy(UTF_8);
+        let asset_url = match url_from_relative_url(movie.url(), &asset_url) {
+            Ok(url) => url.to_string(),
+            Err(_) => asset_url,
+        };
 
         let request = Request::get(asset_url);
 
@@ -4191,6 +4451,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
 
             library.register_import(name, id);
         }
+        library.begin_import();
 
         let player = context.player.clone();
         let fut = LoadManager::load_asset_movie(player, request, self.movie());
@@ -4800,6 +5061,54 @@ impl<'gc, 'a> MovieClip<'gc> {
     }
 }
 
+/// Splits the span from `min` to `max` at the lines of a scaling grid, returning the source and
+/// destination span of each of the 3 slices, in pixels.
+///
+/// The outer slices keep their size once scaled by `scale`, unless the clip is scaled down so
+/// much that they don't fit, in which case they shrink evenly and the middle slice disappears.
+fn scaling_grid_slices(
+    min: f64,
+    grid_min: f64,
+    grid_max: f64,
+    max: f64,
+    scale: f64,
+) -> [((f64, f64), (f64, f64)); 3] {
+    let mut before = (grid_min - min) / scale;
+    let mut after = (max - grid_max) / scale;
+    let fit = (max - min) / (before + after);
+    if fit < 1.0 {
+        before *= fit;
+        after *= fit;
+    }
+    [
+        ((min, grid_min), (min, min + before)),
+        ((grid_min, grid_max), (min + before, max - after)),
+        ((grid_max, max), (max - after, max)),
+    ]
+}
+
+/// Sets the `metaData` of a child to the AMF3 object stored in the PlaceObject4 tag placing it.
+fn set_meta_data_from_amf<'gc>(
+    context: &mut UpdateContext<'gc>,
+    child: DisplayObject<'gc>,
+    amf_data: &[u8],
+) {
+    let amf = match AMF3Decoder::default().parse_single_element(amf_data) {
+        Ok((_, amf)) => amf,
+        Err(e) => {
+            tracing::warn!("Invalid metadata in PlaceObject4 tag: {e:?}");
+            return;
+        }
+    };
+
+    let mut activation = Avm2Activation::from_nothing(context);
+    match crate::avm2::amf::deserialize_value(&mut activation, &amf) {
+        Ok(Avm2Value::Object(meta_data)) => child.set_meta_data(activation.gc(), meta_data),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Couldn't read metadata in PlaceObject4 tag: {e:?}"),
+    }
+}
+
 #[derive(Clone)]
 pub struct Scene {
     pub name: WString,
@@ -4865,6 +5174,16 @@ struct MovieClipStatic<'gc> {
     #[collect(require_static)]
     audio_stream_handle: Option<SoundHandle>,
     total_frames: FrameNumber,
+    /// Whether the tag stream was cut off before all frames, so that only the
+    /// complete ones are played. Only set in partial playback mode.
+    is_shortened: bool,
+    /// The tag stream position each preloaded frame starts at, indexed by
+    /// frame number minus one.
+    ///
+    /// Frames are only decoded when they run, so this lets us seek to a frame
+    /// without reading the tags of the frames before it.
+    #[collect(require_static)]
+    frame_offsets: Vec<u64>,
     /// The last known symbol name under which this movie clip was exported.
     /// Used for looking up constructors registered with `Object.registerClass`.
     exported_name: GcCell<'gc, Option<AvmString<'gc>>>,
@@ -4886,6 +5205,13 @@ struct MovieClipStatic<'gc> {
     // the `Vec` from this map, and process it in `run_eager_script_and_symbol`
     abc_tags: GcCell<'gc, HashMap<FrameNumber, Vec<AbcCodeAndTag>>>,
     symbolclass_names: GcCell<'gc, HashMap<FrameNumber, Vec<(Avm2QName<'gc>, u16)>>>,
+
+    /// Snapshots of the display list built by rewinding gotos, keyed by the
+    /// frame they were taken after.
+    ///
+    /// A rewind can start from the nearest snapshot instead of replaying the
+    /// timeline from frame 1.
+    timeline_snapshots: GcCell<'gc, BTreeMap<FrameNumber, TimelineSnapshot>>,
 }
 
 #[derive(Debug, Collect)]
@@ -4915,6 +5241,8 @@ impl<'gc> MovieClipStatic<'gc> {
             id,
             swf,
             total_frames,
+            is_shortened: false,
+            frame_offsets: vec![0],
             frame_labels: Vec::new(),
             frame_labels_map: HashMap::new(),
             scene_labels: Vec::new(),
@@ -4927,10 +5255,119 @@ impl<'gc> MovieClipStatic<'gc> {
             preload_progress: GcCell::new(gc_context, Default::default()),
             abc_tags: GcCell::new(gc_context, Default::default()),
             symbolclass_names: GcCell::new(gc_context, Default::default()),
+            timeline_snapshots: GcCell::new(gc_context, Default::default()),
         }
     }
 }
 
+/// How many frames apart timeline snapshots are taken.
+const TIMELINE_SNAPSHOT_INTERVAL: FrameNumber = 64;
+
+/// The state of a rewinding goto after a given frame, so that later rewinds
+/// can resume from there.
+#[derive(Debug, Collect)]
+#[collect(require_static)]
+struct TimelineSnapshot {
+    /// The index to give to the next placement command.
+    next_index: usize,
+
+    placements: Vec<SnapshotPlacement>,
+}
+
+/// An owned copy of a `GotoPlaceObject`.
+///
+/// The fields that can't be changed after the first placement (such as
+/// name and clip actions) are re-read from the tag at `tag_start`.
+#[derive(Debug)]
+struct SnapshotPlacement {
+    frame: FrameNumber,
+    index: usize,
+    tag_start: u64,
+    version: u8,
+    action: swf::PlaceObjectAction,
+    matrix: Option<swf::Matrix>,
+    color_transform: Option<swf::ColorTransform>,
+    ratio: Option<u16>,
+    blend_mode: Option<swf::BlendMode>,
+    is_bitmap_cached: Option<bool>,
+    is_visible: Option<bool>,
+    background_color: Option<Color>,
+    filters: Option<Vec<swf::Filter>>,
+}
+
+impl TimelineSnapshot {
+    fn new(next_index: usize, goto_commands: &[GotoPlaceObject<'_>]) -> Self {
+        let placements = goto_commands
+            .iter()
+            .map(|command| {
+                let place_object = &command.place_object;
+                SnapshotPlacement {
+                    frame: command.frame,
+                    index: command.index,
+                    tag_start: command.tag_start,
+                    version: command.version,
+                    action: place_object.action,
+                    matrix: place_object.matrix,
+                    color_transform: place_object.color_transform,
+                    ratio: place_object.ratio,
+                    blend_mode: place_object.blend_mode,
+                    is_bitmap_cached: place_object.is_bitmap_cached,
+                    is_visible: place_object.is_visible,
+                    background_color: place_object.background_color,
+                    filters: place_object.filters.clone(),
+                }
+            })
+            .collect();
+
+        Self {
+            next_index,
+            placements,
+        }
+    }
+
+    /// Rebuild the goto commands of this snapshot.
+    ///
+    /// Returns `false` if a placement tag could not be read again, in which
+    /// case `goto_commands` is left incomplete.
+    fn restore<'a>(
+        &self,
+        data: &'a SwfSlice,
+        goto_commands: &mut Vec<GotoPlaceObject<'a>>,
+    ) -> bool {
+        for placement in &self.placements {
+            let mut reader = data.read_from(placement.tag_start);
+            let place_object = if placement.version == 1 {
+                reader.read_place_object()
+            } else {
+                reader.read_place_object_2_or_3(placement.version)
+            };
+            let Ok(mut place_object) = place_object else {
+                return false;
+            };
+
+            place_object.action = placement.action;
+            place_object.matrix = placement.matrix;
+            place_object.color_transform = placement.color_transform;
+            place_object.ratio = placement.ratio;
+            place_object.blend_mode = placement.blend_mode;
+            place_object.is_bitmap_cached = placement.is_bitmap_cached;
+            place_object.is_visible = placement.is_visible;
+            place_object.background_color = placement.background_color;
+            place_object.filters = placement.filters.clone();
+
+            goto_commands.push(GotoPlaceObject {
+                frame: placement.frame,
+                place_object,
+                index: placement.index,
+                tag_start: placement.tag_start,
+                version: placement.version,
+            });
+        }
+
+        true
+    }
+}
+
 /// Stores the placement settings for display objects during a
 /// goto command.
 #[derive(Debug)]
@@ -5208,3 +5645,31 @@ impl ClipEventHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaling_grid_keeps_outer_slices_size() {
+        // A 100px wide clip with a 10px border, scaled twice as wide.
+        assert_eq!(
+            scaling_grid_slices(0.0, 10.0, 90.0, 100.0, 2.0),
+            [
+                ((0.0, 10.0), (0.0, 5.0)),
+                ((10.0, 90.0), (5.0, 95.0)),
+                ((90.0, 100.0), (95.0, 100.0)),
+            ]
+        );
+
+        // Scaled down to a tenth, the borders would be wider than the clip.
+        assert_eq!(
+            scaling_grid_slices(0.0, 10.0, 90.0, 100.0, 0.1),
+            [
+                ((0.0, 10.0), (0.0, 50.0)),
+                ((10.0, 90.0), (50.0, 50.0)),
+                ((90.0, 100.0), (50.0, 100.0)),
+            ]
+        );
+    }
+}
@@ -16,6 +16,7 @@ use crate::display_object::interactive::{
 use crate::display_object::{render_base, DisplayObjectBase, DisplayObjectPtr};
 use crate::events::{ClipEvent, ClipEventResult};
 use crate::focus_tracker::FocusTracker;
+use crate::frame_lifecycle::broadcast_frame_event;
 use crate::prelude::*;
 use crate::string::{FromWStr, WStr};
 use crate::tag_utils::SwfMovie;
@@ -696,12 +697,14 @@ impl<'gc> Stage<'gc> {
     ///
     /// TODO: Need additional check as Flash Player does not
     /// broadcast the 'render' event on the first render
+    ///
+    /// The stage is no longer invalidated once this starts, so calling `invalidate`
+    /// from a `render` handler requests another `render` event before the next
+    /// render instead of being lost.
     pub fn broadcast_render(&self, context: &mut UpdateContext<'gc>) {
-        let render_evt = Avm2EventObject::bare_default_event(context, "render");
-        let dobject_constr = context.avm2.classes().display_object;
-        Avm2::broadcast_event(context, render_evt, dobject_constr);
-
         self.set_invalidated(context.gc_context, false);
+
+        broadcast_frame_event(context, "render");
     }
 
     /// Fires `Stage.onFullScreen` in AVM1 or `Event.FULLSCREEN` in AVM2.
@@ -858,10 +861,6 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
         for child in self.iter_render_list() {
             child.enter_frame(context);
         }
-
-        let enter_frame_evt = Avm2EventObject::bare_default_event(context, "enterFrame");
-        let dobject_constr = context.avm2.classes().display_object;
-        Avm2::broadcast_event(context, enter_frame_evt, dobject_constr);
     }
 
     fn construct_frame(&self, context: &mut UpdateContext<'gc>) {
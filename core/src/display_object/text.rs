@@ -2,14 +2,13 @@ use crate::avm2::{
     Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
 };
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
+use crate::display_object::{hit_test, DisplayObjectBase, DisplayObjectPtr};
 use crate::font::TextRenderSettings;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
 use core::fmt;
 use gc_arena::{Collect, GcCell, Mutation};
-use ruffle_render::commands::CommandHandler;
 use ruffle_render::transform::Transform;
 use std::cell::{Ref, RefMut};
 use std::sync::Arc;
@@ -148,14 +147,9 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                 transform.color_transform.set_mult_color(&color);
                 for c in &block.glyphs {
                     if let Some(glyph) = font.get_glyph(c.index as usize) {
-                        if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
-                            context.transform_stack.push(&transform);
-                            context.commands.render_shape(
-                                glyph_shape_handle,
-                                context.transform_stack.transform(),
-                            );
-                            context.transform_stack.pop();
-                        }
+                        context.transform_stack.push(&transform);
+                        glyph.render(context, &tf.render_settings);
+                        context.transform_stack.pop();
 
                         transform.matrix.tx += Twips::new(c.advance);
                     }
@@ -175,8 +169,8 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
         mut point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
-        if (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
-            && self.world_bounds().contains(point)
+        if self.world_bounds().contains(point)
+            && hit_test::is_hit_test_candidate((*self).into(), context, point, options)
         {
             // Texts using the "Advanced text rendering" always hit test using their bounding box.
             if self.0.read().render_settings.is_advanced() {
@@ -0,0 +1,332 @@
+//! The 2.5D properties of display objects (`z`, `rotationX`, `rotationY`, `scaleZ`),
+//! and the perspective projections that ancestors apply to them.
+//!
+//! Display objects stay flat: their 3D transform places them as a plane in 3D space,
+//! which then gets projected back onto the stage.
+
+use crate::prelude::*;
+use crate::types::Degrees;
+
+/// The 3D part of the transform of a display object.
+///
+/// The 2D part (`x`, `y`, `scaleX`, `scaleY` and `rotation`, which doubles as `rotationZ`)
+/// still lives in the matrix of the object. An object only has a 3D transform once
+/// one of these properties was set, after which `transform.matrix` becomes `null`
+/// and `transform.matrix3D` becomes available.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform3D {
+    /// The position along the z axis, in pixels.
+    pub z: f64,
+
+    /// The rotation around the x axis.
+    pub rotation_x: Degrees,
+
+    /// The rotation around the y axis.
+    pub rotation_y: Degrees,
+
+    /// The scale along the z axis.
+    pub scale_z: f64,
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self {
+            z: 0.0,
+            rotation_x: Degrees::from_radians(0.0),
+            rotation_y: Degrees::from_radians(0.0),
+            scale_z: 1.0,
+        }
+    }
+}
+
+/// The perspective that a display object applies to its descendants with a 3D transform,
+/// as set by `transform.perspectiveProjection`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The field of view, in degrees between 0 and 180.
+    pub field_of_view: f64,
+
+    /// The vanishing point, in the local space of the object owning the projection.
+    pub projection_center: Point<Twips>,
+}
+
+impl PerspectiveProjection {
+    /// The field of view Flash Player uses by default.
+    pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+    /// The projection that applies when no ancestor has one: centered on the stage.
+    pub fn default_for_stage(stage_size: (u32, u32)) -> Self {
+        Self {
+            field_of_view: Self::DEFAULT_FIELD_OF_VIEW,
+            projection_center: Point::new(
+                Twips::from_pixels(f64::from(stage_size.0) / 2.0),
+                Twips::from_pixels(f64::from(stage_size.1) / 2.0),
+            ),
+        }
+    }
+
+    /// The distance between the viewer and the stage, in pixels.
+    ///
+    /// Like in Flash Player, this follows from the field of view and the width of the stage.
+    pub fn focal_length(&self, stage_width: u32) -> f64 {
+        let half_angle = self.field_of_view.to_radians() / 2.0;
+        f64::from(stage_width) / 2.0 / half_angle.tan()
+    }
+}
+
+/// The projection of a display object that was placed in 3D space onto the plane of its parent.
+///
+/// Points of the object are placed in 3D by its matrix and 3D transform, and then projected
+/// towards the vanishing point by their distance to the viewer.
+#[derive(Clone, Copy, Debug)]
+pub struct Projection {
+    /// The position of the origin of the object, in pixels.
+    origin: [f64; 3],
+
+    /// Where the local x axis of the object points to, in pixels per pixel.
+    x_axis: [f64; 3],
+
+    /// Where the local y axis of the object points to, in pixels per pixel.
+    y_axis: [f64; 3],
+
+    /// The vanishing point, in the coordinate space of the parent, in pixels.
+    center: (f64, f64),
+
+    /// The distance between the viewer and the plane of the parent, in pixels.
+    focal_length: f64,
+}
+
+impl Projection {
+    /// How many strips objects are split into when their depth varies across them.
+    ///
+    /// Each strip is drawn with its own 2D matrix, so more strips follow the perspective closer.
+    pub const STRIPS: usize = 24;
+
+    /// `matrix` is the 2D matrix of the object, `projection_center` the vanishing point in the
+    /// coordinate space of its parent, and `focal_length` the distance to the viewer in pixels.
+    pub fn new(
+        matrix: &Matrix,
+        transform_3d: &Transform3D,
+        projection_center: Point<Twips>,
+        focal_length: f64,
+    ) -> Self {
+        // Flash applies the scale first, then the rotations around the x, y and z axes.
+        let (a, b, c, d) = (
+            f64::from(matrix.a),
+            f64::from(matrix.b),
+            f64::from(matrix.c),
+            f64::from(matrix.d),
+        );
+        let scale_x = f64::hypot(a, b);
+        let scale_y = f64::hypot(c, d);
+        let rotation_z = f64::atan2(b, a);
+        let (sin_x, cos_x) = transform_3d.rotation_x.into_radians().sin_cos();
+        let (sin_y, cos_y) = transform_3d.rotation_y.into_radians().sin_cos();
+        let (sin_z, cos_z) = rotation_z.sin_cos();
+
+        let rotate = |x: f64, y: f64| {
+            // Around the x axis.
+            let (y, z) = (y * cos_x, y * sin_x);
+            // Around the y axis.
+            let (x, z) = (x * cos_y + z * sin_y, z * cos_y - x * sin_y);
+            // Around the z axis.
+            [x * cos_z - y * sin_z, x * sin_z + y * cos_z, z]
+        };
+
+        Self {
+            origin: [matrix.tx.to_pixels(), matrix.ty.to_pixels(), transform_3d.z],
+            x_axis: rotate(scale_x, 0.0),
+            y_axis: rotate(0.0, scale_y),
+            center: (
+                projection_center.x.to_pixels(),
+                projection_center.y.to_pixels(),
+            ),
+            focal_length,
+        }
+    }
+
+    /// Projects a point of the object onto the plane of its parent.
+    ///
+    /// Returns `None` when the point is behind the viewer.
+    pub fn project(&self, point: Point<Twips>) -> Option<Point<Twips>> {
+        let (x, y) = (point.x.to_pixels(), point.y.to_pixels());
+        let position: [f64; 3] =
+            std::array::from_fn(|i| self.origin[i] + x * self.x_axis[i] + y * self.y_axis[i]);
+        let depth = self.focal_length + position[2];
+        if depth <= 0.0 || !depth.is_finite() {
+            return None;
+        }
+        let perspective = self.focal_length / depth;
+        Some(Point::new(
+            Twips::from_pixels(self.center.0 + (position[0] - self.center.0) * perspective),
+            Twips::from_pixels(self.center.1 + (position[1] - self.center.1) * perspective),
+        ))
+    }
+
+    /// Splits `bounds` (in the local space of the object) into strips that can each be drawn
+    /// with a 2D matrix, and returns them along with those matrices.
+    ///
+    /// The strips run across the local axis along which the depth changes the most, so that
+    /// the depth changes as little as possible within each of them. Strips that are (partially)
+    /// behind the viewer are left out.
+    pub fn strips(&self, bounds: &Rectangle<Twips>) -> Vec<(Rectangle<Twips>, Matrix)> {
+        let depth_along_x = self.x_axis[2] * bounds.width().to_pixels();
+        let depth_along_y = self.y_axis[2] * bounds.height().to_pixels();
+        let count = if depth_along_x == 0.0 && depth_along_y == 0.0 {
+            1
+        } else {
+            Self::STRIPS
+        };
+        let split_x = depth_along_x.abs() >= depth_along_y.abs();
+
+        (0..count)
+            .filter_map(|i| {
+                let mut strip = bounds.clone();
+                if split_x {
+                    strip.x_min = lerp(bounds.x_min, bounds.x_max, i, count);
+                    strip.x_max = lerp(bounds.x_min, bounds.x_max, i + 1, count);
+                } else {
+                    strip.y_min = lerp(bounds.y_min, bounds.y_max, i, count);
+                    strip.y_max = lerp(bounds.y_min, bounds.y_max, i + 1, count);
+                }
+                let matrix = self.strip_matrix(&strip)?;
+                Some((strip, matrix))
+            })
+            .collect()
+    }
+
+    /// The 2D matrix that maps the top left, top right and bottom left corners of `strip`
+    /// to their projections.
+    fn strip_matrix(&self, strip: &Rectangle<Twips>) -> Option<Matrix> {
+        let width = strip.width().to_pixels();
+        let height = strip.height().to_pixels();
+        if width <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        let top_left = self.project(Point::new(strip.x_min, strip.y_min))?;
+        let top_right = self.project(Point::new(strip.x_max, strip.y_min))?;
+        let bottom_left = self.project(Point::new(strip.x_min, strip.y_max))?;
+        // Make sure the whole strip is in front of the viewer.
+        self.project(Point::new(strip.x_max, strip.y_max))?;
+
+        let a = (top_right.x - top_left.x).to_pixels() / width;
+        let b = (top_right.y - top_left.y).to_pixels() / width;
+        let c = (bottom_left.x - top_left.x).to_pixels() / height;
+        let d = (bottom_left.y - top_left.y).to_pixels() / height;
+        let (x, y) = (strip.x_min.to_pixels(), strip.y_min.to_pixels());
+        Some(Matrix {
+            a: a as f32,
+            b: b as f32,
+            c: c as f32,
+            d: d as f32,
+            tx: top_left.x - Twips::from_pixels(a * x + c * y),
+            ty: top_left.y - Twips::from_pixels(b * x + d * y),
+        })
+    }
+}
+
+fn lerp(from: Twips, to: Twips, step: usize, steps: usize) -> Twips {
+    from + Twips::new(((to - from).get() as i64 * step as i64 / steps as i64) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOCAL_LENGTH: f64 = 500.0;
+
+    fn center() -> Point<Twips> {
+        Point::new(Twips::from_pixels(100.0), Twips::from_pixels(100.0))
+    }
+
+    fn bounds() -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels(100.0),
+            y_max: Twips::from_pixels(50.0),
+        }
+    }
+
+    fn assert_near(point: Point<Twips>, x: f64, y: f64) {
+        assert!(
+            (point.x.to_pixels() - x).abs() < 0.1 && (point.y.to_pixels() - y).abs() < 0.1,
+            "{point:?} is not near ({x}, {y})"
+        );
+    }
+
+    #[test]
+    fn flat_objects_are_drawn_as_is() {
+        let matrix = Matrix::create_box(2.0, 3.0, Twips::from_pixels(10.0), Twips::ZERO);
+        let projection = Projection::new(&matrix, &Transform3D::default(), center(), FOCAL_LENGTH);
+        let strips = projection.strips(&bounds());
+        assert_eq!(strips.len(), 1);
+        assert_eq!(strips[0].0, bounds());
+        let strip_matrix = strips[0].1;
+        assert!((strip_matrix.a - 2.0).abs() < 1e-6);
+        assert!((strip_matrix.d - 3.0).abs() < 1e-6);
+        assert!(strip_matrix.b.abs() < 1e-6 && strip_matrix.c.abs() < 1e-6);
+        assert_eq!(strip_matrix.tx, matrix.tx);
+        assert_eq!(strip_matrix.ty, matrix.ty);
+    }
+
+    #[test]
+    fn distance_scales_towards_projection_center() {
+        let matrix = Matrix::translate(Twips::from_pixels(200.0), Twips::from_pixels(100.0));
+        let transform_3d = Transform3D {
+            z: FOCAL_LENGTH,
+            ..Default::default()
+        };
+        let projection = Projection::new(&matrix, &transform_3d, center(), FOCAL_LENGTH);
+        assert_near(projection.project(Point::ZERO).unwrap(), 150.0, 100.0);
+        assert_near(
+            projection.project(Point::from_pixels(100.0, 0.0)).unwrap(),
+            200.0,
+            100.0,
+        );
+    }
+
+    #[test]
+    fn rotated_objects_get_perspective() {
+        let matrix = Matrix::translate(Twips::from_pixels(100.0), Twips::from_pixels(100.0));
+        let transform_3d = Transform3D {
+            rotation_y: Degrees::from(60.0),
+            ..Default::default()
+        };
+        let projection = Projection::new(&matrix, &transform_3d, center(), FOCAL_LENGTH);
+
+        // The right edge moves towards the viewer, so it gets longer than the left edge.
+        let top_right = projection
+            .project(Point::from_pixels(100.0, -50.0))
+            .unwrap();
+        let bottom_right = projection.project(Point::from_pixels(100.0, 50.0)).unwrap();
+        let right_edge = (bottom_right.y - top_right.y).to_pixels();
+        assert!(right_edge > 100.0 && right_edge < 150.0);
+
+        let strips = projection.strips(&bounds());
+        assert_eq!(strips.len(), Projection::STRIPS);
+        // Neighbouring strips meet where they're split.
+        for pair in strips.windows(2) {
+            let (left, left_matrix) = &pair[0];
+            let (right, right_matrix) = &pair[1];
+            let seam = Point::new(left.x_max, left.y_min);
+            assert_eq!(seam, Point::new(right.x_min, right.y_min));
+            assert_near(
+                *left_matrix * seam,
+                (*right_matrix * seam).x.to_pixels(),
+                (*right_matrix * seam).y.to_pixels(),
+            );
+        }
+    }
+
+    #[test]
+    fn behind_the_viewer_is_invisible() {
+        let transform_3d = Transform3D {
+            z: -FOCAL_LENGTH,
+            ..Default::default()
+        };
+        let projection = Projection::new(&Matrix::IDENTITY, &transform_3d, center(), FOCAL_LENGTH);
+        assert!(projection.project(Point::ZERO).is_none());
+        assert!(projection.strips(&bounds()).is_empty());
+    }
+}
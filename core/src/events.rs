@@ -6,10 +6,12 @@ pub enum PlayerEvent {
     KeyDown {
         key_code: KeyCode,
         key_char: Option<char>,
+        key_location: KeyLocation,
     },
     KeyUp {
         key_code: KeyCode,
         key_char: Option<char>,
+        key_location: KeyLocation,
     },
     MouseMove {
         x: f64,
@@ -30,6 +32,11 @@ pub enum PlayerEvent {
     MouseWheel {
         delta: MouseWheelDelta,
     },
+    /// The mouse wheel (or touchpad) was scrolled sideways.
+    /// Positive deltas scroll to the left, matching the sign of vertical deltas.
+    MouseHorizontalWheel {
+        delta: MouseWheelDelta,
+    },
     GamepadButtonDown {
         button: GamepadButton,
     },
@@ -58,12 +65,21 @@ impl MouseWheelDelta {
 
     /// Returns the number of lines that this delta represents.
     pub fn lines(self) -> f64 {
-        // TODO: Should we always return an integer here?
         match self {
             Self::Lines(delta) => delta,
             Self::Pixels(delta) => delta / Self::MOUSE_WHEEL_SCALE,
         }
     }
+
+    /// Returns this delta multiplied by the given factor, keeping its unit.
+    ///
+    /// Frontends use this to apply a user-configured wheel speed.
+    pub fn scaled(self, factor: f64) -> Self {
+        match self {
+            Self::Lines(delta) => Self::Lines(delta * factor),
+            Self::Pixels(delta) => Self::Pixels(delta * factor),
+        }
+    }
 }
 
 impl PartialEq for MouseWheelDelta {
@@ -465,6 +481,19 @@ impl TextControlCode {
     }
 }
 
+/// Where on the keyboard a key was pressed, to tell apart keys that exist more than once,
+/// like the left and right Shift keys, or the digits on the numeric keypad.
+///
+/// The values are those of the constants in `flash.ui.KeyLocation`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum KeyLocation {
+    #[default]
+    Standard = 0,
+    Left = 1,
+    Right = 2,
+    NumPad = 3,
+}
+
 /// Flash virtual keycode.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct KeyCode(u32);
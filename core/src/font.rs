@@ -1,10 +1,14 @@
+use crate::asset_cache::{self, ShapeKey};
+use crate::context::RenderContext;
 use crate::drawing::Drawing;
 use crate::html::TextSpan;
 use crate::prelude::*;
 use crate::string::WStr;
+use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, Gc, Mutation};
 use ruffle_render::backend::null::NullBitmapSource;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
+use ruffle_render::commands::CommandHandler;
 use ruffle_render::shape_utils::{DrawCommand, FillRule};
 use ruffle_render::transform::Transform;
 use std::borrow::Cow;
@@ -13,6 +17,8 @@ use std::cmp::max;
 use std::hash::{Hash, Hasher};
 use swf::FillStyle;
 
+pub mod glyph_cache;
+
 pub use swf::TextGridFit;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -208,6 +214,7 @@ impl FontFace {
                         Some(Glyph {
                             shape_handle: Default::default(),
                             shape: GlyphShape::Drawing(drawing),
+                            shape_key: None,
                             advance,
                         })
                     } else {
@@ -216,6 +223,7 @@ impl FontFace {
                         Some(Glyph {
                             shape_handle: Default::default(),
                             shape: GlyphShape::None,
+                            shape_key: None,
                             advance,
                         })
                     }
@@ -388,14 +396,20 @@ impl<'gc> Font<'gc> {
         )))
     }
 
+    /// Creates a font from a `DefineFont` tag.
+    ///
+    /// The tessellation of its glyphs is shared with the same font in identical
+    /// copies of `movie`, if the font is defined by one.
     pub fn from_swf_tag(
         gc_context: &Mutation<'gc>,
         renderer: &mut dyn RenderBackend,
         tag: swf::Font,
         encoding: &'static swf::Encoding,
         font_type: FontType,
+        movie: Option<&SwfMovie>,
     ) -> Font<'gc> {
         let mut code_point_to_glyph = fnv::FnvHashMap::default();
+        let font_id = tag.id;
 
         let descriptor = FontDescriptor::from_swf_tag(&tag, encoding);
         let (ascent, descent, leading) = if let Some(layout) = &tag.layout {
@@ -416,6 +430,7 @@ impl<'gc> Font<'gc> {
                     shape_handle: None.into(),
                     advance: Twips::new(swf_glyph.advance.into()),
                     shape: GlyphShape::Swf(RefCell::new(SwfGlyphOrShape::Glyph(swf_glyph))),
+                    shape_key: movie.map(|movie| ShapeKey::glyph(movie, font_id, index)),
                 };
 
                 // Eager-load ASCII characters.
@@ -783,16 +798,37 @@ impl GlyphShape {
         }
     }
 
-    pub fn register(&self, renderer: &mut dyn RenderBackend) -> Option<ShapeHandle> {
+    pub fn register(
+        &self,
+        renderer: &mut dyn RenderBackend,
+        key: Option<ShapeKey>,
+    ) -> Option<ShapeHandle> {
         match self {
             GlyphShape::Swf(glyph) => {
                 let mut glyph = glyph.borrow_mut();
-                Some(renderer.register_shape((&*glyph.shape()).into(), &NullBitmapSource))
+                let shape: &swf::Shape = glyph.shape();
+                Some(match key {
+                    Some(key) => asset_cache::register_shape(
+                        renderer,
+                        key,
+                        || shape.into(),
+                        &NullBitmapSource,
+                    ),
+                    None => renderer.register_shape(shape.into(), &NullBitmapSource),
+                })
             }
             GlyphShape::Drawing(drawing) => drawing.register_or_replace(renderer),
             GlyphShape::None => None,
         }
     }
+
+    pub fn bounds(&self) -> Rectangle<Twips> {
+        match self {
+            GlyphShape::Swf(glyph) => glyph.borrow_mut().shape().shape_bounds.clone(),
+            GlyphShape::Drawing(drawing) => drawing.self_bounds().clone(),
+            GlyphShape::None => Rectangle::default(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -803,6 +839,10 @@ pub struct Glyph {
     shape_handle: RefCell<Option<Option<ShapeHandle>>>,
 
     shape: GlyphShape,
+
+    /// Identifies the glyph in the asset cache, if it's defined by a movie.
+    shape_key: Option<ShapeKey>,
+
     advance: Twips,
 }
 
@@ -810,7 +850,7 @@ impl Glyph {
     pub fn shape_handle(&self, renderer: &mut dyn RenderBackend) -> Option<ShapeHandle> {
         self.shape_handle
             .borrow_mut()
-            .get_or_insert_with(|| self.shape.register(renderer))
+            .get_or_insert_with(|| self.shape.register(renderer, self.shape_key))
             .clone()
     }
 
@@ -818,6 +858,26 @@ impl Glyph {
         self.shape.hit_test(point, local_matrix)
     }
 
+    /// Renders this glyph with the current transform.
+    ///
+    /// Text using the advanced rendering engine is drawn from the glyph cache when possible,
+    /// to match the grid fitting, thickness and sharpness of Flash Player's text rasterizer.
+    pub fn render(&self, context: &mut RenderContext<'_, '_>, settings: &TextRenderSettings) {
+        let Some(shape_handle) = self.shape_handle(context.renderer) else {
+            return;
+        };
+        if settings.is_advanced() {
+            let glyph_cache = context.glyph_cache;
+            let bounds = self.shape.bounds();
+            if glyph_cache.render_glyph(context, shape_handle.clone(), &bounds, settings) {
+                return;
+            }
+        }
+        context
+            .commands
+            .render_shape(shape_handle, context.transform_stack.transform());
+    }
+
     pub fn advance(&self) -> Twips {
         self.advance
     }
@@ -929,7 +989,7 @@ pub enum TextRenderSettings {
     /// This text should render with the advanced rendering engine.
     /// Set via "Anti-alias for readability" in the Flash IDE.
     /// The parameters are set via the CSMTextSettings SWF tag.
-    /// Ruffle does not support this currently, but this also affects
+    /// Glyphs are rasterized through the `GlyphCache`, and this also affects
     /// hit-testing behavior.
     Advanced {
         grid_fit: TextGridFit,
@@ -1117,6 +1177,7 @@ mod tests {
                     .expect("Built-in font should compile"),
                 reader.encoding(),
                 FontType::Device,
+                None,
             );
 
             callback(mc, device_font);
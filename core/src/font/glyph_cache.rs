@@ -0,0 +1,284 @@
+//! Rasterized glyphs for text using the "Anti-alias for readability" rendering mode.
+
+use crate::context::RenderContext;
+use crate::font::TextRenderSettings;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, GcCell, Mutation};
+use ruffle_render::backend::{BitmapCacheEntry, ShapeHandle};
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::filters::Filter;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::transform::Transform;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+use swf::{Color, ColorMatrixFilter, Rectangle, TextGridFit, Twips};
+
+/// Caches glyphs of advanced anti-aliased text, rasterized at the scale and
+/// subpixel position they're drawn at.
+///
+/// Flash Player renders such text with its own rasterizer, which snaps glyphs to
+/// the pixel grid and applies the `thickness` and `sharpness` of the text field to
+/// their coverage. We approximate this by rendering each glyph into a small texture
+/// with a color matrix filter adjusting its alpha, and drawing that texture at a
+/// pixel aligned position instead of the glyph outline.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct GlyphCache<'gc>(GcCell<'gc, GlyphCacheData>);
+
+#[derive(Collect, Default)]
+#[collect(require_static)]
+struct GlyphCacheData {
+    /// The number of frames rendered so far.
+    frame: u64,
+
+    glyphs: FnvHashMap<GlyphKey, RasterizedGlyph>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    /// The address of the shape of the glyph.
+    shape: usize,
+
+    /// The horizontal and vertical scale, in 1/256ths.
+    scale_x: i32,
+    scale_y: i32,
+
+    /// The horizontal subpixel position, in multiples of `1 / phases`.
+    phase: u8,
+
+    thickness: i16,
+    sharpness: i16,
+}
+
+struct RasterizedGlyph {
+    /// Keeps the shape alive, so that its address can't be reused by another glyph.
+    _shape: ShapeHandle,
+
+    bitmap: BitmapHandle,
+
+    /// The position of the top left corner of the bitmap relative to the glyph origin, in pixels.
+    offset: (i32, i32),
+
+    /// The frame this glyph was last rendered in.
+    last_used: u64,
+}
+
+impl<'gc> GlyphCache<'gc> {
+    /// The number of glyphs kept around between frames.
+    const MAX_GLYPHS: usize = 2048;
+
+    /// Glyphs larger than this, in pixels, are drawn as shapes, as they
+    /// gain nothing from grid fitting and would waste texture memory.
+    const MAX_GLYPH_SIZE: f64 = 256.0;
+
+    pub fn new(mc: &Mutation<'gc>) -> Self {
+        Self(GcCell::new(mc, Default::default()))
+    }
+
+    /// Draws a glyph from the cache with the current transform, rasterizing it first if needed.
+    ///
+    /// Returns `false` if the glyph can't be drawn this way, e.g. because it is rotated or
+    /// the renderer doesn't support offscreen rendering, in which case the caller should
+    /// draw its shape instead.
+    pub fn render_glyph(
+        self,
+        context: &mut RenderContext<'_, 'gc>,
+        shape: ShapeHandle,
+        bounds: &Rectangle<Twips>,
+        settings: &TextRenderSettings,
+    ) -> bool {
+        if !context.use_bitmap_cache || !context.renderer.is_offscreen_supported() {
+            return false;
+        }
+
+        let transform = context.transform_stack.transform();
+        let matrix = transform.matrix;
+        if matrix.b != 0.0 || matrix.c != 0.0 || matrix.a <= 0.0 || matrix.d <= 0.0 {
+            return false;
+        }
+        if !bounds.is_valid() {
+            // Nothing to draw, e.g. for spaces.
+            return true;
+        }
+
+        let (scale_x, scale_y) = (f64::from(matrix.a), f64::from(matrix.d));
+        let left = bounds.x_min.to_pixels() * scale_x;
+        let right = bounds.x_max.to_pixels() * scale_x;
+        let top = bounds.y_min.to_pixels() * scale_y;
+        let bottom = bounds.y_max.to_pixels() * scale_y;
+        if right - left > Self::MAX_GLYPH_SIZE || bottom - top > Self::MAX_GLYPH_SIZE {
+            return false;
+        }
+
+        let phases = subpixel_phases(settings.grid_fit());
+        let (origin_x, phase) = snap_to_phase(matrix.tx.to_pixels(), phases);
+        let origin_y = matrix.ty.to_pixels().round();
+
+        let key = GlyphKey {
+            shape: Arc::as_ptr(&shape.0) as *const () as usize,
+            scale_x: (scale_x * 256.0).round() as i32,
+            scale_y: (scale_y * 256.0).round() as i32,
+            phase,
+            thickness: settings.thickness().round() as i16,
+            sharpness: settings.sharpness().round() as i16,
+        };
+
+        let mut write = self.0.write(context.gc_context);
+        let frame = write.frame;
+        let glyph = match write.glyphs.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                // Leave a pixel of padding around the glyph, so that its anti-aliased edges fit.
+                let x0 = left.floor() as i32 - 1;
+                let y0 = top.floor() as i32 - 1;
+                let width = (right + 1.0).ceil() as i32 + 1 - x0;
+                let height = bottom.ceil() as i32 + 1 - y0;
+                let Ok(bitmap) = context
+                    .renderer
+                    .create_empty_texture(width as u32, height as u32)
+                else {
+                    return false;
+                };
+
+                let mut commands = CommandList::new();
+                commands.render_shape(
+                    shape.clone(),
+                    Transform {
+                        matrix: Matrix {
+                            a: matrix.a,
+                            d: matrix.d,
+                            tx: Twips::from_pixels(
+                                f64::from(phase) / f64::from(phases) - f64::from(x0),
+                            ),
+                            ty: Twips::from_pixels(-f64::from(y0)),
+                            ..Default::default()
+                        },
+                        color_transform: Default::default(),
+                    },
+                );
+                context.cache_draws.push(BitmapCacheEntry {
+                    handle: bitmap.clone(),
+                    commands,
+                    clear: Color::TRANSPARENT,
+                    filters: coverage_filter(settings).into_iter().collect(),
+                });
+
+                entry.insert(RasterizedGlyph {
+                    _shape: shape,
+                    bitmap,
+                    offset: (x0, y0),
+                    last_used: frame,
+                })
+            }
+        };
+        glyph.last_used = frame;
+
+        context.commands.render_bitmap(
+            glyph.bitmap.clone(),
+            Transform {
+                matrix: Matrix::translate(
+                    Twips::from_pixels(origin_x + f64::from(glyph.offset.0)),
+                    Twips::from_pixels(origin_y + f64::from(glyph.offset.1)),
+                ),
+                color_transform: transform.color_transform,
+            },
+            false,
+            PixelSnapping::Always,
+        );
+        true
+    }
+
+    /// Finish a frame, forgetting the least recently used glyphs if there are too many.
+    pub fn end_frame(self, mc: &Mutation<'gc>) {
+        let mut write = self.0.write(mc);
+        write.frame += 1;
+        let excess = write.glyphs.len().saturating_sub(Self::MAX_GLYPHS);
+        if excess == 0 {
+            return;
+        }
+
+        let mut candidates: Vec<_> = write
+            .glyphs
+            .iter()
+            .map(|(key, glyph)| (*key, glyph.last_used))
+            .collect();
+        candidates.sort_unstable_by_key(|(_, last_used)| *last_used);
+        for (key, _) in candidates.into_iter().take(excess) {
+            write.glyphs.remove(&key);
+        }
+    }
+}
+
+/// The number of horizontal positions within a pixel that glyphs are rasterized at.
+///
+/// Pixel grid fitting snaps glyphs to whole pixels, while the other modes
+/// keep some of their subpixel position. Glyphs are always snapped vertically,
+/// to keep the baseline of a line crisp.
+fn subpixel_phases(grid_fit: TextGridFit) -> u8 {
+    match grid_fit {
+        TextGridFit::Pixel => 1,
+        TextGridFit::SubPixel => 3,
+        TextGridFit::None => 4,
+    }
+}
+
+/// Splits a horizontal position into a whole pixel and the nearest subpixel phase.
+fn snap_to_phase(x: f64, phases: u8) -> (f64, u8) {
+    let pixel = x.floor();
+    let phase = ((x - pixel) * f64::from(phases)).round() as u8;
+    if phase >= phases {
+        (pixel + 1.0, 0)
+    } else {
+        (pixel, phase)
+    }
+}
+
+/// Builds the filter that applies the thickness and sharpness of text to the coverage of its glyphs.
+///
+/// Thickness (-200 to 200) scales the coverage, making the glyphs bolder or thinner, while
+/// sharpness (-400 to 400) increases the contrast of the anti-aliased edges around half coverage.
+/// Flash Player blurs glyphs with a negative sharpness; we draw them unchanged instead.
+fn coverage_filter(settings: &TextRenderSettings) -> Option<Filter> {
+    let gain = 1.0 + settings.thickness().clamp(-200.0, 200.0) / 200.0;
+    let contrast = 1.0 + settings.sharpness().clamp(0.0, 400.0) / 400.0;
+    if gain == 1.0 && contrast == 1.0 {
+        return None;
+    }
+
+    let mut filter = ColorMatrixFilter::default();
+    filter.matrix[18] = gain * contrast;
+    filter.matrix[19] = 127.5 * (1.0 - contrast);
+    Some(Filter::ColorMatrixFilter(filter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snap_to_phase_rounds_to_nearest_phase() {
+        assert_eq!(snap_to_phase(10.2, 1), (10.0, 0));
+        assert_eq!(snap_to_phase(10.6, 1), (11.0, 0));
+        assert_eq!(snap_to_phase(10.4, 3), (10.0, 1));
+        assert_eq!(snap_to_phase(10.9, 4), (11.0, 0));
+        assert_eq!(snap_to_phase(-0.5, 4), (-1.0, 2));
+    }
+
+    #[test]
+    fn coverage_filter_only_changes_alpha() {
+        let settings = TextRenderSettings::default();
+        assert!(coverage_filter(&settings).is_none());
+
+        let settings = settings.with_thickness(100.0).with_sharpness(400.0);
+        let Some(Filter::ColorMatrixFilter(filter)) = coverage_filter(&settings) else {
+            panic!("expected a color matrix filter");
+        };
+        assert_eq!(
+            &filter.matrix[..15],
+            &ColorMatrixFilter::default().matrix[..15]
+        );
+        assert_eq!(filter.matrix[18], 3.0);
+        assert_eq!(filter.matrix[19], -127.5);
+    }
+}
@@ -0,0 +1,108 @@
+//! Encoding variables as sent by `LoadVars` in AVM1 and `URLVariables` in AVM2.
+//!
+//! Flash Player doesn't follow `application/x-www-form-urlencoded` when sending variables:
+//! it escapes every byte of the UTF-8 encoding that isn't an ASCII letter or digit (including
+//! `-_.*`), with uppercase hex digits. Spaces become `+` in AVM1 and `%20` in AVM2. Servers
+//! that sign requests compare against that exact string, so both AVMs share these functions.
+
+use std::fmt::Write;
+
+/// Escapes a name or value like `URLVariables` does in AVM2, with spaces as `%20`.
+pub fn escape_form_value(value: &str) -> String {
+    escape(value, "%20")
+}
+
+/// Encodes variables as `name=value` pairs separated by `&`, in the given order,
+/// like `LoadVars` and `loadVariables` do in AVM1, with spaces as `+`.
+///
+/// Names with several values are given once per value, so they must be repeated by `pairs`.
+pub fn encode_form<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+    let mut encoded = String::new();
+    for (name, value) in pairs {
+        if !encoded.is_empty() {
+            encoded.push('&');
+        }
+        encoded.push_str(&escape(name, "+"));
+        encoded.push('=');
+        encoded.push_str(&escape(value, "+"));
+    }
+    encoded
+}
+
+fn escape(value: &str, space: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() {
+            escaped.push(byte as char);
+        } else if byte == b' ' {
+            escaped.push_str(space);
+        } else {
+            let _ = write!(escaped, "%{byte:02X}");
+        }
+    }
+    escaped
+}
+
+/// Reverses [`escape_form_value`], also turning every `+` into a space, as browsers send them.
+///
+/// Unlike `unescape`, escaped null characters are kept, and `%` signs that don't start an
+/// escape are left as they are.
+pub fn unescape_form_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => unescaped.push(b' '),
+            b'%' => {
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = hex {
+                    unescaped.push(byte);
+                    i += 2;
+                } else {
+                    unescaped.push(b'%');
+                }
+            }
+            byte => unescaped.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_like_flash_player() {
+        assert_eq!(escape_form_value("a-b_c.d*e f"), "a%2Db%5Fc%2Ed%2Ae%20f");
+        assert_eq!(escape_form_value("é\0"), "%C3%A9%00");
+        assert_eq!(
+            encode_form([("name", "John Doe"), ("tag", "a"), ("tag", "b+c")]),
+            "name=John+Doe&tag=a&tag=b%2Bc"
+        );
+        assert_eq!(
+            encode_form([("baz", "[object Object]")]),
+            "baz=%5Bobject+Object%5D"
+        );
+    }
+
+    #[test]
+    fn keeps_the_order_of_repeated_names() {
+        assert_eq!(
+            encode_form([("b", "1"), ("a", "2"), ("b", "3"), ("a", "4")]),
+            "b=1&a=2&b=3&a=4"
+        );
+    }
+
+    #[test]
+    fn unescapes_pluses_and_escapes() {
+        assert_eq!(unescape_form_value("John+Doe+Jr"), "John Doe Jr");
+        assert_eq!(unescape_form_value("%C3%a9%00%2B"), "é\0+");
+        assert_eq!(unescape_form_value("100%+%zz%4"), "100% %zz%4");
+    }
+}
@@ -0,0 +1,186 @@
+//! Hooks that embedders run on every frame, to inspect or change the movie without patching
+//! the player, as done by trainer tools, analytics collection or test harnesses.
+//!
+//! Hooks only see the movie through a [`FrameHookContext`], which exposes what they need
+//! (like the variables of the movie) while keeping the player's internals out of reach.
+
+use crate::avm1::{Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier};
+use crate::avm2::object::TObject as _;
+use crate::avm2::{Activation as Avm2Activation, Value as Avm2Value};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::external::Value as ExternalValue;
+use crate::string::AvmString;
+
+/// When a frame hook runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameHookPhase {
+    /// At the start of every tick, before the movie runs any frame or timer.
+    PreTick,
+
+    /// After every render of the movie.
+    PostRender,
+}
+
+/// Identifies a frame hook, to remove it with [`crate::Player::remove_frame_hook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FrameHookHandle(u64);
+
+pub type FrameHook = Box<dyn FnMut(&mut FrameHookContext<'_, '_>)>;
+
+/// The frame hooks added to a player.
+#[derive(Default)]
+pub(crate) struct FrameHooks {
+    hooks: Vec<(FrameHookHandle, FrameHookPhase, FrameHook)>,
+    next_handle: u64,
+}
+
+impl FrameHooks {
+    pub fn add(&mut self, phase: FrameHookPhase, hook: FrameHook) -> FrameHookHandle {
+        let handle = FrameHookHandle(self.next_handle);
+        self.next_handle += 1;
+        self.hooks.push((handle, phase, hook));
+        handle
+    }
+
+    /// Removes a hook, returning whether it was there.
+    pub fn remove(&mut self, handle: FrameHookHandle) -> bool {
+        let len = self.hooks.len();
+        self.hooks
+            .retain(|(hook_handle, _, _)| *hook_handle != handle);
+        self.hooks.len() != len
+    }
+
+    pub fn has_phase(&self, phase: FrameHookPhase) -> bool {
+        self.hooks
+            .iter()
+            .any(|(_, hook_phase, _)| *hook_phase == phase)
+    }
+
+    /// Runs the hooks of a phase, in the order they were added.
+    pub fn run(&mut self, phase: FrameHookPhase, context: &mut UpdateContext<'_>) {
+        let mut hook_context = FrameHookContext { context };
+        for (_, hook_phase, hook) in &mut self.hooks {
+            if *hook_phase == phase {
+                hook(&mut hook_context);
+            }
+        }
+    }
+}
+
+/// What frame hooks can see and change of the movie.
+pub struct FrameHookContext<'a, 'gc> {
+    context: &'a mut UpdateContext<'gc>,
+}
+
+impl<'a, 'gc> FrameHookContext<'a, 'gc> {
+    /// The URL of the root movie.
+    pub fn movie_url(&self) -> &str {
+        self.context.swf.url()
+    }
+
+    /// The frame rate the movie currently runs at.
+    pub fn frame_rate(&self) -> f64 {
+        *self.context.frame_rate
+    }
+
+    /// The frame the root timeline is at (starting from 1), or `None` before the movie loaded.
+    pub fn current_frame(&self) -> Option<u16> {
+        self.root_clip()
+            .and_then(|root| root.as_movie_clip())
+            .map(|root| root.current_frame())
+    }
+
+    /// How many frames the root timeline has, or `None` before the movie loaded.
+    pub fn total_frames(&self) -> Option<u16> {
+        self.root_clip()
+            .and_then(|root| root.as_movie_clip())
+            .map(|root| root.total_frames())
+    }
+
+    /// Reads a variable of the movie, given by its path from the root timeline.
+    ///
+    /// AVM1 movies take any path their scripts could use, like `score` or `_root.player.lives`.
+    /// AVM2 movies take public properties separated by dots, like `game.player.lives`.
+    /// Variables that don't exist read as `undefined`.
+    pub fn get_variable(&mut self, path: &str) -> ExternalValue {
+        let Some(root) = self.root_clip() else {
+            return ExternalValue::Undefined;
+        };
+
+        if root.movie().is_action_script_3() {
+            let mut activation = Avm2Activation::from_nothing(self.context);
+            ExternalValue::from_avm2(get_avm2_path(&mut activation, root.object2(), path))
+        } else {
+            let mut activation = Avm1Activation::from_nothing(
+                self.context,
+                Avm1ActivationIdentifier::root("[Frame Hook]"),
+                root,
+            );
+            let path = AvmString::new_utf8(activation.context.gc_context, path);
+            activation
+                .get_variable(path)
+                .and_then(|value| ExternalValue::from_avm1(&mut activation, value.into()))
+                .unwrap_or(ExternalValue::Undefined)
+        }
+    }
+
+    /// Changes a variable of the movie, given by its path from the root timeline like in
+    /// [`Self::get_variable`]. Returns whether the variable could be set.
+    pub fn set_variable(&mut self, path: &str, value: ExternalValue) -> bool {
+        let Some(root) = self.root_clip() else {
+            return false;
+        };
+
+        if root.movie().is_action_script_3() {
+            let mut activation = Avm2Activation::from_nothing(self.context);
+            let (parent, name) = match path.rsplit_once('.') {
+                Some((parent_path, name)) => (
+                    get_avm2_path(&mut activation, root.object2(), parent_path),
+                    name,
+                ),
+                None => (root.object2(), path),
+            };
+            let Avm2Value::Object(parent) = parent else {
+                return false;
+            };
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let value = value.into_avm2(&mut activation);
+            parent
+                .set_public_property(name, value, &mut activation)
+                .is_ok()
+        } else {
+            let mut activation = Avm1Activation::from_nothing(
+                self.context,
+                Avm1ActivationIdentifier::root("[Frame Hook]"),
+                root,
+            );
+            let path = AvmString::new_utf8(activation.context.gc_context, path);
+            let value = value.into_avm1(&mut activation);
+            activation.set_variable(path, value).is_ok()
+        }
+    }
+
+    fn root_clip(&self) -> Option<DisplayObject<'gc>> {
+        self.context.stage.root_clip()
+    }
+}
+
+/// Follows public properties separated by dots from `value`, or returns `undefined` if any
+/// of them can't be read.
+fn get_avm2_path<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    mut value: Avm2Value<'gc>,
+    path: &str,
+) -> Avm2Value<'gc> {
+    for name in path.split('.') {
+        let Avm2Value::Object(object) = value else {
+            return Avm2Value::Undefined;
+        };
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+        value = object
+            .get_public_property(name, activation)
+            .unwrap_or(Avm2Value::Undefined);
+    }
+    value
+}
@@ -10,11 +10,16 @@
 //! AVM1 code (presumably, either on an AVM1 stage or within an `AVM1Movie`)
 //! runs in one phase, with timeline operations executing with all phases
 //! inline in the order that clips were originally created.
+//!
+//! The frame events (`enterFrame`, `frameConstructed`, `exitFrame` and
+//! `render`) are all broadcast from this module, once the display objects
+//! are done with the matching phase.
 
-use crate::avm2::Avm2;
+use crate::avm2::{Avm2, EventObject as Avm2EventObject};
 use crate::avm2_stub_method_context;
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+use crate::loader::LoadManager;
 use tracing::instrument;
 
 /// Which phase of the frame we're currently in.
@@ -81,13 +86,14 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_>) {
         orphan.enter_frame(context);
     });
     stage.enter_frame(context);
+    broadcast_frame_event(context, "enterFrame");
 
     *context.frame_phase = FramePhase::Construct;
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.construct_frame(context);
     });
     stage.construct_frame(context);
-    stage.frame_constructed(context);
+    broadcast_frame_event(context, "frameConstructed");
 
     *context.frame_phase = FramePhase::FrameScripts;
     Avm2::each_orphan_obj(context, |orphan, context| {
@@ -96,7 +102,7 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_>) {
     stage.run_frame_scripts(context);
 
     *context.frame_phase = FramePhase::Exit;
-    stage.exit_frame(context);
+    exit_frame(context);
 
     // We cannot easily remove dead `GcWeak` instances from the orphan list
     // inside `each_orphan_movie`, since the callback may modify the orphan list.
@@ -153,7 +159,7 @@ pub fn run_inner_goto_frame<'gc>(
         orphan.construct_frame(context);
     });
     stage.construct_frame(context);
-    stage.frame_constructed(context);
+    broadcast_frame_event(context, "frameConstructed");
 
     *context.frame_phase = FramePhase::FrameScripts;
     stage.run_frame_scripts(context);
@@ -166,7 +172,7 @@ pub fn run_inner_goto_frame<'gc>(
     }
 
     *context.frame_phase = FramePhase::Exit;
-    stage.exit_frame(context);
+    exit_frame(context);
 
     // We cannot easily remove dead `GcWeak` instances from the orphan list
     // inside `each_orphan_movie`, since the callback may modify the orphan list.
@@ -178,6 +184,29 @@ pub fn run_inner_goto_frame<'gc>(
     *context.frame_phase = old_phase;
 }
 
+/// Broadcast a frame event to every display object listening to it, in the
+/// order they started listening.
+///
+/// When frame events are logged, the name of the event is traced first, so
+/// that tests can check which phase of the frame each handler runs in.
+pub fn broadcast_frame_event(context: &mut UpdateContext<'_>, event_name: &'static str) {
+    if context.avm2.log_frame_events() {
+        context.avm_trace(&format!("[{event_name}]"));
+    }
+
+    let event = Avm2EventObject::bare_default_event(context, event_name);
+    let dobject_constr = context.avm2.classes().display_object;
+    Avm2::broadcast_event(context, event, dobject_constr);
+}
+
+/// Finish the current frame: broadcast `exitFrame`, then let loaders that
+/// completed during the frame clean up.
+pub fn exit_frame(context: &mut UpdateContext<'_>) {
+    broadcast_frame_event(context, "exitFrame");
+
+    LoadManager::run_exit_frame(context);
+}
+
 /// Run all previously-executed frame phases on a newly-constructed display
 /// object.
 ///
@@ -0,0 +1,422 @@
+//! Summaries of the objects a movie keeps alive, to diagnose memory leaks.
+//!
+//! The garbage collector can't list the objects it manages, so we find them by following
+//! references from the stage and the AVM1 globals: the display list, the script objects of
+//! display objects, and the properties of script objects. Objects only referenced from
+//! elsewhere, e.g. from closures or queued events, aren't found, but are still counted in the
+//! total size reported by the garbage collector.
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, Object as Avm1Object, TObject as _,
+    Value as Avm1Value,
+};
+use crate::avm2::property::Property;
+use crate::avm2::{Object as Avm2Object, TObject as _, Value as Avm2Value};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::string::AvmString;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+
+/// How many objects of each kind were found.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeapSummary {
+    /// The bytes allocated by the garbage collector, including objects that weren't found.
+    pub gc_bytes: usize,
+
+    /// Display objects, by kind.
+    pub display_objects: BTreeMap<String, usize>,
+
+    /// AVM1 objects, by kind.
+    pub avm1_objects: BTreeMap<String, usize>,
+
+    /// AVM2 objects, by class.
+    pub avm2_objects: BTreeMap<String, usize>,
+
+    /// Distinct strings stored in properties.
+    pub strings: usize,
+
+    /// The total length of these strings, in characters.
+    pub string_length: usize,
+
+    /// Interned strings still alive, whether they were found or not.
+    pub interned_strings: usize,
+}
+
+impl fmt::Display for HeapSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "GC heap: {} bytes", self.gc_bytes)?;
+        for (title, counts) in [
+            ("Display objects", &self.display_objects),
+            ("AVM1 objects", &self.avm1_objects),
+            ("AVM2 objects", &self.avm2_objects),
+        ] {
+            writeln!(f, "{title}: {}", counts.values().sum::<usize>())?;
+            let mut counts: Vec<_> = counts.iter().collect();
+            counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+            for (name, count) in counts {
+                writeln!(f, "  {count} {name}")?;
+            }
+        }
+        writeln!(
+            f,
+            "Strings: {} ({} characters)",
+            self.strings, self.string_length
+        )?;
+        write!(f, "Interned strings: {}", self.interned_strings)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeapNodeKind {
+    DisplayObject,
+    Avm1Object,
+    Avm2Object,
+    String,
+}
+
+impl HeapNodeKind {
+    fn name(self) -> &'static str {
+        match self {
+            HeapNodeKind::DisplayObject => "display_object",
+            HeapNodeKind::Avm1Object => "avm1_object",
+            HeapNodeKind::Avm2Object => "avm2_object",
+            HeapNodeKind::String => "string",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HeapNode {
+    pub kind: HeapNodeKind,
+
+    /// The kind of display object or AVM1 object, or the class of an AVM2 object.
+    pub type_name: String,
+
+    /// The instance name of a display object, or the length of a string.
+    pub detail: Option<String>,
+}
+
+/// A reference from one node to another, through a property or the display list.
+#[derive(Clone, Debug)]
+pub struct HeapEdge {
+    pub from: usize,
+    pub to: usize,
+    pub name: String,
+}
+
+/// Everything found and how it references each other, where nodes are referred to by index.
+#[derive(Clone, Debug, Default)]
+pub struct HeapGraph {
+    pub nodes: Vec<HeapNode>,
+    pub edges: Vec<HeapEdge>,
+}
+
+#[derive(Clone, Debug)]
+pub struct HeapDump {
+    pub summary: HeapSummary,
+
+    /// The full reference graph, if it was asked for.
+    pub graph: Option<HeapGraph>,
+}
+
+impl HeapDump {
+    /// Finds the objects reachable from the stage and the AVM1 globals.
+    pub fn capture(context: &mut UpdateContext<'_>, include_graph: bool, gc_bytes: usize) -> Self {
+        let mut walker = Walker {
+            ids: HashMap::new(),
+            queue: VecDeque::new(),
+            summary: HeapSummary {
+                gc_bytes,
+                interned_strings: context.strings.interned_count(),
+                ..Default::default()
+            },
+            graph: include_graph.then(HeapGraph::default),
+        };
+        walker.reach(None, "stage", Node::DisplayObject(context.stage.into()));
+        walker.reach(None, "_global", Node::Avm1(context.avm1.global_object()));
+        walker.run(context);
+
+        Self {
+            summary: walker.summary,
+            graph: walker.graph,
+        }
+    }
+
+    /// Writes this dump as JSON, with a node list and an edge list like most graph tools import.
+    pub fn to_json(&self) -> serde_json::Value {
+        let summary = &self.summary;
+        let mut dump = json!({
+            "summary": {
+                "gc_bytes": summary.gc_bytes,
+                "display_objects": summary.display_objects,
+                "avm1_objects": summary.avm1_objects,
+                "avm2_objects": summary.avm2_objects,
+                "strings": summary.strings,
+                "string_length": summary.string_length,
+                "interned_strings": summary.interned_strings,
+            }
+        });
+        if let Some(graph) = &self.graph {
+            dump["nodes"] = graph
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(id, node)| {
+                    json!({
+                        "id": id,
+                        "kind": node.kind.name(),
+                        "type": node.type_name,
+                        "detail": node.detail,
+                    })
+                })
+                .collect();
+            dump["edges"] = graph
+                .edges
+                .iter()
+                .map(|edge| json!({ "from": edge.from, "to": edge.to, "name": edge.name }))
+                .collect();
+        }
+        dump
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Node<'gc> {
+    DisplayObject(DisplayObject<'gc>),
+    Avm1(Avm1Object<'gc>),
+    Avm2(Avm2Object<'gc>),
+    String(AvmString<'gc>),
+}
+
+impl<'gc> Node<'gc> {
+    fn ptr(self) -> *const () {
+        match self {
+            Node::DisplayObject(object) => object.as_ptr() as *const (),
+            Node::Avm1(object) => object.as_ptr() as *const (),
+            Node::Avm2(object) => object.as_ptr() as *const (),
+            Node::String(string) => string.as_wstr() as *const _ as *const (),
+        }
+    }
+}
+
+struct Walker<'gc> {
+    /// The index of every node found, by address.
+    ids: HashMap<*const (), usize>,
+
+    /// Nodes found whose references haven't been followed yet.
+    queue: VecDeque<(usize, Node<'gc>)>,
+
+    summary: HeapSummary,
+    graph: Option<HeapGraph>,
+}
+
+impl<'gc> Walker<'gc> {
+    fn reach(&mut self, from: Option<usize>, edge: impl fmt::Display, node: Node<'gc>) {
+        let id = match self.ids.get(&node.ptr()) {
+            Some(id) => *id,
+            None => {
+                let id = self.ids.len();
+                self.ids.insert(node.ptr(), id);
+                self.found(node);
+                if !matches!(node, Node::String(_)) {
+                    self.queue.push_back((id, node));
+                }
+                id
+            }
+        };
+
+        if let (Some(graph), Some(from)) = (&mut self.graph, from) {
+            graph.edges.push(HeapEdge {
+                from,
+                to: id,
+                name: edge.to_string(),
+            });
+        }
+    }
+
+    fn found(&mut self, node: Node<'gc>) {
+        let (kind, type_name, detail) = match node {
+            Node::DisplayObject(object) => (
+                HeapNodeKind::DisplayObject,
+                object.type_name().to_string(),
+                Some(object.name().to_string()).filter(|name| !name.is_empty()),
+            ),
+            Node::Avm1(object) => (
+                HeapNodeKind::Avm1Object,
+                avm1_type_name(object).to_string(),
+                None,
+            ),
+            Node::Avm2(object) => (HeapNodeKind::Avm2Object, avm2_type_name(object), None),
+            Node::String(string) => {
+                self.summary.strings += 1;
+                self.summary.string_length += string.len();
+                (
+                    HeapNodeKind::String,
+                    "String".to_string(),
+                    Some(string.len().to_string()),
+                )
+            }
+        };
+
+        let counts = match kind {
+            HeapNodeKind::DisplayObject => Some(&mut self.summary.display_objects),
+            HeapNodeKind::Avm1Object => Some(&mut self.summary.avm1_objects),
+            HeapNodeKind::Avm2Object => Some(&mut self.summary.avm2_objects),
+            HeapNodeKind::String => None,
+        };
+        if let Some(counts) = counts {
+            *counts.entry(type_name.clone()).or_default() += 1;
+        }
+
+        if let Some(graph) = &mut self.graph {
+            graph.nodes.push(HeapNode {
+                kind,
+                type_name,
+                detail,
+            });
+        }
+    }
+
+    fn run(&mut self, context: &mut UpdateContext<'gc>) {
+        while let Some((id, node)) = self.queue.pop_front() {
+            match node {
+                Node::DisplayObject(object) => self.follow_display_object(id, object),
+                Node::Avm1(object) => self.follow_avm1(context, id, object),
+                Node::Avm2(object) => self.follow_avm2(id, object),
+                Node::String(_) => {}
+            }
+        }
+    }
+
+    fn follow_display_object(&mut self, id: usize, object: DisplayObject<'gc>) {
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                let name = child.name();
+                if name.is_empty() {
+                    self.reach(Some(id), "[child]", Node::DisplayObject(child));
+                } else {
+                    self.reach(Some(id), name, Node::DisplayObject(child));
+                }
+            }
+        }
+        if let Avm1Value::Object(script_object) = object.object() {
+            self.reach(Some(id), "[avm1 object]", Node::Avm1(script_object));
+        }
+        if let Avm2Value::Object(script_object) = object.object2() {
+            self.reach(Some(id), "[avm2 object]", Node::Avm2(script_object));
+        }
+    }
+
+    fn follow_avm1(
+        &mut self,
+        context: &mut UpdateContext<'gc>,
+        id: usize,
+        object: Avm1Object<'gc>,
+    ) {
+        let base_clip = context.stage.into();
+        let mut activation = Avm1Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[Heap Dump]"),
+            base_clip,
+        );
+
+        if let Some(display_object) = object.as_display_object() {
+            self.reach(
+                Some(id),
+                "[display object]",
+                Node::DisplayObject(display_object),
+            );
+        }
+        if let Avm1Value::Object(proto) = object.proto(&mut activation) {
+            self.reach(Some(id), "__proto__", Node::Avm1(proto));
+        }
+        for key in object.get_keys(&mut activation, true) {
+            match object.get_local_stored(key, &mut activation, false) {
+                Some(Avm1Value::Object(value)) => self.reach(Some(id), key, Node::Avm1(value)),
+                Some(Avm1Value::String(value)) => self.reach(Some(id), key, Node::String(value)),
+                _ => {}
+            }
+        }
+    }
+
+    fn follow_avm2(&mut self, id: usize, object: Avm2Object<'gc>) {
+        if let Some(display_object) = object.as_display_object() {
+            self.reach(
+                Some(id),
+                "[display object]",
+                Node::DisplayObject(display_object),
+            );
+        }
+        if let Some(proto) = object.proto() {
+            self.reach(Some(id), "[prototype]", Node::Avm2(proto));
+        }
+
+        let slots: Vec<_> = object
+            .vtable()
+            .resolved_traits()
+            .iter()
+            .filter_map(|(name, _, property)| match property {
+                Property::Slot { slot_id } | Property::ConstSlot { slot_id } => {
+                    Some((name, object.get_slot(*slot_id)))
+                }
+                _ => None,
+            })
+            .collect();
+        for (name, value) in slots {
+            self.reach_avm2_value(id, name, value);
+        }
+
+        for (name, value) in object.base().dynamic_properties() {
+            match name {
+                Avm2Value::String(name) => self.reach_avm2_value(id, name, value),
+                Avm2Value::Number(index) => self.reach_avm2_value(id, index, value),
+                Avm2Value::Object(key) => {
+                    // Dictionaries can be keyed by objects.
+                    self.reach(Some(id), "[key]", Node::Avm2(key));
+                    self.reach_avm2_value(id, "[value]", value);
+                }
+                _ => {}
+            }
+        }
+
+        let elements: Vec<_> = object
+            .as_array_storage()
+            .map(|storage| storage.iter().collect())
+            .unwrap_or_default();
+        for (index, value) in elements.into_iter().enumerate() {
+            if let Some(value) = value {
+                self.reach_avm2_value(id, index, value);
+            }
+        }
+    }
+
+    fn reach_avm2_value(&mut self, from: usize, edge: impl fmt::Display, value: Avm2Value<'gc>) {
+        match value {
+            Avm2Value::Object(object) => self.reach(Some(from), edge, Node::Avm2(object)),
+            Avm2Value::String(string) => self.reach(Some(from), edge, Node::String(string)),
+            _ => {}
+        }
+    }
+}
+
+fn avm1_type_name(object: Avm1Object<'_>) -> &'static str {
+    if let Some(display_object) = object.as_display_object() {
+        display_object.type_name()
+    } else if object.as_executable().is_some() {
+        "Function"
+    } else if object.as_array_object().is_some() {
+        "Array"
+    } else {
+        "Object"
+    }
+}
+
+fn avm2_type_name(object: Avm2Object<'_>) -> String {
+    if object.as_class_object().is_some() {
+        "Class".to_string()
+    } else {
+        object.instance_class().name().local_name().to_string()
+    }
+}
@@ -5,12 +5,12 @@ mod iterators;
 mod layout;
 mod text_format;
 
-pub use dimensions::Position;
+pub use dimensions::{BoxBounds, Position, Size};
 pub use layout::{
     lower_from_text_spans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics,
 };
 pub use stylesheet::{transform_dashes_to_camel_case, CssStream};
-pub use text_format::{FormatSpans, TextDisplay, TextFormat, TextSpan};
+pub use text_format::{FormatSpans, TextDisplay, TextFormat, TextImage, TextSpan};
 
 mod stylesheet;
 #[cfg(test)]
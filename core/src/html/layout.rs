@@ -4,7 +4,7 @@ use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{EvalParameters, Font, FontType};
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, TextFormat, TextImage, TextSpan};
 use crate::string::{utils as string_utils, WStr};
 use crate::tag_utils::SwfMovie;
 use crate::DefaultFont;
@@ -116,10 +116,32 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// The images embedded in the text being laid out.
+    text_images: &'a [TextImage],
+
+    /// The sizes of `text_images`, excluding the space around them.
+    image_sizes: &'a [Size<Twips>],
+
+    /// The index of the first image in `text_images` that the layout has not reached yet.
+    next_image: usize,
+
+    /// Images that the layout has reached, but that still wait for the start
+    /// of a line to be placed at.
+    pending_images: Vec<usize>,
+
+    /// The images placed so far.
+    images: Vec<LayoutImage>,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
-    fn new(movie: Arc<SwfMovie>, max_bounds: Twips, text: &'a WStr) -> Self {
+    fn new(
+        movie: Arc<SwfMovie>,
+        max_bounds: Twips,
+        text: &'a WStr,
+        text_images: &'a [TextImage],
+        image_sizes: &'a [Size<Twips>],
+    ) -> Self {
         Self {
             movie,
             cursor: Default::default(),
@@ -136,6 +158,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             has_line_break: false,
             current_line_span: Default::default(),
             max_bounds,
+            text_images,
+            image_sizes,
+            next_image: 0,
+            pending_images: Vec::new(),
+            images: Vec::new(),
         }
     }
 
@@ -297,9 +324,12 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let mut line_bounds = line_bounds.unwrap_or_default();
 
+        let (left_image_offset, right_image_offset) = self.image_offsets();
         let left_adjustment =
-            Self::left_alignment_offset(&self.current_line_span, self.is_first_line);
-        let right_adjustment = Twips::from_pixels(self.current_line_span.right_margin);
+            Self::left_alignment_offset(&self.current_line_span, self.is_first_line)
+                + left_image_offset;
+        let right_adjustment =
+            Twips::from_pixels(self.current_line_span.right_margin) + right_image_offset;
 
         let misalignment =
             self.max_bounds - left_adjustment - right_adjustment - line_bounds.width();
@@ -435,6 +465,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         self.is_first_line = true;
         self.has_line_break = true;
         self.max_font_size = Twips::from_pixels(self.current_line_span.font.size);
+        self.reach_position(end);
     }
 
     /// Adjust the text layout cursor down to the next line.
@@ -464,6 +495,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         self.is_first_line = false;
         self.has_line_break = true;
         self.max_font_size = Twips::from_pixels(self.current_line_span.font.size);
+        self.reach_position(end);
     }
 
     /// Adjust the text layout cursor in response to a tab.
@@ -665,7 +697,8 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
             bullet_cursor.set_x(
                 Twips::from_pixels(18.0)
-                    + Self::left_alignment_offset_without_bullet(span, self.is_first_line),
+                    + Self::left_alignment_offset_without_bullet(span, self.is_first_line)
+                    + self.image_offsets().0,
             );
 
             let params = EvalParameters::from_span(span);
@@ -735,12 +768,106 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// Offsets returned by this function should not be considered final;
     fn wrap_dimensions(&self, current_span: &TextSpan) -> (Twips, Twips) {
-        let width = self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin);
-        let offset = Self::left_alignment_offset(current_span, self.is_first_line);
+        let (left_image_offset, right_image_offset) = self.image_offsets();
+        let width = self.max_bounds
+            - Twips::from_pixels(self.current_line_span.right_margin)
+            - right_image_offset;
+        let offset =
+            Self::left_alignment_offset(current_span, self.is_first_line) + left_image_offset;
 
         (width, offset + self.cursor.x())
     }
 
+    /// Calculate how much space the images floating to the left and right of
+    /// the current line take from it.
+    fn image_offsets(&self) -> (Twips, Twips) {
+        let y = self.cursor.y();
+        self.images
+            .iter()
+            .filter(|image| {
+                image.exterior_bounds.offset_y() <= y && y < image.exterior_bounds.extent_y()
+            })
+            .fold((Twips::ZERO, Twips::ZERO), |(left, right), image| {
+                if image.align == swf::TextAlign::Right {
+                    (
+                        left,
+                        max(right, self.max_bounds - image.exterior_bounds.offset_x()),
+                    )
+                } else {
+                    (max(left, image.exterior_bounds.extent_x()), right)
+                }
+            })
+    }
+
+    /// Note that the layout has reached the given position in the text.
+    ///
+    /// Images anchored up to this position are placed right away when we are
+    /// at the start of a line, or otherwise at the start of the next line.
+    fn reach_position(&mut self, position: usize) {
+        while let Some(image) = self.text_images.get(self.next_image) {
+            if image.position > position {
+                break;
+            }
+            self.pending_images.push(self.next_image);
+            self.next_image += 1;
+        }
+
+        if self.is_start_of_line() {
+            self.place_pending_images();
+        }
+    }
+
+    /// Place the images that are waiting to be placed at the top of the current line.
+    ///
+    /// Images float to the left or right side of the text field, next to any
+    /// images that already float there.
+    fn place_pending_images(&mut self) {
+        for index in mem::take(&mut self.pending_images) {
+            let image = &self.text_images[index];
+            let size = self.image_sizes.get(index).copied().unwrap_or_default();
+            if size.width() <= Twips::ZERO || size.height() <= Twips::ZERO {
+                // The image isn't loaded yet, and no size was given for it.
+                continue;
+            }
+
+            let hspace = Twips::from_pixels(image.hspace);
+            let vspace = Twips::from_pixels(image.vspace);
+            let exterior_size = Size::from((size.width() + hspace * 2, size.height() + vspace * 2));
+            let (left_image_offset, right_image_offset) = self.image_offsets();
+            let x = if image.align == swf::TextAlign::Right {
+                self.max_bounds - right_image_offset - exterior_size.width()
+            } else {
+                left_image_offset
+            };
+            let exterior_bounds = BoxBounds::from_position_and_size(
+                Position::from((x, self.cursor.y())),
+                exterior_size,
+            );
+            let bounds = BoxBounds::from_position_and_size(
+                Position::from((x + hspace, self.cursor.y() + vspace)),
+                size,
+            );
+
+            if let Some(lb) = &mut self.bounds {
+                *lb += bounds;
+            } else {
+                self.bounds = Some(bounds);
+            }
+            if let Some(eb) = &mut self.exterior_bounds {
+                *eb += exterior_bounds;
+            } else {
+                self.exterior_bounds = Some(exterior_bounds);
+            }
+
+            self.images.push(LayoutImage {
+                index,
+                bounds,
+                exterior_bounds,
+                align: image.align,
+            });
+        }
+    }
+
     /// Destroy the layout context, returning the newly constructed layout list.
     fn end_layout(
         mut self,
@@ -758,10 +885,22 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             font_type,
         );
 
+        // Images anchored at the end of the text go below its last line.
+        self.reach_position(usize::MAX);
+        if !self.pending_images.is_empty() {
+            self.cursor += (
+                Twips::ZERO,
+                self.max_font_size + self.line_leading_adjustment(),
+            )
+                .into();
+            self.place_pending_images();
+        }
+
         Layout {
             bounds: self.bounds.unwrap_or_default(),
             exterior_bounds: self.exterior_bounds.unwrap_or_default(),
             lines: self.lines,
+            images: self.images,
         }
     }
 
@@ -782,6 +921,9 @@ pub struct Layout<'gc> {
     exterior_bounds: BoxBounds<Twips>,
 
     lines: Vec<LayoutLine<'gc>>,
+
+    #[collect(require_static)]
+    images: Vec<LayoutImage>,
 }
 
 impl<'gc> Layout<'gc> {
@@ -802,6 +944,11 @@ impl<'gc> Layout<'gc> {
         &self.lines
     }
 
+    /// The images embedded in the text that could be placed.
+    pub fn images(&self) -> &[LayoutImage] {
+        &self.images
+    }
+
     pub fn boxes_iter(&self) -> LayoutBoxIter<'_, 'gc> {
         LayoutBoxIter {
             lines_iter: self.lines.iter(),
@@ -830,6 +977,34 @@ impl<'gc> Layout<'gc> {
     }
 }
 
+/// An image embedded in the text with an `<img>` tag, floating to one side
+/// of the text field.
+#[derive(Clone, Debug)]
+pub struct LayoutImage {
+    /// The index of the image in `FormatSpans::images`.
+    index: usize,
+
+    /// The bounds of the image itself.
+    bounds: BoxBounds<Twips>,
+
+    /// The bounds of the image including the space around it,
+    /// which text flows around.
+    exterior_bounds: BoxBounds<Twips>,
+
+    /// The side of the text field that the image floats to.
+    align: swf::TextAlign,
+}
+
+impl LayoutImage {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn bounds(&self) -> BoxBounds<Twips> {
+        self.bounds
+    }
+}
+
 /// A `LayoutLine` represents a single line of text.
 /// It consists of [`LayoutBox`]es.
 #[derive(Clone, Debug, Collect)]
@@ -1134,6 +1309,10 @@ impl<'gc> LayoutBox<'gc> {
 }
 
 /// Construct a new layout from text spans.
+/// Lay out the given text spans.
+///
+/// `image_sizes` contains the sizes of the images in `fs.images()`, in the same order.
+/// Images without a size are not placed.
 pub fn lower_from_text_spans<'gc>(
     fs: &FormatSpans,
     context: &mut UpdateContext<'gc>,
@@ -1141,8 +1320,10 @@ pub fn lower_from_text_spans<'gc>(
     bounds: Twips,
     is_word_wrap: bool,
     font_type: FontType,
+    image_sizes: &[Size<Twips>],
 ) -> Layout<'gc> {
-    let mut layout_context = LayoutContext::new(movie, bounds, fs.displayed_text());
+    let mut layout_context =
+        LayoutContext::new(movie, bounds, fs.displayed_text(), fs.images(), image_sizes);
 
     for (span_start, _end, span_text, span) in fs.iter_spans() {
         if let Some(font) = layout_context.resolve_font(context, span, font_type) {
@@ -1173,6 +1354,7 @@ pub fn lower_from_text_spans<'gc>(
                 }
 
                 let start = span_start + slice_start;
+                layout_context.reach_position(start);
 
                 let mut last_breakpoint = 0;
 
@@ -1,7 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, TextFormat, TextImage, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
 
@@ -868,3 +868,51 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn formatspans_from_html_img() {
+    let fs = FormatSpans::from_html(
+        WStr::from_units(b"ab<img src='pic.png' id=pic width=\"20\" align='right' hspace='2'>cd"),
+        TextFormat::default(),
+        true,
+        false,
+        10,
+    );
+
+    assert_eq!(WStr::from_units(b"abcd"), fs.text());
+    assert_eq!(
+        fs.images(),
+        &[TextImage {
+            position: 2,
+            src: WString::from_utf8("pic.png"),
+            id: Some(WString::from_utf8("pic")),
+            width: Some(20.0),
+            height: None,
+            align: swf::TextAlign::Right,
+            hspace: 2.0,
+            vspace: 8.0,
+        }]
+    );
+}
+
+#[test]
+fn formatspans_replace_text_moves_images() {
+    let mut fs = FormatSpans::from_html(
+        WStr::from_units(b"abc<img src='a'>def<img src='b'>ghi"),
+        TextFormat::default(),
+        true,
+        false,
+        10,
+    );
+
+    fs.replace_text(1, 5, WStr::from_units(b"12"), None);
+
+    assert_eq!(WStr::from_units(b"a12fghi"), fs.text());
+    assert_eq!(
+        fs.images()
+            .iter()
+            .map(|image| image.position)
+            .collect::<Vec<_>>(),
+        vec![1, 4]
+    );
+}
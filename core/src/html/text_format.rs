@@ -571,6 +571,44 @@ impl TextSpan {
     }
 }
 
+/// An image embedded in HTML text with an `<img>` tag.
+///
+/// Images are not part of the text itself. They are anchored to the position
+/// of their tag in the text, and float to one side of the text field with the
+/// text flowing around them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextImage {
+    /// The position in the text that the image is anchored to.
+    pub position: usize,
+
+    /// The URL of the image, or the linkage name of a library symbol.
+    pub src: WString,
+
+    /// The name that the loaded image can be retrieved by from ActionScript.
+    pub id: Option<WString>,
+
+    /// The width of the image in pixels, or `None` to use its own width.
+    pub width: Option<f64>,
+
+    /// The height of the image in pixels, or `None` to use its own height.
+    pub height: Option<f64>,
+
+    /// The side of the text field that the image floats to.
+    /// Only `Left` and `Right` are supported.
+    pub align: swf::TextAlign,
+
+    /// The space between the image and the text to its sides, in pixels.
+    pub hspace: f64,
+
+    /// The space between the image and the text above and below it, in pixels.
+    pub vspace: f64,
+}
+
+impl TextImage {
+    /// The default space around images, in pixels.
+    const DEFAULT_SPACE: f64 = 8.0;
+}
+
 /// Struct which contains text formatted by `TextSpan`s.
 #[derive(Clone, Debug)]
 pub struct FormatSpans {
@@ -578,6 +616,7 @@ pub struct FormatSpans {
     displayed_text: WString,
     spans: Vec<TextSpan>,
     default_format: TextFormat,
+    images: Vec<TextImage>,
 }
 
 impl Default for FormatSpans {
@@ -593,6 +632,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::default()],
             default_format: TextFormat::default(),
+            images: Vec::new(),
         }
     }
 
@@ -604,6 +644,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: spans.to_vec(),
             default_format: Default::default(),
+            images: Vec::new(),
         }
     }
 
@@ -614,6 +655,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::with_length_and_format(len, &format)],
             default_format: format,
+            images: Vec::new(),
         }
     }
 
@@ -637,6 +679,7 @@ impl FormatSpans {
         let mut format_stack = vec![default_format.clone()];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
+        let mut images: Vec<TextImage> = Vec::new();
 
         // quick_xml::Reader requires a [u8] slice, but doesn't actually care about Unicode;
         // this means we can pass the raw buffer in the Latin1 case.
@@ -682,7 +725,9 @@ impl FormatSpans {
             match reader.read_event() {
                 Ok(Event::Start(ref e)) => {
                     let tag_name = &e.name().into_inner().to_ascii_lowercase()[..];
-                    let attributes: Result<Vec<_>, _> = e.attributes().with_checks(false).collect();
+                    // Flash accepts unquoted attribute values, as in `<font color=#ff0000>`.
+                    let attributes: Result<Vec<_>, _> =
+                        e.html_attributes().with_checks(false).collect();
                     let attributes = match attributes {
                         Ok(attributes) => attributes,
                         Err(e) => {
@@ -719,6 +764,36 @@ impl FormatSpans {
                             // Skip push to `format_stack`.
                             continue;
                         }
+                        b"img" => {
+                            if let Some(src) = attribute(b"src") {
+                                let number = |name: &'static [u8]| {
+                                    attribute(name)
+                                        .and_then(|v| v.trim().parse::<f64>().ok())
+                                        .filter(|v| v.is_finite() && *v >= 0.0)
+                                };
+                                let align = match attribute(b"align") {
+                                    Some(align)
+                                        if align.eq_ignore_case(WStr::from_units(b"right")) =>
+                                    {
+                                        swf::TextAlign::Right
+                                    }
+                                    _ => swf::TextAlign::Left,
+                                };
+                                images.push(TextImage {
+                                    position: text.len(),
+                                    src,
+                                    id: attribute(b"id"),
+                                    width: number(b"width"),
+                                    height: number(b"height"),
+                                    align,
+                                    hspace: number(b"hspace").unwrap_or(TextImage::DEFAULT_SPACE),
+                                    vspace: number(b"vspace").unwrap_or(TextImage::DEFAULT_SPACE),
+                                });
+                            }
+
+                            // Skip push to `format_stack`.
+                            continue;
+                        }
                         b"p" => {
                             p_open = true;
                             if let Some(align) = attribute(b"align") {
@@ -909,7 +984,7 @@ impl FormatSpans {
                     }
 
                     match tag_name {
-                        b"br" | b"sbr" => {
+                        b"br" | b"sbr" | b"img" => {
                             // Skip pop from `format_stack`.
                             continue;
                         }
@@ -969,6 +1044,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans,
             default_format,
+            images,
         };
         if condense_white && swf_version >= 8 {
             ret.condense_white_swf8();
@@ -998,6 +1074,11 @@ impl FormatSpans {
         &self.default_format
     }
 
+    /// The images embedded with `<img>` tags, in the order they appear in the text.
+    pub fn images(&self) -> &[TextImage] {
+        &self.images
+    }
+
     pub fn set_default_format(&mut self, tf: TextFormat) {
         self.default_format = tf.mix_with(self.default_format.clone());
     }
@@ -1311,6 +1392,7 @@ impl FormatSpans {
             return;
         }
 
+        let old_len = self.text.len();
         if from < self.text.len() {
             self.ensure_span_break_at(from);
             self.ensure_span_break_at(to);
@@ -1350,6 +1432,19 @@ impl FormatSpans {
 
         self.text = new_string;
 
+        // Keep images anchored to the text around them.
+        let removed = to.min(old_len).saturating_sub(from);
+        for image in &mut self.images {
+            if image.position <= from {
+                continue;
+            }
+            if image.position >= from + removed {
+                image.position = image.position - removed + with.len();
+            } else {
+                image.position = from;
+            }
+        }
+
         self.normalize();
     }
 
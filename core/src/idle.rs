@@ -0,0 +1,129 @@
+//! Detecting movies that are idle, so that embedders can stop ticking them until input arrives.
+//!
+//! A movie is idle when running another frame can't change anything: no timeline is playing or
+//! loading, no script runs on every frame, and nothing is pending on timers, sounds, streams,
+//! or the network. Many movies (like static "card" SWFs) end up like this once loaded.
+//!
+//! Everything that can wake a movie up again, such as input or a call from the embedder,
+//! goes through the player, which then clears its idle state.
+
+use crate::avm2::Avm2;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+
+/// Whether running more frames of the movie would not change it, until something outside of it
+/// (like input) happens.
+pub(crate) fn movie_is_idle<'gc>(context: &mut UpdateContext<'gc>) -> bool {
+    if !context.timers.is_empty()
+        || !context.audio_manager.is_empty()
+        || !context.stream_manager.is_empty()
+        || !context.load_manager.is_empty()
+        || !context.action_queue.is_empty()
+        || !context.sockets.is_empty()
+        || !context.net_connections.is_empty()
+        || context.local_connections.has_pending_messages()
+        || !context.post_frame_callbacks.is_empty()
+        || Avm2::has_broadcast_listeners(context)
+    {
+        return false;
+    }
+
+    // The caret of a focused text field blinks.
+    if context
+        .focus_tracker
+        .get_as_edit_text()
+        .is_some_and(|text| text.is_editable())
+    {
+        return false;
+    }
+
+    let stage: DisplayObject<'gc> = context.stage.into();
+    is_idle_object(context, stage)
+}
+
+fn is_idle_object<'gc>(context: &mut UpdateContext<'gc>, object: DisplayObject<'gc>) -> bool {
+    if let Some(clip) = object.as_movie_clip() {
+        let is_animating = clip.playing() && clip.total_frames() > 1;
+        let is_loading = clip.frames_loaded() < clip.total_frames() as i32;
+        if is_animating || is_loading || clip.has_enter_frame_handler(context) {
+            return false;
+        }
+    }
+
+    match object.as_container() {
+        Some(container) => container
+            .iter_render_list()
+            .all(|child| is_idle_object(context, child)),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::movie_is_idle;
+    use crate::avm1::test_utils::with_avm;
+    use crate::avm1::{Activation, Error, ExecutionReason, TObject};
+    use crate::display_object::TDisplayObject;
+    use crate::limits::ExecutionLimit;
+    use crate::player::Player;
+
+    /// Loads the root clip and runs its queued actions, as its first frame would.
+    fn load_root(activation: &mut Activation<'_, '_>) {
+        let root = activation
+            .context
+            .stage
+            .root_clip()
+            .and_then(|root| root.as_movie_clip())
+            .expect("Root should exist for freshly made movie");
+        root.preload(activation.context, &mut ExecutionLimit::none());
+        Player::run_actions(activation.context);
+    }
+
+    #[test]
+    fn static_movie_is_idle() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            load_root(activation);
+            assert!(movie_is_idle(activation.context));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn enter_frame_handler_keeps_movie_awake() {
+        with_avm(10, |activation, root| -> Result<(), Error> {
+            load_root(activation);
+            let handler = root.get("toString", activation)?;
+            root.set("onEnterFrame", handler, activation)?;
+            assert!(!movie_is_idle(activation.context));
+
+            root.delete(activation, "onEnterFrame".into());
+            assert!(movie_is_idle(activation.context));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn interval_keeps_movie_awake() {
+        with_avm(10, |activation, root| -> Result<(), Error> {
+            load_root(activation);
+            let global = activation.context.avm1.global_object();
+            let callback = root.get("toString", activation)?;
+            let id = global.call_method(
+                "setInterval".into(),
+                &[callback, 100.into()],
+                activation,
+                ExecutionReason::Special,
+            )?;
+            assert!(!movie_is_idle(activation.context));
+
+            global.call_method(
+                "clearInterval".into(),
+                &[id],
+                activation,
+                ExecutionReason::Special,
+            )?;
+            assert!(movie_is_idle(activation.context));
+            Ok(())
+        });
+    }
+}
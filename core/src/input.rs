@@ -1,4 +1,6 @@
-use crate::events::{GamepadButton, KeyCode, MouseButton, PlayerEvent, TextControlCode};
+use crate::events::{
+    GamepadButton, KeyCode, KeyLocation, MouseButton, MouseWheelDelta, PlayerEvent, TextControlCode,
+};
 use chrono::{DateTime, TimeDelta, Utc};
 use std::collections::{HashMap, HashSet};
 
@@ -25,8 +27,33 @@ pub struct InputManager {
     last_text_control: Option<TextControlCode>,
     last_click: Option<ClickEventData>,
 
+    /// Fractional wheel lines that have not been delivered yet.
+    /// Flash only reports whole lines, so small (e.g. touchpad) deltas are accumulated.
+    wheel_remainder: f64,
+
     /// A map from gamepad buttons to key codes.
     gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+
+    /// The game controllers that movies can access through `flash.ui.GameInput`.
+    game_input_devices: Vec<GameInputDevice>,
+}
+
+/// A game controller, as exposed to movies through `flash.ui.GameInputDevice`.
+#[derive(Debug, Clone)]
+pub struct GameInputDevice {
+    /// Identifies the device for as long as it stays connected.
+    pub id: String,
+    pub name: String,
+    pub controls: Vec<GameInputControl>,
+}
+
+/// A button or axis of a game controller, as exposed through `flash.ui.GameInputControl`.
+#[derive(Debug, Clone)]
+pub struct GameInputControl {
+    pub id: String,
+    pub min_value: f64,
+    pub max_value: f64,
+    pub value: f64,
 }
 
 impl InputManager {
@@ -38,7 +65,9 @@ impl InputManager {
             last_char: None,
             last_text_control: None,
             last_click: None,
+            wheel_remainder: 0.0,
             gamepad_button_mapping,
+            game_input_devices: Vec::new(),
         }
     }
 
@@ -75,6 +104,7 @@ impl InputManager {
                     Some(PlayerEvent::KeyDown {
                         key_code: *key_code,
                         key_char: None,
+                        key_location: KeyLocation::Standard,
                     })
                 } else {
                     // Just ignore this event.
@@ -86,24 +116,53 @@ impl InputManager {
                     Some(PlayerEvent::KeyUp {
                         key_code: *key_code,
                         key_char: None,
+                        key_location: KeyLocation::Standard,
                     })
                 } else {
                     // Just ignore this event.
                     None
                 }
             }
+            PlayerEvent::MouseWheel { delta } => {
+                self.accumulate_wheel_lines(delta)
+                    .map(|lines| PlayerEvent::MouseWheel {
+                        delta: MouseWheelDelta::Lines(lines),
+                    })
+            }
             _ => Some(event),
         }
     }
 
+    /// Adds the given delta to the pending wheel lines, returning the whole lines to deliver.
+    fn accumulate_wheel_lines(&mut self, delta: MouseWheelDelta) -> Option<f64> {
+        let lines = delta.lines();
+        if !lines.is_finite() {
+            return None;
+        }
+
+        // Changing direction discards whatever was left over from the other direction.
+        if lines.signum() != self.wheel_remainder.signum() {
+            self.wheel_remainder = 0.0;
+        }
+
+        let total = self.wheel_remainder + lines;
+        let whole = total.trunc();
+        self.wheel_remainder = total - whole;
+        (whole != 0.0).then_some(whole)
+    }
+
     pub fn handle_event(&mut self, event: &PlayerEvent) {
         match *event {
-            PlayerEvent::KeyDown { key_code, key_char } => {
+            PlayerEvent::KeyDown {
+                key_code, key_char, ..
+            } => {
                 self.last_char = key_char;
                 self.toggle_key(key_code);
                 self.add_key(key_code);
             }
-            PlayerEvent::KeyUp { key_code, key_char } => {
+            PlayerEvent::KeyUp {
+                key_code, key_char, ..
+            } => {
                 self.last_char = key_char;
                 self.remove_key(key_code);
                 self.last_text_control = None;
@@ -189,4 +248,92 @@ impl InputManager {
         }
         buttons
     }
+
+    pub fn game_input_devices(&self) -> &[GameInputDevice] {
+        &self.game_input_devices
+    }
+
+    pub fn game_input_device(&self, id: &str) -> Option<&GameInputDevice> {
+        self.game_input_devices
+            .iter()
+            .find(|device| device.id == id)
+    }
+
+    pub fn add_game_input_device(&mut self, device: GameInputDevice) {
+        self.game_input_devices.retain(|d| d.id != device.id);
+        self.game_input_devices.push(device);
+    }
+
+    pub fn remove_game_input_device(&mut self, id: &str) {
+        self.game_input_devices.retain(|device| device.id != id);
+    }
+
+    /// Updates the value of a control, returning true if it changed.
+    pub fn set_game_input_value(&mut self, id: &str, control: usize, value: f64) -> bool {
+        let Some(control) = self
+            .game_input_devices
+            .iter_mut()
+            .find(|device| device.id == id)
+            .and_then(|device| device.controls.get_mut(control))
+        else {
+            return false;
+        };
+        let value = value.clamp(control.min_value, control.max_value);
+        let changed = control.value != value;
+        control.value = value;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wheel(input: &mut InputManager, delta: MouseWheelDelta) -> Option<f64> {
+        input.accumulate_wheel_lines(delta)
+    }
+
+    #[test]
+    fn whole_wheel_lines_are_delivered_directly() {
+        let mut input = InputManager::new(HashMap::new());
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(3.0)), Some(3.0));
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(-2.0)), Some(-2.0));
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Pixels(100.0)), Some(1.0));
+    }
+
+    #[test]
+    fn fractional_wheel_lines_accumulate() {
+        let mut input = InputManager::new(HashMap::new());
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(0.25)), None);
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Pixels(50.0)), None);
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(0.5)), Some(1.0));
+        // The fraction left over from the last delta is kept.
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(1.75)), Some(2.0));
+
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(-0.5)), None);
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(-0.75)), Some(-1.0));
+    }
+
+    #[test]
+    fn changing_wheel_direction_discards_remainder() {
+        let mut input = InputManager::new(HashMap::new());
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(0.75)), None);
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(-0.5)), None);
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(-0.5)), Some(-1.0));
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(0.5)), None);
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(0.25)), None);
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(0.25)), Some(1.0));
+    }
+
+    #[test]
+    fn non_finite_wheel_deltas_are_ignored() {
+        let mut input = InputManager::new(HashMap::new());
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(0.5)), None);
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(f64::NAN)), None);
+        assert_eq!(
+            wheel(&mut input, MouseWheelDelta::Lines(f64::INFINITY)),
+            None
+        );
+        assert_eq!(wheel(&mut input, MouseWheelDelta::Lines(0.5)), Some(1.0));
+    }
 }
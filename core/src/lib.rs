@@ -20,6 +20,7 @@ extern crate num_derive;
 
 #[macro_use]
 mod avm1;
+mod asset_cache;
 mod avm2;
 mod binary_data;
 pub mod bitmap;
@@ -32,8 +33,12 @@ mod ecma_conversions;
 pub mod events;
 pub mod focus_tracker;
 mod font;
+mod form_encoding;
+pub mod frame_hooks;
 mod frame_lifecycle;
+pub mod heap_dump;
 mod html;
+mod idle;
 mod input;
 mod library;
 pub mod limits;
@@ -41,14 +46,20 @@ pub mod loader;
 mod local_connection;
 mod locale;
 mod net_connection;
+pub mod opcode_counts;
 pub mod pixel_bender;
 mod player;
+pub mod policy_file;
+pub mod prefetch;
 mod prelude;
 pub mod sandbox;
+mod shared_object;
+pub mod sitelock;
 pub mod socket;
 mod streams;
 pub mod string;
 pub mod tag_utils;
+pub mod time;
 pub mod timer;
 mod types;
 mod vminterface;
@@ -67,6 +78,7 @@ pub use context_menu::ContextMenuItem;
 pub use events::PlayerEvent;
 pub use font::DefaultFont;
 pub use indexmap;
+pub use input::{GameInputControl, GameInputDevice};
 pub use loader::LoadBehavior;
 pub use player::{Player, PlayerBuilder, PlayerRuntime, StaticCallstack};
 pub use ruffle_render::backend::ViewportDimensions;
@@ -1,3 +1,4 @@
+use crate::asset_cache;
 use crate::avm1::{PropertyMap as Avm1PropertyMap, PropertyMap};
 use crate::avm2::{Class as Avm2Class, Domain as Avm2Domain};
 use crate::backend::audio::SoundHandle;
@@ -123,6 +124,10 @@ pub struct MovieLibrary<'gc> {
     characters: HashMap<CharacterId, Character<'gc>>,
     export_characters: Avm1PropertyMap<'gc, CharacterId>,
     imported_assets: HashMap<AvmString<'gc>, CharacterId>,
+
+    /// How many of the movies this movie imports assets from are still loading.
+    pending_imports: usize,
+
     jpeg_tables: Option<Vec<u8>>,
     fonts: FontMap<'gc>,
     avm2_domain: Option<Avm2Domain<'gc>>,
@@ -134,6 +139,7 @@ impl<'gc> MovieLibrary<'gc> {
             swf,
             characters: HashMap::new(),
             imported_assets: HashMap::new(),
+            pending_imports: 0,
             export_characters: Avm1PropertyMap::new(),
             jpeg_tables: None,
             fonts: Default::default(),
@@ -196,6 +202,24 @@ impl<'gc> MovieLibrary<'gc> {
         self.imported_assets.insert(name, id);
     }
 
+    /// Notes that this movie started loading a movie to import assets from.
+    pub fn begin_import(&mut self) {
+        self.pending_imports += 1;
+    }
+
+    /// Notes that a movie this movie imports assets from was loaded, or failed to load.
+    pub fn finish_import(&mut self) {
+        self.pending_imports = self.pending_imports.saturating_sub(1);
+    }
+
+    /// Whether this movie is still waiting for the movies it imports assets from.
+    ///
+    /// Like in Flash Player, the frames after an `ImportAssets` tag aren't loaded until then,
+    /// so that the movie never tries to place an imported character that isn't there yet.
+    pub fn has_pending_imports(&self) -> bool {
+        self.pending_imports > 0
+    }
+
     /// Instantiates the library item with the given character ID into a display object.
     /// The object must then be post-instantiated before being used.
     pub fn instantiate_by_id(
@@ -243,8 +267,8 @@ impl<'gc> MovieLibrary<'gc> {
                 avm2_bitmapdata_class,
                 handle: _,
             } => {
-                let bitmap = compressed.decode().unwrap();
-                let bitmap = Bitmap::new(mc, id, bitmap, self.swf.clone())
+                let bitmap = asset_cache::decode_bitmap(&self.swf, id, compressed).unwrap();
+                let bitmap = Bitmap::new(mc, id, Arc::unwrap_or_clone(bitmap), self.swf.clone())
                     .map_err(|e| Cow::Owned(format!("Failed to instantiate bitmap: {:?}", e)))?;
                 bitmap.set_avm2_bitmapdata_class(mc, *avm2_bitmapdata_class.read());
                 Ok(bitmap.instantiate(mc))
@@ -376,14 +400,14 @@ impl<'a, 'gc> ruffle_render::bitmap::BitmapSource for MovieLibrarySource<'a, 'gc
         if let Some(handle) = &*handle {
             return Some(handle.clone());
         }
-        let decoded = match compressed.decode() {
+        let decoded = match asset_cache::decode_bitmap(&self.library.swf, id, compressed) {
             Ok(decoded) => decoded,
             Err(e) => {
                 tracing::error!("Failed to decode bitmap character {id:?}: {e:?}");
                 return None;
             }
         };
-        let new_handle = match backend.register_bitmap(decoded) {
+        let new_handle = match backend.register_bitmap(Arc::unwrap_or_clone(decoded)) {
             Ok(handle) => handle,
             Err(e) => {
                 tracing::error!("Failed to register bitmap character {id:?}: {e:?}");
@@ -422,6 +446,9 @@ pub struct Library<'gc> {
     /// A list of the symbols associated with specific AVM2 constructor
     /// prototypes.
     avm2_class_registry: Avm2ClassRegistry<'gc>,
+
+    /// How many intermediate frames (and their tessellations) each morph shape keeps.
+    morph_shape_cache_size: usize,
 }
 
 unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
@@ -449,9 +476,20 @@ impl<'gc> Library<'gc> {
             default_font_names: Default::default(),
             default_font_cache: Default::default(),
             avm2_class_registry: Default::default(),
+            morph_shape_cache_size: MorphShape::DEFAULT_CACHE_SIZE,
         }
     }
 
+    pub fn morph_shape_cache_size(&self) -> usize {
+        self.morph_shape_cache_size
+    }
+
+    /// Sets how many intermediate frames each morph shape keeps.
+    /// This applies to morph shapes defined after the change.
+    pub fn set_morph_shape_cache_size(&mut self, size: usize) {
+        self.morph_shape_cache_size = size;
+    }
+
     pub fn library_for_movie(&self, movie: Arc<SwfMovie>) -> Option<&MovieLibrary<'gc>> {
         self.movie_libraries.get(&movie)
     }
@@ -593,7 +631,7 @@ impl<'gc> Library<'gc> {
         match definition {
             FontDefinition::SwfTag(tag, encoding) => {
                 let font =
-                    Font::from_swf_tag(gc_context, renderer, tag, encoding, FontType::Device);
+                    Font::from_swf_tag(gc_context, renderer, tag, encoding, FontType::Device, None);
                 let name = font.descriptor().name().to_owned();
                 let is_bold = font.descriptor().bold();
                 let is_italic = font.descriptor().italic();
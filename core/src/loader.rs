@@ -5,14 +5,15 @@ use crate::avm1::{Attribute, Avm1};
 use crate::avm1::{ExecutionReason, NativeObject};
 use crate::avm1::{Object, TObject, Value};
 use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::globals::flash::net::url_stream;
 use crate::avm2::globals::flash::utils::byte_array::strip_bom;
 use crate::avm2::object::{
     ByteArrayObject, EventObject as Avm2EventObject, FileReferenceObject, LoaderStream,
     TObject as _,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, BitmapDataObject, Domain as Avm2Domain,
-    Object as Avm2Object,
+    Activation as Avm2Activation, ArrayObject as Avm2ArrayObject, Avm2, BitmapDataObject,
+    Domain as Avm2Domain, Object as Avm2Object,
 };
 use crate::backend::navigator::{ErrorResponse, OwnedFuture, Request, SuccessResponse};
 use crate::backend::ui::DialogResultFuture;
@@ -26,10 +27,14 @@ use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
 use crate::player::{Player, PostFrameCallback};
+use crate::prefetch;
+use crate::streams::hls::{self, Playlist};
+use crate::streams::mpegts::TsToFlv;
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
+use crate::PlayerRuntime;
 use crate::{avm2_stub_method, avm2_stub_method_context};
 use chardetng::EncodingDetector;
 use encoding_rs::{UTF_8, WINDOWS_1252};
@@ -39,8 +44,11 @@ use ruffle_render::utils::{determine_jpeg_tag_format, JpegTagFormat};
 use slotmap::{new_key_type, SlotMap};
 use std::borrow::Borrow;
 use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll};
 use std::time::Duration;
 use swf::read::{extract_swz, read_compression_type};
 use thiserror::Error;
@@ -237,6 +245,44 @@ impl From<crate::avm1::Error<'_>> for Error {
     }
 }
 
+/// A future that resolves once the player's timers have been updated for a duration.
+///
+/// Loaders use this to wait without blocking, and pause along with the player.
+struct PlayerDelay {
+    player: Weak<Mutex<Player>>,
+
+    /// The `Player::load_clock` time to wait for.
+    time: f64,
+}
+
+impl PlayerDelay {
+    fn new(player: &Arc<Mutex<Player>>, duration: Duration) -> Self {
+        let time = player.lock().unwrap().load_clock() + duration.as_secs_f64() * 1000.0;
+        Self {
+            player: Arc::downgrade(player),
+            time,
+        }
+    }
+}
+
+impl Future for PlayerDelay {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Some(player) = self.player.upgrade() else {
+            return Poll::Ready(Err(Error::Cancelled));
+        };
+
+        let mut player = player.lock().unwrap();
+        if player.load_clock() >= self.time {
+            Poll::Ready(Ok(()))
+        } else {
+            player.wake_loader_at(self.time, cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
 /// Holds all in-progress loads for the player.
 pub struct LoadManager<'gc>(SlotMap<LoaderHandle, Loader<'gc>>);
 
@@ -254,6 +300,11 @@ impl<'gc> LoadManager<'gc> {
         Self(SlotMap::with_key())
     }
 
+    /// Whether no loads are in progress.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     /// Add a new loader to the `LoadManager`.
     ///
     /// Returns the loader handle for later inspection. A loader handle is
@@ -271,6 +322,7 @@ impl<'gc> LoadManager<'gc> {
             | Loader::Form { self_handle, .. }
             | Loader::LoadVars { self_handle, .. }
             | Loader::LoadURLLoader { self_handle, .. }
+            | Loader::LoadURLStream { self_handle, .. }
             | Loader::SoundAvm1 { self_handle, .. }
             | Loader::SoundAvm2 { self_handle, .. }
             | Loader::NetStream { self_handle, .. }
@@ -330,6 +382,29 @@ impl<'gc> LoadManager<'gc> {
         request: Request,
         loader_url: Option<String>,
         vm_data: MovieLoaderVMData<'gc>,
+    ) -> OwnedFuture<(), Error> {
+        self.load_movie_into_security_domain(
+            player,
+            target_clip,
+            request,
+            loader_url,
+            vm_data,
+            None,
+        )
+    }
+
+    /// Kick off a movie clip load, where the loaded movie joins the security
+    /// domain of `security_domain_importer`, if any.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_movie_into_security_domain(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_clip: DisplayObject<'gc>,
+        request: Request,
+        loader_url: Option<String>,
+        vm_data: MovieLoaderVMData<'gc>,
+        security_domain_importer: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Movie {
             self_handle: None,
@@ -338,6 +413,7 @@ impl<'gc> LoadManager<'gc> {
             loader_status: LoaderStatus::Pending,
             from_bytes: false,
             movie: None,
+            security_domain_importer,
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
@@ -355,51 +431,26 @@ impl<'gc> LoadManager<'gc> {
 
         Box::pin(async move {
             let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = Loader::wait_for_full_response(fetch).await;
 
-            match Loader::wait_for_full_response(fetch).await {
-                Ok((body, url, _status, _redirected)) => {
-                    let content_type = ContentType::sniff(&body);
-                    tracing::info!("Loading imported movie: {:?}", url);
-                    match content_type {
-                        ContentType::Swf => {
-                            let movie = SwfMovie::from_data(&body, url.clone(), Some(url.clone()))
-                                .expect("Could not load movie");
-
-                            let movie = Arc::new(movie);
-
-                            player.lock().unwrap().mutate_with_update_context(|uc| {
-                                let clip = MovieClip::new_import_assets(uc, movie, importer_movie);
-
-                                clip.set_cur_preload_frame(uc.gc_context, 0);
-                                let mut execution_limit = ExecutionLimit::none();
-
-                                tracing::debug!("Preloading swf to run exports {:?}", url);
-
-                                // Create library for exports before preloading
-                                uc.library.library_for_movie_mut(clip.movie());
-                                let res = clip.preload(uc, &mut execution_limit);
-                                tracing::debug!(
-                                    "Preloaded swf to run exports result {:?} {}",
-                                    url,
-                                    res
-                                );
-                            });
-                            Ok(())
-                        }
-                        _ => {
-                            tracing::warn!(
-                                "Unsupported content type for ImportAssets: {:?}",
-                                content_type
-                            );
-                            Ok(())
-                        }
+            player.lock().unwrap().mutate_with_update_context(|uc| {
+                let result = match response {
+                    Ok((body, url, _status, _redirected)) => {
+                        bind_asset_movie(uc, &body, url, importer_movie.clone())
                     }
-                }
-                Err(e) => Err(Error::FetchError(format!(
-                    "Could not fetch: {:?} because {:?}",
-                    e.url, e.error
-                ))),
-            }
+                    Err(e) => Err(Error::FetchError(format!(
+                        "Could not fetch: {:?} because {:?}",
+                        e.url, e.error
+                    ))),
+                };
+
+                // The importer carries on loading whether the assets could be imported or not,
+                // which leaves the missing ones undefined, like in Flash Player.
+                uc.library
+                    .library_for_movie_mut(importer_movie)
+                    .finish_import();
+                result
+            })
         })
     }
 
@@ -419,6 +470,7 @@ impl<'gc> LoadManager<'gc> {
             loader_status: LoaderStatus::Pending,
             movie: None,
             from_bytes: true,
+            security_domain_importer: None,
         };
         let handle = context.load_manager.add_loader(loader);
         Loader::movie_loader_bytes(handle, context, bytes)
@@ -524,6 +576,27 @@ impl<'gc> LoadManager<'gc> {
         loader.load_url_loader(player, request)
     }
 
+    /// Kick off a streaming data load into a `URLStream`, appending
+    /// data to `buffer` as it arrives.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_data_into_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        buffer: Avm2Object<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::LoadURLStream {
+            self_handle: None,
+            target_object,
+            buffer,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.load_url_stream(player, request)
+    }
+
     /// Kick off an AVM1 audio load.
     ///
     /// Returns the loader's async process, which you will need to spawn.
@@ -573,7 +646,11 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.stream_loader(player, request)
+        if hls::is_hls_url(request.url()) {
+            loader.hls_stream_loader(player, request)
+        } else {
+            loader.stream_loader(player, request)
+        }
     }
 
     /// Process tags on all loaders in the Parsing phase.
@@ -719,6 +796,39 @@ impl<'gc> LoadManager<'gc> {
     }
 }
 
+/// Preloads a movie loaded by `ImportAssets`, which binds the characters it exports
+/// to the IDs the importer gave them.
+fn bind_asset_movie(
+    uc: &mut UpdateContext<'_>,
+    body: &[u8],
+    url: String,
+    importer_movie: Arc<SwfMovie>,
+) -> Result<(), Error> {
+    let content_type = ContentType::sniff(body);
+    tracing::info!("Loading imported movie: {:?}", url);
+    if content_type != ContentType::Swf {
+        tracing::warn!(
+            "Unsupported content type for ImportAssets: {:?}",
+            content_type
+        );
+        return Ok(());
+    }
+
+    let movie = Arc::new(SwfMovie::from_data(body, url.clone(), Some(url.clone()))?);
+    let clip = MovieClip::new_import_assets(uc, movie, importer_movie);
+
+    clip.set_cur_preload_frame(uc.gc_context, 0);
+    let mut execution_limit = ExecutionLimit::none();
+
+    tracing::debug!("Preloading swf to run exports {:?}", url);
+
+    // Create library for exports before preloading
+    uc.library.library_for_movie_mut(clip.movie());
+    let res = clip.preload(uc, &mut execution_limit);
+    tracing::debug!("Preloaded swf to run exports result {:?} {}", url, res);
+    Ok(())
+}
+
 impl<'gc> Default for LoadManager<'gc> {
     fn default() -> Self {
         Self::new()
@@ -798,6 +908,10 @@ pub enum Loader<'gc> {
 
         /// Whether or not this was loaded as a result of a `Loader.loadBytes` call
         from_bytes: bool,
+
+        /// The movie whose security domain the loaded movie joins, as requested
+        /// through `LoaderContext.securityDomain`.
+        security_domain_importer: Option<Arc<SwfMovie>>,
     },
 
     /// Loader that is loading form data into an AVM1 object scope.
@@ -831,6 +945,20 @@ pub enum Loader<'gc> {
         target_object: Avm2Object<'gc>,
     },
 
+    /// Loader that is streaming data into a `URLStream`.
+    /// Data becomes readable as soon as each chunk arrives.
+    LoadURLStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<LoaderHandle>,
+
+        /// The target `URLStream`.
+        target_object: Avm2Object<'gc>,
+
+        /// The `ByteArray` the stream was reading from when the load began.
+        buffer: Avm2Object<'gc>,
+    },
+
     /// Loader that is loading an MP3 into an AVM1 Sound object.
     SoundAvm1 {
         /// The handle to refer to this loader instance.
@@ -1551,187 +1679,516 @@ impl<'gc> Loader<'gc> {
             .upgrade()
             .expect("Could not upgrade weak reference to player");
 
-        Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
-            let response = Self::wait_for_full_response(fetch).await;
+        fn target<'gc>(
+            uc: &UpdateContext<'gc>,
+            handle: LoaderHandle,
+        ) -> Result<Avm2Object<'gc>, Error> {
+            match uc.load_manager.get_loader(handle) {
+                Some(&Loader::LoadURLLoader { target_object, .. }) => Ok(target_object),
+                None => Err(Error::Cancelled),
+                _ => Err(Error::NotLoadDataLoader),
+            }
+        }
 
-            player.lock().unwrap().update(|uc| {
-                let loader = uc.load_manager.get_loader(handle);
-                let target = match loader {
-                    Some(&Loader::LoadURLLoader { target_object, .. }) => target_object,
-                    // We would have already returned after the previous 'update' call
-                    _ => unreachable!(),
+        fn set_data<'a, 'gc: 'a>(
+            body: Vec<u8>,
+            activation: &mut Avm2Activation<'a, 'gc>,
+            target: Avm2Object<'gc>,
+        ) {
+            let data_format = target
+                .get_public_property("dataFormat", activation)
+                .expect("The dataFormat field exists on URLLoaders")
+                .coerce_to_string(activation)
+                .expect("The dataFormat field is typed String");
+
+            let data_object = if &data_format == b"binary" {
+                let storage = ByteArrayStorage::from_vec(body);
+                let bytearray = ByteArrayObject::from_storage(activation, storage).unwrap();
+
+                Some(bytearray.into())
+            } else if &data_format == b"variables" {
+                if body.is_empty() {
+                    None
+                } else {
+                    let string_value = strip_bom(activation, &body);
+
+                    activation
+                        .avm2()
+                        .classes()
+                        .urlvariables
+                        .construct(activation, &[string_value.into()])
+                        .ok()
+                        .map(|o| o.into())
+                }
+            } else {
+                if &data_format != b"text" {
+                    tracing::warn!("Invalid URLLoaderDataFormat: {}", data_format);
+                }
+
+                Some(strip_bom(activation, &body).into())
+            };
+
+            if let Some(data_object) = data_object {
+                target
+                    .set_public_property("data", data_object, activation)
+                    .unwrap();
+            }
+        }
+
+        fn report_error<'gc>(
+            uc: &mut UpdateContext<'gc>,
+            handle: LoaderHandle,
+            response: ErrorResponse,
+        ) -> Result<(), Error> {
+            let target = target(uc, handle)?;
+
+            tracing::error!(
+                "Error during URLLoader load of {:?}: {:?}",
+                response.url,
+                response.error
+            );
+
+            let mut activation = Avm2Activation::from_nothing(uc);
+
+            // Testing with Flash shoes that the 'data' property is cleared
+            // when an error occurs
+            set_data(Vec::new(), &mut activation, target);
+
+            let (status_code, redirected) =
+                if let Error::HttpNotOk(_, status_code, redirected, _) = response.error {
+                    (status_code, redirected)
+                } else {
+                    (0, false)
                 };
+            Loader::dispatch_http_status_event(
+                &mut activation,
+                target,
+                "httpStatus",
+                status_code,
+                redirected,
+                &response.url,
+                &[],
+            )?;
+
+            // FIXME - Match the exact error message generated by Flash
+            Loader::dispatch_io_error_event(&mut activation, target, "Error #2032: Stream Error")
+        }
 
-                let mut activation = Avm2Activation::from_nothing(uc);
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let mut response = match fetch.await {
+                Ok(response) => response,
+                Err(response) => {
+                    return player
+                        .lock()
+                        .unwrap()
+                        .update(|uc| report_error(uc, handle, response));
+                }
+            };
 
-                fn set_data<'a, 'gc: 'a>(
-                    body: Vec<u8>,
-                    activation: &mut Avm2Activation<'a, 'gc>,
-                    target: Avm2Object<'gc>,
-                ) {
-                    let data_format = target
-                        .get_public_property("dataFormat", activation)
-                        .expect("The dataFormat field exists on URLLoaders")
-                        .coerce_to_string(activation)
-                        .expect("The dataFormat field is typed String");
-
-                    let data_object = if &data_format == b"binary" {
-                        let storage = ByteArrayStorage::from_vec(body);
-                        let bytearray = ByteArrayObject::from_storage(activation, storage).unwrap();
-
-                        Some(bytearray.into())
-                    } else if &data_format == b"variables" {
-                        if body.is_empty() {
-                            None
-                        } else {
-                            let string_value = strip_bom(activation, &body);
-
-                            activation
-                                .avm2()
-                                .classes()
-                                .urlvariables
-                                .construct(activation, &[string_value.into()])
-                                .ok()
-                                .map(|o| o.into())
-                        }
-                    } else {
-                        if &data_format != b"text" {
-                            tracing::warn!("Invalid URLLoaderDataFormat: {}", data_format);
-                        }
+            let status = response.status();
+            let redirected = response.redirected();
+            let response_url = response.url().to_string();
+            let response_headers = response.headers();
+            let expected_length = response.expected_length().ok().flatten().unwrap_or(0) as usize;
 
-                        Some(strip_bom(activation, &body).into())
-                    };
+            player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                let target = target(uc, handle)?;
+                let mut activation = Avm2Activation::from_nothing(uc);
+                target
+                    .set_public_property("bytesLoaded", 0.into(), &mut activation)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                target
+                    .set_public_property("bytesTotal", expected_length.into(), &mut activation)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                Ok(())
+            })?;
 
-                    if let Some(data_object) = data_object {
-                        target
-                            .set_public_property("data", data_object, activation)
-                            .unwrap();
+            // The "open" event is only fired once the first chunk arrives, as
+            // local files that fail to open only report their error then.
+            // Flash doesn't fire "open" at all in that case.
+            let mut opened = false;
+            let mut body = Vec::new();
+            loop {
+                let chunk = match response.next_chunk().await {
+                    Ok(chunk) => chunk,
+                    Err(error) => {
+                        let url = response.url().to_string();
+                        return player
+                            .lock()
+                            .unwrap()
+                            .update(|uc| report_error(uc, handle, ErrorResponse { url, error }));
                     }
-                }
+                };
+                let is_end = chunk.is_none();
 
-                match response {
-                    Ok((body, _, status, redirected)) => {
-                        let total_len = body.len();
+                player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                    let target = target(uc, handle)?;
+                    let mut activation = Avm2Activation::from_nothing(uc);
 
-                        // FIXME - the "open" event should be fired earlier, just before
-                        // we start to fetch the data.
-                        // However, the "open" event should not be fired if an IO error
-                        // occurs opening the connection (e.g. if a file does not exist on disk).
-                        // We currently have no way of detecting this, so we settle for firing
-                        // the event after the entire fetch is complete. This causes there
-                        // to a longer delay between the initial load triggered by the script
-                        // and the "load" event firing, but it ensures that we match
-                        // the Flash behavior w.r.t when an event is fired vs not fired.
+                    if !opened {
+                        opened = true;
                         let open_evt =
                             Avm2EventObject::bare_default_event(activation.context, "open");
                         Avm2::dispatch_event(activation.context, open_evt, target);
-                        set_data(body, &mut activation, target);
 
-                        // FIXME - we should fire "progress" events as we receive data, not
-                        // just at the end
-                        let progress_evt = activation
-                            .avm2()
-                            .classes()
-                            .progressevent
-                            .construct(
+                        if activation.avm2().player_runtime == PlayerRuntime::AIR {
+                            Loader::dispatch_http_status_event(
                                 &mut activation,
-                                &[
-                                    "progress".into(),
-                                    false.into(),
-                                    false.into(),
-                                    total_len.into(),
+                                target,
+                                "httpResponseStatus",
+                                status,
+                                redirected,
+                                &response_url,
+                                &response_headers,
+                            )?;
+                        }
+                    }
+
+                    match chunk {
+                        Some(chunk) => {
+                            body.extend_from_slice(&chunk);
+                            let total_len = body.len().max(expected_length);
+                            target
+                                .set_public_property(
+                                    "bytesLoaded",
+                                    body.len().into(),
+                                    &mut activation,
+                                )
+                                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                            target
+                                .set_public_property(
+                                    "bytesTotal",
                                     total_len.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                                    &mut activation,
+                                )
+                                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                            Loader::dispatch_progress_event(
+                                &mut activation,
+                                target,
+                                body.len(),
+                                total_len,
+                            )?;
+                        }
+                        None => {
+                            set_data(std::mem::take(&mut body), &mut activation, target);
+                            Loader::dispatch_http_status_event(
+                                &mut activation,
+                                target,
+                                "httpStatus",
+                                status,
+                                redirected,
+                                &response_url,
+                                &response_headers,
+                            )?;
 
-                        Avm2::dispatch_event(activation.context, progress_evt, target);
+                            let complete_evt =
+                                Avm2EventObject::bare_default_event(activation.context, "complete");
+                            Avm2::dispatch_event(activation.context, complete_evt, target);
+                        }
+                    }
 
-                        let http_status_evt = activation
-                            .avm2()
-                            .classes()
-                            .httpstatusevent
-                            .construct(
-                                &mut activation,
-                                &[
-                                    "httpStatus".into(),
-                                    false.into(),
-                                    false.into(),
-                                    status.into(),
-                                    redirected.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    Ok(())
+                })?;
 
-                        Avm2::dispatch_event(activation.context, http_status_evt, target);
+                if is_end {
+                    break;
+                }
+            }
 
-                        let complete_evt =
-                            Avm2EventObject::bare_default_event(activation.context, "complete");
-                        Avm2::dispatch_event(uc, complete_evt, target);
-                    }
-                    Err(response) => {
-                        tracing::error!(
-                            "Error during URLLoader load of {:?}: {:?}",
-                            response.url,
-                            response.error
-                        );
+            Ok(())
+        })
+    }
 
-                        // Testing with Flash shoes that the 'data' property is cleared
-                        // when an error occurs
+    /// Creates a future for a LoadURLStream load call.
+    ///
+    /// Each chunk is appended to the stream's buffer as soon as it arrives,
+    /// so that it can be read during the "progress" event that follows.
+    fn load_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::LoadURLStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotLoadDataLoader) }),
+        };
 
-                        set_data(Vec::new(), &mut activation, target);
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
 
-                        let (status_code, redirected) =
-                            if let Error::HttpNotOk(_, status_code, redirected, _) = response.error
-                            {
-                                (status_code, redirected)
-                            } else {
-                                (0, false)
-                            };
-                        let http_status_evt = activation
-                            .avm2()
-                            .classes()
-                            .httpstatusevent
-                            .construct(
-                                &mut activation,
-                                &[
-                                    "httpStatus".into(),
-                                    false.into(),
-                                    false.into(),
-                                    status_code.into(),
-                                    redirected.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+        /// Returns the stream and its buffer, or `Error::Cancelled` if
+        /// the stream was closed or started another load in the meantime.
+        fn target<'gc>(
+            activation: &mut Avm2Activation<'_, 'gc>,
+            handle: LoaderHandle,
+        ) -> Result<(Avm2Object<'gc>, Avm2Object<'gc>), Error> {
+            let (target, buffer) = match activation.context.load_manager.get_loader(handle) {
+                Some(&Loader::LoadURLStream {
+                    target_object,
+                    buffer,
+                    ..
+                }) => (target_object, buffer),
+                None => return Err(Error::Cancelled),
+                _ => return Err(Error::NotLoadDataLoader),
+            };
 
-                        Avm2::dispatch_event(activation.context, http_status_evt, target);
+            let is_loading = url_stream::is_loading_into(activation, target, buffer)
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            if is_loading {
+                Ok((target, buffer))
+            } else {
+                Err(Error::Cancelled)
+            }
+        }
 
-                        // FIXME - Match the exact error message generated by Flash
+        fn report_error<'gc>(
+            uc: &mut UpdateContext<'gc>,
+            handle: LoaderHandle,
+            response: ErrorResponse,
+        ) -> Result<(), Error> {
+            let mut activation = Avm2Activation::from_nothing(uc);
+            let (target, _) = target(&mut activation, handle)?;
+
+            tracing::error!(
+                "Error during URLStream load of {:?}: {:?}",
+                response.url,
+                response.error
+            );
 
-                        let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
-                        let io_error_evt = io_error_evt_cls
-                            .construct(
+            url_stream::disconnect(&mut activation, target)
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+            let (status_code, redirected) =
+                if let Error::HttpNotOk(_, status_code, redirected, _) = response.error {
+                    (status_code, redirected)
+                } else {
+                    (0, false)
+                };
+            Loader::dispatch_http_status_event(
+                &mut activation,
+                target,
+                "httpStatus",
+                status_code,
+                redirected,
+                &response.url,
+                &[],
+            )?;
+            Loader::dispatch_io_error_event(&mut activation, target, "Error #2032: Stream Error")
+        }
+
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let mut response = match fetch.await {
+                Ok(response) => response,
+                Err(response) => {
+                    return player
+                        .lock()
+                        .unwrap()
+                        .update(|uc| report_error(uc, handle, response));
+                }
+            };
+
+            let status = response.status();
+            let redirected = response.redirected();
+            let response_url = response.url().to_string();
+            let response_headers = response.headers();
+            let expected_length = response.expected_length().ok().flatten().unwrap_or(0) as usize;
+
+            let mut opened = false;
+            let mut bytes_loaded = 0;
+            loop {
+                let chunk = match response.next_chunk().await {
+                    Ok(chunk) => chunk,
+                    Err(error) => {
+                        let url = response.url().to_string();
+                        return player
+                            .lock()
+                            .unwrap()
+                            .update(|uc| report_error(uc, handle, ErrorResponse { url, error }));
+                    }
+                };
+                let is_end = chunk.is_none();
+
+                player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                    let mut activation = Avm2Activation::from_nothing(uc);
+                    let (target, buffer) = target(&mut activation, handle)?;
+
+                    if !opened {
+                        opened = true;
+                        let open_evt =
+                            Avm2EventObject::bare_default_event(activation.context, "open");
+                        Avm2::dispatch_event(activation.context, open_evt, target);
+
+                        if activation.avm2().player_runtime == PlayerRuntime::AIR {
+                            Loader::dispatch_http_status_event(
                                 &mut activation,
-                                &[
-                                    "ioError".into(),
-                                    false.into(),
-                                    false.into(),
-                                    "Error #2032: Stream Error".into(),
-                                    2032.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                                target,
+                                "httpResponseStatus",
+                                status,
+                                redirected,
+                                &response_url,
+                                &response_headers,
+                            )?;
+                        }
+
+                        // Unlike `URLLoader`, the status is known before any data is
+                        // delivered, so that readers can check it while streaming.
+                        Loader::dispatch_http_status_event(
+                            &mut activation,
+                            target,
+                            "httpStatus",
+                            status,
+                            redirected,
+                            &response_url,
+                            &response_headers,
+                        )?;
+                    }
 
-                        Avm2::dispatch_event(uc, io_error_evt, target);
+                    match chunk {
+                        Some(chunk) => {
+                            if let Some(mut storage) = buffer.as_bytearray_mut() {
+                                let end = storage.len();
+                                storage
+                                    .write_at(&chunk, end)
+                                    .map_err(|e| Error::Avm2Error(format!("{e:?}")))?;
+                            }
+                            bytes_loaded += chunk.len();
+                            Loader::dispatch_progress_event(
+                                &mut activation,
+                                target,
+                                bytes_loaded,
+                                expected_length.max(bytes_loaded),
+                            )?;
+                        }
+                        None => {
+                            let complete_evt =
+                                Avm2EventObject::bare_default_event(activation.context, "complete");
+                            Avm2::dispatch_event(activation.context, complete_evt, target);
+                        }
                     }
+
+                    Ok(())
+                })?;
+
+                if is_end {
+                    break;
                 }
+            }
 
-                Ok(())
-            })
+            Ok(())
         })
     }
 
+    fn dispatch_progress_event(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        bytes_loaded: usize,
+        bytes_total: usize,
+    ) -> Result<(), Error> {
+        let progress_evt = activation
+            .avm2()
+            .classes()
+            .progressevent
+            .construct(
+                activation,
+                &[
+                    "progress".into(),
+                    false.into(),
+                    false.into(),
+                    bytes_loaded.into(),
+                    bytes_total.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        Avm2::dispatch_event(activation.context, progress_evt, target);
+        Ok(())
+    }
+
+    /// Dispatches an `HTTPStatusEvent` of the given type.
+    ///
+    /// AIR content additionally receives the URL and headers of the response.
+    fn dispatch_http_status_event(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        event_type: &'static str,
+        status: u16,
+        redirected: bool,
+        response_url: &str,
+        response_headers: &[(String, String)],
+    ) -> Result<(), Error> {
+        let http_status_evt = activation
+            .avm2()
+            .classes()
+            .httpstatusevent
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    false.into(),
+                    false.into(),
+                    status.into(),
+                    redirected.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        if activation.avm2().player_runtime == PlayerRuntime::AIR {
+            let header_class = activation.avm2().classes().urlrequestheader;
+            let mut headers = Vec::with_capacity(response_headers.len());
+            for (name, value) in response_headers {
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+                let value = AvmString::new_utf8(activation.context.gc_context, value);
+                let header = header_class
+                    .construct(activation, &[name.into(), value.into()])
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                headers.push(header);
+            }
+            let headers = Avm2ArrayObject::from_storage(activation, headers.into_iter().collect())
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            let response_url = AvmString::new_utf8(activation.context.gc_context, response_url);
+
+            http_status_evt
+                .set_public_property("responseHeaders", headers.into(), activation)
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            http_status_evt
+                .set_public_property("responseURL", response_url.into(), activation)
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+        }
+
+        Avm2::dispatch_event(activation.context, http_status_evt, target);
+        Ok(())
+    }
+
+    fn dispatch_io_error_event(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        message: &'static str,
+    ) -> Result<(), Error> {
+        let io_error_evt = activation
+            .avm2()
+            .classes()
+            .ioerrorevent
+            .construct(
+                activation,
+                &[
+                    "ioError".into(),
+                    false.into(),
+                    false.into(),
+                    message.into(),
+                    2032.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        Avm2::dispatch_event(activation.context, io_error_evt, target);
+        Ok(())
+    }
+
     /// Creates a future for a Sound load call.
     fn sound_loader_avm1(
         &mut self,
@@ -1982,6 +2439,137 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Play an HLS stream into a `NetStream`.
+    ///
+    /// Segments are downloaded one after another and converted into FLV tags,
+    /// so that the stream plays like a progressively downloaded FLV.
+    fn hls_stream_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::NetStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotNetStreamLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        fn target_stream<'gc>(
+            uc: &mut UpdateContext<'gc>,
+            handle: LoaderHandle,
+        ) -> Result<NetStream<'gc>, Error> {
+            match uc.load_manager.get_loader(handle) {
+                Some(&Loader::NetStream { target_stream, .. }) => Ok(target_stream),
+                None => Err(Error::Cancelled),
+                _ => Err(Error::NotNetStreamLoader),
+            }
+        }
+
+        Box::pin(async move {
+            let fetch_body = |url: String| {
+                let fetch = player.lock().unwrap().navigator().fetch(Request::get(url));
+                async move {
+                    let response = fetch.await.map_err(|response| response.error)?;
+                    response.body().await
+                }
+            };
+            let fetch_playlist = |url: String| {
+                let body = fetch_body(url.clone());
+                async move {
+                    let body = body.await?;
+                    Playlist::parse(&String::from_utf8_lossy(&body), &url)
+                        .map_err(|e| Error::FetchError(e.to_string()))
+                }
+            };
+
+            let result = async {
+                let mut playlist_url = request.url().to_string();
+                let mut playlist = fetch_playlist(playlist_url.clone()).await?;
+                if let Playlist::Master(variants) = &playlist {
+                    let variant =
+                        hls::select_variant(variants).expect("Master playlist has variants");
+                    playlist_url = variant.url.clone();
+                    playlist = fetch_playlist(playlist_url.clone()).await?;
+                }
+                let Playlist::Media(mut media) = playlist else {
+                    return Err(Error::FetchError("Nested HLS master playlist".to_string()));
+                };
+
+                player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                    let stream = target_stream(uc, handle)?;
+                    stream.reset_buffer(uc);
+                    stream.load_buffer(uc, &mut TsToFlv::flv_header());
+                    Ok(())
+                })?;
+
+                let mut remuxer = TsToFlv::new();
+                let mut next_sequence = 0;
+                loop {
+                    for segment in &media.segments {
+                        if segment.sequence < next_sequence {
+                            continue;
+                        }
+
+                        let segment_data = fetch_body(segment.url.clone()).await?;
+                        let mut tags = remuxer.push_segment(&segment_data);
+                        player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                            let stream = target_stream(uc, handle)?;
+                            stream.load_buffer(uc, &mut tags);
+                            Ok(())
+                        })?;
+
+                        next_sequence = segment.sequence + 1;
+                    }
+
+                    if media.ended {
+                        break;
+                    }
+
+                    // Live playlists grow over time; reload the playlist about once
+                    // per segment until it ends or the stream is closed.
+                    let target_duration = media
+                        .target_duration
+                        .unwrap_or(hls::DEFAULT_TARGET_DURATION);
+                    PlayerDelay::new(&player, Duration::from_secs_f64(target_duration)).await?;
+                    player
+                        .lock()
+                        .unwrap()
+                        .update(|uc| target_stream(uc, handle).map(|_| ()))?;
+
+                    media = match fetch_playlist(playlist_url.clone()).await? {
+                        Playlist::Media(reloaded) => reloaded,
+                        Playlist::Master(_) => {
+                            return Err(Error::FetchError(
+                                "Live HLS playlist became a master playlist".to_string(),
+                            ));
+                        }
+                    };
+                }
+
+                Ok::<(), Error>(())
+            }
+            .await;
+
+            player.lock().unwrap().update(|uc| {
+                let stream = target_stream(uc, handle)?;
+                match result {
+                    Ok(()) => stream.finish_buffer(uc),
+                    Err(Error::Cancelled) => return Err(Error::Cancelled),
+                    Err(e) => {
+                        tracing::error!("HLS playback failed: {}", e);
+                        stream.report_error(e);
+                    }
+                }
+                Ok(())
+            })
+        })
+    }
+
     /// Report a movie loader start event to script code.
     fn movie_loader_start(handle: LoaderHandle, uc: &mut UpdateContext<'gc>) -> Result<(), Error> {
         let me = uc.load_manager.get_loader_mut(handle);
@@ -2043,16 +2631,23 @@ impl<'gc> Loader<'gc> {
                 );
             }
         }
-        let (clip, vm_data, from_bytes) = match uc.load_manager.get_loader(handle) {
-            Some(Loader::Movie {
-                target_clip,
-                vm_data,
-                from_bytes,
-                ..
-            }) => (*target_clip, *vm_data, *from_bytes),
-            None => return Err(Error::Cancelled),
-            _ => unreachable!(),
-        };
+        let (clip, vm_data, from_bytes, security_domain_importer) =
+            match uc.load_manager.get_loader(handle) {
+                Some(Loader::Movie {
+                    target_clip,
+                    vm_data,
+                    from_bytes,
+                    security_domain_importer,
+                    ..
+                }) => (
+                    *target_clip,
+                    *vm_data,
+                    *from_bytes,
+                    security_domain_importer.clone(),
+                ),
+                None => return Err(Error::Cancelled),
+                _ => unreachable!(),
+            };
 
         let mut activation = Avm2Activation::from_nothing(uc);
 
@@ -2082,7 +2677,9 @@ impl<'gc> Loader<'gc> {
 
         let movie = match sniffed_type {
             ContentType::Swf => {
-                Arc::new(SwfMovie::from_data(data, url.clone(), loader_url.clone())?)
+                let movie = Arc::new(SwfMovie::from_data(data, url.clone(), loader_url.clone())?);
+                prefetch::prefetch_assets(activation.context, &movie);
+                movie
             }
             ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
                 Arc::new(SwfMovie::from_loaded_image(url.clone(), length))
@@ -2090,6 +2687,16 @@ impl<'gc> Loader<'gc> {
             ContentType::Unknown => Arc::new(SwfMovie::error_movie(url.clone())),
         };
 
+        if let Some(importer) = security_domain_importer {
+            // The loaded movie joins the loader's security domain, as if it was served from there.
+            // This is keyed by the URL the movie ended up at, which is what its sandbox is
+            // looked up by, and may differ from the requested one after a redirect.
+            activation
+                .context
+                .domain_permissions
+                .import_into_security_domain(movie.url().to_string(), &importer);
+        }
+
         match activation.context.load_manager.get_loader_mut(handle) {
             Some(Loader::Movie {
                 movie: old,
@@ -2219,7 +2826,7 @@ impl<'gc> Loader<'gc> {
 
                 // This will construct AVM2-side objects even under AVM1, but it doesn't matter,
                 // since Bitmap and BitmapData never have AVM1-side objects.
-                let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data, None)?;
+                let bitmap = ruffle_render::utils::decode_loaded_image(data)?;
 
                 let transparency = true;
                 let bitmap_data = BitmapData::new_with_pixels(
@@ -3122,7 +3729,6 @@ impl<'gc> Loader<'gc> {
                                     match err.error {
                                         Error::InvalidDomain(_) => {
                                             activation
-                                                .context
                                                 .avm_trace(&format!("Error opening URL '{}'", url));
 
                                             as_broadcaster::broadcast_internal(
@@ -3143,7 +3749,6 @@ impl<'gc> Loader<'gc> {
                                             )?;
 
                                             activation
-                                                .context
                                                 .avm_trace(&format!("Error opening URL '{}'", url));
 
                                             as_broadcaster::broadcast_internal(
@@ -3177,7 +3782,6 @@ impl<'gc> Loader<'gc> {
                                             )?;
 
                                             activation
-                                                .context
                                                 .avm_trace(&format!("Error opening URL '{}'", url));
 
                                             as_broadcaster::broadcast_internal(
@@ -3383,3 +3987,178 @@ impl<'gc> Loader<'gc> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::navigator::{NavigationMethod, NavigatorBackend};
+    use crate::player::PlayerBuilder;
+    use crate::socket::{SocketAction, SocketHandle};
+    use async_channel::{Receiver, Sender};
+    use encoding_rs::Encoding;
+    use std::borrow::Cow;
+    use std::collections::VecDeque;
+
+    struct TestResponse {
+        url: String,
+        body: Option<Vec<u8>>,
+    }
+
+    impl SuccessResponse for TestResponse {
+        fn url(&self) -> Cow<str> {
+            Cow::Borrowed(&self.url)
+        }
+
+        fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
+            let body = self.body.unwrap_or_default();
+            Box::pin(async move { Ok(body) })
+        }
+
+        fn text_encoding(&self) -> Option<&'static Encoding> {
+            None
+        }
+
+        fn status(&self) -> u16 {
+            200
+        }
+
+        fn redirected(&self) -> bool {
+            false
+        }
+
+        fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+            let chunk = self.body.take();
+            Box::pin(async move { Ok(chunk) })
+        }
+
+        fn expected_length(&self) -> Result<Option<u64>, Error> {
+            Ok(self.body.as_ref().map(|body| body.len() as u64))
+        }
+    }
+
+    /// Serves a live playlist that changes on every fetch, and empty segments.
+    struct LiveNavigator {
+        playlists: Arc<Mutex<VecDeque<&'static str>>>,
+        fetches: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl NavigatorBackend for LiveNavigator {
+        fn navigate_to_url(
+            &self,
+            _url: &str,
+            _target: &str,
+            _vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+        ) {
+        }
+
+        fn fetch(&self, request: Request) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+            let url = request.url().to_string();
+            self.fetches.lock().unwrap().push(url.clone());
+            let body = if hls::is_hls_url(&url) {
+                let mut playlists = self.playlists.lock().unwrap();
+                let playlist = if playlists.len() > 1 {
+                    playlists.pop_front()
+                } else {
+                    playlists.front().copied()
+                };
+                playlist.unwrap_or_default().as_bytes().to_vec()
+            } else {
+                Vec::new()
+            };
+            let response: Box<dyn SuccessResponse> = Box::new(TestResponse {
+                url,
+                body: Some(body),
+            });
+            Box::pin(async move { Ok(response) })
+        }
+
+        fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+            Url::parse(url)
+        }
+
+        fn spawn_future(&mut self, _future: OwnedFuture<(), Error>) {}
+
+        fn pre_process_url(&self, url: Url) -> Url {
+            url
+        }
+
+        fn connect_socket(
+            &mut self,
+            _host: String,
+            _port: u16,
+            _timeout: Duration,
+            _handle: SocketHandle,
+            _receiver: Receiver<Vec<u8>>,
+            _sender: Sender<SocketAction>,
+        ) {
+        }
+    }
+
+    #[test]
+    fn live_hls_playlist_is_reloaded_until_it_ends() {
+        let playlists = [
+            "#EXTM3U\n#EXT-X-TARGETDURATION:2\n#EXT-X-MEDIA-SEQUENCE:0\n\
+            #EXTINF:2,\nsegment0.ts\n#EXTINF:2,\nsegment1.ts\n",
+            // Nothing new yet; the stream keeps polling.
+            "#EXTM3U\n#EXT-X-TARGETDURATION:2\n#EXT-X-MEDIA-SEQUENCE:0\n\
+            #EXTINF:2,\nsegment0.ts\n#EXTINF:2,\nsegment1.ts\n",
+            "#EXTM3U\n#EXT-X-TARGETDURATION:2\n#EXT-X-MEDIA-SEQUENCE:1\n\
+            #EXTINF:2,\nsegment1.ts\n#EXTINF:2,\nsegment2.ts\n",
+            "#EXTM3U\n#EXT-X-TARGETDURATION:2\n#EXT-X-MEDIA-SEQUENCE:2\n\
+            #EXTINF:2,\nsegment2.ts\n#EXTINF:2,\nsegment3.ts\n#EXT-X-ENDLIST\n",
+        ];
+        let fetches = Arc::new(Mutex::new(Vec::new()));
+        let player = PlayerBuilder::new()
+            .with_navigator(LiveNavigator {
+                playlists: Arc::new(Mutex::new(playlists.into_iter().collect())),
+                fetches: fetches.clone(),
+            })
+            .with_movie(SwfMovie::empty(10))
+            .build();
+
+        let mut future = player.lock().unwrap().update(|uc| {
+            let stream = NetStream::new(uc.gc_context, None);
+            uc.load_manager.load_netstream(
+                Arc::downgrade(&player),
+                stream,
+                Request::get("https://example.com/live.m3u8".to_string()),
+            )
+        });
+
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut run = |dt: f64| {
+            player.lock().unwrap().update_timers(dt);
+            let poll = future.as_mut().poll(&mut cx);
+            let fetched = std::mem::take(&mut *fetches.lock().unwrap());
+            let fetched: Vec<_> = fetched
+                .iter()
+                .map(|url| url.trim_start_matches("https://example.com/").to_string())
+                .collect();
+            (poll.is_ready(), fetched)
+        };
+
+        assert_eq!(
+            run(0.0),
+            (
+                false,
+                vec![
+                    "live.m3u8".into(),
+                    "segment0.ts".into(),
+                    "segment1.ts".into()
+                ]
+            )
+        );
+        // The playlist isn't reloaded before its target duration has passed.
+        assert_eq!(run(1000.0), (false, vec![]));
+        assert_eq!(run(1000.0), (false, vec!["live.m3u8".into()]));
+        assert_eq!(
+            run(2000.0),
+            (false, vec!["live.m3u8".into(), "segment2.ts".into()])
+        );
+        assert_eq!(
+            run(2000.0),
+            (true, vec!["live.m3u8".into(), "segment3.ts".into()])
+        );
+    }
+}
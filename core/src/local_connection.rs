@@ -137,6 +137,11 @@ impl<'gc> LocalConnections<'gc> {
         }
     }
 
+    /// Whether messages are waiting to be delivered at the end of the frame.
+    pub fn has_pending_messages(&self) -> bool {
+        !self.messages.is_empty()
+    }
+
     pub fn connect<C: Into<LocalConnectionKind<'gc>>>(
         &mut self,
         domain: &str,
@@ -124,6 +124,11 @@ impl<'gc> Default for NetConnections<'gc> {
 }
 
 impl<'gc> NetConnections<'gc> {
+    /// Whether no connections are open.
+    pub fn is_empty(&self) -> bool {
+        self.connections.is_empty()
+    }
+
     pub fn connect_to_local<O: Into<NetConnectionObject<'gc>>>(
         context: &mut UpdateContext<'gc>,
         target: O,
@@ -0,0 +1,165 @@
+//! Counts of the AVM1 actions and AVM2 opcodes run by movies, to find out which ones dominate
+//! real content when working on the performance of the interpreters.
+//!
+//! AVM2 opcodes are counted as they run, after the optimizer rewrote them, so the counts
+//! include the opcodes only the optimizer produces. Counting is disabled by default, as it
+//! slows down every opcode.
+
+use fnv::FnvHashMap;
+use serde_json::json;
+use std::fmt;
+use swf::avm1::OpCode as Avm1OpCode;
+
+/// How many times each AVM1 action and AVM2 opcode ran, as collected by [`OpcodeCounters`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpcodeReport {
+    /// Whether counting was enabled at all.
+    pub enabled: bool,
+
+    /// AVM1 actions and how many times they ran, most run first.
+    pub avm1: Vec<(String, u64)>,
+
+    /// AVM2 opcodes and how many times they ran, most run first.
+    pub avm2: Vec<(String, u64)>,
+}
+
+impl OpcodeReport {
+    /// Writes this report as JSON, with an object of counts by name for each interpreter,
+    /// so that reports of different runs or builds can be compared by scripts.
+    pub fn to_json(&self) -> serde_json::Value {
+        let counts = |counts: &[(String, u64)]| -> serde_json::Map<String, serde_json::Value> {
+            counts
+                .iter()
+                .map(|(name, count)| (name.clone(), (*count).into()))
+                .collect()
+        };
+        json!({
+            "enabled": self.enabled,
+            "avm1": counts(&self.avm1),
+            "avm2": counts(&self.avm2),
+        })
+    }
+}
+
+impl fmt::Display for OpcodeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.enabled {
+            return write!(f, "Opcode counting is disabled");
+        }
+        for (title, counts) in [("AVM1 actions", &self.avm1), ("AVM2 opcodes", &self.avm2)] {
+            writeln!(
+                f,
+                "{title}: {}",
+                counts.iter().map(|(_, count)| count).sum::<u64>()
+            )?;
+            for (name, count) in counts {
+                writeln!(f, "  {count} {name}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct OpcodeCounters {
+    enabled: bool,
+
+    /// AVM1 actions, by action code.
+    avm1: Vec<u64>,
+
+    /// AVM2 opcodes, by name.
+    avm2: FnvHashMap<&'static str, u64>,
+}
+
+impl OpcodeCounters {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            avm1: vec![0; 256],
+            avm2: FnvHashMap::default(),
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Whether opcodes should be reported through [`Self::count_avm1`] and [`Self::count_avm2`].
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records that the AVM1 action with the given action code ran.
+    pub fn count_avm1(&mut self, action_code: u8) {
+        self.avm1[action_code as usize] += 1;
+    }
+
+    /// Records that the AVM2 opcode with the given name ran.
+    pub fn count_avm2(&mut self, name: &'static str) {
+        *self.avm2.entry(name).or_default() += 1;
+    }
+
+    /// Forgets everything counted so far, e.g. to only count what runs after some point.
+    pub fn reset(&mut self) {
+        self.avm1.fill(0);
+        self.avm2.clear();
+    }
+
+    pub fn report(&self) -> OpcodeReport {
+        let sorted = |mut counts: Vec<(String, u64)>| {
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            counts
+        };
+        OpcodeReport {
+            enabled: self.enabled,
+            avm1: sorted(
+                self.avm1
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, count)| **count > 0)
+                    .map(|(code, count)| (Avm1OpCode::format(code as u8), *count))
+                    .collect(),
+            ),
+            avm2: sorted(
+                self.avm2
+                    .iter()
+                    .map(|(name, count)| (name.to_string(), *count))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_most_run_opcodes_first() {
+        let mut counters = OpcodeCounters::new(true);
+        counters.count_avm1(Avm1OpCode::Push as u8);
+        counters.count_avm1(Avm1OpCode::Add2 as u8);
+        counters.count_avm1(Avm1OpCode::Push as u8);
+        counters.count_avm2("GetLocal");
+        counters.count_avm2("GetLocal");
+        counters.count_avm2("Add");
+
+        let report = counters.report();
+        assert_eq!(
+            report.avm1,
+            vec![("Push".to_string(), 2), ("Add2".to_string(), 1)]
+        );
+        assert_eq!(
+            report.to_json(),
+            json!({
+                "enabled": true,
+                "avm1": { "Push": 2, "Add2": 1 },
+                "avm2": { "GetLocal": 2, "Add": 1 },
+            })
+        );
+
+        counters.reset();
+        assert!(counters.report().avm1.is_empty());
+        assert!(counters.report().avm2.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::asset_cache;
 use crate::avm1::Attribute;
 use crate::avm1::Avm1;
 use crate::avm1::Object;
@@ -5,26 +6,29 @@ use crate::avm1::SystemProperties;
 use crate::avm1::VariableDumper;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{TObject, Value};
+use crate::avm2::globals::flash::ui::game_input;
 use crate::avm2::{
     object::TObject as _, Activation as Avm2Activation, Avm2, CallStack, Object as Avm2Object,
 };
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
-    audio::{AudioBackend, AudioManager},
+    audio::{AudioBackend, AudioEffects, AudioManager, FastForwardAudio},
+    filesystem::FilesystemBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
     ui::{MouseCursor, UiBackend},
 };
+use crate::bitmap::texture_budget::TextureBudget;
 use crate::compatibility_rules::CompatibilityRules;
-use crate::config::Letterbox;
+use crate::config::{Letterbox, ScreenInfo, SpoofedCapabilities};
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
     BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
 };
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
-    EditText, InteractiveObject, Stage, StageAlign, StageDisplayState, StageScaleMode,
+    EditText, InteractiveObject, MorphShape, Stage, StageAlign, StageDisplayState, StageScaleMode,
     TInteractiveObject, WindowMode,
 };
 use crate::events::GamepadButton;
@@ -32,21 +36,30 @@ use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseBut
 use crate::external::{ExternalInterface, ExternalInterfaceProvider, NullFsCommandProvider};
 use crate::external::{FsCommandProvider, Value as ExternalValue};
 use crate::focus_tracker::NavigationDirection;
+use crate::font::glyph_cache::GlyphCache;
+use crate::frame_hooks::{FrameHookContext, FrameHookHandle, FrameHookPhase, FrameHooks};
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
-use crate::input::InputManager;
+use crate::heap_dump::HeapDump;
+use crate::idle;
+use crate::input::{GameInputDevice, InputManager};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
 use crate::local_connection::LocalConnections;
-use crate::locale::get_current_date_time;
 use crate::net_connection::NetConnections;
+use crate::opcode_counts::{OpcodeCounters, OpcodeReport};
+use crate::policy_file::PolicyFiles;
+use crate::prefetch::PrefetchPolicy;
 use crate::prelude::*;
+use crate::sandbox::DomainPermissions;
+use crate::sitelock::{SitelockDetector, SitelockReport};
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::StringContext;
 use crate::string::{AvmString, AvmStringInterner};
-use crate::stub::StubCollection;
+use crate::stub::{RecentStubs, StubCollection};
 use crate::tag_utils::SwfMovie;
+use crate::time::{SystemTimeSource, TimeSource};
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
 use crate::DefaultFont;
@@ -54,7 +67,8 @@ use gc_arena::lock::GcRefLock;
 use gc_arena::{Collect, DynamicRootSet, Mutation, Rootable};
 use rand::{rngs::SmallRng, SeedableRng};
 use ruffle_render::backend::{null::NullRenderer, RenderBackend, ViewportDimensions};
-use ruffle_render::commands::CommandList;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, PixelRegion};
+use ruffle_render::commands::{CommandHandler, CommandList};
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::TransformStack;
 use ruffle_video::backend::VideoBackend;
@@ -64,6 +78,7 @@ use std::ops::{Deref, DerefMut};
 use std::rc::{Rc, Weak as RcWeak};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex, Weak};
+use std::task::Waker;
 use std::time::Duration;
 use tracing::instrument;
 use web_time::Instant;
@@ -195,6 +210,12 @@ struct GcRootData<'gc> {
     dynamic_root: DynamicRootSet<'gc>,
 
     post_frame_callbacks: Vec<PostFrameCallback<'gc>>,
+
+    /// Limits the GPU memory used by the textures of bitmaps and shapes.
+    texture_budget: TextureBudget<'gc>,
+
+    /// Rasterized glyphs of text using advanced anti-aliasing.
+    glyph_cache: GlyphCache<'gc>,
 }
 
 #[derive(Collect)]
@@ -235,6 +256,8 @@ impl<'gc> GcRootData<'gc> {
         &mut Vec<PostFrameCallback<'gc>>,
         &mut MouseData<'gc>,
         DynamicRootSet<'gc>,
+        TextureBudget<'gc>,
+        GlyphCache<'gc>,
     ) {
         (
             self.stage,
@@ -259,6 +282,8 @@ impl<'gc> GcRootData<'gc> {
             &mut self.post_frame_callbacks,
             &mut self.mouse_data,
             self.dynamic_root,
+            self.texture_budget,
+            self.glyph_cache,
         )
     }
 }
@@ -269,6 +294,7 @@ type Audio = Box<dyn AudioBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
+type Filesystem = Box<dyn FilesystemBackend>;
 type Log = Box<dyn LogBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
@@ -300,6 +326,7 @@ pub struct Player {
     audio: Audio,
     navigator: Navigator,
     storage: Storage,
+    filesystem: Filesystem,
     log: Log,
     ui: Ui,
     video: Video,
@@ -318,6 +345,22 @@ pub struct Player {
 
     stub_tracker: StubCollection,
 
+    sitelock_detector: SitelockDetector,
+
+    opcode_counters: OpcodeCounters,
+
+    /// The hooks added by the embedder, run on every frame.
+    frame_hooks: FrameHooks,
+
+    /// Which movies are allowed to script each other.
+    domain_permissions: DomainPermissions,
+
+    /// The cross-domain policy files loaded by movies.
+    policy_files: PolicyFiles,
+
+    /// Which files referenced by loaded movies are fetched ahead of time.
+    prefetch_policy: PrefetchPolicy,
+
     /// A time budget for executing frames.
     /// Gained by passage of time between host frames, spent by executing SWF frames.
     /// This is how we support custom SWF framerates
@@ -325,6 +368,22 @@ pub struct Player {
     frame_accumulator: f64,
     recent_run_frame_timings: VecDeque<f64>,
 
+    /// How many times faster than real time the movie runs, e.g. to fast-forward cutscenes.
+    speed_multiplier: f64,
+
+    /// What happens to sounds while the movie runs faster or slower than real time.
+    fast_forward_audio: FastForwardAudio,
+
+    /// Whether to check if the movie is idle after each tick.
+    idle_detection: bool,
+
+    /// Whether the last tick left the movie idle, with nothing to do until input arrives.
+    /// See [`Self::is_idle`].
+    is_idle: bool,
+
+    /// The number of frames that have been run so far.
+    frames_run: u64,
+
     /// Faked time passage for fooling hand-written busy-loop FPS limiters.
     time_offset: u32,
 
@@ -333,6 +392,13 @@ pub struct Player {
     mouse_in_stage: bool,
     mouse_position: Point<Twips>,
 
+    /// Whether mouse moves are held back until the next tick or input event,
+    /// so that only the last of them is handled.
+    coalesce_mouse_moves: bool,
+
+    /// The last mouse move that was held back by coalescing.
+    pending_mouse_move: Option<PlayerEvent>,
+
     /// The current mouse cursor icon.
     mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
@@ -347,8 +413,18 @@ pub struct Player {
     /// Time remaining until the next timer will fire.
     time_til_next_timer: Option<f64>,
 
-    /// The instant at which the SWF was launched.
-    start_time: Instant,
+    /// How long timers have been updated for, in milliseconds.
+    ///
+    /// Loaders that wait for time to pass use this clock, so that they pause along
+    /// with the player.
+    load_clock: f64,
+
+    /// Loaders waiting for `load_clock` to reach a time, such as live HLS streams
+    /// between reloads of their playlist.
+    load_wakers: Vec<(f64, Waker)>,
+
+    /// The clocks `getTimer` and `Date` read the time from.
+    time_source: Box<dyn TimeSource>,
 
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
@@ -365,6 +441,12 @@ pub struct Player {
     /// The first frame is frame 1.
     current_frame: Option<u16>,
 
+    /// Whether truncated movies are played up to their last complete frame.
+    partial_playback: bool,
+
+    /// Whether the main timeline was shortened because its movie is truncated.
+    movie_shortened: bool,
+
     /// How Ruffle should load movies.
     load_behavior: LoadBehavior,
 
@@ -456,6 +538,26 @@ impl Player {
         });
     }
 
+    /// Loads a movie from memory as the root movie, as if it was fetched from `movie_url`.
+    ///
+    /// This lets embedders play movies that don't exist as a file, such as generated ones.
+    /// The URL is still used to resolve relative URLs and to determine the security sandbox
+    /// of the movie, unless a spoofed URL was set.
+    pub fn load_movie_from_bytes(
+        &mut self,
+        data: &[u8],
+        movie_url: String,
+        parameters: Vec<(String, String)>,
+    ) -> Result<(), crate::loader::Error> {
+        let movie_url = self.spoofed_url().map(str::to_owned).unwrap_or(movie_url);
+        let mut movie = SwfMovie::from_data(data, movie_url, None)?;
+        movie.append_parameters(parameters);
+        self.mutate_with_update_context(|context| {
+            context.set_root_movie(movie);
+        });
+        Ok(())
+    }
+
     /// Get rough estimate of the max # of times we can update the frame.
     ///
     /// In some cases, we might want to update several times in a row.
@@ -472,13 +574,16 @@ impl Player {
     fn max_frames_per_tick(&self) -> u32 {
         const MAX_FRAMES_PER_TICK: u32 = 5;
 
+        // A fast-forwarded movie needs to run more frames per tick to keep up.
+        let max_frames_per_tick =
+            (f64::from(MAX_FRAMES_PER_TICK) * self.speed_multiplier.max(1.0)).ceil() as u32;
         if self.recent_run_frame_timings.is_empty() {
-            5
+            max_frames_per_tick
         } else {
             let frame_time = 1000.0 / self.frame_rate;
             let average_run_frame_time = self.recent_run_frame_timings.iter().sum::<f64>()
                 / self.recent_run_frame_timings.len() as f64;
-            ((frame_time / average_run_frame_time) as u32).clamp(1, MAX_FRAMES_PER_TICK)
+            ((frame_time / average_run_frame_time) as u32).clamp(1, max_frames_per_tick)
         }
     }
 
@@ -490,6 +595,13 @@ impl Player {
     }
 
     pub fn tick(&mut self, dt: f64) {
+        // Everything in the movie sees time passing faster when fast-forwarding.
+        let dt = dt * self.speed_multiplier;
+        self.time_source
+            .advance(Duration::from_secs_f64(dt.max(0.0) / 1000.0));
+        self.flush_mouse_move();
+        self.run_frame_hooks(FrameHookPhase::PreTick);
+
         if self.is_playing() {
             self.frame_accumulator += dt;
             let frame_rate = self.frame_rate;
@@ -547,6 +659,10 @@ impl Player {
                 StreamManager::tick(context, dt);
             });
             self.audio.tick();
+
+            if self.idle_detection {
+                self.is_idle = self.mutate_with_update_context(idle::movie_is_idle);
+            }
         }
     }
     pub fn time_til_next_timer(&self) -> Option<f64> {
@@ -569,9 +685,9 @@ impl Player {
             dt = dt.min(time_til_next_timer)
         }
 
-        dt = dt.max(0.0);
+        dt = dt.max(0.0) / self.speed_multiplier;
 
-        std::time::Duration::from_micros(dt as u64 * 1000)
+        std::time::Duration::from_micros((dt * 1000.0) as u64)
     }
 
     pub fn is_playing(&self) -> bool {
@@ -586,6 +702,40 @@ impl Player {
         self.mouse_in_stage = is_in;
     }
 
+    /// The game controllers that are currently connected, as far as the player knows.
+    pub fn game_input_devices(&self) -> &[GameInputDevice] {
+        self.input.game_input_devices()
+    }
+
+    /// Connects a game controller, announcing it to the `GameInput` objects of the movie.
+    pub fn add_game_input_device(&mut self, device: GameInputDevice) {
+        let id = device.id.clone();
+        self.input.add_game_input_device(device);
+        self.mutate_with_update_context(|context| {
+            game_input::dispatch_device_event(context, "deviceAdded", &id);
+        });
+    }
+
+    /// Disconnects a game controller, announcing it to the `GameInput` objects of the movie.
+    pub fn remove_game_input_device(&mut self, id: &str) {
+        if self.input.game_input_device(id).is_none() {
+            return;
+        }
+        self.mutate_with_update_context(|context| {
+            game_input::dispatch_device_event(context, "deviceRemoved", id);
+        });
+        self.input.remove_game_input_device(id);
+    }
+
+    /// Updates the value of a button or axis of a game controller.
+    pub fn set_game_input_value(&mut self, id: &str, control: usize, value: f64) {
+        if self.input.set_game_input_value(id, control, value) {
+            self.mutate_with_update_context(|context| {
+                game_input::dispatch_control_change(context, id, control);
+            });
+        }
+    }
+
     /// Returns the master volume of the player. 1.0 is 100% volume.
     ///
     /// The volume is linear and not adapted for logarithmic hearing.
@@ -600,6 +750,57 @@ impl Player {
         self.audio.set_volume(volume)
     }
 
+    /// Returns the effects that are applied to the audio output.
+    pub fn audio_effects(&self) -> AudioEffects {
+        self.audio.effects()
+    }
+
+    /// Sets the effects that are applied to the audio output, which are off by default.
+    ///
+    /// These aren't something Flash Player has, and are meant for users who want to
+    /// improve the sound of old movies.
+    pub fn set_audio_effects(&mut self, effects: AudioEffects) {
+        self.audio.set_effects(effects)
+    }
+
+    /// How many times faster than real time the movie runs.
+    pub fn speed_multiplier(&self) -> f64 {
+        self.speed_multiplier
+    }
+
+    /// Makes the movie run faster (or slower) than real time, e.g. to fast-forward cutscenes.
+    ///
+    /// Frames, timers and sounds all speed up, so the movie behaves as it would at normal speed.
+    /// What happens to the pitch of sounds is chosen with [`Self::set_fast_forward_audio`].
+    pub fn set_speed_multiplier(&mut self, speed_multiplier: f64) {
+        if speed_multiplier.is_finite() && speed_multiplier > 0.0 {
+            self.speed_multiplier = speed_multiplier;
+            self.audio
+                .set_playback_speed(speed_multiplier, self.fast_forward_audio);
+        }
+    }
+
+    pub fn fast_forward_audio(&self) -> FastForwardAudio {
+        self.fast_forward_audio
+    }
+
+    /// Sets whether sounds are time-stretched or muted while the movie isn't at normal speed.
+    pub fn set_fast_forward_audio(&mut self, fast_forward_audio: FastForwardAudio) {
+        self.fast_forward_audio = fast_forward_audio;
+        self.audio
+            .set_playback_speed(self.speed_multiplier, fast_forward_audio);
+    }
+
+    /// Whether the movie has nothing to do until input arrives, so that it doesn't need to be
+    /// ticked until then: no timeline is playing or loading, no script runs on every frame,
+    /// and no timer, sound, stream or network request is pending.
+    ///
+    /// This is only checked when enabled with [`PlayerBuilder::with_idle_detection`], and is
+    /// cleared by any event or update, as scripts may run and wake the movie up.
+    pub fn is_idle(&self) -> bool {
+        self.is_idle
+    }
+
     pub fn prepare_context_menu(&mut self) -> Vec<ContextMenuItem> {
         self.mutate_with_update_context(|context| {
             if !context.stage.show_menu() {
@@ -897,6 +1098,14 @@ impl Player {
         })
     }
 
+    /// Sets the screen the player is shown on, which movies can find out the resolution
+    /// and dpi of through `Capabilities`.
+    ///
+    /// This should be updated whenever the player moves to another screen.
+    pub fn set_screen(&mut self, screen: Option<ScreenInfo>) {
+        self.system.screen = screen;
+    }
+
     pub fn set_show_menu(&mut self, show_menu: bool) {
         self.mutate_with_update_context(|context| {
             let stage = context.stage;
@@ -954,6 +1163,18 @@ impl Player {
     /// Handle an event sent into the player from the external windowing system
     /// or an HTML element.
     pub fn handle_event(&mut self, event: PlayerEvent) -> bool {
+        self.is_idle = false;
+
+        if let PlayerEvent::MouseMove { .. } = event {
+            if self.coalesce_mouse_moves {
+                self.pending_mouse_move = Some(event);
+                return false;
+            }
+        } else {
+            // Other input must see the mouse where it was when it happened.
+            self.flush_mouse_move();
+        }
+
         match event {
             PlayerEvent::FocusGained | PlayerEvent::FocusLost => self.handle_focus_event(event),
             PlayerEvent::KeyDown { .. }
@@ -963,6 +1184,7 @@ impl Player {
             | PlayerEvent::MouseDown { .. }
             | PlayerEvent::MouseLeave
             | PlayerEvent::MouseWheel { .. }
+            | PlayerEvent::MouseHorizontalWheel { .. }
             | PlayerEvent::GamepadButtonDown { .. }
             | PlayerEvent::GamepadButtonUp { .. }
             | PlayerEvent::TextInput { .. }
@@ -970,6 +1192,14 @@ impl Player {
         }
     }
 
+    /// Handles the last mouse move that was held back by coalescing, if any.
+    pub fn flush_mouse_move(&mut self) -> bool {
+        match self.pending_mouse_move.take() {
+            Some(event) => self.handle_input_event(event),
+            None => false,
+        }
+    }
+
     fn handle_focus_event(&mut self, event: PlayerEvent) -> bool {
         if let PlayerEvent::FocusLost = event {
             self.mutate_with_update_context(|context| {
@@ -1094,8 +1324,16 @@ impl Player {
             let button_event = ButtonKeyCode::from_player_event(event)
                 .map(|key_code| ClipEvent::KeyPress { key_code });
 
-            if let PlayerEvent::KeyDown { key_code, key_char }
-            | PlayerEvent::KeyUp { key_code, key_char } = event
+            if let PlayerEvent::KeyDown {
+                key_code,
+                key_char,
+                key_location,
+            }
+            | PlayerEvent::KeyUp {
+                key_code,
+                key_char,
+                key_location,
+            } = event
             {
                 let ctrl_key = context.input.is_key_down(KeyCode::CONTROL);
                 let alt_key = context.input.is_key_down(KeyCode::ALT);
@@ -1113,7 +1351,6 @@ impl Player {
                 let event_name_val: Avm2Value<'_> =
                     AvmString::new_utf8(activation.context.gc_context, event_name).into();
 
-                // TODO: keyLocation should not be a dummy value.
                 // ctrlKey and controlKey can be different from each other on Mac.
                 // commandKey should be supported.
                 let keyboard_event = keyboardevent_class
@@ -1125,7 +1362,7 @@ impl Player {
                             false.into(),                            /* cancelable */
                             key_char.map_or(0, |c| c as u32).into(), /* charCode */
                             key_code.value().into(),                 /* keyCode */
-                            0.into(),                                /* keyLocation */
+                            (key_location as u32).into(),            /* keyLocation */
                             ctrl_key.into(),                         /* ctrlKey */
                             alt_key.into(),                          /* altKey */
                             shift_key.into(),                        /* shiftKey */
@@ -1337,6 +1574,20 @@ impl Player {
             });
         }
 
+        if let PlayerEvent::MouseHorizontalWheel { delta } = event {
+            // Flash has no horizontal wheel events, but scrolling sideways
+            // over a text field should still move its contents.
+            self.mutate_with_update_context(|context| {
+                if let Some(text) = context
+                    .mouse_data
+                    .hovered
+                    .and_then(|o| o.as_displayobject().as_edit_text())
+                {
+                    text.scroll_horizontally(delta, context);
+                }
+            });
+        }
+
         if let PlayerEvent::MouseLeave = event {
             if self.update_mouse_state(&changed_mouse_buttons, true, &mut player_event_handled) {
                 self.needs_render = true;
@@ -1422,17 +1673,18 @@ impl Player {
 
             // Update `_droptarget` property of dragged object.
             if let Some(movie_clip) = display_object.as_movie_clip() {
-                // Turn the dragged object invisible so that we don't pick it.
-                // TODO: This could be handled via adding a `HitTestOptions::SKIP_DRAGGED`.
-                let was_visible = display_object.visible();
-                display_object.set_visible(context, false);
-                // Set `_droptarget` to the object the mouse is hovering over.
-                let drop_target_object = run_mouse_pick(context, false);
-                movie_clip.set_drop_target(
-                    context.gc_context,
-                    drop_target_object.map(|d| d.as_displayobject()),
-                );
-                display_object.set_visible(context, was_visible);
+                // Set `_droptarget` to the deepest object the mouse is hovering over.
+                let mut drop_target = run_drop_target_pick(context, display_object);
+                if !display_object.movie().is_action_script_3() {
+                    // AVM1 reports the clip containing the hit shape, as shapes have no path.
+                    while let Some(target) = drop_target {
+                        if target.as_movie_clip().is_some() {
+                            break;
+                        }
+                        drop_target = target.parent();
+                    }
+                }
+                movie_clip.set_drop_target(context.gc_context, drop_target);
             }
         }
     }
@@ -1906,9 +2158,16 @@ impl Player {
             }
         });
 
+        self.frames_run += 1;
         self.needs_render = true;
     }
 
+    /// The number of frames that have been run so far, for measuring the
+    /// actual frame rate of the movie.
+    pub fn frames_run(&self) -> u64 {
+        self.frames_run
+    }
+
     #[instrument(level = "debug", skip_all)]
     pub fn render(&mut self) {
         let invalidated = self.enter_arena(|_, gc_root, _| gc_root.stage.invalidated());
@@ -1936,6 +2195,8 @@ impl Player {
                 is_offscreen: false,
                 use_bitmap_cache: true,
                 stage,
+                texture_budget: gc_root.texture_budget,
+                glyph_cache: gc_root.glyph_cache,
             };
 
             stage.render(&mut render_context);
@@ -1955,6 +2216,8 @@ impl Player {
                 };
 
             let commands = render_context.commands;
+            gc_root.texture_budget.end_frame(gc_context);
+            gc_root.glyph_cache.end_frame(gc_context);
             (cache_draws, commands)
         });
 
@@ -1962,6 +2225,73 @@ impl Player {
             .submit_frame(background_color, commands, cache_draws);
 
         self.needs_render = false;
+
+        self.run_frame_hooks(FrameHookPhase::PostRender);
+    }
+
+    /// Renders the current frame into a bitmap of the size of the viewport, e.g. for screenshots.
+    ///
+    /// Returns `None` if the renderer doesn't support offscreen rendering.
+    pub fn capture_frame(&mut self) -> Option<Bitmap> {
+        if !self.renderer.is_offscreen_supported() {
+            return None;
+        }
+
+        let viewport = self.renderer.viewport_dimensions();
+        let (width, height) = (viewport.width, viewport.height);
+        let handle = self.renderer.create_empty_texture(width, height).ok()?;
+
+        let (commands, quality) = self.enter_arena_mut(|gc_context, gc_root, this| {
+            let stage = gc_root.stage;
+
+            let mut cache_draws = vec![];
+            let mut render_context = RenderContext {
+                renderer: this.renderer.deref_mut(),
+                commands: CommandList::new(),
+                cache_draws: &mut cache_draws,
+                gc_context,
+                library: &gc_root.library,
+                transform_stack: &mut this.transform_stack,
+                is_offscreen: true,
+                use_bitmap_cache: false,
+                stage,
+                texture_budget: gc_root.texture_budget,
+                glyph_cache: gc_root.glyph_cache,
+            };
+
+            render_context.commands.draw_rect(
+                stage.background_color().unwrap_or(Color::WHITE),
+                Matrix::create_box(
+                    width as f32,
+                    height as f32,
+                    Twips::default(),
+                    Twips::default(),
+                ),
+            );
+            stage.render(&mut render_context);
+
+            (render_context.commands, stage.quality())
+        });
+
+        let sync_handle = self.renderer.render_offscreen(
+            handle,
+            commands,
+            quality,
+            PixelRegion::for_whole_size(width, height),
+        )?;
+
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        self.renderer
+            .resolve_sync_handle(
+                sync_handle,
+                Box::new(|buffer, buffer_width| {
+                    for row in buffer.chunks(buffer_width as usize).take(height as usize) {
+                        data.extend_from_slice(&row[..width as usize * 4]);
+                    }
+                }),
+            )
+            .ok()?;
+        Some(Bitmap::new(width, height, BitmapFormat::Rgba, data))
     }
 
     /// The current frame of the main timeline, if available.
@@ -1970,6 +2300,12 @@ impl Player {
         self.current_frame
     }
 
+    /// Whether the main timeline stops at its last complete frame because the movie is
+    /// truncated. This only happens with [`PlayerBuilder::with_partial_playback`].
+    pub fn is_movie_shortened(&self) -> bool {
+        self.movie_shortened
+    }
+
     pub fn audio(&self) -> &Audio {
         &self.audio
     }
@@ -2007,6 +2343,14 @@ impl Player {
         &mut self.storage
     }
 
+    pub fn filesystem(&self) -> &Filesystem {
+        &self.filesystem
+    }
+
+    pub fn filesystem_mut(&mut self) -> &mut Filesystem {
+        &mut self.filesystem
+    }
+
     pub fn destroy(self) -> Renderer {
         self.renderer
     }
@@ -2145,6 +2489,8 @@ impl Player {
                 post_frame_callbacks,
                 mouse_data,
                 dynamic_root,
+                texture_budget,
+                glyph_cache,
             ) = gc_root.update_context_params();
 
             let mut update_context = UpdateContext {
@@ -2170,6 +2516,7 @@ impl Player {
                 page_url: &mut this.page_url,
                 instance_counter: &mut this.instance_counter,
                 storage: this.storage.deref_mut(),
+                filesystem: this.filesystem.deref_mut(),
                 log: this.log.deref_mut(),
                 video: this.video.deref_mut(),
                 avm1_shared_objects,
@@ -2181,24 +2528,33 @@ impl Player {
                 avm1,
                 avm2,
                 external_interface,
-                start_time: this.start_time,
+                time_source: this.time_source.deref(),
                 update_start: Instant::now(),
                 max_execution_duration: this.max_execution_duration,
+                script_timed_out: false,
                 focus_tracker: stage.focus_tracker(),
                 times_get_time_called: 0,
                 time_offset: &mut this.time_offset,
                 audio_manager,
                 frame_rate: &mut this.frame_rate,
                 forced_frame_rate: this.forced_frame_rate,
+                partial_playback: this.partial_playback,
                 actions_since_timeout_check: &mut this.actions_since_timeout_check,
                 frame_phase: &mut this.frame_phase,
                 stub_tracker: &mut this.stub_tracker,
+                sitelock_detector: &mut this.sitelock_detector,
+                opcode_counters: &mut this.opcode_counters,
+                domain_permissions: &mut this.domain_permissions,
+                policy_files: &mut this.policy_files,
+                prefetch_policy: this.prefetch_policy,
                 stream_manager,
                 sockets,
                 net_connections,
                 local_connections,
                 dynamic_root,
                 post_frame_callbacks,
+                texture_budget,
+                glyph_cache,
             };
 
             let prev_frame_rate = *update_context.frame_rate;
@@ -2213,11 +2569,12 @@ impl Player {
                     .set_frame_rate(*update_context.frame_rate);
             }
 
-            this.current_frame = update_context
+            let root_clip = update_context
                 .stage
                 .root_clip()
-                .and_then(|root| root.as_movie_clip())
-                .map(|clip| clip.current_frame());
+                .and_then(|root| root.as_movie_clip());
+            this.current_frame = root_clip.map(|clip| clip.current_frame());
+            this.movie_shortened = root_clip.is_some_and(|clip| clip.is_shortened());
             ret
         })
     }
@@ -2251,6 +2608,8 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'gc>) -> R,
     {
+        self.is_idle = false;
+
         let rval = self.mutate_with_update_context(|context| {
             let rval = func(context);
 
@@ -2303,6 +2662,26 @@ impl Player {
     pub fn update_timers(&mut self, dt: f64) {
         self.time_til_next_timer =
             self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
+
+        self.load_clock += dt;
+        let load_clock = self.load_clock;
+        self.load_wakers.retain(|(time, waker)| {
+            let is_due = *time <= load_clock;
+            if is_due {
+                waker.wake_by_ref();
+            }
+            !is_due
+        });
+    }
+
+    /// The time of the clock that loaders wait on, in milliseconds.
+    pub(crate) fn load_clock(&self) -> f64 {
+        self.load_clock
+    }
+
+    /// Wakes a loader once `load_clock` reaches `time`.
+    pub(crate) fn wake_loader_at(&mut self, time: f64, waker: Waker) {
+        self.load_wakers.push((time, waker));
     }
 
     /// Update connected Sockets.
@@ -2345,6 +2724,51 @@ impl Player {
         })
     }
 
+    /// Jumps the main timeline to a frame, then either stops or keeps playing from there.
+    pub fn goto_frame(&mut self, frame: u16, stop: bool) {
+        self.update(|context| {
+            if let Some(mc) = context
+                .stage
+                .root_clip()
+                .and_then(|root| root.as_movie_clip())
+            {
+                mc.goto_frame(context, frame, stop);
+                if !stop {
+                    mc.play(context);
+                }
+            }
+        });
+    }
+
+    /// Reads a value by its dot-separated path from the root of the movie, such as `hero.health`.
+    ///
+    /// This is meant for tools automating the player, e.g. to check the state of a movie in tests.
+    /// Returns `None` if the path doesn't lead to a value.
+    pub fn read_property(&mut self, path: &str) -> Option<ExternalValue> {
+        self.mutate_with_update_context(|context| {
+            let root_clip = context.stage.root_clip()?;
+            if root_clip.movie().is_action_script_3() {
+                let mut activation = Avm2Activation::from_nothing(context);
+                let mut value = root_clip.object2();
+                for name in path.split('.').filter(|name| !name.is_empty()) {
+                    let object = value.as_object()?;
+                    let name = AvmString::new_utf8(activation.context.gc_context, name);
+                    value = object.get_public_property(name, &mut activation).ok()?;
+                }
+                Some(ExternalValue::from_avm2(value))
+            } else {
+                let mut activation = Activation::from_nothing(
+                    context,
+                    ActivationIdentifier::root("[Automation]"),
+                    root_clip,
+                );
+                let path = AvmString::new_utf8(activation.context.gc_context, path);
+                let value = activation.get_variable(path).ok()?.into();
+                ExternalValue::from_avm1(&mut activation, value).ok()
+            }
+        })
+    }
+
     pub fn spoofed_url(&self) -> Option<&str> {
         self.spoofed_url.as_deref()
     }
@@ -2365,12 +2789,112 @@ impl Player {
         self.max_execution_duration = max_execution_duration
     }
 
+    /// Sets the maximum GPU memory, in bytes, to use for the textures of bitmaps and shapes.
+    pub fn set_texture_memory_budget(&mut self, budget: Option<usize>) {
+        self.mutate_with_update_context(|context| {
+            context
+                .texture_budget
+                .set_budget(context.gc_context, budget)
+        });
+    }
+
+    /// Sets how long timer callbacks may run per update, before the rest are postponed.
+    pub fn set_timer_budget(&mut self, budget: Duration) {
+        self.mutate_with_update_context(|context| context.timers.set_budget(budget));
+    }
+
     pub fn callstack(&self) -> StaticCallstack {
         StaticCallstack {
             arena: Rc::downgrade(&self.gc_arena),
         }
     }
 
+    /// The stubs most recently encountered by this player.
+    pub fn recent_stubs(&self) -> RecentStubs {
+        self.stub_tracker.recent()
+    }
+
+    /// What the movie did to find out where it is running, if sitelock detection is enabled.
+    pub fn sitelock_report(&self) -> SitelockReport {
+        self.sitelock_detector.report()
+    }
+
+    /// Counts the objects the movie keeps alive, optionally with how they reference each other.
+    pub fn dump_heap(&mut self, include_graph: bool) -> HeapDump {
+        let gc_bytes = self.gc_arena.borrow().metrics().total_allocation();
+        self.mutate_with_update_context(|context| {
+            HeapDump::capture(context, include_graph, gc_bytes)
+        })
+    }
+
+    pub fn set_sitelock_detection(&mut self, enabled: bool) {
+        self.sitelock_detector.set_enabled(enabled);
+    }
+
+    /// How many times each AVM1 action and AVM2 opcode ran, if opcode counting is enabled.
+    pub fn opcode_report(&self) -> OpcodeReport {
+        self.opcode_counters.report()
+    }
+
+    pub fn set_opcode_counting(&mut self, enabled: bool) {
+        self.opcode_counters.set_enabled(enabled);
+    }
+
+    /// Forgets the opcodes counted so far.
+    pub fn reset_opcode_counts(&mut self) {
+        self.opcode_counters.reset();
+    }
+
+    /// Adds a hook that runs at the start of every tick, before the movie runs any frame.
+    pub fn add_pre_tick_hook(
+        &mut self,
+        hook: impl FnMut(&mut FrameHookContext<'_, '_>) + 'static,
+    ) -> FrameHookHandle {
+        self.frame_hooks
+            .add(FrameHookPhase::PreTick, Box::new(hook))
+    }
+
+    /// Adds a hook that runs after every render of the movie.
+    pub fn add_post_render_hook(
+        &mut self,
+        hook: impl FnMut(&mut FrameHookContext<'_, '_>) + 'static,
+    ) -> FrameHookHandle {
+        self.frame_hooks
+            .add(FrameHookPhase::PostRender, Box::new(hook))
+    }
+
+    /// Removes a hook added by [`Self::add_pre_tick_hook`] or [`Self::add_post_render_hook`],
+    /// returning whether it was still there.
+    pub fn remove_frame_hook(&mut self, handle: FrameHookHandle) -> bool {
+        self.frame_hooks.remove(handle)
+    }
+
+    fn run_frame_hooks(&mut self, phase: FrameHookPhase) {
+        if !self.frame_hooks.has_phase(phase) {
+            return;
+        }
+
+        // The hooks are taken out while they run, as they get to borrow the rest of the player.
+        // Unlike `update`, this keeps the movie idle: hooks run on every frame anyway.
+        let mut frame_hooks = std::mem::take(&mut self.frame_hooks);
+        self.mutate_with_update_context(|context| {
+            frame_hooks.run(phase, context);
+            Self::run_actions(context);
+        });
+        self.frame_hooks = frame_hooks;
+    }
+
+    /// Reseeds the random number generator, so that the random numbers the movie
+    /// gets from now on are the same every time, e.g. when replaying recorded input.
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    /// The root movie of this player.
+    pub fn swf(&self) -> &Arc<SwfMovie> {
+        &self.swf
+    }
+
     /// Eagerly load any device fonts.
     /// It's preferable to use [UiBackend::load_device_font] for lazy font loading,
     /// but this is for situations where you don't know the names of the fonts you're going to register.
@@ -2399,6 +2923,7 @@ pub struct PlayerBuilder {
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
     storage: Option<Storage>,
+    filesystem: Option<Filesystem>,
     ui: Option<Ui>,
     video: Option<Video>,
 
@@ -2429,6 +2954,23 @@ pub struct PlayerBuilder {
     #[cfg(feature = "known_stubs")]
     stub_report_output: Option<std::path::PathBuf>,
     avm2_optimizer_enabled: bool,
+    avm2_strict_verification: bool,
+    log_frame_events: bool,
+    prefetch_policy: PrefetchPolicy,
+    detect_sitelocks: bool,
+    count_opcodes: bool,
+    fast_forward_audio: FastForwardAudio,
+    idle_detection: bool,
+    partial_playback: bool,
+    random_seed: Option<u64>,
+    time_source: Option<Box<dyn TimeSource>>,
+    relaxed_security: bool,
+    texture_memory_budget: Option<usize>,
+    timer_budget: Duration,
+    spoofed_capabilities: SpoofedCapabilities,
+    morph_shape_cache_size: usize,
+    coalesce_mouse_moves: bool,
+    asset_cache_size: Option<usize>,
 }
 
 impl PlayerBuilder {
@@ -2446,6 +2988,7 @@ impl PlayerBuilder {
             navigator: None,
             renderer: None,
             storage: None,
+            filesystem: None,
             ui: None,
             video: None,
 
@@ -2480,6 +3023,23 @@ impl PlayerBuilder {
             #[cfg(feature = "known_stubs")]
             stub_report_output: None,
             avm2_optimizer_enabled: true,
+            avm2_strict_verification: false,
+            log_frame_events: false,
+            prefetch_policy: PrefetchPolicy::Disabled,
+            detect_sitelocks: false,
+            count_opcodes: false,
+            fast_forward_audio: FastForwardAudio::PreservePitch,
+            idle_detection: false,
+            partial_playback: false,
+            random_seed: None,
+            time_source: None,
+            relaxed_security: false,
+            texture_memory_budget: None,
+            timer_budget: Timers::DEFAULT_BUDGET,
+            spoofed_capabilities: SpoofedCapabilities::default(),
+            morph_shape_cache_size: MorphShape::DEFAULT_CACHE_SIZE,
+            coalesce_mouse_moves: false,
+            asset_cache_size: None,
         }
     }
 
@@ -2539,6 +3099,13 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the filesystem backend used by AIR content to access files.
+    #[inline]
+    pub fn with_filesystem(mut self, filesystem: Box<dyn FilesystemBackend>) -> Self {
+        self.filesystem = Some(filesystem);
+        self
+    }
+
     /// Sets the UI backend of the player.
     #[inline]
     pub fn with_ui(mut self, ui: impl 'static + UiBackend) -> Self {
@@ -2683,11 +3250,147 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets whether to collect what the movie does to find out where it is running,
+    /// to help spoofing the URL that sitelocked movies expect.
+    pub fn with_sitelock_detection(mut self, value: bool) -> Self {
+        self.detect_sitelocks = value;
+        self
+    }
+
+    /// Sets whether to count how many times each AVM1 action and AVM2 opcode runs,
+    /// to find out which ones matter the most for the performance of the movie.
+    pub fn with_opcode_counting(mut self, value: bool) -> Self {
+        self.count_opcodes = value;
+        self
+    }
+
+    /// Sets whether sounds are time-stretched or muted while the movie is fast-forwarded.
+    pub fn with_fast_forward_audio(mut self, fast_forward_audio: FastForwardAudio) -> Self {
+        self.fast_forward_audio = fast_forward_audio;
+        self
+    }
+
+    /// Sets whether to check if the movie is idle, so that the embedder can stop ticking it
+    /// until input arrives. See [`Player::is_idle`].
+    pub fn with_idle_detection(mut self, value: bool) -> Self {
+        self.idle_detection = value;
+        self
+    }
+
+    /// Sets whether to play truncated movies up to their last complete frame.
+    ///
+    /// Movies recovered from incomplete downloads often end in the middle of a frame. With
+    /// this, the tags of that frame are left out, and the timeline stops at the last complete
+    /// frame instead of looping. See [`Player::is_movie_shortened`].
+    pub fn with_partial_playback(mut self, value: bool) -> Self {
+        self.partial_playback = value;
+        self
+    }
+
+    /// Sets the seed of the random number generator used by `Math.random` and `random`.
+    ///
+    /// By default, the generator is seeded from the current time. A fixed seed makes
+    /// runs reproducible, e.g. for automated tests or replaying recorded input.
+    pub fn with_random_seed(mut self, seed: Option<u64>) -> Self {
+        self.random_seed = seed;
+        self
+    }
+
+    /// Sets where `getTimer` and `Date` read the time from.
+    ///
+    /// By default, this is the clocks of the system. A [`crate::time::SimulatedTimeSource`]
+    /// makes the times movies see reproducible, e.g. when replaying recorded input.
+    pub fn with_time_source(mut self, time_source: Box<dyn TimeSource>) -> Self {
+        self.time_source = Some(time_source);
+        self
+    }
+
     pub fn with_avm2_optimizer_enabled(mut self, value: bool) -> Self {
         self.avm2_optimizer_enabled = value;
         self
     }
 
+    /// Sets whether AVM2 methods are verified as soon as their ABC file is loaded.
+    /// This reports VerifyErrors in code that never runs, which Flash Player ignores,
+    /// so it's intended for developers validating their content.
+    pub fn with_avm2_strict_verification(mut self, value: bool) -> Self {
+        self.avm2_strict_verification = value;
+        self
+    }
+
+    /// Sets whether the name of each AVM2 frame event (`enterFrame`, `frameConstructed`,
+    /// `exitFrame` and `render`) is traced before it's broadcast.
+    /// This is used by tests that check the order of frame events.
+    pub fn with_frame_event_logging(mut self, value: bool) -> Self {
+        self.log_frame_events = value;
+        self
+    }
+
+    /// Sets which files referenced in the code of loaded movies are fetched in the background,
+    /// before the movies ask for them.
+    pub fn with_prefetch_policy(mut self, policy: PrefetchPolicy) -> Self {
+        self.prefetch_policy = policy;
+        self
+    }
+
+    /// Sets whether all movies may script each other, ignoring the security sandbox rules.
+    /// This is intended for testing local copies of content made of multiple SWFs.
+    pub fn with_relaxed_security(mut self, value: bool) -> Self {
+        self.relaxed_security = value;
+        self
+    }
+
+    /// Sets the capabilities to report to movies instead of Ruffle's own.
+    pub fn with_spoofed_capabilities(mut self, capabilities: SpoofedCapabilities) -> Self {
+        self.spoofed_capabilities = capabilities;
+        self
+    }
+
+    /// Sets the maximum GPU memory, in bytes, to use for the textures of bitmaps and shapes.
+    /// The least recently rendered textures are released when over budget,
+    /// and uploaded again when next used.
+    pub fn with_texture_memory_budget(mut self, budget: Option<usize>) -> Self {
+        self.texture_memory_budget = budget;
+        self
+    }
+
+    /// Sets whether mouse moves between two ticks are coalesced into the last one.
+    ///
+    /// Mice with high polling rates report moves much more often than movies run frames,
+    /// and handling each of them means picking the hovered object and running its scripts.
+    /// Buttons still see the position of every tick, so they rarely miss a rollover.
+    ///
+    /// This is off by default, so that every mouse move is handled on its own.
+    pub fn with_mouse_move_coalescing(mut self, enabled: bool) -> Self {
+        self.coalesce_mouse_moves = enabled;
+        self
+    }
+
+    /// Sets the maximum memory, in bytes, used to share decoded bitmaps and tessellated
+    /// shapes between identical movies, where 0 turns the sharing off.
+    ///
+    /// This cache is shared by every player in the process, so this also applies to
+    /// the players that were already built.
+    pub fn with_asset_cache_size(mut self, size: Option<usize>) -> Self {
+        self.asset_cache_size = size;
+        self
+    }
+
+    /// Sets how many intermediate frames each morph shape keeps tessellated.
+    /// Larger caches avoid tessellating shape tweens again, at the cost of memory.
+    pub fn with_morph_shape_cache_size(mut self, size: usize) -> Self {
+        self.morph_shape_cache_size = size;
+        self
+    }
+
+    /// Sets how long timer callbacks may run per update.
+    /// Once they are over budget, the remaining timers are postponed to the next update,
+    /// and intervals skip the ticks they missed.
+    pub fn with_timer_budget(mut self, budget: Duration) -> Self {
+        self.timer_budget = budget;
+        self
+    }
+
     fn create_gc_root<'gc>(
         gc_context: &'gc Mutation<'gc>,
         player_version: u8,
@@ -2696,6 +3399,8 @@ impl PlayerBuilder {
         fake_movie: Arc<SwfMovie>,
         external_interface_providers: Vec<Box<dyn ExternalInterfaceProvider>>,
         fs_command_provider: Box<dyn FsCommandProvider>,
+        texture_memory_budget: Option<usize>,
+        timer_budget: Duration,
     ) -> GcRoot<'gc> {
         let mut interner = AvmStringInterner::new(gc_context);
         let (avm1, avm2) = {
@@ -2732,7 +3437,11 @@ impl PlayerBuilder {
             avm1_shared_objects: HashMap::new(),
             avm2_shared_objects: HashMap::new(),
             stage: Stage::empty(gc_context, fullscreen, fake_movie),
-            timers: Timers::new(),
+            timers: {
+                let mut timers = Timers::new();
+                timers.set_budget(timer_budget);
+                timers
+            },
             unbound_text_fields: Vec::new(),
             stream_manager: StreamManager::new(),
             sockets: Sockets::empty(),
@@ -2740,6 +3449,8 @@ impl PlayerBuilder {
             local_connections: LocalConnections::empty(),
             dynamic_root: DynamicRootSet::new(gc_context),
             post_frame_callbacks: Vec::new(),
+            texture_budget: TextureBudget::new(gc_context, texture_memory_budget),
+            glyph_cache: GlyphCache::new(gc_context),
         };
 
         GcRoot {
@@ -2752,6 +3463,9 @@ impl PlayerBuilder {
     pub fn build(self) -> Arc<Mutex<Player>> {
         use crate::backend::*;
         use ruffle_video::null;
+        if let Some(size) = self.asset_cache_size {
+            asset_cache::set_max_size(size);
+        }
         let audio = self
             .audio
             .unwrap_or_else(|| Box::new(audio::NullAudioBackend::new()));
@@ -2771,6 +3485,9 @@ impl PlayerBuilder {
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
+        let filesystem = self
+            .filesystem
+            .unwrap_or_else(|| Box::new(filesystem::NullFilesystemBackend::new()));
         let ui = self
             .ui
             .unwrap_or_else(|| Box::new(ui::NullUiBackend::new()));
@@ -2779,6 +3496,12 @@ impl PlayerBuilder {
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
 
         let player_version = self.player_version.unwrap_or(NEWEST_PLAYER_VERSION);
+        let time_source = self
+            .time_source
+            .unwrap_or_else(|| Box::new(SystemTimeSource::new()));
+        let random_seed = self
+            .random_seed
+            .unwrap_or_else(|| time_source.now().timestamp_millis() as u64);
 
         // Instantiate the player.
         let fake_movie = Arc::new(SwfMovie::empty(player_version));
@@ -2792,12 +3515,15 @@ impl PlayerBuilder {
                 navigator,
                 renderer,
                 storage,
+                filesystem,
                 ui,
                 video,
 
                 // SWF info
                 swf: fake_movie.clone(),
                 current_frame: None,
+                partial_playback: self.partial_playback,
+                movie_shortened: false,
 
                 // Timing
                 frame_rate,
@@ -2805,9 +3531,16 @@ impl PlayerBuilder {
                 frame_phase: Default::default(),
                 frame_accumulator: 0.0,
                 recent_run_frame_timings: VecDeque::with_capacity(10),
-                start_time: Instant::now(),
+                speed_multiplier: 1.0,
+                fast_forward_audio: self.fast_forward_audio,
+                idle_detection: self.idle_detection,
+                is_idle: false,
+                frames_run: 0,
+                time_source,
                 time_offset: 0,
                 time_til_next_timer: None,
+                load_clock: 0.0,
+                load_wakers: Vec::new(),
                 max_execution_duration: self.max_execution_duration,
                 actions_since_timeout_check: 0,
 
@@ -2815,12 +3548,14 @@ impl PlayerBuilder {
                 input: InputManager::new(self.gamepad_button_mapping),
                 mouse_in_stage: true,
                 mouse_position: Point::ZERO,
+                coalesce_mouse_moves: self.coalesce_mouse_moves,
+                pending_mouse_move: None,
                 mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
 
                 // Misc. state
-                rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
-                system: SystemProperties::new(),
+                rng: SmallRng::seed_from_u64(random_seed),
+                system: SystemProperties::new(self.spoofed_capabilities.clone()),
                 page_url: self.page_url.clone(),
                 transform_stack: TransformStack::new(),
                 instance_counter: 0,
@@ -2833,6 +3568,12 @@ impl PlayerBuilder {
                 spoofed_url: self.spoofed_url.clone(),
                 compatibility_rules: self.compatibility_rules.clone(),
                 stub_tracker: StubCollection::new(),
+                sitelock_detector: SitelockDetector::new(self.detect_sitelocks),
+                opcode_counters: OpcodeCounters::new(self.count_opcodes),
+                frame_hooks: FrameHooks::default(),
+                domain_permissions: DomainPermissions::new(self.relaxed_security),
+                policy_files: PolicyFiles::default(),
+                prefetch_policy: self.prefetch_policy,
                 #[cfg(feature = "egui")]
                 debug_ui: Default::default(),
 
@@ -2846,6 +3587,8 @@ impl PlayerBuilder {
                         fake_movie.clone(),
                         self.external_interface_providers,
                         self.fs_command_provider,
+                        self.texture_memory_budget,
+                        self.timer_budget,
                     )
                 }))),
             })
@@ -2885,6 +3628,13 @@ impl PlayerBuilder {
             context
                 .avm2
                 .set_optimizer_enabled(self.avm2_optimizer_enabled);
+            context
+                .avm2
+                .set_strict_verification(self.avm2_strict_verification);
+            context.avm2.set_log_frame_events(self.log_frame_events);
+            context
+                .library
+                .set_morph_shape_cache_size(self.morph_shape_cache_size);
             Avm2::load_player_globals(context).expect("Unable to load AVM2 globals");
 
             let stage = context.stage;
@@ -2972,6 +3722,82 @@ fn run_mouse_pick<'gc>(
     })
 }
 
+/// Finds the deepest display object under the mouse cursor, used as the drop target of a drag.
+///
+/// Unlike mouse picking, this also returns non-interactive objects such as shapes,
+/// and never returns the dragged object or any of its children.
+fn run_drop_target_pick<'gc>(
+    context: &mut UpdateContext<'gc>,
+    dragged: DisplayObject<'gc>,
+) -> Option<DisplayObject<'gc>> {
+    let point = *context.mouse_position;
+    context
+        .stage
+        .iter_render_list()
+        .rev()
+        .find_map(|level| drop_target_pick(context, level, dragged, point))
+}
+
+fn drop_target_pick<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: DisplayObject<'gc>,
+    dragged: DisplayObject<'gc>,
+    point: Point<Twips>,
+) -> Option<DisplayObject<'gc>> {
+    if DisplayObject::ptr_eq(object, dragged) || !object.visible() || object.maskee().is_some() {
+        return None;
+    }
+
+    if let Some(masker) = object.masker() {
+        if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+            return None;
+        }
+    }
+
+    let Some(container) = object.as_container() else {
+        return object
+            .hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
+            .then_some(object);
+    };
+
+    if !object.world_bounds().contains(point) {
+        return None;
+    }
+
+    // Children are tested bottom-to-top so that clip layers are applied in order;
+    // the last hit is the top-most one.
+    let mut clip_depth = 0;
+    let mut result = None;
+    for child in container.iter_render_list() {
+        if child.clip_depth() > 0 {
+            if child.hit_test_shape(
+                context,
+                point,
+                HitTestOptions::SKIP_MASK | HitTestOptions::SKIP_INVISIBLE,
+            ) {
+                clip_depth = 0;
+            } else {
+                clip_depth = child.clip_depth();
+            }
+        } else if child.depth() >= clip_depth {
+            if let Some(hit) = drop_target_pick(context, child, dragged, point) {
+                result = Some(hit);
+            }
+        }
+    }
+    if result.is_some() {
+        return result;
+    }
+
+    // Fall back to the container's own vector art, if any.
+    let local_matrix = object.global_to_local_matrix()?;
+    let movie_clip = object.as_movie_clip()?;
+    let hit = movie_clip
+        .drawing()
+        .is_some_and(|drawing| drawing.hit_test(local_matrix * point, &local_matrix));
+    hit.then_some(object)
+}
+
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
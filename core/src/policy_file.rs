@@ -0,0 +1,406 @@
+//! Cross-domain policy files, as loaded by `Security.loadPolicyFile`.
+//!
+//! Policy files are served over HTTP, or over a raw socket in response to a
+//! `<policy-file-request/>` when loaded from an `xmlsocket://host:port` URL.
+//! Socket policy files let a server opt in to connections on its other ports,
+//! which spares the user from confirming each of those connections.
+
+use crate::backend::navigator::{NavigatorBackend, OwnedFuture, Request};
+use crate::context::UpdateContext;
+use crate::loader::Error;
+use crate::socket::{sanitize_host, ConnectionState, SocketAction, SocketHandle};
+use crate::tag_utils::SwfMovie;
+use async_channel::unbounded;
+use fnv::FnvHashMap;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+use url::Url;
+
+/// How long to wait for a socket policy server to respond.
+const SOCKET_POLICY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Ports below this may only be granted by a policy served from a port below it too.
+const FIRST_UNPRIVILEGED_PORT: u16 = 1024;
+
+/// One `<allow-access-from>` rule of a policy file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AllowAccessFrom {
+    /// A host name, a wildcard such as `*.example.com`, or `*` for any domain.
+    domain: String,
+
+    /// The ports a socket policy grants access to. HTTP policies have none.
+    to_ports: Vec<RangeInclusive<u16>>,
+}
+
+/// A parsed cross-domain policy file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyFile {
+    rules: Vec<AllowAccessFrom>,
+}
+
+impl PolicyFile {
+    /// Parses a policy file, or returns `None` if it isn't well-formed.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        // Socket policy servers terminate their response with a null byte.
+        let data = data.split(|b| *b == 0).next().unwrap_or_default();
+
+        let mut reader = Reader::from_reader(data);
+        let mut rules = Vec::new();
+        let mut is_policy = false;
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let attribute = |name: &[u8]| {
+                        e.attributes().with_checks(false).find_map(|attribute| {
+                            let attribute = attribute.ok()?;
+                            (attribute.key.into_inner() == name)
+                                .then(|| String::from_utf8_lossy(&attribute.value).into_owned())
+                        })
+                    };
+                    match e.name().into_inner() {
+                        b"cross-domain-policy" => is_policy = true,
+                        b"site-control" => {
+                            // A master policy can forbid all other policies on the server,
+                            // including itself.
+                            if attribute(b"permitted-cross-domain-policies").as_deref()
+                                == Some("none")
+                            {
+                                return Some(Self::default());
+                            }
+                        }
+                        b"allow-access-from" => {
+                            let Some(domain) = attribute(b"domain") else {
+                                continue;
+                            };
+                            let to_ports = attribute(b"to-ports")
+                                .map(|ports| parse_ports(&ports))
+                                .unwrap_or_default();
+                            rules.push(AllowAccessFrom {
+                                domain: domain.trim().to_ascii_lowercase(),
+                                to_ports,
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("Error while parsing policy file: {e}");
+                    return None;
+                }
+            }
+        }
+
+        is_policy.then_some(Self { rules })
+    }
+
+    /// Whether this policy lets movies served from `domain` connect to `port`.
+    ///
+    /// `domain` is `None` for local movies, which only a `*` rule allows.
+    pub fn allows_socket(&self, domain: Option<&str>, port: u16) -> bool {
+        self.rules.iter().any(|rule| {
+            domain_matches(&rule.domain, domain)
+                && rule.to_ports.iter().any(|ports| ports.contains(&port))
+        })
+    }
+}
+
+/// Parses a `to-ports` attribute, such as `*`, `507` or `507,516-523`.
+fn parse_ports(ports: &str) -> Vec<RangeInclusive<u16>> {
+    ports
+        .split(',')
+        .filter_map(|ports| {
+            let ports = ports.trim();
+            if ports == "*" {
+                return Some(0..=u16::MAX);
+            }
+            match ports.split_once('-') {
+                Some((start, end)) => Some(start.trim().parse().ok()?..=end.trim().parse().ok()?),
+                None => {
+                    let port = ports.parse().ok()?;
+                    Some(port..=port)
+                }
+            }
+        })
+        .collect()
+}
+
+fn domain_matches(pattern: &str, domain: Option<&str>) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    let Some(domain) = domain else {
+        return false;
+    };
+    let domain = domain.to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => domain == suffix || domain.ends_with(&format!(".{suffix}")),
+        None => domain == pattern,
+    }
+}
+
+enum PolicyState {
+    Loading,
+    Loaded(PolicyFile),
+    Failed,
+}
+
+/// The socket policy server a policy was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SocketPolicySource {
+    host: String,
+    port: u16,
+}
+
+/// All policy files loaded by movies, keyed by URL.
+///
+/// Every policy file is only ever loaded once.
+#[derive(Default)]
+pub struct PolicyFiles {
+    http: FnvHashMap<String, PolicyState>,
+    sockets: FnvHashMap<SocketPolicySource, PolicyState>,
+}
+
+impl PolicyFiles {
+    /// Whether a loaded socket policy lets `movie` connect to `host` on `port`.
+    pub fn allows_socket(&self, movie: &SwfMovie, host: &str, port: u16) -> bool {
+        let movie_url = Url::parse(movie.url()).ok();
+        let domain = movie_url
+            .as_ref()
+            .filter(|url| url.scheme() != "file")
+            .and_then(|url| url.host_str());
+        self.sockets.iter().any(|(source, state)| {
+            let PolicyState::Loaded(policy) = state else {
+                return false;
+            };
+            source.host.eq_ignore_ascii_case(host)
+                && (port >= FIRST_UNPRIVILEGED_PORT || source.port < FIRST_UNPRIVILEGED_PORT)
+                && policy.allows_socket(domain, port)
+        })
+    }
+
+    /// Lets the navigator skip asking the user before `movie` connects to
+    /// `host` on `port`, if a socket policy served by `host` allows it.
+    pub fn grant_socket(
+        &self,
+        navigator: &mut dyn NavigatorBackend,
+        movie: &SwfMovie,
+        host: &str,
+        port: u16,
+    ) {
+        let host = sanitize_host(host);
+        if self.allows_socket(movie, host, port) {
+            navigator.allow_socket_by_policy(host, port);
+        }
+    }
+
+    fn finish_http(&mut self, url: String, policy: Option<PolicyFile>) {
+        self.http.insert(url, PolicyState::from(policy));
+    }
+
+    fn finish_socket(&mut self, source: SocketPolicySource, policy: Option<PolicyFile>) {
+        self.sockets.insert(source, PolicyState::from(policy));
+    }
+}
+
+impl From<Option<PolicyFile>> for PolicyState {
+    fn from(policy: Option<PolicyFile>) -> Self {
+        match policy {
+            Some(policy) => Self::Loaded(policy),
+            None => Self::Failed,
+        }
+    }
+}
+
+/// Starts loading the policy file at `url`, unless it was already requested.
+///
+/// `url` is either an HTTP(S) URL, or an `xmlsocket://host:port` URL.
+pub fn load_policy_file(context: &mut UpdateContext<'_>, url: &str) {
+    if let Some(address) = url.strip_prefix("xmlsocket://") {
+        let Some((host, port)) = address
+            .trim_end_matches('/')
+            .rsplit_once(':')
+            .and_then(|(host, port)| Some((host, port.parse::<u16>().ok()?)))
+        else {
+            tracing::warn!("Security.loadPolicyFile: Invalid socket policy URL {url}");
+            return;
+        };
+        let source = SocketPolicySource {
+            host: sanitize_host(host).to_ascii_lowercase(),
+            port,
+        };
+        if context.policy_files.sockets.contains_key(&source) {
+            return;
+        }
+        context
+            .policy_files
+            .sockets
+            .insert(source.clone(), PolicyState::Loading);
+        let future = load_socket_policy(context, source);
+        context.navigator.spawn_future(future);
+    } else {
+        let url = match context.navigator.resolve_url(url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => url.to_string(),
+            _ => {
+                tracing::warn!("Security.loadPolicyFile: Unsupported policy URL {url}");
+                return;
+            }
+        };
+        if context.policy_files.http.contains_key(&url) {
+            return;
+        }
+        context
+            .policy_files
+            .http
+            .insert(url.clone(), PolicyState::Loading);
+        let future = load_http_policy(context, url);
+        context.navigator.spawn_future(future);
+    }
+}
+
+fn load_http_policy(context: &mut UpdateContext<'_>, url: String) -> OwnedFuture<(), Error> {
+    let player = context
+        .player
+        .upgrade()
+        .expect("Could not upgrade weak reference to player");
+
+    Box::pin(async move {
+        let fetch = player
+            .lock()
+            .unwrap()
+            .navigator()
+            .fetch(Request::get(url.clone()));
+        let policy = match fetch.await {
+            Ok(response) => response
+                .body()
+                .await
+                .ok()
+                .and_then(|body| PolicyFile::parse(&body)),
+            Err(_) => None,
+        };
+        if policy.is_none() {
+            tracing::warn!("Failed to load policy file from {url}");
+        }
+
+        player
+            .lock()
+            .unwrap()
+            .update(|uc| uc.policy_files.finish_http(url, policy));
+        Ok(())
+    })
+}
+
+fn load_socket_policy(
+    context: &mut UpdateContext<'_>,
+    source: SocketPolicySource,
+) -> OwnedFuture<(), Error> {
+    let player = context
+        .player
+        .upgrade()
+        .expect("Could not upgrade weak reference to player");
+
+    Box::pin(async move {
+        let (request_sender, request_receiver) = unbounded();
+        let (action_sender, action_receiver) = unbounded();
+        player.lock().unwrap().navigator_mut().connect_socket(
+            source.host.clone(),
+            source.port,
+            SOCKET_POLICY_TIMEOUT,
+            SocketHandle::default(),
+            request_receiver,
+            action_sender,
+        );
+
+        let mut response = Vec::new();
+        while let Ok(action) = action_receiver.recv().await {
+            match action {
+                SocketAction::Connect(_, ConnectionState::Connected) => {
+                    let _ = request_sender
+                        .send(b"<policy-file-request/>\0".to_vec())
+                        .await;
+                }
+                SocketAction::Connect(_, _) | SocketAction::Close(_) => break,
+                SocketAction::Data(_, data) => {
+                    response.extend(data);
+                    if response.contains(&0) {
+                        break;
+                    }
+                }
+            }
+        }
+        // Dropping the request channel closes the connection.
+        drop(request_sender);
+
+        let policy = PolicyFile::parse(&response);
+        if policy.is_none() {
+            tracing::warn!(
+                "Failed to load socket policy file from {}:{}",
+                source.host,
+                source.port
+            );
+        }
+
+        player
+            .lock()
+            .unwrap()
+            .update(|uc| uc.policy_files.finish_socket(source, policy));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_socket_policy() {
+        let policy = PolicyFile::parse(
+            br#"<?xml version="1.0"?>
+<!DOCTYPE cross-domain-policy SYSTEM "/xml/dtds/cross-domain-policy.dtd">
+<cross-domain-policy>
+    <site-control permitted-cross-domain-policies="master-only"/>
+    <allow-access-from domain="*.example.com" to-ports="507,5000-5010" />
+    <allow-access-from domain="games.net" to-ports="*" />
+</cross-domain-policy>
+"#,
+        )
+        .expect("policy should parse");
+
+        assert!(policy.allows_socket(Some("www.example.com"), 507));
+        assert!(policy.allows_socket(Some("example.com"), 5005));
+        assert!(!policy.allows_socket(Some("example.com"), 5011));
+        assert!(!policy.allows_socket(Some("notexample.com"), 507));
+        assert!(policy.allows_socket(Some("GAMES.net"), 1));
+        assert!(!policy.allows_socket(None, 507));
+    }
+
+    #[test]
+    fn http_policies_grant_no_ports() {
+        let policy = PolicyFile::parse(
+            br#"<cross-domain-policy><allow-access-from domain="*"/></cross-domain-policy>"#,
+        )
+        .expect("policy should parse");
+        assert!(!policy.allows_socket(Some("example.com"), 80));
+    }
+
+    #[test]
+    fn site_control_none_grants_nothing() {
+        let policy = PolicyFile::parse(
+            b"<cross-domain-policy>\
+                <site-control permitted-cross-domain-policies=\"none\"/>\
+                <allow-access-from domain=\"*\" to-ports=\"*\"/>\
+            </cross-domain-policy>\0trailing",
+        )
+        .expect("policy should parse");
+        assert!(!policy.allows_socket(None, 1234));
+    }
+
+    #[test]
+    fn rejects_other_documents() {
+        assert_eq!(PolicyFile::parse(b"<html></html>"), None);
+        assert_eq!(PolicyFile::parse(b""), None);
+    }
+}
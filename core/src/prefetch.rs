@@ -0,0 +1,226 @@
+//! Prefetching of the external files a movie is likely to load.
+//!
+//! Many movies load their levels, pictures and music with `loadMovie` or `Loader` as they go,
+//! and stop until the file arrives. The URLs of these files are usually written as constants
+//! in the code of the movie, so we can find them as soon as the movie is loaded and ask the
+//! navigator to fetch them in the background.
+//!
+//! This is a heuristic: any string in the code that looks like the URL of an asset is
+//! prefetched, whether the movie ends up loading it or not.
+
+use crate::backend::navigator::url_from_relative_url;
+use crate::context::UpdateContext;
+use crate::tag_utils::{self, ControlFlow, SwfMovie, SwfSlice, SwfStream};
+use std::sync::Arc;
+use swf::avm1::types::{Action, Value};
+use swf::extensions::ReadSwfExt;
+use swf::{Encoding, TagCode};
+use url::Url;
+
+/// Which of the files referenced by a movie are prefetched.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrefetchPolicy {
+    /// Nothing is prefetched.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "disabled"))]
+    Disabled,
+
+    /// Only files on the same server as the movie are prefetched.
+    #[cfg_attr(feature = "serde", serde(rename = "same-origin"))]
+    SameOrigin,
+
+    /// Files are prefetched wherever they are.
+    #[cfg_attr(feature = "serde", serde(rename = "any"))]
+    Any,
+}
+
+/// The most files prefetched for a single movie.
+const MAX_PREFETCHES_PER_MOVIE: usize = 32;
+
+/// The extensions of the files movies load with `loadMovie`, `Loader` and `Sound.loadSound`.
+const ASSET_EXTENSIONS: &[&str] = &["swf", "jpg", "jpeg", "png", "gif", "mp3"];
+
+/// Asks the navigator to prefetch the files `movie` is likely to load, as allowed by the
+/// prefetch policy of the player.
+pub fn prefetch_assets(context: &mut UpdateContext<'_>, movie: &Arc<SwfMovie>) {
+    if context.prefetch_policy == PrefetchPolicy::Disabled {
+        return;
+    }
+
+    let urls = find_asset_urls(movie, context.prefetch_policy);
+    if !urls.is_empty() {
+        tracing::debug!("Prefetching {} files for {}", urls.len(), movie.url());
+    }
+    for url in urls {
+        context.navigator.prefetch(url);
+    }
+}
+
+/// Finds the URLs of the files referenced in the code of `movie`, resolved against its URL.
+pub fn find_asset_urls(movie: &Arc<SwfMovie>, policy: PrefetchPolicy) -> Vec<Url> {
+    let Ok(base) = Url::parse(movie.url()) else {
+        return Vec::new();
+    };
+
+    let mut strings = Vec::new();
+    let slice = SwfSlice::from(movie.clone());
+    let mut reader = slice.read_from(0);
+    find_strings(&mut reader, &mut strings);
+
+    let mut urls: Vec<Url> = Vec::new();
+    for string in strings {
+        if urls.len() >= MAX_PREFETCHES_PER_MOVIE {
+            break;
+        }
+        if let Some(url) = resolve_asset_url(&base, &string, policy) {
+            if url != base && !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+    }
+    urls
+}
+
+/// Collects the string constants of all AVM1 and AVM2 code in the given tags.
+fn find_strings(reader: &mut SwfStream<'_>, strings: &mut Vec<String>) {
+    let encoding = reader.encoding();
+    let version = reader.version();
+    let _ = tag_utils::decode_tags(reader, |reader, tag_code, _tag_len| {
+        match tag_code {
+            TagCode::DoAction => find_avm1_strings(reader.get_ref(), version, encoding, strings),
+            TagCode::DoInitAction => {
+                reader.read_u16()?;
+                find_avm1_strings(reader.get_ref(), version, encoding, strings);
+            }
+            TagCode::DoAbc => find_avm2_strings(reader.get_ref(), strings),
+            TagCode::DoAbc2 => find_avm2_strings(reader.read_do_abc_2()?.data, strings),
+            TagCode::DefineSprite => {
+                // The sprite ID and frame count come before the tags of the sprite.
+                reader.read_u16()?;
+                reader.read_u16()?;
+                find_strings(reader, strings);
+            }
+            _ => {}
+        }
+        Ok(ControlFlow::Continue)
+    });
+}
+
+fn find_avm1_strings(
+    actions: &[u8],
+    version: u8,
+    encoding: &'static Encoding,
+    strings: &mut Vec<String>,
+) {
+    let mut reader = swf::avm1::read::Reader::new(actions, version);
+    while !reader.get_ref().is_empty() {
+        let Ok(action) = reader.read_action() else {
+            break;
+        };
+        match action {
+            Action::ConstantPool(pool) => strings.extend(
+                pool.strings
+                    .iter()
+                    .map(|string| string.to_str_lossy(encoding).into_owned()),
+            ),
+            Action::Push(push) => {
+                for value in push.values {
+                    if let Value::Str(string) = value {
+                        strings.push(string.to_str_lossy(encoding).into_owned());
+                    }
+                }
+            }
+            Action::DefineFunction(function) => {
+                find_avm1_strings(function.actions, version, encoding, strings)
+            }
+            Action::DefineFunction2(function) => {
+                find_avm1_strings(function.actions, version, encoding, strings)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn find_avm2_strings(abc: &[u8], strings: &mut Vec<String>) {
+    if let Ok(abc) = swf::avm2::read::Reader::new(abc).read() {
+        strings.extend(
+            abc.constant_pool
+                .strings
+                .iter()
+                .map(|string| String::from_utf8_lossy(string).into_owned()),
+        );
+    }
+}
+
+/// Resolves a string found in the code of a movie, if it looks like the URL of an asset
+/// that the policy allows prefetching.
+fn resolve_asset_url(base: &Url, string: &str, policy: PrefetchPolicy) -> Option<Url> {
+    if string.len() > 2048 || string.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let path = string.split(['?', '#']).next().unwrap_or_default();
+    let (_, extension) = path.rsplit_once('.')?;
+    if !ASSET_EXTENSIONS
+        .iter()
+        .any(|asset_extension| extension.eq_ignore_ascii_case(asset_extension))
+    {
+        return None;
+    }
+
+    let url = url_from_relative_url(base.as_str(), string).ok()?;
+    if !matches!(url.scheme(), "http" | "https" | "file") {
+        return None;
+    }
+    match policy {
+        PrefetchPolicy::Disabled => None,
+        PrefetchPolicy::SameOrigin
+            if url.scheme() != base.scheme()
+                || url.host() != base.host()
+                || url.port_or_known_default() != base.port_or_known_default() =>
+        {
+            None
+        }
+        PrefetchPolicy::SameOrigin | PrefetchPolicy::Any => Some(url),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(string: &str, policy: PrefetchPolicy) -> Option<String> {
+        let base = Url::parse("https://example.com/games/main.swf").unwrap();
+        resolve_asset_url(&base, string, policy).map(String::from)
+    }
+
+    #[test]
+    fn asset_urls_are_resolved_against_the_movie() {
+        assert_eq!(
+            resolve("levels/level1.swf", PrefetchPolicy::SameOrigin).as_deref(),
+            Some("https://example.com/games/levels/level1.swf")
+        );
+        assert_eq!(
+            resolve("/music/Theme.MP3?v=2", PrefetchPolicy::SameOrigin).as_deref(),
+            Some("https://example.com/music/Theme.MP3?v=2")
+        );
+    }
+
+    #[test]
+    fn other_strings_are_not_asset_urls() {
+        assert_eq!(resolve("onRelease", PrefetchPolicy::Any), None);
+        assert_eq!(resolve("data.xml", PrefetchPolicy::Any), None);
+        assert_eq!(resolve("Press start.swf", PrefetchPolicy::Any), None);
+        assert_eq!(resolve("folder.swf/readme", PrefetchPolicy::Any), None);
+    }
+
+    #[test]
+    fn policy_limits_origins() {
+        let url = "http://cdn.example.net/intro.swf";
+        assert_eq!(resolve(url, PrefetchPolicy::SameOrigin), None);
+        assert_eq!(resolve(url, PrefetchPolicy::Any).as_deref(), Some(url));
+        assert_eq!(resolve("intro.swf", PrefetchPolicy::Disabled), None);
+    }
+}
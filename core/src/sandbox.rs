@@ -1,6 +1,8 @@
 //! Security Sandbox implementation, see
 //! https://help.adobe.com/en_US/as3/dev/WS5b3ccc516d4fbf351e63e3d118a9b90204-7e3f.html
 
+use crate::tag_utils::SwfMovie;
+use fnv::FnvHashMap;
 use swf::HeaderExt;
 use url::Url;
 
@@ -61,3 +63,206 @@ impl SandboxType {
         }
     }
 }
+
+/// A domain granted access through `Security.allowDomain` or `Security.allowInsecureDomain`.
+#[derive(Debug, Clone)]
+struct DomainGrant {
+    /// A host name, IP address, or `*` for any domain.
+    domain: String,
+
+    /// Whether movies served over insecure protocols are allowed to script
+    /// a movie served over HTTPS.
+    insecure: bool,
+}
+
+/// Tracks which movies are allowed to script each other, following the
+/// rules of Flash Player's security sandboxes.
+///
+/// Movies are identified by their URL, so every copy of a loaded movie
+/// shares the permissions it has granted.
+#[derive(Debug, Default)]
+pub struct DomainPermissions {
+    /// When set, every movie may script every other movie.
+    ///
+    /// This is useful for testing local copies of multi-SWF content.
+    relaxed: bool,
+
+    /// The domains each movie has allowed to script it, keyed by movie URL.
+    grants: FnvHashMap<String, Vec<DomainGrant>>,
+
+    /// Movies loaded into the security domain of the movie that loaded them,
+    /// keyed by movie URL.
+    imported: FnvHashMap<String, (String, SandboxType)>,
+}
+
+impl DomainPermissions {
+    pub fn new(relaxed: bool) -> Self {
+        Self {
+            relaxed,
+            ..Default::default()
+        }
+    }
+
+    /// Allows movies from `domain` to script `movie`.
+    ///
+    /// `domain` may be a host name, an IP address, a URL or `*`.
+    pub fn allow_domain(&mut self, movie: &SwfMovie, domain: &str, insecure: bool) {
+        let domain = domain.trim();
+        let domain = Url::parse(domain)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| domain.to_ascii_lowercase());
+        if domain.is_empty() {
+            return;
+        }
+        self.grants
+            .entry(movie.url().to_string())
+            .or_default()
+            .push(DomainGrant { domain, insecure });
+    }
+
+    /// Places the movie at `url` into the security domain of `importer`,
+    /// as done by `LoaderContext.securityDomain`.
+    pub fn import_into_security_domain(&mut self, url: String, importer: &SwfMovie) {
+        let (importer_url, importer_sandbox) = self.effective_origin(importer);
+        self.imported
+            .insert(url, (importer_url.to_string(), importer_sandbox));
+    }
+
+    /// The URL and sandbox that determine the security domain of a movie.
+    fn effective_origin<'a>(&'a self, movie: &'a SwfMovie) -> (&'a str, SandboxType) {
+        match self.imported.get(movie.url()) {
+            Some((url, sandbox_type)) => (url, *sandbox_type),
+            None => (movie.url(), movie.sandbox_type()),
+        }
+    }
+
+    /// Whether both movies belong to the same security domain.
+    pub fn same_domain(&self, a: &SwfMovie, b: &SwfMovie) -> bool {
+        let (a_url, a_sandbox) = self.effective_origin(a);
+        let (b_url, b_sandbox) = self.effective_origin(b);
+        if a_sandbox != b_sandbox {
+            return false;
+        }
+        if a_sandbox != SandboxType::Remote {
+            // All local movies in the same sandbox share a domain.
+            return true;
+        }
+        match (Url::parse(a_url), Url::parse(b_url)) {
+            (Ok(a), Ok(b)) => a.origin() == b.origin(),
+            _ => a_url == b_url,
+        }
+    }
+
+    /// Whether code from `accessor` may access the code and display list of `target`.
+    pub fn can_script(&self, accessor: &SwfMovie, target: &SwfMovie) -> bool {
+        if self.relaxed || self.same_domain(accessor, target) {
+            return true;
+        }
+
+        let (accessor_url, accessor_sandbox) = self.effective_origin(accessor);
+        let (target_url, target_sandbox) = self.effective_origin(target);
+        match (accessor_sandbox, target_sandbox) {
+            // Trusted local content may script any other local content.
+            (SandboxType::LocalTrusted | SandboxType::Application, target)
+                if target != SandboxType::Remote =>
+            {
+                true
+            }
+            // Local movies with file access can never script remote content.
+            (SandboxType::LocalWithFile, SandboxType::Remote) => false,
+            (SandboxType::Remote, SandboxType::Remote) => {
+                let accessor_url = Url::parse(accessor_url).ok();
+                let accessor_host = accessor_url.as_ref().and_then(|url| url.host_str());
+                let accessor_secure = accessor_url.as_ref().map(Url::scheme) == Some("https");
+                let target_secure = target_url.starts_with("https:");
+                self.is_granted(target, |grant| {
+                    (grant.domain == "*" || Some(grant.domain.as_str()) == accessor_host)
+                        && (grant.insecure || accessor_secure || !target_secure)
+                })
+            }
+            // Across sandbox types, only a wildcard grant gives access.
+            _ => self.is_granted(target, |grant| grant.domain == "*"),
+        }
+    }
+
+    fn is_granted(&self, target: &SwfMovie, predicate: impl Fn(&DomainGrant) -> bool) -> bool {
+        self.grants
+            .get(target.url())
+            .is_some_and(|grants| grants.iter().any(predicate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(url: &str) -> SwfMovie {
+        SwfMovie::error_movie(url.to_string())
+    }
+
+    #[test]
+    fn same_origin_movies_can_script_each_other() {
+        let permissions = DomainPermissions::default();
+        let a = movie("https://example.com/a.swf");
+        let b = movie("https://example.com/b/b.swf");
+        assert!(permissions.can_script(&a, &b));
+        assert!(permissions.can_script(&b, &a));
+    }
+
+    #[test]
+    fn allow_domain_grants_access() {
+        let mut permissions = DomainPermissions::default();
+        let portal = movie("https://portal.com/portal.swf");
+        let game = movie("https://games.com/game.swf");
+        assert!(!permissions.can_script(&portal, &game));
+
+        permissions.allow_domain(&game, "portal.com", false);
+        assert!(permissions.can_script(&portal, &game));
+        assert!(!permissions.can_script(&game, &portal));
+
+        permissions.allow_domain(&portal, "*", false);
+        assert!(permissions.can_script(&game, &portal));
+    }
+
+    #[test]
+    fn insecure_access_requires_allow_insecure_domain() {
+        let mut permissions = DomainPermissions::default();
+        let insecure = movie("http://portal.com/portal.swf");
+        let secure = movie("https://games.com/game.swf");
+
+        permissions.allow_domain(&secure, "http://portal.com/", false);
+        assert!(!permissions.can_script(&insecure, &secure));
+
+        permissions.allow_domain(&secure, "portal.com", true);
+        assert!(permissions.can_script(&insecure, &secure));
+    }
+
+    #[test]
+    fn local_with_file_cannot_script_remote() {
+        let mut permissions = DomainPermissions::default();
+        let local = movie("file:///game/main.swf");
+        let remote = movie("https://games.com/game.swf");
+        permissions.allow_domain(&remote, "*", false);
+        assert!(!permissions.can_script(&local, &remote));
+    }
+
+    #[test]
+    fn imported_movies_share_the_importer_domain() {
+        let mut permissions = DomainPermissions::default();
+        let portal = movie("https://portal.com/portal.swf");
+        let game = movie("https://games.com/game.swf");
+        permissions.import_into_security_domain(game.url().to_string(), &portal);
+        assert!(permissions.same_domain(&portal, &game));
+        assert!(permissions.can_script(&game, &portal));
+    }
+
+    #[test]
+    fn relaxed_permissions_allow_everything() {
+        let permissions = DomainPermissions::new(true);
+        let local = movie("file:///game/main.swf");
+        let remote = movie("https://games.com/game.swf");
+        assert!(permissions.can_script(&local, &remote));
+        assert!(permissions.can_script(&remote, &local));
+    }
+}
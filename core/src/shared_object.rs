@@ -0,0 +1,126 @@
+//! Writing local shared objects to storage, within the storage quota of their site.
+//!
+//! When a shared object outgrows the quota, Flash Player asks the user for more space
+//! and `flush` reports that it is pending. The answer arrives later, as an `onStatus`
+//! call in AVM1 or a `netStatus` event in AVM2.
+
+use crate::avm1::{Activation as Avm1Activation, ActivationIdentifier, ExecutionReason, TObject};
+use crate::avm2::{Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject};
+use crate::backend::navigator::OwnedFuture;
+use crate::backend::storage::{site_of, QuotaStatus};
+use crate::context::UpdateContext;
+use crate::loader::Error;
+
+/// The outcome of flushing a shared object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlushResult {
+    /// The shared object was written to storage.
+    Flushed,
+
+    /// The user is being asked for more space, and the shared object will be written
+    /// (or not) once they have answered.
+    Pending,
+
+    /// The shared object could not be written.
+    Failed,
+}
+
+/// Writes the serialized shared object `name` to storage.
+///
+/// `min_disk_space` lets a movie ask for more space than it currently needs,
+/// so that the user isn't asked again as the shared object grows.
+pub fn flush(
+    context: &mut UpdateContext<'_>,
+    name: &str,
+    bytes: Vec<u8>,
+    min_disk_space: usize,
+) -> FlushResult {
+    match context
+        .storage
+        .check_quota(name, bytes.len().max(min_disk_space))
+    {
+        QuotaStatus::Allowed => {
+            if context.storage.put(name, &bytes) {
+                FlushResult::Flushed
+            } else {
+                FlushResult::Failed
+            }
+        }
+        QuotaStatus::Denied => FlushResult::Failed,
+        QuotaStatus::Exceeded { required } => {
+            let Some(request) = context.ui.request_storage_quota(site_of(name), required) else {
+                return FlushResult::Failed;
+            };
+            let future = finish_flush(context, name.to_string(), bytes, request);
+            context.navigator.spawn_future(future);
+            FlushResult::Pending
+        }
+    }
+}
+
+fn finish_flush(
+    context: &mut UpdateContext<'_>,
+    name: String,
+    bytes: Vec<u8>,
+    request: OwnedFuture<bool, ()>,
+) -> OwnedFuture<(), Error> {
+    let player = context
+        .player
+        .upgrade()
+        .expect("Could not upgrade weak reference to player");
+
+    Box::pin(async move {
+        let granted = request.await.unwrap_or(false);
+        player.lock().unwrap().update(|uc| {
+            let flushed = granted && uc.storage.put(&name, &bytes);
+            dispatch_flush_status(uc, &name, flushed);
+        });
+        Ok(())
+    })
+}
+
+/// Tells the movie whether a pending flush of `name` went through.
+fn dispatch_flush_status(context: &mut UpdateContext<'_>, name: &str, flushed: bool) {
+    let (code, level) = if flushed {
+        ("SharedObject.Flush.Success", "status")
+    } else {
+        ("SharedObject.Flush.Failed", "error")
+    };
+
+    if let Some(object) = context.avm1_shared_objects.get(name).copied() {
+        let Some(root_clip) = context.stage.root_clip() else {
+            tracing::warn!("Ignored SharedObject status as there's no root movie");
+            return;
+        };
+        let mut activation = Avm1Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[SharedObject onStatus]"),
+            root_clip,
+        );
+        let constructor = activation.context.avm1.prototypes().object_constructor;
+        let result = constructor
+            .construct(&mut activation, &[])
+            .map(|event| event.coerce_to_object(&mut activation))
+            .and_then(|event| {
+                event.set("code", code.into(), &mut activation)?;
+                event.set("level", level.into(), &mut activation)?;
+                object.call_method(
+                    "onStatus".into(),
+                    &[event.into()],
+                    &mut activation,
+                    ExecutionReason::Special,
+                )
+            });
+        if let Err(e) = result {
+            tracing::error!("Unhandled error dispatching SharedObject status: {e}");
+        }
+    } else if let Some(object) = context.avm2_shared_objects.get(name).copied() {
+        let mut activation = Avm2Activation::from_nothing(context);
+        let event = Avm2EventObject::net_status_event(
+            &mut activation,
+            "netStatus",
+            vec![("code", code), ("level", level)],
+        );
+        Avm2::dispatch_event(activation.context, event, object);
+    }
+}
@@ -0,0 +1,189 @@
+//! Detection of sitelocks, checks that stop a movie from running anywhere but on its original site.
+//!
+//! Sitelocked movies read the URL they were loaded from and compare it, or a part of it,
+//! against the domains they were licensed to. When detection is enabled, we remember the URLs
+//! a movie read, and every string that was later compared against them. Anything that looks
+//! like a domain or URL there is likely where the movie expects to be loaded from, which users
+//! preserving such movies can then spoof.
+
+use crate::string::{WStr, WString};
+use std::collections::BTreeSet;
+
+/// How many URLs a movie may be expected at before we stop collecting more.
+const MAX_EXPECTED_URLS: usize = 32;
+
+/// Parts of a URL shorter than this aren't recognized in comparisons, as they match too much.
+const MIN_URL_PART_LENGTH: usize = 3;
+
+/// A way for a movie to find out where it is running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SitelockCheck {
+    /// The AVM1 `_url` property of a movie clip.
+    Avm1Url,
+
+    /// The AVM2 `LoaderInfo.url` property.
+    LoaderInfoUrl,
+
+    /// `System.security.sandboxType` or `Security.sandboxType`.
+    SandboxType,
+
+    /// `LocalConnection.domain`.
+    LocalConnectionDomain,
+}
+
+/// What a movie did to find out where it is running, as collected by a [`SitelockDetector`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SitelockReport {
+    /// Whether detection was enabled at all.
+    pub enabled: bool,
+
+    /// The checks the movie made.
+    pub checks: Vec<SitelockCheck>,
+
+    /// Domains and URLs the movie compared its own URL against.
+    pub expected_urls: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct SitelockDetector {
+    enabled: bool,
+    checks: BTreeSet<SitelockCheck>,
+
+    /// The URLs the movie read, which comparisons are checked against.
+    read_urls: Vec<WString>,
+
+    expected_urls: BTreeSet<String>,
+}
+
+impl SitelockDetector {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Whether string comparisons should be reported through [`Self::note_comparison`].
+    ///
+    /// This is only the case once the movie has read a URL, to keep the cost of detection low.
+    pub fn is_watching_comparisons(&self) -> bool {
+        self.enabled && !self.read_urls.is_empty()
+    }
+
+    /// Records that the movie checked where it is running.
+    pub fn note_check(&mut self, check: SitelockCheck) {
+        if self.enabled {
+            self.checks.insert(check);
+        }
+    }
+
+    /// Records that the movie read a URL it was loaded from.
+    pub fn note_url_read(&mut self, check: SitelockCheck, url: &WStr) {
+        if !self.enabled {
+            return;
+        }
+        self.checks.insert(check);
+        if !url.is_empty() && !self.read_urls.iter().any(|read| &read[..] == url) {
+            self.read_urls.push(url.into());
+        }
+    }
+
+    /// Records that the movie compared two strings, or searched for one in the other.
+    pub fn note_comparison(&mut self, a: &WStr, b: &WStr) {
+        if !self.is_watching_comparisons() || self.expected_urls.len() >= MAX_EXPECTED_URLS {
+            return;
+        }
+        for (subject, other) in [(a, b), (b, a)] {
+            if self.is_part_of_read_url(subject) && !self.is_part_of_read_url(other) {
+                if let Some(url) = as_site(other) {
+                    self.expected_urls.insert(url);
+                }
+            }
+        }
+    }
+
+    fn is_part_of_read_url(&self, value: &WStr) -> bool {
+        value.len() >= MIN_URL_PART_LENGTH && self.read_urls.iter().any(|url| url.contains(value))
+    }
+
+    pub fn report(&self) -> SitelockReport {
+        SitelockReport {
+            enabled: self.enabled,
+            checks: self.checks.iter().copied().collect(),
+            expected_urls: self.expected_urls.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Returns the string if it looks like a domain or a URL.
+fn as_site(value: &WStr) -> Option<String> {
+    let value = value.to_utf8_lossy();
+    let value = value.trim();
+    let looks_like_site = value.len() <= 256
+        && !value.contains(char::is_whitespace)
+        && (value.contains("://") || value.trim_matches('.').contains('.'))
+        && value.chars().any(|c| c.is_ascii_alphabetic());
+    looks_like_site.then(|| value.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> SitelockDetector {
+        let mut detector = SitelockDetector::new(true);
+        detector.note_url_read(
+            SitelockCheck::LoaderInfoUrl,
+            WStr::from_units(b"file:///home/user/game.swf"),
+        );
+        detector
+    }
+
+    #[test]
+    fn reports_domains_compared_to_the_url() {
+        let mut detector = detector();
+        detector.note_comparison(
+            WStr::from_units(b"file:///home/user/game.swf"),
+            WStr::from_units(b"www.example.com"),
+        );
+        // A part of the URL, like the domain extracted by the movie.
+        detector.note_comparison(
+            WStr::from_units(b"home"),
+            WStr::from_units(b"games.example.org"),
+        );
+        assert_eq!(
+            detector.report(),
+            SitelockReport {
+                enabled: true,
+                checks: vec![SitelockCheck::LoaderInfoUrl],
+                expected_urls: vec!["games.example.org".into(), "www.example.com".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_comparisons() {
+        let mut detector = detector();
+        detector.note_comparison(
+            WStr::from_units(b"score"),
+            WStr::from_units(b"www.example.com"),
+        );
+        detector.note_comparison(
+            WStr::from_units(b"file:///home/user/game.swf"),
+            WStr::from_units(b"http"),
+        );
+        detector.note_comparison(
+            WStr::from_units(b"file:///home/user/game.swf"),
+            WStr::from_units(b"game.swf"),
+        );
+        assert!(detector.report().expected_urls.is_empty());
+
+        let mut disabled = SitelockDetector::new(false);
+        disabled.note_check(SitelockCheck::SandboxType);
+        assert_eq!(disabled.report(), SitelockReport::default());
+    }
+}
@@ -89,6 +89,11 @@ impl<'gc> Sockets<'gc> {
         }
     }
 
+    /// Whether no sockets are open.
+    pub fn is_empty(&self) -> bool {
+        self.sockets.is_empty()
+    }
+
     pub fn connect_avm2(
         &mut self,
         backend: &mut dyn NavigatorBackend,
@@ -438,7 +443,7 @@ impl<'gc> Sockets<'gc> {
 
 /// Flash treats a socket host as a cstring, and stops reading at a null byte.
 /// We need to account for this here.
-fn sanitize_host(host: &str) -> &str {
+pub(crate) fn sanitize_host(host: &str) -> &str {
     host.split('\0').next().unwrap()
 }
 
@@ -1,5 +1,8 @@
 //! NetStream implementation
 
+pub mod hls;
+pub mod mpegts;
+
 use crate::avm1::{
     Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
     ExecutionReason as Avm1ExecutionReason, FlvValueAvm1Ext, ScriptObject as Avm1ScriptObject,
@@ -92,6 +95,11 @@ impl<'gc> StreamManager<'gc> {
         }
     }
 
+    /// Whether no streams are playing media or otherwise need to be ticked.
+    pub fn is_empty(&self) -> bool {
+        self.active_streams.is_empty()
+    }
+
     /// Activate a `NetStream`.
     ///
     /// This can be called at any time to flag that a `NetStream` has work to
@@ -218,10 +226,21 @@ pub struct NetStreamData<'gc> {
     /// Seeks are only executed on the next stream tick.
     queued_seek_time: Option<f64>,
 
-    /// The number of seconds of video data that should be buffered. This is
-    /// currently unsupported and changing it has no effect.
+    /// The number of seconds of media data that should be buffered ahead of
+    /// the playhead before playback starts or resumes.
     buffer_time: f64,
 
+    /// The timestamp of the last complete tag in the buffer, in milliseconds.
+    buffered_time: f64,
+
+    /// The buffer position up to which tags have been scanned for their
+    /// timestamps. This always points to the start of a tag.
+    scan_offset: usize,
+
+    /// True while the stream is waiting for `buffer_time` worth of data to
+    /// arrive. Playback does not advance while downloading in this state.
+    buffering: bool,
+
     /// The last decoded bitmap.
     ///
     /// Any `Video`s on the stage will display the bitmap here when attached to
@@ -243,6 +262,14 @@ pub struct NetStreamData<'gc> {
     #[collect(require_static)]
     audio_stream: Option<(Substream, SoundStreamInfo)>,
 
+    /// The AAC sequence header of the stream, if any.
+    ///
+    /// AAC decoders are configured by the first chunk of their substream, but
+    /// the sequence header is only present at the start of the file, so it
+    /// has to be replayed when a seek restarts the audio stream.
+    #[collect(require_static)]
+    aac_config: Option<Slice>,
+
     /// The currently playing sound stream
     #[collect(require_static)]
     sound_instance: Option<SoundInstanceHandle>,
@@ -268,11 +295,15 @@ impl<'gc> NetStream<'gc> {
                 stream_time: 0.0,
                 queued_seek_time: None,
                 buffer_time: 0.1,
+                buffered_time: 0.0,
+                scan_offset: 0,
+                buffering: true,
                 last_decoded_bitmap: None,
                 avm_object,
                 avm2_client: None,
                 url: None,
                 audio_stream: None,
+                aac_config: None,
                 sound_instance: None,
                 attached_to: None,
                 playing: false,
@@ -319,7 +350,11 @@ impl<'gc> NetStream<'gc> {
         write.stream_type = None;
         write.stream_time = 0.0;
         write.queued_seek_time = None;
+        write.buffered_time = 0.0;
+        write.scan_offset = 0;
+        write.buffering = true;
         write.audio_stream = None;
+        write.aac_config = None;
         write.sound_instance = None;
         write.expected_length = Some(0);
     }
@@ -351,17 +386,62 @@ impl<'gc> NetStream<'gc> {
 
         StreamManager::activate(context, self);
 
+        if self.0.read().stream_type.is_some() || self.sniff_stream_type(context) {
+            Self::scan_buffered_tags(&mut self.0.write(context.gc_context));
+        }
+
         // NOTE: The onMetaData event triggers before this event in Flash due to its streaming behavior.
-        self.trigger_status_event(
-            context,
-            vec![("code", "NetStream.Buffer.Full"), ("level", "status")],
-        );
+        self.check_buffer_full(context);
     }
 
     /// Indicate that the buffer has finished loading and that no further data
     /// is expected to be downloaded to it.
     pub fn finish_buffer(self, context: &mut UpdateContext<'gc>) {
         self.0.write(context.gc_context).expected_length = None;
+        self.check_buffer_full(context);
+    }
+
+    /// Find the timestamp of the last complete tag in the buffer.
+    fn scan_buffered_tags(write: &mut NetStreamData<'gc>) {
+        if !matches!(write.stream_type, Some(NetStreamType::Flv { .. })) {
+            return;
+        }
+
+        let slice = write.buffer.to_full_slice();
+        let buffer = slice.data();
+        let mut reader = FlvReader::from_parts(&buffer, max(write.scan_offset, write.offset));
+
+        // Stop at the first incomplete (or broken) tag; the next call will
+        // pick up from there once more data has arrived.
+        while let Ok(tag) = FlvTag::parse(&mut reader) {
+            write.buffered_time = tag.timestamp as f64;
+            write.scan_offset = reader
+                .stream_position()
+                .expect("FLV reader stream position") as usize;
+        }
+    }
+
+    /// Leave the buffering state once enough data has been buffered, or once
+    /// no more data will arrive.
+    fn check_buffer_full(self, context: &mut UpdateContext<'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.buffering {
+            return;
+        }
+
+        let has_data = write.scan_offset > 0;
+        let buffer_length = (write.buffered_time - write.stream_time).max(0.0) / 1000.0;
+        if (!has_data || buffer_length < write.buffer_time) && write.expected_length.is_some() {
+            return;
+        }
+
+        write.buffering = false;
+        drop(write);
+
+        self.trigger_status_event(
+            context,
+            vec![("code", "NetStream.Buffer.Full"), ("level", "status")],
+        );
     }
 
     pub fn report_error(self, _error: Error) {
@@ -388,13 +468,20 @@ impl<'gc> NetStream<'gc> {
     }
 
     pub fn set_buffer_time(self, mc: &Mutation<'gc>, buffer_time: f64) {
-        self.0.write(mc).buffer_time = buffer_time;
+        self.0.write(mc).buffer_time = buffer_time.max(0.0);
+    }
+
+    /// The number of seconds of media currently buffered ahead of the playhead.
+    pub fn buffer_length(self) -> f64 {
+        let read = self.0.read();
+        (read.buffered_time - read.stream_time).max(0.0) / 1000.0
     }
 
     /// Queue a seek to be executed on the next frame tick.
     ///
     /// `offset` is in milliseconds.
     pub fn seek(self, context: &mut UpdateContext<'gc>, offset: f64, notify: bool) {
+        let offset = offset.max(0.0);
         self.0.write(context.gc_context).queued_seek_time = Some(offset);
         StreamManager::activate(context, self);
 
@@ -431,6 +518,26 @@ impl<'gc> NetStream<'gc> {
     /// This function should be run during stream ticks and *not* called by AVM
     /// code to service seek requests.
     pub fn execute_seek(self, context: &mut UpdateContext<'gc>, offset: f64) {
+        // Progressive downloads can't be seeked past the data that has
+        // arrived so far; Flash reports the last valid time instead.
+        let (is_downloading, buffered_time) = {
+            let read = self.0.read();
+            (read.expected_length.is_some(), read.buffered_time)
+        };
+        if is_downloading && offset > buffered_time {
+            let details =
+                AvmString::new_utf8(context.gc_context, (buffered_time / 1000.0).to_string());
+            self.trigger_status_event(
+                context,
+                vec![
+                    ("code", "NetStream.Seek.InvalidTime".into()),
+                    ("level", "error".into()),
+                    ("details", details),
+                ],
+            );
+            return;
+        }
+
         self.trigger_status_event(
             context,
             vec![("code", "NetStream.Seek.Notify"), ("level", "status")],
@@ -594,18 +701,27 @@ impl<'gc> NetStream<'gc> {
     }
 
     /// Resume stream playback.
-    pub fn resume(self, context: &mut UpdateContext<'gc>) {
+    ///
+    /// Downloading continues while paused, so playback resumes from whatever
+    /// has been buffered in the meantime.
+    pub fn resume(self, context: &mut UpdateContext<'gc>, notify: bool) {
         self.0.write(context.gc_context).playing = true;
         StreamManager::activate(context, self);
+
+        if notify {
+            self.trigger_status_event(
+                context,
+                vec![("code", "NetStream.Unpause.Notify"), ("level", "status")],
+            );
+        }
     }
 
     /// Resume stream playback if paused, pause otherwise.
-    pub fn toggle_paused(self, context: &mut UpdateContext<'gc>) {
-        let mut write = self.0.write(context.gc_context);
-        write.playing = !write.playing;
-
-        if write.playing {
-            StreamManager::activate(context, self);
+    pub fn toggle_paused(self, context: &mut UpdateContext<'gc>, notify: bool) {
+        if self.0.read().playing {
+            self.pause(context, notify);
+        } else {
+            self.resume(context, notify);
         }
     }
 
@@ -651,11 +767,16 @@ impl<'gc> NetStream<'gc> {
         slice: &Slice,
         audio_data: FlvAudioData<'_>,
     ) -> Result<(), NetstreamError> {
+        let is_aac_config = matches!(audio_data.data, FlvAudioDataType::AacSequenceHeader(_));
         let data = match audio_data.data {
             FlvAudioDataType::Raw(data)
             | FlvAudioDataType::AacSequenceHeader(data)
             | FlvAudioDataType::AacRaw(data) => slice.to_subslice(data),
         };
+        if is_aac_config {
+            write.aac_config = Some(data.clone());
+        }
+
         let substream = match &mut write.audio_stream {
             Some((substream, _sound_stream_info)) => {
                 if substream
@@ -673,6 +794,11 @@ impl<'gc> NetStream<'gc> {
                     return Ok(());
                 }
 
+                if is_aac_config {
+                    // The decoder has already been configured.
+                    return Ok(());
+                }
+
                 substream
             }
             audio_stream => {
@@ -691,7 +817,7 @@ impl<'gc> NetStream<'gc> {
                         FlvSoundFormat::Nellymoser => AudioCompression::Nellymoser,
                         FlvSoundFormat::G711ALawPCM => return Err(NetstreamError::UnknownCodec),
                         FlvSoundFormat::G711MuLawPCM => return Err(NetstreamError::UnknownCodec),
-                        FlvSoundFormat::Aac => return Err(NetstreamError::UnknownCodec),
+                        FlvSoundFormat::Aac => AudioCompression::Aac,
                         FlvSoundFormat::Speex => AudioCompression::Speex,
                         FlvSoundFormat::MP38kHz => AudioCompression::Mp3,
                         FlvSoundFormat::DeviceSpecific => return Err(NetstreamError::UnknownCodec),
@@ -722,7 +848,11 @@ impl<'gc> NetStream<'gc> {
 
                 *audio_stream = Some((substream, sound_stream_head));
 
-                &mut audio_stream.as_mut().unwrap().0
+                let substream = &mut audio_stream.as_mut().unwrap().0;
+                if let (Some(config), false) = (&write.aac_config, is_aac_config) {
+                    substream.append(config.clone())?;
+                }
+                substream
             }
         };
 
@@ -1154,6 +1284,15 @@ impl<'gc> NetStream<'gc> {
             return;
         }
 
+        // Playback waits for the buffer to refill after running dry.
+        self.check_buffer_full(context);
+        {
+            let read = self.0.read();
+            if read.buffering && read.expected_length.is_some() {
+                return;
+            }
+        }
+
         let mut write = self.0.write(context.gc_context);
 
         self.cleanup_sound_stream(context, &mut write);
@@ -1244,29 +1383,28 @@ impl<'gc> NetStream<'gc> {
         }
         drop(write);
 
-        if buffer_underrun {
-            let is_end_of_video = self.0.read().expected_length.is_none();
-
+        if buffer_underrun && self.0.read().expected_length.is_some() {
+            // Still downloading: wait for the buffer to fill up again.
+            self.0.write(context.gc_context).buffering = true;
+            self.trigger_status_event(
+                context,
+                vec![("code", "NetStream.Buffer.Empty"), ("level", "status")],
+            );
+        } else if buffer_underrun {
             self.trigger_status_event(
                 context,
                 vec![("code", "NetStream.Buffer.Flush"), ("level", "status")],
             );
-
-            if is_end_of_video {
-                self.trigger_status_event(
-                    context,
-                    vec![("code", "NetStream.Play.Stop"), ("level", "status")],
-                );
-            }
-
+            self.trigger_status_event(
+                context,
+                vec![("code", "NetStream.Play.Stop"), ("level", "status")],
+            );
             self.trigger_status_event(
                 context,
                 vec![("code", "NetStream.Buffer.Empty"), ("level", "status")],
             );
 
-            if is_end_of_video {
-                self.pause(context, false);
-            }
+            self.pause(context, false);
         }
 
         if error {
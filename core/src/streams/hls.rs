@@ -0,0 +1,275 @@
+//! HTTP Live Streaming playlists.
+//!
+//! An HLS stream is a playlist of short MPEG-TS segments. A master playlist
+//! may first list several variants of the same stream at different bitrates,
+//! each of which is a media playlist of its own.
+
+use thiserror::Error;
+use url::Url;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum HlsError {
+    #[error("Not an M3U8 playlist")]
+    NotAPlaylist,
+
+    #[error("Master playlist has no variants")]
+    NoVariants,
+
+    #[error("Encrypted HLS streams are not supported")]
+    Encrypted,
+}
+
+/// How often live playlists without an `EXT-X-TARGETDURATION` are reloaded, in seconds.
+pub const DEFAULT_TARGET_DURATION: f64 = 10.0;
+
+/// Check if a URL points to an HLS playlist.
+pub fn is_hls_url(url: &str) -> bool {
+    let path = match Url::parse(url) {
+        Ok(url) => url.path().to_string(),
+        Err(_) => url.split(['?', '#']).next().unwrap_or_default().to_string(),
+    };
+
+    path.to_ascii_lowercase().ends_with(".m3u8")
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Playlist {
+    /// A list of alternative renditions of the same stream.
+    Master(Vec<Variant>),
+
+    /// A list of media segments.
+    Media(MediaPlaylist),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Variant {
+    /// The peak bitrate of this variant, in bits per second.
+    pub bandwidth: u64,
+
+    pub url: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MediaPlaylist {
+    pub segments: Vec<Segment>,
+
+    /// The maximum duration of a segment, in seconds, from `EXT-X-TARGETDURATION`.
+    ///
+    /// Live playlists are reloaded about this often.
+    pub target_duration: Option<f64>,
+
+    /// True if no further segments will be added to the playlist.
+    ///
+    /// Live playlists must be reloaded to discover new segments.
+    pub ended: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Segment {
+    /// The media sequence number of this segment.
+    ///
+    /// Sequence numbers identify segments across reloads of a live playlist.
+    pub sequence: u64,
+
+    pub url: String,
+}
+
+impl Playlist {
+    /// Parse an M3U8 playlist, resolving segment and variant URLs against
+    /// the URL of the playlist itself.
+    pub fn parse(text: &str, playlist_url: &str) -> Result<Self, HlsError> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        if lines.next() != Some("#EXTM3U") {
+            return Err(HlsError::NotAPlaylist);
+        }
+
+        let resolve = |uri: &str| {
+            Url::parse(playlist_url)
+                .and_then(|base| base.join(uri))
+                .map(|url| url.to_string())
+                .unwrap_or_else(|_| uri.to_string())
+        };
+
+        let mut variants = Vec::new();
+        let mut segments = Vec::new();
+        let mut ended = false;
+        let mut target_duration = None;
+        let mut sequence = 0;
+
+        let mut pending_bandwidth = None;
+        let mut pending_segment = false;
+
+        for line in lines {
+            if let Some(attributes) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+                pending_bandwidth = Some(
+                    attribute(attributes, "BANDWIDTH")
+                        .and_then(|bandwidth| bandwidth.parse().ok())
+                        .unwrap_or(0),
+                );
+            } else if line.starts_with("#EXTINF:") {
+                pending_segment = true;
+            } else if let Some(value) = line.strip_prefix("#EXT-X-TARGETDURATION:") {
+                target_duration = value.trim().parse().ok();
+            } else if let Some(value) = line.strip_prefix("#EXT-X-MEDIA-SEQUENCE:") {
+                sequence = value.trim().parse().unwrap_or(0);
+            } else if let Some(attributes) = line.strip_prefix("#EXT-X-KEY:") {
+                if attribute(attributes, "METHOD") != Some("NONE") {
+                    return Err(HlsError::Encrypted);
+                }
+            } else if line == "#EXT-X-ENDLIST" {
+                ended = true;
+            } else if line.starts_with('#') {
+                // Unsupported tags and comments.
+            } else if let Some(bandwidth) = pending_bandwidth.take() {
+                variants.push(Variant {
+                    bandwidth,
+                    url: resolve(line),
+                });
+            } else if pending_segment {
+                segments.push(Segment {
+                    sequence,
+                    url: resolve(line),
+                });
+                sequence += 1;
+                pending_segment = false;
+            }
+        }
+
+        if !variants.is_empty() {
+            Ok(Playlist::Master(variants))
+        } else if text.contains("#EXT-X-STREAM-INF") {
+            Err(HlsError::NoVariants)
+        } else {
+            Ok(Playlist::Media(MediaPlaylist {
+                segments,
+                target_duration,
+                ended,
+            }))
+        }
+    }
+}
+
+/// Pick the variant to play from a master playlist.
+///
+/// We don't measure bandwidth, so this is simply the best quality variant.
+pub fn select_variant(variants: &[Variant]) -> Option<&Variant> {
+    variants.iter().max_by_key(|variant| variant.bandwidth)
+}
+
+/// Read an attribute from an M3U8 attribute list, without quotes.
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while !rest.is_empty() {
+        let (key, value) = rest.split_once('=')?;
+        let (value, remainder) = if let Some(quoted) = value.strip_prefix('"') {
+            let end = quoted.find('"')?;
+            (&quoted[..end], quoted[end + 1..].trim_start_matches(','))
+        } else {
+            match value.split_once(',') {
+                Some((value, remainder)) => (value, remainder),
+                None => (value, ""),
+            }
+        };
+
+        if key.trim() == name {
+            return Some(value);
+        }
+        rest = remainder;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_hls_urls() {
+        assert!(is_hls_url("https://example.com/live/stream.m3u8"));
+        assert!(is_hls_url("https://example.com/stream.M3U8?token=abc"));
+        assert!(is_hls_url("videos/stream.m3u8?token=abc"));
+        assert!(!is_hls_url("https://example.com/video.flv"));
+        assert!(!is_hls_url("https://example.com/video.flv?list=a.m3u8"));
+    }
+
+    #[test]
+    fn parses_master_playlist() {
+        let playlist = Playlist::parse(
+            "#EXTM3U\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=800000,CODECS=\"avc1.4d401e,mp4a.40.2\",RESOLUTION=640x360\n\
+            low/index.m3u8\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=2500000,RESOLUTION=1280x720\n\
+            https://cdn.example.com/high/index.m3u8\n",
+            "https://example.com/video/master.m3u8",
+        )
+        .unwrap();
+
+        let Playlist::Master(variants) = playlist else {
+            panic!("Expected a master playlist");
+        };
+        assert_eq!(
+            variants,
+            vec![
+                Variant {
+                    bandwidth: 800000,
+                    url: "https://example.com/video/low/index.m3u8".to_string(),
+                },
+                Variant {
+                    bandwidth: 2500000,
+                    url: "https://cdn.example.com/high/index.m3u8".to_string(),
+                },
+            ]
+        );
+        assert_eq!(select_variant(&variants), Some(&variants[1]));
+    }
+
+    #[test]
+    fn parses_media_playlist() {
+        let playlist = Playlist::parse(
+            "#EXTM3U\n\
+            #EXT-X-TARGETDURATION:10\n\
+            #EXT-X-MEDIA-SEQUENCE:7\n\
+            #EXTINF:9.009,\n\
+            segment7.ts\n\
+            #EXTINF:3.5,title\n\
+            segment8.ts\n\
+            #EXT-X-ENDLIST\n",
+            "https://example.com/video/index.m3u8",
+        )
+        .unwrap();
+
+        assert_eq!(
+            playlist,
+            Playlist::Media(MediaPlaylist {
+                segments: vec![
+                    Segment {
+                        sequence: 7,
+                        url: "https://example.com/video/segment7.ts".to_string(),
+                    },
+                    Segment {
+                        sequence: 8,
+                        url: "https://example.com/video/segment8.ts".to_string(),
+                    },
+                ],
+                target_duration: Some(10.0),
+                ended: true,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_playlists() {
+        assert_eq!(
+            Playlist::parse("<html></html>", "https://example.com/"),
+            Err(HlsError::NotAPlaylist)
+        );
+        assert_eq!(
+            Playlist::parse(
+                "#EXTM3U\n#EXT-X-KEY:METHOD=AES-128,URI=\"key\"\n#EXTINF:1,\na.ts\n",
+                "https://example.com/"
+            ),
+            Err(HlsError::Encrypted)
+        );
+    }
+}
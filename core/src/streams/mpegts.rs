@@ -0,0 +1,478 @@
+//! MPEG-2 transport stream demuxing.
+//!
+//! HLS segments are transport streams carrying H.264 video and AAC audio.
+//! Rather than teaching `NetStream` a second container format, the elementary
+//! streams are rewrapped into FLV tags, which the rest of the stream code
+//! already knows how to play.
+
+use std::collections::HashMap;
+
+const PACKET_SIZE: usize = 188;
+const SYNC_BYTE: u8 = 0x47;
+
+const PAT_PID: u16 = 0;
+
+const STREAM_TYPE_AAC: u8 = 0x0F;
+const STREAM_TYPE_H264: u8 = 0x1B;
+
+const FLV_TAG_AUDIO: u8 = 8;
+const FLV_TAG_VIDEO: u8 = 9;
+
+const NAL_IDR: u8 = 5;
+const NAL_SPS: u8 = 7;
+const NAL_PPS: u8 = 8;
+const NAL_AUD: u8 = 9;
+
+/// The sample rates addressed by an ADTS sampling frequency index.
+const AAC_SAMPLE_RATES: [u32; 13] = [
+    96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ElementaryStream {
+    H264,
+    Aac,
+}
+
+/// A PES packet being reassembled from transport stream packets.
+#[derive(Default)]
+struct PesBuffer {
+    data: Vec<u8>,
+}
+
+/// Converts a sequence of transport stream segments into FLV tags.
+///
+/// State is kept across segments: timestamps continue where the previous
+/// segment left off, and codec configuration is only written once.
+#[derive(Default)]
+pub struct TsToFlv {
+    /// The PIDs of the program map tables announced by the PAT.
+    pmt_pids: Vec<u16>,
+
+    /// The elementary streams we know how to rewrap, by PID.
+    streams: HashMap<u16, ElementaryStream>,
+
+    /// Partially received PES packets, by PID.
+    pes: HashMap<u16, PesBuffer>,
+
+    /// The first timestamp in the stream, in 90kHz units. FLV timestamps
+    /// are relative to this.
+    base_time: Option<u64>,
+
+    sps: Option<Vec<u8>>,
+    pps: Option<Vec<u8>>,
+    wrote_avc_config: bool,
+    wrote_aac_config: bool,
+
+    /// The size of the last written tag, which FLV repeats before the next one.
+    last_tag_size: u32,
+
+    /// The FLV data produced so far, not yet taken by the caller.
+    output: Vec<u8>,
+}
+
+impl TsToFlv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The FLV file header, which must precede the first converted tags.
+    pub fn flv_header() -> Vec<u8> {
+        // Signature, version 1, audio and video present, 9 byte header.
+        vec![b'F', b'L', b'V', 1, 0x05, 0, 0, 0, 9]
+    }
+
+    /// Convert a complete transport stream segment.
+    ///
+    /// Returns the FLV tags for every frame in the segment.
+    pub fn push_segment(&mut self, data: &[u8]) -> Vec<u8> {
+        let start = data
+            .iter()
+            .position(|b| *b == SYNC_BYTE)
+            .unwrap_or(data.len());
+        for packet in data[start..].chunks_exact(PACKET_SIZE) {
+            if packet[0] != SYNC_BYTE {
+                tracing::warn!("Lost sync in MPEG-TS segment");
+                break;
+            }
+            self.packet(packet);
+        }
+
+        // Segments always end on a frame boundary.
+        let pids: Vec<u16> = self.pes.keys().copied().collect();
+        for pid in pids {
+            self.flush_pes(pid);
+        }
+
+        std::mem::take(&mut self.output)
+    }
+
+    fn packet(&mut self, packet: &[u8]) {
+        let payload_unit_start = packet[1] & 0x40 != 0;
+        let pid = u16::from_be_bytes([packet[1] & 0x1F, packet[2]]);
+        let adaptation_field_control = (packet[3] >> 4) & 0x03;
+
+        let mut payload_start = 4;
+        if adaptation_field_control & 0x02 != 0 {
+            payload_start += 1 + packet[4] as usize;
+        }
+        if adaptation_field_control & 0x01 == 0 || payload_start >= PACKET_SIZE {
+            return;
+        }
+        let payload = &packet[payload_start..];
+
+        if pid == PAT_PID {
+            if payload_unit_start {
+                self.parse_pat(payload);
+            }
+        } else if self.pmt_pids.contains(&pid) {
+            if payload_unit_start {
+                self.parse_pmt(payload);
+            }
+        } else if self.streams.contains_key(&pid) {
+            if payload_unit_start {
+                self.flush_pes(pid);
+            }
+            self.pes
+                .entry(pid)
+                .or_default()
+                .data
+                .extend_from_slice(payload);
+        }
+    }
+
+    /// Find the section data of a PSI table, skipping the pointer field.
+    fn psi_section(payload: &[u8]) -> Option<&[u8]> {
+        let pointer = *payload.first()? as usize;
+        let section = payload.get(1 + pointer..)?;
+        let length = (u16::from_be_bytes([*section.get(1)? & 0x0F, *section.get(2)?])) as usize;
+
+        // Skip the table header and leave out the trailing CRC.
+        section.get(8..(3 + length).checked_sub(4)?)
+    }
+
+    fn parse_pat(&mut self, payload: &[u8]) {
+        let Some(entries) = Self::psi_section(payload) else {
+            return;
+        };
+
+        for entry in entries.chunks_exact(4) {
+            let program_number = u16::from_be_bytes([entry[0], entry[1]]);
+            let pid = u16::from_be_bytes([entry[2] & 0x1F, entry[3]]);
+            if program_number != 0 && !self.pmt_pids.contains(&pid) {
+                self.pmt_pids.push(pid);
+            }
+        }
+    }
+
+    fn parse_pmt(&mut self, payload: &[u8]) {
+        let Some(section) = Self::psi_section(payload) else {
+            return;
+        };
+        let Some(info_length) = section
+            .get(2..4)
+            .map(|b| u16::from_be_bytes([b[0] & 0x0F, b[1]]) as usize)
+        else {
+            return;
+        };
+
+        let mut entries = section.get(4 + info_length..).unwrap_or_default();
+        while entries.len() >= 5 {
+            let stream_type = entries[0];
+            let pid = u16::from_be_bytes([entries[1] & 0x1F, entries[2]]);
+            let es_info_length = u16::from_be_bytes([entries[3] & 0x0F, entries[4]]) as usize;
+
+            match stream_type {
+                STREAM_TYPE_H264 => {
+                    self.streams.insert(pid, ElementaryStream::H264);
+                }
+                STREAM_TYPE_AAC => {
+                    self.streams.insert(pid, ElementaryStream::Aac);
+                }
+                _ => tracing::warn!("Unsupported MPEG-TS stream type {:#x}", stream_type),
+            }
+
+            entries = entries.get(5 + es_info_length..).unwrap_or_default();
+        }
+    }
+
+    fn flush_pes(&mut self, pid: u16) {
+        let Some(pes) = self.pes.remove(&pid) else {
+            return;
+        };
+        let Some(stream) = self.streams.get(&pid).copied() else {
+            return;
+        };
+        let Some((pts, dts, payload)) = parse_pes(&pes.data) else {
+            return;
+        };
+
+        let base = *self.base_time.get_or_insert(dts);
+        let to_ms = |time: u64| (time.saturating_sub(base) / 90) as u32;
+
+        match stream {
+            ElementaryStream::H264 => {
+                let composition_offset = (pts.saturating_sub(dts) / 90) as u32;
+                self.h264_frame(to_ms(dts), composition_offset, payload);
+            }
+            ElementaryStream::Aac => self.aac_frames(pts.saturating_sub(base), payload),
+        }
+    }
+
+    fn h264_frame(&mut self, timestamp: u32, composition_offset: u32, data: &[u8]) {
+        let mut is_keyframe = false;
+        let mut nalus = Vec::new();
+
+        for nal in annex_b_nal_units(data) {
+            match nal.first().map(|b| b & 0x1F) {
+                Some(NAL_SPS) => self.sps = Some(nal.to_vec()),
+                Some(NAL_PPS) => self.pps = Some(nal.to_vec()),
+                Some(NAL_AUD) | None => continue,
+                Some(NAL_IDR) => is_keyframe = true,
+                Some(_) => {}
+            }
+            nalus.extend_from_slice(&(nal.len() as u32).to_be_bytes());
+            nalus.extend_from_slice(nal);
+        }
+
+        if !self.wrote_avc_config {
+            let (Some(sps), Some(pps)) = (&self.sps, &self.pps) else {
+                // Frames can't be decoded before the decoder is configured.
+                return;
+            };
+            if sps.len() < 4 {
+                return;
+            }
+
+            let mut config = vec![0x17, 0, 0, 0, 0];
+            config.extend_from_slice(&[1, sps[1], sps[2], sps[3], 0xFF, 0xE1]);
+            config.extend_from_slice(&(sps.len() as u16).to_be_bytes());
+            config.extend_from_slice(sps);
+            config.push(1);
+            config.extend_from_slice(&(pps.len() as u16).to_be_bytes());
+            config.extend_from_slice(pps);
+            self.write_tag(FLV_TAG_VIDEO, timestamp, &config);
+            self.wrote_avc_config = true;
+        }
+
+        if nalus.is_empty() {
+            return;
+        }
+
+        let frame_type = if is_keyframe { 0x10 } else { 0x20 };
+        let cts = composition_offset.to_be_bytes();
+        let mut tag = vec![frame_type | 0x07, 1, cts[1], cts[2], cts[3]];
+        tag.extend_from_slice(&nalus);
+        self.write_tag(FLV_TAG_VIDEO, timestamp, &tag);
+    }
+
+    /// Rewrap the ADTS frames of a PES packet.
+    ///
+    /// `pts` is relative to the start of the stream, in 90kHz units.
+    fn aac_frames(&mut self, pts: u64, mut data: &[u8]) {
+        let mut frame_index = 0;
+
+        while data.len() >= 7 && data[0] == 0xFF && data[1] & 0xF0 == 0xF0 {
+            let protection_absent = data[1] & 0x01 != 0;
+            let object_type = ((data[2] >> 6) & 0x03) + 1;
+            let sample_rate_index = (data[2] >> 2) & 0x0F;
+            let channels = ((data[2] & 0x01) << 2) | (data[3] >> 6);
+            let frame_length = (((data[3] & 0x03) as usize) << 11)
+                | ((data[4] as usize) << 3)
+                | ((data[5] as usize) >> 5);
+            let header_length = if protection_absent { 7 } else { 9 };
+
+            let Some(sample_rate) = AAC_SAMPLE_RATES.get(sample_rate_index as usize) else {
+                tracing::warn!("Invalid ADTS sample rate index {}", sample_rate_index);
+                return;
+            };
+            if frame_length < header_length || frame_length > data.len() {
+                return;
+            }
+
+            // Every AAC frame holds 1024 samples.
+            let timestamp = ((pts + frame_index * 1024 * 90000 / *sample_rate as u64) / 90) as u32;
+
+            if !self.wrote_aac_config {
+                let config = ((object_type as u16) << 11)
+                    | ((sample_rate_index as u16) << 7)
+                    | ((channels as u16) << 3);
+                let config = config.to_be_bytes();
+                self.write_tag(FLV_TAG_AUDIO, timestamp, &[0xAF, 0, config[0], config[1]]);
+                self.wrote_aac_config = true;
+            }
+
+            let mut tag = vec![0xAF, 1];
+            tag.extend_from_slice(&data[header_length..frame_length]);
+            self.write_tag(FLV_TAG_AUDIO, timestamp, &tag);
+
+            data = &data[frame_length..];
+            frame_index += 1;
+        }
+    }
+
+    fn write_tag(&mut self, tag_type: u8, timestamp: u32, data: &[u8]) {
+        let size = (data.len() as u32).to_be_bytes();
+        let time = timestamp.to_be_bytes();
+
+        self.output
+            .extend_from_slice(&self.last_tag_size.to_be_bytes());
+        self.output.push(tag_type);
+        self.output.extend_from_slice(&size[1..]);
+        self.output
+            .extend_from_slice(&[time[1], time[2], time[3], time[0]]);
+        self.output.extend_from_slice(&[0, 0, 0]);
+        self.output.extend_from_slice(data);
+        self.last_tag_size = 11 + data.len() as u32;
+    }
+}
+
+/// Parse a PES packet, returning its PTS, DTS and payload.
+fn parse_pes(data: &[u8]) -> Option<(u64, u64, &[u8])> {
+    if data.get(0..3)? != [0, 0, 1] {
+        return None;
+    }
+
+    let flags = *data.get(7)? >> 6;
+    let header_length = *data.get(8)? as usize;
+    let payload = data.get(9 + header_length..)?;
+
+    let pts = if flags & 0x02 != 0 {
+        parse_timestamp(data.get(9..14)?)
+    } else {
+        return None;
+    };
+    let dts = if flags == 0x03 {
+        parse_timestamp(data.get(14..19)?)
+    } else {
+        pts
+    };
+
+    Some((pts, dts, payload))
+}
+
+/// Parse a 33-bit PES timestamp.
+fn parse_timestamp(bytes: &[u8]) -> u64 {
+    (((bytes[0] >> 1) & 0x07) as u64) << 30
+        | (bytes[1] as u64) << 22
+        | ((bytes[2] >> 1) as u64) << 15
+        | (bytes[3] as u64) << 7
+        | (bytes[4] >> 1) as u64
+}
+
+/// Split an Annex B byte stream into NAL units, without their start codes.
+fn annex_b_nal_units(data: &[u8]) -> Vec<&[u8]> {
+    let mut starts = Vec::new();
+    let mut i = 0;
+    while i + 3 <= data.len() {
+        if data[i] == 0 && data[i + 1] == 0 && data[i + 2] == 1 {
+            starts.push(i + 3);
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+
+    let mut units = Vec::with_capacity(starts.len());
+    for (n, start) in starts.iter().enumerate() {
+        let mut end = starts.get(n + 1).map(|s| s - 3).unwrap_or(data.len());
+        // Four byte start codes leave a leading zero behind.
+        while end > *start && data[end - 1] == 0 && n + 1 < starts.len() {
+            end -= 1;
+        }
+        units.push(&data[*start..end]);
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flv_rs::{FlvReader, Header, Tag, TagData, VideoPacket};
+
+    fn ts_packet(pid: u16, start: bool, payload: &[u8]) -> Vec<u8> {
+        let mut packet = vec![SYNC_BYTE, (pid >> 8) as u8, pid as u8, 0x10];
+        if start {
+            packet[1] |= 0x40;
+        }
+        let stuffing = PACKET_SIZE - 4 - payload.len();
+        if stuffing > 0 {
+            // Pad using an adaptation field.
+            packet[3] = 0x30;
+            packet.push((stuffing - 1) as u8);
+            if stuffing > 1 {
+                packet.push(0);
+                packet.extend(std::iter::repeat(0xFF).take(stuffing - 2));
+            }
+        }
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    fn psi(table_id: u8, body: &[u8]) -> Vec<u8> {
+        let length = 5 + body.len() + 4;
+        let mut section = vec![0, table_id, 0xB0 | (length >> 8) as u8, length as u8];
+        section.extend_from_slice(&[0, 1, 0xC1, 0, 0]);
+        section.extend_from_slice(body);
+        section.extend_from_slice(&[0, 0, 0, 0]);
+        section
+    }
+
+    fn timestamp(marker: u8, time: u64) -> [u8; 5] {
+        [
+            marker << 4 | ((time >> 29) & 0x0E) as u8 | 1,
+            (time >> 22) as u8,
+            ((time >> 14) & 0xFE) as u8 | 1,
+            (time >> 7) as u8,
+            ((time << 1) & 0xFE) as u8 | 1,
+        ]
+    }
+
+    #[test]
+    fn converts_h264_to_flv() {
+        let mut segment = Vec::new();
+        segment.extend(ts_packet(0, true, &psi(0, &[0, 1, 0xE1, 0x00])));
+        segment.extend(ts_packet(
+            0x100,
+            true,
+            &psi(2, &[0xE1, 0x01, 0xF0, 0, 0x1B, 0xE1, 0x01, 0xF0, 0]),
+        ));
+
+        let mut pes = vec![0, 0, 1, 0xE0, 0, 0, 0x80, 0x80, 5];
+        pes.extend_from_slice(&timestamp(2, 90_000));
+        pes.extend_from_slice(&[0, 0, 0, 1, 0x67, 0x42, 0, 0x1E]);
+        pes.extend_from_slice(&[0, 0, 0, 1, 0x68, 0xCE]);
+        pes.extend_from_slice(&[0, 0, 1, 0x65, 0x88, 0x84]);
+        segment.extend(ts_packet(0x101, true, &pes));
+
+        let mut flv = TsToFlv::flv_header();
+        flv.extend(TsToFlv::new().push_segment(&segment));
+
+        let mut reader = FlvReader::from_source(&flv);
+        Header::parse(&mut reader).unwrap();
+
+        let config = Tag::parse(&mut reader).unwrap();
+        assert_eq!(config.timestamp, 0);
+        assert!(matches!(
+            config.data,
+            TagData::Video(ref video) if matches!(video.data, VideoPacket::AvcSequenceHeader(_))
+        ));
+
+        let frame = Tag::parse(&mut reader).unwrap();
+        match frame.data {
+            TagData::Video(video) => match video.data {
+                VideoPacket::AvcNalu { data, .. } => {
+                    assert_eq!(
+                        data,
+                        &[
+                            0, 0, 0, 4, 0x67, 0x42, 0, 0x1E, 0, 0, 0, 2, 0x68, 0xCE, 0, 0, 0, 3,
+                            0x65, 0x88, 0x84
+                        ]
+                    );
+                }
+                other => panic!("Expected NAL units, got {other:?}"),
+            },
+            other => panic!("Expected a video tag, got {other:?}"),
+        }
+    }
+}
@@ -4,6 +4,9 @@ use gc_arena::{Gc, Mutation};
 
 use super::{AvmAtom, AvmString, AvmStringInterner, AvmStringRepr, WStr, WString};
 
+/// Concatenations resulting in strings up to this length get interned.
+const MAX_INTERNED_CONCAT_LEN: usize = 16;
+
 /// Context for managing `AvmString`s: allocating them, interning them, etc...
 pub struct StringContext<'gc> {
     /// The mutation context to allocate and mutate `Gc` pointers.
@@ -70,6 +73,12 @@ impl<'gc> StringContext<'gc> {
         self.interner.get(self.gc(), s)
     }
 
+    /// The number of interned strings that are still alive.
+    #[must_use]
+    pub fn interned_count(&self) -> usize {
+        self.interner.len(self.gc())
+    }
+
     #[must_use]
     pub fn empty(&self) -> AvmString<'gc> {
         self.interner.empty.into()
@@ -90,6 +99,60 @@ impl<'gc> StringContext<'gc> {
         self.interner.chars[c as usize].into()
     }
 
+    /// Concatenates two strings, as done by the `+` operator of both AVMs.
+    ///
+    /// Short results are interned, so that strings repeatedly built from the same parts
+    /// (e.g. `"item" + i`) don't need a new allocation each time. Longer ones are
+    /// appended in-place when possible, see [`AvmString::concat`].
+    #[must_use]
+    pub fn concat(&mut self, left: AvmString<'gc>, right: AvmString<'gc>) -> AvmString<'gc> {
+        let len = left.len() + right.len();
+        if left.is_empty() || right.is_empty() || len > MAX_INTERNED_CONCAT_LEN {
+            return AvmString::concat(self.gc(), left, right);
+        }
+
+        let units = left.iter().chain(right.iter());
+        if left.is_wide() || right.is_wide() {
+            let mut buf = [0u16; MAX_INTERNED_CONCAT_LEN];
+            buf.iter_mut()
+                .zip(units)
+                .for_each(|(dst, unit)| *dst = unit);
+            self.intern_wstr(WStr::from_units(&buf[..len])).into()
+        } else {
+            let mut buf = [0u8; MAX_INTERNED_CONCAT_LEN];
+            buf.iter_mut()
+                .zip(units)
+                .for_each(|(dst, unit)| *dst = unit as u8);
+            self.intern_wstr(WStr::from_units(&buf[..len])).into()
+        }
+    }
+
+    /// Concatenates several strings at once, as done by `String.concat` in both AVMs.
+    ///
+    /// Unlike concatenating them one by one, this allocates the result only once, with the
+    /// length of all parts. Like with [`Self::concat`], short results are interned.
+    #[must_use]
+    pub fn concat_all(&mut self, parts: &[AvmString<'gc>]) -> AvmString<'gc> {
+        let mut non_empty = parts.iter().filter(|part| !part.is_empty());
+        match (non_empty.next(), non_empty.next()) {
+            (None, _) => return self.empty(),
+            (Some(part), None) => return *part,
+            (Some(_), Some(_)) => {}
+        }
+
+        let len = parts.iter().map(|part| part.len()).sum();
+        let is_wide = parts.iter().any(|part| part.is_wide());
+        let mut out = WString::with_capacity(len, is_wide);
+        for part in parts {
+            out.push_str(part);
+        }
+        if len <= MAX_INTERNED_CONCAT_LEN {
+            self.intern_wstr(out).into()
+        } else {
+            AvmString::new(self.gc(), out)
+        }
+    }
+
     #[must_use]
     pub fn substring(&self, s: AvmString<'gc>, range: Range<usize>) -> AvmString<'gc> {
         self.interner
@@ -108,6 +108,12 @@ impl<'gc> AvmStringInterner<'gc> {
         self.interned.get(mc, s).map(AvmAtom)
     }
 
+    /// The number of interned strings that are still alive.
+    #[must_use]
+    pub(super) fn len(&self, mc: &Mutation<'gc>) -> usize {
+        self.interned.len(mc)
+    }
+
     #[must_use]
     pub(super) fn substring(
         &self,
@@ -151,6 +157,15 @@ impl<'gc, T: Hash + 'gc> WeakSet<'gc, T> {
         build_hasher.hash_one(key)
     }
 
+    /// The number of keys in the set that are still alive.
+    fn len(&self, mc: &Mutation<'gc>) -> usize {
+        self.table
+            .as_ref(mc)
+            .iter()
+            .filter(|weak| weak.upgrade(mc).is_some())
+            .count()
+    }
+
     /// Finds the given key in the map.
     fn get<Q>(&self, mc: &Mutation<'gc>, key: &Q) -> Option<Gc<'gc, T>>
     where
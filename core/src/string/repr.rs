@@ -2,7 +2,7 @@ use std::cell::Cell;
 use std::ops::Deref;
 
 use gc_arena::{Collect, Gc};
-use ruffle_wstr::{panic_on_invalid_length, ptr as wptr, wstr_impl_traits, WStr, WString};
+use ruffle_wstr::{panic_on_invalid_length, ptr as wptr, wstr_impl_traits, Units, WStr, WString};
 
 /// Internal representation of `AvmAtom`s and (owned) `AvmString`s.
 ///
@@ -75,9 +75,9 @@ impl<'gc> AvmStringRepr<'gc> {
     }
 
     pub fn try_append_inline(left: Gc<'gc, Self>, right: &WStr) -> Option<Self> {
-        // note: we could also in-place append a byte string to a wide string
-        // But it was skipped for now.
-        if left.is_wide() != right.is_wide() {
+        // A byte string can be widened in-place when appended to a wide string,
+        // but a wide string can't be appended to a byte string without reallocating.
+        if !left.is_wide() && right.is_wide() {
             return None;
         }
 
@@ -120,9 +120,23 @@ impl<'gc> AvmStringRepr<'gc> {
                     ((left_capacity_end as usize) - (first_available as usize)) / char_size;
             }
             if chars_available >= right.len() {
-                let first_available = first_available as *mut u8;
-                let right_ptr = right as *const WStr as *const () as *const u8;
-                std::ptr::copy_nonoverlapping(right_ptr, first_available, char_size * right.len());
+                match right.units() {
+                    Units::Bytes(units) if left.is_wide() => {
+                        let first_available = first_available as *mut u16;
+                        for (i, unit) in units.iter().enumerate() {
+                            first_available.add(i).write(u16::from(*unit));
+                        }
+                    }
+                    _ => {
+                        let first_available = first_available as *mut u8;
+                        let right_ptr = right as *const WStr as *const () as *const u8;
+                        std::ptr::copy_nonoverlapping(
+                            right_ptr,
+                            first_available,
+                            char_size * right.len(),
+                        );
+                    }
+                }
 
                 let new_chars_used: usize = left_origin.chars_used.get() as usize + right.len();
                 if new_chars_used >= u32::MAX as usize {
@@ -211,3 +225,111 @@ impl<'gc> Default for AvmStringRepr<'gc> {
 }
 
 wstr_impl_traits!(impl['gc] for AvmStringRepr<'gc>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gc_arena::{rootless_arena, Mutation};
+
+    fn owned<'gc>(mc: &Mutation<'gc>, s: &WStr, wide: bool) -> Gc<'gc, AvmStringRepr<'gc>> {
+        let mut buf = WString::with_capacity(8, wide);
+        buf.push_str(s);
+        Gc::new(mc, AvmStringRepr::from_raw(buf, false))
+    }
+
+    fn capacity(repr: Gc<'_, AvmStringRepr<'_>>) -> usize {
+        repr.capacity.get().len()
+    }
+
+    #[test]
+    fn append_up_to_capacity() {
+        rootless_arena(|mc| {
+            let left = owned(mc, WStr::from_units(b"abc"), false);
+            let fill = "d".repeat(capacity(left) - 3);
+            let right = WStr::from_units(fill.as_bytes());
+
+            let appended = AvmStringRepr::try_append_inline(left, right).unwrap();
+            assert!(!appended.is_wide());
+            assert_eq!(appended.len(), capacity(left));
+            assert_eq!(&appended[..3], WStr::from_units(b"abc"));
+            assert_eq!(&appended[3..], right);
+            assert_eq!(left.chars_used.get() as usize, capacity(left));
+
+            // The buffer is full now.
+            let appended = Gc::new(mc, appended);
+            assert!(AvmStringRepr::try_append_inline(appended, WStr::from_units(b"e")).is_none());
+            // An empty string still fits.
+            assert!(AvmStringRepr::try_append_inline(appended, WStr::empty()).is_some());
+        })
+    }
+
+    #[test]
+    fn append_past_capacity() {
+        rootless_arena(|mc| {
+            let left = owned(mc, WStr::from_units(b"abc"), false);
+            let fill = "d".repeat(capacity(left) - 2);
+            let right = WStr::from_units(fill.as_bytes());
+
+            assert!(AvmStringRepr::try_append_inline(left, right).is_none());
+            assert_eq!(left.chars_used.get(), 3);
+        })
+    }
+
+    #[test]
+    fn append_bytes_to_wide() {
+        rootless_arena(|mc| {
+            let left = owned(mc, WStr::from_units(&[0x2603u16, 0x61]), true);
+            let fill = vec![0xffu8; capacity(left) - 2];
+            let right = WStr::from_units(&fill[..]);
+            assert!(!right.is_wide());
+
+            let appended = AvmStringRepr::try_append_inline(left, right).unwrap();
+            assert!(appended.is_wide());
+            assert_eq!(appended.len(), capacity(left));
+            assert_eq!(appended.get(0), Some(0x2603));
+            assert_eq!(appended.get(1), Some(0x61));
+            assert!(appended[2..].iter().all(|unit| unit == 0xff));
+
+            let appended = Gc::new(mc, appended);
+            assert!(AvmStringRepr::try_append_inline(appended, WStr::from_units(b"x")).is_none());
+        })
+    }
+
+    #[test]
+    fn append_bytes_to_wide_past_capacity() {
+        rootless_arena(|mc| {
+            let left = owned(mc, WStr::from_units(&[0x2603u16, 0x61]), true);
+            let fill = "d".repeat(capacity(left) - 1);
+            let right = WStr::from_units(fill.as_bytes());
+            assert!(AvmStringRepr::try_append_inline(left, right).is_none());
+            assert_eq!(left.chars_used.get(), 2);
+        })
+    }
+
+    #[test]
+    fn append_wide_to_bytes() {
+        rootless_arena(|mc| {
+            let left = owned(mc, WStr::from_units(b"abc"), false);
+            let right = WStr::from_units(&[0x2603u16]);
+            assert!(AvmStringRepr::try_append_inline(left, right).is_none());
+            assert_eq!(left.chars_used.get(), 3);
+        })
+    }
+
+    #[test]
+    fn append_after_other_append() {
+        rootless_arena(|mc| {
+            let left = owned(mc, WStr::from_units(b"abc"), false);
+            let first = AvmStringRepr::try_append_inline(left, WStr::from_units(b"d")).unwrap();
+            let first = Gc::new(mc, first);
+
+            // The space after `left` is used by `first` now.
+            assert!(AvmStringRepr::try_append_inline(left, WStr::from_units(b"e")).is_none());
+
+            let second = AvmStringRepr::try_append_inline(first, WStr::from_units(b"e")).unwrap();
+            assert_eq!(&*second, WStr::from_units(b"abcde"));
+            assert!(Gc::ptr_eq(second.owner().unwrap(), left));
+            assert_eq!(&**first, WStr::from_units(b"abcd"));
+        })
+    }
+}
@@ -1,7 +1,13 @@
 use fnv::FnvHashSet;
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::collections::hash_set::Iter;
+use std::collections::VecDeque;
 use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+/// How many stubs are remembered by [`RecentStubs`].
+const RECENT_STUBS_LIMIT: usize = 32;
 
 #[cfg(feature = "known_stubs")]
 #[linkme::distributed_slice]
@@ -132,6 +138,7 @@ impl Display for Stub {
 #[derive(Debug, Default)]
 pub struct StubCollection {
     inner: FnvHashSet<Stub>,
+    recent: RecentStubs,
 }
 
 impl StubCollection {
@@ -143,12 +150,42 @@ impl StubCollection {
         if !self.inner.contains(stub) {
             tracing::warn!("Encountered stub: {stub}");
             self.inner.insert(stub.clone());
+            self.recent.push(stub.clone());
         }
     }
 
     pub fn iter(&self) -> Iter<Stub> {
         self.inner.iter()
     }
+
+    /// A handle to the most recently encountered stubs, which stays valid
+    /// without access to the player (e.g. from a panic hook).
+    pub fn recent(&self) -> RecentStubs {
+        self.recent.clone()
+    }
+}
+
+/// The last few distinct stubs encountered by a player, oldest first.
+#[derive(Debug, Default, Clone)]
+pub struct RecentStubs(Rc<RefCell<VecDeque<Stub>>>);
+
+impl RecentStubs {
+    fn push(&self, stub: Stub) {
+        let mut stubs = self.0.borrow_mut();
+        if stubs.len() == RECENT_STUBS_LIMIT {
+            stubs.pop_front();
+        }
+        stubs.push_back(stub);
+    }
+
+    /// Calls `f` with each recent stub, oldest first.
+    ///
+    /// Does nothing if the stubs are currently being updated.
+    pub fn for_each(&self, mut f: impl FnMut(&Stub)) {
+        if let Ok(stubs) = self.0.try_borrow() {
+            stubs.iter().for_each(&mut f);
+        }
+    }
 }
 
 #[macro_export]
@@ -1,5 +1,6 @@
 use gc_arena::Collect;
-use std::sync::Arc;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, OnceLock};
 use swf::{CharacterId, Fixed8, HeaderExt, Rectangle, TagCode, Twips};
 use thiserror::Error;
 use url::Url;
@@ -42,6 +43,9 @@ pub enum ControlFlow {
 pub type DecodeResult = Result<ControlFlow, Error>;
 pub type SwfStream<'a> = swf::read::Reader<'a>;
 
+/// A SHA-256 hash of the data of a movie.
+pub type ContentHash = [u8; 32];
+
 /// An open, fully parsed SWF movie ready to play back, either in a Player or a
 /// MovieClip.
 #[derive(Debug, Clone, Collect)]
@@ -78,6 +82,9 @@ pub struct SwfMovie {
     /// It absolutely cannot be changed after constructing
     /// the object in order to ensure proper sandboxing.
     sandbox_type: SandboxType,
+
+    /// A hash of the uncompressed SWF data, computed on first use.
+    content_hash: OnceLock<ContentHash>,
 }
 
 impl SwfMovie {
@@ -98,6 +105,7 @@ impl SwfMovie {
             compressed_len: 0,
             is_movie: false,
             sandbox_type,
+            content_hash: OnceLock::new(),
         }
     }
 
@@ -121,6 +129,7 @@ impl SwfMovie {
             encoding: swf::UTF_8,
             is_movie: false,
             sandbox_type,
+            content_hash: OnceLock::new(),
         }
     }
 
@@ -142,6 +151,7 @@ impl SwfMovie {
             encoding: swf::UTF_8,
             is_movie: false,
             sandbox_type,
+            content_hash: OnceLock::new(),
         }
     }
 
@@ -165,6 +175,7 @@ impl SwfMovie {
             compressed_len: 0,
             is_movie: false,
             sandbox_type,
+            content_hash: OnceLock::new(),
         }
     }
 
@@ -202,6 +213,7 @@ impl SwfMovie {
             compressed_len,
             is_movie: true,
             sandbox_type,
+            content_hash: OnceLock::new(),
         };
         movie.append_parameters_from_url();
         Ok(movie)
@@ -221,6 +233,7 @@ impl SwfMovie {
             compressed_len: length,
             is_movie: false,
             sandbox_type,
+            content_hash: OnceLock::new(),
         };
         movie.append_parameters_from_url();
         movie
@@ -307,6 +320,11 @@ impl SwfMovie {
         self.header.is_action_script_3()
     }
 
+    /// The XMP metadata of the SWF, an RDF document describing e.g. its title and author.
+    pub fn metadata(&self) -> Option<&str> {
+        self.header.metadata()
+    }
+
     pub fn stage_size(&self) -> &Rectangle<Twips> {
         self.header.stage_size()
     }
@@ -326,6 +344,13 @@ impl SwfMovie {
     pub fn sandbox_type(&self) -> SandboxType {
         self.sandbox_type
     }
+
+    /// A hash of the SWF data, identifying identical movies across players.
+    pub fn content_hash(&self) -> ContentHash {
+        *self
+            .content_hash
+            .get_or_init(|| Sha256::digest(&self.data).into())
+    }
 }
 
 /// A shared-ownership reference to some portion of an SWF datastream.
@@ -0,0 +1,109 @@
+//! The clocks movies read the time from, through `getTimer` and `Date`.
+//!
+//! Flash Player reads these from two different clocks: `getTimer` counts the time since the
+//! player started with a monotonic clock, while `Date` follows the system clock. Both are
+//! read when asked, so they drift apart whenever the system clock is adjusted, and only
+//! `Date` ever jumps or goes backwards.
+
+use crate::locale::get_current_date_time;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Where the player gets the current time from.
+pub trait TimeSource {
+    /// The time elapsed since the player started, as returned by `getTimer`.
+    ///
+    /// This must never go backwards.
+    fn elapsed(&self) -> Duration;
+
+    /// The current date and time, as returned by `new Date()`.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Called when the player is ticked forward by `dt`.
+    ///
+    /// Sources following a real clock don't need to do anything here.
+    fn advance(&mut self, _dt: Duration) {}
+}
+
+/// Reads the time from the clocks of the system, like Flash Player.
+pub struct SystemTimeSource {
+    start: Instant,
+}
+
+impl SystemTimeSource {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemTimeSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSource for SystemTimeSource {
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        get_current_date_time()
+    }
+}
+
+/// A clock that only moves when the player is ticked, starting at a fixed date.
+///
+/// Movies see the same times on every run given the same ticks, e.g. when replaying recorded
+/// input. `getTimer` and `Date` never drift apart.
+pub struct SimulatedTimeSource {
+    start_date: DateTime<Utc>,
+    elapsed: Duration,
+}
+
+impl SimulatedTimeSource {
+    pub fn new(start_date: DateTime<Utc>) -> Self {
+        Self {
+            start_date,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl TimeSource for SimulatedTimeSource {
+    fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.start_date + self.elapsed
+    }
+
+    fn advance(&mut self, dt: Duration) {
+        self.elapsed += dt;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn simulated_time_only_moves_when_advanced() {
+        let start_date = Utc.with_ymd_and_hms(2005, 1, 1, 0, 0, 0).unwrap();
+        let mut source = SimulatedTimeSource::new(start_date);
+        assert_eq!(source.elapsed(), Duration::ZERO);
+        assert_eq!(source.now(), start_date);
+
+        source.advance(Duration::from_millis(1500));
+        assert_eq!(source.elapsed(), Duration::from_millis(1500));
+        assert_eq!(
+            source.now(),
+            Utc.with_ymd_and_hms(2005, 1, 1, 0, 0, 1).unwrap() + Duration::from_millis(500)
+        );
+    }
+}
@@ -3,18 +3,25 @@
 //! We tick the timers during our normal frame loop for deterministic operation.
 //! The timers are stored in a priority queue, where we check if the nearest timer
 //! is ready to tick each frame.
+//!
+//! Timer callbacks may only take up a limited amount of time per update, as measured by
+//! the player's time source. When they fall behind, intervals skip the ticks they missed
+//! instead of firing in a burst later, so that they stay on their original schedule, like
+//! in Flash Player. A single callback that runs for too long is handled like any other slow
+//! script, see `UpdateContext::is_script_timed_out`.
 
 use crate::avm1::ExecutionReason;
 use crate::avm1::{
     Activation, ActivationIdentifier, Object as Avm1Object, TObject as _, Value as Avm1Value,
 };
 use crate::avm2::object::TObject;
-use crate::avm2::{Activation as Avm2Activation, Object as Avm2Object, Value as Avm2Value};
+use crate::avm2::{Activation as Avm2Activation, Avm2, Object as Avm2Object, Value as Avm2Value};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::string::AvmString;
 use gc_arena::Collect;
 use std::collections::{binary_heap::PeekMut, BinaryHeap};
+use std::time::Duration;
 
 /// Manages the collection of timers.
 pub struct Timers<'gc> {
@@ -26,6 +33,9 @@ pub struct Timers<'gc> {
 
     /// The current global time.
     cur_time: u64,
+
+    /// How long timer callbacks may run in a single update.
+    budget: Duration,
 }
 
 impl<'gc> Timers<'gc> {
@@ -42,6 +52,7 @@ impl<'gc> Timers<'gc> {
 
         let level0 = context.stage.root_clip();
 
+        let start = context.time_source.elapsed();
         let mut tick_count = 0;
 
         // We have to be careful because the timer list can be mutated while updating;
@@ -53,16 +64,19 @@ impl<'gc> Timers<'gc> {
             .unwrap_or(context.timers.cur_time)
             < context.timers.cur_time
         {
-            let timer = context.timers.peek().unwrap();
-
             tick_count += 1;
             // SANITY: Only allow so many ticks per update.
             if tick_count > Self::MAX_TICKS {
-                // Reset our time to a little bit before the nearest timer.
-                context.timers.cur_time = timer.tick_time.wrapping_sub(100);
+                context.timers.skip_missed_ticks();
+                break;
+            }
+            if context.time_source.elapsed().saturating_sub(start) > context.timers.budget {
+                context.timers.skip_missed_ticks();
                 break;
             }
 
+            let timer = context.timers.peek().unwrap();
+
             // TODO: Can we avoid these clones?
             let callback = timer.callback.clone();
             let expected_id = timer.id;
@@ -145,7 +159,12 @@ impl<'gc> Timers<'gc> {
                     match closure.call(Avm2Value::Null, &params, &mut avm2_activation) {
                         Ok(v) => v.coerce_to_boolean(),
                         Err(e) => {
-                            tracing::error!("Unhandled AVM2 error in timer callback: {e:?}",);
+                            Avm2::uncaught_error(
+                                &mut avm2_activation,
+                                None,
+                                e,
+                                "Unhandled AVM2 error in timer callback",
+                            );
                             false
                         }
                     }
@@ -189,6 +208,24 @@ impl<'gc> Timers<'gc> {
         })
     }
 
+    /// Reschedules the intervals that are overdue to their next tick in the future,
+    /// dropping the ticks that there was no time for.
+    ///
+    /// Timeouts stay overdue, and fire during the next update.
+    fn skip_missed_ticks(&mut self) {
+        let cur_time = self.cur_time;
+        let timers = std::mem::take(&mut self.timers);
+        self.timers = timers
+            .into_iter()
+            .map(|mut timer| {
+                if !timer.is_timeout && timer.tick_time < cur_time {
+                    timer.tick_time = next_tick_time(timer.tick_time, timer.interval, cur_time);
+                }
+                timer
+            })
+            .collect();
+    }
+
     /// The minimum interval we allow for timers.
     const MIN_INTERVAL: i32 = 10;
 
@@ -198,15 +235,24 @@ impl<'gc> Timers<'gc> {
     /// The scale of the timers (microseconds).
     const TIMER_SCALE: f64 = 1000.0;
 
+    /// How long timer callbacks may run per update, unless configured otherwise.
+    pub const DEFAULT_BUDGET: Duration = Duration::from_millis(100);
+
     /// Creates a new `Timers` collection.
     pub fn new() -> Self {
         Self {
             timers: Default::default(),
             timer_counter: 0,
             cur_time: 0,
+            budget: Self::DEFAULT_BUDGET,
         }
     }
 
+    /// Sets how long timer callbacks may run per update.
+    pub fn set_budget(&mut self, budget: Duration) {
+        self.budget = budget;
+    }
+
     /// The number of timers currently active.
     pub fn num_timers(&self) -> usize {
         self.timers.len()
@@ -298,6 +344,17 @@ impl<'gc> Timers<'gc> {
     }
 }
 
+/// The first tick of an interval that is due at or after `cur_time`,
+/// given that it was supposed to tick at `tick_time`.
+fn next_tick_time(tick_time: u64, interval: u64, cur_time: u64) -> u64 {
+    let next = tick_time.wrapping_add(interval);
+    if next >= cur_time || interval == 0 {
+        return next;
+    }
+    let missed = (cur_time - next).div_ceil(interval);
+    next.wrapping_add(missed * interval)
+}
+
 impl Default for Timers<'_> {
     fn default() -> Self {
         Self::new()
@@ -375,3 +432,134 @@ pub enum TimerCallback<'gc> {
         params: Vec<Avm2Value<'gc>>,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::{ArrayObject, TObject as _};
+    use crate::player::{Player, PlayerBuilder};
+    use crate::tag_utils::SwfMovie;
+    use crate::time::TimeSource;
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::cell::Cell;
+
+    /// A clock that moves forward by `step` every time it is read.
+    ///
+    /// Only the timer loop reads it, once when an update starts and once before every
+    /// callback, so each callback appears to take `step` to run.
+    struct SteppingTimeSource {
+        elapsed: Cell<Duration>,
+        step: Duration,
+    }
+
+    impl TimeSource for SteppingTimeSource {
+        fn elapsed(&self) -> Duration {
+            let elapsed = self.elapsed.get();
+            self.elapsed.set(elapsed + self.step);
+            elapsed
+        }
+
+        fn now(&self) -> DateTime<Utc> {
+            Utc.timestamp_opt(0, 0).unwrap() + self.elapsed.get()
+        }
+    }
+
+    fn with_player(step: Duration, test: impl FnOnce(&mut Player)) {
+        let player = PlayerBuilder::new()
+            .with_movie(SwfMovie::empty(8))
+            .with_time_source(Box::new(SteppingTimeSource {
+                elapsed: Cell::new(Duration::ZERO),
+                step,
+            }))
+            .build();
+        let mut player = player.lock().unwrap();
+        test(&mut player);
+    }
+
+    /// Adds `count` timers, which push to the `ticks` array of the root clip when they fire.
+    fn add_timers(player: &mut Player, interval: i32, is_timeout: bool, count: usize) {
+        player.mutate_with_update_context(|context| {
+            let root = context.stage.root_clip().expect("root");
+            let mut activation =
+                Activation::from_nothing(context, ActivationIdentifier::root("[Test]"), root);
+            let ticks: Avm1Object<'_> = ArrayObject::empty(&activation).into();
+            let root_object = root.object().coerce_to_object(&mut activation);
+            root_object
+                .set("ticks", ticks.into(), &mut activation)
+                .unwrap();
+
+            for _ in 0..count {
+                activation.context.timers.add_timer(
+                    TimerCallback::Avm1Method {
+                        this: ticks,
+                        method_name: "push".into(),
+                        params: vec![true.into()],
+                    },
+                    interval,
+                    is_timeout,
+                );
+            }
+        });
+    }
+
+    /// How many times the timers added by `add_timers` have fired.
+    fn ticks(player: &mut Player) -> i32 {
+        player.mutate_with_update_context(|context| {
+            let root = context.stage.root_clip().expect("root");
+            let mut activation =
+                Activation::from_nothing(context, ActivationIdentifier::root("[Test]"), root);
+            let root_object = root.object().coerce_to_object(&mut activation);
+            let ticks = root_object.get("ticks", &mut activation).unwrap();
+            ticks
+                .coerce_to_object(&mut activation)
+                .length(&mut activation)
+                .unwrap()
+        })
+    }
+
+    fn update(player: &mut Player, dt: f64) -> Option<f64> {
+        player.mutate_with_update_context(|context| Timers::update_timers(context, dt))
+    }
+
+    #[test]
+    fn next_tick_time_stays_on_schedule() {
+        assert_eq!(next_tick_time(100, 100, 150), 200);
+        assert_eq!(next_tick_time(100, 100, 200), 200);
+        assert_eq!(next_tick_time(100, 100, 350), 400);
+        assert_eq!(next_tick_time(100, 100, 400), 400);
+        assert_eq!(next_tick_time(100, 0, 350), 100);
+    }
+
+    #[test]
+    fn late_interval_fires_once_and_realigns() {
+        // Only the first callback fits within the budget.
+        with_player(Timers::DEFAULT_BUDGET * 6 / 10, |player| {
+            add_timers(player, 100, false, 1);
+
+            // The ticks at 200ms and 300ms are skipped.
+            assert_eq!(update(player, 350.0), Some(50.0));
+            assert_eq!(ticks(player), 1);
+
+            assert_eq!(update(player, 60.0), Some(90.0));
+            assert_eq!(ticks(player), 2);
+        });
+    }
+
+    #[test]
+    fn budget_defers_callbacks_to_next_update() {
+        // Three callbacks fit within the budget.
+        with_player(Timers::DEFAULT_BUDGET * 3 / 10, |player| {
+            add_timers(player, 10, true, 5);
+
+            update(player, 20.0);
+            assert_eq!(ticks(player), 3);
+            assert_eq!(
+                player.mutate_with_update_context(|c| c.timers.num_timers()),
+                2
+            );
+
+            assert_eq!(update(player, 0.0), None);
+            assert_eq!(ticks(player), 5);
+        });
+    }
+}
@@ -1 +1,2 @@
 context-menu-exit-fullscreen = Exit Full Screen
+context-menu-settings = Settings...
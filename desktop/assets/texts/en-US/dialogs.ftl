@@ -1,2 +1,24 @@
 dialog-ok = OK
 dialog-cancel = Cancel
+
+storage-quota-dialog-title = Local Storage
+storage-quota-dialog-message = { $site } is requesting permission to store up to { $size } of information on your computer.
+storage-quota-dialog-allow = Allow
+storage-quota-dialog-deny = Deny
+
+local-storage-dialog = Local Storage
+local-storage-dialog-empty = No movie has stored any information yet.
+local-storage-dialog-site = { $site } ({ $used } of { $limit })
+local-storage-dialog-export = Export...
+local-storage-dialog-remove-site = Remove All
+local-storage-dialog-import = Import from Flash Player...
+
+sitelock-report = Sitelock Report
+sitelock-report-no-movie = No movie is playing.
+sitelock-report-disabled = Sitelock detection is disabled for this movie.
+sitelock-report-reload = Reload with Detection
+sitelock-report-no-checks = The movie hasn't checked where it is running yet.
+sitelock-report-checks = The movie checked where it is running through:
+sitelock-report-no-expected-urls = The movie hasn't compared its location to any site yet.
+sitelock-report-expected-urls = The movie compared its location to these sites:
+sitelock-report-spoof = Reload on This Site
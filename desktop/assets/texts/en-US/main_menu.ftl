@@ -10,16 +10,21 @@
 file-menu = File
 file-menu-open-quick = Open...
 file-menu-open-advanced = Open Advanced...
+file-menu-open-url = Open URL...
 file-menu-close = Close
 file-menu-reload = Reload
 file-menu-recents = Recents
 file-menu-recents-empty = No recent entries
 file-menu-preferences = Preferences...
+file-menu-local-storage = Manage Local Storage...
+file-menu-export-saves = Export All Saves...
+file-menu-import-saves = Import Saves...
 file-menu-exit = Exit
 
 controls-menu = Controls
 controls-menu-suspend = Suspend
 controls-menu-resume = Resume
+controls-menu-fast-forward = Fast Forward
 controls-menu-volume = Volume controls
 
 help-menu = Help
@@ -39,6 +44,31 @@ debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-open-domain-list = Show Domains
 debug-menu-search-display-objects = Search Display Objects...
+debug-menu-sitelock-report = Sitelock Report...
+debug-menu-dump-heap = Dump Heap...
+debug-menu-dump-opcode-counts = Dump Opcode Counts...
+debug-menu-frame-pacing-stats = Show Frame Pacing Stats
+
+frame-pacing-movie-frame-rate = Movie frames: { $rate }/s
+frame-pacing-present-rate = Presented frames: { $rate }/s
+frame-pacing-presents-per-frame = Presents per movie frame: { $ratio }
+frame-pacing-longest-interval = Longest present interval: { $milliseconds } ms
+frame-pacing-skipped-renders = Redundant renders skipped: { $count }
 
 view-menu = View
 view-menu-fullscreen = Full Screen
+
+movie-shortened = Shortened Movie
+movie-shortened-tooltip =
+    This movie is truncated. Only its complete frames are played, and it stops after the last one.
+
+action-add-bookmark = Add Bookmark...
+action-toggle-playing = Suspend or Resume
+action-toggle-mute = Mute or Unmute
+action-screenshot = Save Screenshot...
+action-command-palette = Command Palette...
+
+command-palette = Command Palette
+command-palette-hint = Search for an action...
+command-palette-no-matches = No matching actions
+command-palette-open-bookmark = Open Bookmark: { $name }
@@ -1,2 +1,6 @@
 message-dialog-root-movie-load-error-title = Movie Failed to Load
 message-dialog-root-movie-load-error-description = Failed to open or download this movie.
+message-dialog-player-crashed-title = Movie Stopped
+message-dialog-player-crashed-description = Ruffle encountered an error while playing this movie, so it has been stopped. This is a bug.
+message-dialog-preferences-recovered-title = Preferences Restored
+message-dialog-preferences-recovered-description = Your preferences could not be read, possibly because Ruffle stopped while saving them. They have been restored from a backup, so your most recent changes may be missing.
@@ -5,3 +5,8 @@ open-dialog-path = File or URL
 open-dialog-add-parameter = Add
 open-dialog-remove-parameter = Remove
 open-dialog-remove-parameters = Remove all
+
+open-location-dialog = Open URL
+open-location-dialog-url = URL
+open-location-dialog-recent = Recent
+open-location-dialog-open = Open
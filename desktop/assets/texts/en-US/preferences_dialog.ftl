@@ -4,11 +4,38 @@ preference-locked-by-cli = Read-Only (Set by CLI)
 
 graphics-backend = Graphics Backend
 graphics-backend-default = Default
+graphics-backend-software = Software (CPU)
 
 graphics-power = Power Preference
 graphics-power-low = Low (e.g. iGPU)
 graphics-power-high = High (e.g. GPU)
 
+gpu-memory-budget = GPU Memory Budget
+gpu-memory-budget-tooltip =
+    The most GPU memory to use for bitmaps and shapes, or 0 for no limit.
+    Bitmaps and shapes that haven't been shown recently are removed from the GPU when over budget.
+    Changes apply to the next movie that is opened.
+
+blend-space = Blending
+blend-space-flash = Like Flash Player
+blend-space-linear = Linear (Gamma-Correct)
+blend-space-tooltip =
+    How transparent shapes and antialiased edges are blended.
+    Flash Player blends without gamma correction, which darkens soft edges and gradients.
+    Linear blending looks smoother, but movies may not look exactly as their authors intended.
+    Changes apply to the next movie that is opened.
+
+morph-shape-cache-size = Shape Tween Cache
+morph-shape-cache-size-tooltip =
+    How many frames of each shape tween to keep ready for drawing, or 0 for the default.
+    Larger caches make shape tweens smoother in long animations, but use more memory.
+    Changes apply to the next movie that is opened.
+
+vsync = Vertical Sync
+vsync-tooltip =
+    Wait for the display to refresh before showing each frame.
+    Turning this off can reduce input latency, but may cause tearing.
+
 language = Language
 
 audio-output-device = Audio Output Device
@@ -22,10 +49,55 @@ log-filename-pattern = Log Filename
 log-filename-pattern-single-file = Single File (ruffle.log)
 log-filename-pattern-with-timestamp = With Timestamp
 
+crash-reports = Save Crash Reports
+crash-reports-tooltip =
+    When Ruffle crashes, save a report describing the crash to your configuration folder.
+    Reports are never sent anywhere; you may choose to attach them to a bug report yourself.
+
+movie-trace-files = Separate Trace File per Movie
+movie-trace-files-tooltip =
+    Write the traces of each movie to its own file in the log folder, named after the movie.
+    This helps when a movie loads other movies, as their traces no longer get mixed up.
+mirror-movie-traces = Also Log Traces to Main Log
+
 storage-backend = Save-File Location
 storage-backend-disk = Disk
 storage-backend-memory = Memory
 
+air-sandbox-directory = AIR Filesystem Folder
+air-sandbox-directory-tooltip =
+    AIR content can read and write files inside this folder.
+    The application storage, documents and desktop directories are mapped to folders within it.
+air-sandbox-directory-disabled = Disabled
+air-sandbox-directory-disable = Disable
+
+network-timeout = Network Timeout
+network-timeout-tooltip =
+    How long a movie may wait for a network request, or 0 to wait as long as needed.
+    Changes apply to the next movie that is opened.
+network-retries = Network Retries
+network-retries-tooltip =
+    How many times to retry a network request that failed for a temporary reason.
+    Only requests that don't send data, like loading files, are retried.
+    Changes apply to the next movie that is opened.
+offline = Offline Mode
+offline-tooltip =
+    Don't access the network, so movies can only load local files.
+    Changes apply to the next movie that is opened.
+relaxed-security = Relaxed Security
+relaxed-security-tooltip =
+    Let movies reach content outside their security sandbox, like local movies loading from the network.
+    Only enable this for movies you trust.
+    Changes apply to the next movie that is opened.
+
+mouse-wheel-scale = Mouse Wheel Speed
+
+coalesce-mouse-moves = Coalesce Mouse Moves
+coalesce-mouse-moves-tooltip =
+    Handle only the last of several mouse moves that happen before the movie updates.
+    This keeps fast mice from slowing movies down. Disable it if a movie misses quick movements.
+    Changes apply to the next movie that is opened.
+
 recent-limit = Recent Limit
 recent-clear = Clear
 
@@ -41,3 +113,19 @@ gamemode-tooltip =
     Ruffle requests GameMode only when a movie is being played.
 gamemode-default = Default
 gamemode-default-tooltip = GameMode will be enabled only when power preference is set to high.
+
+focus-loss = When Unfocused
+focus-loss-tooltip =
+    What the movie does while the Ruffle window isn't focused.
+    Throttling or pausing movies in the background saves battery.
+focus-loss-keep-running = Keep Running
+focus-loss-throttle = Throttle
+focus-loss-pause = Pause and Mute
+
+background-frame-rate = Background Frame Rate
+
+keyboard-shortcuts = Keyboard Shortcuts
+keyboard-shortcuts-help =
+    Type shortcuts like Ctrl+Shift+P, where Ctrl is Cmd on macOS.
+    Leave a shortcut empty to disable it, for example if it's needed by a game.
+keyboard-shortcuts-conflict = "{ $first }" and "{ $second }" have the same shortcut, so only "{ $first }" can be used.
@@ -5,6 +5,7 @@ movie-parameters = Movie Parameters
 custom-base-url = Custom Base URL
 
 spoof-swf-url = Spoof SWF URL
+remember-movie-urls = Remember URLs for This Movie
 referer-url = Referer URL
 cookie = Cookie
 
@@ -92,3 +93,13 @@ custom-framerate = Custom Framerate
 custom-framerate-suffix = {" "} fps
 
 dummy-external-interface = Dummy External Interface
+
+relaxed-security = Relaxed Security
+relaxed-security-tooltip =
+    Allow all loaded movies to script each other, regardless of where they were loaded from.
+    Only use this for testing content you trust.
+
+partial-playback = Partial Playback
+partial-playback-tooltip =
+    Play truncated movies, e.g. from incomplete downloads, up to their last complete frame.
+    The movie stops there instead of looping.
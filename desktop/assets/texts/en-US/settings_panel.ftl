@@ -0,0 +1,19 @@
+settings-panel-title = Settings
+
+settings-panel-privacy = Privacy
+settings-panel-local-storage = Local Storage
+settings-panel-display = Display
+
+settings-panel-privacy-message = Allow { $site } to access your camera and microphone?
+settings-panel-camera = Camera
+settings-panel-microphone = Microphone
+settings-panel-ask = Ask
+settings-panel-allow = Allow
+settings-panel-deny = Deny
+
+settings-panel-local-storage-message = How much information may { $site } store on your computer?
+settings-panel-storage-none = None
+settings-panel-storage-unlimited = Unlimited
+
+settings-panel-hardware-acceleration = Enable hardware acceleration
+settings-panel-hardware-acceleration-reload = This will take effect the next time a movie is opened.
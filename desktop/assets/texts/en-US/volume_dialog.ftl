@@ -1,3 +1,5 @@
 volume-controls = Volume controls
 volume-controls-mute = Mute
 volume-controls-volume = Volume
+volume-controls-audio-enhancements = Audio enhancements
+volume-controls-audio-enhancements-tooltip = Adds equalization and reverb to improve the sound of old movies. This doesn't sound like Flash Player did.
@@ -1,24 +1,32 @@
+use crate::backends::DesktopUiBackend;
+use crate::cli::FocusLossPreference;
+use crate::crash_report::MovieInfo;
 use crate::custom_event::RuffleEvent;
-use crate::gui::{GuiController, MENU_HEIGHT};
+use crate::gui::dialogs::message_dialog::MessageDialogConfiguration;
+use crate::gui::{DialogDescriptor, GuiController, LocalizableText, MENU_HEIGHT};
 use crate::player::{LaunchOptions, PlayerController};
 use crate::preferences::GlobalPreferences;
 use crate::util::{
-    get_screen_size, gilrs_button_to_gamepad_button, parse_url, plot_stats_in_tracy,
-    winit_to_ruffle_key_code, winit_to_ruffle_text_control,
+    get_screen_size, gilrs_button_to_gamepad_button, gilrs_game_input_control,
+    gilrs_game_input_device, gilrs_game_input_id, parse_url, plot_stats_in_tracy,
+    winit_to_ruffle_key_code, winit_to_ruffle_key_location, winit_to_ruffle_text_control,
 };
+use crate::MOVIE_INFO;
 use anyhow::Error;
 use gilrs::{Event, EventType, Gilrs};
+use ruffle_core::config::{ScreenInfo, ScreenResolution};
 use ruffle_core::swf::HeaderExt;
 use ruffle_core::PlayerEvent;
 use ruffle_render::backend::ViewportDimensions;
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use url::Url;
 use winit::application::ApplicationHandler;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
 use winit::event::{ElementState, KeyEvent, Modifiers, StartCause, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy};
-use winit::keyboard::{Key, NamedKey};
+use winit::keyboard::{Key, NamedKey, PhysicalKey};
 use winit::window::{Fullscreen, Icon, WindowAttributes, WindowId};
 
 struct MainWindow {
@@ -26,8 +34,14 @@ struct MainWindow {
     gui: GuiController,
     player: PlayerController,
     minimized: bool,
+    focused: bool,
+    /// Whether the movie was paused because the window lost focus, so it resumes on focus.
+    paused_by_focus_loss: bool,
     mouse_pos: PhysicalPosition<f64>,
     modifiers: Modifiers,
+    alt_graph: bool,
+    /// The characters typed by the keys that are held down, as FP reports them again on key up.
+    key_chars: HashMap<PhysicalKey, char>,
     min_window_size: LogicalSize<u32>,
     max_window_size: PhysicalSize<u32>,
     no_gui: bool,
@@ -45,13 +59,20 @@ impl MainWindow {
         if matches!(event, WindowEvent::RedrawRequested) {
             // Don't render when minimized to avoid potential swap chain errors in `wgpu`.
             if !self.minimized {
-                if let Some(mut player) = self.player.get() {
-                    // Even if the movie is paused, user interaction with debug tools can change the render output
-                    player.render();
-                    self.gui.render(Some(player));
-                } else {
-                    self.gui.render(None);
+                // Only render the movie if it has changed since it was last rendered;
+                // otherwise the previous frame is presented again.
+                let movie_needs_render = self
+                    .player
+                    .get()
+                    .is_some_and(|player| player.needs_render());
+                if self
+                    .gui
+                    .frame_pacer_mut()
+                    .should_render_movie(movie_needs_render)
+                {
+                    self.player.catch_panic(|player| player.render());
                 }
+                self.gui.render(self.player.get());
                 plot_stats_in_tracy(&self.gui.descriptors().wgpu_instance);
             }
 
@@ -62,6 +83,8 @@ impl MainWindow {
 
         if self.gui.handle_event(&event) {
             // Event consumed by GUI.
+            // Even if the movie is paused, user interaction with debug tools can change the render output
+            self.gui.frame_pacer_mut().invalidate();
             return;
         }
         let height_offset = if self.gui.window().fullscreen().is_some() || self.no_gui {
@@ -76,6 +99,7 @@ impl MainWindow {
             WindowEvent::Resized(size) => {
                 // TODO: Change this when winit adds a `Window::minimized` or `WindowEvent::Minimize`.
                 self.minimized = size.width == 0 && size.height == 0;
+                self.gui.frame_pacer_mut().invalidate();
 
                 if let Some(mut player) = self.player.get() {
                     let viewport_scale_factor = self.gui.window().scale_factor();
@@ -85,11 +109,16 @@ impl MainWindow {
                         scale_factor: viewport_scale_factor,
                     });
                 }
+                self.update_screen();
                 self.gui.window().request_redraw();
                 if matches!(self.loaded, LoadingState::WaitingForResize) {
                     self.loaded = LoadingState::Loaded;
                 }
             }
+            WindowEvent::Moved(_) => {
+                // The window may have moved to another monitor.
+                self.update_screen();
+            }
             WindowEvent::CursorMoved { position, .. } => {
                 if self.gui.is_context_menu_visible() {
                     return;
@@ -104,6 +133,17 @@ impl MainWindow {
                 self.check_redraw();
             }
             WindowEvent::DroppedFile(file) => {
+                // A movie waiting for the user to pick a file with `FileReference.browse()`
+                // gets the dropped file, instead of it being opened as a new movie.
+                let accepted = self.player.get().is_some_and(|mut player| {
+                    player
+                        .ui_mut()
+                        .downcast_mut::<DesktopUiBackend>()
+                        .is_some_and(|ui| ui.accept_dropped_file(&file))
+                });
+                if accepted {
+                    return;
+                }
                 if let Ok(url) = parse_url(&file) {
                     self.gui.create_movie(
                         &mut self.player,
@@ -113,10 +153,26 @@ impl MainWindow {
                 }
             }
             WindowEvent::Focused(true) => {
+                self.focused = true;
                 self.player.handle_event(PlayerEvent::FocusGained);
+                if std::mem::take(&mut self.paused_by_focus_loss) {
+                    if let Some(mut player) = self.player.get() {
+                        player.set_is_playing(true);
+                    }
+                }
             }
             WindowEvent::Focused(false) => {
+                self.focused = false;
                 self.player.handle_event(PlayerEvent::FocusLost);
+                if self.preferences.focus_loss_preference() == FocusLossPreference::Pause {
+                    // Pausing the player pauses its sounds too.
+                    if let Some(mut player) = self.player.get() {
+                        if player.is_playing() {
+                            player.set_is_playing(false);
+                            self.paused_by_focus_loss = true;
+                        }
+                    }
+                }
             }
             WindowEvent::MouseInput { button, state, .. } => {
                 if self.gui.is_context_menu_visible() {
@@ -170,12 +226,26 @@ impl MainWindow {
 
                 use ruffle_core::events::MouseWheelDelta;
                 use winit::event::MouseScrollDelta;
-                let delta = match delta {
-                    MouseScrollDelta::LineDelta(_, dy) => MouseWheelDelta::Lines(dy.into()),
-                    MouseScrollDelta::PixelDelta(pos) => MouseWheelDelta::Pixels(pos.y),
+                let (horizontal, vertical) = match delta {
+                    MouseScrollDelta::LineDelta(dx, dy) => (
+                        MouseWheelDelta::Lines(dx.into()),
+                        MouseWheelDelta::Lines(dy.into()),
+                    ),
+                    MouseScrollDelta::PixelDelta(pos) => (
+                        MouseWheelDelta::Pixels(pos.x),
+                        MouseWheelDelta::Pixels(pos.y),
+                    ),
                 };
-                let event = PlayerEvent::MouseWheel { delta };
-                self.player.handle_event(event);
+                let scale = self.preferences.mouse_wheel_scale();
+                if vertical.lines() != 0.0 {
+                    let delta = vertical.scaled(scale);
+                    self.player.handle_event(PlayerEvent::MouseWheel { delta });
+                }
+                if horizontal.lines() != 0.0 {
+                    let delta = horizontal.scaled(scale);
+                    self.player
+                        .handle_event(PlayerEvent::MouseHorizontalWheel { delta });
+                }
                 self.check_redraw();
             }
             WindowEvent::CursorEntered { .. } => {
@@ -213,15 +283,32 @@ impl MainWindow {
                         .send_event(RuffleEvent::ExitFullScreen);
                 }
 
-                let key_code = winit_to_ruffle_key_code(&event);
+                if event.logical_key == Key::Named(NamedKey::AltGraph) {
+                    self.alt_graph = event.state == ElementState::Pressed;
+                }
+
+                let key_code = winit_to_ruffle_key_code(&event, self.alt_graph);
+                let key_location = winit_to_ruffle_key_location(event.location);
                 // [NA] TODO: This event used to give a single char. `last()` is functionally the same,
                 // but we may want to be better at this in the future.
-                let key_char = event.text.clone().and_then(|text| text.chars().last());
+                let key_char = match event.state {
+                    ElementState::Pressed => {
+                        let key_char = event.text.clone().and_then(|text| text.chars().last());
+                        if let Some(key_char) = key_char {
+                            self.key_chars.insert(event.physical_key, key_char);
+                        }
+                        key_char
+                    }
+                    ElementState::Released => self.key_chars.remove(&event.physical_key),
+                };
 
                 match (key_code, &event.state) {
                     (Some(key_code), ElementState::Pressed) => {
-                        self.player
-                            .handle_event(PlayerEvent::KeyDown { key_code, key_char });
+                        self.player.handle_event(PlayerEvent::KeyDown {
+                            key_code,
+                            key_char,
+                            key_location,
+                        });
                         if let Some(control_code) =
                             winit_to_ruffle_text_control(&event, &self.modifiers)
                         {
@@ -235,8 +322,11 @@ impl MainWindow {
                         }
                     }
                     (Some(key_code), ElementState::Released) => {
-                        self.player
-                            .handle_event(PlayerEvent::KeyUp { key_code, key_char });
+                        self.player.handle_event(PlayerEvent::KeyUp {
+                            key_code,
+                            key_char,
+                            key_location,
+                        });
                     }
                     _ => {}
                 };
@@ -246,7 +336,39 @@ impl MainWindow {
         }
     }
 
+    /// Lets the movie know about the monitor the window is on.
+    fn update_screen(&mut self) {
+        // The dpi that a scale factor of 1 stands for on this platform.
+        let base_dpi = if cfg!(target_os = "macos") {
+            72.0
+        } else {
+            96.0
+        };
+        let screen = self.gui.window().current_monitor().map(|monitor| {
+            let size = monitor.size();
+            ScreenInfo {
+                resolution: ScreenResolution {
+                    width: size.width,
+                    height: size.height,
+                },
+                dpi: base_dpi * monitor.scale_factor(),
+            }
+        });
+        if let Some(mut player) = self.player.get() {
+            player.set_screen(screen);
+        }
+    }
+
     fn on_metadata(&mut self, swf_header: HeaderExt) {
+        // Hashing the movie is only worthwhile if it may end up in a crash report.
+        if let Some(player) = self
+            .player
+            .get()
+            .filter(|_| self.preferences.crash_reports_enabled())
+        {
+            MOVIE_INFO.with(|i| *i.borrow_mut() = Some(MovieInfo::new(player.swf())));
+        }
+
         let height_offset = if self.gui.window().fullscreen().is_some() || self.no_gui {
             0.0
         } else {
@@ -340,8 +462,8 @@ impl MainWindow {
         }
     }
 
-    fn about_to_wait(&mut self, gilrs: Option<&mut Gilrs>) {
-        if let Some(Event { event, .. }) = gilrs.and_then(|gilrs| gilrs.next_event()) {
+    fn handle_gamepad_events(&mut self, gilrs: &mut Gilrs) {
+        while let Some(Event { id, event, .. }) = gilrs.next_event() {
             match event {
                 EventType::ButtonPressed(button, _) => {
                     if let Some(button) = gilrs_button_to_gamepad_button(button) {
@@ -359,27 +481,116 @@ impl MainWindow {
                 }
                 _ => {}
             }
+
+            if let Some((control, value)) = gilrs_game_input_control(&event) {
+                if let Some(mut player) = self.player.get() {
+                    player.set_game_input_value(&gilrs_game_input_id(id), control, value);
+                }
+            }
+        }
+
+        self.sync_game_input_devices(gilrs);
+    }
+
+    /// Makes the `GameInput` devices of the player match the gamepads that are connected,
+    /// which also tells a newly loaded movie about the gamepads that were already there.
+    fn sync_game_input_devices(&mut self, gilrs: &Gilrs) {
+        let Some(mut player) = self.player.get() else {
+            return;
+        };
+        let connected: Vec<_> = gilrs.gamepads().collect();
+        let removed: Vec<String> = player
+            .game_input_devices()
+            .iter()
+            .map(|device| device.id.clone())
+            .filter(|id| {
+                !connected
+                    .iter()
+                    .any(|(gilrs_id, _)| gilrs_game_input_id(*gilrs_id) == *id)
+            })
+            .collect();
+        for id in removed {
+            player.remove_game_input_device(&id);
+        }
+        for (id, gamepad) in connected {
+            let device_id = gilrs_game_input_id(id);
+            if !player
+                .game_input_devices()
+                .iter()
+                .any(|device| device.id == device_id)
+            {
+                player.add_game_input_device(gilrs_game_input_device(id, &gamepad));
+            }
+        }
+    }
+
+    fn about_to_wait(&mut self, gilrs: Option<&mut Gilrs>) {
+        if let Some(gilrs) = gilrs {
+            self.handle_gamepad_events(gilrs);
         }
 
         // Core loop
         // [NA] This used to be called `MainEventsCleared`, but I think the behaviour is different now.
         // We should look at changing our tick to happen somewhere else if we see any behavioural problems.
         if matches!(self.loaded, LoadingState::Loaded) {
+            // An idle movie isn't ticked until input (or a finished load) wakes it up.
+            // Its clock is held still meanwhile, so that waking up doesn't run every
+            // frame it slept through at once.
+            if self.player.get().is_some_and(|player| player.is_idle()) {
+                self.time = Instant::now();
+                self.next_frame_time = None;
+                return;
+            }
+
             let new_time = Instant::now();
+            let throttle_interval = self.throttle_interval();
+            if let Some(interval) = throttle_interval {
+                if new_time < self.time + interval {
+                    self.next_frame_time = Some(self.time + interval);
+                    return;
+                }
+            }
             let dt = new_time.duration_since(self.time).as_micros();
             if dt > 0 {
                 self.time = new_time;
-                if let Some(mut player) = self.player.get() {
-                    player.tick(dt as f64 / 1000.0);
-                    self.next_frame_time = Some(new_time + player.time_til_next_frame());
-                } else {
-                    self.next_frame_time = None;
+                let result = self.player.catch_panic(|player| {
+                    let frames_run = player.frames_run();
+                    let mut dt = dt as f64 / 1000.0;
+                    if throttle_interval.is_some() {
+                        // Run at most one frame per update in the background,
+                        // rather than catching up on every frame in between.
+                        dt = dt.min(1000.0 / player.frame_rate());
+                    }
+                    player.tick(dt);
+                    (
+                        player.frames_run() - frames_run,
+                        player.time_til_next_frame(),
+                    )
+                });
+                if let Some((frames_run, _)) = result {
+                    for _ in 0..frames_run {
+                        self.gui.frame_pacer_mut().record_movie_frame(new_time);
+                    }
                 }
+                self.next_frame_time = result.map(|(_, time_til_next_frame)| {
+                    new_time + time_til_next_frame.max(throttle_interval.unwrap_or_default())
+                });
                 self.check_redraw();
             }
         }
     }
 
+    /// The least time between two updates of the movie, if it's throttled in the background.
+    fn throttle_interval(&self) -> Option<Duration> {
+        if self.focused || self.preferences.focus_loss_preference() != FocusLossPreference::Throttle
+        {
+            return None;
+        }
+        Some(Duration::from_secs_f64(
+            1.0 / self.preferences.background_frame_rate(),
+        ))
+    }
+
     fn check_redraw(&self) {
         let player = self.player.get();
         if player.map(|p| p.needs_render()).unwrap_or_default() || self.gui.needs_render() {
@@ -412,6 +623,11 @@ impl App {
             .ok();
         let event_loop_proxy = event_loop.create_proxy();
 
+        #[cfg(feature = "automation")]
+        if let Some(port) = preferences.cli.automation_port {
+            crate::automation::start_server(port, event_loop_proxy.clone())?;
+        }
+
         Ok((
             Self {
                 main_window: None,
@@ -484,6 +700,19 @@ impl ApplicationHandler<RuffleEvent> for App {
                 gui.show_open_dialog();
             }
 
+            if preferences.recovered_from_backup() {
+                gui.open_dialog(DialogDescriptor::ShowMessage(
+                    MessageDialogConfiguration::new(
+                        LocalizableText::LocalizedText(
+                            "message-dialog-preferences-recovered-title",
+                        ),
+                        LocalizableText::LocalizedText(
+                            "message-dialog-preferences-recovered-description",
+                        ),
+                    ),
+                ));
+            }
+
             let mut loaded = LoadingState::Loading;
 
             if movie_url.is_none() {
@@ -504,8 +733,12 @@ impl ApplicationHandler<RuffleEvent> for App {
                 start_fullscreen,
                 loaded,
                 minimized: false,
+                focused: true,
+                paused_by_focus_loss: false,
                 mouse_pos: PhysicalPosition::new(0.0, 0.0),
                 modifiers: Modifiers::default(),
+                alt_graph: false,
+                key_chars: HashMap::new(),
                 time: Instant::now(),
                 next_frame_time: None,
                 event_loop_proxy,
@@ -547,6 +780,7 @@ impl ApplicationHandler<RuffleEvent> for App {
                 main_window
                     .gui
                     .create_movie(&mut main_window.player, *options, url);
+                main_window.update_screen();
             }
 
             (Some(main_window), RuffleEvent::OpenDialog(descriptor)) => {
@@ -574,6 +808,20 @@ impl ApplicationHandler<RuffleEvent> for App {
                 }
             }
 
+            #[cfg(feature = "automation")]
+            (main_window, RuffleEvent::Automation(request)) => {
+                let result = match main_window {
+                    Some(main_window) => {
+                        let result =
+                            crate::automation::execute(&mut main_window.player, request.command);
+                        main_window.check_redraw();
+                        result
+                    }
+                    None => Err("The player hasn't started yet".to_string()),
+                };
+                let _ = request.reply.send(result);
+            }
+
             (_, RuffleEvent::ExitRequested) => {
                 event_loop.exit();
             }
@@ -612,6 +860,10 @@ impl ApplicationHandler<RuffleEvent> for App {
                     // It's sooner than ours, don't delay it
                     _ => {}
                 }
+            } else {
+                // Nothing to update until the next event, so don't keep waking up for
+                // a frame that was due before the movie went idle.
+                event_loop.set_control_flow(ControlFlow::Wait);
             }
         }
     }
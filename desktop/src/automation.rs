@@ -0,0 +1,304 @@
+//! A server that lets external scripts control the player, for automated testing of movies.
+//!
+//! When started with `--automation-port`, Ruffle accepts WebSocket connections on that port
+//! of the loopback interface. Each text message is a JSON request naming a `command`,
+//! and optionally an `id` that is copied to the response:
+//!
+//! ```json
+//! {"id": 1, "command": "play"}
+//! {"id": 2, "command": "pause"}
+//! {"id": 3, "command": "goto_frame", "frame": 10, "play": false}
+//! {"id": 4, "command": "send_input", "event": {"type": "MouseDown", "pos": [20, 30], "btn": "Left"}}
+//! {"id": 5, "command": "read_property", "path": "hero.health"}
+//! {"id": 6, "command": "screenshot"}
+//! {"id": 7, "command": "set_random_seed", "seed": 1234}
+//! ```
+//!
+//! Input events use the format of the input files of our tests. Mouse positions are in
+//! pixels of the area the movie is drawn in.
+//!
+//! Responses are either `{"id": 1, "ok": true, "result": ...}` or
+//! `{"id": 1, "ok": false, "error": "..."}`. Properties are read as `ExternalInterface`
+//! would pass them to JavaScript, and screenshots are returned as base64 encoded PNGs.
+
+use crate::custom_event::RuffleEvent;
+use crate::player::PlayerController;
+use anyhow::Context;
+use base64::Engine;
+use ruffle_core::events::{
+    KeyCode, KeyLocation, MouseButton, MouseWheelDelta, PlayerEvent, TextControlCode,
+};
+use ruffle_core::external::Value as ExternalValue;
+use ruffle_input_format::{
+    AutomatedEvent, MouseButton as InputMouseButton, TextControlCode as InputTextControlCode,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::io::Cursor;
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::mpsc;
+use tungstenite::Message;
+use winit::event_loop::EventLoopProxy;
+
+/// A command sent by an automation client.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    Play,
+    Pause,
+    GotoFrame {
+        frame: u16,
+        #[serde(default)]
+        play: bool,
+    },
+    SendInput {
+        event: AutomatedEvent,
+    },
+    ReadProperty {
+        path: String,
+    },
+    Screenshot,
+    SetRandomSeed {
+        seed: u64,
+    },
+}
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: serde_json::Value,
+    #[serde(flatten)]
+    command: Command,
+}
+
+/// A command to execute on the main thread, with the channel to send its result back on.
+pub struct AutomationRequest {
+    pub command: Command,
+    pub reply: mpsc::Sender<Result<serde_json::Value, String>>,
+}
+
+/// Starts listening for automation clients on a port of the loopback interface.
+pub fn start_server(port: u16, event_loop: EventLoopProxy<RuffleEvent>) -> anyhow::Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .with_context(|| format!("Couldn't start the automation server on port {port}"))?;
+    tracing::info!("Automation server listening on {}", listener.local_addr()?);
+
+    std::thread::Builder::new()
+        .name("automation-server".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let event_loop = event_loop.clone();
+                        std::thread::spawn(move || serve_client(stream, event_loop));
+                    }
+                    Err(e) => tracing::warn!("Couldn't accept automation client: {e}"),
+                }
+            }
+        })?;
+    Ok(())
+}
+
+fn serve_client(stream: TcpStream, event_loop: EventLoopProxy<RuffleEvent>) {
+    let mut socket = match tungstenite::accept(stream) {
+        Ok(socket) => socket,
+        Err(e) => {
+            tracing::warn!("Automation client failed to connect: {e}");
+            return;
+        }
+    };
+
+    loop {
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => return,
+            Ok(_) => continue,
+        };
+
+        let response = match serde_json::from_str::<Request>(&text) {
+            Ok(request) => {
+                let result = run_on_main_thread(&event_loop, request.command);
+                to_response(request.id, result)
+            }
+            Err(e) => to_response(
+                serde_json::Value::Null,
+                Err(format!("Invalid request: {e}")),
+            ),
+        };
+
+        if socket.send(Message::Text(response.to_string())).is_err() {
+            return;
+        }
+    }
+}
+
+fn run_on_main_thread(
+    event_loop: &EventLoopProxy<RuffleEvent>,
+    command: Command,
+) -> Result<serde_json::Value, String> {
+    let (reply, receiver) = mpsc::channel();
+    event_loop
+        .send_event(RuffleEvent::Automation(AutomationRequest {
+            command,
+            reply,
+        }))
+        .map_err(|_| "Ruffle is shutting down".to_string())?;
+    receiver
+        .recv()
+        .map_err(|_| "Ruffle is shutting down".to_string())?
+}
+
+fn to_response(
+    id: serde_json::Value,
+    result: Result<serde_json::Value, String>,
+) -> serde_json::Value {
+    match result {
+        Ok(result) => json!({ "id": id, "ok": true, "result": result }),
+        Err(error) => json!({ "id": id, "ok": false, "error": error }),
+    }
+}
+
+/// Executes a command on the player of the main window.
+pub fn execute(
+    player: &mut PlayerController,
+    command: Command,
+) -> Result<serde_json::Value, String> {
+    if let Command::SendInput { event } = &command {
+        let event = to_player_event(event)?;
+        return Ok(player.handle_event(event).into());
+    }
+
+    let Some(mut player) = player.get() else {
+        return Err("No movie is loaded".to_string());
+    };
+
+    match command {
+        Command::Play => player.set_is_playing(true),
+        Command::Pause => player.set_is_playing(false),
+        Command::GotoFrame { frame, play } => player.goto_frame(frame, !play),
+        Command::SetRandomSeed { seed } => player.set_random_seed(seed),
+        Command::ReadProperty { path } => {
+            return player
+                .read_property(&path)
+                .map(to_json)
+                .ok_or_else(|| format!("Couldn't read `{path}`"));
+        }
+        Command::Screenshot => {
+            let bitmap = player
+                .capture_frame()
+                .ok_or("This renderer can't take screenshots")?;
+            let (width, height) = (bitmap.width(), bitmap.height());
+            let image = image::RgbaImage::from_raw(width, height, bitmap.data().to_vec())
+                .ok_or("The captured frame is incomplete")?;
+            let mut png = Vec::new();
+            image
+                .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+                .map_err(|e| format!("Couldn't encode the screenshot: {e}"))?;
+            return Ok(json!({
+                "width": width,
+                "height": height,
+                "png": base64::engine::general_purpose::STANDARD.encode(png),
+            }));
+        }
+        Command::SendInput { .. } => unreachable!(),
+    }
+    Ok(serde_json::Value::Null)
+}
+
+fn to_json(value: ExternalValue) -> serde_json::Value {
+    match value {
+        ExternalValue::Undefined | ExternalValue::Null => serde_json::Value::Null,
+        ExternalValue::Bool(value) => value.into(),
+        ExternalValue::Number(value) => serde_json::Number::from_f64(value)
+            .map(serde_json::Value::Number)
+            .unwrap_or_default(),
+        ExternalValue::String(value) => value.into(),
+        ExternalValue::Object(values) => values
+            .into_iter()
+            .map(|(key, value)| (key, to_json(value)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        ExternalValue::List(values) => values.into_iter().map(to_json).collect(),
+    }
+}
+
+fn to_player_event(event: &AutomatedEvent) -> Result<PlayerEvent, String> {
+    let button = |btn: &InputMouseButton| match btn {
+        InputMouseButton::Left => MouseButton::Left,
+        InputMouseButton::Middle => MouseButton::Middle,
+        InputMouseButton::Right => MouseButton::Right,
+    };
+
+    Ok(match event {
+        AutomatedEvent::MouseMove { pos } => PlayerEvent::MouseMove { x: pos.0, y: pos.1 },
+        AutomatedEvent::MouseDown {
+            pos, btn, index, ..
+        } => PlayerEvent::MouseDown {
+            x: pos.0,
+            y: pos.1,
+            button: button(btn),
+            index: *index,
+        },
+        AutomatedEvent::MouseUp { pos, btn } => PlayerEvent::MouseUp {
+            x: pos.0,
+            y: pos.1,
+            button: button(btn),
+        },
+        AutomatedEvent::MouseWheel { lines, pixels } => PlayerEvent::MouseWheel {
+            delta: match (lines, pixels) {
+                (Some(lines), None) => MouseWheelDelta::Lines(*lines),
+                (None, Some(pixels)) => MouseWheelDelta::Pixels(*pixels),
+                _ => return Err("Expected only one of `lines` or `pixels`".to_string()),
+            },
+        },
+        AutomatedEvent::KeyDown { key_code } => PlayerEvent::KeyDown {
+            key_code: KeyCode::from_code(*key_code),
+            key_char: None,
+            key_location: KeyLocation::Standard,
+        },
+        AutomatedEvent::KeyUp { key_code } => PlayerEvent::KeyUp {
+            key_code: KeyCode::from_code(*key_code),
+            key_char: None,
+            key_location: KeyLocation::Standard,
+        },
+        AutomatedEvent::TextInput { codepoint } => PlayerEvent::TextInput {
+            codepoint: *codepoint,
+        },
+        AutomatedEvent::TextControl { code } => PlayerEvent::TextControl {
+            code: to_text_control_code(*code),
+        },
+        AutomatedEvent::FocusGained => PlayerEvent::FocusGained,
+        AutomatedEvent::FocusLost => PlayerEvent::FocusLost,
+        AutomatedEvent::Wait | AutomatedEvent::SetClipboardText { .. } => {
+            return Err("This event can't be sent to the player".to_string())
+        }
+    })
+}
+
+fn to_text_control_code(code: InputTextControlCode) -> TextControlCode {
+    match code {
+        InputTextControlCode::MoveLeft => TextControlCode::MoveLeft,
+        InputTextControlCode::MoveLeftWord => TextControlCode::MoveLeftWord,
+        InputTextControlCode::MoveLeftLine => TextControlCode::MoveLeftLine,
+        InputTextControlCode::MoveLeftDocument => TextControlCode::MoveLeftDocument,
+        InputTextControlCode::MoveRight => TextControlCode::MoveRight,
+        InputTextControlCode::MoveRightWord => TextControlCode::MoveRightWord,
+        InputTextControlCode::MoveRightLine => TextControlCode::MoveRightLine,
+        InputTextControlCode::MoveRightDocument => TextControlCode::MoveRightDocument,
+        InputTextControlCode::SelectLeft => TextControlCode::SelectLeft,
+        InputTextControlCode::SelectLeftWord => TextControlCode::SelectLeftWord,
+        InputTextControlCode::SelectLeftLine => TextControlCode::SelectLeftLine,
+        InputTextControlCode::SelectLeftDocument => TextControlCode::SelectLeftDocument,
+        InputTextControlCode::SelectRight => TextControlCode::SelectRight,
+        InputTextControlCode::SelectRightWord => TextControlCode::SelectRightWord,
+        InputTextControlCode::SelectRightLine => TextControlCode::SelectRightLine,
+        InputTextControlCode::SelectRightDocument => TextControlCode::SelectRightDocument,
+        InputTextControlCode::SelectAll => TextControlCode::SelectAll,
+        InputTextControlCode::Copy => TextControlCode::Copy,
+        InputTextControlCode::Paste => TextControlCode::Paste,
+        InputTextControlCode::Cut => TextControlCode::Cut,
+        InputTextControlCode::Backspace => TextControlCode::Backspace,
+        InputTextControlCode::Enter => TextControlCode::Enter,
+        InputTextControlCode::Delete => TextControlCode::Delete,
+    }
+}
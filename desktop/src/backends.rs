@@ -1,10 +1,12 @@
 mod external_interface;
 mod fscommand;
+mod log;
 mod navigator;
 mod ui;
 
 pub use external_interface::DesktopExternalInterfaceProvider;
 pub use fscommand::DesktopFSCommandProvider;
+pub use log::DesktopLogBackend;
 pub use navigator::DesktopNavigatorInterface;
 pub use navigator::PathAllowList;
 pub use ui::DesktopUiBackend;
@@ -0,0 +1,116 @@
+use crate::log::{movie_file_stem, FilenamePattern};
+use ruffle_core::backend::log::{LogBackend, TraceSource};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::PathBuf;
+
+/// Logs the traces of movies, optionally also writing the traces of each movie to its own file,
+/// as the traces of movies that load other movies are hard to follow once interleaved.
+pub struct DesktopLogBackend {
+    /// Whether the traces of movies go to Ruffle's log even when they have their own file.
+    mirror_movie_traces: bool,
+
+    trace_files: Option<RefCell<TraceFiles>>,
+}
+
+impl DesktopLogBackend {
+    pub fn new(mirror_movie_traces: bool) -> Self {
+        Self {
+            mirror_movie_traces,
+            trace_files: None,
+        }
+    }
+
+    /// Writes the traces of each movie to its own file in `directory`.
+    pub fn with_trace_files(
+        mut self,
+        directory: PathBuf,
+        filename_pattern: FilenamePattern,
+    ) -> Self {
+        self.trace_files = Some(RefCell::new(TraceFiles {
+            directory,
+            filename_pattern,
+            files: HashMap::new(),
+            names: HashSet::new(),
+        }));
+        self
+    }
+}
+
+impl LogBackend for DesktopLogBackend {
+    fn avm_trace(&self, message: &str) {
+        tracing::info!(target: "avm_trace", "{}", message);
+    }
+
+    fn movie_trace(&self, message: &str, source: TraceSource<'_>) {
+        if let Some(trace_files) = &self.trace_files {
+            trace_files.borrow_mut().write(message, source);
+            if !self.mirror_movie_traces {
+                return;
+            }
+        }
+        tracing::info!(
+            target: "avm_trace",
+            avm = %source.avm,
+            movie = source.movie_url,
+            "{}",
+            message
+        );
+    }
+}
+
+struct TraceFiles {
+    directory: PathBuf,
+    filename_pattern: FilenamePattern,
+
+    /// The trace file of each movie by URL, or `None` if it couldn't be written to.
+    files: HashMap<String, Option<LineWriter<File>>>,
+
+    /// The names given to trace files so far, so that movies with the same file name
+    /// don't share one.
+    names: HashSet<String>,
+}
+
+impl TraceFiles {
+    fn write(&mut self, message: &str, source: TraceSource<'_>) {
+        if !self.files.contains_key(source.movie_url) {
+            let file = self.create(source.movie_url);
+            self.files.insert(source.movie_url.to_owned(), file);
+        }
+        let slot = self
+            .files
+            .get_mut(source.movie_url)
+            .expect("Trace file was just added");
+        if let Some(file) = slot {
+            if let Err(e) = writeln!(file, "[{}] {message}", source.avm) {
+                tracing::warn!("Couldn't write trace of {}: {e}", source.movie_url);
+                *slot = None;
+            }
+        }
+    }
+
+    fn create(&mut self, movie_url: &str) -> Option<LineWriter<File>> {
+        let stem = movie_file_stem(movie_url);
+        let mut name = stem.clone();
+        let mut index = 1;
+        while !self.names.insert(name.clone()) {
+            index += 1;
+            name = format!("{stem}_{index}");
+        }
+
+        let path = self
+            .filename_pattern
+            .create_movie_trace_path(&self.directory, &name);
+        let mut file = match File::create(&path) {
+            Ok(file) => LineWriter::new(file),
+            Err(e) => {
+                tracing::warn!("Couldn't create trace file {}: {e}", path.display());
+                return None;
+            }
+        };
+        writeln!(file, "Traces of {movie_url}").ok()?;
+        Some(file)
+    }
+}
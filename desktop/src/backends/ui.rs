@@ -1,21 +1,25 @@
 use crate::custom_event::RuffleEvent;
 use crate::gui::dialogs::message_dialog::MessageDialogConfiguration;
+use crate::gui::dialogs::storage_quota_dialog::StorageQuotaDialogConfiguration;
 use crate::gui::{DialogDescriptor, FilePicker, LocalizableText};
 use crate::preferences::GlobalPreferences;
 use anyhow::Error;
 use chrono::{DateTime, Utc};
 use egui_winit::clipboard::Clipboard;
 use fontdb::Family;
+use futures::future::{select, Either};
 use rfd::{
     AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel,
 };
-use ruffle_core::backend::navigator::OpenURLMode;
+use ruffle_core::backend::navigator::{OpenURLMode, OwnedFuture};
 use ruffle_core::backend::ui::{
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
+    FullscreenError, LanguageIdentifier, MouseCursor, SettingsPanel, UiBackend,
 };
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::Arc;
+use tokio::sync::oneshot;
 use tracing::error;
 use url::Url;
 use winit::event_loop::EventLoopProxy;
@@ -121,11 +125,15 @@ pub struct DesktopUiBackend {
     event_loop: EventLoopProxy<RuffleEvent>,
     cursor_visible: bool,
     clipboard: Clipboard,
+    /// The user's answer to the clipboard read prompt, remembered for this session.
+    clipboard_read_permission: Option<bool>,
     preferences: GlobalPreferences,
     preferred_cursor: MouseCursor,
     open_url_mode: OpenURLMode,
     font_database: Rc<fontdb::Database>,
     file_picker: FilePicker,
+    /// Answers the pending `FileReference.browse()` with a file dropped onto the window.
+    dropped_file: Option<oneshot::Sender<PathBuf>>,
 }
 
 impl DesktopUiBackend {
@@ -151,14 +159,26 @@ impl DesktopUiBackend {
             event_loop,
             cursor_visible: true,
             clipboard,
+            clipboard_read_permission: None,
             preferences,
             preferred_cursor: MouseCursor::Arrow,
             open_url_mode,
             font_database,
             file_picker,
+            dropped_file: None,
         })
     }
 
+    /// Gives a file dropped onto the window to the movie, if it is waiting for the user to pick one.
+    ///
+    /// Returns `false` if the movie isn't waiting for a file.
+    pub fn accept_dropped_file(&mut self, path: &Path) -> bool {
+        match self.dropped_file.take() {
+            Some(sender) => sender.send(path.to_owned()).is_ok(),
+            None => false,
+        }
+    }
+
     pub fn cursor(&self) -> egui::CursorIcon {
         if self.cursor_visible {
             match self.preferred_cursor {
@@ -194,6 +214,32 @@ impl UiBackend for DesktopUiBackend {
         self.clipboard.set(content);
     }
 
+    fn request_clipboard_read_permission(&mut self) -> bool {
+        *self.clipboard_read_permission.get_or_insert_with(|| {
+            MessageDialog::new()
+                .set_title("Allow clipboard access?")
+                .set_level(MessageLevel::Info)
+                .set_description("The SWF file wants to read the contents of your clipboard.")
+                .set_buttons(MessageButtons::YesNo)
+                .show()
+                == MessageDialogResult::Yes
+        })
+    }
+
+    fn ask_abort_slow_script(&mut self) -> Option<bool> {
+        let result = MessageDialog::new()
+            .set_title("Abort script?")
+            .set_level(MessageLevel::Warning)
+            .set_description(
+                "A script in this movie is causing Ruffle to run slowly. \
+                 If it continues to run, your computer may become unresponsive. \
+                 Do you want to abort the script?",
+            )
+            .set_buttons(MessageButtons::YesNo)
+            .show();
+        Some(result == MessageDialogResult::Yes)
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
@@ -347,10 +393,18 @@ impl UiBackend for DesktopUiBackend {
         }
 
         let result = self.file_picker.show_dialog(dialog, |d| d.pick_file())?;
+        let (sender, dropped_file) = oneshot::channel();
+        self.dropped_file = Some(sender);
 
         Some(Box::pin(async move {
+            // Whichever comes first: the user picks a file in the dialog, or drops one onto the window.
+            let handle = match select(Box::pin(result), dropped_file).await {
+                Either::Left((handle, _)) => handle,
+                Either::Right((Ok(path), _)) => Some(FileHandle::from(path)),
+                Either::Right((Err(_), result)) => result.await,
+            };
             let result: Result<Box<dyn FileDialogResult>, DialogLoaderError> =
-                Ok(Box::new(DesktopFileDialogResult::new(result.await)));
+                Ok(Box::new(DesktopFileDialogResult::new(handle)));
             result
         }))
     }
@@ -375,4 +429,28 @@ impl UiBackend for DesktopUiBackend {
     }
 
     fn close_file_dialog(&mut self) {}
+
+    fn set_window_title(&mut self, title: &str) {
+        self.window.set_title(title);
+    }
+
+    fn display_settings_panel(&mut self, panel: SettingsPanel) {
+        let _ = self
+            .event_loop
+            .send_event(RuffleEvent::OpenDialog(DialogDescriptor::Settings(panel)));
+    }
+
+    fn request_storage_quota(
+        &mut self,
+        site: &str,
+        required: usize,
+    ) -> Option<OwnedFuture<bool, ()>> {
+        let (notifier, receiver) = oneshot::channel();
+        let _ =
+            self.event_loop
+                .send_event(RuffleEvent::OpenDialog(DialogDescriptor::StorageQuota(
+                    StorageQuotaDialogConfiguration::new(notifier, site, required),
+                )));
+        Some(Box::pin(async move { Ok(receiver.await.unwrap_or(false)) }))
+    }
 }
@@ -2,9 +2,11 @@ use crate::preferences::storage::StorageBackend;
 use crate::RUFFLE_VERSION;
 use anyhow::{anyhow, Error};
 use clap::{Parser, ValueEnum};
+use ruffle_core::backend::audio::FastForwardAudio;
 use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
-use ruffle_core::config::Letterbox;
+use ruffle_core::config::{Letterbox, ScreenResolution};
 use ruffle_core::events::{GamepadButton, KeyCode};
+use ruffle_core::prefetch::PrefetchPolicy;
 use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
@@ -20,6 +22,17 @@ fn get_default_save_directory() -> std::path::PathBuf {
         .join("SharedObjects")
 }
 
+/// Where Flash Player keeps its settings and shared objects.
+pub fn get_default_flash_player_directory() -> Option<std::path::PathBuf> {
+    if cfg!(windows) {
+        dirs::config_dir().map(|dir| dir.join("Macromedia").join("Flash Player"))
+    } else if cfg!(target_os = "macos") {
+        dirs::preference_dir().map(|dir| dir.join("Macromedia").join("Flash Player"))
+    } else {
+        dirs::home_dir().map(|dir| dir.join(".macromedia").join("Flash_Player"))
+    }
+}
+
 fn get_default_config_directory() -> std::path::PathBuf {
     dirs::config_local_dir()
         .expect("Couldn't find a valid config_local dir")
@@ -51,6 +64,7 @@ pub struct Opt {
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     ///
     /// Default will attempt to pick the most supported graphics backend.
+    /// Software renders movies on the CPU, which is slow but works without a GPU.
     /// This option temporarily overrides any stored preference.
     #[clap(long, short)]
     pub graphics: Option<GraphicsBackend>,
@@ -63,6 +77,26 @@ pub struct Opt {
     #[clap(long, short)]
     pub power: Option<PowerPreference>,
 
+    /// Maximum GPU memory, in megabytes, to use for bitmap textures.
+    ///
+    /// When over budget, the least recently rendered bitmaps are removed from the GPU
+    /// and uploaded again when next used. The default is no limit.
+    /// This option temporarily overrides any stored preference.
+    #[clap(long)]
+    pub gpu_memory_budget: Option<u64>,
+
+    /// Maximum memory, in megabytes, used to share decoded bitmaps and tessellated shapes
+    /// between copies of the same movie, or 0 to not share them. The default is 256.
+    #[clap(long)]
+    pub asset_cache_size: Option<usize>,
+
+    /// Present frames as soon as they're rendered, without waiting for the display's vertical blank.
+    ///
+    /// This can reduce input latency at the cost of tearing.
+    /// This option temporarily overrides any stored preference.
+    #[clap(long, action)]
+    pub no_vsync: bool,
+
     /// GameMode preference.
     ///
     /// This allows enabling or disabling GameMode manually.
@@ -76,6 +110,14 @@ pub struct Opt {
     #[cfg_attr(not(target_os = "linux"), clap(hide = true))]
     pub gamemode: Option<GameModePreference>,
 
+    /// What to do with the movie while the window isn't focused:
+    /// keep running, run at the background frame rate set in the preferences,
+    /// or pause the movie and its sounds until the window is focused again.
+    ///
+    /// This option temporarily overrides any stored preference.
+    #[clap(long)]
+    pub focus_loss: Option<FocusLossPreference>,
+
     /// Type of storage backend to use. This determines where local storage data is saved (e.g. shared objects).
     ///
     /// This option temporarily overrides any stored preference.
@@ -94,8 +136,18 @@ pub struct Opt {
     #[clap(long, short, value_parser(parse_duration_seconds))]
     pub max_execution_duration: Option<Duration>,
 
+    /// Maximum number of seconds that timer callbacks may run per frame.
+    /// The remaining timers are postponed, and intervals skip the ticks they missed.
+    #[clap(long, value_parser(parse_duration_seconds))]
+    pub timer_budget: Option<Duration>,
+
+    /// Seed random number generation with this value instead of the current time,
+    /// so that every run of a movie gets the same random numbers.
+    #[clap(long)]
+    pub random_seed: Option<u64>,
+
     /// Base directory or URL used to resolve all relative path statements in the SWF file.
-    /// The default is the current directory.
+    /// The default is the current directory, unless a base URL was remembered for the movie.
     #[clap(long)]
     pub base: Option<Url>,
 
@@ -129,6 +181,13 @@ pub struct Opt {
     #[clap(long, default_value_os_t=get_default_save_directory())]
     pub save_directory: std::path::PathBuf,
 
+    /// Copy the saves of a Flash Player installation into `save-directory`, then exit.
+    ///
+    /// Takes the Flash Player directory (or its `#SharedObjects` directory),
+    /// and defaults to the one of the current user. Existing saves are never overwritten.
+    #[clap(long, value_name = "FLASH PLAYER DIRECTORY")]
+    pub import_flash_saves: Option<Option<std::path::PathBuf>>,
+
     /// Location of a directory to store Ruffle configuration.
     #[clap(long, default_value_os_t=get_default_config_directory())]
     pub config: std::path::PathBuf,
@@ -139,6 +198,14 @@ pub struct Opt {
     #[clap(long, default_value_os_t=get_default_cache_directory())]
     pub cache_directory: std::path::PathBuf,
 
+    /// Start an automation server on this local port, which test scripts can connect to
+    /// over a WebSocket to control the player and inspect the movie.
+    ///
+    /// See the documentation of the `automation` module for the protocol.
+    #[cfg(feature = "automation")]
+    #[clap(long, value_name = "PORT")]
+    pub automation_port: Option<u16>,
+
     /// Proxy to use when loading movies via URL.
     #[clap(long)]
     pub proxy: Option<Url>,
@@ -166,7 +233,8 @@ pub struct Opt {
     #[clap(long)]
     pub letterbox: Option<Letterbox>,
 
-    /// Spoofs the root SWF URL provided to ActionScript.
+    /// Spoofs the root SWF URL provided to ActionScript, as seen in `_url` and `loaderInfo.url`.
+    /// Overrides the URL remembered for the movie, if any.
     #[clap(long, value_parser)]
     pub spoof_url: Option<Url>,
 
@@ -187,6 +255,30 @@ pub struct Opt {
     #[clap(long)]
     pub player_runtime: Option<PlayerRuntime>,
 
+    /// Spoofs the player version string reported to ActionScript, such as "WIN 9,0,115,0".
+    #[clap(long)]
+    pub spoof_version: Option<String>,
+
+    /// Spoofs the operating system reported to ActionScript, such as "Windows XP".
+    #[clap(long)]
+    pub spoof_os: Option<String>,
+
+    /// Spoofs the language code reported to ActionScript, such as "ja".
+    #[clap(long)]
+    pub spoof_language: Option<String>,
+
+    /// Spoofs the player type reported to ActionScript, such as "PlugIn".
+    #[clap(long)]
+    pub spoof_player_type: Option<String>,
+
+    /// Reports the player as a debugger build to ActionScript.
+    #[clap(long, action)]
+    pub spoof_debugger: bool,
+
+    /// Spoofs the screen resolution reported to ActionScript, such as "1024x768".
+    #[clap(long)]
+    pub spoof_screen_resolution: Option<ScreenResolution>,
+
     /// Set and lock the player's frame rate, overriding the movie's frame rate.
     #[clap(long)]
     pub frame_rate: Option<f64>,
@@ -205,6 +297,17 @@ pub struct Opt {
     #[clap(long)]
     pub dummy_external_interface: bool,
 
+    /// Allow all loaded movies to script each other, regardless of where they were loaded from.
+    /// This is useful for testing local copies of content made of multiple SWFs,
+    /// but should not be used for untrusted content.
+    #[clap(long)]
+    pub relaxed_security: bool,
+
+    /// Play truncated movies, e.g. from incomplete downloads, up to their last complete frame.
+    /// The incomplete frame is left out, and the movie stops there instead of looping.
+    #[clap(long)]
+    pub partial_playback: bool,
+
     /// Hides the menu bar (the bar at the top of the window).
     #[clap(long)]
     pub no_gui: bool,
@@ -243,6 +346,44 @@ pub struct Opt {
     /// (like inlining constant pool entries) can't be disabled.
     #[clap(long)]
     pub no_avm2_optimizer: bool,
+
+    /// Verify all AVM2 code as soon as it's loaded, instead of when it first runs.
+    /// Errors in code that never runs are reported too, which Flash Player doesn't do,
+    /// so this is meant for checking your own content.
+    #[clap(long)]
+    pub avm2_strict_verification: bool,
+
+    /// Collect what the movie does to find out where it is running, and the domains it expects.
+    /// The report is shown in the Debug Tools menu.
+    #[clap(long)]
+    pub detect_sitelocks: bool,
+
+    /// Count how many times each AVM1 action and AVM2 opcode runs.
+    /// The counts can be saved as JSON from the Debug Tools menu.
+    #[clap(long)]
+    pub count_opcodes: bool,
+
+    /// How many frames of video to decode ahead of time on a background thread.
+    /// Set to 0 to only decode frames when they're shown.
+    #[clap(long, default_value = "4")]
+    pub video_decode_ahead: usize,
+
+    /// Fetch the files referenced in the code of loaded movies in the background,
+    /// so that loading them later doesn't stop the movie.
+    /// With "same-origin", only files on the same server as the movie are fetched.
+    #[clap(long, default_value = "disabled")]
+    pub prefetch: PrefetchPolicy,
+
+    /// What happens to sounds while the movie is fast-forwarded from the Controls menu.
+    /// With "preserve-pitch", sounds are sped up without sounding higher.
+    #[clap(long, default_value = "preserve-pitch")]
+    pub fast_forward_audio: FastForwardAudio,
+
+    /// Keep updating the movie when it has nothing to do.
+    /// By default, movies that are idle (with no animation, sound, timer or network request
+    /// going on) aren't updated until input arrives, to save CPU.
+    #[clap(long)]
+    pub no_idle_detection: bool,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
@@ -342,6 +483,42 @@ impl FromStr for GameModePreference {
     }
 }
 
+/// What happens to the movie while the window isn't focused.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum FocusLossPreference {
+    /// The movie runs as usual, like in Flash Player.
+    #[default]
+    KeepRunning,
+
+    /// The movie is updated at the background frame rate, to save power.
+    Throttle,
+
+    /// The movie and its sounds are paused until the window is focused again.
+    Pause,
+}
+
+impl FocusLossPreference {
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            FocusLossPreference::KeepRunning => None,
+            FocusLossPreference::Throttle => Some("throttle"),
+            FocusLossPreference::Pause => Some("pause"),
+        }
+    }
+}
+
+impl FromStr for FocusLossPreference {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "throttle" => Ok(FocusLossPreference::Throttle),
+            "pause" => Ok(FocusLossPreference::Pause),
+            _ => Err(()),
+        }
+    }
+}
+
 // TODO The following enum exists in order to preserve
 //   the behavior of mapping gamepad buttons,
 //   We should probably do something smarter here.
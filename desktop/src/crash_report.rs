@@ -0,0 +1,150 @@
+//! Local crash reports.
+//!
+//! When enabled by the user, a plain-text report is written to disk on panic.
+//! Reports are never uploaded; the user decides whether to share them.
+
+use chrono::{DateTime, Utc};
+use ruffle_core::tag_utils::SwfMovie;
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// Identifies the movie that was playing when a crash happened.
+#[derive(Debug, Clone)]
+pub struct MovieInfo {
+    pub url: String,
+    pub sha256: String,
+}
+
+impl MovieInfo {
+    pub fn new(movie: &SwfMovie) -> Self {
+        Self {
+            url: movie.url().to_string(),
+            sha256: format!("{:x}", Sha256::digest(movie.data())),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CrashReport {
+    pub panic_text: String,
+    pub ruffle_version: String,
+    pub operating_system: String,
+    pub movie_name: Option<String>,
+    pub movie: Option<MovieInfo>,
+    pub recent_stubs: Vec<String>,
+    pub avm2_callstack: Option<String>,
+    pub render_info: Option<String>,
+    pub player_config: Option<String>,
+}
+
+impl CrashReport {
+    /// Writes this report into `directory`, returning the path of the new file.
+    pub fn write_to(&self, directory: &Path, time: DateTime<Utc>) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(directory)?;
+        let path = directory.join(time.format("ruffle_crash_%F_%H-%M-%S.txt").to_string());
+        std::fs::write(&path, self.to_string())?;
+        Ok(path)
+    }
+}
+
+impl Display for CrashReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Ruffle crash report")?;
+        writeln!(f)?;
+        writeln!(f, "Ruffle version: {}", self.ruffle_version)?;
+        writeln!(f, "Operating system: {}", self.operating_system)?;
+        writeln!(f)?;
+        writeln!(f, "## Panic")?;
+        writeln!(f, "{}", self.panic_text.trim())?;
+
+        writeln!(f)?;
+        writeln!(f, "## Movie")?;
+        if let Some(name) = &self.movie_name {
+            writeln!(f, "Name: {name}")?;
+        }
+        match &self.movie {
+            Some(movie) => {
+                writeln!(f, "URL: {}", movie.url)?;
+                writeln!(f, "SHA-256: {}", movie.sha256)?;
+            }
+            None if self.movie_name.is_none() => writeln!(f, "No movie loaded")?,
+            None => {}
+        }
+
+        writeln!(f)?;
+        writeln!(f, "## Recent stubs")?;
+        if self.recent_stubs.is_empty() {
+            writeln!(f, "None")?;
+        }
+        for stub in &self.recent_stubs {
+            writeln!(f, "- {stub}")?;
+        }
+
+        if let Some(callstack) = &self.avm2_callstack {
+            writeln!(f)?;
+            writeln!(f, "## AVM2 callstack")?;
+            writeln!(f, "{}", callstack.trim_end())?;
+        }
+
+        if let Some(render_info) = &self.render_info {
+            writeln!(f)?;
+            writeln!(f, "## Render info")?;
+            writeln!(f, "{}", render_info.trim_end())?;
+        }
+
+        if let Some(player_config) = &self.player_config {
+            writeln!(f)?;
+            writeln!(f, "## Player configuration")?;
+            writeln!(f, "{}", player_config.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_contents() {
+        let report = CrashReport {
+            panic_text: "panicked at core/src/player.rs:1:1:\noops\n".to_string(),
+            ruffle_version: "0.1.0".to_string(),
+            operating_system: "TestOS".to_string(),
+            movie_name: Some("game.swf".to_string()),
+            movie: Some(MovieInfo {
+                url: "file:///game.swf".to_string(),
+                sha256: "abcd".to_string(),
+            }),
+            recent_stubs: vec!["AVM2 flash.display.Stage.foo getter".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            report.to_string(),
+            "Ruffle crash report\n\
+            \n\
+            Ruffle version: 0.1.0\n\
+            Operating system: TestOS\n\
+            \n\
+            ## Panic\n\
+            panicked at core/src/player.rs:1:1:\noops\n\
+            \n\
+            ## Movie\n\
+            Name: game.swf\n\
+            URL: file:///game.swf\n\
+            SHA-256: abcd\n\
+            \n\
+            ## Recent stubs\n\
+            - AVM2 flash.display.Stage.foo getter\n"
+        );
+    }
+
+    #[test]
+    fn report_without_movie() {
+        let report = CrashReport::default().to_string();
+        assert!(report.contains("## Movie\nNo movie loaded\n"));
+        assert!(report.contains("## Recent stubs\nNone\n"));
+    }
+}
@@ -33,4 +33,8 @@ pub enum RuffleEvent {
 
     /// The movie wants to open a dialog.
     OpenDialog(DialogDescriptor),
+
+    /// A client of the automation server sent a command.
+    #[cfg(feature = "automation")]
+    Automation(crate::automation::AutomationRequest),
 }
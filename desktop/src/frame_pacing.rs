@@ -0,0 +1,164 @@
+//! Decouples the movie's frame rate from the display's refresh rate.
+//!
+//! The movie is only rendered again when its output may have changed, so a
+//! 24 FPS movie on a 144 Hz display presents the latest rendered frame on
+//! every refresh instead of redrawing it six times over.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How far back the rates in [`FramePacingStats`] are measured over.
+const STATS_WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+pub struct FramePacer {
+    /// When the movie produced a new frame, within the stats window.
+    movie_frames: VecDeque<Instant>,
+
+    /// When a frame was presented to the window, within the stats window.
+    presents: VecDeque<Instant>,
+
+    /// Set when something other than the movie itself changed how it
+    /// renders, such as a resize or the debug tools highlighting an object.
+    invalidated: bool,
+
+    /// The number of presents that reused the previously rendered movie.
+    skipped_renders: u64,
+}
+
+impl FramePacer {
+    /// Record that the movie advanced and has a new frame to render.
+    pub fn record_movie_frame(&mut self, now: Instant) {
+        self.movie_frames.push_back(now);
+        trim(&mut self.movie_frames, now);
+    }
+
+    /// Force the movie to be rendered again on the next present.
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Decide whether the movie must be rendered before the next present,
+    /// or whether its last rendered frame can be presented again.
+    pub fn should_render_movie(&mut self, movie_needs_render: bool) -> bool {
+        if movie_needs_render || std::mem::take(&mut self.invalidated) {
+            true
+        } else {
+            self.skipped_renders += 1;
+            false
+        }
+    }
+
+    /// Record that a frame was presented to the window.
+    pub fn record_present(&mut self, now: Instant) {
+        self.presents.push_back(now);
+        trim(&mut self.presents, now);
+    }
+
+    pub fn stats(&self, now: Instant) -> FramePacingStats {
+        let window_start = now.checked_sub(STATS_WINDOW);
+        let count = |times: &VecDeque<Instant>| {
+            times
+                .iter()
+                .filter(|time| window_start.map_or(true, |start| **time >= start))
+                .count()
+        };
+        let movie_frames = count(&self.movie_frames);
+        let presents = count(&self.presents);
+
+        let longest_present_interval = self
+            .presents
+            .iter()
+            .zip(self.presents.iter().skip(1))
+            .map(|(previous, next)| next.duration_since(*previous))
+            .max()
+            .unwrap_or_default();
+
+        FramePacingStats {
+            movie_frame_rate: movie_frames as f64 / STATS_WINDOW.as_secs_f64(),
+            present_rate: presents as f64 / STATS_WINDOW.as_secs_f64(),
+            longest_present_interval,
+            skipped_renders: self.skipped_renders,
+        }
+    }
+}
+
+/// Forget about events that happened before the stats window.
+fn trim(times: &mut VecDeque<Instant>, now: Instant) {
+    while times
+        .front()
+        .is_some_and(|time| now.duration_since(*time) > STATS_WINDOW)
+    {
+        times.pop_front();
+    }
+}
+
+/// The cadence of movie frames compared to presented frames, over the last second.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FramePacingStats {
+    /// How many new frames the movie produced per second.
+    pub movie_frame_rate: f64,
+
+    /// How many frames were presented to the window per second.
+    pub present_rate: f64,
+
+    /// The longest gap between two presents, which shows up as a stutter.
+    pub longest_present_interval: Duration,
+
+    /// How many presents reused the last rendered movie frame, in total.
+    pub skipped_renders: u64,
+}
+
+impl FramePacingStats {
+    /// How many times each movie frame was presented, on average.
+    pub fn presents_per_movie_frame(&self) -> f64 {
+        if self.movie_frame_rate > 0.0 {
+            self.present_rate / self.movie_frame_rate
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_redundant_renders() {
+        let mut pacer = FramePacer::default();
+        assert!(pacer.should_render_movie(true));
+        assert!(!pacer.should_render_movie(false));
+
+        pacer.invalidate();
+        assert!(pacer.should_render_movie(false));
+        assert!(!pacer.should_render_movie(false));
+
+        assert_eq!(pacer.stats(Instant::now()).skipped_renders, 2);
+    }
+
+    #[test]
+    fn measures_cadence() {
+        let mut pacer = FramePacer::default();
+        let start = Instant::now();
+        for i in 0..24 {
+            pacer.record_movie_frame(start + Duration::from_millis(i * 1000 / 24));
+        }
+        for i in 0..144 {
+            pacer.record_present(start + Duration::from_millis(i * 1000 / 144));
+        }
+
+        let stats = pacer.stats(start + Duration::from_millis(999));
+        assert_eq!(stats.movie_frame_rate, 24.0);
+        assert_eq!(stats.present_rate, 144.0);
+        assert_eq!(stats.presents_per_movie_frame(), 6.0);
+        assert_eq!(stats.longest_present_interval, Duration::from_millis(7));
+
+        // Old events fall out of the window.
+        pacer.record_present(start + Duration::from_millis(2500));
+        let stats = pacer.stats(start + Duration::from_millis(2500));
+        assert_eq!(stats.movie_frame_rate, 0.0);
+        assert_eq!(stats.present_rate, 1.0);
+        assert_eq!(stats.presents_per_movie_frame(), 0.0);
+    }
+}
@@ -1,3 +1,5 @@
+mod actions;
+mod command_palette;
 mod context_menu;
 mod controller;
 pub mod dialogs;
@@ -7,6 +9,7 @@ mod picker;
 mod theme;
 mod widgets;
 
+pub use actions::{parse_shortcut, shortcut_to_string, Action, Shortcuts};
 pub use controller::GuiController;
 pub use dialogs::DialogDescriptor;
 pub use movie::MovieView;
@@ -16,6 +19,7 @@ pub use theme::ThemePreference;
 use url::Url;
 
 use crate::custom_event::RuffleEvent;
+use crate::frame_pacing::FramePacingStats;
 use crate::gui::context_menu::ContextMenu;
 use crate::player::LaunchOptions;
 use crate::preferences::GlobalPreferences;
@@ -29,6 +33,7 @@ use ruffle_core::debug_ui::Message as DebugMessage;
 use ruffle_core::{Player, PlayerEvent};
 use std::collections::HashMap;
 use std::sync::{MutexGuard, Weak};
+use std::time::Duration;
 use std::{fs, mem};
 use unic_langid::LanguageIdentifier;
 use winit::event_loop::EventLoopProxy;
@@ -63,7 +68,6 @@ pub fn available_languages() -> Vec<&'static LanguageIdentifier> {
     result
 }
 
-#[allow(dead_code)]
 pub fn text_with_args<'a, T: AsRef<str>>(
     locale: &LanguageIdentifier,
     id: &'a str,
@@ -146,6 +150,7 @@ impl RuffleGui {
         show_menu: bool,
         mut player: Option<&mut Player>,
         menu_height_offset: f64,
+        frame_pacing_stats: &FramePacingStats,
     ) {
         let locale = self.preferences.language();
 
@@ -155,9 +160,19 @@ impl RuffleGui {
             self.menu_bar
                 .show(&locale, egui_ctx, &mut self.dialogs, player.as_deref_mut());
         }
+        self.menu_bar.show_command_palette(
+            &locale,
+            egui_ctx,
+            &mut self.dialogs,
+            player.as_deref_mut(),
+        );
 
         self.dialogs.show(&locale, egui_ctx, player.as_deref_mut());
 
+        if self.menu_bar.show_frame_pacing_stats {
+            self.show_frame_pacing_stats(&locale, egui_ctx, frame_pacing_stats, menu_height_offset);
+        }
+
         if let Some(player) = player {
             let was_suspended = player.debug_ui().should_suspend_player();
             player.show_debug_ui(egui_ctx, menu_height_offset);
@@ -193,6 +208,62 @@ impl RuffleGui {
         };
     }
 
+    /// Shows how often the movie produces frames compared to how often they're presented.
+    fn show_frame_pacing_stats(
+        &self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        stats: &FramePacingStats,
+        menu_height_offset: f64,
+    ) {
+        let rate = |value: f64| FluentValue::from(format!("{value:.1}"));
+        let rows: [(&str, HashMap<&str, FluentValue>); 5] = [
+            (
+                "frame-pacing-movie-frame-rate",
+                HashMap::from([("rate", rate(stats.movie_frame_rate))]),
+            ),
+            (
+                "frame-pacing-present-rate",
+                HashMap::from([("rate", rate(stats.present_rate))]),
+            ),
+            (
+                "frame-pacing-presents-per-frame",
+                HashMap::from([("ratio", rate(stats.presents_per_movie_frame()))]),
+            ),
+            (
+                "frame-pacing-longest-interval",
+                HashMap::from([(
+                    "milliseconds",
+                    rate(stats.longest_present_interval.as_secs_f64() * 1000.0),
+                )]),
+            ),
+            (
+                "frame-pacing-skipped-renders",
+                HashMap::from([("count", FluentValue::from(stats.skipped_renders))]),
+            ),
+        ];
+
+        Area::new(Id::new("frame_pacing_stats"))
+            .anchor(
+                Align2::RIGHT_TOP,
+                vec2(
+                    -8.0,
+                    menu_height_offset as f32 / egui_ctx.pixels_per_point() + 8.0,
+                ),
+            )
+            .interactable(false)
+            .show(egui_ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    for (id, args) in &rows {
+                        ui.label(text_with_args(locale, id, args));
+                    }
+                });
+            });
+
+        // Keep the numbers current even when nothing else is being redrawn.
+        egui_ctx.request_repaint_after(Duration::from_millis(250));
+    }
+
     pub fn show_context_menu(
         &mut self,
         menu: Vec<ruffle_core::ContextMenuItem>,
@@ -221,11 +292,13 @@ impl RuffleGui {
         mut player: MutexGuard<Player>,
     ) {
         self.menu_bar.currently_opened = Some((movie_url.clone(), opt.clone()));
+        self.dialogs.set_movie_url(&movie_url);
 
         // Update dialog state to reflect the newly-opened movie's options.
         self.dialogs
             .recreate_open_dialog(opt, Some(movie_url), self.event_loop.clone());
 
         player.set_volume(self.dialogs.volume_controls.get_volume());
+        player.set_audio_effects(self.dialogs.volume_controls.get_audio_effects());
     }
 }
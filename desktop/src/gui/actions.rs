@@ -0,0 +1,278 @@
+use egui::{Key, KeyboardShortcut, Modifiers};
+use std::collections::BTreeMap;
+
+/// Something the user can do from the GUI.
+///
+/// Actions are shared by the menu bar, keyboard shortcuts and the command palette,
+/// and are run by [`crate::gui::menu_bar::MenuBar::run_action`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    OpenQuick,
+    OpenAdvanced,
+    OpenUrl,
+    Reload,
+    Close,
+    Preferences,
+    LocalStorage,
+    ExportSaves,
+    ImportSaves,
+    Exit,
+    TogglePlaying,
+    ToggleFastForward,
+    VolumeControls,
+    ToggleMute,
+    Fullscreen,
+    Screenshot,
+    AddBookmark,
+    ManageBookmarks,
+    DebugStage,
+    DebugMovie,
+    DebugMovieList,
+    DebugDomainList,
+    DebugSearchDisplayObjects,
+    SitelockReport,
+    DumpHeap,
+    DumpOpcodeCounts,
+    FramePacingStats,
+    About,
+    CommandPalette,
+}
+
+impl Action {
+    pub const ALL: [Action; 29] = [
+        Action::OpenQuick,
+        Action::OpenAdvanced,
+        Action::OpenUrl,
+        Action::Reload,
+        Action::Close,
+        Action::Preferences,
+        Action::LocalStorage,
+        Action::ExportSaves,
+        Action::ImportSaves,
+        Action::Exit,
+        Action::TogglePlaying,
+        Action::ToggleFastForward,
+        Action::VolumeControls,
+        Action::ToggleMute,
+        Action::Fullscreen,
+        Action::Screenshot,
+        Action::AddBookmark,
+        Action::ManageBookmarks,
+        Action::DebugStage,
+        Action::DebugMovie,
+        Action::DebugMovieList,
+        Action::DebugDomainList,
+        Action::DebugSearchDisplayObjects,
+        Action::SitelockReport,
+        Action::DumpHeap,
+        Action::DumpOpcodeCounts,
+        Action::FramePacingStats,
+        Action::About,
+        Action::CommandPalette,
+    ];
+
+    /// The name of this action in the preferences.
+    pub fn id(self) -> &'static str {
+        match self {
+            Action::OpenQuick => "open",
+            Action::OpenAdvanced => "open_advanced",
+            Action::OpenUrl => "open_url",
+            Action::Reload => "reload",
+            Action::Close => "close",
+            Action::Preferences => "preferences",
+            Action::LocalStorage => "local_storage",
+            Action::ExportSaves => "export_saves",
+            Action::ImportSaves => "import_saves",
+            Action::Exit => "exit",
+            Action::TogglePlaying => "toggle_playing",
+            Action::ToggleFastForward => "toggle_fast_forward",
+            Action::VolumeControls => "volume_controls",
+            Action::ToggleMute => "toggle_mute",
+            Action::Fullscreen => "fullscreen",
+            Action::Screenshot => "screenshot",
+            Action::AddBookmark => "add_bookmark",
+            Action::ManageBookmarks => "manage_bookmarks",
+            Action::DebugStage => "debug_stage",
+            Action::DebugMovie => "debug_movie",
+            Action::DebugMovieList => "debug_movie_list",
+            Action::DebugDomainList => "debug_domain_list",
+            Action::DebugSearchDisplayObjects => "debug_search_display_objects",
+            Action::SitelockReport => "sitelock_report",
+            Action::DumpHeap => "dump_heap",
+            Action::DumpOpcodeCounts => "dump_opcode_counts",
+            Action::FramePacingStats => "frame_pacing_stats",
+            Action::About => "about",
+            Action::CommandPalette => "command_palette",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    /// The id of the text describing this action.
+    ///
+    /// Suspending and resuming are the same action, so which one it does depends on `is_playing`,
+    /// if known.
+    pub fn text_id(self, is_playing: Option<bool>) -> &'static str {
+        match self {
+            Action::OpenQuick => "file-menu-open-quick",
+            Action::OpenAdvanced => "file-menu-open-advanced",
+            Action::OpenUrl => "file-menu-open-url",
+            Action::Reload => "file-menu-reload",
+            Action::Close => "file-menu-close",
+            Action::Preferences => "file-menu-preferences",
+            Action::LocalStorage => "file-menu-local-storage",
+            Action::ExportSaves => "file-menu-export-saves",
+            Action::ImportSaves => "file-menu-import-saves",
+            Action::Exit => "file-menu-exit",
+            Action::TogglePlaying => match is_playing {
+                Some(true) => "controls-menu-suspend",
+                Some(false) => "controls-menu-resume",
+                None => "action-toggle-playing",
+            },
+            Action::ToggleFastForward => "controls-menu-fast-forward",
+            Action::VolumeControls => "controls-menu-volume",
+            Action::ToggleMute => "action-toggle-mute",
+            Action::Fullscreen => "view-menu-fullscreen",
+            Action::Screenshot => "action-screenshot",
+            Action::AddBookmark => "action-add-bookmark",
+            Action::ManageBookmarks => "bookmarks-menu-manage",
+            Action::DebugStage => "debug-menu-open-stage",
+            Action::DebugMovie => "debug-menu-open-movie",
+            Action::DebugMovieList => "debug-menu-open-movie-list",
+            Action::DebugDomainList => "debug-menu-open-domain-list",
+            Action::DebugSearchDisplayObjects => "debug-menu-search-display-objects",
+            Action::SitelockReport => "debug-menu-sitelock-report",
+            Action::DumpHeap => "debug-menu-dump-heap",
+            Action::DumpOpcodeCounts => "debug-menu-dump-opcode-counts",
+            Action::FramePacingStats => "debug-menu-frame-pacing-stats",
+            Action::About => "help-menu-about",
+            Action::CommandPalette => "action-command-palette",
+        }
+    }
+
+    /// The keyboard shortcut running this action, unless the user changed it.
+    pub fn default_shortcut(self) -> Option<KeyboardShortcut> {
+        let shortcut = |modifiers, key| Some(KeyboardShortcut::new(modifiers, key));
+        match self {
+            Action::OpenQuick => shortcut(Modifiers::COMMAND, Key::O),
+            Action::OpenAdvanced => shortcut(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::O),
+            Action::OpenUrl => shortcut(Modifiers::COMMAND, Key::L),
+            Action::Exit => shortcut(Modifiers::COMMAND, Key::Q),
+            Action::TogglePlaying => shortcut(Modifiers::COMMAND, Key::P),
+            Action::Fullscreen => shortcut(Modifiers::NONE, Key::F11),
+            Action::CommandPalette => shortcut(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::P),
+            _ => None,
+        }
+    }
+
+    /// Whether this action can only be run while a movie is open.
+    pub fn needs_player(self) -> bool {
+        matches!(
+            self,
+            Action::Reload
+                | Action::Close
+                | Action::TogglePlaying
+                | Action::ToggleFastForward
+                | Action::Fullscreen
+                | Action::Screenshot
+                | Action::DebugStage
+                | Action::DebugMovie
+                | Action::DebugMovieList
+                | Action::DebugDomainList
+                | Action::DebugSearchDisplayObjects
+                | Action::DumpHeap
+                | Action::DumpOpcodeCounts
+        )
+    }
+}
+
+/// The keyboard shortcuts of all actions, with the changes made by the user applied.
+#[derive(Clone, Debug, Default)]
+pub struct Shortcuts {
+    bindings: BTreeMap<Action, KeyboardShortcut>,
+}
+
+impl Shortcuts {
+    /// Applies the shortcuts set by the user, where `None` removes the shortcut of an action.
+    pub fn new(custom: &BTreeMap<Action, Option<KeyboardShortcut>>) -> Self {
+        let bindings = Action::ALL
+            .into_iter()
+            .filter_map(|action| {
+                let shortcut = match custom.get(&action) {
+                    Some(shortcut) => *shortcut,
+                    None => action.default_shortcut(),
+                };
+                Some((action, shortcut?))
+            })
+            .collect();
+        Self { bindings }
+    }
+
+    pub fn get(&self, action: Action) -> Option<KeyboardShortcut> {
+        self.bindings.get(&action).copied()
+    }
+
+    /// Pairs of actions sharing the same shortcut, of which only the first can be used.
+    pub fn conflicts(&self) -> Vec<(Action, Action)> {
+        let mut conflicts = Vec::new();
+        for (i, (action, shortcut)) in self.bindings.iter().enumerate() {
+            for (other, other_shortcut) in self.bindings.iter().skip(i + 1) {
+                if shortcut == other_shortcut {
+                    conflicts.push((*action, *other));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Consumes the shortcuts pressed since the last frame, returning their actions.
+    pub fn consume_pressed(&self, egui_ctx: &egui::Context) -> Vec<Action> {
+        // Shortcuts ignore extra Shift and Alt modifiers, so e.g. Ctrl+Shift+O must be
+        // checked before Ctrl+O.
+        let mut bindings: Vec<_> = self.bindings.iter().collect();
+        bindings.sort_by_key(|(_, shortcut)| {
+            std::cmp::Reverse(shortcut.modifiers.shift as u8 + shortcut.modifiers.alt as u8)
+        });
+        bindings
+            .into_iter()
+            .filter(|(_, shortcut)| egui_ctx.input_mut(|input| input.consume_shortcut(shortcut)))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+}
+
+/// Parses a shortcut as written in the preferences, e.g. `Ctrl+Shift+P`.
+///
+/// `Ctrl` stands for the Command key on macOS.
+pub fn parse_shortcut(value: &str) -> Option<KeyboardShortcut> {
+    let mut parts: Vec<&str> = value.split('+').map(str::trim).collect();
+    let key = Key::from_name(parts.pop()?)?;
+    let mut modifiers = Modifiers::NONE;
+    for part in parts {
+        modifiers = modifiers.plus(match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "cmd" | "command" => Modifiers::COMMAND,
+            "shift" => Modifiers::SHIFT,
+            "alt" | "option" => Modifiers::ALT,
+            _ => return None,
+        });
+    }
+    Some(KeyboardShortcut::new(modifiers, key))
+}
+
+/// Writes a shortcut the way [`parse_shortcut`] reads it.
+pub fn shortcut_to_string(shortcut: KeyboardShortcut) -> String {
+    let mut value = String::new();
+    for (pressed, name) in [
+        (shortcut.modifiers.command, "Ctrl+"),
+        (shortcut.modifiers.alt, "Alt+"),
+        (shortcut.modifiers.shift, "Shift+"),
+    ] {
+        if pressed {
+            value.push_str(name);
+        }
+    }
+    value.push_str(shortcut.logical_key.name());
+    value
+}
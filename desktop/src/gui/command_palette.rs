@@ -0,0 +1,137 @@
+use crate::gui::actions::Action;
+use crate::gui::text;
+use egui::{
+    vec2, Align, Align2, Button, Key, Layout, Modifiers, ScrollArea, TextEdit, Widget, Window,
+};
+use unic_langid::LanguageIdentifier;
+use url::Url;
+
+/// What running a command of the palette does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PaletteTarget {
+    Action(Action),
+    OpenBookmark(Url),
+}
+
+/// A command listed in the palette.
+pub struct PaletteCommand {
+    pub label: String,
+    pub shortcut: Option<String>,
+    pub target: PaletteTarget,
+}
+
+pub enum PaletteOutcome {
+    Open,
+    Closed,
+    Picked(PaletteTarget),
+}
+
+/// A searchable list of everything the menus can do, opened with a keyboard shortcut.
+#[derive(Default)]
+pub struct CommandPalette {
+    query: String,
+
+    /// The position of the selected command among those matching the query.
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn show(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        commands: Vec<PaletteCommand>,
+    ) -> PaletteOutcome {
+        let matching: Vec<PaletteCommand> = commands
+            .into_iter()
+            .filter(|command| matches_query(&command.label, &self.query))
+            .collect();
+
+        // These are consumed before the text field gets to see them.
+        let (up, down, enter, escape) = egui_ctx.input_mut(|input| {
+            (
+                input.consume_key(Modifiers::NONE, Key::ArrowUp),
+                input.consume_key(Modifiers::NONE, Key::ArrowDown),
+                input.consume_key(Modifiers::NONE, Key::Enter),
+                input.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+        if escape {
+            return PaletteOutcome::Closed;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        if down {
+            self.selected += 1;
+        }
+        self.selected = self.selected.min(matching.len().saturating_sub(1));
+        if enter {
+            return match matching.into_iter().nth(self.selected) {
+                Some(command) => PaletteOutcome::Picked(command.target),
+                None => PaletteOutcome::Open,
+            };
+        }
+
+        let mut picked = None;
+        let window = Window::new(text(locale, "command-palette"))
+            .title_bar(false)
+            .anchor(Align2::CENTER_TOP, vec2(0.0, 48.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                ui.set_width(400.0);
+                let query = TextEdit::singleline(&mut self.query)
+                    .hint_text(text(locale, "command-palette-hint"))
+                    .desired_width(f32::INFINITY)
+                    .ui(ui);
+                query.request_focus();
+                if query.changed() {
+                    self.selected = 0;
+                }
+                ui.separator();
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    if matching.is_empty() {
+                        ui.label(text(locale, "command-palette-no-matches"));
+                    }
+                    ui.with_layout(Layout::top_down_justified(Align::Min), |ui| {
+                        for (position, command) in matching.iter().enumerate() {
+                            let mut button = Button::new(&command.label)
+                                .selected(position == self.selected)
+                                .wrap_mode(egui::TextWrapMode::Extend);
+                            if let Some(shortcut) = &command.shortcut {
+                                button = button.shortcut_text(shortcut);
+                            }
+                            let response = button.ui(ui);
+                            if position == self.selected && (up || down) {
+                                response.scroll_to_me(None);
+                            }
+                            if response.clicked() {
+                                picked = Some(command.target.clone());
+                            }
+                        }
+                    });
+                });
+            });
+
+        match (picked, window) {
+            (Some(target), _) => PaletteOutcome::Picked(target),
+            (None, Some(window)) if window.response.clicked_elsewhere() => PaletteOutcome::Closed,
+            _ => PaletteOutcome::Open,
+        }
+    }
+}
+
+/// Whether all words of the query appear in the label, ignoring case.
+fn matches_query(label: &str, query: &str) -> bool {
+    let label = label.to_lowercase();
+    query
+        .to_lowercase()
+        .split_whitespace()
+        .all(|word| label.contains(word))
+}
@@ -1,8 +1,10 @@
 use crate::custom_event::RuffleEvent;
+use crate::gui::DialogDescriptor;
 use egui::{
     vec2, Align, Area, Button, Checkbox, Color32, Frame, Id, Key, KeyboardShortcut, Layout,
     Modifiers, Order, Pos2, Stroke, Style, Widget,
 };
+use ruffle_core::backend::ui::SettingsPanel;
 use ruffle_core::{ContextMenuItem, PlayerEvent};
 use unic_langid::LanguageIdentifier;
 use winit::event_loop::EventLoopProxy;
@@ -67,6 +69,18 @@ impl ContextMenu {
                             }
                         }
 
+                        ui.separator();
+                        if Button::new(text(locale, "context-menu-settings"))
+                            .wrap_mode(egui::TextWrapMode::Extend)
+                            .ui(ui)
+                            .clicked()
+                        {
+                            let _ = event_loop.send_event(RuffleEvent::OpenDialog(
+                                DialogDescriptor::Settings(SettingsPanel::Default),
+                            ));
+                            item_clicked = true;
+                        }
+
                         if fullscreen {
                             ui.separator();
                             if Button::new(text(locale, "context-menu-exit-fullscreen"))
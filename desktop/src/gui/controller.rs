@@ -1,5 +1,6 @@
 use crate::backends::DesktopUiBackend;
 use crate::custom_event::RuffleEvent;
+use crate::frame_pacing::FramePacer;
 use crate::gui::movie::{MovieView, MovieViewRenderer};
 use crate::gui::theme::ThemeController;
 use crate::gui::{RuffleGui, MENU_HEIGHT};
@@ -9,8 +10,10 @@ use anyhow::anyhow;
 use egui::{Context, ViewportId};
 use fontdb::{Database, Family, Query, Source};
 use ruffle_core::{Player, PlayerEvent};
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::descriptors::Descriptors;
+use ruffle_render_wgpu::target::RenderTarget;
 use ruffle_render_wgpu::utils::{format_list, get_backend_names};
 use std::sync::{Arc, MutexGuard};
 use std::time::{Duration, Instant};
@@ -37,12 +40,18 @@ pub struct GuiController {
     surface: wgpu::Surface<'static>,
     surface_format: wgpu::TextureFormat,
     movie_view_renderer: Arc<MovieViewRenderer>,
+    /// The view that frames of the software renderer are uploaded to, if it's in use.
+    software_movie_view: Option<MovieView>,
     // Note that `window.get_inner_size` can change at any point on x11, even between two lines of code.
     // Use this instead.
     size: PhysicalSize<u32>,
     /// If this is set, we should not render the main menu.
     no_gui: bool,
     theme_controller: ThemeController,
+    preferences: GlobalPreferences,
+    /// Whether the surface is currently configured to wait for vertical blanks.
+    vsync: bool,
+    frame_pacer: FramePacer,
 }
 
 impl GuiController {
@@ -80,6 +89,7 @@ impl GuiController {
             .cloned()
             .expect("At least one format should be supported");
         let size = window.inner_size();
+        let vsync = preferences.vsync();
         surface.configure(
             &device,
             &wgpu::SurfaceConfiguration {
@@ -87,7 +97,7 @@ impl GuiController {
                 format: surface_format,
                 width: size.width,
                 height: size.height,
-                present_mode: Default::default(),
+                present_mode: present_mode(vsync),
                 desired_maximum_frame_latency: 2,
                 alpha_mode: Default::default(),
                 view_formats: Default::default(),
@@ -145,9 +155,13 @@ impl GuiController {
             surface,
             surface_format,
             movie_view_renderer,
+            software_movie_view: None,
             size,
             no_gui,
             theme_controller,
+            preferences,
+            vsync,
+            frame_pacer: FramePacer::default(),
         })
     }
 
@@ -182,7 +196,7 @@ impl GuiController {
                 format: self.surface_format,
                 width: self.size.width,
                 height: self.size.height,
-                present_mode: Default::default(),
+                present_mode: present_mode(self.vsync),
                 desired_maximum_frame_latency: 2,
                 alpha_mode: Default::default(),
                 view_formats: Default::default(),
@@ -250,6 +264,12 @@ impl GuiController {
     }
 
     pub fn render(&mut self, mut player: Option<MutexGuard<Player>>) {
+        let vsync = self.preferences.vsync();
+        if vsync != self.vsync {
+            self.vsync = vsync;
+            self.reconfigure_surface();
+        }
+
         let surface_texture = match self.surface.get_current_texture() {
             Ok(surface_texture) => surface_texture,
             Err(e @ (SurfaceError::Lost | SurfaceError::Outdated)) => {
@@ -280,6 +300,7 @@ impl GuiController {
 
         let raw_input = self.egui_winit.take_egui_input(&self.window);
         let show_menu = self.window.fullscreen().is_none() && !self.no_gui;
+        let frame_pacing_stats = self.frame_pacer.stats(Instant::now());
         let mut full_output = self.egui_winit.egui_ctx().run(raw_input, |context| {
             self.gui.update(
                 context,
@@ -290,6 +311,7 @@ impl GuiController {
                 } else {
                     0.0
                 },
+                &frame_pacing_stats,
             );
         });
         self.repaint_after = full_output
@@ -347,11 +369,30 @@ impl GuiController {
         );
 
         let movie_view = if let Some(player) = player.as_deref_mut() {
-            let renderer = player
-                .renderer_mut()
-                .downcast_mut::<WgpuRenderBackend<MovieView>>()
-                .expect("Renderer must be correct type");
-            Some(renderer.target())
+            let renderer = player.renderer();
+            if let Some(renderer) = renderer.downcast_ref::<WgpuRenderBackend<MovieView>>() {
+                Some(renderer.target())
+            } else {
+                let renderer = renderer
+                    .downcast_ref::<SoftwareRenderBackend>()
+                    .expect("Renderer must be correct type");
+                let pixmap = renderer.target();
+                let movie_view = match self.software_movie_view.take() {
+                    Some(view)
+                        if view.width() == pixmap.width() && view.height() == pixmap.height() =>
+                    {
+                        view
+                    }
+                    _ => MovieView::new(
+                        self.movie_view_renderer.clone(),
+                        &self.descriptors.device,
+                        pixmap.width(),
+                        pixmap.height(),
+                    ),
+                };
+                movie_view.upload(&self.descriptors.queue, pixmap.data());
+                Some(&*self.software_movie_view.insert(movie_view))
+            }
         } else {
             None
         };
@@ -389,6 +430,11 @@ impl GuiController {
         command_buffers.push(encoder.finish());
         self.descriptors.queue.submit(command_buffers);
         surface_texture.present();
+        self.frame_pacer.record_present(Instant::now());
+    }
+
+    pub fn frame_pacer_mut(&mut self) -> &mut FramePacer {
+        &mut self.frame_pacer
     }
 
     pub fn show_context_menu(
@@ -533,3 +579,12 @@ fn load_system_fonts(
 
     Ok(fd)
 }
+
+fn present_mode(vsync: bool) -> wgpu::PresentMode {
+    if vsync {
+        wgpu::PresentMode::Fifo
+    } else {
+        // Falls back to `Fifo` where tearing isn't supported.
+        wgpu::PresentMode::AutoNoVsync
+    }
+}
@@ -1,25 +1,37 @@
 mod about_dialog;
 mod bookmarks_dialog;
 pub mod filesystem_access_dialog;
+mod local_storage_dialog;
 pub mod message_dialog;
 pub mod network_access_dialog;
 mod open_dialog;
+mod open_location_dialog;
 mod open_url_dialog;
 mod preferences_dialog;
+mod settings_dialog;
+mod sitelock_report_dialog;
+pub mod storage_quota_dialog;
 mod volume_controls;
 
 use crate::custom_event::RuffleEvent;
 use crate::player::LaunchOptions;
+use crate::preferences::sites::site_for_url;
 use crate::preferences::GlobalPreferences;
 use bookmarks_dialog::{BookmarkAddDialog, BookmarksDialog};
 use filesystem_access_dialog::{FilesystemAccessDialog, FilesystemAccessDialogConfiguration};
+use local_storage_dialog::LocalStorageDialog;
 use message_dialog::{MessageDialog, MessageDialogConfiguration};
 use network_access_dialog::{NetworkAccessDialog, NetworkAccessDialogConfiguration};
 use open_dialog::OpenDialog;
+use open_location_dialog::OpenLocationDialog;
 use open_url_dialog::OpenUrlDialog;
 use preferences_dialog::PreferencesDialog;
+use ruffle_core::backend::ui::SettingsPanel;
 use ruffle_core::Player;
+use settings_dialog::SettingsDialog;
+use sitelock_report_dialog::SitelockReportDialog;
 use std::{collections::VecDeque, sync::Weak};
+use storage_quota_dialog::{StorageQuotaDialog, StorageQuotaDialogConfiguration};
 use unic_langid::LanguageIdentifier;
 use url::Url;
 use volume_controls::VolumeControls;
@@ -35,7 +47,17 @@ pub struct Dialogs {
     bookmarks_dialog: Option<BookmarksDialog>,
     bookmark_add_dialog: Option<BookmarkAddDialog>,
     open_url_dialog: Option<OpenUrlDialog>,
+    open_location_dialog: Option<OpenLocationDialog>,
     message_dialog: Option<MessageDialog>,
+    settings_dialog: Option<SettingsDialog>,
+    local_storage_dialog: Option<LocalStorageDialog>,
+    sitelock_report_dialog: Option<SitelockReportDialog>,
+
+    /// The Settings panel reopens on this panel when a movie doesn't ask for a particular one.
+    last_settings_panel: SettingsPanel,
+
+    /// The site the current movie was loaded from, which the Settings panel applies to.
+    movie_site: String,
 
     // Use a queue for the following dialogs in order to:
     //  1. support handling multiple instances of them,
@@ -44,10 +66,14 @@ pub struct Dialogs {
     network_access_dialog_queue: VecDeque<NetworkAccessDialog>,
     filesystem_access_dialog: Option<FilesystemAccessDialog>,
     filesystem_access_dialog_queue: VecDeque<FilesystemAccessDialogConfiguration>,
+    storage_quota_dialog_queue: VecDeque<StorageQuotaDialog>,
 
     open_dialog: OpenDialog,
     is_open_dialog_visible: bool,
 
+    /// The options that movies opened from the Open URL dialog start with.
+    launch_options: LaunchOptions,
+
     pub volume_controls: VolumeControls,
     is_volume_visible: bool,
 
@@ -61,6 +87,8 @@ pub enum DialogDescriptor {
     ShowMessage(MessageDialogConfiguration),
     NetworkAccess(NetworkAccessDialogConfiguration),
     FilesystemAccess(FilesystemAccessDialogConfiguration),
+    Settings(SettingsPanel),
+    StorageQuota(StorageQuotaDialogConfiguration),
 }
 
 impl Dialogs {
@@ -77,19 +105,29 @@ impl Dialogs {
             bookmarks_dialog: None,
             bookmark_add_dialog: None,
             open_url_dialog: None,
+            open_location_dialog: None,
             message_dialog: None,
+            settings_dialog: None,
+            local_storage_dialog: None,
+            sitelock_report_dialog: None,
+
+            last_settings_panel: SettingsPanel::Privacy,
+            movie_site: "localhost".to_string(),
 
             network_access_dialog_queue: VecDeque::new(),
             filesystem_access_dialog: None,
             filesystem_access_dialog_queue: VecDeque::new(),
+            storage_quota_dialog_queue: VecDeque::new(),
 
             open_dialog: OpenDialog::new(
-                player_options,
+                player_options.clone(),
                 default_path,
                 picker.clone(),
                 event_loop.clone(),
+                preferences.clone(),
             ),
             is_open_dialog_visible: false,
+            launch_options: player_options,
 
             volume_controls: VolumeControls::new(&preferences),
             is_volume_visible: false,
@@ -113,17 +151,40 @@ impl Dialogs {
         event_loop: EventLoopProxy<RuffleEvent>,
     ) {
         self.is_open_dialog_visible = false;
-        self.open_dialog = OpenDialog::new(opt, url, self.picker.clone(), event_loop);
+        self.open_dialog = OpenDialog::new(
+            opt,
+            url,
+            self.picker.clone(),
+            event_loop,
+            self.preferences.clone(),
+        );
+    }
+
+    /// Makes the Settings panel apply to the site of a newly opened movie.
+    pub fn set_movie_url(&mut self, url: &Url) {
+        self.movie_site = site_for_url(url);
     }
 
     pub fn open_file_advanced(&mut self) {
         self.is_open_dialog_visible = true;
     }
 
+    pub fn open_location(&mut self) {
+        self.open_location_dialog = Some(OpenLocationDialog::new(
+            self.launch_options.clone(),
+            self.preferences.clone(),
+            self.event_loop.clone(),
+        ));
+    }
+
     pub fn open_preferences(&mut self) {
         self.preferences_dialog = Some(PreferencesDialog::new(self.preferences.clone()));
     }
 
+    pub fn open_local_storage(&mut self) {
+        self.local_storage_dialog = Some(LocalStorageDialog::new(self.preferences.clone()));
+    }
+
     pub fn open_bookmarks(&mut self) {
         self.bookmarks_dialog = Some(BookmarksDialog::new(
             self.preferences.clone(),
@@ -140,6 +201,11 @@ impl Dialogs {
         ))
     }
 
+    pub fn open_sitelock_report(&mut self, movie: Option<(Url, LaunchOptions)>) {
+        self.sitelock_report_dialog =
+            Some(SitelockReportDialog::new(self.event_loop.clone(), movie));
+    }
+
     pub fn open_volume_controls(&mut self) {
         self.is_volume_visible = true;
     }
@@ -162,6 +228,17 @@ impl Dialogs {
             DialogDescriptor::FilesystemAccess(config) => {
                 self.filesystem_access_dialog_queue.push_back(config)
             }
+            DialogDescriptor::Settings(panel) => {
+                self.settings_dialog = Some(SettingsDialog::new(
+                    self.preferences.clone(),
+                    self.movie_site.clone(),
+                    panel,
+                    self.last_settings_panel,
+                ));
+            }
+            DialogDescriptor::StorageQuota(config) => self
+                .storage_quota_dialog_queue
+                .push_back(StorageQuotaDialog::new(config, self.preferences.clone())),
         }
     }
 
@@ -169,16 +246,21 @@ impl Dialogs {
         &mut self,
         locale: &LanguageIdentifier,
         egui_ctx: &egui::Context,
-        player: Option<&mut Player>,
+        mut player: Option<&mut Player>,
     ) {
         self.show_open_dialog(locale, egui_ctx);
+        self.show_open_location_dialog(locale, egui_ctx);
         self.show_preferences_dialog(locale, egui_ctx);
         self.show_bookmarks_dialog(locale, egui_ctx);
         self.show_bookmark_add_dialog(locale, egui_ctx);
+        self.show_sitelock_report_dialog(locale, egui_ctx, player.as_deref_mut());
         self.show_volume_controls(locale, egui_ctx, player);
         self.show_about_dialog(locale, egui_ctx);
         self.show_open_url_dialog(locale, egui_ctx);
         self.show_message_dialog(locale, egui_ctx);
+        self.show_settings_dialog(locale, egui_ctx);
+        self.show_local_storage_dialog(locale, egui_ctx);
+        self.show_storage_quota_dialog(locale, egui_ctx);
         self.show_network_access_dialog(locale, egui_ctx);
         self.show_filesystem_access_dialog(locale, egui_ctx);
     }
@@ -190,6 +272,17 @@ impl Dialogs {
         }
     }
 
+    fn show_open_location_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        let keep_open = if let Some(dialog) = &mut self.open_location_dialog {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.open_location_dialog = None;
+        }
+    }
+
     fn show_preferences_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
         let keep_open = if let Some(dialog) = &mut self.preferences_dialog {
             dialog.show(locale, egui_ctx)
@@ -237,6 +330,22 @@ impl Dialogs {
         }
     }
 
+    fn show_sitelock_report_dialog(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        player: Option<&mut Player>,
+    ) {
+        let keep_open = if let Some(dialog) = &mut self.sitelock_report_dialog {
+            dialog.show(locale, egui_ctx, player)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.sitelock_report_dialog = None;
+        }
+    }
+
     fn show_about_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
         if self.is_about_visible {
             let keep_open = about_dialog::show_about_dialog(locale, egui_ctx);
@@ -266,6 +375,41 @@ impl Dialogs {
         }
     }
 
+    fn show_settings_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        let keep_open = if let Some(dialog) = &mut self.settings_dialog {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            if let Some(dialog) = self.settings_dialog.take() {
+                self.last_settings_panel = dialog.panel();
+            }
+        }
+    }
+
+    fn show_local_storage_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        let keep_open = if let Some(dialog) = &mut self.local_storage_dialog {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.local_storage_dialog = None;
+        }
+    }
+
+    fn show_storage_quota_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        let keep_open = if let Some(dialog) = &mut self.storage_quota_dialog_queue.front_mut() {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.storage_quota_dialog_queue.pop_front();
+        }
+    }
+
     fn show_network_access_dialog(
         &mut self,
         locale: &LanguageIdentifier,
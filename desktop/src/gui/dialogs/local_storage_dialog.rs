@@ -0,0 +1,184 @@
+use crate::cli::get_default_flash_player_directory;
+use crate::gui::dialogs::storage_quota_dialog::format_size;
+use crate::gui::{text, text_with_args};
+use crate::preferences::GlobalPreferences;
+use egui::{Align2, Button, CollapsingHeader, Grid, ScrollArea, Ui, Widget, Window};
+use fluent_templates::fluent_bundle::FluentValue;
+use rfd::FileDialog;
+use ruffle_frontend_utils::backends::storage::{
+    import_flash_player_shared_objects, stored_shared_objects, StoredSharedObject,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use unic_langid::LanguageIdentifier;
+
+/// Lists the shared objects that movies have saved, so that the user can export or delete them.
+pub struct LocalStorageDialog {
+    preferences: GlobalPreferences,
+    shared_objects: BTreeMap<String, Vec<StoredSharedObject>>,
+}
+
+impl LocalStorageDialog {
+    pub fn new(preferences: GlobalPreferences) -> Self {
+        let shared_objects = stored_shared_objects(&preferences.cli.save_directory);
+        Self {
+            preferences,
+            shared_objects,
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.shared_objects = stored_shared_objects(&self.preferences.cli.save_directory);
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+
+        Window::new(text(locale, "local-storage-dialog"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(true)
+            .show(egui_ctx, |ui| {
+                let mut changed = false;
+                if self.shared_objects.is_empty() {
+                    ui.label(text(locale, "local-storage-dialog-empty"));
+                } else {
+                    ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                        for (site, shared_objects) in &self.shared_objects {
+                            changed |= self.show_site(locale, site, shared_objects, ui);
+                        }
+                    });
+                }
+
+                ui.separator();
+                if Button::new(text(locale, "local-storage-dialog-import"))
+                    .ui(ui)
+                    .clicked()
+                {
+                    changed |= self.import();
+                }
+                if changed {
+                    self.refresh();
+                }
+            });
+
+        keep_open
+    }
+
+    /// Asks for a Flash Player directory and copies its saves, returning true if any were imported.
+    fn import(&self) -> bool {
+        let mut dialog = FileDialog::new();
+        if let Some(directory) = get_default_flash_player_directory().filter(|dir| dir.is_dir()) {
+            dialog = dialog.set_directory(directory);
+        }
+        let Some(source) = dialog.pick_folder() else {
+            return false;
+        };
+        match import_flash_player_shared_objects(&source, &self.preferences.cli.save_directory) {
+            Ok(summary) => {
+                tracing::info!(
+                    "Imported {} saves from {source:?} ({} skipped)",
+                    summary.imported,
+                    summary.skipped
+                );
+                summary.imported > 0
+            }
+            Err(e) => {
+                tracing::error!("Couldn't import saves from {source:?}: {e}");
+                false
+            }
+        }
+    }
+
+    /// Shows the shared objects of one site, returning true if any of them were deleted.
+    fn show_site(
+        &self,
+        locale: &LanguageIdentifier,
+        site: &str,
+        shared_objects: &[StoredSharedObject],
+        ui: &mut Ui,
+    ) -> bool {
+        let mut changed = false;
+        let used: u64 = shared_objects.iter().map(|so| so.size).sum();
+        let limit = match self
+            .preferences
+            .site_preferences(site)
+            .storage_limit
+            .bytes()
+        {
+            Some(bytes) => format_size(bytes),
+            None => text(locale, "settings-panel-storage-unlimited").into_owned(),
+        };
+        let header = text_with_args(
+            locale,
+            "local-storage-dialog-site",
+            &HashMap::from([
+                ("site", FluentValue::from(site)),
+                ("used", FluentValue::from(format_size(used))),
+                ("limit", FluentValue::from(limit)),
+            ]),
+        );
+
+        CollapsingHeader::new(header).id_salt(site).show(ui, |ui| {
+            Grid::new(("local-storage-dialog-site", site))
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    for shared_object in shared_objects {
+                        ui.label(&shared_object.name);
+                        ui.label(format_size(shared_object.size));
+                        if Button::new(text(locale, "local-storage-dialog-export"))
+                            .ui(ui)
+                            .clicked()
+                        {
+                            export(shared_object);
+                        }
+                        if Button::new(text(locale, "remove")).ui(ui).clicked() {
+                            delete(&shared_object.path);
+                            changed = true;
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            if Button::new(text(locale, "local-storage-dialog-remove-site"))
+                .ui(ui)
+                .clicked()
+            {
+                for shared_object in shared_objects {
+                    delete(&shared_object.path);
+                }
+                changed = true;
+            }
+        });
+
+        changed
+    }
+}
+
+fn delete(path: &Path) {
+    if let Err(e) = fs::remove_file(path) {
+        tracing::error!("Couldn't delete {path:?}: {e}");
+    }
+}
+
+fn export(shared_object: &StoredSharedObject) {
+    let source = shared_object.path.clone();
+    let file_name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    std::thread::spawn(move || {
+        if let Some(destination) = FileDialog::new()
+            .set_file_name(&file_name)
+            .add_filter("Local Shared Object", &["sol"])
+            .save_file()
+        {
+            if let Err(e) = fs::copy(&source, &destination) {
+                tracing::error!("Couldn't export {source:?} to {destination:?}: {e}");
+            }
+        }
+    });
+}
@@ -2,6 +2,8 @@ use crate::custom_event::RuffleEvent;
 use crate::gui::widgets::PathOrUrlField;
 use crate::gui::{text, FilePicker, LocalizableText};
 use crate::player::LaunchOptions;
+use crate::preferences::movies::MoviePreferences;
+use crate::preferences::GlobalPreferences;
 use egui::{
     emath, Align2, Button, Checkbox, ComboBox, Grid, Layout, Slider, TextEdit, Ui, Widget, Window,
 };
@@ -19,6 +21,10 @@ use winit::event_loop::EventLoopProxy;
 pub struct OpenDialog {
     options: LaunchOptions,
     event_loop: EventLoopProxy<RuffleEvent>,
+    preferences: GlobalPreferences,
+
+    /// Whether the spoofed and base URLs should be used whenever this movie is opened.
+    remember_movie_urls: bool,
 
     // These are outside of PlayerOptions as it can be an invalid value (ie URL) during typing,
     // and we don't want to clear the value if the user, ie, toggles the checkbox.
@@ -42,6 +48,8 @@ pub struct OpenDialog {
     player_version: OptionalField<NumberField<u8>>,
     player_runtime: OptionalField<EnumDropdownField<PlayerRuntime>>,
     dummy_external_interface: OptionalField<BooleanDropdownField>,
+    relaxed_security: OptionalField<BooleanDropdownField>,
+    partial_playback: OptionalField<BooleanDropdownField>,
     upgrade_to_https: OptionalField<BooleanDropdownField>,
 }
 
@@ -51,6 +59,7 @@ impl OpenDialog {
         default_url: Option<Url>,
         picker: FilePicker,
         event_loop: EventLoopProxy<RuffleEvent>,
+        preferences: GlobalPreferences,
     ) -> Self {
         let spoof_url = OptionalField::new(
             defaults.player.spoof_url.as_ref().map(Url::to_string),
@@ -245,6 +254,26 @@ impl OpenDialog {
                 }),
             ),
         );
+        let relaxed_security = OptionalField::new(
+            defaults.player.relaxed_security,
+            BooleanDropdownField::new(
+                false,
+                Box::new(|value, locale| match value {
+                    true => text(locale, "enable"),
+                    false => text(locale, "disable"),
+                }),
+            ),
+        );
+        let partial_playback = OptionalField::new(
+            defaults.player.partial_playback,
+            BooleanDropdownField::new(
+                false,
+                Box::new(|value, locale| match value {
+                    true => text(locale, "enable"),
+                    false => text(locale, "disable"),
+                }),
+            ),
+        );
         let upgrade_to_https = OptionalField::new(
             defaults.player.upgrade_to_https,
             BooleanDropdownField::new(
@@ -259,6 +288,8 @@ impl OpenDialog {
         Self {
             options: defaults,
             event_loop,
+            preferences,
+            remember_movie_urls: false,
             spoof_url,
             referer,
             cookie,
@@ -277,6 +308,8 @@ impl OpenDialog {
             player_version,
             player_runtime,
             dummy_external_interface,
+            relaxed_security,
+            partial_playback,
             upgrade_to_https,
         }
     }
@@ -288,6 +321,9 @@ impl OpenDialog {
             self.options.player.frame_rate = None;
         }
         if let Some(url) = self.path.result() {
+            if self.remember_movie_urls {
+                remember_movie_urls(&self.preferences, url, &self.options);
+            }
             if self
                 .event_loop
                 .send_event(RuffleEvent::Open(
@@ -374,6 +410,10 @@ impl OpenDialog {
                     .is_valid();
                 ui.end_row();
 
+                ui.label(text(locale, "remember-movie-urls"));
+                ui.checkbox(&mut self.remember_movie_urls, "");
+                ui.end_row();
+
                 ui.label(text(locale, "referer-url"));
                 is_valid &= self
                     .referer
@@ -508,6 +548,18 @@ impl OpenDialog {
                 );
                 ui.end_row();
 
+                ui.label(text(locale, "relaxed-security"))
+                    .on_hover_text(text(locale, "relaxed-security-tooltip"));
+                self.relaxed_security
+                    .ui(ui, &mut self.options.player.relaxed_security, locale);
+                ui.end_row();
+
+                ui.label(text(locale, "partial-playback"))
+                    .on_hover_text(text(locale, "partial-playback-tooltip"));
+                self.partial_playback
+                    .ui(ui, &mut self.options.player.partial_playback, locale);
+                ui.end_row();
+
                 ui.label(text(locale, "player-version"));
                 self.player_version
                     .ui(ui, &mut self.options.player.player_version, locale);
@@ -594,6 +646,21 @@ trait InnerField {
     fn value_to_result(&self, value: &Self::Value) -> Result<Self::Result, ()>;
 }
 
+/// Stores the spoofed and base URLs of the options as the ones to use for the movie at `url`.
+pub fn remember_movie_urls(preferences: &GlobalPreferences, url: &Url, options: &LaunchOptions) {
+    if let Err(e) = preferences.write_preferences(|writer| {
+        writer.set_movie_preferences(
+            url,
+            MoviePreferences {
+                spoof_url: options.player.spoof_url.clone(),
+                base_url: options.player.base.clone(),
+            },
+        )
+    }) {
+        tracing::warn!("Couldn't update movie preferences: {e}");
+    }
+}
+
 struct UrlField {
     hint: &'static str,
 }
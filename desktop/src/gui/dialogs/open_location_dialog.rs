@@ -0,0 +1,206 @@
+use super::open_dialog::remember_movie_urls;
+use crate::custom_event::RuffleEvent;
+use crate::gui::text;
+use crate::player::LaunchOptions;
+use crate::preferences::GlobalPreferences;
+use egui::{Align2, Button, Checkbox, Grid, Layout, TextEdit, Ui, Widget, Window};
+use unic_langid::LanguageIdentifier;
+use url::Url;
+use winit::event_loop::EventLoopProxy;
+
+/// Opens a movie hosted on the web, optionally with the headers a site would send for it.
+///
+/// Many web games check where they were loaded from, or expect the cookies and referer
+/// of the page that embeds them, so these can be set before the movie is requested.
+pub struct OpenLocationDialog {
+    options: LaunchOptions,
+    event_loop: EventLoopProxy<RuffleEvent>,
+    preferences: GlobalPreferences,
+
+    /// Previously opened web URLs, newest first.
+    recent_urls: Vec<Url>,
+
+    url: String,
+    referer: String,
+    cookie: String,
+    spoof_url_enabled: bool,
+    spoof_url: String,
+    base_url: String,
+    remember_movie_urls: bool,
+}
+
+impl OpenLocationDialog {
+    pub fn new(
+        defaults: LaunchOptions,
+        preferences: GlobalPreferences,
+        event_loop: EventLoopProxy<RuffleEvent>,
+    ) -> Self {
+        let mut recent_urls: Vec<Url> = Vec::new();
+        preferences.recents(|recents| {
+            for recent in recents.iter().rev() {
+                if matches!(recent.url.scheme(), "http" | "https")
+                    && !recent_urls.contains(&recent.url)
+                {
+                    recent_urls.push(recent.url.clone());
+                }
+            }
+        });
+
+        Self {
+            url: recent_urls.first().map(Url::to_string).unwrap_or_default(),
+            referer: defaults
+                .player
+                .referer
+                .as_ref()
+                .map(Url::to_string)
+                .unwrap_or_default(),
+            cookie: defaults.player.cookie.clone().unwrap_or_default(),
+            spoof_url_enabled: defaults.player.spoof_url.is_some(),
+            spoof_url: defaults
+                .player
+                .spoof_url
+                .as_ref()
+                .map(Url::to_string)
+                .unwrap_or_default(),
+            base_url: defaults
+                .player
+                .base
+                .as_ref()
+                .map(Url::to_string)
+                .unwrap_or_default(),
+            remember_movie_urls: false,
+            options: defaults,
+            event_loop,
+            preferences,
+            recent_urls,
+        }
+    }
+
+    fn url(&self) -> Option<Url> {
+        parse_web_url(&self.url)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.url().is_some()
+            && (self.referer.trim().is_empty() || parse_web_url(&self.referer).is_some())
+            && (!self.spoof_url_enabled || parse_web_url(&self.spoof_url).is_some())
+            && (self.base_url.trim().is_empty() || parse_web_url(&self.base_url).is_some())
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+        let mut should_close = false;
+
+        Window::new(text(locale, "open-location-dialog"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                Grid::new("open-location-dialog-grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(text(locale, "open-location-dialog-url"));
+                        ui.horizontal(|ui| {
+                            url_edit(ui, &mut self.url, "https://example.org/game.swf");
+                            ui.add_enabled_ui(!self.recent_urls.is_empty(), |ui| {
+                                ui.menu_button(text(locale, "open-location-dialog-recent"), |ui| {
+                                    for url in &self.recent_urls {
+                                        if ui.button(url.as_str()).clicked() {
+                                            self.url = url.to_string();
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                            });
+                        });
+                        ui.end_row();
+
+                        ui.label(text(locale, "referer-url"));
+                        url_edit(ui, &mut self.referer, "https://example.org");
+                        ui.end_row();
+
+                        ui.label(text(locale, "cookie"));
+                        TextEdit::singleline(&mut self.cookie)
+                            .hint_text("value1=cookie1; value2=cookie2")
+                            .desired_width(f32::INFINITY)
+                            .ui(ui);
+                        ui.end_row();
+
+                        ui.label(text(locale, "spoof-swf-url"));
+                        ui.horizontal(|ui| {
+                            Checkbox::without_text(&mut self.spoof_url_enabled).ui(ui);
+                            ui.add_enabled_ui(self.spoof_url_enabled, |ui| {
+                                url_edit(ui, &mut self.spoof_url, "https://example.org/game.swf");
+                            });
+                        });
+                        ui.end_row();
+
+                        ui.label(text(locale, "custom-base-url"));
+                        url_edit(ui, &mut self.base_url, "https://example.org");
+                        ui.end_row();
+
+                        ui.label(text(locale, "remember-movie-urls"));
+                        ui.checkbox(&mut self.remember_movie_urls, "");
+                        ui.end_row();
+                    });
+
+                ui.separator();
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add_enabled(
+                            self.is_valid(),
+                            Button::new(text(locale, "open-location-dialog-open")),
+                        )
+                        .clicked()
+                    {
+                        should_close = self.open();
+                    }
+                    if Button::new(text(locale, "cancel")).ui(ui).clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        keep_open && !should_close
+    }
+
+    fn open(&mut self) -> bool {
+        let Some(url) = self.url() else {
+            return false;
+        };
+
+        self.options.player.referer = parse_web_url(&self.referer);
+        let cookie = self.cookie.trim();
+        self.options.player.cookie = (!cookie.is_empty()).then(|| cookie.to_string());
+        self.options.player.spoof_url = if self.spoof_url_enabled {
+            parse_web_url(&self.spoof_url)
+        } else {
+            None
+        };
+        self.options.player.base = parse_web_url(&self.base_url);
+        if self.remember_movie_urls {
+            remember_movie_urls(&self.preferences, &url, &self.options);
+        }
+
+        self.event_loop
+            .send_event(RuffleEvent::Open(url, Box::new(self.options.clone())))
+            .is_ok()
+    }
+}
+
+fn parse_web_url(value: &str) -> Option<Url> {
+    Url::parse(value.trim())
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+fn url_edit(ui: &mut Ui, value: &mut String, hint: &str) {
+    let error = !value.trim().is_empty() && parse_web_url(value).is_none();
+    TextEdit::singleline(value)
+        .hint_text(hint)
+        .text_color_opt(error.then(|| ui.style().visuals.error_fg_color))
+        .desired_width(320.0)
+        .ui(ui);
+}
@@ -1,11 +1,22 @@
-use crate::cli::GameModePreference;
-use crate::gui::{available_languages, optional_text, text, ThemePreference};
+use crate::cli::{FocusLossPreference, GameModePreference};
+use crate::gui::{
+    available_languages, optional_text, parse_shortcut, shortcut_to_string, text, text_with_args,
+    Action, Shortcuts, ThemePreference,
+};
 use crate::log::FilenamePattern;
 use crate::preferences::{storage::StorageBackend, GlobalPreferences};
 use cpal::traits::{DeviceTrait, HostTrait};
-use egui::{Align2, Button, Checkbox, ComboBox, DragValue, Grid, Ui, Widget, Window};
+use egui::{
+    Align2, Button, Checkbox, CollapsingHeader, ComboBox, DragValue, Grid, KeyboardShortcut,
+    ScrollArea, TextEdit, Ui, Widget, Window,
+};
+use fluent_templates::fluent_bundle::FluentValue;
+use rfd::FileDialog;
+use ruffle_render_wgpu::backend::BlendSpace;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 use unic_langid::LanguageIdentifier;
 
 pub struct PreferencesDialog {
@@ -20,10 +31,33 @@ pub struct PreferencesDialog {
     power_preference_readonly: bool,
     power_preference_changed: bool,
 
+    /// In megabytes, with 0 meaning no limit.
+    gpu_memory_budget: u64,
+    gpu_memory_budget_readonly: bool,
+    gpu_memory_budget_changed: bool,
+
+    blend_space: BlendSpace,
+    blend_space_changed: bool,
+
+    /// With 0 meaning the default.
+    morph_shape_cache_size: usize,
+    morph_shape_cache_size_changed: bool,
+
+    vsync: bool,
+    vsync_readonly: bool,
+    vsync_changed: bool,
+
     gamemode_preference: GameModePreference,
     gamemode_preference_readonly: bool,
     gamemode_preference_changed: bool,
 
+    focus_loss_preference: FocusLossPreference,
+    focus_loss_preference_readonly: bool,
+    focus_loss_preference_changed: bool,
+
+    background_frame_rate: f64,
+    background_frame_rate_changed: bool,
+
     language: LanguageIdentifier,
     language_changed: bool,
 
@@ -38,15 +72,51 @@ pub struct PreferencesDialog {
     recent_limit: usize,
     recent_limit_changed: bool,
 
+    mouse_wheel_scale: f64,
+    mouse_wheel_scale_changed: bool,
+
+    coalesce_mouse_moves: bool,
+    coalesce_mouse_moves_changed: bool,
+
     log_filename_pattern: FilenamePattern,
     log_filename_pattern_changed: bool,
 
+    crash_reports: bool,
+    crash_reports_changed: bool,
+
+    movie_trace_files: bool,
+    movie_trace_files_changed: bool,
+
+    mirror_movie_traces: bool,
+    mirror_movie_traces_changed: bool,
+
     storage_backend: StorageBackend,
     storage_backend_readonly: bool,
     storage_backend_changed: bool,
 
+    air_sandbox_directory: Option<PathBuf>,
+    air_sandbox_directory_changed: bool,
+
+    /// The network timeout in seconds, where 0 means no timeout.
+    network_timeout: f64,
+    network_timeout_changed: bool,
+
+    network_retries: u32,
+    network_retries_changed: bool,
+
+    offline: bool,
+    offline_changed: bool,
+
+    relaxed_security: bool,
+    relaxed_security_readonly: bool,
+    relaxed_security_changed: bool,
+
     theme_preference: ThemePreference,
     theme_preference_changed: bool,
+
+    /// The shortcut of each action, as typed by the user.
+    shortcuts: Vec<(Action, String)>,
+    shortcuts_changed: bool,
 }
 
 impl PreferencesDialog {
@@ -73,10 +143,31 @@ impl PreferencesDialog {
             power_preference_readonly: preferences.cli.power.is_some(),
             power_preference_changed: false,
 
+            gpu_memory_budget: preferences.gpu_memory_budget().unwrap_or_default(),
+            gpu_memory_budget_readonly: preferences.cli.gpu_memory_budget.is_some(),
+            gpu_memory_budget_changed: false,
+
+            blend_space: preferences.blend_space(),
+            blend_space_changed: false,
+
+            morph_shape_cache_size: preferences.morph_shape_cache_size().unwrap_or_default(),
+            morph_shape_cache_size_changed: false,
+
+            vsync: preferences.vsync(),
+            vsync_readonly: preferences.cli.no_vsync,
+            vsync_changed: false,
+
             gamemode_preference: preferences.gamemode_preference(),
             gamemode_preference_readonly: preferences.cli.gamemode.is_some(),
             gamemode_preference_changed: false,
 
+            focus_loss_preference: preferences.focus_loss_preference(),
+            focus_loss_preference_readonly: preferences.cli.focus_loss.is_some(),
+            focus_loss_preference_changed: false,
+
+            background_frame_rate: preferences.background_frame_rate(),
+            background_frame_rate_changed: false,
+
             language: preferences.language(),
             language_changed: false,
 
@@ -91,16 +182,59 @@ impl PreferencesDialog {
             recent_limit: preferences.recent_limit(),
             recent_limit_changed: false,
 
+            mouse_wheel_scale: preferences.mouse_wheel_scale(),
+            mouse_wheel_scale_changed: false,
+
+            coalesce_mouse_moves: preferences.coalesce_mouse_moves(),
+            coalesce_mouse_moves_changed: false,
+
             log_filename_pattern: preferences.log_filename_pattern(),
             log_filename_pattern_changed: false,
 
+            crash_reports: preferences.crash_reports_enabled(),
+            crash_reports_changed: false,
+
+            movie_trace_files: preferences.movie_trace_files(),
+            movie_trace_files_changed: false,
+
+            mirror_movie_traces: preferences.mirror_movie_traces(),
+            mirror_movie_traces_changed: false,
+
             storage_backend: preferences.storage_backend(),
             storage_backend_readonly: preferences.cli.storage.is_some(),
             storage_backend_changed: false,
 
+            air_sandbox_directory: preferences.air_sandbox_directory(),
+            air_sandbox_directory_changed: false,
+
+            network_timeout: preferences.network_timeout().unwrap_or_default(),
+            network_timeout_changed: false,
+
+            network_retries: preferences.network_retries(),
+            network_retries_changed: false,
+
+            offline: preferences.offline(),
+            offline_changed: false,
+
+            relaxed_security: preferences.cli.relaxed_security || preferences.relaxed_security(),
+            relaxed_security_readonly: preferences.cli.relaxed_security,
+            relaxed_security_changed: false,
+
             theme_preference: preferences.theme_preference(),
             theme_preference_changed: false,
 
+            shortcuts: {
+                let shortcuts = preferences.shortcuts();
+                Action::ALL
+                    .into_iter()
+                    .map(|action| {
+                        let shortcut = shortcuts.get(action).map(shortcut_to_string);
+                        (action, shortcut.unwrap_or_default())
+                    })
+                    .collect()
+            },
+            shortcuts_changed: false,
+
             preferences,
         }
     }
@@ -127,6 +261,8 @@ impl PreferencesDialog {
                                 self.show_gamemode_preferences(locale, &locked_text, ui);
                             }
 
+                            self.show_focus_loss_preferences(locale, &locked_text, ui);
+
                             self.show_language_preferences(locale, ui);
 
                             self.show_theme_preferences(locale, ui);
@@ -139,9 +275,17 @@ impl PreferencesDialog {
 
                             self.show_storage_preferences(locale, &locked_text, ui);
 
+                            self.show_air_preferences(locale, ui);
+
+                            self.show_network_preferences(locale, &locked_text, ui);
+
+                            self.show_input_preferences(locale, ui);
+
                             self.show_misc_preferences(locale, ui);
                         });
 
+                    self.show_shortcut_preferences(locale, ui);
+
                     if self.restart_required() {
                         ui.colored_label(
                             ui.style().visuals.error_fg_color,
@@ -169,7 +313,9 @@ impl PreferencesDialog {
             || self.output_device != self.preferences.output_device_name()
             || self.enable_openh264 != self.preferences.openh264_enabled()
             || self.log_filename_pattern != self.preferences.log_filename_pattern()
+            || self.crash_reports != self.preferences.crash_reports_enabled()
             || self.storage_backend != self.preferences.storage_backend()
+            || self.air_sandbox_directory != self.preferences.air_sandbox_directory()
     }
 
     fn show_graphics_preferences(
@@ -220,6 +366,11 @@ impl PreferencesDialog {
                             "OpenGL",
                         );
                     }
+                    ui.selectable_value(
+                        &mut self.graphics_backend,
+                        GraphicsBackend::Software,
+                        text(locale, "graphics-backend-software"),
+                    );
                 });
             if self.graphics_backend != previous {
                 self.graphics_backend_changed = true;
@@ -252,6 +403,70 @@ impl PreferencesDialog {
             }
         }
         ui.end_row();
+
+        ui.label(text(locale, "gpu-memory-budget"))
+            .on_hover_text(text(locale, "gpu-memory-budget-tooltip"));
+        if self.gpu_memory_budget_readonly {
+            ui.label(format!("{} MB", self.gpu_memory_budget))
+                .on_hover_text(locked_text);
+        } else {
+            let previous = self.gpu_memory_budget;
+            DragValue::new(&mut self.gpu_memory_budget)
+                .speed(16)
+                .suffix(" MB")
+                .ui(ui);
+            if self.gpu_memory_budget != previous {
+                self.gpu_memory_budget_changed = true;
+            }
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "blend-space"))
+            .on_hover_text(text(locale, "blend-space-tooltip"));
+        let previous = self.blend_space;
+        ComboBox::from_id_salt("blend-space")
+            .selected_text(blend_space_name(locale, self.blend_space))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut self.blend_space,
+                    BlendSpace::Flash,
+                    text(locale, "blend-space-flash"),
+                );
+                ui.selectable_value(
+                    &mut self.blend_space,
+                    BlendSpace::Linear,
+                    text(locale, "blend-space-linear"),
+                );
+            });
+        if self.blend_space != previous {
+            self.blend_space_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "morph-shape-cache-size"))
+            .on_hover_text(text(locale, "morph-shape-cache-size-tooltip"));
+        let previous = self.morph_shape_cache_size;
+        DragValue::new(&mut self.morph_shape_cache_size)
+            .range(0..=4096)
+            .ui(ui);
+        if self.morph_shape_cache_size != previous {
+            self.morph_shape_cache_size_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "vsync"))
+            .on_hover_text(text(locale, "vsync-tooltip"));
+        if self.vsync_readonly {
+            ui.add_enabled(false, Checkbox::without_text(&mut self.vsync))
+                .on_disabled_hover_text(locked_text);
+        } else {
+            let previous = self.vsync;
+            ui.add(Checkbox::without_text(&mut self.vsync));
+            if self.vsync != previous {
+                self.vsync_changed = true;
+            }
+        }
+        ui.end_row();
     }
 
     fn show_language_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
@@ -342,6 +557,62 @@ impl PreferencesDialog {
         ui.end_row();
     }
 
+    fn show_focus_loss_preferences(
+        &mut self,
+        locale: &LanguageIdentifier,
+        locked_text: &str,
+        ui: &mut Ui,
+    ) {
+        ui.label(text(locale, "focus-loss"))
+            .on_hover_text_at_pointer(text(locale, "focus-loss-tooltip"));
+        if self.focus_loss_preference_readonly {
+            ui.label(focus_loss_preference_name(
+                locale,
+                self.focus_loss_preference,
+            ))
+            .on_hover_text(locked_text);
+        } else {
+            let previous = self.focus_loss_preference;
+            ComboBox::from_id_salt("focus-loss")
+                .selected_text(focus_loss_preference_name(
+                    locale,
+                    self.focus_loss_preference,
+                ))
+                .show_ui(ui, |ui| {
+                    let values = [
+                        FocusLossPreference::KeepRunning,
+                        FocusLossPreference::Throttle,
+                        FocusLossPreference::Pause,
+                    ];
+                    for value in values {
+                        ui.selectable_value(
+                            &mut self.focus_loss_preference,
+                            value,
+                            focus_loss_preference_name(locale, value),
+                        );
+                    }
+                });
+            if self.focus_loss_preference != previous {
+                self.focus_loss_preference_changed = true;
+            }
+        }
+        ui.end_row();
+
+        if self.focus_loss_preference == FocusLossPreference::Throttle {
+            ui.label(text(locale, "background-frame-rate"));
+            let previous = self.background_frame_rate;
+            DragValue::new(&mut self.background_frame_rate)
+                .range(1.0..=60.0)
+                .speed(0.5)
+                .suffix(" fps")
+                .ui(ui);
+            if self.background_frame_rate != previous {
+                self.background_frame_rate_changed = true;
+            }
+            ui.end_row();
+        }
+    }
+
     fn show_audio_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
         ui.label(text(locale, "audio-output-device"));
 
@@ -421,6 +692,38 @@ impl PreferencesDialog {
             self.log_filename_pattern_changed = true;
         }
         ui.end_row();
+
+        ui.label(text(locale, "crash-reports"))
+            .on_hover_text(text(locale, "crash-reports-tooltip"));
+
+        let previous = self.crash_reports;
+        ui.add(Checkbox::without_text(&mut self.crash_reports));
+        if self.crash_reports != previous {
+            self.crash_reports_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "movie-trace-files"))
+            .on_hover_text(text(locale, "movie-trace-files-tooltip"));
+
+        let previous = self.movie_trace_files;
+        ui.add(Checkbox::without_text(&mut self.movie_trace_files));
+        if self.movie_trace_files != previous {
+            self.movie_trace_files_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "mirror-movie-traces"));
+
+        let previous = self.mirror_movie_traces;
+        ui.add_enabled(
+            self.movie_trace_files,
+            Checkbox::without_text(&mut self.mirror_movie_traces),
+        );
+        if self.mirror_movie_traces != previous {
+            self.mirror_movie_traces_changed = true;
+        }
+        ui.end_row();
     }
 
     fn show_storage_preferences(
@@ -459,6 +762,119 @@ impl PreferencesDialog {
         ui.end_row();
     }
 
+    fn show_air_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "air-sandbox-directory"))
+            .on_hover_text(text(locale, "air-sandbox-directory-tooltip"));
+
+        ui.horizontal(|ui| {
+            match &self.air_sandbox_directory {
+                Some(directory) => ui.label(directory.to_string_lossy()),
+                None => ui.label(text(locale, "air-sandbox-directory-disabled")),
+            };
+
+            if ui.button(text(locale, "browse")).clicked() {
+                let mut dialog = FileDialog::new();
+                if let Some(directory) = &self.air_sandbox_directory {
+                    dialog = dialog.set_directory(directory);
+                }
+                if let Some(directory) = dialog.pick_folder() {
+                    self.air_sandbox_directory = Some(directory);
+                    self.air_sandbox_directory_changed = true;
+                }
+            }
+
+            if self.air_sandbox_directory.is_some()
+                && ui
+                    .button(text(locale, "air-sandbox-directory-disable"))
+                    .clicked()
+            {
+                self.air_sandbox_directory = None;
+                self.air_sandbox_directory_changed = true;
+            }
+        });
+
+        ui.end_row();
+    }
+
+    fn show_network_preferences(
+        &mut self,
+        locale: &LanguageIdentifier,
+        locked_text: &str,
+        ui: &mut Ui,
+    ) {
+        ui.label(text(locale, "network-timeout"))
+            .on_hover_text(text(locale, "network-timeout-tooltip"));
+        let previous = self.network_timeout;
+        DragValue::new(&mut self.network_timeout)
+            .range(0.0..=600.0)
+            .speed(1)
+            .suffix(" s")
+            .ui(ui);
+        if self.network_timeout != previous {
+            self.network_timeout_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "network-retries"))
+            .on_hover_text(text(locale, "network-retries-tooltip"));
+        let previous = self.network_retries;
+        DragValue::new(&mut self.network_retries)
+            .range(0..=10)
+            .ui(ui);
+        if self.network_retries != previous {
+            self.network_retries_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "offline"))
+            .on_hover_text(text(locale, "offline-tooltip"));
+        let previous = self.offline;
+        ui.add(Checkbox::without_text(&mut self.offline));
+        if self.offline != previous {
+            self.offline_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "relaxed-security"))
+            .on_hover_text(text(locale, "relaxed-security-tooltip"));
+        if self.relaxed_security_readonly {
+            ui.add_enabled(false, Checkbox::without_text(&mut self.relaxed_security))
+                .on_disabled_hover_text(locked_text);
+        } else {
+            let previous = self.relaxed_security;
+            ui.add(Checkbox::without_text(&mut self.relaxed_security));
+            if self.relaxed_security != previous {
+                self.relaxed_security_changed = true;
+            }
+        }
+        ui.end_row();
+    }
+
+    fn show_input_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "mouse-wheel-scale"));
+
+        let previous = self.mouse_wheel_scale;
+        DragValue::new(&mut self.mouse_wheel_scale)
+            .range(0.1..=10.0)
+            .speed(0.1)
+            .suffix("x")
+            .ui(ui);
+        if self.mouse_wheel_scale != previous {
+            self.mouse_wheel_scale_changed = true;
+        }
+
+        ui.end_row();
+
+        ui.label(text(locale, "coalesce-mouse-moves"))
+            .on_hover_text(text(locale, "coalesce-mouse-moves-tooltip"));
+        let previous = self.coalesce_mouse_moves;
+        ui.add(Checkbox::without_text(&mut self.coalesce_mouse_moves));
+        if self.coalesce_mouse_moves != previous {
+            self.coalesce_mouse_moves_changed = true;
+        }
+        ui.end_row();
+    }
+
     fn show_misc_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
         ui.label(text(locale, "recent-limit"));
 
@@ -482,6 +898,63 @@ impl PreferencesDialog {
         ui.end_row()
     }
 
+    fn show_shortcut_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        CollapsingHeader::new(text(locale, "keyboard-shortcuts")).show(ui, |ui| {
+            ui.label(text(locale, "keyboard-shortcuts-help"));
+            ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                Grid::new("preferences-dialog-shortcuts")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (action, shortcut) in &mut self.shortcuts {
+                            ui.label(text(locale, action.text_id(None)));
+                            let is_invalid =
+                                !shortcut.is_empty() && parse_shortcut(shortcut).is_none();
+                            let mut edit = TextEdit::singleline(shortcut).desired_width(150.0);
+                            if is_invalid {
+                                edit = edit.text_color(ui.style().visuals.error_fg_color);
+                            }
+                            if edit.ui(ui).changed() {
+                                self.shortcuts_changed = true;
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+            for (action, other) in Shortcuts::new(&self.custom_shortcuts()).conflicts() {
+                let args = HashMap::from([
+                    (
+                        "first",
+                        FluentValue::from(text(locale, action.text_id(None))),
+                    ),
+                    (
+                        "second",
+                        FluentValue::from(text(locale, other.text_id(None))),
+                    ),
+                ]);
+                ui.colored_label(
+                    ui.style().visuals.warn_fg_color,
+                    text_with_args(locale, "keyboard-shortcuts-conflict", &args),
+                );
+            }
+        });
+    }
+
+    /// The valid shortcuts typed by the user, where an empty one means no shortcut.
+    fn custom_shortcuts(&self) -> BTreeMap<Action, Option<KeyboardShortcut>> {
+        self.shortcuts
+            .iter()
+            .filter_map(|(action, shortcut)| {
+                if shortcut.is_empty() {
+                    Some((*action, None))
+                } else {
+                    parse_shortcut(shortcut).map(|shortcut| (*action, Some(shortcut)))
+                }
+            })
+            .collect()
+    }
+
     fn save(&mut self) {
         if let Err(e) = self.preferences.write_preferences(|preferences| {
             if self.graphics_backend_changed {
@@ -490,6 +963,22 @@ impl PreferencesDialog {
             if self.power_preference_changed {
                 preferences.set_graphics_power_preference(self.power_preference);
             }
+            if self.gpu_memory_budget_changed {
+                preferences.set_gpu_memory_budget(
+                    Some(self.gpu_memory_budget).filter(|budget| *budget > 0),
+                );
+            }
+            if self.blend_space_changed {
+                preferences.set_blend_space(self.blend_space);
+            }
+            if self.morph_shape_cache_size_changed {
+                preferences.set_morph_shape_cache_size(
+                    Some(self.morph_shape_cache_size).filter(|size| *size > 0),
+                );
+            }
+            if self.vsync_changed {
+                preferences.set_vsync(self.vsync);
+            }
             if self.language_changed {
                 preferences.set_language(self.language.clone());
             }
@@ -503,18 +992,65 @@ impl PreferencesDialog {
             if self.log_filename_pattern_changed {
                 preferences.set_log_filename_pattern(self.log_filename_pattern);
             }
+            if self.crash_reports_changed {
+                preferences.set_crash_reports(self.crash_reports);
+            }
+            if self.movie_trace_files_changed {
+                preferences.set_movie_trace_files(self.movie_trace_files);
+            }
+            if self.mirror_movie_traces_changed {
+                preferences.set_mirror_movie_traces(self.mirror_movie_traces);
+            }
             if self.storage_backend_changed {
                 preferences.set_storage_backend(self.storage_backend);
             }
+            if self.air_sandbox_directory_changed {
+                preferences.set_air_sandbox_directory(self.air_sandbox_directory.clone());
+            }
+            if self.network_timeout_changed {
+                preferences.set_network_timeout(
+                    Some(self.network_timeout).filter(|timeout| *timeout > 0.0),
+                );
+            }
+            if self.network_retries_changed {
+                preferences.set_network_retries(self.network_retries);
+            }
+            if self.offline_changed {
+                preferences.set_offline(self.offline);
+            }
+            if self.relaxed_security_changed {
+                preferences.set_relaxed_security(self.relaxed_security);
+            }
             if self.recent_limit_changed {
                 preferences.set_recent_limit(self.recent_limit);
             }
+            if self.mouse_wheel_scale_changed {
+                preferences.set_mouse_wheel_scale(self.mouse_wheel_scale);
+            }
+            if self.coalesce_mouse_moves_changed {
+                preferences.set_coalesce_mouse_moves(self.coalesce_mouse_moves);
+            }
             if self.theme_preference_changed {
                 preferences.set_theme_preference(self.theme_preference);
             }
             if self.gamemode_preference_changed {
                 preferences.set_gamemode_preference(self.gamemode_preference);
             }
+            if self.focus_loss_preference_changed {
+                preferences.set_focus_loss_preference(self.focus_loss_preference);
+            }
+            if self.background_frame_rate_changed {
+                preferences.set_background_frame_rate(self.background_frame_rate);
+            }
+            if self.shortcuts_changed {
+                for (action, shortcut) in self.custom_shortcuts() {
+                    if shortcut == action.default_shortcut() {
+                        preferences.reset_shortcut(action);
+                    } else {
+                        preferences.set_shortcut(action, shortcut);
+                    }
+                }
+            }
         }) {
             // [NA] TODO: Better error handling... everywhere in desktop, really
             tracing::error!("Could not save preferences: {e}");
@@ -529,6 +1065,7 @@ fn graphics_backend_name(locale: &LanguageIdentifier, backend: GraphicsBackend)
         GraphicsBackend::Metal => Cow::Borrowed("Metal"),
         GraphicsBackend::Dx12 => Cow::Borrowed("DirectX 12"),
         GraphicsBackend::Gl => Cow::Borrowed("OpenGL"),
+        GraphicsBackend::Software => text(locale, "graphics-backend-software"),
     }
 }
 
@@ -539,6 +1076,13 @@ fn graphics_power_name(locale: &LanguageIdentifier, power_preference: PowerPrefe
     }
 }
 
+fn blend_space_name(locale: &LanguageIdentifier, blend_space: BlendSpace) -> Cow<str> {
+    match blend_space {
+        BlendSpace::Flash => text(locale, "blend-space-flash"),
+        BlendSpace::Linear => text(locale, "blend-space-linear"),
+    }
+}
+
 fn language_name(language: &LanguageIdentifier) -> String {
     optional_text(language, "language-name")
         .map(|s| s.to_string())
@@ -556,6 +1100,17 @@ fn theme_preference_name(
     }
 }
 
+fn focus_loss_preference_name(
+    locale: &LanguageIdentifier,
+    focus_loss_preference: FocusLossPreference,
+) -> Cow<str> {
+    match focus_loss_preference {
+        FocusLossPreference::KeepRunning => text(locale, "focus-loss-keep-running"),
+        FocusLossPreference::Throttle => text(locale, "focus-loss-throttle"),
+        FocusLossPreference::Pause => text(locale, "focus-loss-pause"),
+    }
+}
+
 fn gamemode_preference_name(
     locale: &LanguageIdentifier,
     gamemode_preference: GameModePreference,
@@ -0,0 +1,220 @@
+use crate::gui::{text, text_with_args};
+use crate::preferences::sites::{DevicePermission, SitePreferences, StorageLimit};
+use crate::preferences::GlobalPreferences;
+use egui::{Align2, Button, Checkbox, Grid, Ui, Widget, Window};
+use fluent_templates::fluent_bundle::FluentValue;
+use ruffle_core::backend::ui::SettingsPanel;
+use std::collections::HashMap;
+use unic_langid::LanguageIdentifier;
+
+/// The tabs of the dialog, which mirror the tabs of Flash Player's own Settings panel.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SettingsTab {
+    Privacy,
+    LocalStorage,
+    Display,
+}
+
+impl SettingsTab {
+    fn for_panel(panel: SettingsPanel) -> Option<Self> {
+        match panel {
+            SettingsPanel::Default => None,
+            SettingsPanel::Privacy | SettingsPanel::Microphone | SettingsPanel::Camera => {
+                Some(SettingsTab::Privacy)
+            }
+            SettingsPanel::LocalStorage => Some(SettingsTab::LocalStorage),
+            SettingsPanel::Display => Some(SettingsTab::Display),
+        }
+    }
+
+    fn panel(&self) -> SettingsPanel {
+        match self {
+            SettingsTab::Privacy => SettingsPanel::Privacy,
+            SettingsTab::LocalStorage => SettingsPanel::LocalStorage,
+            SettingsTab::Display => SettingsPanel::Display,
+        }
+    }
+}
+
+/// Emulates the Flash Player Settings panel, which lets the user
+/// decide what the site of the current movie is allowed to do.
+pub struct SettingsDialog {
+    preferences: GlobalPreferences,
+    site: String,
+    tab: SettingsTab,
+    site_preferences: SitePreferences,
+    hardware_acceleration: bool,
+}
+
+impl SettingsDialog {
+    /// Opens the dialog on the given panel, or on `last_panel` if the movie didn't ask for one.
+    pub fn new(
+        preferences: GlobalPreferences,
+        site: String,
+        panel: SettingsPanel,
+        last_panel: SettingsPanel,
+    ) -> Self {
+        let tab = SettingsTab::for_panel(panel)
+            .or_else(|| SettingsTab::for_panel(last_panel))
+            .unwrap_or(SettingsTab::Privacy);
+        Self {
+            site_preferences: preferences.site_preferences(&site),
+            hardware_acceleration: preferences.hardware_acceleration(),
+            preferences,
+            site,
+            tab,
+        }
+    }
+
+    /// The panel that is currently being shown, to reopen it next time.
+    pub fn panel(&self) -> SettingsPanel {
+        self.tab.panel()
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+        let mut should_close = false;
+
+        Window::new(text(locale, "settings-panel-title"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(
+                        &mut self.tab,
+                        SettingsTab::Privacy,
+                        text(locale, "settings-panel-privacy"),
+                    );
+                    ui.selectable_value(
+                        &mut self.tab,
+                        SettingsTab::LocalStorage,
+                        text(locale, "settings-panel-local-storage"),
+                    );
+                    ui.selectable_value(
+                        &mut self.tab,
+                        SettingsTab::Display,
+                        text(locale, "settings-panel-display"),
+                    );
+                });
+                ui.separator();
+
+                match self.tab {
+                    SettingsTab::Privacy => self.show_privacy_settings(locale, ui),
+                    SettingsTab::LocalStorage => self.show_local_storage_settings(locale, ui),
+                    SettingsTab::Display => self.show_display_settings(locale, ui),
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if Button::new(text(locale, "save")).ui(ui).clicked() {
+                            self.save();
+                            should_close = true;
+                        }
+                        if Button::new(text(locale, "cancel")).ui(ui).clicked() {
+                            should_close = true;
+                        }
+                    })
+                });
+            });
+
+        keep_open && !should_close
+    }
+
+    fn site_label(&self, locale: &LanguageIdentifier, id: &'static str, ui: &mut Ui) {
+        ui.label(text_with_args(
+            locale,
+            id,
+            &HashMap::from([("site", FluentValue::from(self.site.as_str()))]),
+        ));
+    }
+
+    fn show_privacy_settings(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        self.site_label(locale, "settings-panel-privacy-message", ui);
+        Grid::new("settings-panel-privacy")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(text(locale, "settings-panel-camera"));
+                device_permission_choice(locale, &mut self.site_preferences.camera, ui);
+                ui.end_row();
+
+                ui.label(text(locale, "settings-panel-microphone"));
+                device_permission_choice(locale, &mut self.site_preferences.microphone, ui);
+                ui.end_row();
+            });
+    }
+
+    fn show_local_storage_settings(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        self.site_label(locale, "settings-panel-local-storage-message", ui);
+        ui.horizontal(|ui| {
+            for limit in StorageLimit::ALL {
+                ui.radio_value(
+                    &mut self.site_preferences.storage_limit,
+                    limit,
+                    storage_limit_name(locale, limit),
+                );
+            }
+        });
+    }
+
+    fn show_display_settings(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        Checkbox::new(
+            &mut self.hardware_acceleration,
+            text(locale, "settings-panel-hardware-acceleration"),
+        )
+        .ui(ui);
+        if self.hardware_acceleration != self.preferences.hardware_acceleration() {
+            ui.colored_label(
+                ui.style().visuals.warn_fg_color,
+                text(locale, "settings-panel-hardware-acceleration-reload"),
+            );
+        }
+    }
+
+    fn save(&mut self) {
+        if let Err(e) = self.preferences.write_preferences(|writer| {
+            writer.set_site_preferences(&self.site, self.site_preferences);
+            writer.set_hardware_acceleration(self.hardware_acceleration);
+        }) {
+            tracing::error!("Could not save settings: {e}");
+        }
+    }
+}
+
+fn device_permission_choice(
+    locale: &LanguageIdentifier,
+    permission: &mut DevicePermission,
+    ui: &mut Ui,
+) {
+    ui.horizontal(|ui| {
+        ui.radio_value(
+            permission,
+            DevicePermission::Ask,
+            text(locale, "settings-panel-ask"),
+        );
+        ui.radio_value(
+            permission,
+            DevicePermission::Allow,
+            text(locale, "settings-panel-allow"),
+        );
+        ui.radio_value(
+            permission,
+            DevicePermission::Deny,
+            text(locale, "settings-panel-deny"),
+        );
+    });
+}
+
+fn storage_limit_name(locale: &LanguageIdentifier, limit: StorageLimit) -> String {
+    match limit {
+        StorageLimit::None => text(locale, "settings-panel-storage-none").into_owned(),
+        StorageLimit::Kilobytes10 => "10 KB".to_string(),
+        StorageLimit::Kilobytes100 => "100 KB".to_string(),
+        StorageLimit::Megabytes1 => "1 MB".to_string(),
+        StorageLimit::Megabytes10 => "10 MB".to_string(),
+        StorageLimit::Unlimited => text(locale, "settings-panel-storage-unlimited").into_owned(),
+    }
+}
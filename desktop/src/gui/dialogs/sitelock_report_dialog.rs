@@ -0,0 +1,136 @@
+use crate::custom_event::RuffleEvent;
+use crate::gui::text;
+use crate::player::LaunchOptions;
+use egui::{Align2, Grid, Window};
+use ruffle_core::sitelock::SitelockCheck;
+use ruffle_core::Player;
+use unic_langid::LanguageIdentifier;
+use url::Url;
+use winit::event_loop::EventLoopProxy;
+
+/// Shows what the current movie did to find out where it is running,
+/// and lets the user reload it pretending to be on one of the sites it expects.
+pub struct SitelockReportDialog {
+    event_loop: EventLoopProxy<RuffleEvent>,
+
+    /// The movie the report is about, with the options it was opened with.
+    movie: Option<(Url, LaunchOptions)>,
+}
+
+impl SitelockReportDialog {
+    pub fn new(
+        event_loop: EventLoopProxy<RuffleEvent>,
+        movie: Option<(Url, LaunchOptions)>,
+    ) -> Self {
+        Self { event_loop, movie }
+    }
+
+    pub fn show(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        player: Option<&mut Player>,
+    ) -> bool {
+        let mut keep_open = true;
+        let mut should_close = false;
+        let report = player.map(|player| player.sitelock_report());
+
+        Window::new(text(locale, "sitelock-report"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                let Some(report) = report else {
+                    ui.label(text(locale, "sitelock-report-no-movie"));
+                    return;
+                };
+
+                if !report.enabled {
+                    ui.label(text(locale, "sitelock-report-disabled"));
+                    if ui.button(text(locale, "sitelock-report-reload")).clicked() {
+                        should_close = self.reload(None);
+                    }
+                    return;
+                }
+
+                if report.checks.is_empty() {
+                    ui.label(text(locale, "sitelock-report-no-checks"));
+                } else {
+                    ui.label(text(locale, "sitelock-report-checks"));
+                    for check in &report.checks {
+                        ui.label(format!("• {}", check_name(*check)));
+                    }
+                }
+
+                ui.separator();
+                if report.expected_urls.is_empty() {
+                    ui.label(text(locale, "sitelock-report-no-expected-urls"));
+                    return;
+                }
+                ui.label(text(locale, "sitelock-report-expected-urls"));
+                Grid::new("sitelock-report-expected-urls")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for expected in &report.expected_urls {
+                            ui.label(expected);
+                            let spoof_url = self.spoof_url_for(expected);
+                            if ui
+                                .add_enabled(
+                                    spoof_url.is_some(),
+                                    egui::Button::new(text(locale, "sitelock-report-spoof")),
+                                )
+                                .clicked()
+                            {
+                                should_close = self.reload(spoof_url);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        keep_open && !should_close
+    }
+
+    /// The URL to spoof for the movie to believe it's on the given site.
+    ///
+    /// Bare domains get the file name of the movie appended, as movies often check both.
+    fn spoof_url_for(&self, expected: &str) -> Option<Url> {
+        if let Ok(url) = Url::parse(expected) {
+            return matches!(url.scheme(), "http" | "https").then_some(url);
+        }
+        let (movie_url, _) = self.movie.as_ref()?;
+        let file_name = movie_url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or_default();
+        let domain = expected.trim_start_matches('.');
+        Url::parse(&format!("https://{domain}/{file_name}")).ok()
+    }
+
+    /// Reopens the movie with sitelock detection enabled, optionally spoofing its URL.
+    fn reload(&self, spoof_url: Option<Url>) -> bool {
+        let Some((movie_url, options)) = &self.movie else {
+            return false;
+        };
+        let mut options = options.clone();
+        options.detect_sitelocks = true;
+        if spoof_url.is_some() {
+            options.player.spoof_url = spoof_url;
+        }
+        let _ = self.event_loop.send_event(RuffleEvent::CloseFile);
+        self.event_loop
+            .send_event(RuffleEvent::Open(movie_url.clone(), Box::new(options)))
+            .is_ok()
+    }
+}
+
+fn check_name(check: SitelockCheck) -> &'static str {
+    match check {
+        SitelockCheck::Avm1Url => "_url",
+        SitelockCheck::LoaderInfoUrl => "LoaderInfo.url",
+        SitelockCheck::SandboxType => "Security.sandboxType",
+        SitelockCheck::LocalConnectionDomain => "LocalConnection.domain",
+    }
+}
@@ -0,0 +1,137 @@
+use crate::gui::{text, text_with_args};
+use crate::preferences::sites::StorageLimit;
+use crate::preferences::GlobalPreferences;
+use egui::{Align2, Ui, Window};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
+use tokio::sync::oneshot::Sender;
+use unic_langid::LanguageIdentifier;
+
+pub struct StorageQuotaDialogConfiguration {
+    notifier: Option<Sender<bool>>,
+    site: String,
+    required: usize,
+}
+
+impl StorageQuotaDialogConfiguration {
+    pub fn new(notifier: Sender<bool>, site: impl Into<String>, required: usize) -> Self {
+        Self {
+            notifier: Some(notifier),
+            site: site.into(),
+            required,
+        }
+    }
+}
+
+/// Asks the user whether a site may store more than its storage quota allows.
+pub struct StorageQuotaDialog {
+    config: StorageQuotaDialogConfiguration,
+    preferences: GlobalPreferences,
+}
+
+impl Drop for StorageQuotaDialog {
+    fn drop(&mut self) {
+        self.respond(false);
+    }
+}
+
+impl StorageQuotaDialog {
+    pub fn new(config: StorageQuotaDialogConfiguration, preferences: GlobalPreferences) -> Self {
+        Self {
+            config,
+            preferences,
+        }
+    }
+
+    fn respond(&mut self, allowed: bool) {
+        if let Some(notifier) = std::mem::take(&mut self.config.notifier) {
+            let _ = notifier.send(allowed);
+        }
+    }
+
+    /// Raises the storage limit of the site to the smallest one that fits the request,
+    /// like dragging the slider in Flash Player's Local Storage panel.
+    fn allow(&mut self) {
+        let mut site_preferences = self.preferences.site_preferences(&self.config.site);
+        site_preferences.storage_limit = StorageLimit::ALL
+            .into_iter()
+            .find(|limit| {
+                limit
+                    .bytes()
+                    .map_or(true, |bytes| bytes >= self.config.required as u64)
+            })
+            .unwrap_or(StorageLimit::Unlimited);
+        let result = self.preferences.write_preferences(|writer| {
+            writer.set_site_preferences(&self.config.site, site_preferences)
+        });
+        if let Err(e) = result {
+            tracing::error!("Could not save storage limit: {e}");
+        }
+        self.respond(true);
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+        let mut should_close = false;
+
+        Window::new(text(locale, "storage-quota-dialog-title"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                should_close = self.render_window_contents(locale, ui);
+            });
+
+        keep_open && !should_close
+    }
+
+    fn render_window_contents(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) -> bool {
+        let mut should_close = false;
+
+        ui.label(text_with_args(
+            locale,
+            "storage-quota-dialog-message",
+            &HashMap::from([
+                ("site", FluentValue::from(self.config.site.as_str())),
+                (
+                    "size",
+                    FluentValue::from(format_size(self.config.required as u64)),
+                ),
+            ]),
+        ));
+        ui.label("");
+
+        ui.horizontal(|ui| {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .button(text(locale, "storage-quota-dialog-allow"))
+                    .clicked()
+                {
+                    self.allow();
+                    should_close = true;
+                }
+                if ui
+                    .button(text(locale, "storage-quota-dialog-deny"))
+                    .clicked()
+                {
+                    self.respond(false);
+                    should_close = true;
+                }
+            })
+        });
+
+        should_close
+    }
+}
+
+/// Formats a number of bytes the way Flash Player's storage dialogs do.
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
@@ -1,6 +1,7 @@
 use crate::gui::text;
 use crate::preferences::GlobalPreferences;
 use egui::{Align2, Slider};
+use ruffle_core::backend::audio::AudioEffects;
 use ruffle_core::Player;
 use unic_langid::LanguageIdentifier;
 
@@ -8,6 +9,7 @@ use unic_langid::LanguageIdentifier;
 pub struct VolumeControls {
     is_muted: bool,
     volume: f32,
+    audio_enhancements: bool,
 }
 
 impl VolumeControls {
@@ -15,6 +17,7 @@ impl VolumeControls {
         Self {
             is_muted: preferences.mute(),
             volume: preferences.preferred_volume() * 100.0,
+            audio_enhancements: preferences.audio_enhancements(),
         }
     }
 
@@ -22,7 +25,7 @@ impl VolumeControls {
         &mut self,
         locale: &LanguageIdentifier,
         egui_ctx: &egui::Context,
-        player: Option<&mut Player>,
+        mut player: Option<&mut Player>,
         preferences: &GlobalPreferences,
     ) -> bool {
         let mut keep_open = true;
@@ -47,29 +50,73 @@ impl VolumeControls {
                     });
                 });
 
-                if changed_checkbox || changed_slider {
-                    if let Some(player) = player {
-                        player.set_volume(self.get_volume());
+                ui.separator();
+                if ui
+                    .checkbox(
+                        &mut self.audio_enhancements,
+                        text(locale, "volume-controls-audio-enhancements"),
+                    )
+                    .on_hover_text(text(locale, "volume-controls-audio-enhancements-tooltip"))
+                    .changed()
+                {
+                    if let Some(player) = player.as_deref_mut() {
+                        player.set_audio_effects(self.get_audio_effects());
                     }
-                    // Don't update persisted volume if the CLI set it
-                    if preferences.cli.volume.is_none() {
-                        if let Err(e) = preferences.write_preferences(|writer| {
-                            if changed_checkbox {
-                                writer.set_mute(self.is_muted);
-                            }
-                            if changed_slider {
-                                writer.set_volume(self.volume / 100.0);
-                            }
-                        }) {
-                            tracing::warn!("Couldn't update volume preferences: {e}");
-                        }
+                    if let Err(e) = preferences.write_preferences(|writer| {
+                        writer.set_audio_enhancements(self.audio_enhancements);
+                    }) {
+                        tracing::warn!("Couldn't update audio enhancements preference: {e}");
                     }
                 }
+
+                if changed_checkbox || changed_slider {
+                    self.apply_volume(player, preferences, changed_checkbox, changed_slider);
+                }
             });
 
         keep_open
     }
 
+    /// Mutes or unmutes the audio, e.g. with a keyboard shortcut.
+    pub fn toggle_mute(&mut self, player: Option<&mut Player>, preferences: &GlobalPreferences) {
+        self.is_muted = !self.is_muted;
+        self.apply_volume(player, preferences, true, false);
+    }
+
+    fn apply_volume(
+        &self,
+        player: Option<&mut Player>,
+        preferences: &GlobalPreferences,
+        changed_mute: bool,
+        changed_volume: bool,
+    ) {
+        if let Some(player) = player {
+            player.set_volume(self.get_volume());
+        }
+        // Don't update persisted volume if the CLI set it
+        if preferences.cli.volume.is_none() {
+            if let Err(e) = preferences.write_preferences(|writer| {
+                if changed_mute {
+                    writer.set_mute(self.is_muted);
+                }
+                if changed_volume {
+                    writer.set_volume(self.volume / 100.0);
+                }
+            }) {
+                tracing::warn!("Couldn't update volume preferences: {e}");
+            }
+        }
+    }
+
+    /// Returns the effects to apply to the audio output.
+    pub fn get_audio_effects(&self) -> AudioEffects {
+        if self.audio_enhancements {
+            AudioEffects::ENHANCED
+        } else {
+            AudioEffects::NONE
+        }
+    }
+
     /// Returns the volume between 0 and 1 (calculated out of the
     /// checkbox and the slider).
     pub fn get_volume(&self) -> f32 {
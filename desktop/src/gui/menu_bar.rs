@@ -1,13 +1,24 @@
 use crate::custom_event::RuffleEvent;
+use crate::gui::actions::{Action, Shortcuts};
+use crate::gui::command_palette::{CommandPalette, PaletteCommand, PaletteOutcome, PaletteTarget};
 use crate::gui::dialogs::Dialogs;
-use crate::gui::{text, DebugMessage};
+use crate::gui::{text, text_with_args, DebugMessage};
 use crate::player::LaunchOptions;
 use crate::preferences::GlobalPreferences;
 use egui::{menu, Button, Key, KeyboardShortcut, Modifiers, Widget};
+use fluent_templates::fluent_bundle::FluentValue;
+use rfd::FileDialog;
 use ruffle_core::config::Letterbox;
 use ruffle_core::{Player, StageScaleMode};
+use ruffle_frontend_utils::backends::storage::{
+    export_shared_objects_archive, import_shared_objects_archive,
+};
 use ruffle_frontend_utils::recents::Recent;
 use ruffle_render::quality::StageQuality;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
 use unic_langid::LanguageIdentifier;
 use url::Url;
 use winit::event_loop::EventLoopProxy;
@@ -19,28 +30,39 @@ pub struct MenuBar {
 
     cached_recents: Option<Vec<Recent>>,
     pub currently_opened: Option<(Url, LaunchOptions)>,
+    pub show_frame_pacing_stats: bool,
+    command_palette: Option<CommandPalette>,
+    shortcuts: Shortcuts,
 }
 
 impl MenuBar {
-    const SHORTCUT_FULLSCREEN: KeyboardShortcut = KeyboardShortcut::new(Modifiers::NONE, Key::F11);
+    /// How many times faster than real time movies run while fast-forwarded.
+    const FAST_FORWARD_SPEED: f64 = 4.0;
+
     const SHORTCUT_FULLSCREEN_WINDOWS: KeyboardShortcut =
         KeyboardShortcut::new(Modifiers::ALT, Key::Enter);
-    const SHORTCUT_OPEN: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::O);
-    const SHORTCUT_OPEN_ADVANCED: KeyboardShortcut =
-        KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::O);
-    const SHORTCUT_PAUSE: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::P);
-    const SHORTCUT_QUIT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Q);
 
     pub fn new(
         event_loop: EventLoopProxy<RuffleEvent>,
         default_launch_options: LaunchOptions,
         preferences: GlobalPreferences,
     ) -> Self {
+        let shortcuts = preferences.shortcuts();
+        for (action, other) in shortcuts.conflicts() {
+            tracing::warn!(
+                "The shortcut of {} is also used by {}, which can't be used with it",
+                action.id(),
+                other.id()
+            );
+        }
         Self {
             event_loop,
             default_launch_options,
             cached_recents: None,
             currently_opened: None,
+            show_frame_pacing_stats: false,
+            command_palette: None,
+            shortcuts,
             preferences,
         }
     }
@@ -51,36 +73,179 @@ impl MenuBar {
         dialogs: &mut Dialogs,
         mut player: Option<&mut Player>,
     ) {
-        // TODO(mike): Make some MenuItem struct with shortcut info to handle this more cleanly.
-        if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_OPEN_ADVANCED)) {
-            dialogs.open_file_advanced();
+        // Pick up shortcuts changed in the preferences.
+        self.shortcuts = self.preferences.shortcuts();
+        for action in self.shortcuts.consume_pressed(egui_ctx) {
+            self.run_action(action, dialogs, player.as_deref_mut());
         }
-        if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_OPEN)) {
-            self.open_file();
+
+        // The usual shortcut on Windows, unless the user chose another one.
+        let fullscreen = self.shortcuts.get(Action::Fullscreen);
+        if cfg!(windows)
+            && fullscreen == Action::Fullscreen.default_shortcut()
+            && egui_ctx
+                .input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_FULLSCREEN_WINDOWS))
+        {
+            self.run_action(Action::Fullscreen, dialogs, player);
         }
-        if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_QUIT)) {
-            self.request_exit();
+    }
+
+    /// Runs an action picked from the menus, the command palette, or with its shortcut.
+    pub fn run_action(
+        &mut self,
+        action: Action,
+        dialogs: &mut Dialogs,
+        player: Option<&mut Player>,
+    ) {
+        if action.needs_player() && player.is_none() {
+            return;
         }
-        if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_PAUSE)) {
-            if let Some(player) = &mut player {
-                player.set_is_playing(!player.is_playing());
+        match action {
+            Action::OpenQuick => self.open_file(),
+            Action::OpenAdvanced => dialogs.open_file_advanced(),
+            Action::OpenUrl => dialogs.open_location(),
+            Action::Reload => self.reload_movie(),
+            Action::Close => self.close_movie(),
+            Action::Preferences => dialogs.open_preferences(),
+            Action::LocalStorage => dialogs.open_local_storage(),
+            Action::ExportSaves => export_saves(self.preferences.cli.save_directory.clone()),
+            Action::ImportSaves => import_saves(self.preferences.cli.save_directory.clone()),
+            Action::Exit => self.request_exit(),
+            Action::TogglePlaying => {
+                if let Some(player) = player {
+                    player.set_is_playing(!player.is_playing());
+                }
             }
+            Action::ToggleFastForward => {
+                if let Some(player) = player {
+                    let speed = if player.speed_multiplier() > 1.0 {
+                        1.0
+                    } else {
+                        Self::FAST_FORWARD_SPEED
+                    };
+                    player.set_speed_multiplier(speed);
+                }
+            }
+            Action::VolumeControls => dialogs.open_volume_controls(),
+            Action::ToggleMute => dialogs
+                .volume_controls
+                .toggle_mute(player, &self.preferences),
+            Action::Fullscreen => {
+                if let Some(player) = player {
+                    let is_fullscreen = player.is_fullscreen();
+                    player.set_fullscreen(!is_fullscreen);
+                }
+            }
+            Action::Screenshot => {
+                if let Some(player) = player {
+                    save_screenshot(player);
+                }
+            }
+            Action::AddBookmark => {
+                let initial_url = self.currently_opened.as_ref().map(|(url, _)| url.clone());
+                dialogs.open_add_bookmark(initial_url);
+            }
+            Action::ManageBookmarks => dialogs.open_bookmarks(),
+            Action::DebugStage => queue_debug_message(player, DebugMessage::TrackStage),
+            Action::DebugMovie => queue_debug_message(player, DebugMessage::TrackTopLevelMovie),
+            Action::DebugMovieList => queue_debug_message(player, DebugMessage::ShowKnownMovies),
+            Action::DebugDomainList => queue_debug_message(player, DebugMessage::ShowDomains),
+            Action::DebugSearchDisplayObjects => {
+                queue_debug_message(player, DebugMessage::SearchForDisplayObject)
+            }
+            Action::SitelockReport => dialogs.open_sitelock_report(self.currently_opened.clone()),
+            Action::DumpHeap => {
+                if let Some(player) = player {
+                    save_heap_dump(player);
+                }
+            }
+            Action::DumpOpcodeCounts => {
+                if let Some(player) = player {
+                    save_opcode_report(player);
+                }
+            }
+            Action::FramePacingStats => {
+                self.show_frame_pacing_stats = !self.show_frame_pacing_stats
+            }
+            Action::About => dialogs.open_about_screen(),
+            Action::CommandPalette => self.command_palette = Some(CommandPalette::new()),
         }
-        let mut fullscreen_pressed =
-            egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_FULLSCREEN));
-        if cfg!(windows) && !fullscreen_pressed {
-            // TODO We can remove this shortcut when we add some kind of preferences.
-            fullscreen_pressed = egui_ctx
-                .input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_FULLSCREEN_WINDOWS));
-        }
-        if fullscreen_pressed {
-            if let Some(player) = &mut player {
-                let is_fullscreen = player.is_fullscreen();
-                player.set_fullscreen(!is_fullscreen);
+    }
+
+    /// Shows the command palette if it's open, and runs the command picked from it.
+    pub fn show_command_palette(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        dialogs: &mut Dialogs,
+        player: Option<&mut Player>,
+    ) {
+        let Some(palette) = &mut self.command_palette else {
+            return;
+        };
+
+        let is_playing = player.as_ref().is_some_and(|player| player.is_playing());
+        let mut commands: Vec<_> = Action::ALL
+            .into_iter()
+            .filter(|action| *action != Action::CommandPalette)
+            .filter(|action| !action.needs_player() || player.is_some())
+            .map(|action| PaletteCommand {
+                label: text(locale, action.text_id(Some(is_playing))).into_owned(),
+                shortcut: self
+                    .shortcuts
+                    .get(action)
+                    .map(|shortcut| egui_ctx.format_shortcut(&shortcut)),
+                target: PaletteTarget::Action(action),
+            })
+            .collect();
+        self.preferences.bookmarks(|bookmarks| {
+            for bookmark in bookmarks.iter().filter(|x| !x.is_invalid()) {
+                let args = HashMap::from([("name", FluentValue::from(bookmark.name.as_str()))]);
+                commands.push(PaletteCommand {
+                    label: text_with_args(locale, "command-palette-open-bookmark", &args)
+                        .into_owned(),
+                    shortcut: None,
+                    target: PaletteTarget::OpenBookmark(bookmark.url.clone()),
+                });
+            }
+        });
+
+        match palette.show(locale, egui_ctx, commands) {
+            PaletteOutcome::Open => {}
+            PaletteOutcome::Closed => self.command_palette = None,
+            PaletteOutcome::Picked(target) => {
+                self.command_palette = None;
+                match target {
+                    PaletteTarget::Action(action) => self.run_action(action, dialogs, player),
+                    PaletteTarget::OpenBookmark(url) => self.open_url(url),
+                }
             }
         }
     }
 
+    /// A menu item running an action, showing its shortcut.
+    fn action_button(
+        &mut self,
+        locale: &LanguageIdentifier,
+        ui: &mut egui::Ui,
+        action: Action,
+        dialogs: &mut Dialogs,
+        player: &mut Option<&mut Player>,
+    ) {
+        let is_playing = player.as_ref().is_some_and(|player| player.is_playing());
+        let mut button = Button::new(text(locale, action.text_id(Some(is_playing))));
+        if let Some(shortcut) = self.shortcuts.get(action) {
+            button = button.shortcut_text(ui.ctx().format_shortcut(&shortcut));
+        }
+        if ui
+            .add_enabled(!action.needs_player() || player.is_some(), button)
+            .clicked()
+        {
+            ui.close_menu();
+            self.run_action(action, dialogs, player.as_deref_mut());
+        }
+    }
+
     pub fn show(
         &mut self,
         locale: &LanguageIdentifier,
@@ -90,83 +255,60 @@ impl MenuBar {
     ) {
         egui::TopBottomPanel::top("menu_bar").show(egui_ctx, |ui| {
             menu::bar(ui, |ui| {
-                self.file_menu(locale, ui, dialogs, player.is_some());
-                self.view_menu(locale, ui, &mut player);
+                self.file_menu(locale, ui, dialogs, &mut player);
+                self.view_menu(locale, ui, dialogs, &mut player);
 
                 menu::menu_button(ui, text(locale, "controls-menu"), |ui| {
-                    ui.add_enabled_ui(player.is_some(), |ui| {
-                        let playing = player.as_ref().map(|p| p.is_playing()).unwrap_or_default();
-                        if Button::new(text(locale, if playing { "controls-menu-suspend" } else { "controls-menu-resume" })).shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_PAUSE)).ui(ui).clicked() {
-                            ui.close_menu();
-                            if let Some(player) = &mut player {
-                                player.set_is_playing(!player.is_playing());
-                            }
-                        }
-                    });
-                    if Button::new(text(locale, "controls-menu-volume")).ui(ui).clicked() {
-                        dialogs.open_volume_controls();
-                        ui.close_menu();
-                    }
+                    self.action_button(locale, ui, Action::TogglePlaying, dialogs, &mut player);
+                    self.action_button(locale, ui, Action::ToggleFastForward, dialogs, &mut player);
+                    self.action_button(locale, ui, Action::VolumeControls, dialogs, &mut player);
                 });
                 menu::menu_button(ui, text(locale, "bookmarks-menu"), |ui| {
                     if Button::new(text(locale, "bookmarks-menu-add")).ui(ui).clicked() {
                         ui.close_menu();
-
-                        let initial_url = self.currently_opened.as_ref().map(|(url, _)| url.clone());
-
-                        dialogs.open_add_bookmark(initial_url);
-                    }
-
-                    if Button::new(text(locale, "bookmarks-menu-manage")).ui(ui).clicked() {
-                        ui.close_menu();
-                        dialogs.open_bookmarks();
+                        self.run_action(Action::AddBookmark, dialogs, player.as_deref_mut());
                     }
+                    self.action_button(locale, ui, Action::ManageBookmarks, dialogs, &mut player);
 
                     if self.preferences.have_bookmarks() {
                         ui.separator();
+                        let mut clicked = None;
                         self.preferences.bookmarks(|bookmarks| {
                             for bookmark in bookmarks.iter().filter(|x| !x.is_invalid()) {
                                 if Button::new(&bookmark.name).ui(ui).clicked() {
                                     ui.close_menu();
-                                    let _ = self.event_loop.send_event(RuffleEvent::Open(bookmark.url.clone(), Box::new(self.default_launch_options.clone())));
+                                    clicked = Some(bookmark.url.clone());
                                 }
                             }
                         });
+                        if let Some(url) = clicked {
+                            self.open_url(url);
+                        }
                     }
                 });
                 menu::menu_button(ui, text(locale, "debug-menu"), |ui| {
-                    ui.add_enabled_ui(player.is_some(), |ui| {
-                        if Button::new(text(locale, "debug-menu-open-stage")).ui(ui).clicked() {
-                            ui.close_menu();
-                            if let Some(player) = &mut player {
-                                player.debug_ui().queue_message(DebugMessage::TrackStage);
-                            }
-                        }
-                        if Button::new(text(locale, "debug-menu-open-movie")).ui(ui).clicked() {
-                            ui.close_menu();
-                            if let Some(player) = &mut player {
-                                player.debug_ui().queue_message(DebugMessage::TrackTopLevelMovie);
-                            }
-                        }
-                        if Button::new(text(locale, "debug-menu-open-movie-list")).ui(ui).clicked() {
-                            ui.close_menu();
-                            if let Some(player) = &mut player {
-                                player.debug_ui().queue_message(DebugMessage::ShowKnownMovies);
-                            }
-                        }
-                        if Button::new(text(locale, "debug-menu-open-domain-list")).ui(ui).clicked() {
-                            ui.close_menu();
-                            if let Some(player) = &mut player {
-                                player.debug_ui().queue_message(DebugMessage::ShowDomains);
-                            }
-                        }
-                        if Button::new(text(locale, "debug-menu-search-display-objects")).ui(ui).clicked() {
-                            ui.close_menu();
-                            if let Some(player) = &mut player {
-                                player.debug_ui().queue_message(DebugMessage::SearchForDisplayObject);
-                            }
-                        }
-                    });
+                    for action in [
+                        Action::DebugStage,
+                        Action::DebugMovie,
+                        Action::DebugMovieList,
+                        Action::DebugDomainList,
+                        Action::DebugSearchDisplayObjects,
+                        Action::SitelockReport,
+                        Action::DumpHeap,
+                        Action::DumpOpcodeCounts,
+                    ] {
+                        self.action_button(locale, ui, action, dialogs, &mut player);
+                    }
+                    ui.separator();
+                    if ui
+                        .checkbox(
+                            &mut self.show_frame_pacing_stats,
+                            text(locale, "debug-menu-frame-pacing-stats"),
+                        )
+                        .clicked()
+                    {
+                        ui.close_menu();
+                    }
                 });
                 menu::menu_button(ui, text(locale, "help-menu"), |ui| {
                     if ui.button(text(locale, "help-menu-join-discord")).clicked() {
@@ -182,11 +324,18 @@ impl MenuBar {
                         self.launch_website(ui, "https://crowdin.com/project/ruffle");
                     }
                     ui.separator();
-                    if ui.button(text(locale, "help-menu-about")).clicked() {
-                        dialogs.open_about_screen();
-                        ui.close_menu();
-                    }
+                    self.action_button(locale, ui, Action::About, dialogs, &mut player);
                 });
+
+                if player
+                    .as_ref()
+                    .is_some_and(|player| player.is_movie_shortened())
+                {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(text(locale, "movie-shortened"))
+                            .on_hover_text(text(locale, "movie-shortened-tooltip"));
+                    });
+                }
             });
         });
     }
@@ -196,39 +345,17 @@ impl MenuBar {
         locale: &LanguageIdentifier,
         ui: &mut egui::Ui,
         dialogs: &mut Dialogs,
-        player_exists: bool,
+        player: &mut Option<&mut Player>,
     ) {
         menu::menu_button(ui, text(locale, "file-menu"), |ui| {
-            if Button::new(text(locale, "file-menu-open-quick"))
-                .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_OPEN))
-                .ui(ui)
-                .clicked()
-            {
-                ui.close_menu();
-                self.open_file();
-            }
-
-            if Button::new(text(locale, "file-menu-open-advanced"))
-                .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_OPEN_ADVANCED))
-                .ui(ui)
-                .clicked()
-            {
-                ui.close_menu();
-                dialogs.open_file_advanced();
-            }
-
-            if ui
-                .add_enabled(player_exists, Button::new(text(locale, "file-menu-reload")))
-                .clicked()
-            {
-                self.reload_movie(ui);
-            }
-
-            if ui
-                .add_enabled(player_exists, Button::new(text(locale, "file-menu-close")))
-                .clicked()
-            {
-                self.close_movie(ui);
+            for action in [
+                Action::OpenQuick,
+                Action::OpenAdvanced,
+                Action::OpenUrl,
+                Action::Reload,
+                Action::Close,
+            ] {
+                self.action_button(locale, ui, action, dialogs, player);
             }
             ui.separator();
 
@@ -275,23 +402,12 @@ impl MenuBar {
             }
 
             ui.separator();
-            if Button::new(text(locale, "file-menu-preferences"))
-                .ui(ui)
-                .clicked()
-            {
-                ui.close_menu();
-                dialogs.open_preferences();
-            }
+            self.action_button(locale, ui, Action::Preferences, dialogs, player);
+            self.action_button(locale, ui, Action::LocalStorage, dialogs, player);
+            self.action_button(locale, ui, Action::ExportSaves, dialogs, player);
+            self.action_button(locale, ui, Action::ImportSaves, dialogs, player);
             ui.separator();
-
-            if Button::new(text(locale, "file-menu-exit"))
-                .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_QUIT))
-                .ui(ui)
-                .clicked()
-            {
-                ui.close_menu();
-                self.request_exit();
-            }
+            self.action_button(locale, ui, Action::Exit, dialogs, player);
         });
     }
 
@@ -299,6 +415,7 @@ impl MenuBar {
         &mut self,
         locale: &LanguageIdentifier,
         ui: &mut egui::Ui,
+        dialogs: &mut Dialogs,
         player: &mut Option<&mut Player>,
     ) {
         menu::menu_button(ui, text(locale, "view-menu"), |ui| {
@@ -375,16 +492,7 @@ impl MenuBar {
                 }
                 ui.separator();
 
-                if Button::new(text(locale, "view-menu-fullscreen"))
-                    .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_FULLSCREEN))
-                    .ui(ui)
-                    .clicked()
-                {
-                    ui.close_menu();
-                    if let Some(player) = player {
-                        player.set_fullscreen(true);
-                    }
-                }
+                self.action_button(locale, ui, Action::Fullscreen, dialogs, player);
                 ui.separator();
 
                 ui.menu_button(text(locale, "quality"), |ui| {
@@ -425,20 +533,25 @@ impl MenuBar {
             )));
     }
 
-    fn close_movie(&mut self, ui: &mut egui::Ui) {
+    fn open_url(&mut self, url: Url) {
+        let _ = self.event_loop.send_event(RuffleEvent::Open(
+            url,
+            Box::new(self.default_launch_options.clone()),
+        ));
+    }
+
+    fn close_movie(&mut self) {
         let _ = self.event_loop.send_event(RuffleEvent::CloseFile);
         self.currently_opened = None;
-        ui.close_menu();
     }
 
-    fn reload_movie(&mut self, ui: &mut egui::Ui) {
+    fn reload_movie(&mut self) {
         let _ = self.event_loop.send_event(RuffleEvent::CloseFile);
         if let Some((movie_url, opts)) = self.currently_opened.take() {
             let _ = self
                 .event_loop
                 .send_event(RuffleEvent::Open(movie_url, opts.into()));
         }
-        ui.close_menu();
     }
 
     fn request_exit(&mut self) {
@@ -450,3 +563,113 @@ impl MenuBar {
         ui.close_menu();
     }
 }
+
+fn queue_debug_message(player: Option<&mut Player>, message: DebugMessage) {
+    if let Some(player) = player {
+        player.debug_ui().queue_message(message);
+    }
+}
+
+/// Asks the user where to save a screenshot of the movie, then saves it as a PNG.
+fn save_screenshot(player: &mut Player) {
+    let Some(bitmap) = player.capture_frame() else {
+        tracing::warn!("This renderer can't take screenshots");
+        return;
+    };
+    let Some(image) =
+        image::RgbaImage::from_raw(bitmap.width(), bitmap.height(), bitmap.data().to_vec())
+    else {
+        tracing::warn!("The captured frame is incomplete");
+        return;
+    };
+    std::thread::spawn(move || {
+        if let Some(path) = FileDialog::new()
+            .add_filter("PNG", &["png"])
+            .set_file_name("screenshot.png")
+            .save_file()
+        {
+            if let Err(e) = image.save(&path) {
+                tracing::error!("Couldn't save screenshot to {path:?}: {e}");
+            }
+        }
+    });
+}
+
+/// Asks the user where to save an archive of every shared object that movies have stored.
+fn export_saves(save_directory: PathBuf) {
+    std::thread::spawn(move || {
+        let Some(path) = FileDialog::new()
+            .add_filter("ZIP", &["zip"])
+            .set_file_name("ruffle_saves.zip")
+            .save_file()
+        else {
+            return;
+        };
+        let result = File::create(&path)
+            .and_then(|file| export_shared_objects_archive(&save_directory, file));
+        match result {
+            Ok(exported) => tracing::info!("Exported {exported} saves to {path:?}"),
+            Err(e) => tracing::error!("Couldn't export saves to {path:?}: {e}"),
+        }
+    });
+}
+
+/// Asks the user for an archive made by [`export_saves`] and copies its shared objects into
+/// the save directory.
+fn import_saves(save_directory: PathBuf) {
+    std::thread::spawn(move || {
+        let Some(path) = FileDialog::new().add_filter("ZIP", &["zip"]).pick_file() else {
+            return;
+        };
+        let result = File::open(&path)
+            .and_then(|file| import_shared_objects_archive(BufReader::new(file), &save_directory));
+        match result {
+            Ok(summary) => tracing::info!(
+                "Imported {} saves from {path:?} ({} skipped)",
+                summary.imported,
+                summary.skipped
+            ),
+            Err(e) => tracing::error!("Couldn't import saves from {path:?}: {e}"),
+        }
+    });
+}
+
+/// Logs a summary of the objects the movie keeps alive, then asks the user where to save
+/// the full reference graph as JSON.
+fn save_heap_dump(player: &mut Player) {
+    let dump = player.dump_heap(true);
+    tracing::info!("Heap summary:\n{}", dump.summary);
+    let json = format!("{:#}", dump.to_json());
+    std::thread::spawn(move || {
+        if let Some(path) = FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_file_name("heap.json")
+            .save_file()
+        {
+            if let Err(e) = std::fs::write(&path, json) {
+                tracing::error!("Couldn't save heap dump to {path:?}: {e}");
+            }
+        }
+    });
+}
+
+/// Logs how many times each opcode ran, then asks the user where to save the counts as JSON.
+fn save_opcode_report(player: &mut Player) {
+    let report = player.opcode_report();
+    tracing::info!("Opcode counts:\n{report}");
+    if !report.enabled {
+        return;
+    }
+    let json = format!("{:#}", report.to_json());
+    std::thread::spawn(move || {
+        if let Some(path) = FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_file_name("opcodes.json")
+            .save_file()
+        {
+            if let Err(e) = std::fs::write(&path, json) {
+                tracing::error!("Couldn't save opcode counts to {path:?}: {e}");
+            }
+        }
+    });
+}
@@ -1,6 +1,7 @@
 use chrono::Utc;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use url::Url;
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug, Default)]
 pub enum FilenamePattern {
@@ -31,6 +32,18 @@ impl FilenamePattern {
         }
     }
 
+    /// The path of the file that the traces of a movie are written to, where `name` tells apart
+    /// the movie from others (see [`movie_file_stem`]).
+    pub fn create_movie_trace_path(&self, directory: &Path, name: &str) -> PathBuf {
+        match self {
+            FilenamePattern::SingleFile => directory.join(format!("trace_{name}.log")),
+            FilenamePattern::WithTimestamp => directory.join(format!(
+                "trace_{name}_{}.log",
+                Utc::now().format("%F_%H-%M-%S")
+            )),
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             FilenamePattern::SingleFile => "single_file",
@@ -38,3 +51,45 @@ impl FilenamePattern {
         }
     }
 }
+
+/// A name for a movie that can be used in file names, taken from the file name in its URL.
+pub fn movie_file_stem(movie_url: &str) -> String {
+    let file_name = Url::parse(movie_url)
+        .ok()
+        .and_then(|url| Some(url.path_segments()?.next_back()?.to_owned()))
+        .unwrap_or_default();
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name.as_str(), |(stem, _)| stem);
+    let stem: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "movie".to_string()
+    } else {
+        stem
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn movie_file_stems() {
+        assert_eq!(movie_file_stem("file:///home/user/game.swf"), "game");
+        assert_eq!(
+            movie_file_stem("https://example.com/swf/game.v2.swf?level=1"),
+            "game_v2"
+        );
+        assert_eq!(movie_file_stem("https://example.com/"), "movie");
+        assert_eq!(movie_file_stem("not a url"), "movie");
+    }
+}
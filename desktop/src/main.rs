@@ -7,10 +7,14 @@
 #![windows_subsystem = "windows"]
 
 mod app;
+#[cfg(feature = "automation")]
+mod automation;
 mod backends;
 mod cli;
+mod crash_report;
 mod custom_event;
 mod dbus;
+mod frame_pacing;
 mod gui;
 mod log;
 mod player;
@@ -19,17 +23,21 @@ mod preferences;
 mod tracy;
 mod util;
 
+use crate::crash_report::{CrashReport, MovieInfo};
 use crate::preferences::GlobalPreferences;
 use anyhow::{Context, Error};
 use app::App;
 use clap::Parser;
 use cli::Opt;
 use rfd::MessageDialogResult;
+use ruffle_core::stub::RecentStubs;
 use ruffle_core::StaticCallstack;
-use std::cell::RefCell;
+use ruffle_frontend_utils::backends::storage::import_flash_player_shared_objects;
+use std::cell::{Cell, RefCell};
 use std::env;
 use std::fs::File;
 use std::panic::PanicHookInfo;
+use std::path::PathBuf;
 use tracing_subscriber::fmt::Layer;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
@@ -39,6 +47,14 @@ thread_local! {
     static CALLSTACK: RefCell<Option<StaticCallstack>> = RefCell::default();
     static RENDER_INFO: RefCell<Option<String>> = RefCell::default();
     static SWF_INFO: RefCell<Option<String>> = RefCell::default();
+    static MOVIE_INFO: RefCell<Option<MovieInfo>> = RefCell::default();
+    static RECENT_STUBS: RefCell<Option<RecentStubs>> = RefCell::default();
+    static PLAYER_CONFIG: RefCell<Option<String>> = RefCell::default();
+    /// Where crash reports are written, if the user opted into them.
+    static CRASH_REPORT_DIRECTORY: RefCell<Option<PathBuf>> = RefCell::default();
+    /// Set while the player is running inside a panic boundary,
+    /// so that a panic only stops the movie rather than the application.
+    static PLAYER_PANIC_RECOVERABLE: Cell<bool> = const { Cell::new(false) };
 }
 
 #[cfg(feature = "tracy")]
@@ -81,6 +97,16 @@ fn panic_hook(info: &PanicHookInfo) {
         }
     });
 
+    if PLAYER_PANIC_RECOVERABLE.with(Cell::get) {
+        // The movie will be stopped and the error shown in the GUI, so don't interrupt the user here.
+        if let Some(directory) = CRASH_REPORT_DIRECTORY.with(|d| d.borrow().clone()) {
+            if let Err(e) = write_crash_report(info, &directory) {
+                eprintln!("Couldn't write crash report: {e}");
+            }
+        }
+        return;
+    }
+
     // [NA] Let me just point out that PanicInfo::message() exists but isn't stable and that sucks.
     let panic_text = info.to_string();
     let message = if let Some(text) = panic_text.strip_prefix("panicked at '") {
@@ -93,6 +119,35 @@ fn panic_hook(info: &PanicHookInfo) {
     } else {
         panic_text.trim()
     };
+
+    if let Some(directory) = CRASH_REPORT_DIRECTORY.with(|d| d.borrow().clone()) {
+        match write_crash_report(info, &directory) {
+            Ok(path) => {
+                if rfd::MessageDialog::new()
+                    .set_level(rfd::MessageLevel::Error)
+                    .set_title("Ruffle")
+                    .set_description(format!(
+                        "Ruffle has encountered a fatal error, this is a bug.\n\n\
+                        {message}\n\n\
+                        A crash report was saved to {}.\n\
+                        It has not been sent anywhere. If you report this bug to us, please attach it. Thank you!\n\
+                        Pressing Yes will open the report.",
+                        path.display()
+                    ))
+                    .set_buttons(rfd::MessageButtons::YesNo)
+                    .show()
+                    == MessageDialogResult::Yes
+                {
+                    if let Ok(url) = Url::from_file_path(&path) {
+                        let _ = webbrowser::open(url.as_str());
+                    }
+                }
+                return;
+            }
+            Err(e) => eprintln!("Couldn't write crash report: {e}"),
+        }
+    }
+
     if rfd::MessageDialog::new()
         .set_level(rfd::MessageLevel::Error)
         .set_title("Ruffle")
@@ -140,6 +195,33 @@ fn panic_hook(info: &PanicHookInfo) {
     }
 }
 
+fn write_crash_report(
+    info: &PanicHookInfo,
+    directory: &std::path::Path,
+) -> std::io::Result<PathBuf> {
+    let mut report = CrashReport {
+        panic_text: info.to_string(),
+        ruffle_version: RUFFLE_VERSION.to_string(),
+        operating_system: os_info::get().to_string(),
+        ..Default::default()
+    };
+    SWF_INFO.with(|i| report.movie_name = i.borrow().clone());
+    MOVIE_INFO.with(|i| report.movie = i.borrow().clone());
+    RECENT_STUBS.with(|stubs| {
+        if let Some(stubs) = &*stubs.borrow() {
+            stubs.for_each(|stub| report.recent_stubs.push(stub.to_string()));
+        }
+    });
+    CALLSTACK.with(|callstack| {
+        if let Some(callstack) = &*callstack.borrow() {
+            callstack.avm2(|callstack| report.avm2_callstack = Some(callstack.to_string()));
+        }
+    });
+    RENDER_INFO.with(|i| report.render_info = i.borrow().clone());
+    PLAYER_CONFIG.with(|i| report.player_config = i.borrow().clone());
+    report.write_to(directory, chrono::Utc::now())
+}
+
 fn shutdown() {
     // Without explicitly detaching the console cmd won't redraw it's prompt.
     #[cfg(windows)]
@@ -155,6 +237,11 @@ async fn main() -> Result<(), Error> {
     let opt = Opt::parse();
     let preferences = GlobalPreferences::load(opt.clone())?;
 
+    if preferences.crash_reports_enabled() {
+        let directory = preferences.cli.config.join("crash_reports");
+        CRASH_REPORT_DIRECTORY.with(|d| *d.borrow_mut() = Some(directory));
+    }
+
     let logs_path = &preferences.cli.cache_directory.join("log");
     let log_path = preferences.log_filename_pattern().create_path(logs_path);
     if let Some(parent) = log_path.parent() {
@@ -188,6 +275,12 @@ async fn main() -> Result<(), Error> {
 
     subscriber.init();
 
+    if let Some(source) = &preferences.cli.import_flash_saves {
+        let result = import_flash_saves(source.clone(), &preferences.cli.save_directory);
+        shutdown();
+        return result;
+    }
+
     let result = App::new(preferences)
         .await
         .and_then(|(mut app, event_loop)| {
@@ -202,6 +295,25 @@ async fn main() -> Result<(), Error> {
     result
 }
 
+/// Copies the shared objects of a Flash Player installation into Ruffle's save directory.
+fn import_flash_saves(
+    source: Option<PathBuf>,
+    save_directory: &std::path::Path,
+) -> Result<(), Error> {
+    let source = source
+        .or_else(cli::get_default_flash_player_directory)
+        .context("Couldn't find the Flash Player directory")?;
+    let summary = import_flash_player_shared_objects(&source, save_directory)
+        .with_context(|| format!("Couldn't import saves from {}", source.display()))?;
+    println!(
+        "Imported {} saves from {} ({} skipped)",
+        summary.imported,
+        source.display(),
+        summary.skipped
+    );
+    Ok(())
+}
+
 /// Move logs from config directory into proper log directory.
 ///
 /// This exists because in older versions Ruffle created log files in the config directory.
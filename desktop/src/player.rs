@@ -1,20 +1,29 @@
 use crate::backends::{
-    DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopNavigatorInterface,
-    DesktopUiBackend,
+    DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopLogBackend,
+    DesktopNavigatorInterface, DesktopUiBackend,
 };
 use crate::cli::FilesystemAccessMode;
 use crate::cli::GameModePreference;
 use crate::custom_event::RuffleEvent;
+use crate::gui::dialogs::message_dialog::MessageDialogConfiguration;
+use crate::gui::{text, DialogDescriptor, LocalizableText};
 use crate::gui::{FilePicker, MovieView};
 use crate::preferences::GlobalPreferences;
-use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
+use crate::{
+    CALLSTACK, MOVIE_INFO, PLAYER_CONFIG, PLAYER_PANIC_RECOVERABLE, RECENT_STUBS, RENDER_INFO,
+    SWF_INFO,
+};
 use anyhow::anyhow;
+use ruffle_core::backend::audio::FastForwardAudio;
 use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
-use ruffle_core::config::Letterbox;
+use ruffle_core::config::{Letterbox, SpoofedCapabilities};
 use ruffle_core::events::{GamepadButton, KeyCode};
+use ruffle_core::prefetch::PrefetchPolicy;
+use ruffle_core::timer::Timers;
 use ruffle_core::{DefaultFont, LoadBehavior, Player, PlayerBuilder, PlayerEvent};
 use ruffle_frontend_utils::backends::audio::CpalAudioBackend;
 use ruffle_frontend_utils::backends::executor::{AsyncExecutor, PollRequester};
+use ruffle_frontend_utils::backends::filesystem::DiskFilesystemBackend;
 use ruffle_frontend_utils::backends::navigator::ExternalNavigatorBackend;
 use ruffle_frontend_utils::bundle::source::BundleSourceError;
 use ruffle_frontend_utils::bundle::{Bundle, BundleError};
@@ -23,12 +32,16 @@ use ruffle_frontend_utils::player_options::PlayerOptions;
 use ruffle_frontend_utils::recents::Recent;
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::WgpuRenderBackend;
-use ruffle_render_wgpu::clap::PowerPreference;
+use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
+use ruffle_render_wgpu::target::RenderTarget;
+use std::any::Any;
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex, MutexGuard};
@@ -52,6 +65,15 @@ pub struct LaunchOptions {
     pub filesystem_access_mode: FilesystemAccessMode,
     pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     pub avm2_optimizer_enabled: bool,
+    pub avm2_strict_verification: bool,
+    pub prefetch_policy: PrefetchPolicy,
+    pub video_decode_ahead: usize,
+    pub timer_budget: Option<Duration>,
+    pub detect_sitelocks: bool,
+    pub count_opcodes: bool,
+    pub fast_forward_audio: FastForwardAudio,
+    pub idle_detection: bool,
+    pub random_seed: Option<u64>,
 }
 
 impl From<&GlobalPreferences> for LaunchOptions {
@@ -92,6 +114,28 @@ impl From<&GlobalPreferences> for LaunchOptions {
                 } else {
                     None
                 },
+                relaxed_security: if value.cli.relaxed_security {
+                    Some(true)
+                } else {
+                    None
+                },
+                partial_playback: if value.cli.partial_playback {
+                    Some(true)
+                } else {
+                    None
+                },
+                capabilities: SpoofedCapabilities {
+                    version: value.cli.spoof_version.clone(),
+                    os: value.cli.spoof_os.clone(),
+                    language: value.cli.spoof_language.clone(),
+                    player_type: value.cli.spoof_player_type.clone(),
+                    is_debugger: if value.cli.spoof_debugger {
+                        Some(true)
+                    } else {
+                        None
+                    },
+                    screen_resolution: value.cli.spoof_screen_resolution,
+                },
             },
             proxy: value.cli.proxy.clone(),
             fullscreen: value.cli.fullscreen,
@@ -103,6 +147,15 @@ impl From<&GlobalPreferences> for LaunchOptions {
             tcp_connections: value.cli.tcp_connections,
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
+            avm2_strict_verification: value.cli.avm2_strict_verification,
+            prefetch_policy: value.cli.prefetch,
+            video_decode_ahead: value.cli.video_decode_ahead,
+            timer_budget: value.cli.timer_budget,
+            detect_sitelocks: value.cli.detect_sitelocks,
+            count_opcodes: value.cli.count_opcodes,
+            fast_forward_audio: value.cli.fast_forward_audio,
+            idle_detection: !value.cli.no_idle_detection,
+            random_seed: value.cli.random_seed,
         }
     }
 }
@@ -193,8 +246,19 @@ impl ActivePlayer {
             tracing::warn!("Couldn't update recents: {e}");
         }
 
+        // Options remembered for this movie apply unless they were given explicitly.
+        let movie_preferences = preferences.movie_preferences(&movie_url);
+        let opt = if movie_preferences.is_empty() {
+            Cow::Borrowed(opt)
+        } else {
+            let mut opt = opt.clone();
+            opt.player.spoof_url = opt.player.spoof_url.or(movie_preferences.spoof_url);
+            opt.player.base = opt.player.base.or(movie_preferences.base_url);
+            Cow::Owned(opt)
+        };
+
         let opt = match &content {
-            PlayingContent::DirectFile(_) => Cow::Borrowed(opt),
+            PlayingContent::DirectFile(_) => opt,
             PlayingContent::Bundle(_, bundle) => {
                 let player = opt.player.or(&bundle.information().player);
 
@@ -210,6 +274,15 @@ impl ActivePlayer {
                     filesystem_access_mode: opt.filesystem_access_mode,
                     gamepad_button_mapping: opt.gamepad_button_mapping.clone(),
                     avm2_optimizer_enabled: opt.avm2_optimizer_enabled,
+                    avm2_strict_verification: opt.avm2_strict_verification,
+                    prefetch_policy: opt.prefetch_policy,
+                    video_decode_ahead: opt.video_decode_ahead,
+                    timer_budget: opt.timer_budget,
+                    detect_sitelocks: opt.detect_sitelocks,
+                    count_opcodes: opt.count_opcodes,
+                    fast_forward_audio: opt.fast_forward_audio,
+                    idle_detection: opt.idle_detection,
+                    random_seed: opt.random_seed,
                 })
             }
         };
@@ -230,13 +303,15 @@ impl ActivePlayer {
             opt.open_url_mode,
             opt.socket_allowed.clone(),
             opt.tcp_connections.unwrap_or(SocketMode::Ask),
+            preferences.socket_proxies(),
             Rc::new(content),
             DesktopNavigatorInterface::new(
                 event_loop.clone(),
                 movie_url.to_file_path().ok(),
                 opt.filesystem_access_mode,
             ),
-        );
+        )
+        .with_fetch_policy(preferences.fetch_policy());
 
         if cfg!(feature = "external_video") && preferences.openh264_enabled() {
             #[cfg(feature = "external_video")]
@@ -254,13 +329,15 @@ impl ActivePlayer {
                         ExternalVideoBackend::new()
                     }
                 };
-                builder = builder.with_video(backend);
+                builder = builder.with_video(backend.with_decode_ahead(opt.video_decode_ahead));
             }
         } else {
             #[cfg(feature = "software_video")]
             {
-                builder =
-                    builder.with_video(ruffle_video_software::backend::SoftwareVideoBackend::new());
+                builder = builder.with_video(
+                    ruffle_video_software::backend::SoftwareVideoBackend::new()
+                        .with_decode_ahead(opt.video_decode_ahead),
+                );
             }
         }
 
@@ -278,9 +355,22 @@ impl ActivePlayer {
             GameModePreference::Off => false,
         };
 
-        let renderer = WgpuRenderBackend::new(descriptors, movie_view)
-            .map_err(|e| anyhow!(e.to_string()))
-            .expect("Couldn't create wgpu rendering backend");
+        // Turning off hardware acceleration in the Settings panel only applies
+        // when no particular graphics backend was chosen on the command line.
+        let software_rendering = preferences.graphics_backends() == GraphicsBackend::Software
+            || (preferences.cli.graphics.is_none() && !preferences.hardware_acceleration());
+        let renderer: Box<dyn RenderBackend> = if software_rendering {
+            Box::new(SoftwareRenderBackend::new(
+                movie_view.width(),
+                movie_view.height(),
+            ))
+        } else {
+            let mut renderer = WgpuRenderBackend::new(descriptors, movie_view)
+                .map_err(|e| anyhow!(e.to_string()))
+                .expect("Couldn't create wgpu rendering backend");
+            renderer.set_blend_space(preferences.blend_space());
+            Box::new(renderer)
+        };
         RENDER_INFO.with(|i| *i.borrow_mut() = Some(renderer.debug_info().to_string()));
 
         if opt.player.dummy_external_interface.unwrap_or_default() {
@@ -289,14 +379,48 @@ impl ActivePlayer {
             }));
         }
 
+        if let Some(directory) = preferences.air_sandbox_directory() {
+            builder = builder.with_filesystem(Box::new(DiskFilesystemBackend::new(directory)));
+        }
+
         if !opt.gamepad_button_mapping.is_empty() {
             builder = builder.with_gamepad_button_mapping(opt.gamepad_button_mapping.clone());
         }
 
+        builder = builder.with_mouse_move_coalescing(preferences.coalesce_mouse_moves());
+
+        let mut log = DesktopLogBackend::new(preferences.mirror_movie_traces());
+        if preferences.movie_trace_files() {
+            log = log.with_trace_files(
+                opt.cache_directory.join("log"),
+                preferences.log_filename_pattern(),
+            );
+        }
+        builder = builder.with_log(log);
+
+        if let Some(size) = preferences.morph_shape_cache_size() {
+            builder = builder.with_morph_shape_cache_size(size);
+        }
+
+        builder = builder.with_asset_cache_size(
+            preferences
+                .cli
+                .asset_cache_size
+                .map(|megabytes| megabytes * 1024 * 1024),
+        );
+
+        let texture_memory_budget = preferences
+            .gpu_memory_budget()
+            .map(|megabytes| megabytes as usize * 1024 * 1024);
+
         builder = builder
             .with_navigator(navigator)
-            .with_renderer(renderer)
-            .with_storage(preferences.storage_backend().create_backend(&opt))
+            .with_boxed_renderer(renderer)
+            .with_storage(
+                preferences
+                    .storage_backend()
+                    .create_backend(&opt, &preferences),
+            )
             .with_fs_commands(Box::new(DesktopFSCommandProvider {
                 event_loop: event_loop.clone(),
             }))
@@ -330,12 +454,30 @@ impl ActivePlayer {
             .with_player_version(opt.player.player_version)
             .with_player_runtime(opt.player.player_runtime.unwrap_or_default())
             .with_frame_rate(opt.player.frame_rate)
-            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled);
+            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled)
+            .with_avm2_strict_verification(opt.avm2_strict_verification)
+            .with_prefetch_policy(opt.prefetch_policy)
+            .with_sitelock_detection(opt.detect_sitelocks)
+            .with_opcode_counting(opt.count_opcodes)
+            .with_fast_forward_audio(opt.fast_forward_audio)
+            .with_idle_detection(opt.idle_detection)
+            .with_random_seed(opt.random_seed)
+            .with_relaxed_security(
+                opt.player
+                    .relaxed_security
+                    .unwrap_or_else(|| preferences.relaxed_security()),
+            )
+            .with_partial_playback(opt.player.partial_playback.unwrap_or_default())
+            .with_spoofed_capabilities(opt.player.capabilities.clone())
+            .with_texture_memory_budget(texture_memory_budget)
+            .with_timer_budget(opt.timer_budget.unwrap_or(Timers::DEFAULT_BUDGET));
         let player = builder.build();
 
         window.set_title(&format!("Ruffle - {readable_name}"));
 
         SWF_INFO.with(|i| *i.borrow_mut() = Some(readable_name));
+        MOVIE_INFO.with(|i| *i.borrow_mut() = None);
+        PLAYER_CONFIG.with(|i| *i.borrow_mut() = Some(format!("{:#?}", opt.player)));
 
         let on_metadata = move |swf_header: &ruffle_core::swf::HeaderExt| {
             let _ = event_loop.send_event(RuffleEvent::OnMetadata(swf_header.clone()));
@@ -346,6 +488,9 @@ impl ActivePlayer {
             CALLSTACK.with(|callstack| {
                 *callstack.borrow_mut() = Some(player_lock.callstack());
             });
+            RECENT_STUBS.with(|stubs| {
+                *stubs.borrow_mut() = Some(player_lock.recent_stubs());
+            });
             player_lock.fetch_root_movie(
                 movie_url.to_string(),
                 opt.player.parameters.to_owned(),
@@ -480,19 +625,75 @@ impl PlayerController {
         }
     }
 
-    pub fn handle_event(&self, event: PlayerEvent) -> bool {
-        if let Some(mut player) = self.get() {
-            if player.is_playing() {
-                return player.handle_event(event);
+    /// Runs `f` with the player, if there is one.
+    ///
+    /// If the player panics, the movie is stopped and an error is shown
+    /// instead of bringing down the whole application.
+    pub fn catch_panic<R>(&mut self, f: impl FnOnce(&mut Player) -> R) -> Option<R> {
+        self.isolate(|player| {
+            f(&mut player
+                .player
+                .try_lock()
+                .expect("Player lock must be available"))
+        })
+    }
+
+    fn isolate<R>(&mut self, f: impl FnOnce(&ActivePlayer) -> R) -> Option<R> {
+        let player = self.player.as_ref()?;
+        let result = PLAYER_PANIC_RECOVERABLE.with(|recoverable| {
+            recoverable.set(true);
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| f(player)));
+            recoverable.set(false);
+            result
+        });
+
+        match result {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                self.recover_from_panic(payload);
+                None
             }
         }
+    }
 
-        false
+    fn recover_from_panic(&mut self, payload: Box<dyn Any + Send>) {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Unknown error".to_string()
+        };
+        tracing::error!("Player crashed, stopping movie: {message}");
+
+        // The player may be in an inconsistent state and its lock is poisoned, so it can't be used anymore.
+        self.destroy();
+        let _ = self.event_loop.send_event(RuffleEvent::CloseFile);
+        let _ = self
+            .event_loop
+            .send_event(RuffleEvent::OpenDialog(DialogDescriptor::ShowMessage(
+                MessageDialogConfiguration::new(
+                    LocalizableText::LocalizedText("message-dialog-player-crashed-title"),
+                    LocalizableText::NonLocalizedText(
+                        format!(
+                            "{}\n\n{message}",
+                            text(
+                                &self.preferences.language(),
+                                "message-dialog-player-crashed-description"
+                            )
+                        )
+                        .into(),
+                    ),
+                ),
+            )));
     }
 
-    pub fn poll(&self) {
-        if let Some(player) = &self.player {
-            player.executor.poll_all()
-        }
+    pub fn handle_event(&mut self, event: PlayerEvent) -> bool {
+        self.catch_panic(|player| player.is_playing() && player.handle_event(event))
+            .unwrap_or_default()
+    }
+
+    pub fn poll(&mut self) {
+        self.isolate(|player| player.executor.poll_all());
     }
 }
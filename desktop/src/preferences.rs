@@ -1,24 +1,36 @@
+mod file;
 mod read;
 mod write;
 
+pub mod movies;
+pub mod sites;
 pub mod storage;
 
-use crate::cli::{GameModePreference, Opt};
-use crate::gui::ThemePreference;
+use crate::cli::{FocusLossPreference, GameModePreference, Opt};
+use crate::gui::{Action, Shortcuts, ThemePreference};
 use crate::log::FilenamePattern;
+use crate::preferences::movies::MoviePreferences;
 use crate::preferences::read::read_preferences;
+use crate::preferences::sites::SitePreferences;
 use crate::preferences::write::PreferencesWriter;
 use anyhow::{Context, Error};
+use egui::KeyboardShortcut;
 use ruffle_core::backend::ui::US_ENGLISH;
+use ruffle_frontend_utils::backends::navigator::FetchPolicy;
 use ruffle_frontend_utils::bookmarks::{read_bookmarks, Bookmarks, BookmarksWriter};
-use ruffle_frontend_utils::parse::DocumentHolder;
+use ruffle_frontend_utils::parse::{DocumentHolder, ParseWarning};
 use ruffle_frontend_utils::recents::{read_recents, Recents, RecentsWriter};
+use ruffle_render_wgpu::backend::BlendSpace;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use sys_locale::get_locale;
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::{Receiver, Sender};
 use unic_langid::LanguageIdentifier;
+use url::Url;
 
 /// The preferences that relate to the application itself.
 ///
@@ -46,6 +58,9 @@ pub struct GlobalPreferences {
 
     recents: Arc<Mutex<DocumentHolder<Recents>>>,
 
+    /// Whether the saved preferences were corrupted, and their backup was loaded instead.
+    recovered_from_backup: bool,
+
     watchers: GlobalPreferencesWatchers,
 }
 
@@ -53,18 +68,7 @@ impl GlobalPreferences {
     pub fn load(cli: Opt) -> Result<Self, Error> {
         std::fs::create_dir_all(&cli.config).context("Failed to create configuration directory")?;
         let preferences_path = cli.config.join("preferences.toml");
-        let preferences = if preferences_path.exists() {
-            let contents = std::fs::read_to_string(&preferences_path)
-                .context("Failed to read saved preferences")?;
-            let result = read_preferences(&contents);
-            for warning in result.warnings {
-                // TODO: A way to display warnings to users, generally
-                tracing::warn!("{warning}");
-            }
-            result.result
-        } else {
-            Default::default()
-        };
+        let (preferences, recovered_from_backup) = load_preferences(&preferences_path);
 
         let bookmarks_path = cli.config.join("bookmarks.toml");
         let bookmarks = if bookmarks_path.exists() {
@@ -97,10 +101,17 @@ impl GlobalPreferences {
             preferences: Arc::new(Mutex::new(preferences)),
             bookmarks: Arc::new(Mutex::new(bookmarks)),
             recents: Arc::new(Mutex::new(recents)),
+            recovered_from_backup,
             watchers: Default::default(),
         })
     }
 
+    /// Whether the saved preferences were corrupted when loading them, so that their backup
+    /// was loaded instead, and the latest changes to them may be missing.
+    pub fn recovered_from_backup(&self) -> bool {
+        self.recovered_from_backup
+    }
+
     pub fn graphics_backends(&self) -> GraphicsBackend {
         self.cli.graphics.unwrap_or_else(|| {
             self.preferences
@@ -119,6 +130,52 @@ impl GlobalPreferences {
         })
     }
 
+    /// The maximum GPU memory to use for bitmap textures, in megabytes.
+    pub fn gpu_memory_budget(&self) -> Option<u64> {
+        self.cli.gpu_memory_budget.or_else(|| {
+            self.preferences
+                .lock()
+                .expect("Preferences is not reentrant")
+                .gpu_memory_budget
+        })
+    }
+
+    /// The color space in which the movie is blended and antialiased.
+    pub fn blend_space(&self) -> BlendSpace {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .blend_space
+    }
+
+    /// How many intermediate frames each morph shape keeps tessellated,
+    /// or `None` for Ruffle's default.
+    pub fn morph_shape_cache_size(&self) -> Option<usize> {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .morph_shape_cache_size
+    }
+
+    /// Whether the graphics card may be used for rendering,
+    /// rather than always falling back to a software renderer.
+    pub fn hardware_acceleration(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .hardware_acceleration
+    }
+
+    /// Whether presenting frames should wait for the display's vertical blank.
+    pub fn vsync(&self) -> bool {
+        !self.cli.no_vsync
+            && self
+                .preferences
+                .lock()
+                .expect("Preferences is not reentrant")
+                .vsync
+    }
+
     pub fn gamemode_preference(&self) -> GameModePreference {
         self.cli.gamemode.unwrap_or_else(|| {
             self.preferences
@@ -128,6 +185,23 @@ impl GlobalPreferences {
         })
     }
 
+    pub fn focus_loss_preference(&self) -> FocusLossPreference {
+        self.cli.focus_loss.unwrap_or_else(|| {
+            self.preferences
+                .lock()
+                .expect("Preferences is not reentrant")
+                .focus_loss_preference
+        })
+    }
+
+    /// How many times per second the movie is updated while throttled in the background.
+    pub fn background_frame_rate(&self) -> f64 {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .background_frame_rate
+    }
+
     pub fn language(&self) -> LanguageIdentifier {
         self.preferences
             .lock()
@@ -151,6 +225,13 @@ impl GlobalPreferences {
             .mute
     }
 
+    pub fn audio_enhancements(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .audio_enhancements
+    }
+
     pub fn preferred_volume(&self) -> f32 {
         self.cli.volume.unwrap_or_else(|| {
             self.preferences
@@ -167,6 +248,41 @@ impl GlobalPreferences {
             .enable_openh264
     }
 
+    pub fn mouse_wheel_scale(&self) -> f64 {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .mouse_wheel_scale
+    }
+
+    /// The keyboard shortcuts of the GUI, including those changed by the user.
+    pub fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new(
+            &self
+                .preferences
+                .lock()
+                .expect("Preferences is not reentrant")
+                .shortcuts,
+        )
+    }
+
+    /// Whether mouse moves between two updates of the player are handled as one.
+    pub fn coalesce_mouse_moves(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .coalesce_mouse_moves
+    }
+
+    /// Whether a local crash report should be written when Ruffle panics.
+    pub fn crash_reports_enabled(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .log
+            .crash_reports
+    }
+
     pub fn log_filename_pattern(&self) -> FilenamePattern {
         self.preferences
             .lock()
@@ -175,6 +291,22 @@ impl GlobalPreferences {
             .filename_pattern
     }
 
+    pub fn movie_trace_files(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .log
+            .movie_trace_files
+    }
+
+    pub fn mirror_movie_traces(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .log
+            .mirror_movie_traces
+    }
+
     pub fn bookmarks(&self, fun: impl FnOnce(&Bookmarks)) {
         fun(&self.bookmarks.lock().expect("Bookmarks is not reentrant"))
     }
@@ -195,6 +327,95 @@ impl GlobalPreferences {
         })
     }
 
+    /// The directory exposed to AIR content through `flash.filesystem`,
+    /// or `None` if AIR filesystem access is disabled.
+    pub fn air_sandbox_directory(&self) -> Option<PathBuf> {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .air
+            .sandbox_directory
+            .clone()
+    }
+
+    /// The settings chosen for the given site in the Settings panel.
+    pub fn site_preferences(&self, site: &str) -> SitePreferences {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .sites
+            .get(site)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The options remembered for the movie at the given URL.
+    pub fn movie_preferences(&self, url: &Url) -> MoviePreferences {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .movies
+            .get(url.as_str())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The WebSocket endpoints to tunnel socket connections through, keyed by `host:port`.
+    pub fn socket_proxies(&self) -> HashMap<String, Url> {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .socket_proxies
+            .iter()
+            .map(|(endpoint, proxy)| (endpoint.clone(), proxy.clone()))
+            .collect()
+    }
+
+    pub fn network_timeout(&self) -> Option<f64> {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .network
+            .timeout
+    }
+
+    pub fn network_retries(&self) -> u32 {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .network
+            .retries
+    }
+
+    pub fn offline(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .network
+            .offline
+    }
+
+    /// Whether movies may reach content outside their security sandbox.
+    pub fn relaxed_security(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .relaxed_security
+    }
+
+    /// How fetches over the network behave on slow or unreliable connections.
+    pub fn fetch_policy(&self) -> FetchPolicy {
+        let preferences = self
+            .preferences
+            .lock()
+            .expect("Preferences is not reentrant");
+        FetchPolicy {
+            timeout: preferences.network.timeout.map(Duration::from_secs_f64),
+            max_retries: preferences.network.retries,
+            offline: preferences.network.offline,
+        }
+    }
+
     pub fn recent_limit(&self) -> usize {
         self.preferences
             .lock()
@@ -228,7 +449,7 @@ impl GlobalPreferences {
         fun(&mut writer);
 
         let serialized = preferences.serialize();
-        std::fs::write(self.cli.config.join("preferences.toml"), serialized)
+        file::write_with_backup(&self.cli.config.join("preferences.toml"), &serialized)
             .context("Could not write preferences to disk")
     }
 
@@ -255,20 +476,82 @@ impl GlobalPreferences {
     }
 }
 
+/// Reads saved preferences, falling back to their backup if they're missing, corrupted
+/// or cut off. Also returns whether the backup was used.
+fn load_preferences(path: &Path) -> (DocumentHolder<SavedGlobalPreferences>, bool) {
+    if let Some(preferences) = load_preferences_file(path) {
+        return (preferences, false);
+    }
+    match load_preferences_file(&file::backup_path(path)) {
+        Some(backup) => {
+            tracing::warn!("Saved preferences were lost, loaded their backup instead");
+            (backup, true)
+        }
+        None => (Default::default(), false),
+    }
+}
+
+/// Reads saved preferences from a file, unless it's missing or isn't valid TOML,
+/// like a save that was cut off in the middle of a value.
+fn load_preferences_file(path: &Path) -> Option<DocumentHolder<SavedGlobalPreferences>> {
+    if !path.exists() {
+        return None;
+    }
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            tracing::warn!("Failed to read {}: {e}", path.display());
+            return None;
+        }
+    };
+    let result = read_preferences(&contents);
+    if let Some(warning) = result
+        .warnings
+        .iter()
+        .find(|warning| matches!(warning, ParseWarning::InvalidToml(_)))
+    {
+        tracing::warn!("Failed to read {}: {warning}", path.display());
+        return None;
+    }
+    for warning in result.warnings {
+        // TODO: A way to display warnings to users, generally
+        tracing::warn!("{warning}");
+    }
+    Some(result.result)
+}
+
 #[derive(PartialEq, Debug)]
 pub struct SavedGlobalPreferences {
     pub graphics_backend: GraphicsBackend,
     pub graphics_power_preference: PowerPreference,
+    pub gpu_memory_budget: Option<u64>,
+    pub blend_space: BlendSpace,
+    pub morph_shape_cache_size: Option<usize>,
+    pub hardware_acceleration: bool,
+    pub vsync: bool,
     pub gamemode_preference: GameModePreference,
+    pub focus_loss_preference: FocusLossPreference,
+    pub background_frame_rate: f64,
     pub language: LanguageIdentifier,
     pub output_device: Option<String>,
     pub mute: bool,
     pub volume: f32,
+    pub audio_enhancements: bool,
     pub enable_openh264: bool,
     pub recent_limit: usize,
+    pub mouse_wheel_scale: f64,
+    pub coalesce_mouse_moves: bool,
+    pub relaxed_security: bool,
     pub log: LogPreferences,
     pub storage: StoragePreferences,
+    pub air: AirPreferences,
     pub theme_preference: ThemePreference,
+    pub sites: BTreeMap<String, SitePreferences>,
+    pub movies: BTreeMap<String, MoviePreferences>,
+    pub socket_proxies: BTreeMap<String, Url>,
+    pub network: NetworkPreferences,
+    /// Shortcuts changed from their default, where `None` means the action has no shortcut.
+    pub shortcuts: BTreeMap<Action, Option<KeyboardShortcut>>,
 }
 
 impl Default for SavedGlobalPreferences {
@@ -281,23 +564,80 @@ impl Default for SavedGlobalPreferences {
         Self {
             graphics_backend: Default::default(),
             graphics_power_preference: Default::default(),
+            gpu_memory_budget: None,
+            blend_space: Default::default(),
+            morph_shape_cache_size: None,
+            hardware_acceleration: true,
+            vsync: true,
             gamemode_preference: Default::default(),
+            focus_loss_preference: Default::default(),
+            background_frame_rate: 5.0,
             language: locale,
             output_device: None,
             mute: false,
             volume: 1.0,
+            audio_enhancements: false,
             enable_openh264: true,
             recent_limit: 10,
+            mouse_wheel_scale: 1.0,
+            coalesce_mouse_moves: true,
+            relaxed_security: false,
             log: Default::default(),
             storage: Default::default(),
+            air: Default::default(),
             theme_preference: Default::default(),
+            sites: Default::default(),
+            movies: Default::default(),
+            socket_proxies: Default::default(),
+            network: Default::default(),
+            shortcuts: Default::default(),
         }
     }
 }
 
-#[derive(PartialEq, Debug, Default)]
+#[derive(PartialEq, Debug)]
 pub struct LogPreferences {
     pub filename_pattern: FilenamePattern,
+    pub crash_reports: bool,
+
+    /// Whether the traces of each movie are written to their own file.
+    pub movie_trace_files: bool,
+
+    /// Whether the traces of movies still go to the main log when they have their own file.
+    pub mirror_movie_traces: bool,
+}
+
+impl Default for LogPreferences {
+    fn default() -> Self {
+        Self {
+            filename_pattern: Default::default(),
+            crash_reports: false,
+            movie_trace_files: false,
+            mirror_movie_traces: true,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct NetworkPreferences {
+    /// How many seconds a request may take before failing, or `None` for no limit.
+    pub timeout: Option<f64>,
+
+    /// How many times a request that failed temporarily is sent again.
+    pub retries: u32,
+
+    /// Whether movies may only load files that don't need the network.
+    pub offline: bool,
+}
+
+impl Default for NetworkPreferences {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            retries: 2,
+            offline: false,
+        }
+    }
 }
 
 #[derive(PartialEq, Debug, Default)]
@@ -305,6 +645,11 @@ pub struct StoragePreferences {
     pub backend: storage::StorageBackend,
 }
 
+#[derive(PartialEq, Debug, Default)]
+pub struct AirPreferences {
+    pub sandbox_directory: Option<PathBuf>,
+}
+
 #[derive(Clone)]
 pub struct GlobalPreferencesWatchers {
     theme_preference_watcher: Arc<Sender<ThemePreference>>,
@@ -317,3 +662,41 @@ impl Default for GlobalPreferencesWatchers {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cut_off_preferences_load_the_backup() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("preferences.toml");
+
+        file::write_with_backup(&path, "volume = 0.25\n").unwrap();
+        file::write_with_backup(&path, "volume = 0.5\nmute = true\n").unwrap();
+        let (preferences, recovered) = load_preferences(&path);
+        assert_eq!(preferences.volume, 0.5);
+        assert!(!recovered);
+
+        for contents in ["volume = 0.5\nmute = tr", "volume = 0.5\nmute = ", "[log"] {
+            std::fs::write(&path, contents).unwrap();
+            let (preferences, recovered) = load_preferences(&path);
+            assert_eq!(preferences.volume, 0.25, "{contents:?}");
+            assert!(recovered, "{contents:?}");
+        }
+
+        // Files edited by hand don't need to end with a line break.
+        std::fs::write(&path, "volume = 0.5\nmute = true").unwrap();
+        let (preferences, recovered) = load_preferences(&path);
+        assert_eq!(preferences.volume, 0.5);
+        assert!(preferences.mute);
+        assert!(!recovered);
+
+        std::fs::remove_file(file::backup_path(&path)).unwrap();
+        std::fs::write(&path, "volume = 0.5\nmute = tr").unwrap();
+        let (preferences, recovered) = load_preferences(&path);
+        assert_eq!(preferences.volume, 1.0);
+        assert!(!recovered);
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The file that keeps the previous version of a saved file.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Replaces the contents of `path`, keeping its previous version at [`backup_path`].
+///
+/// The new contents are written to a temporary file first, which then takes the place of the
+/// old file, so a crash while saving leaves either the old or the new file behind, never a
+/// truncated one. The old file is copied to the backup rather than moved there, so `path`
+/// always exists.
+pub fn write_with_backup(path: &Path, contents: &str) -> io::Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    let mut file = File::create(&temp_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    if path.exists() {
+        std::fs::copy(path, backup_path(path))?;
+    }
+    std::fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_keeps_one_backup() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("preferences.toml");
+
+        write_with_backup(&path, "first\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\n");
+        assert!(!backup_path(&path).exists());
+
+        write_with_backup(&path, "second\n").unwrap();
+        write_with_backup(&path, "third\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "third\n");
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&path)).unwrap(),
+            "second\n"
+        );
+    }
+}
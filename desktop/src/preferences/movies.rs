@@ -0,0 +1,21 @@
+use url::Url;
+
+/// Options remembered for a single movie, used whenever it is opened without them being given.
+///
+/// Sitelocked movies refuse to run unless they were loaded from their original site,
+/// so users can store the URL to pretend they were loaded from once instead of passing it
+/// every time.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct MoviePreferences {
+    /// The URL the movie is told it was loaded from, as seen in `_url` and `loaderInfo.url`.
+    pub spoof_url: Option<Url>,
+
+    /// The URL that relative requests of the movie are resolved against.
+    pub base_url: Option<Url>,
+}
+
+impl MoviePreferences {
+    pub fn is_empty(&self) -> bool {
+        self.spoof_url.is_none() && self.base_url.is_none()
+    }
+}
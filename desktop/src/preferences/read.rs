@@ -1,6 +1,9 @@
+use crate::gui::{parse_shortcut, Action};
+use crate::preferences::movies::MoviePreferences;
+use crate::preferences::sites::SitePreferences;
 use crate::preferences::SavedGlobalPreferences;
 use ruffle_frontend_utils::parse::{
-    DocumentHolder, ParseContext, ParseDetails, ParseWarning, ReadExt,
+    DocumentHolder, ItemExt, ParseContext, ParseDetails, ParseWarning, ReadExt,
 };
 use toml_edit::DocumentMut;
 
@@ -35,6 +38,26 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.graphics_power_preference = value;
     };
 
+    if let Some(value) = document.get_integer(&mut cx, "gpu_memory_budget") {
+        result.gpu_memory_budget = u64::try_from(value).ok().filter(|value| *value > 0);
+    };
+
+    if let Some(value) = document.parse_from_str(&mut cx, "blend_space") {
+        result.blend_space = value;
+    };
+
+    if let Some(value) = document.get_integer(&mut cx, "morph_shape_cache_size") {
+        result.morph_shape_cache_size = usize::try_from(value).ok().filter(|value| *value > 0);
+    };
+
+    if let Some(value) = document.get_bool(&mut cx, "hardware_acceleration") {
+        result.hardware_acceleration = value;
+    };
+
+    if let Some(value) = document.get_bool(&mut cx, "vsync") {
+        result.vsync = value;
+    };
+
     if let Some(value) = document.parse_from_str(&mut cx, "language") {
         result.language = value;
     };
@@ -51,6 +74,10 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.mute = value;
     };
 
+    if let Some(value) = document.get_bool(&mut cx, "audio_enhancements") {
+        result.audio_enhancements = value;
+    };
+
     if let Some(value) = document.get_bool(&mut cx, "enable_openh264") {
         result.enable_openh264 = value;
     };
@@ -59,6 +86,20 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.recent_limit = value as usize;
     }
 
+    if let Some(value) = document.get_float_like(&mut cx, "mouse_wheel_scale") {
+        if value.is_finite() {
+            result.mouse_wheel_scale = value.clamp(0.1, 10.0);
+        }
+    }
+
+    if let Some(value) = document.get_bool(&mut cx, "coalesce_mouse_moves") {
+        result.coalesce_mouse_moves = value;
+    };
+
+    if let Some(value) = document.get_bool(&mut cx, "relaxed_security") {
+        result.relaxed_security = value;
+    };
+
     if let Some(value) = document.parse_from_str(&mut cx, "theme") {
         result.theme_preference = value;
     }
@@ -67,10 +108,29 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.gamemode_preference = value;
     }
 
+    if let Some(value) = document.parse_from_str(&mut cx, "focus_loss") {
+        result.focus_loss_preference = value;
+    }
+
+    if let Some(value) = document.get_float_like(&mut cx, "background_frame_rate") {
+        if value.is_finite() {
+            result.background_frame_rate = value.clamp(1.0, 60.0);
+        }
+    }
+
     document.get_table_like(&mut cx, "log", |cx, log| {
         if let Some(value) = log.parse_from_str(cx, "filename_pattern") {
             result.log.filename_pattern = value;
         };
+        if let Some(value) = log.get_bool(cx, "crash_reports") {
+            result.log.crash_reports = value;
+        };
+        if let Some(value) = log.get_bool(cx, "movie_trace_files") {
+            result.log.movie_trace_files = value;
+        };
+        if let Some(value) = log.get_bool(cx, "mirror_movie_traces") {
+            result.log.mirror_movie_traces = value;
+        };
     });
 
     document.get_table_like(&mut cx, "storage", |cx, storage| {
@@ -79,6 +139,97 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         }
     });
 
+    document.get_table_like(&mut cx, "air", |cx, air| {
+        if let Some(value) = air.parse_from_str(cx, "sandbox_directory") {
+            result.air.sandbox_directory = Some(value);
+        }
+    });
+
+    document.get_table_like(&mut cx, "sites", |cx, sites| {
+        for (site, item) in sites.iter() {
+            cx.push_key(site);
+            if let Some(table) = item.as_table_like() {
+                let mut site_preferences = SitePreferences::default();
+                if let Some(value) = table.parse_from_str(cx, "storage_limit") {
+                    site_preferences.storage_limit = value;
+                }
+                if let Some(value) = table.parse_from_str(cx, "camera") {
+                    site_preferences.camera = value;
+                }
+                if let Some(value) = table.parse_from_str(cx, "microphone") {
+                    site_preferences.microphone = value;
+                }
+                result.sites.insert(site.to_owned(), site_preferences);
+            } else {
+                cx.unexpected_type("table", item.type_name());
+            }
+            cx.pop_key();
+        }
+    });
+
+    document.get_table_like(&mut cx, "movies", |cx, movies| {
+        for (movie, item) in movies.iter() {
+            cx.push_key(movie);
+            if let Some(table) = item.as_table_like() {
+                let movie_preferences = MoviePreferences {
+                    spoof_url: table.parse_from_str(cx, "spoof_url"),
+                    base_url: table.parse_from_str(cx, "base_url"),
+                };
+                result.movies.insert(movie.to_owned(), movie_preferences);
+            } else {
+                cx.unexpected_type("table", item.type_name());
+            }
+            cx.pop_key();
+        }
+    });
+
+    document.get_table_like(&mut cx, "socket_proxies", |cx, proxies| {
+        for (endpoint, item) in proxies.iter() {
+            cx.push_key(endpoint);
+            if let Some(proxy) = item.as_str_or_warn(cx) {
+                match proxy.parse() {
+                    Ok(proxy) => {
+                        result.socket_proxies.insert(endpoint.to_owned(), proxy);
+                    }
+                    Err(_) => cx.unsupported_value(proxy.to_owned()),
+                }
+            }
+            cx.pop_key();
+        }
+    });
+
+    document.get_table_like(&mut cx, "network", |cx, network| {
+        if let Some(value) = network.get_float_like(cx, "timeout") {
+            result.network.timeout = Some(value).filter(|value| value.is_finite() && *value > 0.0);
+        }
+        if let Some(value) = network.get_integer(cx, "retries") {
+            result.network.retries = value.clamp(0, 10) as u32;
+        }
+        if let Some(value) = network.get_bool(cx, "offline") {
+            result.network.offline = value;
+        }
+    });
+
+    document.get_table_like(&mut cx, "shortcuts", |cx, shortcuts| {
+        for (id, item) in shortcuts.iter() {
+            let Some(action) = Action::from_id(id) else {
+                cx.unsupported_value(id.to_owned());
+                continue;
+            };
+            cx.push_key(id);
+            if let Some(shortcut) = item.as_str_or_warn(cx) {
+                if shortcut.is_empty() {
+                    result.shortcuts.insert(action, None);
+                } else if let Some(parsed) = parse_shortcut(shortcut) {
+                    result.shortcuts.insert(action, Some(parsed));
+                } else {
+                    cx.unsupported_value(shortcut.to_owned());
+                }
+            }
+            cx.pop_key();
+        }
+    });
+
     ParseDetails {
         warnings: cx.warnings,
         result: DocumentHolder::new(result, document),
@@ -88,12 +239,20 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli::GameModePreference;
+    use crate::cli::{FocusLossPreference, GameModePreference};
     use crate::gui::ThemePreference;
     use crate::log::FilenamePattern;
-    use crate::preferences::{storage::StorageBackend, LogPreferences, StoragePreferences};
+    use crate::preferences::sites::{DevicePermission, StorageLimit};
+    use crate::preferences::{
+        storage::StorageBackend, AirPreferences, LogPreferences, NetworkPreferences,
+        StoragePreferences,
+    };
     use fluent_templates::loader::langid;
+    use ruffle_render_wgpu::backend::BlendSpace;
     use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use url::Url;
 
     #[test]
     fn invalid_toml() {
@@ -301,6 +460,36 @@ mod tests {
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
     }
 
+    #[test]
+    fn audio_enhancements() {
+        let result = read_preferences("audio_enhancements = 1");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                audio_enhancements: false,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "integer",
+                path: "audio_enhancements".to_string()
+            }],
+            result.warnings
+        );
+
+        let result = read_preferences("audio_enhancements = true");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                audio_enhancements: true,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
     #[test]
     fn volume() {
         let result = read_preferences("volume = \"0.5\"");
@@ -417,6 +606,63 @@ mod tests {
             &SavedGlobalPreferences {
                 log: LogPreferences {
                     filename_pattern: FilenamePattern::WithTimestamp,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn log_crash_reports() {
+        let result = read_preferences("log = {crash_reports = \"yes\"}");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "string",
+                path: "log.crash_reports".to_string()
+            }],
+            result.warnings
+        );
+
+        let result = read_preferences("[log]\ncrash_reports = true");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                log: LogPreferences {
+                    crash_reports: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn log_movie_traces() {
+        let result = read_preferences("[log]\nmovie_trace_files = 1");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "integer",
+                path: "log.movie_trace_files".to_string()
+            }],
+            result.warnings
+        );
+
+        let result =
+            read_preferences("[log]\nmovie_trace_files = true\nmirror_movie_traces = false");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                log: LogPreferences {
+                    movie_trace_files: true,
+                    mirror_movie_traces: false,
+                    ..Default::default()
                 },
                 ..Default::default()
             },
@@ -489,6 +735,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn air_sandbox_directory() {
+        let result = read_preferences("air = {sandbox_directory = 5}");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "string",
+                actual: "integer",
+                path: "air.sandbox_directory".to_string()
+            }],
+            result.warnings
+        );
+
+        let result = read_preferences("[air]\nsandbox_directory = \"/home/user/air\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                air: AirPreferences {
+                    sandbox_directory: Some(PathBuf::from("/home/user/air")),
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
     #[test]
     fn recent_limit() {
         let result = read_preferences("recent_limit = \"1\"");
@@ -536,6 +808,171 @@ mod tests {
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
     }
 
+    #[test]
+    fn gpu_memory_budget() {
+        let result = read_preferences("gpu_memory_budget = 512");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                gpu_memory_budget: Some(512),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("gpu_memory_budget = -1");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("gpu_memory_budget = \"lots\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "integer",
+                actual: "string",
+                path: "gpu_memory_budget".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn blend_space() {
+        let result = read_preferences("blend_space = \"linear\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                blend_space: BlendSpace::Linear,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("blend_space = \"gamma\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "gamma".to_string(),
+                path: "blend_space".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn morph_shape_cache_size() {
+        let result = read_preferences("morph_shape_cache_size = 128");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                morph_shape_cache_size: Some(128),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("morph_shape_cache_size = 0");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn hardware_acceleration() {
+        let result = read_preferences("hardware_acceleration = false");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                hardware_acceleration: false,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn coalesce_mouse_moves() {
+        let result = read_preferences("coalesce_mouse_moves = false");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                coalesce_mouse_moves: false,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn relaxed_security() {
+        let result = read_preferences("relaxed_security = true");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                relaxed_security: true,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn vsync() {
+        let result = read_preferences("vsync = false");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                vsync: false,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("vsync = \"off\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "string",
+                path: "vsync".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn mouse_wheel_scale() {
+        let result = read_preferences("mouse_wheel_scale = 3");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                mouse_wheel_scale: 3.0,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("mouse_wheel_scale = 100.0");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                mouse_wheel_scale: 10.0,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("mouse_wheel_scale = \"fast\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "float or integer",
+                actual: "string",
+                path: "mouse_wheel_scale".to_string()
+            }],
+            result.warnings
+        );
+    }
+
     #[test]
     fn theme() {
         let result = read_preferences("theme = \"light\"");
@@ -647,4 +1084,225 @@ mod tests {
             result.warnings
         );
     }
+
+    #[test]
+    fn focus_loss() {
+        let result = read_preferences("focus_loss = \"pause\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                focus_loss_preference: FocusLossPreference::Pause,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("focus_loss = \"throttle\"\nbackground_frame_rate = 120");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                focus_loss_preference: FocusLossPreference::Throttle,
+                background_frame_rate: 60.0,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("focus_loss = \"sleep\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "sleep".to_string(),
+                path: "focus_loss".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn sites() {
+        let result = read_preferences(
+            "[sites.\"example.com\"]\nstorage_limit = \"10mb\"\ncamera = \"deny\"\n\n[sites.localhost]\nmicrophone = \"allow\"\n",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                sites: BTreeMap::from([
+                    (
+                        "example.com".to_string(),
+                        SitePreferences {
+                            storage_limit: StorageLimit::Megabytes10,
+                            camera: DevicePermission::Deny,
+                            microphone: DevicePermission::Ask,
+                        }
+                    ),
+                    (
+                        "localhost".to_string(),
+                        SitePreferences {
+                            microphone: DevicePermission::Allow,
+                            ..Default::default()
+                        }
+                    ),
+                ]),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("[sites.localhost]\nstorage_limit = \"lots\"\n");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                sites: BTreeMap::from([("localhost".to_string(), SitePreferences::default())]),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "lots".to_string(),
+                path: "sites.localhost.storage_limit".to_string(),
+            }],
+            result.warnings
+        );
+
+        let result = read_preferences("sites = { localhost = true }");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "table",
+                actual: "boolean",
+                path: "sites.localhost".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn movies() {
+        let result = read_preferences(
+            "[movies.\"file:///games/game.swf\"]\nspoof_url = \"https://example.com/game.swf\"\nbase_url = \"https://example.com/assets/\"\n",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                movies: BTreeMap::from([(
+                    "file:///games/game.swf".to_string(),
+                    MoviePreferences {
+                        spoof_url: Some(Url::parse("https://example.com/game.swf").unwrap()),
+                        base_url: Some(Url::parse("https://example.com/assets/").unwrap()),
+                    }
+                )]),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result =
+            read_preferences("[movies.\"file:///games/game.swf\"]\nspoof_url = \"not a url\"\n");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                movies: BTreeMap::from([(
+                    "file:///games/game.swf".to_string(),
+                    MoviePreferences::default()
+                )]),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "not a url".to_string(),
+                path: "movies.file:///games/game.swf.spoof_url".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn socket_proxies() {
+        let result = read_preferences(
+            "[socket_proxies]\n\"example.com:843\" = \"wss://proxy.example.com/\"\n\"localhost:1234\" = \"not a url\"\n",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                socket_proxies: BTreeMap::from([(
+                    "example.com:843".to_string(),
+                    Url::parse("wss://proxy.example.com/").unwrap()
+                )]),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "not a url".to_string(),
+                path: "socket_proxies.localhost:1234".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn network() {
+        let result = read_preferences("[network]\ntimeout = 15\nretries = 50\noffline = true\n");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                network: NetworkPreferences {
+                    timeout: Some(15.0),
+                    retries: 10,
+                    offline: true,
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("[network]\ntimeout = 0\nretries = \"many\"\n");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "integer",
+                actual: "string",
+                path: "network.retries".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn shortcuts() {
+        let result = read_preferences(
+            "[shortcuts]\ntoggle_playing = \"ctrl+shift+Space\"\nfullscreen = \"\"\nscreenshot = \"Hyper+S\"\nteleport = \"T\"\n",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                shortcuts: BTreeMap::from([
+                    (
+                        Action::TogglePlaying,
+                        Some(egui::KeyboardShortcut::new(
+                            egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT),
+                            egui::Key::Space
+                        ))
+                    ),
+                    (Action::Fullscreen, None),
+                ]),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![
+                ParseWarning::UnsupportedValue {
+                    value: "Hyper+S".to_string(),
+                    path: "shortcuts.screenshot".to_string(),
+                },
+                ParseWarning::UnsupportedValue {
+                    value: "teleport".to_string(),
+                    path: "shortcuts".to_string(),
+                },
+            ],
+            result.warnings
+        );
+    }
 }
@@ -0,0 +1,108 @@
+use std::str::FromStr;
+use url::Url;
+
+/// The settings a user chose for a single site through the Flash Player Settings panel.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SitePreferences {
+    pub storage_limit: StorageLimit,
+    pub camera: DevicePermission,
+    pub microphone: DevicePermission,
+}
+
+/// How much data a site may keep in local shared objects.
+///
+/// These are the steps of the slider in Flash Player's Local Storage panel.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StorageLimit {
+    None,
+    Kilobytes10,
+    #[default]
+    Kilobytes100,
+    Megabytes1,
+    Megabytes10,
+    Unlimited,
+}
+
+impl StorageLimit {
+    pub const ALL: [StorageLimit; 6] = [
+        StorageLimit::None,
+        StorageLimit::Kilobytes10,
+        StorageLimit::Kilobytes100,
+        StorageLimit::Megabytes1,
+        StorageLimit::Megabytes10,
+        StorageLimit::Unlimited,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StorageLimit::None => "none",
+            StorageLimit::Kilobytes10 => "10kb",
+            StorageLimit::Kilobytes100 => "100kb",
+            StorageLimit::Megabytes1 => "1mb",
+            StorageLimit::Megabytes10 => "10mb",
+            StorageLimit::Unlimited => "unlimited",
+        }
+    }
+
+    /// The maximum number of bytes the site may store, or `None` if there is no limit.
+    pub fn bytes(&self) -> Option<u64> {
+        match self {
+            StorageLimit::None => Some(0),
+            StorageLimit::Kilobytes10 => Some(10 * 1024),
+            StorageLimit::Kilobytes100 => Some(100 * 1024),
+            StorageLimit::Megabytes1 => Some(1024 * 1024),
+            StorageLimit::Megabytes10 => Some(10 * 1024 * 1024),
+            StorageLimit::Unlimited => None,
+        }
+    }
+}
+
+impl FromStr for StorageLimit {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StorageLimit::ALL
+            .into_iter()
+            .find(|limit| limit.as_str() == s)
+            .ok_or(())
+    }
+}
+
+/// Whether a site may use the camera or microphone.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DevicePermission {
+    #[default]
+    Ask,
+    Allow,
+    Deny,
+}
+
+impl DevicePermission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DevicePermission::Ask => "ask",
+            DevicePermission::Allow => "allow",
+            DevicePermission::Deny => "deny",
+        }
+    }
+}
+
+impl FromStr for DevicePermission {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ask" => Ok(DevicePermission::Ask),
+            "allow" => Ok(DevicePermission::Allow),
+            "deny" => Ok(DevicePermission::Deny),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The name that settings for the movie at the given URL are stored under.
+///
+/// Like Flash Player, all local movies share the settings of `localhost`.
+pub fn site_for_url(url: &Url) -> String {
+    url.host_str().unwrap_or("localhost").to_owned()
+}
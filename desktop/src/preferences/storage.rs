@@ -1,4 +1,5 @@
 use crate::player::LaunchOptions;
+use crate::preferences::GlobalPreferences;
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_frontend_utils::backends::storage::DiskStorageBackend;
 use std::str::FromStr;
@@ -33,9 +34,21 @@ impl StorageBackend {
     pub fn create_backend(
         &self,
         opt: &LaunchOptions,
+        preferences: &GlobalPreferences,
     ) -> Box<dyn ruffle_core::backend::storage::StorageBackend> {
         match self {
-            StorageBackend::Disk => Box::new(DiskStorageBackend::new(opt.save_directory.clone())),
+            StorageBackend::Disk => {
+                let preferences = preferences.clone();
+                Box::new(
+                    DiskStorageBackend::new(opt.save_directory.clone()).with_quota(move |site| {
+                        preferences
+                            .site_preferences(site)
+                            .storage_limit
+                            .bytes()
+                            .map(|bytes| bytes as usize)
+                    }),
+                )
+            }
             StorageBackend::Memory => Box::new(MemoryStorageBackend::new()),
         }
     }
@@ -1,12 +1,18 @@
-use crate::cli::GameModePreference;
-use crate::gui::ThemePreference;
+use crate::cli::{FocusLossPreference, GameModePreference};
+use crate::gui::{shortcut_to_string, Action, ThemePreference};
 use crate::log::FilenamePattern;
+use crate::preferences::movies::MoviePreferences;
+use crate::preferences::sites::SitePreferences;
 use crate::preferences::storage::StorageBackend;
 use crate::preferences::{GlobalPreferencesWatchers, SavedGlobalPreferences};
+use egui::KeyboardShortcut;
 use ruffle_frontend_utils::parse::DocumentHolder;
+use ruffle_render_wgpu::backend::BlendSpace;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
-use toml_edit::value;
+use std::path::PathBuf;
+use toml_edit::{table, value, Item, Table};
 use unic_langid::LanguageIdentifier;
+use url::Url;
 
 pub struct PreferencesWriter<'a>(
     &'a mut DocumentHolder<SavedGlobalPreferences>,
@@ -36,6 +42,49 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_gpu_memory_budget(&mut self, budget: Option<u64>) {
+        self.0.edit(|values, toml_document| {
+            if let Some(budget) = budget {
+                toml_document["gpu_memory_budget"] = value(budget as i64);
+            } else {
+                toml_document.remove("gpu_memory_budget");
+            }
+            values.gpu_memory_budget = budget;
+        })
+    }
+
+    pub fn set_blend_space(&mut self, blend_space: BlendSpace) {
+        self.0.edit(|values, toml_document| {
+            toml_document["blend_space"] = value(blend_space.as_str());
+            values.blend_space = blend_space;
+        })
+    }
+
+    pub fn set_morph_shape_cache_size(&mut self, size: Option<usize>) {
+        self.0.edit(|values, toml_document| {
+            if let Some(size) = size {
+                toml_document["morph_shape_cache_size"] = value(size as i64);
+            } else {
+                toml_document.remove("morph_shape_cache_size");
+            }
+            values.morph_shape_cache_size = size;
+        })
+    }
+
+    pub fn set_hardware_acceleration(&mut self, enabled: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["hardware_acceleration"] = value(enabled);
+            values.hardware_acceleration = enabled;
+        })
+    }
+
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["vsync"] = value(vsync);
+            values.vsync = vsync;
+        })
+    }
+
     pub fn set_language(&mut self, language: LanguageIdentifier) {
         self.0.edit(|values, toml_document| {
             toml_document["language"] = value(language.to_string());
@@ -61,6 +110,13 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_audio_enhancements(&mut self, enabled: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["audio_enhancements"] = value(enabled);
+            values.audio_enhancements = enabled;
+        })
+    }
+
     pub fn set_volume(&mut self, volume: f32) {
         self.0.edit(|values, toml_document| {
             toml_document["volume"] = value(volume as f64);
@@ -82,6 +138,27 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_crash_reports(&mut self, enabled: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["log"]["crash_reports"] = value(enabled);
+            values.log.crash_reports = enabled;
+        })
+    }
+
+    pub fn set_movie_trace_files(&mut self, enabled: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["log"]["movie_trace_files"] = value(enabled);
+            values.log.movie_trace_files = enabled;
+        })
+    }
+
+    pub fn set_mirror_movie_traces(&mut self, enabled: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["log"]["mirror_movie_traces"] = value(enabled);
+            values.log.mirror_movie_traces = enabled;
+        })
+    }
+
     pub fn set_storage_backend(&mut self, backend: StorageBackend) {
         self.0.edit(|values, toml_document| {
             toml_document["storage"]["backend"] = value(backend.as_str());
@@ -89,6 +166,50 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_air_sandbox_directory(&mut self, directory: Option<PathBuf>) {
+        self.0.edit(|values, toml_document| {
+            if let Some(directory) = &directory {
+                toml_document["air"]["sandbox_directory"] =
+                    value(directory.to_string_lossy().as_ref());
+            } else if let Some(air) = toml_document
+                .get_mut("air")
+                .and_then(|air| air.as_table_like_mut())
+            {
+                air.remove("sandbox_directory");
+            }
+            values.air.sandbox_directory = directory;
+        })
+    }
+
+    /// Limits how many seconds network requests may take, or removes the limit if `None`.
+    pub fn set_network_timeout(&mut self, timeout: Option<f64>) {
+        self.0.edit(|values, toml_document| {
+            if let Some(timeout) = timeout {
+                toml_document["network"]["timeout"] = value(timeout);
+            } else if let Some(network) = toml_document
+                .get_mut("network")
+                .and_then(|network| network.as_table_like_mut())
+            {
+                network.remove("timeout");
+            }
+            values.network.timeout = timeout;
+        })
+    }
+
+    pub fn set_network_retries(&mut self, retries: u32) {
+        self.0.edit(|values, toml_document| {
+            toml_document["network"]["retries"] = value(retries as i64);
+            values.network.retries = retries;
+        })
+    }
+
+    pub fn set_offline(&mut self, offline: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["network"]["offline"] = value(offline);
+            values.network.offline = offline;
+        })
+    }
+
     pub fn set_recent_limit(&mut self, limit: usize) {
         self.0.edit(|values, toml_document| {
             toml_document["recent_limit"] = value(limit as i64);
@@ -96,6 +217,27 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_mouse_wheel_scale(&mut self, scale: f64) {
+        self.0.edit(|values, toml_document| {
+            toml_document["mouse_wheel_scale"] = value(scale);
+            values.mouse_wheel_scale = scale;
+        })
+    }
+
+    pub fn set_coalesce_mouse_moves(&mut self, enabled: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["coalesce_mouse_moves"] = value(enabled);
+            values.coalesce_mouse_moves = enabled;
+        })
+    }
+
+    pub fn set_relaxed_security(&mut self, enabled: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["relaxed_security"] = value(enabled);
+            values.relaxed_security = enabled;
+        })
+    }
+
     pub fn set_theme_preference(&mut self, theme_preference: ThemePreference) {
         self.0.edit(|values, toml_document| {
             if let Some(theme_preference) = theme_preference.as_str() {
@@ -110,6 +252,109 @@ impl<'a> PreferencesWriter<'a> {
         }
     }
 
+    pub fn set_site_preferences(&mut self, site: &str, preferences: SitePreferences) {
+        self.0.edit(|values, toml_document| {
+            if !toml_document.contains_key("sites") {
+                let mut sites = Table::new();
+                sites.set_implicit(true);
+                toml_document["sites"] = Item::Table(sites);
+            }
+            let site_table = &mut toml_document["sites"][site];
+            if site_table.is_none() {
+                *site_table = table();
+            }
+            site_table["storage_limit"] = value(preferences.storage_limit.as_str());
+            site_table["camera"] = value(preferences.camera.as_str());
+            site_table["microphone"] = value(preferences.microphone.as_str());
+            values.sites.insert(site.to_owned(), preferences);
+        })
+    }
+
+    /// Tunnels socket connections to `endpoint` (`host:port`) through a WebSocket proxy,
+    /// or connects directly if `proxy` is `None`.
+    pub fn set_socket_proxy(&mut self, endpoint: &str, proxy: Option<Url>) {
+        self.0.edit(|values, toml_document| {
+            let Some(proxy) = proxy else {
+                if let Some(proxies) = toml_document
+                    .get_mut("socket_proxies")
+                    .and_then(Item::as_table_like_mut)
+                {
+                    proxies.remove(endpoint);
+                }
+                values.socket_proxies.remove(endpoint);
+                return;
+            };
+
+            if !toml_document.contains_key("socket_proxies") {
+                toml_document["socket_proxies"] = table();
+            }
+            toml_document["socket_proxies"][endpoint] = value(proxy.as_str());
+            values.socket_proxies.insert(endpoint.to_owned(), proxy);
+        })
+    }
+
+    /// Changes the shortcut of an action, where `None` leaves it without a shortcut.
+    pub fn set_shortcut(&mut self, action: Action, shortcut: Option<KeyboardShortcut>) {
+        self.0.edit(|values, toml_document| {
+            if !toml_document.contains_key("shortcuts") {
+                toml_document["shortcuts"] = table();
+            }
+            toml_document["shortcuts"][action.id()] =
+                value(shortcut.map(shortcut_to_string).unwrap_or_default());
+            values.shortcuts.insert(action, shortcut);
+        })
+    }
+
+    /// Goes back to the default shortcut of an action.
+    pub fn reset_shortcut(&mut self, action: Action) {
+        self.0.edit(|values, toml_document| {
+            if let Some(shortcuts) = toml_document
+                .get_mut("shortcuts")
+                .and_then(Item::as_table_like_mut)
+            {
+                shortcuts.remove(action.id());
+            }
+            values.shortcuts.remove(&action);
+        })
+    }
+
+    /// Remembers options for the movie at the given URL, or forgets them if they're all unset.
+    pub fn set_movie_preferences(&mut self, url: &Url, preferences: MoviePreferences) {
+        self.0.edit(|values, toml_document| {
+            if preferences.is_empty() {
+                if let Some(movies) = toml_document
+                    .get_mut("movies")
+                    .and_then(Item::as_table_like_mut)
+                {
+                    movies.remove(url.as_str());
+                }
+                values.movies.remove(url.as_str());
+                return;
+            }
+
+            if !toml_document.contains_key("movies") {
+                let mut movies = Table::new();
+                movies.set_implicit(true);
+                toml_document["movies"] = Item::Table(movies);
+            }
+            let movie_table = &mut toml_document["movies"][url.as_str()];
+            if movie_table.is_none() {
+                *movie_table = table();
+            }
+            for (key, url) in [
+                ("spoof_url", &preferences.spoof_url),
+                ("base_url", &preferences.base_url),
+            ] {
+                if let Some(url) = url {
+                    movie_table[key] = value(url.as_str());
+                } else if let Some(movie_table) = movie_table.as_table_like_mut() {
+                    movie_table.remove(key);
+                }
+            }
+            values.movies.insert(url.to_string(), preferences);
+        })
+    }
+
     pub fn set_gamemode_preference(&mut self, gamemode_preference: GameModePreference) {
         self.0.edit(|values, toml_document| {
             if let Some(gamemode_preference) = gamemode_preference.as_str() {
@@ -120,12 +365,31 @@ impl<'a> PreferencesWriter<'a> {
             values.gamemode_preference = gamemode_preference;
         });
     }
+
+    pub fn set_focus_loss_preference(&mut self, focus_loss_preference: FocusLossPreference) {
+        self.0.edit(|values, toml_document| {
+            if let Some(focus_loss_preference) = focus_loss_preference.as_str() {
+                toml_document["focus_loss"] = value(focus_loss_preference);
+            } else {
+                toml_document.remove("focus_loss");
+            }
+            values.focus_loss_preference = focus_loss_preference;
+        });
+    }
+
+    pub fn set_background_frame_rate(&mut self, frame_rate: f64) {
+        self.0.edit(|values, toml_document| {
+            toml_document["background_frame_rate"] = value(frame_rate);
+            values.background_frame_rate = frame_rate;
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::preferences::read::read_preferences;
+    use crate::preferences::sites::{DevicePermission, StorageLimit};
     use fluent_templates::loader::langid;
 
     ruffle_frontend_utils::define_serialization_test_helpers!(
@@ -164,6 +428,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_gpu_memory_budget() {
+        test(
+            "",
+            |writer| writer.set_gpu_memory_budget(Some(256)),
+            "gpu_memory_budget = 256\n",
+        );
+
+        test(
+            "gpu_memory_budget = 256",
+            |writer| writer.set_gpu_memory_budget(None),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_blend_space() {
+        test(
+            "",
+            |writer| writer.set_blend_space(BlendSpace::Linear),
+            "blend_space = \"linear\"\n",
+        );
+
+        test(
+            "blend_space = \"linear\"",
+            |writer| writer.set_blend_space(BlendSpace::Flash),
+            "blend_space = \"flash\"\n",
+        );
+    }
+
+    #[test]
+    fn set_morph_shape_cache_size() {
+        test(
+            "",
+            |writer| writer.set_morph_shape_cache_size(Some(128)),
+            "morph_shape_cache_size = 128\n",
+        );
+
+        test(
+            "morph_shape_cache_size = 128",
+            |writer| writer.set_morph_shape_cache_size(None),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_hardware_acceleration() {
+        test(
+            "",
+            |writer| writer.set_hardware_acceleration(false),
+            "hardware_acceleration = false\n",
+        );
+        test(
+            "hardware_acceleration = false",
+            |writer| writer.set_hardware_acceleration(true),
+            "hardware_acceleration = true\n",
+        );
+    }
+
+    #[test]
+    fn set_vsync() {
+        test("", |writer| writer.set_vsync(false), "vsync = false\n");
+        test(
+            "vsync = false",
+            |writer| writer.set_vsync(true),
+            "vsync = true\n",
+        );
+    }
+
     #[test]
     fn set_language() {
         test(
@@ -209,6 +542,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_audio_enhancements() {
+        test(
+            "",
+            |writer| writer.set_audio_enhancements(true),
+            "audio_enhancements = true\n",
+        );
+        test(
+            "audio_enhancements = true",
+            |writer| writer.set_audio_enhancements(false),
+            "audio_enhancements = false\n",
+        );
+    }
+
     #[test]
     fn set_enable_openh264() {
         test(
@@ -242,6 +589,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_crash_reports() {
+        test(
+            "",
+            |writer| writer.set_crash_reports(true),
+            "log = { crash_reports = true }\n",
+        );
+        test(
+            "[log]\nfilename_pattern = \"single_file\"\ncrash_reports = true\n",
+            |writer| writer.set_crash_reports(false),
+            "[log]\nfilename_pattern = \"single_file\"\ncrash_reports = false\n",
+        );
+    }
+
+    #[test]
+    fn set_movie_traces() {
+        test(
+            "",
+            |writer| writer.set_movie_trace_files(true),
+            "log = { movie_trace_files = true }\n",
+        );
+        test(
+            "[log]\nmovie_trace_files = true\n",
+            |writer| writer.set_mirror_movie_traces(false),
+            "[log]\nmovie_trace_files = true\nmirror_movie_traces = false\n",
+        );
+    }
+
     #[test]
     fn set_storage_backend() {
         test(
@@ -261,6 +636,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_air_sandbox_directory() {
+        test(
+            "",
+            |writer| writer.set_air_sandbox_directory(Some(PathBuf::from("/tmp/air"))),
+            "air = { sandbox_directory = \"/tmp/air\" }\n",
+        );
+        test(
+            "[air]\nsandbox_directory = \"/tmp/air\"\n",
+            |writer| writer.set_air_sandbox_directory(None),
+            "[air]\n",
+        );
+    }
+
     #[test]
     fn set_recent_limit() {
         test(
@@ -275,6 +664,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_mouse_wheel_scale() {
+        test(
+            "",
+            |writer| writer.set_mouse_wheel_scale(2.5),
+            "mouse_wheel_scale = 2.5\n",
+        );
+    }
+
+    #[test]
+    fn set_coalesce_mouse_moves() {
+        test(
+            "",
+            |writer| writer.set_coalesce_mouse_moves(false),
+            "coalesce_mouse_moves = false\n",
+        );
+        test(
+            "coalesce_mouse_moves = false",
+            |writer| writer.set_coalesce_mouse_moves(true),
+            "coalesce_mouse_moves = true\n",
+        );
+    }
+
+    #[test]
+    fn set_relaxed_security() {
+        test(
+            "",
+            |writer| writer.set_relaxed_security(true),
+            "relaxed_security = true\n",
+        );
+        test(
+            "relaxed_security = true",
+            |writer| writer.set_relaxed_security(false),
+            "relaxed_security = false\n",
+        );
+    }
+
     #[test]
     fn set_theme() {
         test(
@@ -302,4 +728,150 @@ mod tests {
             "",
         );
     }
+
+    #[test]
+    fn set_focus_loss() {
+        test(
+            "",
+            |writer| writer.set_focus_loss_preference(FocusLossPreference::Pause),
+            "focus_loss = \"pause\"\n",
+        );
+        test(
+            "focus_loss = \"throttle\"",
+            |writer| writer.set_focus_loss_preference(FocusLossPreference::KeepRunning),
+            "",
+        );
+        test(
+            "",
+            |writer| writer.set_background_frame_rate(10.0),
+            "background_frame_rate = 10.0\n",
+        );
+    }
+
+    #[test]
+    fn set_site_preferences() {
+        test(
+            "",
+            |writer| {
+                writer.set_site_preferences(
+                    "example.com",
+                    SitePreferences {
+                        storage_limit: StorageLimit::Megabytes1,
+                        camera: DevicePermission::Allow,
+                        microphone: DevicePermission::Deny,
+                    },
+                )
+            },
+            "[sites.\"example.com\"]\nstorage_limit = \"1mb\"\ncamera = \"allow\"\nmicrophone = \"deny\"\n",
+        );
+        test(
+            "[sites.localhost]\nstorage_limit = \"none\"\ncamera = \"ask\"\nmicrophone = \"ask\"\n",
+            |writer| {
+                writer.set_site_preferences(
+                    "localhost",
+                    SitePreferences {
+                        storage_limit: StorageLimit::Unlimited,
+                        ..Default::default()
+                    },
+                )
+            },
+            "[sites.localhost]\nstorage_limit = \"unlimited\"\ncamera = \"ask\"\nmicrophone = \"ask\"\n",
+        );
+    }
+
+    #[test]
+    fn set_movie_preferences() {
+        let movie = Url::parse("file:///games/game.swf").unwrap();
+        test(
+            "",
+            |writer| {
+                writer.set_movie_preferences(
+                    &movie,
+                    MoviePreferences {
+                        spoof_url: Some(Url::parse("https://example.com/game.swf").unwrap()),
+                        base_url: None,
+                    },
+                )
+            },
+            "[movies.\"file:///games/game.swf\"]\nspoof_url = \"https://example.com/game.swf\"\n",
+        );
+        test(
+            "[movies.\"file:///games/game.swf\"]\nspoof_url = \"https://example.com/game.swf\"\n",
+            |writer| {
+                writer.set_movie_preferences(
+                    &movie,
+                    MoviePreferences {
+                        spoof_url: None,
+                        base_url: Some(Url::parse("https://example.com/").unwrap()),
+                    },
+                )
+            },
+            "[movies.\"file:///games/game.swf\"]\nbase_url = \"https://example.com/\"\n",
+        );
+        test(
+            "[movies.\"file:///games/game.swf\"]\nspoof_url = \"https://example.com/game.swf\"\n",
+            |writer| writer.set_movie_preferences(&movie, MoviePreferences::default()),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_socket_proxy() {
+        test(
+            "",
+            |writer| {
+                writer.set_socket_proxy(
+                    "example.com:843",
+                    Some(Url::parse("wss://proxy.example.com/").unwrap()),
+                )
+            },
+            "[socket_proxies]\n\"example.com:843\" = \"wss://proxy.example.com/\"\n",
+        );
+        test(
+            "[socket_proxies]\n\"example.com:843\" = \"wss://proxy.example.com/\"\n",
+            |writer| writer.set_socket_proxy("example.com:843", None),
+            "[socket_proxies]\n",
+        );
+    }
+
+    #[test]
+    fn set_network() {
+        test(
+            "",
+            |writer| writer.set_network_timeout(Some(10.0)),
+            "network = { timeout = 10.0 }\n",
+        );
+        test(
+            "[network]\ntimeout = 10.0\n",
+            |writer| {
+                writer.set_network_timeout(None);
+                writer.set_network_retries(0);
+                writer.set_offline(true);
+            },
+            "[network]\nretries = 0\noffline = true\n",
+        );
+    }
+
+    #[test]
+    fn set_shortcut() {
+        test(
+            "",
+            |writer| {
+                writer.set_shortcut(
+                    Action::TogglePlaying,
+                    Some(KeyboardShortcut::new(
+                        egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT),
+                        egui::Key::Space,
+                    )),
+                );
+                writer.set_shortcut(Action::Fullscreen, None);
+            },
+            "[shortcuts]\ntoggle_playing = \"Ctrl+Shift+Space\"\nfullscreen = \"\"\n",
+        );
+        test(
+            "[shortcuts]\ntoggle_playing = \"Ctrl+Shift+Space\"\nfullscreen = \"\"\n",
+            |writer| writer.reset_shortcut(Action::Fullscreen),
+            "[shortcuts]\ntoggle_playing = \"Ctrl+Shift+Space\"\n",
+        );
+    }
 }
@@ -1,11 +1,15 @@
 use anyhow::{anyhow, Error};
-use gilrs::Button;
-use ruffle_core::events::{GamepadButton, KeyCode, TextControlCode};
+use gilrs::{Axis, Button, EventType, Gamepad, GamepadId};
+use ruffle_core::events::{
+    GamepadButton, KeyCode, KeyLocation as RuffleKeyLocation, TextControlCode,
+};
+use ruffle_core::{GameInputControl, GameInputDevice};
 use std::path::Path;
 use url::Url;
 use winit::dpi::PhysicalSize;
 use winit::event::{KeyEvent, Modifiers};
 use winit::keyboard::{Key, KeyLocation, NamedKey};
+use winit::platform::modifier_supplement::KeyEventExtModifierSupplement;
 use winit::window::Window;
 
 /// Converts a winit event to a Ruffle `TextControlCode`.
@@ -57,13 +61,22 @@ pub fn winit_to_ruffle_text_control(
 
 /// Convert a winit event into a Ruffle `KeyCode`.
 /// Return `KeyCode::Unknown` if there is no matching Flash key code.
-pub fn winit_to_ruffle_key_code(event: &KeyEvent) -> Option<KeyCode> {
+///
+/// `alt_graph` tells whether AltGr is being held down.
+pub fn winit_to_ruffle_key_code(event: &KeyEvent, alt_graph: bool) -> Option<KeyCode> {
     // Note: it would be tempting to use event.key_without_modifiers() here, but FP
     // does not care about keys without modifiers at all, it does its own mapping,
     // so that on English UK, Shift+3 produces 16+163, not 16+51.
+    // AltGr is the exception: on German layouts, AltGr+Q types '@' and produces 81,
+    // so the key is looked up without it.
+    let logical_key = if alt_graph && matches!(event.logical_key, Key::Character(_)) {
+        event.key_without_modifiers()
+    } else {
+        event.logical_key.clone()
+    };
 
     let is_numpad = event.location == KeyLocation::Numpad;
-    let key_code = match event.logical_key.as_ref() {
+    let key_code = match logical_key.as_ref() {
         Key::Named(NamedKey::Backspace) => KeyCode::BACKSPACE,
         Key::Named(NamedKey::Tab) => KeyCode::TAB,
         Key::Named(NamedKey::Enter) => KeyCode::RETURN,
@@ -159,6 +172,16 @@ pub fn winit_to_ruffle_key_code(event: &KeyEvent) -> Option<KeyCode> {
     Some(key_code)
 }
 
+/// Convert the location of a winit key into a Ruffle `KeyLocation`.
+pub fn winit_to_ruffle_key_location(location: KeyLocation) -> RuffleKeyLocation {
+    match location {
+        KeyLocation::Standard => RuffleKeyLocation::Standard,
+        KeyLocation::Left => RuffleKeyLocation::Left,
+        KeyLocation::Right => RuffleKeyLocation::Right,
+        KeyLocation::Numpad => RuffleKeyLocation::NumPad,
+    }
+}
+
 fn alpha_to_ruffle_key_code(char: &str) -> Option<KeyCode> {
     let char = char.chars().next()?;
 
@@ -200,6 +223,86 @@ pub fn gilrs_button_to_gamepad_button(button: Button) -> Option<GamepadButton> {
     }
 }
 
+/// The axes of a gamepad, in the order `GameInput` exposes them.
+const GAME_INPUT_AXES: [Axis; 4] = [
+    Axis::LeftStickX,
+    Axis::LeftStickY,
+    Axis::RightStickX,
+    Axis::RightStickY,
+];
+
+/// The buttons of a gamepad, in the order `GameInput` exposes them after the axes.
+/// This is the order Flash Player uses for XInput controllers.
+const GAME_INPUT_BUTTONS: [Button; 16] = [
+    Button::South,
+    Button::East,
+    Button::West,
+    Button::North,
+    Button::LeftTrigger,
+    Button::RightTrigger,
+    Button::LeftTrigger2,
+    Button::RightTrigger2,
+    Button::Select,
+    Button::Start,
+    Button::LeftThumb,
+    Button::RightThumb,
+    Button::DPadUp,
+    Button::DPadDown,
+    Button::DPadLeft,
+    Button::DPadRight,
+];
+
+pub fn gilrs_game_input_id(id: GamepadId) -> String {
+    usize::from(id).to_string()
+}
+
+/// Describes a gamepad for `flash.ui.GameInput`, with controls named like Flash Player names them.
+pub fn gilrs_game_input_device(id: GamepadId, gamepad: &Gamepad) -> GameInputDevice {
+    let axes = GAME_INPUT_AXES.iter().enumerate().map(|(index, axis)| {
+        let value = gamepad.axis_data(*axis).map_or(0.0, |data| data.value());
+        GameInputControl {
+            id: format!("AXIS_{index}"),
+            min_value: -1.0,
+            max_value: 1.0,
+            value: value as f64,
+        }
+    });
+    let buttons = GAME_INPUT_BUTTONS
+        .iter()
+        .enumerate()
+        .map(|(index, button)| {
+            let value = gamepad
+                .button_data(*button)
+                .map_or(0.0, |data| data.value());
+            GameInputControl {
+                id: format!("BUTTON_{}", GAME_INPUT_AXES.len() + index),
+                min_value: 0.0,
+                max_value: 1.0,
+                value: value as f64,
+            }
+        });
+    GameInputDevice {
+        id: gilrs_game_input_id(id),
+        name: gamepad.name().to_string(),
+        controls: axes.chain(buttons).collect(),
+    }
+}
+
+/// Finds the `GameInput` control that a gamepad event changes, and its new value.
+pub fn gilrs_game_input_control(event: &EventType) -> Option<(usize, f64)> {
+    match *event {
+        EventType::AxisChanged(axis, value, _) => {
+            let index = GAME_INPUT_AXES.iter().position(|a| *a == axis)?;
+            Some((index, value as f64))
+        }
+        EventType::ButtonChanged(button, value, _) => {
+            let index = GAME_INPUT_BUTTONS.iter().position(|b| *b == button)?;
+            Some((GAME_INPUT_AXES.len() + index, value as f64))
+        }
+        _ => None,
+    }
+}
+
 pub fn get_screen_size(window: &Window) -> PhysicalSize<u32> {
     let mut min_x = 0;
     let mut min_y = 0;
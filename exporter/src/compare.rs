@@ -0,0 +1,163 @@
+//! Comparing captured frames against reference images, to find rendering changes.
+//!
+//! References are numbered PNGs in a directory, as written when exporting multiple frames:
+//! `0.png` is the first captured frame, `1.png` the second, and so on.
+
+use anyhow::Result;
+use image::{Rgba, RgbaImage};
+use std::fmt;
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// How a captured frame compares to its reference image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameResult {
+    Matches,
+    MissingReference,
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    Differs {
+        /// The number of pixels with a channel differing more than the tolerance.
+        outliers: usize,
+        /// The largest difference of any channel.
+        max_difference: u8,
+    },
+}
+
+impl fmt::Display for FrameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameResult::Matches => write!(f, "matches"),
+            FrameResult::MissingReference => write!(f, "no reference image"),
+            FrameResult::SizeMismatch { expected, actual } => write!(
+                f,
+                "size is {}x{} instead of {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            FrameResult::Differs {
+                outliers,
+                max_difference,
+            } => write!(
+                f,
+                "{outliers} pixels differ (max difference {max_difference})"
+            ),
+        }
+    }
+}
+
+pub struct ComparisonOptions {
+    /// The largest difference of a channel that still counts as the same.
+    pub tolerance: u8,
+
+    /// How many pixels may differ more than the tolerance before a frame fails.
+    pub max_outliers: usize,
+}
+
+pub struct ComparisonReport {
+    pub frames: Vec<FrameResult>,
+}
+
+impl ComparisonReport {
+    /// The number of frames that don't match their reference.
+    pub fn failures(&self) -> usize {
+        self.frames
+            .iter()
+            .filter(|frame| **frame != FrameResult::Matches)
+            .count()
+    }
+}
+
+/// Compares frames against the reference images in `reference_dir`.
+///
+/// For each frame that doesn't match, the captured image and an image highlighting
+/// the differing pixels in red are written to `report_dir`, along with a `report.txt`
+/// summarizing all frames.
+pub fn compare_frames(
+    frames: &[RgbaImage],
+    reference_dir: &Path,
+    report_dir: &Path,
+    options: &ComparisonOptions,
+) -> Result<ComparisonReport> {
+    create_dir_all(report_dir)?;
+
+    let mut results = Vec::with_capacity(frames.len());
+    for (frame, image) in frames.iter().enumerate() {
+        let reference_path = reference_dir.join(format!("{frame}.png"));
+        let result = match image::open(&reference_path) {
+            Ok(reference) => compare_frame(image, &reference.into_rgba8(), options, |diff| {
+                diff.save(report_dir.join(format!("{frame}-diff.png")))
+            })?,
+            Err(_) => FrameResult::MissingReference,
+        };
+        if result != FrameResult::Matches {
+            image.save(report_dir.join(format!("{frame}.png")))?;
+        }
+        results.push(result);
+    }
+
+    let report = ComparisonReport { frames: results };
+    let mut file = BufWriter::new(File::create(report_dir.join("report.txt"))?);
+    for (frame, result) in report.frames.iter().enumerate() {
+        writeln!(file, "frame {frame}: {result}")?;
+    }
+    writeln!(
+        file,
+        "{} of {} frames differ",
+        report.failures(),
+        report.frames.len()
+    )?;
+    file.flush()?;
+
+    Ok(report)
+}
+
+fn compare_frame(
+    actual: &RgbaImage,
+    expected: &RgbaImage,
+    options: &ComparisonOptions,
+    save_diff: impl FnOnce(RgbaImage) -> image::ImageResult<()>,
+) -> Result<FrameResult> {
+    if actual.dimensions() != expected.dimensions() {
+        return Ok(FrameResult::SizeMismatch {
+            expected: expected.dimensions(),
+            actual: actual.dimensions(),
+        });
+    }
+
+    let mut outliers = 0;
+    let mut max_difference = 0;
+    let mut diff = RgbaImage::new(actual.width(), actual.height());
+    for ((a, e), d) in actual
+        .pixels()
+        .zip(expected.pixels())
+        .zip(diff.pixels_mut())
+    {
+        let difference =
+            a.0.iter()
+                .zip(e.0)
+                .map(|(a, e)| a.abs_diff(e))
+                .max()
+                .unwrap_or_default();
+        max_difference = max_difference.max(difference);
+        *d = if difference > options.tolerance {
+            outliers += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            // Dim the matching pixels, so that the differences stand out.
+            let [r, g, b, _] = a.0;
+            Rgba([r / 4, g / 4, b / 4, 255])
+        };
+    }
+
+    if outliers <= options.max_outliers {
+        return Ok(FrameResult::Matches);
+    }
+    save_diff(diff)?;
+    Ok(FrameResult::Differs {
+        outliers,
+        max_difference,
+    })
+}
@@ -1,3 +1,6 @@
+mod compare;
+
+use crate::compare::{compare_frames, ComparisonOptions, ComparisonReport};
 use anyhow::{anyhow, Result};
 use clap::Parser;
 use image::RgbaImage;
@@ -6,6 +9,7 @@ use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -15,6 +19,7 @@ use std::fs::create_dir_all;
 use std::io::{self, Write};
 use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use walkdir::{DirEntry, WalkDir};
 
@@ -65,6 +70,7 @@ struct Opt {
 
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
+    /// Software renders on the CPU, which works without a GPU.
     #[clap(long, short, default_value = "default")]
     graphics: GraphicsBackend,
 
@@ -76,11 +82,36 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// Compare the captured frames against the numbered PNGs in this directory instead of saving them.
+    /// The output is then the directory that differences are reported to,
+    /// by default the name of the swf + "-report".
+    /// When given a directory of swfs, the references of each swf are expected in a directory
+    /// of the same relative path, without extension.
+    #[clap(long, value_name = "REFERENCE DIR")]
+    compare: Option<PathBuf>,
+
+    /// The largest difference of a color channel that still counts as the same when comparing frames
+    #[clap(long, default_value = "0")]
+    tolerance: u8,
+
+    /// How many pixels of a frame may differ more than the tolerance when comparing frames
+    #[clap(long, default_value = "0")]
+    max_outliers: usize,
+}
+
+impl Opt {
+    fn comparison_options(&self) -> ComparisonOptions {
+        ComparisonOptions {
+            tolerance: self.tolerance,
+            max_outliers: self.max_outliers,
+        }
+    }
 }
 
 /// Captures a screenshot. The resulting image uses straight alpha
 fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+    descriptors: Option<Arc<Descriptors>>,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
@@ -106,12 +137,17 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
+    let builder = PlayerBuilder::new();
+    let builder = if let Some(descriptors) = descriptors {
+        let target = TextureTarget::new(&descriptors.device, (width, height))
+            .map_err(|e| anyhow!(e.to_string()))?;
+        builder.with_renderer(
             WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
         )
+    } else {
+        builder.with_renderer(SoftwareRenderBackend::new(width, height))
+    };
+    let player = builder
         .with_movie(movie)
         .with_viewport_dimensions(width, height, size.scale)
         .build();
@@ -135,11 +171,14 @@ fn take_screenshot(
             let image = || {
                 player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
+                let renderer = player.renderer_mut();
+                if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                {
+                    renderer.capture_frame()
+                } else {
+                    let renderer = renderer.downcast_mut::<SoftwareRenderBackend>().unwrap();
+                    Some(renderer.capture_frame())
+                }
             };
             match catch_unwind(image) {
                 Ok(Some(image)) => result.push(image),
@@ -192,7 +231,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
@@ -202,7 +241,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         result
     });
 
-    if opt.frames > 1 {
+    if opt.frames > 1 && opt.compare.is_none() {
         let _ = create_dir_all(&output);
     }
 
@@ -234,6 +273,35 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
     }
 
+    if let Some(reference_dir) = &opt.compare {
+        let report_dir = opt.output_path.clone().unwrap_or_else(|| {
+            PathBuf::from(format!(
+                "{}-report",
+                opt.swf.file_stem().unwrap().to_string_lossy()
+            ))
+        });
+        let report = compare_frames(
+            &frames,
+            reference_dir,
+            &report_dir,
+            &opt.comparison_options(),
+        )?;
+        let message = format!(
+            "{} of {} frames of {} differ from {}, see {}",
+            report.failures(),
+            report.frames.len(),
+            opt.swf.to_string_lossy(),
+            reference_dir.to_string_lossy(),
+            report_dir.join("report.txt").to_string_lossy()
+        );
+        if let Some(progress) = progress {
+            progress.finish_with_message(message);
+        } else {
+            println!("{message}");
+        }
+        return check_report(&report);
+    }
+
     if frames.len() == 1 {
         let image = frames.first().unwrap();
         if opt.output_path == Some(PathBuf::from("-")) {
@@ -286,7 +354,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -304,6 +372,7 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
         None
     };
 
+    let differing_files = AtomicUsize::new(0);
     files.par_iter().try_for_each(|file| -> Result<()> {
         if let Some(progress) = &progress {
             progress.set_message(
@@ -329,7 +398,18 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                 .unwrap_or_else(|_| file.path())
                 .to_path_buf();
 
-            if frames.len() == 1 {
+            if let Some(reference_dir) = &opt.compare {
+                relative_path.set_extension("");
+                let report = compare_frames(
+                    &frames,
+                    &reference_dir.join(&relative_path),
+                    &output.join(&relative_path),
+                    &opt.comparison_options(),
+                )?;
+                if report.failures() > 0 {
+                    differing_files.fetch_add(1, Ordering::Relaxed);
+                }
+            } else if frames.len() == 1 {
                 let mut destination: PathBuf = (&output).into();
                 relative_path.set_extension("png");
                 destination.push(relative_path);
@@ -353,7 +433,16 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
         Ok(())
     })?;
 
-    let message = if opt.frames == 1 {
+    let differing_files = differing_files.into_inner();
+    let message = if let Some(reference_dir) = &opt.compare {
+        format!(
+            "{} of {} files differ from {}, see {}",
+            differing_files,
+            files.len(),
+            reference_dir.to_string_lossy(),
+            output.to_string_lossy()
+        )
+    } else if opt.frames == 1 {
         format!(
             "Saved first frame of {} files to {}",
             files.len(),
@@ -374,29 +463,48 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
         println!("{message}");
     }
 
+    if differing_files > 0 {
+        return Err(anyhow!(
+            "{differing_files} files differ from the reference images"
+        ));
+    }
     Ok(())
 }
 
+/// Fails if any frame differs from its reference, so that comparisons can be used in scripts.
+fn check_report(report: &ComparisonReport) -> Result<()> {
+    match report.failures() {
+        0 => Ok(()),
+        failures => Err(anyhow!(
+            "{failures} frames differ from the reference images"
+        )),
+    }
+}
+
 fn trace_path(_opt: &Opt) -> Option<&Path> {
     None
 }
 
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-        backends: opt.graphics.into(),
-        ..Default::default()
-    });
-    let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
-        opt.graphics.into(),
-        &instance,
-        None,
-        opt.power.into(),
-        trace_path(&opt),
-    ))
-    .map_err(|e| anyhow!(e.to_string()))?;
-
-    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+    let descriptors = if opt.graphics == GraphicsBackend::Software {
+        None
+    } else {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: opt.graphics.into(),
+            ..Default::default()
+        });
+        let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
+            opt.graphics.into(),
+            &instance,
+            None,
+            opt.power.into(),
+            trace_path(&opt),
+        ))
+        .map_err(|e| anyhow!(e.to_string()))?;
+
+        Some(Arc::new(Descriptors::new(instance, adapter, device, queue)))
+    };
 
     if opt.swf.is_file() {
         capture_single_swf(descriptors, &opt)?;
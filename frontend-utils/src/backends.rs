@@ -1,5 +1,6 @@
 #[cfg(feature = "cpal")]
 pub mod audio;
 pub mod executor;
+pub mod filesystem;
 pub mod navigator;
 pub mod storage;
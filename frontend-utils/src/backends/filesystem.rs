@@ -0,0 +1,214 @@
+use ruffle_core::backend::filesystem::FilesystemBackend;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A filesystem backend that exposes a single directory on disk to AIR content.
+pub struct DiskFilesystemBackend {
+    root: PathBuf,
+}
+
+impl DiskFilesystemBackend {
+    pub fn new(root: PathBuf) -> Self {
+        if !root.exists() {
+            tracing::info!("Creating AIR sandbox dir");
+            if let Err(r) = fs::create_dir_all(&root) {
+                tracing::warn!("Unable to create AIR sandbox dir {}", r);
+            }
+        }
+
+        DiskFilesystemBackend { root }
+    }
+
+    /// Maps a sandbox path onto the disk, refusing anything that could escape the sandbox root.
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        let relative = Path::new(path.trim_start_matches('/'));
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return None;
+        }
+        Some(self.root.join(relative))
+    }
+}
+
+impl FilesystemBackend for DiskFilesystemBackend {
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        let path = self.resolve(path)?;
+        match fs::read(&path) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                tracing::warn!("Unable to read file \"{}\": {:?}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    fn write(&mut self, path: &str, data: &[u8]) -> bool {
+        let Some(path) = self.resolve(path) else {
+            return false;
+        };
+        if let Some(parent_dir) = path.parent() {
+            if let Err(r) = fs::create_dir_all(parent_dir) {
+                tracing::warn!("Unable to create dir {}", r);
+                return false;
+            }
+        }
+        if let Err(r) = fs::write(&path, data) {
+            tracing::warn!("Unable to write file \"{}\": {:?}", path.display(), r);
+            return false;
+        }
+        true
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.resolve(path).is_some_and(|path| path.exists())
+    }
+
+    fn is_directory(&self, path: &str) -> bool {
+        self.resolve(path).is_some_and(|path| path.is_dir())
+    }
+
+    fn size(&self, path: &str) -> Option<u64> {
+        let metadata = fs::metadata(self.resolve(path)?).ok()?;
+        Some(metadata.len())
+    }
+
+    fn list_directory(&self, path: &str) -> Option<Vec<String>> {
+        let entries = fs::read_dir(self.resolve(path)?).ok()?;
+        Some(
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect(),
+        )
+    }
+
+    fn create_directory(&mut self, path: &str) -> bool {
+        self.resolve(path)
+            .is_some_and(|path| fs::create_dir_all(path).is_ok())
+    }
+
+    fn delete(&mut self, path: &str, recursive: bool) -> bool {
+        let Some(path) = self.resolve(path) else {
+            return false;
+        };
+        let result = if path.is_dir() {
+            if recursive {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_dir(path)
+            }
+        } else {
+            fs::remove_file(path)
+        };
+        result.is_ok()
+    }
+
+    fn rename(&mut self, from: &str, to: &str) -> bool {
+        let (Some(from), Some(to)) = (self.resolve(from), self.resolve(to)) else {
+            return false;
+        };
+        if !from.exists() || to.exists() {
+            return false;
+        }
+        if let Some(parent_dir) = to.parent() {
+            if let Err(r) = fs::create_dir_all(parent_dir) {
+                tracing::warn!("Unable to create dir {}", r);
+                return false;
+            }
+        }
+        if let Err(r) = fs::rename(&from, &to) {
+            tracing::warn!(
+                "Unable to move \"{}\" to \"{}\": {:?}",
+                from.display(),
+                to.display(),
+                r
+            );
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiskFilesystemBackend;
+    use ruffle_core::backend::filesystem::FilesystemBackend;
+    use tempfile::tempdir;
+
+    // As with the bundle tests, drop the temporary directory before asserting
+    // so that a failure doesn't leak it.
+
+    #[test]
+    fn write_then_read() {
+        let tmp_dir = tempdir().unwrap();
+        let mut backend = DiskFilesystemBackend::new(tmp_dir.path().to_path_buf());
+        let written = backend.write("app-storage/saves/slot1.dat", &[1, 2, 3]);
+        let read = backend.read("app-storage/saves/slot1.dat");
+        let size = backend.size("app-storage/saves/slot1.dat");
+        let is_directory = backend.is_directory("app-storage/saves");
+        drop(tmp_dir);
+
+        assert!(written);
+        assert_eq!(read, Some(vec![1, 2, 3]));
+        assert_eq!(size, Some(3));
+        assert!(is_directory);
+    }
+
+    #[test]
+    fn list_and_delete() {
+        let tmp_dir = tempdir().unwrap();
+        let mut backend = DiskFilesystemBackend::new(tmp_dir.path().to_path_buf());
+        backend.write("documents/a.txt", b"a");
+        backend.create_directory("documents/folder");
+        let mut listing = backend.list_directory("documents").unwrap_or_default();
+        listing.sort();
+        let non_recursive = backend.delete("documents", false);
+        let recursive = backend.delete("documents", true);
+        let exists = backend.exists("documents");
+        drop(tmp_dir);
+
+        assert_eq!(listing, vec!["a.txt".to_string(), "folder".to_string()]);
+        assert!(!non_recursive);
+        assert!(recursive);
+        assert!(!exists);
+    }
+
+    #[test]
+    fn rename_directory() {
+        let tmp_dir = tempdir().unwrap();
+        let mut backend = DiskFilesystemBackend::new(tmp_dir.path().to_path_buf());
+        backend.write("documents/old/nested/a.txt", b"a");
+        backend.write("documents/taken.txt", b"b");
+        let renamed = backend.rename("documents/old", "app-storage/new");
+        let moved = backend.read("app-storage/new/nested/a.txt");
+        let source_exists = backend.exists("documents/old");
+        let overwritten = backend.rename("app-storage/new", "documents/taken.txt");
+        let escaped = backend.rename("app-storage/new", "../new");
+        drop(tmp_dir);
+
+        assert!(renamed);
+        assert_eq!(moved, Some(b"a".to_vec()));
+        assert!(!source_exists);
+        assert!(!overwritten);
+        assert!(!escaped);
+    }
+
+    #[test]
+    fn cannot_escape_sandbox() {
+        let tmp_dir = tempdir().unwrap();
+        let sandbox = tmp_dir.path().join("sandbox");
+        let _ = std::fs::create_dir(&sandbox);
+        let _ = std::fs::write(tmp_dir.path().join("secret.txt"), "Secret!");
+        let mut backend = DiskFilesystemBackend::new(sandbox);
+        let read = backend.read("../secret.txt");
+        let written = backend.write("documents/../../escaped.txt", b"oops");
+        let escaped = tmp_dir.path().join("escaped.txt").exists();
+        drop(tmp_dir);
+
+        assert_eq!(read, None);
+        assert!(!written);
+        assert!(!escaped);
+    }
+}
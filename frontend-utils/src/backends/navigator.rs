@@ -1,20 +1,27 @@
 mod fetch;
+mod ftp;
 
 use crate::backends::executor::{spawn_tokio, FutureSpawner};
 use crate::backends::navigator::fetch::{Response, ResponseBody};
+use crate::backends::navigator::ftp::fetch_ftp;
 use crate::content::PlayingContent;
 use async_channel::{Receiver, Sender, TryRecvError};
 use async_io::Timer;
+use futures::future::Either;
+use futures::{SinkExt, StreamExt};
 use futures_lite::FutureExt;
-use reqwest::{cookie, header, Proxy};
+use reqwest::{cookie, header, Method, Proxy, StatusCode};
 use ruffle_core::backend::navigator::{
-    async_return, create_fetch_error, get_encoding, ErrorResponse, NavigationMethod,
-    NavigatorBackend, OpenURLMode, OwnedFuture, Request, SocketMode, SuccessResponse,
+    async_return, create_fetch_error, fetch_data_url, get_encoding, ErrorResponse,
+    NavigationMethod, NavigatorBackend, OpenURLMode, OwnedFuture, Request, SocketMode,
+    SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
-use std::collections::HashSet;
+use ruffle_core::swf::Encoding;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io;
 use std::io::ErrorKind;
@@ -24,6 +31,7 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
 use tracing::warn;
 use url::{ParseError, Url};
 
@@ -53,22 +61,86 @@ pub struct ExternalNavigatorBackend<F: FutureSpawner, I: NavigatorInterface> {
 
     socket_allowed: HashSet<String>,
 
+    /// Sockets that a policy file served by their host allows connecting to.
+    /// These don't need to be confirmed by the user.
+    socket_allowed_by_policy: HashSet<String>,
+
     socket_mode: SocketMode,
 
+    /// WebSocket endpoints to tunnel socket connections through, keyed by `host:port`.
+    socket_proxies: HashMap<String, Url>,
+
     upgrade_to_https: bool,
 
     open_url_mode: OpenURLMode,
 
     content: Rc<PlayingContent>,
 
+    /// Files fetched before the movie asked for them, handed out to the next fetch of their URL.
+    /// Files that are still being fetched are `None`.
+    prefetched: Rc<RefCell<HashMap<Url, Option<PrefetchedFile>>>>,
+
+    fetch_policy: FetchPolicy,
+
     interface: I,
 }
 
+/// How fetches over the network behave on slow or unreliable connections.
+///
+/// Requests that still fail reach the movie as usual, e.g. as an `IOErrorEvent`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FetchPolicy {
+    /// How long a request may take before failing, or `None` to wait for as long as it takes.
+    pub timeout: Option<Duration>,
+
+    /// How many times a request is sent again after failing to connect, timing out or getting a
+    /// temporary server error, waiting longer before each retry.
+    /// `POST` requests are never retried, as repeating them may not be safe.
+    pub max_retries: u32,
+
+    /// Whether the network is never used, so that only files that were prefetched or are local
+    /// can be loaded, and everything else fails right away instead of waiting for a timeout.
+    pub offline: bool,
+}
+
+impl FetchPolicy {
+    /// The delay before the given retry (starting from 1), doubling from half a second
+    /// up to 8 seconds.
+    fn retry_delay(&self, retry: u32) -> Duration {
+        Duration::from_millis(500 << retry.saturating_sub(1).min(4))
+    }
+}
+
+/// Whether a server answering with this status may well succeed when asked again.
+fn is_temporary_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// A file fetched by [`NavigatorBackend::prefetch`].
+struct PrefetchedFile {
+    url: String,
+    body: Vec<u8>,
+    headers: Vec<(String, String)>,
+    text_encoding: Option<&'static Encoding>,
+    status: u16,
+    redirected: bool,
+}
+
+/// The largest file kept around after being prefetched.
+const MAX_PREFETCHED_FILE_SIZE: usize = 16 * 1024 * 1024;
+
 impl<F: FutureSpawner, I: NavigatorInterface> ExternalNavigatorBackend<F, I> {
     /// Construct a navigator backend with fetch and async capability.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
-        mut base_url: Url,
+        base_url: Url,
         referer: Option<Url>,
         cookie: Option<String>,
         future_spawner: F,
@@ -77,6 +149,7 @@ impl<F: FutureSpawner, I: NavigatorInterface> ExternalNavigatorBackend<F, I> {
         open_url_mode: OpenURLMode,
         socket_allowed: HashSet<String>,
         socket_mode: SocketMode,
+        socket_proxies: HashMap<String, Url>,
         content: Rc<PlayingContent>,
         interface: I,
     ) -> Self {
@@ -114,24 +187,27 @@ impl<F: FutureSpawner, I: NavigatorInterface> ExternalNavigatorBackend<F, I> {
 
         let client = builder.build().ok().map(Rc::new);
 
-        // Force replace the last segment with empty. //
-
-        if let Ok(mut base_url) = base_url.path_segments_mut() {
-            base_url.pop().pop_if_empty().push("");
-        }
-
         Self {
             future_spawner,
             client,
-            base_url,
+            base_url: base_directory(base_url),
             upgrade_to_https,
             open_url_mode,
             socket_allowed,
+            socket_allowed_by_policy: HashSet::new(),
             socket_mode,
+            socket_proxies,
             content,
+            prefetched: Default::default(),
+            fetch_policy: FetchPolicy::default(),
             interface,
         }
     }
+
+    pub fn with_fetch_policy(mut self, fetch_policy: FetchPolicy) -> Self {
+        self.fetch_policy = fetch_policy;
+        self
+    }
 }
 
 impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
@@ -200,9 +276,53 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
             }
         };
 
+        // The prefetched file is only used once, as it may change on the server afterwards.
+        let is_plain_get =
+            matches!(request.method(), NavigationMethod::Get) && request.body().is_none();
+        if is_plain_get && request.headers().is_empty() {
+            if let Some(Some(file)) = self.prefetched.borrow_mut().remove(&processed_url) {
+                let response: Box<dyn SuccessResponse> = Box::new(Response {
+                    url: file.url,
+                    response_body: ResponseBody::File {
+                        contents: Ok(file.body),
+                        position: 0,
+                    },
+                    headers: file.headers,
+                    text_encoding: file.text_encoding,
+                    status: file.status,
+                    redirected: file.redirected,
+                });
+                return async_return(Ok(response));
+            }
+        }
+
         let client = self.client.clone();
+        let fetch_policy = self.fetch_policy;
 
         match processed_url.scheme() {
+            "data" => fetch_data_url(&processed_url),
+            "ftp" => Box::pin(async move {
+                let contents = spawn_tokio(fetch_ftp(processed_url.clone()))
+                    .await
+                    .map_err(|e| ErrorResponse {
+                        url: processed_url.to_string(),
+                        error: Error::FetchError(e.to_string()),
+                    })?;
+
+                let response: Box<dyn SuccessResponse> = Box::new(Response {
+                    url: processed_url.to_string(),
+                    response_body: ResponseBody::File {
+                        contents: Ok(contents),
+                        position: 0,
+                    },
+                    headers: Vec::new(),
+                    text_encoding: None,
+                    status: 0,
+                    redirected: false,
+                });
+
+                Ok(response)
+            }),
             "file" => {
                 let content = self.content.clone();
                 let interface = self.interface.clone();
@@ -219,7 +339,11 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
 
                     let response: Box<dyn SuccessResponse> = Box::new(Response {
                         url: response_url.to_string(),
-                        response_body: ResponseBody::File(contents),
+                        response_body: ResponseBody::File {
+                            contents,
+                            position: 0,
+                        },
+                        headers: Vec::new(),
                         text_encoding: None,
                         status: 0,
                         redirected: false,
@@ -229,24 +353,70 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
                 })
             }
             _ => Box::pin(async move {
+                if fetch_policy.offline {
+                    return Err(ErrorResponse {
+                        url: processed_url.to_string(),
+                        error: Error::FetchError("Not fetched as Ruffle is offline".to_string()),
+                    });
+                }
+
                 let client = client.ok_or_else(|| ErrorResponse {
                     url: processed_url.to_string(),
                     error: Error::FetchError("Network unavailable".to_string()),
                 })?;
 
-                let mut request_builder = match request.method() {
-                    NavigationMethod::Get => client.get(processed_url.clone()),
-                    NavigationMethod::Post => client.post(processed_url.clone()),
+                let method = match request.method() {
+                    NavigationMethod::Get => Method::GET,
+                    NavigationMethod::Post => Method::POST,
+                    NavigationMethod::Put => Method::PUT,
+                    NavigationMethod::Delete => Method::DELETE,
+                    NavigationMethod::Head => Method::HEAD,
+                    NavigationMethod::Options => Method::OPTIONS,
                 };
-                let (body_data, mime) = request.body().clone().unwrap_or_default();
+                let mut request_builder = client.request(method, processed_url.clone());
                 for (name, val) in request.headers().iter() {
                     request_builder = request_builder.header(name, val);
                 }
-                request_builder = request_builder.header("Content-Type", &mime);
+                if let Some((body_data, mime)) = request.body().clone() {
+                    // A `Content-Type` passed in the request headers takes precedence
+                    // over the request's `contentType`.
+                    let has_content_type = request
+                        .headers()
+                        .keys()
+                        .any(|name| name.eq_ignore_ascii_case("Content-Type"));
+                    if !has_content_type {
+                        request_builder = request_builder.header("Content-Type", &mime);
+                    }
 
-                request_builder = request_builder.body(body_data);
+                    request_builder = request_builder.body(body_data);
+                }
+                if let Some(timeout) = fetch_policy.timeout {
+                    request_builder = request_builder.timeout(timeout);
+                }
 
-                let response = spawn_tokio(request_builder.send()).await.map_err(|e| {
+                let can_retry = !matches!(request.method(), NavigationMethod::Post);
+                let mut retry = 0;
+                let response = loop {
+                    let attempt = request_builder
+                        .try_clone()
+                        .expect("Request bodies are never streamed");
+                    let result = spawn_tokio(attempt.send()).await;
+                    let failed_temporarily = match &result {
+                        Ok(response) => is_temporary_status(response.status()),
+                        Err(e) => e.is_connect() || e.is_timeout(),
+                    };
+                    if !can_retry || !failed_temporarily || retry >= fetch_policy.max_retries {
+                        break result;
+                    }
+                    retry += 1;
+                    let delay = fetch_policy.retry_delay(retry);
+                    tracing::info!(
+                        "Retrying {processed_url} in {delay:?} ({retry} of {})",
+                        fetch_policy.max_retries
+                    );
+                    Timer::after(delay).await;
+                };
+                let response = response.map_err(|e| {
                     let inner = if e.is_connect() {
                         Error::InvalidDomain(processed_url.to_string())
                     } else {
@@ -276,9 +446,20 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
                     return Err(ErrorResponse { url, error });
                 }
 
+                let headers = response
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| {
+                        (
+                            name.to_string(),
+                            String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                        )
+                    })
+                    .collect();
                 let response: Box<dyn SuccessResponse> = Box::new(Response {
                     url,
                     response_body: ResponseBody::Network(Arc::new(Mutex::new(Some(response)))),
+                    headers,
                     text_encoding,
                     status,
                     redirected,
@@ -289,7 +470,7 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
     }
 
     fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
-        match self.base_url.join(url) {
+        match resolve_url(&self.base_url, url) {
             Ok(url) => Ok(self.pre_process_url(url)),
             Err(error) => Err(error),
         }
@@ -317,7 +498,9 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
     ) {
         let addr = format!("{}:{}", host, port);
         let is_allowed = self.socket_allowed.contains(&addr);
+        let is_allowed_by_policy = self.socket_allowed_by_policy.contains(&addr);
         let socket_mode = self.socket_mode;
+        let socket_proxy = self.socket_proxies.get(&addr).cloned();
         let interface = self.interface.clone();
 
         let future = Box::pin(async move {
@@ -335,6 +518,8 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
 
                     return;
                 }
+                // The server opted in to this connection, so there's no need to ask.
+                (false, SocketMode::Ask) if is_allowed_by_policy => {}
                 (false, SocketMode::Ask) => {
                     let attempt_sandbox_connect = interface.confirm_socket(&host, port).await;
 
@@ -349,6 +534,11 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
                 }
             }
 
+            if let Some(proxy) = socket_proxy {
+                connect_through_proxy(proxy, timeout, handle, receiver, sender).await;
+                return;
+            }
+
             let host2 = host.clone();
 
             let timeout = async {
@@ -471,6 +661,177 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
 
         tokio::spawn(future);
     }
+
+    fn prefetch(&mut self, url: Url) {
+        // Local files are quick enough to load when they're needed.
+        let url = self.pre_process_url(url);
+        if !matches!(url.scheme(), "http" | "https")
+            || self.fetch_policy.offline
+            || self.prefetched.borrow().contains_key(&url)
+        {
+            return;
+        }
+
+        let fetch = self.fetch(Request::get(url.to_string()));
+        self.prefetched.borrow_mut().insert(url.clone(), None);
+        let prefetched = self.prefetched.clone();
+        self.future_spawner.spawn(Box::pin(async move {
+            let file = match fetch.await {
+                Ok(response) => {
+                    let response_url = response.url().into_owned();
+                    let headers = response.headers();
+                    let text_encoding = response.text_encoding();
+                    let status = response.status();
+                    let redirected = response.redirected();
+                    response.body().await.ok().map(|body| PrefetchedFile {
+                        url: response_url,
+                        body,
+                        headers,
+                        text_encoding,
+                        status,
+                        redirected,
+                    })
+                }
+                Err(e) => {
+                    tracing::debug!("Couldn't prefetch {url}: {}", e.error);
+                    None
+                }
+            };
+
+            // The movie may have fetched the file itself in the meantime, removing its entry.
+            let mut prefetched = prefetched.borrow_mut();
+            match file {
+                Some(file)
+                    if file.body.len() <= MAX_PREFETCHED_FILE_SIZE
+                        && prefetched.contains_key(&url) =>
+                {
+                    prefetched.insert(url, Some(file));
+                }
+                _ => {
+                    if matches!(prefetched.get(&url), Some(None)) {
+                        prefetched.remove(&url);
+                    }
+                }
+            }
+            Ok(())
+        }));
+    }
+
+    fn allow_socket_by_policy(&mut self, host: &str, port: u16) {
+        self.socket_allowed_by_policy
+            .insert(format!("{}:{}", host, port));
+    }
+}
+
+/// The directory that relative URLs are resolved against, for a movie at the given URL.
+///
+/// Like Flash Player projectors, a base URL naming a local directory refers to that
+/// directory, even without a trailing slash. Any other URL refers to the directory
+/// containing it.
+fn base_directory(mut url: Url) -> Url {
+    let is_directory = url.scheme() == "file" && url.to_file_path().is_ok_and(|path| path.is_dir());
+    if let Ok(mut segments) = url.path_segments_mut() {
+        if is_directory {
+            segments.pop_if_empty().push("");
+        } else {
+            segments.pop().pop_if_empty().push("");
+        }
+    }
+    url
+}
+
+/// Resolves a URL requested by a movie against the directory of the movie.
+///
+/// Projectors also accept absolute Windows paths such as `C:\games\level1.swf`,
+/// which we treat as local files on any platform.
+fn resolve_url(base_url: &Url, url: &str) -> Result<Url, ParseError> {
+    let bytes = url.as_bytes();
+    let is_windows_path = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/');
+    if is_windows_path {
+        Url::parse(&format!("file:///{url}"))
+    } else {
+        base_url.join(url)
+    }
+}
+
+/// Tunnels a socket connection through a WebSocket proxy, as the web version of Ruffle does.
+///
+/// Data is exchanged with the proxy as binary messages, which it forwards to and from the
+/// TCP connection it opens on our behalf.
+async fn connect_through_proxy(
+    proxy: Url,
+    timeout: Duration,
+    handle: SocketHandle,
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<SocketAction>,
+) {
+    tracing::info!("Connecting to socket proxy {proxy}");
+
+    let connect = async { Some(tokio_tungstenite::connect_async(proxy.as_str()).await) };
+    let timeout = async {
+        Timer::after(timeout).await;
+        None
+    };
+
+    let stream = match connect.or(timeout).await {
+        Some(Ok((stream, _))) => {
+            sender
+                .try_send(SocketAction::Connect(handle, ConnectionState::Connected))
+                .expect("working channel send");
+            stream
+        }
+        Some(Err(e)) => {
+            warn!("Failed to connect to socket proxy {proxy}, error: {e}");
+            sender
+                .try_send(SocketAction::Connect(handle, ConnectionState::Failed))
+                .expect("working channel send");
+            return;
+        }
+        None => {
+            warn!("Connection to socket proxy {proxy} timed out");
+            sender
+                .try_send(SocketAction::Connect(handle, ConnectionState::TimedOut))
+                .expect("working channel send");
+            return;
+        }
+    };
+
+    let (mut ws_write, mut ws_read) = stream.split();
+    loop {
+        match futures::future::select(ws_read.next(), std::pin::pin!(receiver.recv())).await {
+            // Handle incoming messages.
+            Either::Left((Some(Ok(Message::Binary(data))), _)) => sender
+                .try_send(SocketAction::Data(handle, data))
+                .expect("working channel send"),
+            Either::Left((Some(Ok(Message::Close(_)) | Err(_)) | None, _)) => {
+                sender
+                    .try_send(SocketAction::Close(handle))
+                    .expect("working channel send");
+                break;
+            }
+            Either::Left((Some(Ok(_)), _)) => {} // Pings are answered by tungstenite.
+            // Handle outgoing messages.
+            Either::Right((Ok(data), _)) => {
+                if let Err(e) = ws_write.send(Message::Binary(data)).await {
+                    warn!("Failed to send data to socket proxy: {e}");
+                    sender
+                        .try_send(SocketAction::Close(handle))
+                        .expect("working channel send");
+                    break;
+                }
+            }
+            // The movie closed the socket.
+            Either::Right((Err(_), _)) => {
+                if let Err(e) = ws_write.close().await {
+                    warn!("Failed to close connection to socket proxy: {e}");
+                }
+                break;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -561,6 +922,7 @@ mod tests {
             } else {
                 SocketMode::Deny
             },
+            Default::default(),
             Rc::new(PlayingContent::DirectFile(url)),
             (),
         )
@@ -765,4 +1127,58 @@ mod tests {
 
         assert_eq!(read_server(&mut server_socket).await, "Sending some data");
     }
+
+    #[test]
+    fn retry_delay_doubles_up_to_limit() {
+        let policy = FetchPolicy::default();
+        let delays: Vec<_> = (1..=7)
+            .map(|retry| policy.retry_delay(retry).as_millis())
+            .collect();
+        assert_eq!(delays, vec![500, 1000, 2000, 4000, 8000, 8000, 8000]);
+    }
+
+    #[test]
+    fn resolves_relative_urls_against_movie_directory() {
+        let base = base_directory(Url::parse("file:///games/my%20game/main.swf").unwrap());
+        assert_eq!(base.as_str(), "file:///games/my%20game/");
+        assert_eq!(
+            resolve_url(&base, "levels/level 1.swf").unwrap().as_str(),
+            "file:///games/my%20game/levels/level%201.swf"
+        );
+        assert_eq!(
+            resolve_url(&base, "levels\\level%201.swf")
+                .unwrap()
+                .as_str(),
+            "file:///games/my%20game/levels/level%201.swf"
+        );
+        assert_eq!(
+            resolve_url(&base, "../sounds.swf").unwrap().as_str(),
+            "file:///games/sounds.swf"
+        );
+        assert_eq!(
+            resolve_url(&base, "C:\\games\\level.swf").unwrap().as_str(),
+            "file:///C:/games/level.swf"
+        );
+        assert_eq!(
+            resolve_url(&base, "ftp://example.com/level.swf")
+                .unwrap()
+                .as_str(),
+            "ftp://example.com/level.swf"
+        );
+    }
+
+    #[test]
+    fn base_directory_keeps_local_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut url = Url::from_directory_path(dir.path()).unwrap();
+        assert_eq!(base_directory(url.clone()), url);
+
+        // Without a trailing slash, the directory is still used itself.
+        let expected = url.clone();
+        url.path_segments_mut().unwrap().pop_if_empty();
+        assert_eq!(base_directory(url), expected);
+
+        let url = Url::parse("https://example.com/games/main.swf").unwrap();
+        assert_eq!(base_directory(url).as_str(), "https://example.com/games/");
+    }
 }
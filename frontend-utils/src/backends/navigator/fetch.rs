@@ -4,9 +4,20 @@ use ruffle_core::loader::Error;
 use ruffle_core::swf::Encoding;
 use std::sync::{Arc, Mutex};
 
+/// The size of the chunks that a response from a file is delivered in.
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+
 pub enum ResponseBody {
     /// The response's body comes from a file.
-    File(Result<Vec<u8>, std::io::Error>),
+    ///
+    /// The file is in memory already, but it's still delivered in chunks so that
+    /// movies see it arrive progressively, as they would from the network.
+    File {
+        contents: Result<Vec<u8>, std::io::Error>,
+
+        /// How much of the contents has been read by `next_chunk`.
+        position: usize,
+    },
 
     /// The response's body comes from the network.
     ///
@@ -22,6 +33,7 @@ pub struct Response {
     pub text_encoding: Option<&'static Encoding>,
     pub status: u16,
     pub redirected: bool,
+    pub headers: Vec<(String, String)>,
 }
 
 impl SuccessResponse for Response {
@@ -32,9 +44,11 @@ impl SuccessResponse for Response {
     #[allow(clippy::await_holding_lock)]
     fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
         match self.response_body {
-            ResponseBody::File(file) => {
-                Box::pin(async move { file.map_err(|e| Error::FetchError(e.to_string())) })
-            }
+            ResponseBody::File { contents, position } => Box::pin(async move {
+                let mut contents = contents.map_err(|e| Error::FetchError(e.to_string()))?;
+                contents.drain(..position);
+                Ok(contents)
+            }),
             ResponseBody::Network(response) => Box::pin(async move {
                 Ok(response
                     .lock()
@@ -61,13 +75,22 @@ impl SuccessResponse for Response {
         self.redirected
     }
 
+    fn headers(&self) -> Vec<(String, String)> {
+        self.headers.clone()
+    }
+
     #[allow(clippy::await_holding_lock)]
     fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
         match &mut self.response_body {
-            ResponseBody::File(file) => {
-                let res = file
-                    .as_mut()
-                    .map(std::mem::take)
+            ResponseBody::File { contents, position } => {
+                let res = contents
+                    .as_ref()
+                    .map(|contents| {
+                        let end = contents.len().min(*position + FILE_CHUNK_SIZE);
+                        let chunk = contents[*position..end].to_vec();
+                        *position = end;
+                        chunk
+                    })
                     .map_err(|e| Error::FetchError(e.to_string()));
 
                 Box::pin(async move {
@@ -108,7 +131,9 @@ impl SuccessResponse for Response {
 
     fn expected_length(&self) -> Result<Option<u64>, Error> {
         match &self.response_body {
-            ResponseBody::File(file) => Ok(file.as_ref().map(|file| file.len() as u64).ok()),
+            ResponseBody::File { contents, .. } => {
+                Ok(contents.as_ref().map(|contents| contents.len() as u64).ok())
+            }
             ResponseBody::Network(response) => {
                 let lock = response.lock().expect("no recursive locks");
                 let response = lock.as_ref().expect("Body cannot already be consumed");
@@ -117,3 +142,56 @@ impl SuccessResponse for Response {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn file_response(contents: Vec<u8>) -> Response {
+        Response {
+            url: "file:///test.bin".to_string(),
+            response_body: ResponseBody::File {
+                contents: Ok(contents),
+                position: 0,
+            },
+            text_encoding: None,
+            status: 0,
+            redirected: false,
+            headers: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn file_is_read_in_chunks() {
+        let contents: Vec<u8> = (0..FILE_CHUNK_SIZE * 2 + 10).map(|i| i as u8).collect();
+        let mut response = file_response(contents.clone());
+        assert_eq!(
+            response.expected_length().unwrap(),
+            Some(contents.len() as u64)
+        );
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = response.next_chunk().await.unwrap() {
+            chunks.push(chunk);
+        }
+        let sizes: Vec<usize> = chunks.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [FILE_CHUNK_SIZE, FILE_CHUNK_SIZE, 10]);
+        assert_eq!(chunks.concat(), contents);
+    }
+
+    #[tokio::test]
+    async fn file_body_skips_read_chunks() {
+        let contents: Vec<u8> = (0..FILE_CHUNK_SIZE + 10).map(|i| i as u8).collect();
+        let mut response = file_response(contents.clone());
+        response.next_chunk().await.unwrap();
+        let body = Box::new(response).body().await.unwrap();
+        assert_eq!(body, contents[FILE_CHUNK_SIZE..]);
+    }
+
+    #[tokio::test]
+    async fn empty_file_has_no_chunks() {
+        let mut response = file_response(Vec::new());
+        assert_eq!(response.next_chunk().await.unwrap(), None);
+    }
+}
@@ -0,0 +1,220 @@
+//! A minimal FTP client, for movies that load files from FTP servers.
+//!
+//! Only downloading a single file in passive mode is supported, which is all Flash Player does.
+
+use std::io::{self, ErrorKind};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use url::Url;
+
+/// Downloads the file at the given `ftp://` URL.
+///
+/// Servers are logged into anonymously, unless the URL contains credentials.
+pub async fn fetch_ftp(url: Url) -> io::Result<Vec<u8>> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "FTP URL has no host"))?;
+    let port = url.port().unwrap_or(21);
+
+    let user = match url.username() {
+        "" => "anonymous".into(),
+        user => decode(user)?,
+    };
+    let password = match url.password() {
+        Some(password) => decode(password)?,
+        None => "anonymous@".into(),
+    };
+    // Paths in FTP URLs are relative to the directory we logged into.
+    let path = decode(url.path().trim_start_matches('/'))?;
+
+    let mut control = Control::connect(host, port).await?;
+    control.read_reply(&[220]).await?;
+
+    let (code, _) = control
+        .command(&format!("USER {user}"), &[230, 331])
+        .await?;
+    if code == 331 {
+        control
+            .command(&format!("PASS {password}"), &[202, 230])
+            .await?;
+    }
+
+    control.command("TYPE I", &[200]).await?;
+    let (_, reply) = control.command("PASV", &[227]).await?;
+    let data_port = parse_passive_port(&reply).ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid reply to PASV: {reply}"),
+        )
+    })?;
+    // The address in the reply is often wrong for servers behind a NAT,
+    // so we connect to the host we already know instead.
+    let mut data = TcpStream::connect((host, data_port)).await?;
+
+    control
+        .command(&format!("RETR {path}"), &[125, 150])
+        .await?;
+    let mut contents = Vec::new();
+    data.read_to_end(&mut contents).await?;
+    control.read_reply(&[226, 250]).await?;
+
+    // We have what we came for, so it doesn't matter if the server doesn't say goodbye.
+    let _ = control.command("QUIT", &[221]).await;
+    Ok(contents)
+}
+
+/// Percent-decodes part of an FTP URL to be sent in a command.
+///
+/// Line breaks and null characters are rejected, as they would let the URL
+/// end the command early and smuggle in commands of its own.
+fn decode(value: &str) -> io::Result<String> {
+    let value = urlencoding::decode(value)
+        .map(|value| value.into_owned())
+        .map_err(|e| io::Error::new(ErrorKind::InvalidInput, e))?;
+    if value.contains(['\r', '\n', '\0']) {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "FTP URL contains a line break or null character",
+        ));
+    }
+    Ok(value)
+}
+
+/// The control connection to an FTP server, which commands are sent over.
+struct Control {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl Control {
+    async fn connect(host: &str, port: u16) -> io::Result<Self> {
+        let (reader, writer) = TcpStream::connect((host, port)).await?.into_split();
+        Ok(Self {
+            reader: BufReader::new(reader),
+            writer,
+        })
+    }
+
+    /// Sends a command, and reads the reply, which must have one of the expected codes.
+    async fn command(&mut self, command: &str, expected: &[u16]) -> io::Result<(u16, String)> {
+        self.writer
+            .write_all(format!("{command}\r\n").as_bytes())
+            .await?;
+        self.read_reply(expected).await
+    }
+
+    /// Reads a reply, which must have one of the expected codes.
+    async fn read_reply(&mut self, expected: &[u16]) -> io::Result<(u16, String)> {
+        let (code, text) = read_reply(&mut self.reader).await?;
+        if expected.contains(&code) {
+            Ok((code, text))
+        } else {
+            Err(io::Error::other(format!(
+                "FTP server replied {code} {text}"
+            )))
+        }
+    }
+}
+
+/// Reads a reply from an FTP server, returning its code and its text.
+///
+/// Replies spanning several lines start with `123-`, and end with a line starting with `123 `.
+async fn read_reply(reader: &mut (impl AsyncBufReadExt + Unpin)) -> io::Result<(u16, String)> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Err(ErrorKind::UnexpectedEof.into());
+    }
+    let code = line
+        .get(..3)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "Invalid FTP reply"))?;
+
+    let mut text = line.get(4..).unwrap_or_default().trim_end().to_owned();
+    if line.as_bytes().get(3) == Some(&b'-') {
+        let last_line = format!("{code} ");
+        let continued_line = format!("{code}-");
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 {
+                return Err(ErrorKind::UnexpectedEof.into());
+            }
+            if let Some(last) = line.strip_prefix(&last_line) {
+                text.push('\n');
+                text.push_str(last.trim_end());
+                break;
+            }
+            text.push('\n');
+            text.push_str(
+                line.strip_prefix(&continued_line)
+                    .unwrap_or(&line)
+                    .trim_end(),
+            );
+        }
+    }
+    Ok((code, text))
+}
+
+/// Finds the port of the data connection in a reply to `PASV`,
+/// e.g. `Entering Passive Mode (192,168,0,1,195,149)`.
+fn parse_passive_port(reply: &str) -> Option<u16> {
+    let start = reply.find(|c: char| c.is_ascii_digit())?;
+    let numbers: Vec<u8> = reply[start..]
+        .split(|c: char| !c.is_ascii_digit())
+        .take(6)
+        .map(|number| number.parse().ok())
+        .collect::<Option<_>>()?;
+    match numbers[..] {
+        [_, _, _, _, high, low] => Some(u16::from_be_bytes([high, low])),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passive_port() {
+        assert_eq!(
+            parse_passive_port("Entering Passive Mode (192,168,0,1,195,149)."),
+            Some(50069)
+        );
+        assert_eq!(
+            parse_passive_port("Entering Passive Mode 10,0,0,1,0,21"),
+            Some(21)
+        );
+        assert_eq!(parse_passive_port("Entering Passive Mode (10,0,0,1)"), None);
+        assert_eq!(
+            parse_passive_port("Entering Passive Mode (10,0,0,1,300,1)"),
+            None
+        );
+    }
+
+    #[test]
+    fn decode_rejects_command_injection() {
+        assert_eq!(decode("dir/some%20file.swf").unwrap(), "dir/some file.swf");
+        for value in ["a%0D%0ADELE%20b", "a%0Ab", "a%00b"] {
+            assert_eq!(decode(value).unwrap_err().kind(), ErrorKind::InvalidInput);
+        }
+    }
+
+    #[tokio::test]
+    async fn multi_line_reply() {
+        let mut reader: &[u8] =
+            b"220-Welcome\r\n220-to the server\r\n220 Ready\r\n230 Logged in\r\n";
+        assert_eq!(
+            read_reply(&mut reader).await.unwrap(),
+            (220, "Welcome\nto the server\nReady".to_string())
+        );
+        assert_eq!(
+            read_reply(&mut reader).await.unwrap(),
+            (230, "Logged in".to_string())
+        );
+        assert_eq!(
+            read_reply(&mut reader).await.unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+}
@@ -1,11 +1,296 @@
-use ruffle_core::backend::storage::StorageBackend;
+use ruffle_core::backend::storage::{site_of, QuotaStatus, StorageBackend};
+use std::collections::BTreeMap;
 use std::fs;
 use std::fs::File;
-use std::io::Write;
+use std::io;
+use std::io::{Read, Seek, Write};
 use std::path::{Component, Path, PathBuf};
+use toml_edit::{value, ArrayOfTables, DocumentMut, Item, Table};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Decides how many bytes of shared objects a site may store, or `None` for no limit.
+pub type StorageQuota = Box<dyn Fn(&str) -> Option<usize>>;
+
+/// A shared object found in a storage directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredSharedObject {
+    /// The full name of the shared object, without the site it belongs to.
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Lists every shared object in the given storage directory, grouped by the site that stored it.
+pub fn stored_shared_objects(
+    shared_objects_path: &Path,
+) -> BTreeMap<String, Vec<StoredSharedObject>> {
+    let mut result = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(shared_objects_path) else {
+        return result;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let mut shared_objects = Vec::new();
+        collect_shared_objects(&path, &path, &mut shared_objects);
+        if !shared_objects.is_empty() {
+            shared_objects.sort_by(|a, b| a.name.cmp(&b.name));
+            result.insert(
+                entry.file_name().to_string_lossy().into_owned(),
+                shared_objects,
+            );
+        }
+    }
+    result
+}
+
+fn collect_shared_objects(site_path: &Path, dir: &Path, result: &mut Vec<StoredSharedObject>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_shared_objects(site_path, &path, result);
+        } else if path.extension().is_some_and(|extension| extension == "sol") {
+            let name = path
+                .strip_prefix(site_path)
+                .unwrap_or(&path)
+                .with_extension("");
+            result.push(StoredSharedObject {
+                name: name.to_string_lossy().replace('\\', "/"),
+                path,
+                size: metadata.len(),
+            });
+        }
+    }
+}
+
+/// Checks whether the data starts with the header of a `.sol` file, as written by Flash Player.
+///
+/// The header is a `0x00BF` magic number, the length of the rest of the file,
+/// and the `TCSO` signature, regardless of whether the body uses AMF0 or AMF3.
+pub fn is_shared_object_file(data: &[u8]) -> bool {
+    data.len() >= 10 && data[0..2] == [0x00, 0xBF] && &data[6..10] == b"TCSO"
+}
+
+/// What happened when importing shared objects from a Flash Player profile.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Shared objects that were copied into the storage directory.
+    pub imported: usize,
+
+    /// Shared objects that were left alone, because a save of the same name
+    /// already exists or because the file isn't a valid `.sol` file.
+    pub skipped: usize,
+}
+
+/// Copies the shared objects of a Flash Player profile into the given storage directory.
+///
+/// `source` may be the Flash Player directory itself, its `#SharedObjects` directory,
+/// or one of the randomly named directories inside of it. Flash Player stores shared objects
+/// as `<site>/<movie path>/<name>.sol` in there, which is the same layout that
+/// [`DiskStorageBackend`] uses, so the files are copied as they are.
+/// Existing saves are never overwritten.
+pub fn import_flash_player_shared_objects(
+    source: &Path,
+    shared_objects_path: &Path,
+) -> io::Result<ImportSummary> {
+    let mut summary = ImportSummary::default();
+    for profile in flash_player_profiles(source)? {
+        for entry in fs::read_dir(&profile)?.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            // Local movies with network access get their own directory in Flash Player,
+            // but share `localhost` with every other local movie in Ruffle.
+            let file_name = entry.file_name();
+            let site = match file_name.to_string_lossy().as_ref() {
+                "#localWithNet" => "localhost".to_string(),
+                site => site.to_string(),
+            };
+            import_directory(&path, &shared_objects_path.join(site), &mut summary)?;
+        }
+    }
+    Ok(summary)
+}
+
+/// Finds the directories that contain one directory per site.
+fn flash_player_profiles(source: &Path) -> io::Result<Vec<PathBuf>> {
+    let shared_objects = if source
+        .file_name()
+        .is_some_and(|name| name == "#SharedObjects")
+    {
+        source.to_path_buf()
+    } else if source.join("#SharedObjects").is_dir() {
+        source.join("#SharedObjects")
+    } else {
+        return Ok(vec![source.to_path_buf()]);
+    };
+
+    let mut profiles = Vec::new();
+    for entry in fs::read_dir(shared_objects)?.flatten() {
+        if entry.path().is_dir() {
+            profiles.push(entry.path());
+        }
+    }
+    profiles.sort();
+    Ok(profiles)
+}
+
+fn import_directory(
+    source: &Path,
+    destination: &Path,
+    summary: &mut ImportSummary,
+) -> io::Result<()> {
+    for entry in fs::read_dir(source)?.flatten() {
+        let path = entry.path();
+        let target = destination.join(entry.file_name());
+        if path.is_dir() {
+            import_directory(&path, &target, summary)?;
+        } else if path.extension().is_some_and(|extension| extension == "sol") {
+            let data = fs::read(&path)?;
+            import_shared_object(&path.to_string_lossy(), &data, &target, summary)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes an imported shared object to `target`, unless it isn't valid or a save already exists
+/// there. `source` describes where it comes from.
+fn import_shared_object(
+    source: &str,
+    data: &[u8],
+    target: &Path,
+    summary: &mut ImportSummary,
+) -> io::Result<()> {
+    if !is_shared_object_file(data) {
+        tracing::warn!("Not importing {source:?} as it isn't a valid shared object");
+        summary.skipped += 1;
+    } else if target.exists() {
+        tracing::info!("Not importing {source:?} as {target:?} already exists");
+        summary.skipped += 1;
+    } else {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, data)?;
+        summary.imported += 1;
+    }
+    Ok(())
+}
+
+/// The version of the archives written by [`export_shared_objects_archive`], increased whenever
+/// older versions of Ruffle couldn't import them anymore.
+const ARCHIVE_FORMAT: i64 = 1;
+
+/// The file of an archive that lists the shared objects in it.
+const ARCHIVE_MANIFEST: &str = "manifest.toml";
+
+/// Writes every shared object of the given storage directory to a zip archive, so that they can
+/// be backed up or moved to another computer, then brought back with
+/// [`import_shared_objects_archive`]. Returns how many shared objects were exported.
+///
+/// The archive has a `manifest.toml` listing the full name of each shared object,
+/// which is stored in the archive as `saves/<name>.sol`.
+pub fn export_shared_objects_archive(
+    shared_objects_path: &Path,
+    writer: impl Write + Seek,
+) -> io::Result<usize> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut saves = ArrayOfTables::new();
+    for (site, shared_objects) in stored_shared_objects(shared_objects_path) {
+        for shared_object in shared_objects {
+            let name = format!("{site}/{}", shared_object.name);
+            let data = fs::read(&shared_object.path)?;
+            zip.start_file(format!("saves/{name}.sol"), options)?;
+            zip.write_all(&data)?;
+
+            let mut save = Table::new();
+            save["name"] = value(name);
+            save["size"] = value(data.len() as i64);
+            saves.push(save);
+        }
+    }
+    let exported = saves.len();
+
+    let mut manifest = DocumentMut::new();
+    manifest["format"] = value(ARCHIVE_FORMAT);
+    manifest["saves"] = Item::ArrayOfTables(saves);
+    zip.start_file(ARCHIVE_MANIFEST, options)?;
+    zip.write_all(manifest.to_string().as_bytes())?;
+    zip.finish()?;
+    Ok(exported)
+}
+
+/// Copies the shared objects of an archive written by [`export_shared_objects_archive`] into the
+/// given storage directory. Like when importing from Flash Player, existing saves are never
+/// overwritten.
+pub fn import_shared_objects_archive(
+    reader: impl Read + Seek,
+    shared_objects_path: &Path,
+) -> io::Result<ImportSummary> {
+    let invalid_archive = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let mut zip = ZipArchive::new(reader)?;
+    let mut manifest = String::new();
+    zip.by_name(ARCHIVE_MANIFEST)
+        .map_err(|_| invalid_archive("Not an archive of saves, as it has no manifest"))?
+        .read_to_string(&mut manifest)?;
+    let manifest: DocumentMut = manifest
+        .parse()
+        .map_err(|_| invalid_archive("The manifest of the archive is corrupted"))?;
+    if manifest.get("format").and_then(Item::as_integer) != Some(ARCHIVE_FORMAT) {
+        return Err(invalid_archive(
+            "The archive was made by an incompatible version of Ruffle",
+        ));
+    }
+    let names: Vec<&str> = manifest
+        .get("saves")
+        .and_then(Item::as_array_of_tables)
+        .into_iter()
+        .flatten()
+        .filter_map(|save| save.get("name")?.as_str())
+        .collect();
+
+    let mut summary = ImportSummary::default();
+    for name in names {
+        // Names come from the archive, and must not point outside of the storage directory.
+        if !Path::new(name)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            tracing::warn!("Not importing {name:?} as it isn't a valid name");
+            summary.skipped += 1;
+            continue;
+        }
+        let mut data = Vec::new();
+        match zip.by_name(&format!("saves/{name}.sol")) {
+            Ok(mut file) => file.read_to_end(&mut data)?,
+            Err(e) => {
+                tracing::warn!("Not importing {name:?} as it's missing from the archive: {e}");
+                summary.skipped += 1;
+                continue;
+            }
+        };
+        let target = shared_objects_path.join(format!("{name}.sol"));
+        import_shared_object(name, &data, &target, &mut summary)?;
+    }
+    Ok(summary)
+}
 
 pub struct DiskStorageBackend {
     shared_objects_path: PathBuf,
+    quota: Option<StorageQuota>,
 }
 
 impl DiskStorageBackend {
@@ -20,9 +305,16 @@ impl DiskStorageBackend {
 
         DiskStorageBackend {
             shared_objects_path,
+            quota: None,
         }
     }
 
+    /// Limits how much each site may store, rejecting any shared object that would go over.
+    pub fn with_quota(mut self, quota: impl Fn(&str) -> Option<usize> + 'static) -> Self {
+        self.quota = Some(Box::new(quota));
+        self
+    }
+
     /// Verifies that the path contains no `..` components to prevent accessing files outside of the Ruffle directory.
     fn is_path_allowed(path: &Path) -> bool {
         path.components().all(|c| c != Component::ParentDir)
@@ -53,6 +345,13 @@ impl StorageBackend for DiskStorageBackend {
         if !Self::is_path_allowed(&path) {
             return false;
         }
+        if self.check_quota(name, value.len()) != QuotaStatus::Allowed {
+            tracing::warn!(
+                "Not saving \"{}\" as it would exceed its storage quota",
+                name
+            );
+            return false;
+        }
         if let Some(parent_dir) = path.parent() {
             if !parent_dir.exists() {
                 if let Err(r) = fs::create_dir_all(parent_dir) {
@@ -85,4 +384,195 @@ impl StorageBackend for DiskStorageBackend {
         }
         let _ = fs::remove_file(path);
     }
+
+    fn check_quota(&self, name: &str, size: usize) -> QuotaStatus {
+        let site = site_of(name);
+        let Some(limit) = self.quota.as_ref().and_then(|quota| quota(site)) else {
+            return QuotaStatus::Allowed;
+        };
+        if limit == 0 {
+            return QuotaStatus::Denied;
+        }
+
+        let path = self.get_shared_object_path(name);
+        let replaced = fs::metadata(path).map(|m| m.len()).unwrap_or_default();
+        let used: u64 = stored_shared_objects(&self.shared_objects_path)
+            .get(site)
+            .map(|shared_objects| shared_objects.iter().map(|so| so.size).sum())
+            .unwrap_or_default();
+        let required = (used - replaced.min(used)) as usize + size;
+        if required > limit {
+            QuotaStatus::Exceeded { required }
+        } else {
+            QuotaStatus::Allowed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_shared_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = DiskStorageBackend::new(dir.path().to_path_buf());
+        assert!(storage.put("localhost/game.swf/save", &[0; 10]));
+        assert!(storage.put("localhost/game.swf/#a/b", &[0; 20]));
+        assert!(storage.put("example.com/movie.swf/save", &[0; 30]));
+
+        let stored = stored_shared_objects(dir.path());
+        let names = |site: &str| -> Vec<(String, u64)> {
+            stored[site]
+                .iter()
+                .map(|so| (so.name.clone(), so.size))
+                .collect()
+        };
+        assert_eq!(
+            names("localhost"),
+            vec![
+                ("game.swf/#a/b".to_string(), 20),
+                ("game.swf/save".to_string(), 10)
+            ]
+        );
+        assert_eq!(
+            names("example.com"),
+            vec![("movie.swf/save".to_string(), 30)]
+        );
+    }
+
+    #[test]
+    fn enforces_quota() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage =
+            DiskStorageBackend::new(dir.path().to_path_buf()).with_quota(|site| match site {
+                "localhost" => Some(100),
+                "example.com" => Some(0),
+                _ => None,
+            });
+
+        assert!(storage.put("localhost/game.swf/a", &[0; 60]));
+        // Replacing a shared object only counts its new size.
+        assert!(storage.put("localhost/game.swf/a", &[0; 70]));
+        assert_eq!(
+            storage.check_quota("localhost/game.swf/b", 40),
+            QuotaStatus::Exceeded { required: 110 }
+        );
+        assert!(!storage.put("localhost/game.swf/b", &[0; 40]));
+        assert!(storage.put("localhost/game.swf/b", &[0; 30]));
+
+        assert_eq!(
+            storage.check_quota("example.com/movie.swf/a", 1),
+            QuotaStatus::Denied
+        );
+        assert!(storage.put("elsewhere.com/movie.swf/a", &[0; 1000]));
+    }
+
+    fn sol(body: &[u8]) -> Vec<u8> {
+        let mut data = vec![0x00, 0xBF];
+        data.extend_from_slice(&(body.len() as u32 + 4).to_be_bytes());
+        data.extend_from_slice(b"TCSO");
+        data.extend_from_slice(body);
+        data
+    }
+
+    #[test]
+    fn imports_flash_player_profile() {
+        let flash_player = tempfile::tempdir().unwrap();
+        let profile = flash_player.path().join("#SharedObjects").join("ABCD1234");
+        let write = |path: &str, data: &[u8]| {
+            let path = profile.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, data).unwrap();
+        };
+        write("example.com/game.swf/save.sol", &sol(b"new"));
+        write("example.com/game.swf/existing.sol", &sol(b"new"));
+        write("example.com/game.swf/broken.sol", b"not a shared object");
+        write("example.com/game.swf/notes.txt", b"ignored");
+        write("#localWithNet/game.swf/#a/b.sol", &sol(b"local"));
+
+        let saves = tempfile::tempdir().unwrap();
+        let mut storage = DiskStorageBackend::new(saves.path().to_path_buf());
+        assert!(storage.put("example.com/game.swf/existing", &sol(b"old")));
+
+        let summary =
+            import_flash_player_shared_objects(flash_player.path(), saves.path()).unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 2,
+                skipped: 2
+            }
+        );
+        assert_eq!(storage.get("example.com/game.swf/save"), Some(sol(b"new")));
+        assert_eq!(
+            storage.get("example.com/game.swf/existing"),
+            Some(sol(b"old"))
+        );
+        assert_eq!(storage.get("localhost/game.swf/#a/b"), Some(sol(b"local")));
+        assert_eq!(storage.get("example.com/game.swf/broken"), None);
+    }
+
+    #[test]
+    fn exports_and_imports_archive() {
+        let saves = tempfile::tempdir().unwrap();
+        let mut storage = DiskStorageBackend::new(saves.path().to_path_buf());
+        assert!(storage.put("localhost/game.swf/save", &sol(b"game")));
+        assert!(storage.put("example.com/movie.swf/#a/b", &sol(b"movie")));
+
+        let mut archive = io::Cursor::new(Vec::new());
+        assert_eq!(
+            export_shared_objects_archive(saves.path(), &mut archive).unwrap(),
+            2
+        );
+
+        let other_saves = tempfile::tempdir().unwrap();
+        let mut other_storage = DiskStorageBackend::new(other_saves.path().to_path_buf());
+        assert!(other_storage.put("localhost/game.swf/save", &sol(b"newer")));
+
+        archive.set_position(0);
+        let summary = import_shared_objects_archive(archive, other_saves.path()).unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 1,
+                skipped: 1
+            }
+        );
+        assert_eq!(
+            other_storage.get("example.com/movie.swf/#a/b"),
+            Some(sol(b"movie"))
+        );
+        assert_eq!(
+            other_storage.get("localhost/game.swf/save"),
+            Some(sol(b"newer"))
+        );
+    }
+
+    #[test]
+    fn rejects_unsafe_archive_names() {
+        let mut archive = io::Cursor::new(Vec::new());
+        let mut zip = ZipWriter::new(&mut archive);
+        zip.start_file(ARCHIVE_MANIFEST, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"format = 1\n[[saves]]\nname = \"../escaped\"\n")
+            .unwrap();
+        zip.start_file("saves/../escaped.sol", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&sol(b"escaped")).unwrap();
+        zip.finish().unwrap();
+
+        let parent = tempfile::tempdir().unwrap();
+        let saves = parent.path().join("saves");
+        archive.set_position(0);
+        let summary = import_shared_objects_archive(archive, &saves).unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 0,
+                skipped: 1
+            }
+        );
+        assert!(!parent.path().join("escaped.sol").exists());
+    }
 }
@@ -1,7 +1,7 @@
 mod read;
 pub use read::read_player_options;
 
-use ruffle_core::config::Letterbox;
+use ruffle_core::config::{Letterbox, SpoofedCapabilities};
 use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
 use std::time::Duration;
@@ -27,6 +27,9 @@ pub struct PlayerOptions {
     pub player_runtime: Option<PlayerRuntime>,
     pub frame_rate: Option<f64>,
     pub dummy_external_interface: Option<bool>,
+    pub relaxed_security: Option<bool>,
+    pub partial_playback: Option<bool>,
+    pub capabilities: SpoofedCapabilities,
 }
 
 impl PlayerOptions {
@@ -54,6 +57,38 @@ impl PlayerOptions {
             dummy_external_interface: self
                 .dummy_external_interface
                 .or(other.dummy_external_interface),
+            relaxed_security: self.relaxed_security.or(other.relaxed_security),
+            partial_playback: self.partial_playback.or(other.partial_playback),
+            capabilities: SpoofedCapabilities {
+                version: self
+                    .capabilities
+                    .version
+                    .clone()
+                    .or_else(|| other.capabilities.version.clone()),
+                os: self
+                    .capabilities
+                    .os
+                    .clone()
+                    .or_else(|| other.capabilities.os.clone()),
+                language: self
+                    .capabilities
+                    .language
+                    .clone()
+                    .or_else(|| other.capabilities.language.clone()),
+                player_type: self
+                    .capabilities
+                    .player_type
+                    .clone()
+                    .or_else(|| other.capabilities.player_type.clone()),
+                is_debugger: self
+                    .capabilities
+                    .is_debugger
+                    .or(other.capabilities.is_debugger),
+                screen_resolution: self
+                    .capabilities
+                    .screen_resolution
+                    .or(other.capabilities.screen_resolution),
+            },
         }
     }
 }
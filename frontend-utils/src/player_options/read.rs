@@ -69,6 +69,23 @@ pub fn read_player_options<'a>(
     // Mock external interface
     result.dummy_external_interface = table.get_bool(cx, "mock_external_interface");
 
+    // Relaxed security
+    result.relaxed_security = table.get_bool(cx, "relaxed_security");
+
+    // Play truncated movies up to their last complete frame
+    result.partial_playback = table.get_bool(cx, "partial_playback");
+
+    // Capabilities reported to the movie
+    table.get_table_like(cx, "capabilities", |cx, capabilities| {
+        result.capabilities.version = capabilities.parse_from_str(cx, "version");
+        result.capabilities.os = capabilities.parse_from_str(cx, "os");
+        result.capabilities.language = capabilities.parse_from_str(cx, "language");
+        result.capabilities.player_type = capabilities.parse_from_str(cx, "player_type");
+        result.capabilities.is_debugger = capabilities.get_bool(cx, "is_debugger");
+        result.capabilities.screen_resolution =
+            capabilities.parse_from_str(cx, "screen_resolution");
+    });
+
     result
 }
 
@@ -76,7 +93,7 @@ pub fn read_player_options<'a>(
 mod tests {
     use super::*;
     use crate::parse::{DocumentHolder, ParseDetails, ParseWarning};
-    use ruffle_core::config::Letterbox;
+    use ruffle_core::config::{Letterbox, ScreenResolution, SpoofedCapabilities};
     use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
     use ruffle_render::quality::StageQuality;
     use toml_edit::DocumentMut;
@@ -736,4 +753,111 @@ mod tests {
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
     }
+
+    #[test]
+    fn relaxed_security() {
+        let result = read("relaxed_security = \"yes\"");
+        assert_eq!(&PlayerOptions::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "string",
+                path: "relaxed_security".to_string()
+            }],
+            result.warnings
+        );
+
+        let result = read("relaxed_security = true");
+        assert_eq!(
+            &PlayerOptions {
+                relaxed_security: Some(true),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn partial_playback() {
+        let result = read("partial_playback = 1");
+        assert_eq!(&PlayerOptions::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "integer",
+                path: "partial_playback".to_string()
+            }],
+            result.warnings
+        );
+
+        let result = read("partial_playback = true");
+        assert_eq!(
+            &PlayerOptions {
+                partial_playback: Some(true),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn capabilities() {
+        let result = read("capabilities = true");
+        assert_eq!(&PlayerOptions::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "table",
+                actual: "boolean",
+                path: "capabilities".to_string()
+            }],
+            result.warnings
+        );
+
+        let result = read(
+            r#"
+        [capabilities]
+        version = "WIN 9,0,115,0"
+        os = "Windows XP"
+        language = "ja"
+        player_type = "PlugIn"
+        is_debugger = true
+        screen_resolution = "1024x768"
+        "#,
+        );
+        assert_eq!(
+            &PlayerOptions {
+                capabilities: SpoofedCapabilities {
+                    version: Some("WIN 9,0,115,0".to_string()),
+                    os: Some("Windows XP".to_string()),
+                    language: Some("ja".to_string()),
+                    player_type: Some("PlugIn".to_string()),
+                    is_debugger: Some(true),
+                    screen_resolution: Some(ScreenResolution {
+                        width: 1024,
+                        height: 768
+                    }),
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read(
+            r#"
+        [capabilities]
+        screen_resolution = "big"
+        "#,
+        );
+        assert_eq!(&PlayerOptions::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "big".to_string(),
+                path: "capabilities.screen_resolution".to_string()
+            }],
+            result.warnings
+        );
+    }
 }
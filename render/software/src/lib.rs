@@ -0,0 +1,1029 @@
+//! A render backend that rasterizes on the CPU, using tiny-skia.
+//!
+//! This is much slower than the wgpu backend, but doesn't need a GPU or any
+//! graphics drivers, so it can be used headless, in CI, and as a fallback on
+//! machines where hardware acceleration is broken.
+
+#![deny(clippy::unwrap_used)]
+// Handles must be `Arc`s, but the data behind them is only used from one thread.
+#![allow(clippy::arc_with_non_send_sync)]
+
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    RenderBackend, ShapeHandle, ShapeHandleImpl, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapHandleImpl, BitmapSource, PixelRegion, PixelSnapping, RgbaBufRead,
+    SyncHandle,
+};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::error::Error;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, LineScaleMode, LineScales};
+use ruffle_render::transform::Transform;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::sync::Arc;
+use swf::{BlendMode, Color, ColorTransform, Twips};
+use tiny_skia::{
+    FillRule, FilterQuality, GradientStop, IntSize, LineCap, LineJoin, LinearGradient, Mask,
+    MaskType, Paint, Path, PathBuilder, Pattern, PixmapPaint, RadialGradient, Rect, Shader,
+    SpreadMode, Stroke,
+};
+
+pub use tiny_skia::Pixmap;
+
+pub struct SoftwareRenderBackend {
+    /// The pixmap that the stage is rendered into, in premultiplied RGBA.
+    target: Pixmap,
+    viewport_scale_factor: f64,
+    quality: StageQuality,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            target: create_pixmap(width, height),
+            viewport_scale_factor: 1.0,
+            quality: StageQuality::default(),
+        }
+    }
+
+    /// The last rendered frame.
+    pub fn target(&self) -> &Pixmap {
+        &self.target
+    }
+
+    /// Captures the last rendered frame. The resulting image uses straight alpha.
+    pub fn capture_frame(&self) -> image::RgbaImage {
+        let mut bytes = self.target.data().to_vec();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut bytes);
+        image::RgbaImage::from_raw(self.target.width(), self.target.height(), bytes)
+            .expect("Pixmap must be a valid RgbaImage")
+    }
+}
+
+/// Create a transparent pixmap, at least one pixel in size.
+fn create_pixmap(width: u32, height: u32) -> Pixmap {
+    Pixmap::new(width.max(1), height.max(1)).expect("Pixmap size must be valid")
+}
+
+#[derive(Debug)]
+struct SoftwareBitmap {
+    pixmap: RefCell<Pixmap>,
+}
+
+impl BitmapHandleImpl for SoftwareBitmap {}
+
+fn as_software_bitmap(handle: &BitmapHandle) -> &SoftwareBitmap {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Bitmap handle must be a software bitmap")
+}
+
+/// A bitmap rendered offscreen, whose pixels are ready to be read back.
+#[derive(Debug)]
+struct SoftwareSyncHandle {
+    handle: BitmapHandle,
+    bounds: PixelRegion,
+}
+
+impl SyncHandle for SoftwareSyncHandle {}
+
+/// Shape data ready to be rasterized, in the shape's own coordinate space (in pixels).
+#[derive(Debug)]
+struct ShapeData(Vec<ShapeCommand>);
+
+impl ShapeHandleImpl for ShapeData {}
+
+fn as_shape_data(handle: &ShapeHandle) -> &ShapeData {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Shape handle must be a software ShapeData")
+}
+
+#[derive(Debug)]
+enum ShapeCommand {
+    Fill {
+        path: Path,
+        fill: Fill,
+    },
+    Stroke {
+        path: Path,
+        fill: Fill,
+        width: f32,
+        line_cap: LineCap,
+        line_join: LineJoin,
+        miter_limit: f32,
+        scale_mode: LineScaleMode,
+    },
+}
+
+#[derive(Debug)]
+enum Fill {
+    Color(Color),
+    LinearGradient(swf::Gradient),
+    RadialGradient {
+        gradient: swf::Gradient,
+        focal_point: f32,
+    },
+    Bitmap {
+        handle: BitmapHandle,
+        matrix: swf::Matrix,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        ViewportDimensions {
+            width: self.target.width(),
+            height: self.target.height(),
+            scale_factor: self.viewport_scale_factor,
+        }
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        if dimensions.width != self.target.width() || dimensions.height != self.target.height() {
+            self.target = create_pixmap(dimensions.width, dimensions.height);
+        }
+        self.viewport_scale_factor = dimensions.scale_factor;
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let data = swf_shape_to_commands(&shape, bitmap_source, self);
+        ShapeHandle(Arc::new(ShapeData(data)))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let bitmap = as_software_bitmap(&handle);
+        // Render into a copy, as the commands may draw the bitmap itself.
+        let mut pixmap = bitmap.pixmap.borrow().clone();
+        rasterize(&mut pixmap, quality, commands);
+        *bitmap.pixmap.borrow_mut() = pixmap;
+        Some(Box::new(SoftwareSyncHandle { handle, bounds }))
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            // Filters aren't supported, so cached bitmaps are drawn without them.
+            let bitmap = as_software_bitmap(&entry.handle);
+            let (width, height) = {
+                let pixmap = bitmap.pixmap.borrow();
+                (pixmap.width(), pixmap.height())
+            };
+            let mut pixmap = create_pixmap(width, height);
+            pixmap.fill(to_skia_color(entry.clear));
+            rasterize(&mut pixmap, self.quality, entry.commands);
+            *bitmap.pixmap.borrow_mut() = pixmap;
+        }
+
+        self.target.fill(to_skia_color(clear));
+        rasterize(&mut self.target, self.quality, commands);
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        let pixmap = Pixmap::new(width, height).ok_or(Error::InvalidSize)?;
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap {
+            pixmap: RefCell::new(pixmap),
+        })))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        let bitmap = bitmap.to_rgba();
+        let size = IntSize::from_wh(bitmap.width(), bitmap.height()).ok_or(Error::InvalidSize)?;
+        let pixmap = Pixmap::from_vec(bitmap.data().to_vec(), size).ok_or(Error::InvalidSize)?;
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap {
+            pixmap: RefCell::new(pixmap),
+        })))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        let bitmap = bitmap.to_rgba();
+        let mut pixmap = as_software_bitmap(handle).pixmap.borrow_mut();
+        if pixmap.width() != bitmap.width() || pixmap.height() != bitmap.height() {
+            let size =
+                IntSize::from_wh(bitmap.width(), bitmap.height()).ok_or(Error::InvalidSize)?;
+            *pixmap = Pixmap::from_vec(bitmap.data().to_vec(), size).ok_or(Error::InvalidSize)?;
+            return Ok(());
+        }
+
+        let stride = bitmap.width() as usize * 4;
+        let x_range = region.x_min as usize * 4..region.x_max as usize * 4;
+        let data = pixmap.data_mut();
+        for y in region.y_min as usize..region.y_max as usize {
+            let row = y * stride;
+            data[row + x_range.start..row + x_range.end]
+                .copy_from_slice(&bitmap.data()[row + x_range.start..row + x_range.end]);
+        }
+        Ok(())
+    }
+
+    fn create_context3d(
+        &mut self,
+        _profile: Context3DProfile,
+    ) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "Renderer: Software (tiny-skia)\nSurface size: {}x{}\n",
+            self.target.width(),
+            self.target.height()
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented("compile_pixelbender_shader".into()))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _handle: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: &PixelBenderTarget,
+    ) -> Result<PixelBenderOutput, Error> {
+        Err(Error::Unimplemented("run_pixelbender_shader".into()))
+    }
+
+    fn resolve_sync_handle(
+        &mut self,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), Error> {
+        let handle = handle
+            .downcast::<SoftwareSyncHandle>()
+            .map_err(|_| Error::Unimplemented("Foreign sync handle".into()))?;
+        let pixmap = as_software_bitmap(&handle.handle).pixmap.borrow();
+        let bounds = handle.bounds;
+
+        let stride = pixmap.width() as usize * 4;
+        let x_range = bounds.x_min as usize * 4..bounds.x_max as usize * 4;
+        let mut buffer = Vec::with_capacity(bounds.height() as usize * x_range.len());
+        for y in bounds.y_min as usize..bounds.y_max as usize {
+            let row = y * stride;
+            buffer.extend_from_slice(&pixmap.data()[row + x_range.start..row + x_range.end]);
+        }
+        with_rgba(&buffer, bounds.width() * 4);
+        Ok(())
+    }
+}
+
+/// Execute a command list, drawing on top of the existing contents of `target`.
+fn rasterize(target: &mut Pixmap, quality: StageQuality, commands: CommandList) {
+    let low_quality = quality == StageQuality::Low;
+    let mut rasterizer = Rasterizer {
+        base: target,
+        layers: Vec::new(),
+        masks: Vec::new(),
+        mask_state: MaskState::DrawContent,
+        anti_alias: !low_quality,
+        allow_smoothing: !low_quality,
+    };
+    commands.execute(&mut rasterizer);
+}
+
+/// The current masking behavior of the rasterizer.
+enum MaskState {
+    /// Content is being drawn, clipped by the top of the mask stack.
+    DrawContent,
+
+    /// A mask is being drawn into the given pixmap.
+    DrawMask(Pixmap),
+
+    /// A mask is being cleared. Nothing needs to be drawn for this.
+    ClearMask,
+}
+
+struct Rasterizer<'a> {
+    base: &'a mut Pixmap,
+
+    /// Intermediate pixmaps for blend modes, drawn instead of `base` when present.
+    layers: Vec<Pixmap>,
+
+    /// Active masks, each already intersected with the ones below it.
+    masks: Vec<Mask>,
+
+    mask_state: MaskState,
+    anti_alias: bool,
+    allow_smoothing: bool,
+}
+
+impl Rasterizer<'_> {
+    /// Run `f` with the pixmap that should be drawn into and the mask to clip it by.
+    ///
+    /// While a mask is being drawn, `drawing_mask` is true, and callers should
+    /// draw opaquely, as Flash ignores the colors and alpha of masks.
+    fn draw(&mut self, f: impl FnOnce(&mut Pixmap, Option<&Mask>, bool)) {
+        match &mut self.mask_state {
+            MaskState::DrawContent => {
+                let target = self.layers.last_mut().unwrap_or(&mut *self.base);
+                f(target, self.masks.last(), false)
+            }
+            MaskState::DrawMask(pixmap) => f(pixmap, None, true),
+            MaskState::ClearMask => (),
+        }
+    }
+
+    fn paint_options(&self) -> PaintOptions {
+        PaintOptions {
+            anti_alias: self.anti_alias,
+            allow_smoothing: self.allow_smoothing,
+        }
+    }
+
+    fn draw_lines(&mut self, color: Color, mut matrix: Matrix, closed: bool) {
+        matrix.tx += Twips::HALF;
+        matrix.ty += Twips::HALF;
+
+        let mut builder = PathBuilder::new();
+        builder.move_to(0.0, 0.0);
+        builder.line_to(1.0, 0.0);
+        if closed {
+            builder.line_to(1.0, 1.0);
+            builder.line_to(0.0, 1.0);
+            builder.close();
+        }
+        // The transform is applied to the path directly, so that it doesn't
+        // affect the thickness of the line.
+        let Some(path) = builder
+            .finish()
+            .and_then(|path| path.transform(to_skia_transform(&matrix)))
+        else {
+            return;
+        };
+
+        let anti_alias = self.anti_alias;
+        self.draw(|target, mask, drawing_mask| {
+            let color = if drawing_mask { Color::BLACK } else { color };
+            target.stroke_path(
+                &path,
+                &solid_paint(color, anti_alias),
+                &Stroke::default(),
+                tiny_skia::Transform::identity(),
+                mask,
+            );
+        });
+    }
+}
+
+impl CommandHandler for Rasterizer<'_> {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        let ts = to_skia_transform(&matrix);
+
+        let quality = if smoothing && self.allow_smoothing {
+            FilterQuality::Bilinear
+        } else {
+            FilterQuality::Nearest
+        };
+        let anti_alias = self.anti_alias;
+        let pixmap = as_software_bitmap(&bitmap).pixmap.borrow();
+        self.draw(|target, mask, drawing_mask| {
+            if drawing_mask {
+                if let Some(rect) =
+                    Rect::from_xywh(0.0, 0.0, pixmap.width() as f32, pixmap.height() as f32)
+                {
+                    target.fill_rect(rect, &solid_paint(Color::BLACK, anti_alias), ts, mask);
+                }
+                return;
+            }
+
+            with_color_transform(&pixmap, &transform.color_transform, |pixmap, opacity| {
+                let paint = PixmapPaint {
+                    opacity,
+                    quality,
+                    ..Default::default()
+                };
+                target.draw_pixmap(0, 0, pixmap, &paint, ts, mask);
+            });
+        });
+    }
+
+    fn render_stage3d(&mut self, _bitmap: BitmapHandle, _transform: Transform) {
+        panic!("Stage3D should not have been created on software backend")
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let shape = as_shape_data(&shape);
+        let ts = to_skia_transform(&transform.matrix);
+        let options = self.paint_options();
+        let mut line_scales = LineScales::new(&transform.matrix);
+        // Everything in a mask is drawn opaquely.
+        let mask_fill = Fill::Color(Color::BLACK);
+        self.draw(|target, mask, drawing_mask| {
+            for command in &shape.0 {
+                match command {
+                    ShapeCommand::Fill { path, fill } => {
+                        let fill = if drawing_mask { &mask_fill } else { fill };
+                        with_paint(fill, &transform.color_transform, options, |paint| {
+                            target.fill_path(path, paint, FillRule::EvenOdd, ts, mask);
+                        });
+                    }
+                    ShapeCommand::Stroke {
+                        path,
+                        fill,
+                        width,
+                        line_cap,
+                        line_join,
+                        miter_limit,
+                        scale_mode,
+                    } => {
+                        // The transform is applied to the path directly, so that
+                        // the stroke width can be scaled as Flash does it.
+                        let Some(path) = path.clone().transform(ts) else {
+                            continue;
+                        };
+                        let stroke = Stroke {
+                            width: line_scales.transform_width(*width, *scale_mode),
+                            miter_limit: *miter_limit,
+                            line_cap: *line_cap,
+                            line_join: *line_join,
+                            dash: None,
+                        };
+                        let fill = if drawing_mask { &mask_fill } else { fill };
+                        with_paint(fill, &transform.color_transform, options, |paint| {
+                            let mut paint = paint.clone();
+                            paint.shader.transform(ts);
+                            target.stroke_path(
+                                &path,
+                                &paint,
+                                &stroke,
+                                tiny_skia::Transform::identity(),
+                                mask,
+                            );
+                        });
+                    }
+                }
+            }
+        });
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let ts = to_skia_transform(&matrix);
+        let anti_alias = self.anti_alias;
+        self.draw(|target, mask, drawing_mask| {
+            let color = if drawing_mask { Color::BLACK } else { color };
+            if let Some(rect) = Rect::from_xywh(0.0, 0.0, 1.0, 1.0) {
+                target.fill_rect(rect, &solid_paint(color, anti_alias), ts, mask);
+            }
+        });
+    }
+
+    fn draw_line(&mut self, color: Color, matrix: Matrix) {
+        self.draw_lines(color, matrix, false);
+    }
+
+    fn draw_line_rect(&mut self, color: Color, matrix: Matrix) {
+        self.draw_lines(color, matrix, true);
+    }
+
+    fn push_mask(&mut self) {
+        if matches!(self.mask_state, MaskState::DrawContent) {
+            self.mask_state =
+                MaskState::DrawMask(create_pixmap(self.base.width(), self.base.height()));
+        }
+    }
+
+    fn activate_mask(&mut self) {
+        let MaskState::DrawMask(pixmap) = &self.mask_state else {
+            return;
+        };
+        let mut mask = Mask::from_pixmap(pixmap.as_ref(), MaskType::Alpha);
+        if let Some(parent) = self.masks.last() {
+            for (coverage, parent) in mask.data_mut().iter_mut().zip(parent.data()) {
+                *coverage = mul_u8(*coverage, *parent);
+            }
+        }
+        self.masks.push(mask);
+        self.mask_state = MaskState::DrawContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        if matches!(self.mask_state, MaskState::DrawContent) {
+            self.mask_state = MaskState::ClearMask;
+        }
+    }
+
+    fn pop_mask(&mut self) {
+        if matches!(self.mask_state, MaskState::ClearMask) {
+            self.masks.pop();
+            self.mask_state = MaskState::DrawContent;
+        }
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let blend_mode = match blend_mode {
+            RenderBlendMode::Builtin(blend_mode) => blend_mode,
+            RenderBlendMode::Shader(_) => {
+                tracing::warn!("Shader blend modes are not supported by the software renderer");
+                BlendMode::Normal
+            }
+        };
+
+        // Masks are drawn as a whole, and normal content needs no intermediate layer.
+        if blend_mode == BlendMode::Normal || !matches!(self.mask_state, MaskState::DrawContent) {
+            commands.execute(self);
+            return;
+        }
+
+        // The layer is clipped by the current mask when it's composited,
+        // so its own contents are drawn without it.
+        self.layers
+            .push(create_pixmap(self.base.width(), self.base.height()));
+        let masks = std::mem::take(&mut self.masks);
+        commands.execute(self);
+        self.masks = masks;
+        let Some(layer) = self.layers.pop() else {
+            return;
+        };
+
+        self.draw(|target, mask, _| composite(target, layer, blend_mode, mask));
+    }
+}
+
+#[derive(Clone, Copy)]
+struct PaintOptions {
+    anti_alias: bool,
+    allow_smoothing: bool,
+}
+
+/// Run `f` with a paint for the given fill, with a color transform applied.
+///
+/// Nothing is drawn for fills that can't be represented, such as gradients
+/// with a degenerate matrix.
+fn with_paint(
+    fill: &Fill,
+    color_transform: &ColorTransform,
+    options: PaintOptions,
+    f: impl FnOnce(&Paint),
+) {
+    let shader = match fill {
+        Fill::Color(color) => Some(Shader::SolidColor(to_skia_color(color_transform * *color))),
+        Fill::LinearGradient(gradient) => LinearGradient::new(
+            tiny_skia::Point::from_xy(-16384.0, 0.0),
+            tiny_skia::Point::from_xy(16384.0, 0.0),
+            gradient_stops(gradient, color_transform),
+            spread_mode(gradient.spread),
+            to_skia_transform_swf(&gradient.matrix),
+        ),
+        Fill::RadialGradient {
+            gradient,
+            focal_point,
+        } => RadialGradient::new(
+            tiny_skia::Point::from_xy(focal_point * 16384.0, 0.0),
+            tiny_skia::Point::from_xy(0.0, 0.0),
+            16384.0,
+            gradient_stops(gradient, color_transform),
+            spread_mode(gradient.spread),
+            to_skia_transform_swf(&gradient.matrix),
+        ),
+        Fill::Bitmap {
+            handle,
+            matrix,
+            is_smoothed,
+            is_repeating,
+        } => {
+            let quality = if *is_smoothed && options.allow_smoothing {
+                FilterQuality::Bilinear
+            } else {
+                FilterQuality::Nearest
+            };
+            let spread_mode = if *is_repeating {
+                SpreadMode::Repeat
+            } else {
+                SpreadMode::Pad
+            };
+            let pixmap = as_software_bitmap(handle).pixmap.borrow();
+            with_color_transform(&pixmap, color_transform, |pixmap, opacity| {
+                let paint = Paint {
+                    shader: Pattern::new(
+                        pixmap,
+                        spread_mode,
+                        quality,
+                        opacity,
+                        to_skia_transform_swf(matrix),
+                    ),
+                    anti_alias: options.anti_alias,
+                    ..Default::default()
+                };
+                f(&paint);
+            });
+            return;
+        }
+    };
+
+    if let Some(shader) = shader {
+        f(&Paint {
+            shader,
+            anti_alias: options.anti_alias,
+            ..Default::default()
+        });
+    }
+}
+
+fn solid_paint(color: Color, anti_alias: bool) -> Paint<'static> {
+    Paint {
+        shader: Shader::SolidColor(to_skia_color(color)),
+        anti_alias,
+        ..Default::default()
+    }
+}
+
+fn gradient_stops(gradient: &swf::Gradient, color_transform: &ColorTransform) -> Vec<GradientStop> {
+    // TODO: Linear RGB interpolation is drawn as normal RGB interpolation.
+    gradient
+        .records
+        .iter()
+        .map(|record| {
+            GradientStop::new(
+                f32::from(record.ratio) / 255.0,
+                to_skia_color(color_transform * record.color),
+            )
+        })
+        .collect()
+}
+
+fn spread_mode(spread: swf::GradientSpread) -> SpreadMode {
+    match spread {
+        swf::GradientSpread::Pad => SpreadMode::Pad,
+        swf::GradientSpread::Reflect => SpreadMode::Reflect,
+        swf::GradientSpread::Repeat => SpreadMode::Repeat,
+    }
+}
+
+/// Run `f` with a bitmap that has a color transform applied, and the opacity to draw it with.
+///
+/// Transforms that only change alpha are applied through the opacity, avoiding a copy.
+fn with_color_transform(
+    pixmap: &Pixmap,
+    color_transform: &ColorTransform,
+    f: impl FnOnce(tiny_skia::PixmapRef, f32),
+) {
+    if color_transform.r_multiply.is_one()
+        && color_transform.g_multiply.is_one()
+        && color_transform.b_multiply.is_one()
+        && color_transform.r_add == 0
+        && color_transform.g_add == 0
+        && color_transform.b_add == 0
+        && color_transform.a_add == 0
+    {
+        f(
+            pixmap.as_ref(),
+            f32::from(color_transform.a_multiply).clamp(0.0, 1.0),
+        );
+    } else {
+        let mut transformed = pixmap.clone();
+        apply_color_transform(transformed.data_mut(), color_transform);
+        f(transformed.as_ref(), 1.0);
+    }
+}
+
+/// Apply a color transform to premultiplied RGBA pixels.
+fn apply_color_transform(data: &mut [u8], color_transform: &ColorTransform) {
+    let mult = color_transform.mult_rgba_normalized();
+    let add = color_transform.add_rgba_normalized();
+    for pixel in data.chunks_exact_mut(4) {
+        let alpha = f32::from(pixel[3]) / 255.0;
+        let new_alpha = (alpha * mult[3] + add[3]).clamp(0.0, 1.0);
+        for ((channel, mult), add) in pixel[..3].iter_mut().zip(mult).zip(add) {
+            let straight = if alpha > 0.0 {
+                f32::from(*channel) / 255.0 / alpha
+            } else {
+                0.0
+            };
+            let transformed = (straight * mult + add).clamp(0.0, 1.0);
+            *channel = (transformed * new_alpha * 255.0).round() as u8;
+        }
+        pixel[3] = (new_alpha * 255.0).round() as u8;
+    }
+}
+
+/// Composite a blend mode layer onto `target`.
+fn composite(target: &mut Pixmap, mut layer: Pixmap, blend_mode: BlendMode, mask: Option<&Mask>) {
+    let blend_mode = match blend_mode {
+        BlendMode::Normal | BlendMode::Layer => tiny_skia::BlendMode::SourceOver,
+        BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
+        BlendMode::Screen => tiny_skia::BlendMode::Screen,
+        BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
+        BlendMode::Darken => tiny_skia::BlendMode::Darken,
+        BlendMode::Difference => tiny_skia::BlendMode::Difference,
+        BlendMode::Add => tiny_skia::BlendMode::Plus,
+        BlendMode::Alpha => tiny_skia::BlendMode::DestinationIn,
+        BlendMode::Erase => tiny_skia::BlendMode::DestinationOut,
+        BlendMode::Overlay => tiny_skia::BlendMode::Overlay,
+        BlendMode::HardLight => tiny_skia::BlendMode::HardLight,
+        BlendMode::Subtract | BlendMode::Invert => {
+            // tiny-skia has no equivalent to these, so blend them by hand.
+            if let Some(mask) = mask {
+                layer.apply_mask(mask);
+            }
+            let blend = if blend_mode == BlendMode::Subtract {
+                subtract_pixel
+            } else {
+                invert_pixel
+            };
+            for (dst, src) in target
+                .data_mut()
+                .chunks_exact_mut(4)
+                .zip(layer.data().chunks_exact(4))
+            {
+                blend(dst, src);
+            }
+            return;
+        }
+    };
+
+    let paint = PixmapPaint {
+        blend_mode,
+        ..Default::default()
+    };
+    target.draw_pixmap(
+        0,
+        0,
+        layer.as_ref(),
+        &paint,
+        tiny_skia::Transform::identity(),
+        mask,
+    );
+}
+
+fn subtract_pixel(dst: &mut [u8], src: &[u8]) {
+    for (dst, src) in dst[..3].iter_mut().zip(src) {
+        *dst = dst.saturating_sub(*src);
+    }
+}
+
+fn invert_pixel(dst: &mut [u8], src: &[u8]) {
+    let (src_alpha, dst_alpha) = (src[3], dst[3]);
+    for channel in &mut dst[..3] {
+        let inverted = dst_alpha - (*channel).min(dst_alpha);
+        *channel = mul_u8(*channel, 255 - src_alpha) + mul_u8(inverted, src_alpha);
+    }
+}
+
+/// Multiply two 8-bit fractions.
+fn mul_u8(a: u8, b: u8) -> u8 {
+    ((u16::from(a) * u16::from(b) + 127) / 255) as u8
+}
+
+fn to_skia_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(color.r, color.g, color.b, color.a)
+}
+
+fn to_skia_transform(matrix: &Matrix) -> tiny_skia::Transform {
+    tiny_skia::Transform::from_row(
+        matrix.a,
+        matrix.b,
+        matrix.c,
+        matrix.d,
+        matrix.tx.to_pixels() as f32,
+        matrix.ty.to_pixels() as f32,
+    )
+}
+
+/// Converts a fill matrix, which maps onto the twips of the shape, to a
+/// transform onto the pixels of the shape.
+fn to_skia_transform_swf(matrix: &swf::Matrix) -> tiny_skia::Transform {
+    tiny_skia::Transform::from_row(
+        matrix.a.to_f32() / 20.0,
+        matrix.b.to_f32() / 20.0,
+        matrix.c.to_f32() / 20.0,
+        matrix.d.to_f32() / 20.0,
+        matrix.tx.to_pixels() as f32,
+        matrix.ty.to_pixels() as f32,
+    )
+}
+
+/// Convert a series of `DrawCommand`s to a path, in pixels.
+fn draw_commands_to_path(commands: &[DrawCommand], is_closed: bool) -> Option<Path> {
+    let point =
+        |point: &swf::Point<Twips>| (point.x.to_pixels() as f32, point.y.to_pixels() as f32);
+
+    let mut builder = PathBuilder::new();
+    for command in commands {
+        match command {
+            DrawCommand::MoveTo(move_to) => {
+                let (x, y) = point(move_to);
+                builder.move_to(x, y);
+            }
+            DrawCommand::LineTo(line_to) => {
+                let (x, y) = point(line_to);
+                builder.line_to(x, y);
+            }
+            DrawCommand::QuadraticCurveTo { control, anchor } => {
+                let (x1, y1) = point(control);
+                let (x, y) = point(anchor);
+                builder.quad_to(x1, y1, x, y);
+            }
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => {
+                let (x1, y1) = point(control_a);
+                let (x2, y2) = point(control_b);
+                let (x, y) = point(anchor);
+                builder.cubic_to(x1, y1, x2, y2, x, y);
+            }
+        }
+    }
+
+    if is_closed {
+        builder.close();
+    }
+
+    builder.finish()
+}
+
+fn swf_shape_to_commands(
+    shape: &DistilledShape,
+    bitmap_source: &dyn BitmapSource,
+    backend: &mut SoftwareRenderBackend,
+) -> Vec<ShapeCommand> {
+    use ruffle_render::shape_utils::DrawPath;
+    use swf::{FillStyle, LineCapStyle, LineJoinStyle};
+
+    let mut convert_fill = |style: &FillStyle| -> Option<Fill> {
+        Some(match style {
+            FillStyle::Color(color) => Fill::Color(*color),
+            FillStyle::LinearGradient(gradient) => Fill::LinearGradient(gradient.clone()),
+            FillStyle::RadialGradient(gradient) => Fill::RadialGradient {
+                gradient: gradient.clone(),
+                focal_point: 0.0,
+            },
+            FillStyle::FocalGradient {
+                gradient,
+                focal_point,
+            } => Fill::RadialGradient {
+                gradient: gradient.clone(),
+                focal_point: focal_point.to_f32().clamp(-0.98, 0.98),
+            },
+            FillStyle::Bitmap {
+                id,
+                matrix,
+                is_smoothed,
+                is_repeating,
+            } => {
+                let Some(handle) = bitmap_source.bitmap_handle(*id, backend) else {
+                    tracing::warn!("Couldn't fill shape with unknown bitmap {}", id);
+                    return None;
+                };
+                Fill::Bitmap {
+                    handle,
+                    matrix: *matrix,
+                    is_smoothed: *is_smoothed,
+                    is_repeating: *is_repeating,
+                }
+            }
+        })
+    };
+
+    let mut commands = vec![];
+    for path in &shape.paths {
+        match path {
+            DrawPath::Fill {
+                style,
+                commands: draw_commands,
+                ..
+            } => {
+                let (Some(path), Some(fill)) = (
+                    draw_commands_to_path(draw_commands, false),
+                    convert_fill(style),
+                ) else {
+                    continue;
+                };
+                commands.push(ShapeCommand::Fill { path, fill });
+            }
+            DrawPath::Stroke {
+                style,
+                is_closed,
+                commands: draw_commands,
+            } => {
+                let (Some(path), Some(fill)) = (
+                    draw_commands_to_path(draw_commands, *is_closed),
+                    convert_fill(style.fill_style()),
+                ) else {
+                    continue;
+                };
+                let line_cap = match style.start_cap() {
+                    LineCapStyle::Round => LineCap::Round,
+                    LineCapStyle::Square => LineCap::Square,
+                    LineCapStyle::None => LineCap::Butt,
+                };
+                let (line_join, miter_limit) = match style.join_style() {
+                    LineJoinStyle::Round => (LineJoin::Round, 4.0),
+                    LineJoinStyle::Bevel => (LineJoin::Bevel, 4.0),
+                    LineJoinStyle::Miter(limit) => (LineJoin::Miter, limit.to_f32().max(1.0)),
+                };
+                commands.push(ShapeCommand::Stroke {
+                    path,
+                    fill,
+                    width: style.width().to_pixels() as f32,
+                    line_cap,
+                    line_join,
+                    miter_limit,
+                    scale_mode: match (style.allow_scale_x(), style.allow_scale_y()) {
+                        (false, false) => LineScaleMode::None,
+                        (true, false) => LineScaleMode::Horizontal,
+                        (false, true) => LineScaleMode::Vertical,
+                        (true, true) => LineScaleMode::Both,
+                    },
+                });
+            }
+        }
+    }
+
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::Fixed8;
+
+    #[test]
+    fn applies_color_transform_to_premultiplied_pixels() {
+        // Half-transparent white and a fully transparent pixel.
+        let mut data = [128, 128, 128, 128, 0, 0, 0, 0];
+        let color_transform = ColorTransform {
+            g_multiply: Fixed8::from_f32(0.5),
+            b_multiply: Fixed8::ZERO,
+            r_add: -255,
+            b_add: 255,
+            ..Default::default()
+        };
+        apply_color_transform(&mut data, &color_transform);
+        assert_eq!(data, [0, 64, 128, 128, 0, 0, 0, 0]);
+
+        let mut data = [100, 50, 25, 200];
+        apply_color_transform(&mut data, &ColorTransform::IDENTITY);
+        assert_eq!(data, [100, 50, 25, 200]);
+    }
+
+    #[test]
+    fn blends_by_hand() {
+        let mut dst = [200, 100, 0, 255];
+        subtract_pixel(&mut dst, &[50, 150, 0, 255]);
+        assert_eq!(dst, [150, 0, 0, 255]);
+
+        let mut dst = [200, 100, 0, 255];
+        invert_pixel(&mut dst, &[0, 0, 0, 255]);
+        assert_eq!(dst, [55, 155, 255, 255]);
+
+        // Transparent sources leave the destination untouched.
+        let mut dst = [200, 100, 0, 255];
+        invert_pixel(&mut dst, &[0, 0, 0, 0]);
+        assert_eq!(dst, [200, 100, 0, 255]);
+    }
+}
@@ -34,6 +34,30 @@ pub trait RenderBackend: Downcast {
         bitmap_source: &dyn BitmapSource,
     ) -> ShapeHandle;
 
+    /// Whether this backend draws shapes from the meshes of
+    /// [`ShapeTessellator`](crate::tessellator::ShapeTessellator), and so
+    /// accepts them through [`RenderBackend::register_mesh`].
+    #[cfg(feature = "tessellator")]
+    fn supports_meshes(&self) -> bool {
+        false
+    }
+
+    /// Registers a shape that was already tessellated, such as one whose
+    /// tessellation is shared with other players.
+    ///
+    /// This is only called when `supports_meshes` returns true. Returns `None` if
+    /// the backend can't draw the mesh, in which case the shape should be
+    /// registered with [`RenderBackend::register_shape`] instead.
+    #[cfg(feature = "tessellator")]
+    fn register_mesh(
+        &mut self,
+        _shape_id: swf::CharacterId,
+        _mesh: crate::tessellator::Mesh,
+        _bitmap_source: &dyn BitmapSource,
+    ) -> Option<ShapeHandle> {
+        None
+    }
+
     fn render_offscreen(
         &mut self,
         handle: BitmapHandle,
@@ -1,7 +1,7 @@
 use crate::backend::ShapeHandle;
 use crate::bitmap::{BitmapHandle, PixelSnapping};
 use crate::matrix::Matrix;
-use crate::pixel_bender::PixelBenderShaderHandle;
+use crate::pixel_bender::{PixelBenderShaderArgument, PixelBenderShaderHandle};
 use crate::transform::Transform;
 use swf::{BlendMode, Color};
 
@@ -28,13 +28,24 @@ pub trait CommandHandler {
 
 /// Holds either a normal BlendMode, or the shader for BlendMode.SHADER.
 ///
-/// We cannot store the `PixelBenderShaderHandle` directly in `ExtendedBlendMode`,
+/// We cannot store the `BlendShader` directly in `ExtendedBlendMode`,
 /// since we need to remember the shader even if the blend mode is changed
 /// to something else (so that the shader will still be used if we switch back)
 #[derive(Debug, Clone)]
 pub enum RenderBlendMode {
     Builtin(BlendMode),
-    Shader(PixelBenderShaderHandle),
+    Shader(BlendShader),
+}
+
+/// A shader assigned to `DisplayObject.blendShader`.
+#[derive(Debug, Clone)]
+pub struct BlendShader {
+    pub shader: PixelBenderShaderHandle,
+
+    /// The values of the shader's parameters and inputs when it was assigned, as Flash Player
+    /// doesn't pick up later changes to them. The first two image inputs are left empty:
+    /// they're the background and the display object being blended.
+    pub arguments: Vec<PixelBenderShaderArgument<'static>>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -245,11 +245,13 @@ impl Default for ShapeTessellator {
     }
 }
 
+#[derive(Clone)]
 pub struct Mesh {
     pub draws: Vec<Draw>,
     pub gradients: Vec<Gradient>,
 }
 
+#[derive(Clone)]
 pub struct Draw {
     pub draw_type: DrawType,
     pub vertices: Vec<Vertex>,
@@ -257,6 +259,7 @@ pub struct Draw {
     pub mask_index_count: u32,
 }
 
+#[derive(Clone)]
 pub enum DrawType {
     Color,
     Gradient {
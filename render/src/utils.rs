@@ -21,7 +21,7 @@ pub fn determine_jpeg_tag_format(data: &[u8]) -> JpegTagFormat {
         [0xff, 0xd8, ..] => JpegTagFormat::Jpeg,
         [0xff, 0xd9, 0xff, 0xd8, ..] => JpegTagFormat::Jpeg, // erroneous header in SWF
         [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, ..] => JpegTagFormat::Png,
-        [0x47, 0x49, 0x46, 0x38, 0x39, 0x61, ..] => JpegTagFormat::Gif,
+        [0x47, 0x49, 0x46, 0x38, 0x37 | 0x39, 0x61, ..] => JpegTagFormat::Gif,
         _ => JpegTagFormat::Unknown,
     }
 }
@@ -42,6 +42,18 @@ pub fn decode_define_bits_jpeg(data: &[u8], alpha_data: Option<&[u8]>) -> Result
     }
 }
 
+/// Decodes an image loaded by a `Loader`, turning JPEGs upright according to their EXIF orientation.
+/// The data is returned with pre-multiplied alpha.
+pub fn decode_loaded_image(data: &[u8]) -> Result<Bitmap, Error> {
+    match determine_jpeg_tag_format(data) {
+        JpegTagFormat::Jpeg => {
+            let orientation = jpeg_orientation(data);
+            Ok(apply_orientation(decode_jpeg(data, None)?, orientation))
+        }
+        _ => decode_define_bits_jpeg(data, None),
+    }
+}
+
 pub fn decode_define_bits_jpeg_dimensions(data: &[u8]) -> Result<(u16, u16), Error> {
     let format = determine_jpeg_tag_format(data);
     match format {
@@ -91,7 +103,12 @@ pub fn remove_invalid_jpeg_data(data: &[u8]) -> Cow<[u8]> {
     // See https://github.com/ruffle-rs/ruffle/issues/8775 for various examples.
 
     // JPEG markers
-    const SOF0: u8 = 0xC0; // Start of frame
+    const SOF0: u8 = 0xC0; // Start of frame (baseline)
+    const SOF15: u8 = 0xCF; // Start of frame (lossless, differential, arithmetic coding)
+    const DHT: u8 = 0xC4; // Define Huffman table
+    const JPG: u8 = 0xC8; // Reserved
+    const DAC: u8 = 0xCC; // Define arithmetic coding conditioning
+    const SOS: u8 = 0xDA; // Start of scan
     const RST0: u8 = 0xD0; // Restart (we shouldn't see this before SOS, but just in case)
     const RST7: u8 = 0xD7;
     const SOI: u8 = 0xD8; // Start of image
@@ -121,7 +138,11 @@ pub fn remove_invalid_jpeg_data(data: &[u8]) -> Cow<[u8]> {
                 }
                 // EOI, SOI, RST markers do not include a size.
                 [0xFF, EOI | SOI | RST0..=RST7, _, _] => 0,
-                [0xFF, SOF0, _, _] => {
+                // Progressive JPEGs use other SOF markers than SOF0.
+                // Stop at any of them, or at the image data at the latest.
+                [0xFF, marker @ (SOF0..=SOF15 | SOS), _, _]
+                    if !matches!(*marker, DHT | JPG | DAC) =>
+                {
                     // No invalid sequence found before SOF marker, return data as-is.
                     break data.into();
                 }
@@ -421,26 +442,188 @@ fn decode_gif_dimensions(data: &[u8]) -> Result<(u16, u16), Error> {
     Ok((reader.width(), reader.height()))
 }
 
-/// Decodes the bitmap data in DefineBitsLossless tag into RGBA.
-/// DefineBitsLossless is Zlib encoded pixel data (similar to PNG), possibly
-/// palletized.
+/// Decodes the first frame of a GIF into RGBA.
 fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
     let mut decode_options = gif::DecodeOptions::new();
     decode_options.set_color_output(gif::ColorOutput::RGBA);
     let mut reader = decode_options.read_info(data)?;
+    validate_size(reader.width(), reader.height())?;
+    let (width, height) = (reader.width(), reader.height());
     let frame = reader.read_next_frame()?.ok_or(Error::EmptyGif)?;
+    // The frame may only cover a part of the image.
+    let mut canvas = vec![0; usize::from(width) * usize::from(height) * 4];
+    draw_gif_frame(&mut canvas, width, height, frame);
     // GIFs embedded in a DefineBitsJPEG tag will not have premultiplied alpha and need to be converted before sending to the renderer.
-    let mut data = frame.buffer.to_vec();
-    premultiply_alpha_rgba(&mut data);
+    premultiply_alpha_rgba(&mut canvas);
 
     Ok(Bitmap::new(
-        frame.width.into(),
-        frame.height.into(),
+        width.into(),
+        height.into(),
         BitmapFormat::Rgba,
-        data,
+        canvas,
     ))
 }
 
+/// A frame of an animated GIF.
+pub struct GifFrame {
+    /// The whole image as shown during this frame, with pre-multiplied alpha.
+    pub bitmap: Bitmap,
+
+    /// How long this frame is shown, in hundredths of a second.
+    pub delay: u16,
+}
+
+/// Decodes all frames of an animated GIF.
+///
+/// Flash only ever shows the first frame of a GIF, but embedders may want to play them.
+pub fn decode_gif_frames(data: &[u8]) -> Result<Vec<GifFrame>, Error> {
+    let mut decode_options = gif::DecodeOptions::new();
+    decode_options.set_color_output(gif::ColorOutput::RGBA);
+    let mut reader = decode_options.read_info(data)?;
+    validate_size(reader.width(), reader.height())?;
+    let (width, height) = (reader.width(), reader.height());
+
+    let mut canvas = vec![0; usize::from(width) * usize::from(height) * 4];
+    let mut frames = Vec::new();
+    while let Some(frame) = reader.read_next_frame()? {
+        let previous = (frame.dispose == gif::DisposalMethod::Previous).then(|| canvas.clone());
+        draw_gif_frame(&mut canvas, width, height, frame);
+
+        let mut data = canvas.clone();
+        premultiply_alpha_rgba(&mut data);
+        frames.push(GifFrame {
+            bitmap: Bitmap::new(width.into(), height.into(), BitmapFormat::Rgba, data),
+            delay: frame.delay,
+        });
+
+        match frame.dispose {
+            gif::DisposalMethod::Background => {
+                for row in gif_frame_rows(width, height, frame) {
+                    canvas[row].fill(0);
+                }
+            }
+            gif::DisposalMethod::Previous => {
+                canvas = previous.expect("Saved before drawing the frame");
+            }
+            gif::DisposalMethod::Any | gif::DisposalMethod::Keep => {}
+        }
+    }
+
+    if frames.is_empty() {
+        return Err(Error::EmptyGif);
+    }
+    Ok(frames)
+}
+
+/// Draws a GIF frame over the RGBA pixels of the whole image, leaving its transparent pixels out.
+fn draw_gif_frame(canvas: &mut [u8], width: u16, height: u16, frame: &gif::Frame) {
+    let frame_stride = usize::from(frame.width) * 4;
+    for (y, row) in gif_frame_rows(width, height, frame).enumerate() {
+        let source = &frame.buffer[y * frame_stride..][..row.len()];
+        for (dst, src) in canvas[row].chunks_exact_mut(4).zip(source.chunks_exact(4)) {
+            if src[3] != 0 {
+                dst.copy_from_slice(src);
+            }
+        }
+    }
+}
+
+/// The ranges of the whole image's RGBA pixels that a GIF frame covers, one per row.
+fn gif_frame_rows(
+    width: u16,
+    height: u16,
+    frame: &gif::Frame,
+) -> impl Iterator<Item = std::ops::Range<usize>> {
+    let left = usize::from(frame.left.min(width));
+    let right = usize::from(frame.left.saturating_add(frame.width).min(width));
+    let top = usize::from(frame.top.min(height));
+    let bottom = usize::from(frame.top.saturating_add(frame.height).min(height));
+    let stride = usize::from(width) * 4;
+    (top..bottom).map(move |y| y * stride + left * 4..y * stride + right * 4)
+}
+
+/// Reads the EXIF orientation of a JPEG, from 1 (upright) to 8.
+fn jpeg_orientation(jpeg_data: &[u8]) -> u16 {
+    let jpeg_data = remove_invalid_jpeg_data(jpeg_data);
+    let mut decoder = jpeg_decoder::Decoder::new(&jpeg_data[..]);
+    if decoder.read_info().is_err() {
+        return 1;
+    }
+    decoder.exif_data().and_then(exif_orientation).unwrap_or(1)
+}
+
+/// Finds the orientation tag in the first IFD of EXIF data, which starts with a TIFF header.
+fn exif_orientation(exif: &[u8]) -> Option<u16> {
+    const ORIENTATION: u16 = 0x0112;
+
+    let big_endian = match exif.get(..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    let read_u16 = |pos: usize| {
+        let bytes = exif.get(pos..pos + 2)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let read_u32 = |pos: usize| {
+        let bytes = exif.get(pos..pos + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+
+    let ifd = read_u32(4)? as usize;
+    let entries = read_u16(ifd)?;
+    (0..usize::from(entries))
+        .map(|i| ifd + 2 + i * 12)
+        .find(|&entry| read_u16(entry) == Some(ORIENTATION))
+        .and_then(|entry| read_u16(entry + 8))
+}
+
+/// Turns a bitmap upright according to an EXIF orientation.
+fn apply_orientation(bitmap: Bitmap, orientation: u16) -> Bitmap {
+    let bytes_per_pixel = match bitmap.format() {
+        BitmapFormat::Rgb => 3,
+        BitmapFormat::Rgba => 4,
+        _ => return bitmap,
+    };
+    if !(2..=8).contains(&orientation) {
+        return bitmap;
+    }
+
+    let (width, height) = (bitmap.width() as usize, bitmap.height() as usize);
+    let transposed = orientation >= 5;
+    let (new_width, new_height) = if transposed {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    let source = bitmap.data();
+    let mut data = Vec::with_capacity(source.len());
+    for y in 0..new_height {
+        for x in 0..new_width {
+            let (source_x, source_y) = match orientation {
+                2 => (width - 1 - x, y),
+                3 => (width - 1 - x, height - 1 - y),
+                4 => (x, height - 1 - y),
+                5 => (y, x),
+                6 => (y, height - 1 - x),
+                7 => (width - 1 - y, height - 1 - x),
+                _ => (width - 1 - y, x),
+            };
+            let pos = (source_y * width + source_x) * bytes_per_pixel;
+            data.extend_from_slice(&source[pos..pos + bytes_per_pixel]);
+        }
+    }
+    Bitmap::new(new_width as u32, new_height as u32, bitmap.format(), data)
+}
+
 /// Converts standard RBGA to premultiplied alpha.
 fn premultiply_alpha_rgba(rgba: &mut [u8]) {
     rgba.chunks_exact_mut(4).for_each(|rgba| {
@@ -474,3 +657,39 @@ fn decompress_zlib(data: &[u8]) -> Result<Vec<u8>, Error> {
     out_data.shrink_to_fit();
     Ok(out_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exif_orientation_is_read_in_both_byte_orders() {
+        let little_endian = [
+            b'I', b'I', 42, 0, 8, 0, 0, 0, // TIFF header
+            1, 0, // One entry
+            0x12, 0x01, 3, 0, 1, 0, 0, 0, 6, 0, 0, 0, // Orientation = 6
+        ];
+        assert_eq!(exif_orientation(&little_endian), Some(6));
+
+        let big_endian = [
+            b'M', b'M', 0, 42, 0, 0, 0, 8, // TIFF header
+            0, 1, // One entry
+            0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 3, 0, 0, // Orientation = 3
+        ];
+        assert_eq!(exif_orientation(&big_endian), Some(3));
+        assert_eq!(exif_orientation(b"MM\0*"), None);
+    }
+
+    #[test]
+    fn orientation_rotates_clockwise() {
+        // A 2x1 image of a red and a green pixel.
+        let bitmap = Bitmap::new(2, 1, BitmapFormat::Rgb, vec![255, 0, 0, 0, 255, 0]);
+        let rotated = apply_orientation(bitmap, 6);
+        assert_eq!((rotated.width(), rotated.height()), (1, 2));
+        assert_eq!(rotated.data(), &[255, 0, 0, 0, 255, 0]);
+
+        let bitmap = Bitmap::new(2, 1, BitmapFormat::Rgb, vec![255, 0, 0, 0, 255, 0]);
+        let rotated = apply_orientation(bitmap, 3);
+        assert_eq!(rotated.data(), &[0, 255, 0, 255, 0, 0]);
+    }
+}
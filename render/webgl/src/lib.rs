@@ -17,13 +17,13 @@ use ruffle_render::matrix::Matrix;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, GradientType};
 use ruffle_render::tessellator::{
-    Gradient as TessGradient, ShapeTessellator, Vertex as TessVertex,
+    Gradient as TessGradient, Mesh as TessMesh, ShapeTessellator, Vertex as TessVertex,
 };
 use ruffle_render::transform::Transform;
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
 use std::sync::Arc;
-use swf::{BlendMode, Color, Twips};
+use swf::{BlendMode, CharacterId, Color, Twips};
 use thiserror::Error;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
@@ -562,17 +562,36 @@ impl WebGlRenderBackend {
         Ok(())
     }
 
-    fn register_shape_internal(
+    fn register_tessellated_shape(
         &mut self,
-        shape: DistilledShape,
+        lyon_mesh: TessMesh,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let mesh = match self.register_mesh_internal(lyon_mesh, bitmap_source) {
+            Ok(draws) => Mesh {
+                draws,
+                gl2: self.gl2.clone(),
+                vao_ext: self.vao_ext.clone(),
+            },
+            Err(e) => {
+                log::error!("Couldn't register shape: {:?}", e);
+                Mesh {
+                    draws: vec![],
+                    gl2: self.gl2.clone(),
+                    vao_ext: self.vao_ext.clone(),
+                }
+            }
+        };
+        ShapeHandle(Arc::new(mesh))
+    }
+
+    fn register_mesh_internal(
+        &mut self,
+        lyon_mesh: TessMesh,
         bitmap_source: &dyn BitmapSource,
     ) -> Result<Vec<Draw>, Error> {
         use ruffle_render::tessellator::DrawType as TessDrawType;
 
-        let lyon_mesh = self
-            .shape_tessellator
-            .tessellate_shape(shape, bitmap_source);
-
         let mut draws = Vec::with_capacity(lyon_mesh.draws.len());
         for draw in lyon_mesh.draws {
             let num_indices = draw.indices.len() as i32;
@@ -1039,22 +1058,23 @@ impl RenderBackend for WebGlRenderBackend {
         shape: DistilledShape,
         bitmap_source: &dyn BitmapSource,
     ) -> ShapeHandle {
-        let mesh = match self.register_shape_internal(shape, bitmap_source) {
-            Ok(draws) => Mesh {
-                draws,
-                gl2: self.gl2.clone(),
-                vao_ext: self.vao_ext.clone(),
-            },
-            Err(e) => {
-                log::error!("Couldn't register shape: {:?}", e);
-                Mesh {
-                    draws: vec![],
-                    gl2: self.gl2.clone(),
-                    vao_ext: self.vao_ext.clone(),
-                }
-            }
-        };
-        ShapeHandle(Arc::new(mesh))
+        let lyon_mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        self.register_tessellated_shape(lyon_mesh, bitmap_source)
+    }
+
+    fn supports_meshes(&self) -> bool {
+        true
+    }
+
+    fn register_mesh(
+        &mut self,
+        _shape_id: CharacterId,
+        mesh: TessMesh,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Option<ShapeHandle> {
+        Some(self.register_tessellated_shape(mesh, bitmap_source))
     }
 
     fn submit_frame(
@@ -11,6 +11,7 @@ struct VertexOutput {
 @group(2) @binding(1) var texture: texture_2d<f32>;
 @group(2) @binding(2) var texture_sampler: sampler;
 override late_saturate: bool = false;
+override linear_blending: bool = false;
 
 @vertex
 fn main_vertex(in: common__VertexInput) -> VertexOutput {
@@ -36,5 +37,8 @@ fn main_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
             color = saturate(color);
         }
     }
+    if (linear_blending) {
+        color = common__srgb_to_linear(color);
+    }
     return color;
 }
@@ -13,6 +13,7 @@ struct VertexOutput {
 };
 
 @group(1) @binding(0) var<uniform> transforms: common__Transforms;
+override linear_blending: bool = false;
 
 @vertex
 fn main_vertex(in: VertexInput) -> VertexOutput {
@@ -23,5 +24,8 @@ fn main_vertex(in: VertexInput) -> VertexOutput {
 
 @fragment
 fn main_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    if (linear_blending) {
+        return common__srgb_to_linear(in.color);
+    }
     return in.color;
 }
@@ -36,6 +36,10 @@ struct common__VertexInput {
 /// Common uniform layout shared by all shaders.
 @group(0) @binding(0) var<uniform> common__globals: common__Globals;
 
+/// Shaders that draw colors of the movie declare a pipeline-overridable `bool` constant,
+/// `linear_blending`, which is set when drawing to an sRGB target. They then convert their
+/// color to linear space, so that the GPU blends it in linear space and converts it back.
+
 /// Converts a color from linear to sRGB color space.
 fn common__linear_to_srgb(linear_: vec4<f32>) -> vec4<f32> {
     var rgb: vec3<f32> = linear_.rgb;
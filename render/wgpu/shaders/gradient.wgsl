@@ -18,6 +18,7 @@ struct Gradient {
 @group(2) @binding(1) var<uniform> gradient: Gradient;
 @group(2) @binding(2) var texture: texture_2d<f32>;
 @group(2) @binding(3) var texture_sampler: sampler;
+override linear_blending: bool = false;
 
 struct GradientVertexInput {
     /// The position of the vertex in object space.
@@ -79,5 +80,8 @@ fn main_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     }
     let out = saturate(color * transforms.mult_color + transforms.add_color);
     let alpha = saturate(out.a);
+    if (linear_blending) {
+        return common__srgb_to_linear(vec4<f32>(out.rgb * alpha, alpha));
+    }
     return vec4<f32>(out.rgb * alpha, alpha);
 }
\ No newline at end of file
@@ -0,0 +1,427 @@
+//! Packing of small bitmaps into shared atlas pages.
+//!
+//! Movies with hundreds of tiny bitmaps would otherwise allocate a texture
+//! for each of them. Bitmaps in an atlas can only be drawn as a whole; any
+//! other use moves them into a texture of their own (see [`Texture::texture`]).
+//!
+//! [`Texture::texture`]: crate::Texture::texture
+
+use crate::descriptors::Descriptors;
+use crate::mesh::BitmapBinds;
+use crate::TextureTransforms;
+use etagere::{size2, AllocId, Allocation, AtlasAllocator, Size};
+use ruffle_render::bitmap::Bitmap;
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// The width and height of each atlas page.
+const PAGE_SIZE: u32 = 1024;
+
+/// The number of bitmaps each atlas page can hold, limited by the slots in
+/// its buffer of texture transforms.
+const REGIONS_PER_PAGE: u32 = 1024;
+
+/// The largest width or height of a bitmap that will be packed into an atlas.
+pub const MAX_ATLAS_BITMAP_SIZE: u32 = 64;
+
+/// The border around each bitmap, filled with its edge pixels so that
+/// filtering at the edges doesn't sample neighbouring bitmaps.
+const PADDING: u32 = 1;
+
+struct AtlasPage {
+    binds: Arc<AtlasPageBinds>,
+    allocator: AtlasAllocator,
+
+    /// Slots of the texture transforms buffer not used by any region.
+    free_slots: Vec<u32>,
+}
+
+impl AtlasPage {
+    fn new(descriptors: &Descriptors) -> Self {
+        Self {
+            binds: Arc::new(AtlasPageBinds::new(descriptors)),
+            allocator: AtlasAllocator::new(size2(PAGE_SIZE as i32, PAGE_SIZE as i32)),
+            free_slots: (0..REGIONS_PER_PAGE).rev().collect(),
+        }
+    }
+
+    fn allocate(&mut self, size: Size) -> Option<(Allocation, u32)> {
+        if self.free_slots.is_empty() {
+            return None;
+        }
+        let allocation = self.allocator.allocate(size)?;
+        let slot = self.free_slots.pop().expect("Checked for a free slot");
+        Some((allocation, slot))
+    }
+}
+
+/// The resources shared by every bitmap packed into one atlas page.
+///
+/// Each bitmap owns a slot of the texture transforms buffer, selected with a
+/// dynamic offset, so a single bind group per sampler serves the whole page.
+#[derive(Debug)]
+pub struct AtlasPageBinds {
+    texture: wgpu::Texture,
+    transforms: wgpu::Buffer,
+    transforms_stride: u32,
+    pub bind_linear: BitmapBinds,
+    pub bind_nearest: BitmapBinds,
+}
+
+impl AtlasPageBinds {
+    fn new(descriptors: &Descriptors) -> Self {
+        let texture = create_page_texture(&descriptors.device);
+
+        let alignment = descriptors
+            .limits
+            .min_uniform_buffer_offset_alignment
+            .max(1);
+        let transforms_stride =
+            (std::mem::size_of::<TextureTransforms>() as u32).next_multiple_of(alignment);
+        let label = create_debug_label!("Atlas page tex transforms");
+        let transforms = descriptors.device.create_buffer(&wgpu::BufferDescriptor {
+            label: label.as_deref(),
+            size: u64::from(transforms_stride * REGIONS_PER_PAGE),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let create_binds = |smoothed| {
+            BitmapBinds::new(
+                &descriptors.device,
+                &descriptors.bind_layouts.bitmap,
+                descriptors.bitmap_samplers.get_sampler(false, smoothed),
+                &transforms,
+                0 as wgpu::BufferAddress,
+                texture.create_view(&Default::default()),
+                create_debug_label!("Atlas page bind group (smoothed: {})", smoothed),
+            )
+        };
+        let bind_linear = create_binds(true);
+        let bind_nearest = create_binds(false);
+
+        Self {
+            texture,
+            transforms,
+            transforms_stride,
+            bind_linear,
+            bind_nearest,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TextureAtlas {
+    /// The atlas pages, with `None` left in place of pages that were emptied
+    /// so that the page indices of live regions stay valid.
+    pages: Vec<Option<AtlasPage>>,
+
+    /// Regions whose bitmaps were dropped since the last frame was submitted.
+    ///
+    /// These can't be reused until then, as commands recorded for the current
+    /// frame may still sample them.
+    pending_free: Vec<AtlasAllocation>,
+}
+
+impl TextureAtlas {
+    /// Release the regions of bitmaps dropped before the last submitted frame,
+    /// along with any pages left empty.
+    pub fn release_pending(&mut self) {
+        for allocation in self.pending_free.drain(..) {
+            let Some(page) = &mut self.pages[allocation.page] else {
+                continue;
+            };
+            page.allocator.deallocate(allocation.id);
+            page.free_slots.push(allocation.slot);
+            if page.allocator.is_empty() {
+                self.pages[allocation.page] = None;
+            }
+        }
+    }
+}
+
+/// The space a bitmap occupies in an atlas page.
+#[derive(Debug)]
+struct AtlasAllocation {
+    page: usize,
+    id: AllocId,
+    slot: u32,
+}
+
+/// The location of a bitmap inside an atlas page.
+#[derive(Debug)]
+pub struct AtlasRegion {
+    descriptors: Arc<Descriptors>,
+    pub binds: Arc<AtlasPageBinds>,
+
+    /// The allocation backing this region, until it's released.
+    allocation: RefCell<Option<AtlasAllocation>>,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl AtlasRegion {
+    /// Pack an RGBA bitmap into an atlas page, if it is small enough.
+    pub fn allocate(descriptors: &Arc<Descriptors>, bitmap: &Bitmap) -> Option<Self> {
+        let (width, height) = (bitmap.width(), bitmap.height());
+        if width == 0
+            || height == 0
+            || width > MAX_ATLAS_BITMAP_SIZE
+            || height > MAX_ATLAS_BITMAP_SIZE
+        {
+            return None;
+        }
+
+        let padded_width = width + 2 * PADDING;
+        let padded_height = height + 2 * PADDING;
+        let size = size2(padded_width as i32, padded_height as i32);
+
+        let mut atlas = descriptors
+            .texture_atlas
+            .lock()
+            .expect("Atlas not poisoned");
+        let found = atlas
+            .pages
+            .iter_mut()
+            .enumerate()
+            .find_map(|(index, page)| {
+                let (allocation, slot) = page.as_mut()?.allocate(size)?;
+                Some((index, allocation, slot))
+            });
+        let (page, allocation, slot) = match found {
+            Some(found) => found,
+            None => {
+                let mut new_page = AtlasPage::new(descriptors);
+                let (allocation, slot) = new_page.allocate(size)?;
+                let index = match atlas.pages.iter().position(Option::is_none) {
+                    Some(index) => {
+                        atlas.pages[index] = Some(new_page);
+                        index
+                    }
+                    None => {
+                        atlas.pages.push(Some(new_page));
+                        atlas.pages.len() - 1
+                    }
+                };
+                (index, allocation, slot)
+            }
+        };
+        let binds = atlas.pages[page]
+            .as_ref()
+            .expect("Page was just allocated from")
+            .binds
+            .clone();
+        drop(atlas);
+
+        let origin_x = allocation.rectangle.min.x as u32;
+        let origin_y = allocation.rectangle.min.y as u32;
+        descriptors.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &binds.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: origin_x,
+                    y: origin_y,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &pad_rgba(bitmap.data(), width, height),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * padded_width),
+                rows_per_image: None,
+            },
+            wgpu::Extent3d {
+                width: padded_width,
+                height: padded_height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let region = Self {
+            descriptors: descriptors.clone(),
+            binds,
+            allocation: RefCell::new(Some(AtlasAllocation {
+                page,
+                id: allocation.id,
+                slot,
+            })),
+            x: origin_x + PADDING,
+            y: origin_y + PADDING,
+            width,
+            height,
+        };
+        descriptors.queue.write_buffer(
+            &region.binds.transforms,
+            u64::from(region.binds.transforms_stride * slot),
+            bytemuck::cast_slice(&[region.texture_transforms()]),
+        );
+        Some(region)
+    }
+
+    /// The texture transform that maps the unit square onto this region.
+    fn texture_transforms(&self) -> TextureTransforms {
+        let page_size = PAGE_SIZE as f32;
+        TextureTransforms {
+            u_matrix: [
+                [self.width as f32 / page_size, 0.0, 0.0, 0.0],
+                [0.0, self.height as f32 / page_size, 0.0, 0.0],
+                [
+                    self.x as f32 / page_size,
+                    self.y as f32 / page_size,
+                    1.0,
+                    0.0,
+                ],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// The dynamic offset of this region's texture transforms, to be used
+    /// with the page's bind groups.
+    pub fn transforms_offset(&self) -> wgpu::DynamicOffset {
+        let slot = self
+            .allocation
+            .borrow()
+            .as_ref()
+            .map_or(0, |allocation| allocation.slot);
+        self.binds.transforms_stride * slot
+    }
+
+    /// Copy this region into a new texture of its own.
+    pub fn copy_to_new_texture(&self) -> wgpu::Texture {
+        let descriptors = &self.descriptors;
+        let extent = wgpu::Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: 1,
+        };
+        let texture_label = create_debug_label!("Bitmap");
+        let texture = descriptors.device.create_texture(&wgpu::TextureDescriptor {
+            label: texture_label.as_deref(),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC,
+        });
+
+        let label = create_debug_label!("Atlas region copy");
+        let mut encoder =
+            descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: label.as_deref(),
+                });
+        encoder.copy_texture_to_texture(
+            wgpu::ImageCopyTexture {
+                texture: &self.binds.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: self.x,
+                    y: self.y,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: Default::default(),
+                aspect: wgpu::TextureAspect::All,
+            },
+            extent,
+        );
+        descriptors.queue.submit(Some(encoder.finish()));
+
+        texture
+    }
+
+    /// Give this region's space back to the atlas once the current frame has
+    /// been submitted. The region must not be drawn afterwards.
+    pub fn release(&self) {
+        if let Some(allocation) = self.allocation.borrow_mut().take() {
+            if let Ok(mut atlas) = self.descriptors.texture_atlas.lock() {
+                atlas.pending_free.push(allocation);
+            }
+        }
+    }
+}
+
+impl Drop for AtlasRegion {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+fn create_page_texture(device: &wgpu::Device) -> wgpu::Texture {
+    let label = create_debug_label!("Atlas page");
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: label.as_deref(),
+        size: wgpu::Extent3d {
+            width: PAGE_SIZE,
+            height: PAGE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
+        usage: wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_DST
+            | wgpu::TextureUsages::COPY_SRC,
+    })
+}
+
+/// Surround RGBA pixel data with a border of its edge pixels.
+fn pad_rgba(data: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let (width, height) = (width as usize, height as usize);
+    let padding = PADDING as usize;
+    let padded_width = width + 2 * padding;
+    let padded_height = height + 2 * padding;
+
+    let mut padded = Vec::with_capacity(padded_width * padded_height * 4);
+    for y in 0..padded_height {
+        let src_y = y.saturating_sub(padding).min(height - 1);
+        for x in 0..padded_width {
+            let src_x = x.saturating_sub(padding).min(width - 1);
+            let i = (src_y * width + src_x) * 4;
+            padded.extend_from_slice(&data[i..i + 4]);
+        }
+    }
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pads_with_edge_pixels() {
+        #[rustfmt::skip]
+        let data = [
+            1, 1, 1, 1,  2, 2, 2, 2,
+            3, 3, 3, 3,  4, 4, 4, 4,
+        ];
+        let padded = pad_rgba(&data, 2, 2);
+        let pixels: Vec<u8> = padded.chunks_exact(4).map(|pixel| pixel[0]).collect();
+        assert_eq!(
+            pixels,
+            vec![
+                1, 1, 2, 2, //
+                1, 1, 2, 2, //
+                3, 3, 4, 4, //
+                3, 3, 4, 4, //
+            ]
+        );
+    }
+}
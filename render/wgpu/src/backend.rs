@@ -1,9 +1,10 @@
+use crate::atlas::AtlasRegion;
 use crate::buffer_builder::BufferBuilder;
 use crate::buffer_pool::{BufferPool, TexturePool};
 use crate::context3d::WgpuContext3D;
 use crate::dynamic_transforms::DynamicTransforms;
 use crate::filters::FilterSource;
-use crate::mesh::{CommonGradient, Mesh, PendingDraw};
+use crate::mesh::{srgb_to_linear, CommonGradient, Mesh, PendingDraw};
 use crate::pixel_bender::{run_pixelbender_shader_impl, ShaderMode};
 use crate::surface::{LayerRef, Surface};
 use crate::target::{MaybeOwnedBuffer, TextureTarget};
@@ -30,12 +31,12 @@ use ruffle_render::pixel_bender::{
 };
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::DistilledShape;
-use ruffle_render::tessellator::ShapeTessellator;
+use ruffle_render::tessellator::{Mesh as LyonMesh, ShapeTessellator};
 use std::borrow::Cow;
-use std::cell::Cell;
 use std::path::Path;
+use std::str::FromStr;
 use std::sync::Arc;
-use swf::Color;
+use swf::{CharacterId, Color};
 use tracing::instrument;
 use wgpu::SubmissionIndex;
 
@@ -198,6 +199,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
         let surface = Surface::new(
             &descriptors,
             StageQuality::Low,
+            BlendSpace::Flash,
             target.width(),
             target.height(),
             target.format(),
@@ -241,7 +243,15 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
         let lyon_mesh = self
             .shape_tessellator
             .tessellate_shape(shape, bitmap_source);
+        self.register_mesh_internal(shape_id, lyon_mesh, bitmap_source)
+    }
 
+    fn register_mesh_internal(
+        &mut self,
+        shape_id: CharacterId,
+        lyon_mesh: LyonMesh,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
         let mut draws = Vec::with_capacity(lyon_mesh.draws.len());
         let mut uniform_buffer = BufferBuilder::new_for_uniform(&self.descriptors.limits);
         let mut vertex_buffer = BufferBuilder::new_for_vertices(&self.descriptors.limits);
@@ -326,6 +336,33 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
         }
     }
 
+    /// Changes the color space in which the movie is blended, see [`BlendSpace`].
+    ///
+    /// This only affects what's shown on screen, not what's drawn into bitmaps by the movie.
+    pub fn set_blend_space(&mut self, blend_space: BlendSpace) {
+        let blend_space = if blend_space == BlendSpace::Linear
+            && !self
+                .descriptors
+                .adapter
+                .get_downlevel_capabilities()
+                .flags
+                .contains(wgpu::DownlevelFlags::VIEW_FORMATS)
+        {
+            tracing::warn!("Blending in linear space is not supported by this graphics device");
+            BlendSpace::Flash
+        } else {
+            blend_space
+        };
+        self.surface = Surface::new(
+            &self.descriptors,
+            self.surface.quality(),
+            blend_space,
+            self.surface.size().width,
+            self.surface.size().height,
+            self.target.format(),
+        );
+    }
+
     pub fn descriptors(&self) -> &Arc<Descriptors> {
         &self.descriptors
     }
@@ -392,6 +429,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.surface = Surface::new(
             &self.descriptors,
             self.surface.quality(),
+            self.surface.blend_space(),
             width,
             height,
             self.target.format(),
@@ -463,6 +501,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.surface = Surface::new(
             &self.descriptors,
             quality,
+            self.surface.blend_space(),
             self.surface.size().width,
             self.surface.size().height,
             self.target.format(),
@@ -487,6 +526,21 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         ShapeHandle(Arc::new(mesh))
     }
 
+    fn supports_meshes(&self) -> bool {
+        true
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    fn register_mesh(
+        &mut self,
+        shape_id: CharacterId,
+        mesh: LyonMesh,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Option<ShapeHandle> {
+        let mesh = self.register_mesh_internal(shape_id, mesh, bitmap_source);
+        Some(ShapeHandle(Arc::new(mesh)))
+    }
+
     #[instrument(level = "debug", skip_all)]
     fn submit_frame(
         &mut self,
@@ -513,14 +567,15 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             let mut surface = Surface::new(
                 &self.descriptors,
                 self.surface.quality(),
-                texture.texture.width(),
-                texture.texture.height(),
+                BlendSpace::Flash,
+                texture.texture().width(),
+                texture.texture().height(),
                 wgpu::TextureFormat::Rgba8Unorm,
             );
             if entry.filters.is_empty() {
                 surface.draw_commands(
                     RenderTargetMode::ExistingWithColor(
-                        texture.texture.clone(),
+                        texture.texture().clone(),
                         wgpu::Color {
                             r: f64::from(entry.clear.r) / 255.0,
                             g: f64::from(entry.clear.g) / 255.0,
@@ -544,7 +599,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 // letting us safely copy back to it later.
                 let mut target = surface.draw_commands(
                     RenderTargetMode::ExistingWithColor(
-                        texture.texture.clone(),
+                        texture.texture().clone(),
                         wgpu::Color {
                             r: f64::from(entry.clear.r) / 255.0,
                             g: f64::from(entry.clear.g) / 255.0,
@@ -574,8 +629,8 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 run_copy_pipeline(
                     &self.descriptors,
                     target.color_texture().format(),
-                    texture.texture.format(),
-                    &texture.texture.create_view(&Default::default()),
+                    texture.texture().format(),
+                    &texture.texture().create_view(&Default::default()),
                     target.color_view(),
                     target.whole_frame_bind_group(&self.descriptors),
                     target.globals(),
@@ -585,14 +640,22 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             }
         }
 
+        let mut clear_color = wgpu::Color {
+            r: f64::from(clear.r) / 255.0,
+            g: f64::from(clear.g) / 255.0,
+            b: f64::from(clear.b) / 255.0,
+            a: f64::from(clear.a) / 255.0,
+        };
+        if self.surface.blend_space() == BlendSpace::Linear {
+            // Clearing an sRGB frame buffer takes a linear color.
+            clear_color.r = srgb_to_linear(clear_color.r as f32).into();
+            clear_color.g = srgb_to_linear(clear_color.g as f32).into();
+            clear_color.b = srgb_to_linear(clear_color.b as f32).into();
+        }
+
         self.surface.draw_commands_and_copy_to(
             frame_output.view(),
-            RenderTargetMode::FreshWithColor(wgpu::Color {
-                r: f64::from(clear.r) / 255.0,
-                g: f64::from(clear.g) / 255.0,
-                b: f64::from(clear.b) / 255.0,
-                a: f64::from(clear.a) / 255.0,
-            }),
+            RenderTargetMode::FreshWithColor(clear_color),
             &self.descriptors,
             &mut self.active_frame.staging_belt,
             &self.dynamic_transforms,
@@ -607,6 +670,11 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.active_frame
             .submit_for_target(&self.descriptors, &self.target, frame_output);
         self.offscreen_texture_pool = TexturePool::new();
+        self.descriptors
+            .texture_atlas
+            .lock()
+            .expect("Atlas not poisoned")
+            .release_pending();
     }
 
     #[instrument(level = "debug", skip_all)]
@@ -615,6 +683,10 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         self.clamp_bitmap(&mut bitmap);
 
+        if let Some(region) = AtlasRegion::allocate(&self.descriptors, &bitmap) {
+            return Ok(BitmapHandle(Arc::new(Texture::in_atlas(region))));
+        }
+
         let extent = wgpu::Extent3d {
             width: bitmap.width(),
             height: bitmap.height(),
@@ -655,12 +727,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             extent,
         );
 
-        let handle = BitmapHandle(Arc::new(Texture {
-            texture: Arc::new(texture),
-            bind_linear: Default::default(),
-            bind_nearest: Default::default(),
-            copy_count: Cell::new(0),
-        }));
+        let handle = BitmapHandle(Arc::new(Texture::new(Arc::new(texture))));
 
         Ok(handle)
     }
@@ -689,7 +756,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         self.descriptors.queue.write_texture(
             wgpu::ImageCopyTexture {
-                texture: &texture.texture,
+                texture: texture.texture(),
                 mip_level: 0,
                 origin: wgpu::Origin3d {
                     x: region.x_min,
@@ -698,11 +765,11 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 },
                 aspect: wgpu::TextureAspect::All,
             },
-            &bitmap.data()[(region.y_min * texture.texture.width() * 4) as usize
-                ..(region.y_max * texture.texture.width() * 4) as usize],
+            &bitmap.data()[(region.y_min * texture.texture().width() * 4) as usize
+                ..(region.y_max * texture.texture().width() * 4) as usize],
             wgpu::ImageDataLayout {
                 offset: (region.x_min * 4) as wgpu::BufferAddress,
-                bytes_per_row: Some(4 * texture.texture.width()),
+                bytes_per_row: Some(4 * texture.texture().width()),
                 rows_per_image: None,
             },
             extent,
@@ -722,14 +789,14 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         let texture = as_texture(&handle);
 
         let extent = wgpu::Extent3d {
-            width: texture.texture.width(),
-            height: texture.texture.height(),
+            width: texture.texture().width(),
+            height: texture.texture().height(),
             depth_or_array_layers: 1,
         };
 
         let mut target = TextureTarget {
             size: extent,
-            texture: texture.texture.clone(),
+            texture: texture.texture().clone(),
             format: wgpu::TextureFormat::Rgba8Unorm,
             buffer: None,
         };
@@ -741,8 +808,9 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         let mut surface = Surface::new(
             &self.descriptors,
             quality,
-            texture.texture.width(),
-            texture.texture.height(),
+            BlendSpace::Flash,
+            texture.texture().width(),
+            texture.texture().height(),
             wgpu::TextureFormat::Rgba8Unorm,
         );
         surface.draw_commands_and_copy_to(
@@ -792,17 +860,17 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         let dest_texture = as_texture(&destination);
 
         let copy_area = PixelRegion::for_whole_size(
-            dest_texture.texture.width(),
-            dest_texture.texture.height(),
+            dest_texture.texture().width(),
+            dest_texture.texture().height(),
         );
 
         let target = TextureTarget {
             size: wgpu::Extent3d {
-                width: dest_texture.texture.width(),
-                height: dest_texture.texture.height(),
+                width: dest_texture.texture().width(),
+                height: dest_texture.texture().height(),
                 depth_or_array_layers: 1,
             },
-            texture: dest_texture.texture.clone(),
+            texture: dest_texture.texture().clone(),
             format: wgpu::TextureFormat::Rgba8Unorm,
             buffer: None,
         };
@@ -813,7 +881,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             &mut self.offscreen_texture_pool,
             &mut self.active_frame.staging_belt,
             FilterSource {
-                texture: &source_texture.texture,
+                texture: source_texture.texture(),
                 point: source_point,
                 size: source_size,
             },
@@ -827,7 +895,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 aspect: Default::default(),
             },
             wgpu::ImageCopyTexture {
-                texture: &dest_texture.texture,
+                texture: dest_texture.texture(),
                 mip_level: 0,
                 origin: wgpu::Origin3d {
                     x: dest_point.0,
@@ -837,8 +905,9 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 aspect: Default::default(),
             },
             wgpu::Extent3d {
-                width: (applied_filter.width()).min(dest_texture.texture.width() - dest_point.0),
-                height: (applied_filter.height()).min(dest_texture.texture.height() - dest_point.1),
+                width: (applied_filter.width()).min(dest_texture.texture().width() - dest_point.0),
+                height: (applied_filter.height())
+                    .min(dest_texture.texture().height() - dest_point.1),
                 depth_or_array_layers: 1,
             },
         );
@@ -910,27 +979,22 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                             | wgpu::TextureUsages::RENDER_ATTACHMENT
                             | wgpu::TextureUsages::COPY_SRC,
                     });
-                BitmapHandle(Arc::new(Texture {
-                    texture: Arc::new(texture),
-                    bind_linear: Default::default(),
-                    bind_nearest: Default::default(),
-                    copy_count: Cell::new(0),
-                }))
+                BitmapHandle(Arc::new(Texture::new(Arc::new(texture))))
             }
         };
 
         let target_texture = as_texture(&target_handle);
 
         let extent = wgpu::Extent3d {
-            width: target_texture.texture.width(),
-            height: target_texture.texture.height(),
+            width: target_texture.texture().width(),
+            height: target_texture.texture().height(),
             depth_or_array_layers: 1,
         };
 
         let copy_dimensions = BufferDimensions::new(
-            target_texture.texture.width() as usize,
-            target_texture.texture.height() as usize,
-            target_texture.texture.format(),
+            target_texture.texture().width() as usize,
+            target_texture.texture().height() as usize,
+            target_texture.texture().format(),
         );
         let buffer_info = Some(TextureBufferInfo {
             buffer: MaybeOwnedBuffer::Borrowed(
@@ -939,15 +1003,15 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 copy_dimensions,
             ),
             copy_area: PixelRegion::for_whole_size(
-                target_texture.texture.width(),
-                target_texture.texture.height(),
+                target_texture.texture().width(),
+                target_texture.texture().height(),
             ),
         });
 
         let mut texture_target = TextureTarget {
             size: extent,
-            texture: target_texture.texture.clone(),
-            format: target_texture.texture.format(),
+            texture: target_texture.texture().clone(),
+            format: target_texture.texture().format(),
             buffer: buffer_info,
         };
 
@@ -960,7 +1024,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             shader,
             ShaderMode::ShaderJob,
             arguments,
-            &target_texture.texture,
+            target_texture.texture(),
             &mut self.active_frame.command_encoder,
             Some(wgpu::RenderPassColorAttachment {
                 view: frame_output.view(),
@@ -972,7 +1036,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             }),
             1,
             // When running a standalone shader, we always process the entire image
-            &FilterSource::for_entire_texture(&target_texture.texture),
+            &FilterSource::for_entire_texture(target_texture.texture()),
         )?;
 
         let index = Some(self.active_frame.submit_for_target(
@@ -1070,12 +1134,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                     | wgpu::TextureUsages::RENDER_ATTACHMENT
                     | wgpu::TextureUsages::COPY_SRC,
             });
-        Ok(BitmapHandle(Arc::new(Texture {
-            texture: Arc::new(texture),
-            bind_linear: Default::default(),
-            bind_nearest: Default::default(),
-            copy_count: Cell::new(0),
-        })))
+        Ok(BitmapHandle(Arc::new(Texture::new(Arc::new(texture)))))
     }
 
     fn resolve_sync_handle(
@@ -1157,6 +1216,42 @@ async fn request_device(
         .await
 }
 
+/// The color space in which colors of the movie are blended together, such as when drawing
+/// semi-transparent shapes or layers with blend modes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendSpace {
+    /// Blend colors as they're stored, in sRGB space, just like Flash Player.
+    /// Movies were made to look right like this, including their gradients and fades.
+    #[default]
+    Flash,
+
+    /// Blend colors in linear space, which is physically correct and makes edges and fades
+    /// look smoother, but differs from how the movie looked in Flash Player.
+    /// Color transforms still apply to colors in sRGB space.
+    Linear,
+}
+
+impl BlendSpace {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlendSpace::Flash => "flash",
+            BlendSpace::Linear => "linear",
+        }
+    }
+}
+
+impl FromStr for BlendSpace {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flash" => Ok(BlendSpace::Flash),
+            "linear" => Ok(BlendSpace::Linear),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Determines how we choose our frame buffer
 #[derive(Clone)]
 pub enum RenderTargetMode {
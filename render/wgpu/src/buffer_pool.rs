@@ -1,5 +1,6 @@
 use crate::descriptors::Descriptors;
 use crate::globals::Globals;
+use crate::utils::remove_srgb;
 use fnv::FnvHashMap;
 use std::fmt::{Debug, Formatter};
 use std::ops::Deref;
@@ -50,7 +51,8 @@ impl TexturePool {
                     sample_count,
                     dimension: wgpu::TextureDimension::D2,
                     format,
-                    view_formats: &[format],
+                    // sRGB textures can also be read as they're stored, see `BlendSpace::Linear`.
+                    view_formats: &[format, remove_srgb(format)],
                     usage,
                 });
                 let view = texture.create_view(&Default::default());
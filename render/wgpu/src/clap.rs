@@ -8,6 +8,7 @@ pub enum GraphicsBackend {
     Metal,
     Dx12,
     Gl,
+    Software,
 }
 
 impl GraphicsBackend {
@@ -18,6 +19,7 @@ impl GraphicsBackend {
             GraphicsBackend::Metal => "metal",
             GraphicsBackend::Dx12 => "dx12",
             GraphicsBackend::Gl => "gl",
+            GraphicsBackend::Software => "software",
         }
     }
 }
@@ -32,6 +34,7 @@ impl FromStr for GraphicsBackend {
             "metal" => Ok(GraphicsBackend::Metal),
             "dx12" => Ok(GraphicsBackend::Dx12),
             "gl" => Ok(GraphicsBackend::Gl),
+            "software" => Ok(GraphicsBackend::Software),
             _ => Err(()),
         }
     }
@@ -45,6 +48,9 @@ impl From<GraphicsBackend> for wgpu::Backends {
             GraphicsBackend::Metal => wgpu::Backends::METAL,
             GraphicsBackend::Dx12 => wgpu::Backends::DX12,
             GraphicsBackend::Gl => wgpu::Backends::GL,
+            // The software renderer doesn't use wgpu for the movie itself,
+            // so anything that works is fine for the rest of the UI.
+            GraphicsBackend::Software => wgpu::Backends::all(),
         }
     }
 }
@@ -4,7 +4,6 @@ use ruffle_render::backend::{
 };
 use ruffle_render::bitmap::BitmapHandle;
 use ruffle_render::error::Error;
-use std::cell::Cell;
 use swf::{Rectangle, Twips};
 
 use wgpu::util::StagingBelt;
@@ -119,12 +118,7 @@ impl WgpuContext3D {
                 usage: wgpu::TextureUsages::COPY_SRC,
             });
 
-            BitmapHandle(Arc::new(Texture {
-                bind_linear: Default::default(),
-                bind_nearest: Default::default(),
-                texture: Arc::new(dummy_texture),
-                copy_count: Cell::new(0),
-            }))
+            BitmapHandle(Arc::new(Texture::new(Arc::new(dummy_texture))))
         };
 
         let back_buffer_raw_texture_handle = make_dummy_handle();
@@ -668,34 +662,20 @@ impl Context3D for WgpuContext3D {
                     // We always use a non-multisampled texture as our raw texture handle,
                     // which is what the Stage rendering code expects. In multisample mode,
                     // this is our resolve texture.
-                    self.back_buffer_raw_texture_handle = BitmapHandle(Arc::new(Texture {
-                        texture: Arc::new(back_buffer_resolve_texture.unwrap()),
-                        bind_linear: Default::default(),
-                        bind_nearest: Default::default(),
-                        copy_count: Cell::new(0),
-                    }));
-                    self.front_buffer_raw_texture_handle = BitmapHandle(Arc::new(Texture {
-                        texture: Arc::new(front_buffer_resolve_texture.unwrap()),
-                        bind_linear: Default::default(),
-                        bind_nearest: Default::default(),
-                        copy_count: Cell::new(0),
-                    }));
+                    self.back_buffer_raw_texture_handle = BitmapHandle(Arc::new(Texture::new(
+                        Arc::new(back_buffer_resolve_texture.unwrap()),
+                    )));
+                    self.front_buffer_raw_texture_handle = BitmapHandle(Arc::new(Texture::new(
+                        Arc::new(front_buffer_resolve_texture.unwrap()),
+                    )));
                 } else {
                     // In non-multisample mode, we don't have a separate resolve buffer,
                     // so our main texture gets used as the raw texture handle.
 
-                    self.back_buffer_raw_texture_handle = BitmapHandle(Arc::new(Texture {
-                        texture: Arc::new(back_buffer_texture),
-                        bind_linear: Default::default(),
-                        bind_nearest: Default::default(),
-                        copy_count: Cell::new(0),
-                    }));
-                    self.front_buffer_raw_texture_handle = BitmapHandle(Arc::new(Texture {
-                        texture: Arc::new(front_buffer_texture),
-                        bind_linear: Default::default(),
-                        bind_nearest: Default::default(),
-                        copy_count: Cell::new(0),
-                    }));
+                    self.back_buffer_raw_texture_handle =
+                        BitmapHandle(Arc::new(Texture::new(Arc::new(back_buffer_texture))));
+                    self.front_buffer_raw_texture_handle =
+                        BitmapHandle(Arc::new(Texture::new(Arc::new(front_buffer_texture))));
                     self.current_texture_resolve_view = None;
                 }
 
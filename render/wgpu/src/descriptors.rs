@@ -1,3 +1,4 @@
+use crate::atlas::TextureAtlas;
 use crate::filters::{FilterVertex, Filters};
 use crate::layouts::BindLayouts;
 use crate::pipelines::VERTEX_BUFFERS_DESCRIPTION_POS;
@@ -23,9 +24,11 @@ pub struct Descriptors {
     pub quad: Quad,
     copy_pipeline: Mutex<FnvHashMap<(u32, wgpu::TextureFormat), Arc<wgpu::RenderPipeline>>>,
     copy_srgb_pipeline: Mutex<FnvHashMap<(u32, wgpu::TextureFormat), Arc<wgpu::RenderPipeline>>>,
+    copy_linear_pipeline: Mutex<FnvHashMap<(u32, wgpu::TextureFormat), Arc<wgpu::RenderPipeline>>>,
     pub shaders: Shaders,
     pipelines: Mutex<FnvHashMap<(u32, wgpu::TextureFormat), Arc<Pipelines>>>,
     pub filters: Filters,
+    pub texture_atlas: Mutex<TextureAtlas>,
 }
 
 impl Debug for Descriptors {
@@ -61,9 +64,11 @@ impl Descriptors {
             quad,
             copy_pipeline: Default::default(),
             copy_srgb_pipeline: Default::default(),
+            copy_linear_pipeline: Default::default(),
             shaders,
             pipelines: Default::default(),
             filters,
+            texture_atlas: Default::default(),
         }
     }
 
@@ -137,6 +142,76 @@ impl Descriptors {
             .clone()
     }
 
+    pub fn copy_linear_pipeline(
+        &self,
+        format: wgpu::TextureFormat,
+        msaa_sample_count: u32,
+    ) -> Arc<wgpu::RenderPipeline> {
+        let mut pipelines = self
+            .copy_linear_pipeline
+            .lock()
+            .expect("Pipelines should not be already locked");
+        pipelines
+            .entry((msaa_sample_count, format))
+            .or_insert_with(|| {
+                let copy_texture_pipeline_layout =
+                    &self
+                        .device
+                        .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                            label: create_debug_label!("Copy linear pipeline layout").as_deref(),
+                            bind_group_layouts: &[
+                                &self.bind_layouts.globals,
+                                &self.bind_layouts.transforms,
+                                &self.bind_layouts.bitmap,
+                            ],
+                            push_constant_ranges: &[],
+                        });
+                Arc::new(
+                    self.device
+                        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                            label: create_debug_label!("Copy linear pipeline").as_deref(),
+                            layout: Some(copy_texture_pipeline_layout),
+                            vertex: wgpu::VertexState {
+                                module: &self.shaders.copy_linear_shader,
+                                entry_point: "main_vertex",
+                                buffers: &VERTEX_BUFFERS_DESCRIPTION_POS,
+                                compilation_options: Default::default(),
+                            },
+                            fragment: Some(wgpu::FragmentState {
+                                module: &self.shaders.copy_linear_shader,
+                                entry_point: "main_fragment",
+                                targets: &[Some(wgpu::ColorTargetState {
+                                    format,
+                                    // All of our blending has been done by now, so we want
+                                    // to overwrite the target pixels without any blending
+                                    blend: Some(wgpu::BlendState::REPLACE),
+                                    write_mask: Default::default(),
+                                })],
+                                compilation_options: Default::default(),
+                            }),
+                            primitive: wgpu::PrimitiveState {
+                                topology: wgpu::PrimitiveTopology::TriangleList,
+                                strip_index_format: None,
+                                front_face: wgpu::FrontFace::Ccw,
+                                cull_mode: None,
+                                polygon_mode: wgpu::PolygonMode::default(),
+                                unclipped_depth: false,
+                                conservative: false,
+                            },
+                            depth_stencil: None,
+                            multisample: wgpu::MultisampleState {
+                                count: msaa_sample_count,
+                                mask: !0,
+                                alpha_to_coverage_enabled: false,
+                            },
+                            multiview: None,
+                            cache: None,
+                        }),
+                )
+            })
+            .clone()
+    }
+
     pub fn copy_pipeline(
         &self,
         format: wgpu::TextureFormat,
@@ -193,7 +193,7 @@ impl DisplacementMapFilter {
         let source_view = source.texture.create_view(&Default::default());
         let map_handle = filter.map_bitmap.clone()?;
         let map_texture = as_texture(&map_handle);
-        let map_view = map_texture.texture.create_view(&Default::default());
+        let map_view = map_texture.texture().create_view(&Default::default());
         staging_belt
             .write_buffer(
                 draw_encoder,
@@ -220,8 +220,8 @@ impl DisplacementMapFilter {
                 scale_y: filter.scale_y,
                 source_width: source.texture.width() as f32,
                 source_height: source.texture.height() as f32,
-                map_width: map_texture.texture.width() as f32,
-                map_height: map_texture.texture.height() as f32,
+                map_width: map_texture.texture().width() as f32,
+                map_height: map_texture.texture().height() as f32,
                 offset_x: filter.map_point.0 as f32,
                 offset_y: filter.map_point.1 as f32,
                 viewscale_x: filter.viewscale_x,
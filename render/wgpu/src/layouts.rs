@@ -54,7 +54,9 @@ impl BindLayouts {
                     visibility: wgpu::ShaderStages::VERTEX,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
+                        // Bitmaps packed into an atlas share the bind group of their page,
+                        // each with its own texture transforms.
+                        has_dynamic_offset: true,
                         min_binding_size: wgpu::BufferSize::new(
                             std::mem::size_of::<TextureTransforms>() as u64,
                         ),
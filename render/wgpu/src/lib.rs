@@ -4,6 +4,7 @@
 // TODO: Remove this once all instances are fixed.
 #![allow(clippy::needless_pass_by_ref_mut)]
 
+use crate::atlas::AtlasRegion;
 use crate::backend::ActiveFrame;
 use crate::bitmaps::BitmapSamplers;
 use crate::buffer_pool::{BufferPool, PoolEntry};
@@ -38,6 +39,7 @@ mod pipelines;
 mod pixel_bender;
 pub mod target;
 
+mod atlas;
 pub mod backend;
 mod blend;
 mod buffer_builder;
@@ -195,13 +197,13 @@ impl QueueSyncHandle {
                 let buffer_dimensions = BufferDimensions::new(
                     copy_area.width() as usize,
                     copy_area.height() as usize,
-                    texture.texture.format(),
+                    texture.texture().format(),
                 );
 
                 let buffer = pool.take(&descriptors, buffer_dimensions.clone());
                 frame.command_encoder.copy_texture_to_buffer(
                     wgpu::ImageCopyTexture {
-                        texture: &texture.texture,
+                        texture: texture.texture(),
                         mip_level: 0,
                         origin: wgpu::Origin3d {
                             x: copy_area.x_min,
@@ -248,13 +250,70 @@ impl QueueSyncHandle {
 
 #[derive(Debug)]
 pub struct Texture {
-    pub(crate) texture: Arc<wgpu::Texture>,
+    /// The texture holding only this bitmap.
+    ///
+    /// For bitmaps packed into an atlas, this is created on first use.
+    texture: OnceCell<Arc<wgpu::Texture>>,
+    atlas_region: Option<AtlasRegion>,
     bind_linear: OnceCell<BitmapBinds>,
     bind_nearest: OnceCell<BitmapBinds>,
     copy_count: Cell<u8>,
 }
 
 impl Texture {
+    pub fn new(texture: Arc<wgpu::Texture>) -> Self {
+        Self {
+            texture: OnceCell::from(texture),
+            atlas_region: None,
+            bind_linear: Default::default(),
+            bind_nearest: Default::default(),
+            copy_count: Cell::new(0),
+        }
+    }
+
+    pub(crate) fn in_atlas(region: AtlasRegion) -> Self {
+        Self {
+            texture: OnceCell::new(),
+            atlas_region: Some(region),
+            bind_linear: Default::default(),
+            bind_nearest: Default::default(),
+            copy_count: Cell::new(0),
+        }
+    }
+
+    /// The texture holding only this bitmap.
+    ///
+    /// If this bitmap was packed into an atlas, it's moved into a texture of
+    /// its own, as most uses of a texture can't be restricted to a region,
+    /// and its region is given back to the atlas.
+    pub fn texture(&self) -> &Arc<wgpu::Texture> {
+        self.texture.get_or_init(|| {
+            let region = self
+                .atlas_region
+                .as_ref()
+                .expect("Texture is either standalone or in an atlas");
+            let texture = region.copy_to_new_texture();
+            region.release();
+            Arc::new(texture)
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        match (self.texture.get(), &self.atlas_region) {
+            (None, Some(region)) => region.width,
+            _ => self.texture().width(),
+        }
+    }
+
+    pub fn height(&self) -> u32 {
+        match (self.texture.get(), &self.atlas_region) {
+            (None, Some(region)) => region.height,
+            _ => self.texture().height(),
+        }
+    }
+
+    /// The bind group to draw this bitmap with, along with the dynamic offset
+    /// of its texture transforms.
     pub fn bind_group(
         &self,
         smoothed: bool,
@@ -263,21 +322,30 @@ impl Texture {
         quad: &Quad,
         handle: BitmapHandle,
         samplers: &BitmapSamplers,
-    ) -> &BitmapBinds {
+    ) -> (&BitmapBinds, wgpu::DynamicOffset) {
+        if let (None, Some(region)) = (self.texture.get(), &self.atlas_region) {
+            let bind = match smoothed {
+                true => &region.binds.bind_linear,
+                false => &region.binds.bind_nearest,
+            };
+            return (bind, region.transforms_offset());
+        }
+
         let bind = match smoothed {
             true => &self.bind_linear,
             false => &self.bind_nearest,
         };
-        bind.get_or_init(|| {
+        let bind = bind.get_or_init(|| {
             BitmapBinds::new(
                 device,
                 layout,
                 samplers.get_sampler(false, smoothed),
                 &quad.texture_transforms,
                 0 as wgpu::BufferAddress,
-                self.texture.create_view(&Default::default()),
+                self.texture().create_view(&Default::default()),
                 create_debug_label!("Bitmap {:?} bind group (smoothed: {})", handle.0, smoothed),
             )
-        })
+        });
+        (bind, 0)
     }
 }
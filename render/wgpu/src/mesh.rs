@@ -136,7 +136,7 @@ pub enum PendingDrawType {
 }
 
 /// Converts an RGBA color from sRGB space to linear color space.
-fn srgb_to_linear(color: f32) -> f32 {
+pub fn srgb_to_linear(color: f32) -> f32 {
     if color <= 0.04045 {
         color / 12.92
     } else {
@@ -181,7 +181,7 @@ impl PendingDrawType {
     ) -> Option<Self> {
         let handle = source.bitmap_handle(bitmap.bitmap_id, backend)?;
         let texture = as_texture(&handle);
-        let texture_view = texture.texture.create_view(&Default::default());
+        let texture_view = texture.texture().create_view(&Default::default());
         let texture_transforms_index = create_texture_transforms(&bitmap.matrix, uniform_buffers);
         let bind_group_label =
             create_debug_label!("Shape {} (bitmap) draw {} bindgroup", shape_id, draw_id);
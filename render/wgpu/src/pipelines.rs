@@ -88,6 +88,12 @@ impl Pipelines {
         msaa_sample_count: u32,
         bind_layouts: &BindLayouts,
     ) -> Self {
+        // Drawing to an sRGB target means the movie is blended in linear space,
+        // see `BlendSpace::Linear`.
+        let linear_blending = if format.is_srgb() { 1.0 } else { 0.0 };
+        let color_constants: HashMap<String, f64> =
+            [("linear_blending".to_owned(), linear_blending)].into();
+
         let colort_bindings = vec![&bind_layouts.globals, &bind_layouts.transforms];
 
         let color_pipelines = create_shape_pipeline(
@@ -101,6 +107,7 @@ impl Pipelines {
             BlendState::PREMULTIPLIED_ALPHA_BLENDING,
             &[],
             PrimitiveTopology::TriangleList,
+            &color_constants,
         );
 
         let lines_pipelines = create_shape_pipeline(
@@ -114,6 +121,7 @@ impl Pipelines {
             BlendState::PREMULTIPLIED_ALPHA_BLENDING,
             &[],
             PrimitiveTopology::LineStrip,
+            &color_constants,
         );
 
         let gradient_bindings = vec![
@@ -133,6 +141,7 @@ impl Pipelines {
             BlendState::PREMULTIPLIED_ALPHA_BLENDING,
             &[],
             PrimitiveTopology::TriangleList,
+            &color_constants,
         );
 
         let complex_blend_bindings = vec![
@@ -153,6 +162,7 @@ impl Pipelines {
                 BlendState::REPLACE,
                 &[],
                 PrimitiveTopology::TriangleList,
+                &Default::default(),
             )
         };
 
@@ -177,6 +187,7 @@ impl Pipelines {
                     blend.blend_state(),
                     &[],
                     PrimitiveTopology::TriangleList,
+                    &color_constants,
                 )
             })
             .collect::<Vec<_>>()
@@ -205,7 +216,11 @@ impl Pipelines {
             })],
             &VERTEX_BUFFERS_DESCRIPTION_POS,
             msaa_sample_count,
-            &[("late_saturate".to_owned(), 1.0)].into(),
+            &[
+                ("late_saturate".to_owned(), 1.0),
+                ("linear_blending".to_owned(), linear_blending),
+            ]
+            .into(),
             PrimitiveTopology::TriangleList,
         ));
 
@@ -233,7 +248,7 @@ impl Pipelines {
             })],
             &VERTEX_BUFFERS_DESCRIPTION_POS,
             msaa_sample_count,
-            &Default::default(),
+            &color_constants,
             PrimitiveTopology::TriangleList,
         ));
 
@@ -312,6 +327,7 @@ fn create_shape_pipeline(
     blend: BlendState,
     push_constant_ranges: &[wgpu::PushConstantRange],
     primitive_topology: PrimitiveTopology,
+    fragment_constants: &HashMap<String, f64>,
 ) -> ShapePipeline {
     let pipeline_layout_label = create_debug_label!("{} shape pipeline layout", name);
     let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -345,7 +361,7 @@ fn create_shape_pipeline(
             })],
             vertex_buffers_layout,
             msaa_sample_count,
-            &Default::default(),
+            fragment_constants,
             primitive_topology,
         ))
     };
@@ -364,7 +380,7 @@ fn create_shape_pipeline(
             })],
             vertex_buffers_layout,
             msaa_sample_count,
-            &Default::default(),
+            fragment_constants,
             primitive_topology,
         )),
         |mask_state| match mask_state {
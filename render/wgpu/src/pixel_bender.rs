@@ -1,7 +1,7 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::num::NonZeroU64;
-use std::{borrow::Cow, cell::Cell, sync::Arc};
+use std::{borrow::Cow, sync::Arc};
 
 use indexmap::IndexMap;
 use ruffle_render::error::Error as BitmapError;
@@ -302,7 +302,7 @@ fn image_input_as_texture<'a>(
 ) -> BorrowedOrOwnedTexture<'a> {
     match input {
         ImageInputTexture::Bitmap(handle) => {
-            BorrowedOrOwnedTexture::Borrowed(&as_texture(handle).texture)
+            BorrowedOrOwnedTexture::Borrowed(as_texture(handle).texture())
         }
         ImageInputTexture::TextureRef(raw_texture) => {
             BorrowedOrOwnedTexture::Borrowed(raw_texture_as_texture(*raw_texture))
@@ -513,12 +513,7 @@ pub(super) fn run_pixelbender_shader_impl(
                             extent,
                         );
 
-                        BitmapHandle(Arc::new(Texture {
-                            texture: Arc::new(fresh_texture),
-                            bind_linear: Default::default(),
-                            bind_nearest: Default::default(),
-                            copy_count: Cell::new(0),
-                        }))
+                        BitmapHandle(Arc::new(Texture::new(Arc::new(fresh_texture))))
                     });
                     *texture = Some(cached_fresh_handle.clone().into());
                 }
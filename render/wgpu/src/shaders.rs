@@ -14,6 +14,7 @@ pub struct Shaders {
     pub bitmap_shader: wgpu::ShaderModule,
     pub gradient_shader: wgpu::ShaderModule,
     pub copy_srgb_shader: wgpu::ShaderModule,
+    pub copy_linear_shader: wgpu::ShaderModule,
     pub copy_shader: wgpu::ShaderModule,
     pub blend_shaders: EnumMap<ComplexBlend, wgpu::ShaderModule>,
     pub color_matrix_filter: wgpu::ShaderModule,
@@ -36,6 +37,11 @@ impl Shaders {
             "copy_srgb.wgsl",
             include_str!("../shaders/copy_srgb.wgsl"),
         );
+        let copy_linear_shader = make_shader(
+            device,
+            "copy_linear.wgsl",
+            include_str!("../shaders/copy_linear.wgsl"),
+        );
         let copy_shader = make_shader(device, "copy.wgsl", include_str!("../shaders/copy.wgsl"));
         let color_matrix_filter = make_filter_shader(
             device,
@@ -85,6 +91,7 @@ impl Shaders {
             bitmap_shader,
             gradient_shader,
             copy_srgb_shader,
+            copy_linear_shader,
             copy_shader,
             blend_shaders,
             color_matrix_filter,
@@ -1,7 +1,7 @@
 mod commands;
 pub mod target;
 
-use crate::backend::RenderTargetMode;
+use crate::backend::{BlendSpace, RenderTargetMode};
 use crate::blend::ComplexBlend;
 use crate::buffer_pool::TexturePool;
 use crate::dynamic_transforms::DynamicTransforms;
@@ -9,7 +9,7 @@ use crate::filters::FilterSource;
 use crate::mesh::Mesh;
 use crate::pixel_bender::{run_pixelbender_shader_impl, ShaderMode};
 use crate::surface::commands::{chunk_blends, Chunk, CommandRenderer};
-use crate::utils::{remove_srgb, supported_sample_count};
+use crate::utils::{add_srgb, remove_srgb, supported_sample_count};
 use crate::{Descriptors, MaskState, Pipelines};
 use ruffle_render::commands::CommandList;
 use ruffle_render::pixel_bender::{ImageInputTexture, PixelBenderShaderArgument};
@@ -28,6 +28,7 @@ use self::commands::ChunkBlendMode;
 pub struct Surface {
     size: wgpu::Extent3d,
     quality: StageQuality,
+    blend_space: BlendSpace,
     sample_count: u32,
     pipelines: Arc<Pipelines>,
     format: wgpu::TextureFormat,
@@ -38,6 +39,7 @@ impl Surface {
     pub fn new(
         descriptors: &Descriptors,
         quality: StageQuality,
+        blend_space: BlendSpace,
         width: u32,
         height: u32,
         surface_format: wgpu::TextureFormat,
@@ -47,7 +49,11 @@ impl Surface {
             height,
             depth_or_array_layers: 1,
         };
-        let frame_buffer_format = remove_srgb(surface_format);
+        // The GPU blends in linear space when drawing to sRGB textures.
+        let frame_buffer_format = match blend_space {
+            BlendSpace::Flash => remove_srgb(surface_format),
+            BlendSpace::Linear => add_srgb(surface_format),
+        };
 
         let sample_count = supported_sample_count(
             &descriptors.adapter,
@@ -58,6 +64,7 @@ impl Surface {
         Self {
             size,
             quality,
+            blend_space,
             sample_count,
             pipelines,
             format: frame_buffer_format,
@@ -139,6 +146,7 @@ impl Surface {
             draw_encoder,
             meshes,
             self.quality,
+            self.blend_space,
             target.width(),
             target.height(),
             match nearest_layer {
@@ -194,33 +202,45 @@ impl Surface {
                     num_masks = renderer.num_masks();
                     mask_state = renderer.mask_state();
                 }
-                Chunk::Blend(texture, ChunkBlendMode::Shader(shader), needs_stencil) => {
+                Chunk::Blend(texture, ChunkBlendMode::Shader(blend_shader), needs_stencil) => {
                     assert!(
                         !needs_stencil,
                         "Shader blend should not need stencil buffer"
                     );
                     let parent_blend_buffer =
                         target.update_blend_buffer(descriptors, texture_pool, draw_encoder);
+
+                    // The first image input is the background, and the second one is the
+                    // display object. Any other input and parameter keeps the value it had.
+                    let mut blended_textures =
+                        [parent_blend_buffer.texture(), texture.texture()].into_iter();
+                    let arguments: Vec<_> = blend_shader
+                        .arguments
+                        .iter()
+                        .map(|argument| match argument {
+                            PixelBenderShaderArgument::ImageInput {
+                                index,
+                                channels,
+                                name,
+                                texture,
+                            } => PixelBenderShaderArgument::ImageInput {
+                                index: *index,
+                                channels: *channels,
+                                name: name.clone(),
+                                texture: blended_textures
+                                    .next()
+                                    .map(|texture| ImageInputTexture::TextureRef(texture))
+                                    .or_else(|| texture.clone()),
+                            },
+                            argument => argument.clone(),
+                        })
+                        .collect();
+
                     run_pixelbender_shader_impl(
                         descriptors,
-                        shader,
+                        blend_shader.shader,
                         ShaderMode::Filter,
-                        &[
-                            PixelBenderShaderArgument::ImageInput {
-                                index: 0,
-                                channels: 0xFF,
-                                name: "background".to_string(),
-                                texture: Some(ImageInputTexture::TextureRef(
-                                    parent_blend_buffer.texture(),
-                                )),
-                            },
-                            PixelBenderShaderArgument::ImageInput {
-                                index: 1,
-                                channels: 0xff,
-                                name: "foreground".to_string(),
-                                texture: Some(ImageInputTexture::TextureRef(texture.texture())),
-                            },
-                        ],
+                        &arguments,
                         parent_blend_buffer.texture(),
                         draw_encoder,
                         target.color_attachments(),
@@ -353,6 +373,10 @@ impl Surface {
         self.quality
     }
 
+    pub fn blend_space(&self) -> BlendSpace {
+        self.blend_space
+    }
+
     pub fn sample_count(&self) -> u32 {
         self.sample_count
     }
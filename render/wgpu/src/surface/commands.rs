@@ -1,4 +1,4 @@
-use crate::backend::RenderTargetMode;
+use crate::backend::{BlendSpace, RenderTargetMode};
 use crate::blend::TrivialBlend;
 use crate::blend::{BlendType, ComplexBlend};
 use crate::buffer_builder::BufferBuilder;
@@ -7,13 +7,13 @@ use crate::dynamic_transforms::DynamicTransforms;
 use crate::mesh::{as_mesh, DrawType, Mesh};
 use crate::surface::target::CommandTarget;
 use crate::surface::Surface;
+use crate::utils::remove_srgb;
 use crate::{as_texture, Descriptors, MaskState, Pipelines, Transforms};
 use ruffle_render::backend::ShapeHandle;
 use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
-use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::commands::{BlendShader, CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::lines::{emulate_line, emulate_line_rect};
 use ruffle_render::matrix::Matrix;
-use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
 use std::mem;
@@ -143,6 +143,7 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
     pub fn prep_bitmap(
         &mut self,
         bind_group: &'pass wgpu::BindGroup,
+        texture_transforms: wgpu::DynamicOffset,
         blend_mode: TrivialBlend,
         render_stage3d: bool,
     ) {
@@ -164,7 +165,8 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
             }
         }
 
-        self.render_pass.set_bind_group(2, bind_group, &[]);
+        self.render_pass
+            .set_bind_group(2, bind_group, &[texture_transforms]);
     }
 
     pub fn draw(
@@ -195,7 +197,7 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
         let texture = as_texture(bitmap);
 
         let descriptors = self.descriptors;
-        let bind = texture.bind_group(
+        let (bind, texture_transforms) = texture.bind_group(
             smoothing,
             &descriptors.device,
             &descriptors.bind_layouts.bitmap,
@@ -203,7 +205,12 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
             bitmap.clone(),
             &descriptors.bitmap_samplers,
         );
-        self.prep_bitmap(&bind.bind_group, blend_mode, render_stage3d);
+        self.prep_bitmap(
+            &bind.bind_group,
+            texture_transforms,
+            blend_mode,
+            render_stage3d,
+        );
         self.render_pass.set_bind_group(
             1,
             &self.dynamic_transforms.bind_group,
@@ -229,7 +236,7 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
         if cfg!(feature = "render_debug_labels") {
             self.render_pass.push_debug_group("render_texture");
         }
-        self.prep_bitmap(bind_group, blend_mode, false);
+        self.prep_bitmap(bind_group, 0, blend_mode, false);
 
         self.render_pass.set_bind_group(
             1,
@@ -278,7 +285,7 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                     self.prep_gradient(bind_group);
                 }
                 DrawType::Bitmap { binds, .. } => {
-                    self.prep_bitmap(&binds.bind_group, TrivialBlend::Normal, false);
+                    self.prep_bitmap(&binds.bind_group, 0, TrivialBlend::Normal, false);
                 }
             }
             self.render_pass.set_bind_group(
@@ -395,7 +402,7 @@ pub enum Chunk {
 #[derive(Debug)]
 pub enum ChunkBlendMode {
     Complex(ComplexBlend),
-    Shader(PixelBenderShaderHandle),
+    Shader(BlendShader),
 }
 
 #[derive(Debug)]
@@ -450,6 +457,7 @@ pub fn chunk_blends<'a>(
     draw_encoder: &mut wgpu::CommandEncoder,
     meshes: &'a Vec<Mesh>,
     quality: StageQuality,
+    blend_space: BlendSpace,
     width: u32,
     height: u32,
     nearest_layer: LayerRef,
@@ -462,6 +470,7 @@ pub fn chunk_blends<'a>(
         draw_encoder,
         meshes,
         quality,
+        blend_space,
         width,
         height,
         nearest_layer,
@@ -473,6 +482,7 @@ pub fn chunk_blends<'a>(
 struct WgpuCommandHandler<'a> {
     descriptors: &'a Descriptors,
     quality: StageQuality,
+    blend_space: BlendSpace,
     width: u32,
     height: u32,
     nearest_layer: LayerRef<'a>,
@@ -499,6 +509,7 @@ impl<'a> WgpuCommandHandler<'a> {
         draw_encoder: &'a mut wgpu::CommandEncoder,
         meshes: &'a Vec<Mesh>,
         quality: StageQuality,
+        blend_space: BlendSpace,
         width: u32,
         height: u32,
         nearest_layer: LayerRef<'a>,
@@ -514,6 +525,7 @@ impl<'a> WgpuCommandHandler<'a> {
         Self {
             descriptors,
             quality,
+            blend_space,
             width,
             height,
             nearest_layer,
@@ -613,6 +625,7 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
         let mut surface = Surface::new(
             self.descriptors,
             self.quality,
+            self.blend_space,
             self.width,
             self.height,
             wgpu::TextureFormat::Rgba8Unorm,
@@ -644,6 +657,13 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
                     color_transform: Default::default(),
                 };
                 let texture = target.take_color_texture();
+                // The bitmap shader expects colors as they're stored, not decoded from sRGB.
+                let stored_view = texture.texture().format().is_srgb().then(|| {
+                    texture.texture().create_view(&wgpu::TextureViewDescriptor {
+                        format: Some(remove_srgb(texture.texture().format())),
+                        ..Default::default()
+                    })
+                });
                 let bind_group =
                     self.descriptors
                         .device
@@ -660,7 +680,9 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 1,
-                                    resource: wgpu::BindingResource::TextureView(texture.view()),
+                                    resource: wgpu::BindingResource::TextureView(
+                                        stored_view.as_ref().unwrap_or(texture.view()),
+                                    ),
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 2,
@@ -721,10 +743,7 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
         {
             let texture = as_texture(&bitmap);
             pixel_snapping.apply(&mut matrix);
-            matrix *= Matrix::scale(
-                texture.texture.width() as f32,
-                texture.texture.height() as f32,
-            );
+            matrix *= Matrix::scale(texture.width() as f32, texture.height() as f32);
         }
         self.add_to_current(matrix, transform.color_transform, |transform_buffer| {
             DrawCommand::RenderBitmap {
@@ -741,8 +760,8 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
         {
             let texture = as_texture(&bitmap);
             matrix *= Matrix::scale(
-                texture.texture.width() as f32,
-                texture.texture.height() as f32,
+                texture.texture().width() as f32,
+                texture.texture().height() as f32,
             );
         }
         self.add_to_current(matrix, transform.color_transform, |transform_buffer| {
@@ -37,6 +37,15 @@ pub fn remove_srgb(format: wgpu::TextureFormat) -> wgpu::TextureFormat {
     }
 }
 
+/// The sRGB variant of a format that can be rendered to, which the GPU blends in linear space.
+pub fn add_srgb(format: wgpu::TextureFormat) -> wgpu::TextureFormat {
+    match format {
+        wgpu::TextureFormat::Rgba8Unorm => wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::TextureFormat::Bgra8Unorm => wgpu::TextureFormat::Bgra8UnormSrgb,
+        _ => format,
+    }
+}
+
 pub fn format_list<'a>(values: &[&'a str], connector: &'a str) -> Cow<'a, str> {
     match values.len() {
         0 => Cow::Borrowed(""),
@@ -231,6 +240,8 @@ pub fn run_copy_pipeline(
 
     let pipeline = if actual_surface_format == format {
         descriptors.copy_pipeline(format, sample_count)
+    } else if format.is_srgb() && !actual_surface_format.is_srgb() {
+        descriptors.copy_linear_pipeline(actual_surface_format, sample_count)
     } else {
         descriptors.copy_srgb_pipeline(actual_surface_format, sample_count)
     };
@@ -256,7 +267,7 @@ pub fn run_copy_pipeline(
     render_pass.set_bind_group(0, globals.bind_group(), &[]);
 
     render_pass.set_bind_group(1, whole_frame_bind_group, &[0]);
-    render_pass.set_bind_group(2, &copy_bind_group, &[]);
+    render_pass.set_bind_group(2, &copy_bind_group, &[0]);
 
     render_pass.set_vertex_buffer(0, descriptors.quad.vertices_pos.slice(..));
     render_pass.set_index_buffer(
@@ -0,0 +1,32 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use swf::Tag;
+
+// Reads a whole SWF file, along with the ActionScript in it.
+fuzz_target!(|data: &[u8]| {
+    let Ok(swf_buf) = swf::decompress_swf(data) else {
+        return;
+    };
+    let Ok(swf) = swf::parse_swf(&swf_buf) else {
+        return;
+    };
+    for tag in &swf.tags {
+        match tag {
+            Tag::DoAction(action_data) => {
+                let mut reader = swf::avm1::read::Reader::new(action_data, swf.header.version());
+                while reader
+                    .read_action()
+                    .is_ok_and(|action| action != swf::avm1::types::Action::End)
+                {}
+            }
+            Tag::DoAbc(abc_data) => {
+                let _ = swf::avm2::read::Reader::new(abc_data).read();
+            }
+            Tag::DoAbc2(do_abc) => {
+                let _ = swf::avm2::read::Reader::new(do_abc.data).read();
+            }
+            _ => {}
+        }
+    }
+});
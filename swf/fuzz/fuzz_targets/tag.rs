@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Reads tags one after another, with the SWF version given by the first byte.
+// Unlike the `swf` target, this doesn't need inputs to be valid SWF files.
+fuzz_target!(|data: &[u8]| {
+    let Some((&version, data)) = data.split_first() else {
+        return;
+    };
+    let mut reader = swf::read::Reader::new(data, version);
+    while !reader.get_ref().is_empty() && reader.read_tag().is_ok() {}
+});
@@ -2,3 +2,5 @@ pub(crate) mod opcode;
 pub mod read;
 pub mod types;
 pub mod write;
+
+pub use opcode::OpCode;
@@ -1,7 +1,6 @@
 use crate::avm2::types::*;
 use crate::error::{Error, Result};
 use crate::extensions::ReadSwfExt;
-use std::io::Read;
 
 pub struct Reader<'a> {
     input: &'a [u8],
@@ -40,7 +39,7 @@ impl<'a> Reader<'a> {
         let constant_pool = self.read_constant_pool()?;
 
         let len = self.read_u30()?;
-        let mut methods = Vec::with_capacity(len as usize);
+        let mut methods = self.vec_with_capacity(len);
         for _ in 0..len {
             methods.push(self.read_method()?);
         }
@@ -49,31 +48,35 @@ impl<'a> Reader<'a> {
         let metadata = self.read_metadata(len)?;
 
         let len = self.read_u30()?;
-        let mut instances = Vec::with_capacity(len as usize);
+        let mut instances = self.vec_with_capacity(len);
         for _ in 0..len {
             instances.push(self.read_instance()?);
         }
 
-        let mut classes = Vec::with_capacity(len as usize);
+        let mut classes = self.vec_with_capacity(len);
         for _ in 0..len {
             classes.push(self.read_class()?);
         }
 
         let len = self.read_u30()?;
-        let mut scripts = Vec::with_capacity(len as usize);
+        let mut scripts = self.vec_with_capacity(len);
         for _ in 0..len {
             scripts.push(self.read_script()?);
         }
 
         let len = self.read_u30()?;
-        let mut method_bodies = Vec::with_capacity(len as usize);
+        let mut method_bodies = self.vec_with_capacity(len);
         for body_idx in 0..len {
             let body = self.read_method_body()?;
-            if methods[body.method.0 as usize].body.is_some() {
-                // TODO: this should somehow throw error 1121 in FP.
-                return Err(Error::invalid_data("Duplicate method body"));
+            // Obfuscators like to add bodies for methods that don't exist, or several
+            // bodies for the same method. These are never reachable, so rather than
+            // rejecting the whole file, leave them unattached and keep the first body.
+            // TODO: duplicate bodies should somehow throw error 1121 in FP.
+            if let Some(method) = methods.get_mut(body.method.0 as usize) {
+                if method.body.is_none() {
+                    method.body = Some(Index::new(body_idx));
+                }
             }
-            methods[body.method.0 as usize].body = Some(Index::new(body_idx));
             method_bodies.push(body);
         }
 
@@ -91,6 +94,14 @@ impl<'a> Reader<'a> {
         })
     }
 
+    /// Creates a vector for `len` entries that are about to be read.
+    ///
+    /// Every entry takes at least one byte, so this never reserves more than the rest of the
+    /// file could hold, even when a damaged file gives a huge length.
+    fn vec_with_capacity<T>(&self, len: u32) -> Vec<T> {
+        Vec::with_capacity((len as usize).min(self.input.len()))
+    }
+
     fn read_u30(&mut self) -> Result<u32> {
         self.read_encoded_u32()
     }
@@ -108,9 +119,7 @@ impl<'a> Reader<'a> {
     fn read_string(&mut self) -> Result<Vec<u8>> {
         let len = self.read_u30()?;
         // TODO: Avoid allocating a String.
-        let mut s = Vec::with_capacity(len as usize);
-        self.read_slice(len as usize)?.read_to_end(&mut s)?;
-        Ok(s)
+        Ok(self.read_slice(len as usize)?.to_vec())
     }
 
     fn read_index<T>(&mut self) -> Result<Index<T>> {
@@ -137,7 +146,7 @@ impl<'a> Reader<'a> {
 
     fn read_namespace_set(&mut self) -> Result<NamespaceSet> {
         let len = self.read_u30()?;
-        let mut namespace_set = Vec::with_capacity(len as usize);
+        let mut namespace_set = self.vec_with_capacity(len);
         for _ in 0..len {
             namespace_set.push(self.read_index()?);
         }
@@ -180,7 +189,7 @@ impl<'a> Reader<'a> {
             0x1d => {
                 let base_type = self.read_index()?;
                 let count = self.read_u30()?;
-                let mut parameters = Vec::with_capacity(count as usize);
+                let mut parameters = self.vec_with_capacity(count);
 
                 for _ in 0..count {
                     parameters.push(self.read_index()?);
@@ -197,43 +206,43 @@ impl<'a> Reader<'a> {
 
     fn read_constant_pool(&mut self) -> Result<ConstantPool> {
         let len = self.read_u30()?.saturating_sub(1);
-        let mut ints = Vec::with_capacity(len as usize);
+        let mut ints = self.vec_with_capacity(len);
         for _ in 0..len {
             ints.push(self.read_i32()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut uints = Vec::with_capacity(len as usize);
+        let mut uints = self.vec_with_capacity(len);
         for _ in 0..len {
             uints.push(self.read_u30()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut doubles = Vec::with_capacity(len as usize);
+        let mut doubles = self.vec_with_capacity(len);
         for _ in 0..len {
             doubles.push(self.read_f64()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut strings = Vec::with_capacity(len as usize);
+        let mut strings = self.vec_with_capacity(len);
         for _ in 0..len {
             strings.push(self.read_string()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut namespaces = Vec::with_capacity(len as usize);
+        let mut namespaces = self.vec_with_capacity(len);
         for _ in 0..len {
             namespaces.push(self.read_namespace()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut namespace_sets = Vec::with_capacity(len as usize);
+        let mut namespace_sets = self.vec_with_capacity(len);
         for _ in 0..len {
             namespace_sets.push(self.read_namespace_set()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut multinames = Vec::with_capacity(len as usize);
+        let mut multinames = self.vec_with_capacity(len);
         for _ in 0..len {
             multinames.push(self.read_multiname()?);
         }
@@ -252,7 +261,7 @@ impl<'a> Reader<'a> {
     fn read_method(&mut self) -> Result<Method> {
         let num_params = self.read_u30()?;
         let return_type = self.read_index()?;
-        let mut params = Vec::with_capacity(num_params as usize);
+        let mut params = self.vec_with_capacity(num_params);
         for _ in 0..num_params {
             params.push(MethodParam {
                 kind: self.read_index()?,
@@ -338,19 +347,19 @@ impl<'a> Reader<'a> {
     }
 
     fn read_metadata(&mut self, len: u32) -> Result<Vec<Metadata>> {
-        let mut metadata = Vec::with_capacity(len as usize);
+        let mut metadata = self.vec_with_capacity(len);
         for _ in 0..len {
             let name = self.read_index()?;
             let num_items = self.read_u30()?;
-            let mut key_value_data = Vec::with_capacity(num_items as usize * 2);
+            let mut key_value_data = self.vec_with_capacity(num_items.saturating_mul(2));
 
             // Data includes the keys and values
-            for _ in 0..num_items * 2 {
+            for _ in 0..num_items.saturating_mul(2) {
                 key_value_data.push(self.read_index()?);
             }
 
             // Split them up here
-            let mut items = Vec::with_capacity(num_items as usize);
+            let mut items = self.vec_with_capacity(num_items);
             for i in 0..num_items {
                 items.push(MetadataItem {
                     key: key_value_data[i as usize],
@@ -376,7 +385,7 @@ impl<'a> Reader<'a> {
         };
 
         let num_interfaces = self.read_u30()?;
-        let mut interfaces = Vec::with_capacity(num_interfaces as usize);
+        let mut interfaces = self.vec_with_capacity(num_interfaces);
         for _ in 0..num_interfaces {
             interfaces.push(self.read_index()?);
         }
@@ -384,7 +393,7 @@ impl<'a> Reader<'a> {
         let init_method = self.read_index()?;
 
         let num_traits = self.read_u30()?;
-        let mut traits = Vec::with_capacity(num_traits as usize);
+        let mut traits = self.vec_with_capacity(num_traits);
         for _ in 0..num_traits {
             traits.push(self.read_trait()?);
         }
@@ -405,7 +414,7 @@ impl<'a> Reader<'a> {
     fn read_class(&mut self) -> Result<Class> {
         let init_method = self.read_index()?;
         let num_traits = self.read_u30()?;
-        let mut traits = Vec::with_capacity(num_traits as usize);
+        let mut traits = self.vec_with_capacity(num_traits);
         for _ in 0..num_traits {
             traits.push(self.read_trait()?);
         }
@@ -418,7 +427,7 @@ impl<'a> Reader<'a> {
     fn read_script(&mut self) -> Result<Script> {
         let init_method = self.read_index()?;
         let num_traits = self.read_u30()?;
-        let mut traits = Vec::with_capacity(num_traits as usize);
+        let mut traits = self.vec_with_capacity(num_traits);
         for _ in 0..num_traits {
             traits.push(self.read_trait()?);
         }
@@ -496,13 +505,13 @@ impl<'a> Reader<'a> {
         let code = self.read_slice(code_len as usize)?.to_vec();
 
         let num_exceptions = self.read_u30()?;
-        let mut exceptions = Vec::with_capacity(num_exceptions as usize);
+        let mut exceptions = self.vec_with_capacity(num_exceptions);
         for _ in 0..num_exceptions {
             exceptions.push(self.read_exception()?);
         }
 
         let num_traits = self.read_u30()?;
-        let mut traits = Vec::with_capacity(num_traits as usize);
+        let mut traits = self.vec_with_capacity(num_traits);
         for _ in 0..num_traits {
             traits.push(self.read_trait()?);
         }
@@ -764,8 +773,8 @@ impl<'a> Reader<'a> {
             OpCode::LookupSwitch => Op::LookupSwitch(Box::new(LookupSwitch {
                 default_offset: self.read_i24()?,
                 case_offsets: {
-                    let num_cases = self.read_u30()? + 1;
-                    let mut case_offsets = Vec::with_capacity(num_cases as usize);
+                    let num_cases = self.read_u30()?.saturating_add(1);
+                    let mut case_offsets = self.vec_with_capacity(num_cases);
                     for _ in 0..num_cases {
                         case_offsets.push(self.read_i24()?);
                     }
@@ -931,6 +940,62 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn read_tolerates_orphan_and_duplicate_method_bodies() {
+        use crate::avm2::write::Writer;
+
+        let body = |method, code| MethodBody {
+            method: Index::new(method),
+            max_stack: 0,
+            num_locals: 1,
+            init_scope_depth: 0,
+            max_scope_depth: 1,
+            code,
+            exceptions: vec![],
+            traits: vec![],
+        };
+        let abc = AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec![],
+                namespaces: vec![],
+                namespace_sets: vec![],
+                multinames: vec![],
+            },
+            methods: vec![Method {
+                name: Index::new(0),
+                params: vec![],
+                return_type: Index::new(0),
+                flags: MethodFlags::empty(),
+                body: None,
+            }],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![body(0, vec![0x47]), body(0, vec![0x02]), body(7, vec![])],
+        };
+
+        let mut bytes = vec![];
+        Writer::new(&mut bytes).write(abc).unwrap();
+        let parsed = Reader::new(&bytes[..]).read().unwrap();
+
+        assert_eq!(parsed.methods[0].body, Some(Index::new(0)));
+        assert_eq!(parsed.method_bodies.len(), 3);
+    }
+
+    /// Ensure that huge lengths in a damaged file are an error, rather than reserving memory.
+    #[test]
+    fn read_huge_length() {
+        // A constant pool claiming to have 4 billion ints.
+        let bytes = [0x10, 0x00, 0x2e, 0x00, 0xff, 0xff, 0xff, 0xff, 0x0f];
+        assert!(Reader::new(&bytes[..]).read().is_err());
+    }
+
     #[test]
     fn read_u30() {
         let read = |data: &[u8]| Reader::new(data).read_u30().unwrap();
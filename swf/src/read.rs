@@ -1,7 +1,7 @@
 use crate::extensions::ReadSwfExt;
 use crate::{
     error::{Error, Result},
-    string::{Encoding, SwfStr},
+    string::{Encoding, SwfStr, UTF_8},
     tag_code::TagCode,
     types::*,
 };
@@ -105,12 +105,12 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
             }
             // Uncompressed length includes the 4-byte header and 4-byte uncompressed length itself,
             // subtract it here.
-            make_lzma_reader(input, uncompressed_len - 8)?
+            make_lzma_reader(input, uncompressed_len.saturating_sub(8))?
         }
     };
 
     // Decompress the entire SWF.
-    let mut data = Vec::with_capacity(uncompressed_len as usize);
+    let mut data = Vec::with_capacity(preallocated_len(uncompressed_len));
     if let Err(e) = decompress_stream.read_to_end(&mut data) {
         log::error!("Error decompressing SWF: {}", e);
     }
@@ -152,13 +152,20 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
 
     // In most SWFs, SetBackgroundColor will be the second or third tag after FileAttributes + Metadata.
     // It's possible for the SetBackgroundColor tag to be missing or appear later in wacky SWFs, so let's
-    // return `None` in this case.
+    // return `None` in this case. The same goes for the Metadata tag, which should directly follow
+    // FileAttributes.
     let mut background_color = None;
+    let mut metadata = None;
     for _ in 0..2 {
-        if let Ok(Tag::SetBackgroundColor(color)) = tag {
-            background_color = Some(color);
-            break;
-        };
+        match tag {
+            Ok(Tag::SetBackgroundColor(color)) => {
+                background_color = Some(color);
+                break;
+            }
+            // The XMP metadata is always UTF-8, whatever the SWF version.
+            Ok(Tag::Metadata(xmp)) => metadata = Some(xmp.to_string_lossy(UTF_8)),
+            _ => {}
+        }
         tag = reader.read_tag();
     }
 
@@ -167,6 +174,7 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
             header,
             file_attributes,
             background_color,
+            metadata,
             uncompressed_len: uncompressed_len as i32,
         },
         data,
@@ -186,6 +194,15 @@ fn make_zlib_reader<'a, R: Read + 'a>(_input: R) -> Result<Box<dyn Read + 'a>> {
     ))
 }
 
+/// How much memory to reserve for decompressing a SWF of the given length.
+///
+/// The length comes from the header, which can't be trusted in damaged files,
+/// so larger SWFs grow their buffer as they're decompressed instead.
+fn preallocated_len(uncompressed_len: u32) -> usize {
+    const MAX_PREALLOCATED_LEN: usize = 64 * 1024 * 1024;
+    (uncompressed_len as usize).min(MAX_PREALLOCATED_LEN)
+}
+
 #[cfg(feature = "lzma")]
 fn make_lzma_reader<'a, R: Read + 'a>(
     mut input: R,
@@ -214,7 +231,7 @@ fn make_lzma_reader<'a, R: Read + 'a>(
     let _ = input.read_u32::<LittleEndian>()?;
 
     // TODO: Switch to lzma-rs streaming API when stable.
-    let mut output = Vec::with_capacity(uncompressed_length as usize);
+    let mut output = Vec::with_capacity(preallocated_len(uncompressed_length));
     lzma_decompress_with_options(
         &mut io::BufReader::new(input),
         &mut output,
@@ -912,8 +929,9 @@ impl<'a> Reader<'a> {
     pub fn read_define_scene_and_frame_label_data(
         &mut self,
     ) -> Result<DefineSceneAndFrameLabelData<'a>> {
+        // Damaged files can give huge counts, so don't reserve more than the tag could hold.
         let num_scenes = self.read_encoded_u32()? as usize;
-        let mut scenes = Vec::with_capacity(num_scenes);
+        let mut scenes = Vec::with_capacity(num_scenes.min(self.input.len()));
         for _ in 0..num_scenes {
             scenes.push(FrameLabelData {
                 frame_num: self.read_encoded_u32()?,
@@ -922,7 +940,7 @@ impl<'a> Reader<'a> {
         }
 
         let num_frame_labels = self.read_encoded_u32()? as usize;
-        let mut frame_labels = Vec::with_capacity(num_frame_labels);
+        let mut frame_labels = Vec::with_capacity(num_frame_labels.min(self.input.len()));
         for _ in 0..num_frame_labels {
             frame_labels.push(FrameLabelData {
                 frame_num: self.read_encoded_u32()?,
@@ -946,7 +964,6 @@ impl<'a> Reader<'a> {
 
         let mut glyphs = Vec::with_capacity(num_glyphs as usize);
         for _ in 0..num_glyphs {
-            let mut glyph = vec![];
             let num_bits = self.read_u8()?;
             let mut shape_context = ShapeContext {
                 swf_version: self.version,
@@ -954,11 +971,10 @@ impl<'a> Reader<'a> {
                 num_fill_bits: num_bits >> 4,
                 num_line_bits: num_bits & 0b1111,
             };
-            let mut bits = self.bits();
-            while let Some(record) = Self::read_shape_record(&mut bits, &mut shape_context)? {
-                glyph.push(record);
-            }
-            glyphs.push(glyph);
+            glyphs.push(Self::read_shape_records(
+                &mut self.bits(),
+                &mut shape_context,
+            ));
         }
 
         Ok(FontV1 { id, glyphs })
@@ -1019,19 +1035,17 @@ impl<'a> Reader<'a> {
             // GlyphShapeTable
             for (i, glyph) in glyphs.iter_mut().enumerate() {
                 // The glyph shapes are assumed to be positioned per the offset table.
-                // Panic on debug builds if this assumption is wrong, maybe we need to
-                // seek into these offsets instead?
-                debug_assert_eq!(self.pos(offsets_ref), offsets[i] as usize);
+                // Maybe we need to seek into these offsets instead?
+                if self.pos(offsets_ref) != offsets[i] as usize {
+                    log::warn!("Glyph {i} of font {id} isn't at its offset");
+                }
 
                 // The glyph shapes must not overlap. Avoid exceeding to the next one.
-                // TODO: What happens on decreasing offsets?
-                let available_bytes = if i < num_glyphs - 1 {
-                    offsets[i + 1] - offsets[i]
-                } else {
-                    code_table_offset - offsets[i]
-                };
+                // TODO: What happens on decreasing offsets? For now, they don't bound the glyph.
+                let next_offset = offsets.get(i + 1).copied().unwrap_or(code_table_offset);
+                let available_bytes = next_offset.checked_sub(offsets[i]);
 
-                if available_bytes == 0 {
+                if available_bytes == Some(0) {
                     continue;
                 }
 
@@ -1043,21 +1057,20 @@ impl<'a> Reader<'a> {
                     num_line_bits: num_bits & 0b1111,
                 };
 
-                if available_bytes == 1 {
+                if available_bytes == Some(1) {
                     continue;
                 }
 
                 // TODO: Avoid reading more than `available_bytes - 1`?
-                let mut bits = self.bits();
-                while let Some(record) = Self::read_shape_record(&mut bits, &mut shape_context)? {
-                    glyph.shape_records.push(record);
-                }
+                glyph.shape_records =
+                    Self::read_shape_records(&mut self.bits(), &mut shape_context);
             }
 
             // The code table is assumed to be positioned right after the glyph shapes.
-            // Panic on debug builds if this assumption is wrong, maybe we need to seek
-            // into the code table offset instead?
-            debug_assert_eq!(self.pos(offsets_ref), code_table_offset as usize);
+            // Maybe we need to seek into the code table offset instead?
+            if self.pos(offsets_ref) != code_table_offset as usize {
+                log::warn!("Code table of font {id} isn't at its offset");
+            }
 
             // CodeTable
             for glyph in &mut glyphs {
@@ -1276,12 +1289,8 @@ impl<'a> Reader<'a> {
             num_fill_bits: bits.read_ubits(4)? as u8,
             num_line_bits: bits.read_ubits(4)? as u8,
         };
-        let mut start_shape = Vec::new();
-        while let Some(record) = Self::read_shape_record(&mut bits, &mut shape_context)? {
-            start_shape.push(record);
-        }
+        let start_shape = Self::read_shape_records(&mut bits, &mut shape_context);
 
-        let mut end_shape = Vec::new();
         self.read_u8()?; // NumFillBits and NumLineBits are written as 0 for the end shape.
         let mut shape_context = ShapeContext {
             swf_version: self.version,
@@ -1289,10 +1298,7 @@ impl<'a> Reader<'a> {
             num_fill_bits: 0,
             num_line_bits: 0,
         };
-        let mut bits = self.bits();
-        while let Some(record) = Self::read_shape_record(&mut bits, &mut shape_context)? {
-            end_shape.push(record);
-        }
+        let end_shape = Self::read_shape_records(&mut self.bits(), &mut shape_context);
 
         Ok(DefineMorphShape {
             id,
@@ -1488,17 +1494,13 @@ impl<'a> Reader<'a> {
         }
 
         let (styles, num_fill_bits, num_line_bits) = self.read_shape_styles(version)?;
-        let mut records = Vec::new();
         let mut shape_context = ShapeContext {
             swf_version: self.version,
             shape_version: version,
             num_fill_bits,
             num_line_bits,
         };
-        let mut bits = self.bits();
-        while let Some(record) = Self::read_shape_record(&mut bits, &mut shape_context)? {
-            records.push(record);
-        }
+        let records = Self::read_shape_records(&mut self.bits(), &mut shape_context);
 
         Ok(Shape {
             version,
@@ -1717,6 +1719,28 @@ impl<'a> Reader<'a> {
         Ok((num_records, spread, interpolation))
     }
 
+    /// Reads the records of a shape, up to its end record.
+    ///
+    /// A damaged shape, such as one cut short in a truncated file, keeps the records read before
+    /// the damage, so that as much of it as possible can still be drawn.
+    fn read_shape_records(
+        bits: &mut BitReader<'_, '_>,
+        context: &mut ShapeContext,
+    ) -> Vec<ShapeRecord> {
+        let mut records = Vec::new();
+        loop {
+            match Self::read_shape_record(bits, context) {
+                Ok(Some(record)) => records.push(record),
+                Ok(None) => break,
+                Err(e) => {
+                    log::warn!("Shape is damaged after {} records: {}", records.len(), e);
+                    break;
+                }
+            }
+        }
+        records
+    }
+
     fn read_shape_record(
         bits: &mut BitReader<'_, '_>,
         context: &mut ShapeContext,
@@ -2065,7 +2089,7 @@ impl<'a> Reader<'a> {
             let mut length = self.read_u32()?;
             let key_code = if events.contains(ClipEventFlag::KEY_PRESS) {
                 // ActionData length includes the 1 byte key code.
-                length -= 1;
+                length = length.saturating_sub(1);
                 Some(self.read_u8()?)
             } else {
                 None
@@ -2170,8 +2194,8 @@ impl<'a> Reader<'a> {
         let num_matrix_rows = self.read_u8()?;
         let divisor = self.read_f32()?;
         let bias = self.read_f32()?;
-        let num_entries = num_matrix_cols * num_matrix_rows;
-        let mut matrix = Vec::with_capacity(num_entries as usize);
+        let num_entries = usize::from(num_matrix_cols) * usize::from(num_matrix_rows);
+        let mut matrix = Vec::with_capacity(num_entries);
         for _ in 0..num_entries {
             matrix.push(self.read_f32()?);
         }
@@ -2653,12 +2677,37 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn read_metadata() {
+        let xmp = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"/>"#;
+        let mut data = Vec::new();
+        crate::write_swf(
+            &Header::default_with_swf_version(10),
+            &[
+                Tag::FileAttributes(FileAttributes::HAS_METADATA),
+                Tag::Metadata(SwfStr::from_utf8_str(xmp)),
+                Tag::SetBackgroundColor(Color::WHITE),
+            ],
+            &mut data,
+        )
+        .unwrap();
+
+        let header = decompress_swf(&data[..]).unwrap().header;
+        assert!(header.has_metadata());
+        assert_eq!(header.metadata(), Some(xmp));
+        assert_eq!(header.background_color(), Some(Color::WHITE));
+    }
+
     #[test]
     fn read_invalid_swf() {
         let junk = [0u8; 128];
         let result = decompress_swf(&junk[..]);
         // TODO: Verify correct error.
         assert!(result.is_err());
+
+        // Damaged headers with lengths too small or too large to be real.
+        let _ = decompress_swf(&b"ZWS\x0d\x04\x00\x00\x00\x00\x00\x00\x00"[..]);
+        let _ = decompress_swf(&b"FWS\x0a\xff\xff\xff\xff\x00"[..]);
     }
 
     #[test]
@@ -3045,6 +3094,60 @@ pub mod tests {
         }
     }
 
+    /// Ensure that tags cut short anywhere are either read as well as possible or
+    /// return an error, without panicking.
+    #[test]
+    fn read_truncated_tags() {
+        for (swf_version, _, tag_bytes) in test_data::tag_tests() {
+            let mut reader = Reader::new(&tag_bytes[..], swf_version);
+            let (tag_code, tag_len) = reader.read_tag_code_and_length().unwrap();
+            let Some(tag_code) = TagCode::from_u16(tag_code) else {
+                continue;
+            };
+            for len in 0..tag_len {
+                let data = &reader.get_ref()[..len];
+                let _ = Reader::new(data, swf_version).read_tag_with_code(tag_code, len);
+            }
+        }
+    }
+
+    /// Ensure that lengths and counts from damaged tags can't overflow.
+    #[test]
+    fn read_damaged_lengths() {
+        // A key press clip action whose length doesn't include the key code.
+        let clip_action = reader(&[0x00, 0x00, 0x02, 0x00, 0, 0, 0, 0, 0x20])
+            .read_clip_action()
+            .unwrap()
+            .unwrap();
+        assert_eq!(clip_action.key_code, Some(0x20));
+        assert!(clip_action.action_data.is_empty());
+
+        // A 255x255 convolution matrix, without the data for it.
+        assert!(reader(&[255, 255, 0, 0, 0, 0, 0, 0, 0, 0])
+            .read_convolution_filter()
+            .is_err());
+    }
+
+    /// Ensure that a shape cut short keeps the records before the damage.
+    #[test]
+    fn read_truncated_shape() {
+        for (swf_version, tag, tag_bytes) in test_data::tag_tests() {
+            let Tag::DefineShape(expected) = tag else {
+                continue;
+            };
+            if expected.shape.is_empty() {
+                continue;
+            }
+            let mut reader = Reader::new(&tag_bytes[..], swf_version);
+            let (_, tag_len) = reader.read_tag_code_and_length().unwrap();
+            let data = &reader.get_ref()[..tag_len - 1];
+            let shape = Reader::new(data, swf_version)
+                .read_define_shape(expected.version)
+                .unwrap();
+            assert!(expected.shape.starts_with(&shape.shape));
+        }
+    }
+
     /// Ensure that we can read a PlaceObject3 tag that
     /// inccorrectly omits the 'is_bitmap_cached' u8
     /// Extracted from #7098
@@ -1,9 +1,11 @@
 mod audio;
+mod filesystem;
 mod log;
 mod navigator;
 mod ui;
 
 pub use audio::TestAudioBackend;
+pub use filesystem::TestFilesystemBackend;
 pub use log::TestLogBackend;
 pub use navigator::TestNavigatorBackend;
 pub use ui::TestUiBackend;
@@ -0,0 +1,108 @@
+use ruffle_core::backend::filesystem::FilesystemBackend;
+use std::io::{Read, Write};
+use vfs::{MemoryFS, VfsPath};
+
+/// An in-memory sandbox for AIR content, which starts out empty for every test.
+pub struct TestFilesystemBackend {
+    root: VfsPath,
+}
+
+impl Default for TestFilesystemBackend {
+    fn default() -> Self {
+        Self {
+            root: VfsPath::new(MemoryFS::new()),
+        }
+    }
+}
+
+impl TestFilesystemBackend {
+    fn resolve(&self, path: &str) -> Option<VfsPath> {
+        self.root.join(path.trim_start_matches('/')).ok()
+    }
+
+    fn create_parent_directory(&self, path: &str) -> bool {
+        match path.rsplit_once('/') {
+            Some((parent, _)) => self
+                .resolve(parent)
+                .is_some_and(|parent| parent.create_dir_all().is_ok()),
+            None => true,
+        }
+    }
+}
+
+impl FilesystemBackend for TestFilesystemBackend {
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        let mut data = Vec::new();
+        self.resolve(path)?
+            .open_file()
+            .ok()?
+            .read_to_end(&mut data)
+            .ok()?;
+        Some(data)
+    }
+
+    fn write(&mut self, path: &str, data: &[u8]) -> bool {
+        if !self.create_parent_directory(path) {
+            return false;
+        }
+        let Some(Ok(mut file)) = self.resolve(path).map(|path| path.create_file()) else {
+            return false;
+        };
+        file.write_all(data).is_ok()
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.resolve(path)
+            .is_some_and(|path| path.exists().unwrap_or(false))
+    }
+
+    fn is_directory(&self, path: &str) -> bool {
+        self.resolve(path)
+            .is_some_and(|path| path.is_dir().unwrap_or(false))
+    }
+
+    fn size(&self, path: &str) -> Option<u64> {
+        Some(self.resolve(path)?.metadata().ok()?.len)
+    }
+
+    fn list_directory(&self, path: &str) -> Option<Vec<String>> {
+        let entries = self.resolve(path)?.read_dir().ok()?;
+        Some(entries.map(|entry| entry.filename()).collect())
+    }
+
+    fn create_directory(&mut self, path: &str) -> bool {
+        self.resolve(path)
+            .is_some_and(|path| path.create_dir_all().is_ok())
+    }
+
+    fn delete(&mut self, path: &str, recursive: bool) -> bool {
+        let Some(path) = self.resolve(path) else {
+            return false;
+        };
+        let result = if path.is_dir().unwrap_or(false) {
+            if recursive {
+                path.remove_dir_all()
+            } else {
+                path.remove_dir()
+            }
+        } else {
+            path.remove_file()
+        };
+        result.is_ok()
+    }
+
+    fn rename(&mut self, from: &str, to: &str) -> bool {
+        if !self.exists(from) || self.exists(to) || !self.create_parent_directory(to) {
+            return false;
+        }
+        let (Some(source), Some(destination)) = (self.resolve(from), self.resolve(to)) else {
+            return false;
+        };
+        let result = if source.is_dir().unwrap_or(false) {
+            source.move_dir(&destination)
+        } else {
+            source.move_file(&destination)
+        };
+        result.is_ok()
+    }
+}
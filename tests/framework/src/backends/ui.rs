@@ -107,6 +107,10 @@ impl UiBackend for TestUiBackend {
         self.clipboard = content;
     }
 
+    fn request_clipboard_read_permission(&mut self) -> bool {
+        true
+    }
+
     fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
         Ok(())
     }
@@ -150,6 +150,10 @@ pub struct PlayerOptions {
     with_audio: bool,
     with_video: bool,
     runtime: PlayerRuntime,
+    random_seed: Option<u64>,
+    partial_playback: bool,
+    avm2_strict_verification: bool,
+    log_frame_events: bool,
 }
 
 impl PlayerOptions {
@@ -172,7 +176,12 @@ impl PlayerOptions {
             player_builder = player_builder.with_audio(TestAudioBackend::default());
         }
 
-        player_builder = player_builder.with_player_runtime(self.runtime);
+        player_builder = player_builder
+            .with_player_runtime(self.runtime)
+            .with_random_seed(self.random_seed)
+            .with_partial_playback(self.partial_playback)
+            .with_avm2_strict_verification(self.avm2_strict_verification)
+            .with_frame_event_logging(self.log_frame_events);
 
         if self.with_video {
             #[cfg(feature = "ruffle_video_external")]
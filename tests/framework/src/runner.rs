@@ -1,4 +1,4 @@
-use crate::backends::{TestLogBackend, TestNavigatorBackend, TestUiBackend};
+use crate::backends::{TestFilesystemBackend, TestLogBackend, TestNavigatorBackend, TestUiBackend};
 use crate::environment::RenderInterface;
 use crate::fs_commands::{FsCommand, TestFsCommandProvider};
 use crate::image_trigger::ImageTrigger;
@@ -9,7 +9,7 @@ use anyhow::{anyhow, Result};
 use image::ImageFormat;
 use pretty_assertions::Comparison;
 use ruffle_core::backend::navigator::NullExecutor;
-use ruffle_core::events::{KeyCode, TextControlCode as RuffleTextControlCode};
+use ruffle_core::events::{KeyCode, KeyLocation, TextControlCode as RuffleTextControlCode};
 use ruffle_core::events::{MouseButton as RuffleMouseButton, MouseWheelDelta};
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
@@ -86,7 +86,10 @@ impl TestRunner {
             .with_log(log.clone())
             .with_navigator(navigator)
             .with_max_execution_duration(Duration::from_secs(300))
+            // Timers must fire the same way however fast the machine running the tests is.
+            .with_timer_budget(Duration::MAX)
             .with_fs_commands(Box::new(fs_command_provider))
+            .with_filesystem(Box::new(TestFilesystemBackend::default()))
             .with_ui(TestUiBackend::new(test.fonts()?))
             .with_viewport_dimensions(
                 viewport_dimensions.width,
@@ -238,10 +241,12 @@ impl TestRunner {
                 AutomatedEvent::KeyDown { key_code } => PlayerEvent::KeyDown {
                     key_code: KeyCode::from_code(*key_code),
                     key_char: None,
+                    key_location: KeyLocation::Standard,
                 },
                 AutomatedEvent::KeyUp { key_code } => PlayerEvent::KeyUp {
                     key_code: KeyCode::from_code(*key_code),
                     key_char: None,
+                    key_location: KeyLocation::Standard,
                 },
                 AutomatedEvent::TextInput { codepoint } => PlayerEvent::TextInput {
                     codepoint: *codepoint,
@@ -0,0 +1,27 @@
+// Assembled by hand; this is the equivalent ActionScript of the frame script.
+import flash.filesystem.File;
+import flash.filesystem.FileMode;
+import flash.filesystem.FileStream;
+
+var storage:File = File.applicationStorageDirectory;
+var source:File = storage.resolvePath("old");
+source.resolvePath("nested").createDirectory();
+var stream:FileStream = new FileStream();
+stream.open(source.resolvePath("nested/a.txt"), FileMode.WRITE);
+stream.writeUTFBytes("hello");
+stream.close();
+
+var destination:File = storage.resolvePath("new");
+source.moveTo(destination);
+trace(source.exists);
+trace(destination.isDirectory);
+trace(destination.resolvePath("nested/a.txt").size);
+
+var copy:File = storage.resolvePath("copy");
+destination.copyTo(copy);
+trace(destination.exists);
+trace(copy.resolvePath("nested/a.txt").size);
+
+copy.moveTo(destination, true);
+trace(copy.exists);
+trace(destination.resolvePath("nested/a.txt").size);
@@ -0,0 +1,27 @@
+// Assembled by hand; this is the equivalent ActionScript of the document class.
+// The timeline places a button (character 3) whose DefineButtonSound plays a
+// 0.1 second sound at a constant 0.25 level on rollover.
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.media.SoundMixer;
+    import flash.utils.ByteArray;
+
+    public class Main extends MovieClip {
+        var last;
+
+        public function Main() {
+            addEventListener(Event.ENTER_FRAME, onFrame);
+        }
+
+        function onFrame(e) {
+            var bytes = new ByteArray();
+            SoundMixer.computeSpectrum(bytes, false, 0);
+            var level = Math.round(bytes.readFloat() * 1000) / 1000;
+            if (level !== last) {
+                last = level;
+                trace(level);
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// Assembled by hand; this is the equivalent ActionScript of the frame script.
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+
+    public class Logger extends Sprite {
+        public var label;
+
+        public function Logger(label) {
+            this.label = label;
+            addEventListener(Event.ENTER_FRAME, handle);
+            addEventListener(Event.FRAME_CONSTRUCTED, handle);
+            addEventListener(Event.EXIT_FRAME, handle);
+        }
+
+        public function handle(e) {
+            trace(label + " " + e.type);
+        }
+    }
+}
+
+var a = new Logger("a");
+var b = new Logger("b");
+var c = new Logger("c");
+
+// Frame events are broadcast in the order objects started listening,
+// not in display list order.
+b.addChild(c);
+a.addChild(b);
+
+// Listening again moves `b` to the end of the `enterFrame` broadcast.
+b.removeEventListener(Event.ENTER_FRAME, b.handle);
+b.addEventListener(Event.ENTER_FRAME, b.handle);
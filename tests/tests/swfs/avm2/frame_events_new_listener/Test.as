@@ -0,0 +1,16 @@
+// Assembled by hand; this is the equivalent ActionScript of the frame script.
+// `Logger` is the same class as in `frame_events_listening_order`.
+
+var a = new Logger("a");
+var late;
+
+// An object that starts listening during a broadcast only receives the
+// following ones.
+function spawn(e) {
+    if (!late) {
+        trace("spawning late");
+        late = new Logger("late");
+    }
+}
+
+a.addEventListener(Event.ENTER_FRAME, spawn);
@@ -0,0 +1,32 @@
+// Assembled by hand; this is the equivalent ActionScript of the document class.
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+
+    public class Main extends MovieClip {
+        public var invalidated;
+        public var rerendered;
+
+        public function Main() {
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+            addEventListener(Event.RENDER, onRender);
+        }
+
+        public function onEnterFrame(e) {
+            if (!invalidated) {
+                invalidated = true;
+                stage.invalidate();
+            }
+        }
+
+        // Invalidating the stage while handling `render` requests another
+        // `render` event on the next frame.
+        public function onRender(e) {
+            trace("render handler");
+            if (!rerendered) {
+                rerendered = true;
+                stage.invalidate();
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+// Assembled by hand; this is the equivalent ActionScript of the frame scripts.
+
+// Frame 1
+try {
+    while (true) {}
+} catch (e:Error) {
+    trace(e.message);
+    trace(e.errorID);
+}
+// Ignoring the timeout gets the script terminated with #1503.
+while (true) {}
+trace("unreachable");
+
+// Frame 2
+trace("frame 2");
@@ -0,0 +1,66 @@
+// Assembled by hand; this is the equivalent ActionScript of the document class.
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.FocusEvent;
+
+    public class Main extends Sprite {
+        public var a:Sprite;
+        public var b:Sprite;
+        public var c:Sprite;
+
+        public function Main() {
+            addEventListener("tabEnabledChange", onTabChange);
+            addEventListener("tabIndexChange", onTabChange);
+            stage.addEventListener("keyFocusChange", onKeyFocus);
+            stage.addEventListener("mouseFocusChange", onMouseFocus);
+            stage.addEventListener("focusIn", onFocusIn);
+
+            a = makeBox("a", 0);
+            b = makeBox("b", 100);
+            c = makeBox("c", 200);
+
+            // Setting a property to its current value doesn't dispatch anything.
+            a.tabEnabled = true;
+            a.tabEnabled = true;
+            a.tabIndex = 3;
+            a.tabIndex = 3;
+            b.tabEnabled = true;
+            b.tabIndex = 1;
+            c.tabEnabled = true;
+            c.tabIndex = 2;
+        }
+
+        public function makeBox(name:String, x:Number):Sprite {
+            var box:Sprite = new Sprite();
+            box.name = name;
+            box.x = x;
+            box.graphics.beginFill(0);
+            box.graphics.drawRect(0, 0, 50, 50);
+            addChild(box);
+            return box;
+        }
+
+        public function onTabChange(event:Event):void {
+            trace(event.type + " " + event.target.name);
+        }
+
+        // Refuses the first attempt to focus `c`, and moves it to the end of the tab order instead.
+        public function onKeyFocus(event:FocusEvent):void {
+            trace("keyFocusChange " + event.relatedObject.name);
+            if (event.relatedObject === c && c.tabIndex === 2) {
+                event.preventDefault();
+                c.tabIndex = 4;
+            }
+        }
+
+        public function onMouseFocus(event:FocusEvent):void {
+            trace("mouseFocusChange " + event.relatedObject.name);
+            event.preventDefault();
+        }
+
+        public function onFocusIn(event:FocusEvent):void {
+            trace("focusIn " + event.target.name);
+        }
+    }
+}
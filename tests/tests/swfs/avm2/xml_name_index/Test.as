@@ -0,0 +1,27 @@
+// Assembled by hand; this is the equivalent ActionScript of the frame script.
+var a = new XML('<root><item n="1"/><item n="2"/><item n="3"/><item n="4"/><item n="5"/><item n="6"/><item n="7"/><item n="8"/><item n="9"/><group><item n="g"/></group></root>');
+var b = new XML('<root><item n="b"/></root>');
+trace(a.item.length());
+trace(a..item.length());
+trace(b..item.length());
+
+// Changing another document leaves this one's index alone.
+b.item.setLocalName("thing");
+trace(b..item.length());
+trace(a..item.length());
+
+// Changes deep inside a document clear the index of its root.
+a.group.item.setLocalName("thing");
+trace(a..item.length());
+trace(a..thing.length());
+
+a.appendChild(new XML('<item n="10"/>'));
+trace(a.item.length());
+trace(a..item.length());
+
+var group = a.group;
+trace(group..thing.length());
+
+a.child(0).setLocalName("first");
+trace(a.item.length());
+trace(a.first.length());
@@ -58,6 +58,12 @@ impl ExternalVideoBackend {
         }
     }
 
+    /// Sets how many frames each software-decoded stream decodes ahead.
+    pub fn with_decode_ahead(mut self, frames: usize) -> Self {
+        self.software = self.software.with_decode_ahead(frames);
+        self
+    }
+
     #[cfg(feature = "openh264")]
     pub fn new_with_openh264(openh264_codec: OpenH264Codec) -> Self {
         Self {
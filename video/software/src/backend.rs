@@ -12,6 +12,10 @@ use swf::{VideoCodec, VideoDeblocking};
 /// ship with Ruffle.
 pub struct SoftwareVideoBackend {
     streams: SlotMap<VideoStreamHandle, VideoStream>,
+
+    /// How many frames each stream decodes ahead on a worker thread.
+    /// Zero decodes every frame on the calling thread, when requested.
+    decode_ahead: usize,
 }
 
 impl Default for SoftwareVideoBackend {
@@ -22,14 +26,48 @@ impl Default for SoftwareVideoBackend {
 
 impl SoftwareVideoBackend {
     pub fn new() -> Self {
+        #[cfg(not(target_family = "wasm"))]
+        let decode_ahead = crate::decode_ahead::DEFAULT_DECODE_AHEAD;
+        #[cfg(target_family = "wasm")]
+        let decode_ahead = 0;
+
         Self {
             streams: SlotMap::with_key(),
+            decode_ahead,
         }
     }
+
+    /// Sets how many frames each stream decodes ahead of the one being shown.
+    ///
+    /// This has no effect on platforms without threads.
+    pub fn with_decode_ahead(mut self, frames: usize) -> Self {
+        if cfg!(not(target_family = "wasm")) {
+            self.decode_ahead = frames;
+        }
+        self
+    }
+}
+
+#[allow(unreachable_code, unused_variables)]
+fn make_decoder(
+    size: (u16, u16),
+    codec: VideoCodec,
+    filter: VideoDeblocking,
+) -> Result<Box<dyn VideoDecoder>, Error> {
+    Ok(match codec {
+        #[cfg(feature = "h263")]
+        VideoCodec::H263 => Box::new(crate::decoder::h263::H263Decoder::new(filter)),
+        #[cfg(feature = "vp6")]
+        VideoCodec::Vp6 => Box::new(crate::decoder::vp6::Vp6Decoder::new(false, size)),
+        #[cfg(feature = "vp6")]
+        VideoCodec::Vp6WithAlpha => Box::new(crate::decoder::vp6::Vp6Decoder::new(true, size)),
+        #[cfg(feature = "screenvideo")]
+        VideoCodec::ScreenVideo => Box::new(crate::decoder::screen::ScreenVideoDecoder::new()),
+        other => return Err(Error::UnsupportedCodec(other)),
+    })
 }
 
 impl VideoBackend for SoftwareVideoBackend {
-    #[allow(unreachable_code, unused_variables)]
     fn register_video_stream(
         &mut self,
         _num_frames: u32,
@@ -37,17 +75,18 @@ impl VideoBackend for SoftwareVideoBackend {
         codec: VideoCodec,
         filter: VideoDeblocking,
     ) -> Result<VideoStreamHandle, Error> {
-        let decoder: Box<dyn VideoDecoder> = match codec {
-            #[cfg(feature = "h263")]
-            VideoCodec::H263 => Box::new(crate::decoder::h263::H263Decoder::new(filter)),
-            #[cfg(feature = "vp6")]
-            VideoCodec::Vp6 => Box::new(crate::decoder::vp6::Vp6Decoder::new(false, size)),
-            #[cfg(feature = "vp6")]
-            VideoCodec::Vp6WithAlpha => Box::new(crate::decoder::vp6::Vp6Decoder::new(true, size)),
-            #[cfg(feature = "screenvideo")]
-            VideoCodec::ScreenVideo => Box::new(crate::decoder::screen::ScreenVideoDecoder::new()),
-            other => return Err(Error::UnsupportedCodec(other)),
+        #[cfg(not(target_family = "wasm"))]
+        let decoder = if self.decode_ahead > 0 {
+            StreamDecoder::Worker(crate::decode_ahead::DecodeAheadWorker::spawn(
+                move || make_decoder(size, codec, filter),
+                self.decode_ahead,
+            )?)
+        } else {
+            StreamDecoder::Local(make_decoder(size, codec, filter)?)
         };
+        #[cfg(target_family = "wasm")]
+        let decoder = StreamDecoder::Local(make_decoder(size, codec, filter)?);
+
         let stream = VideoStream::new(decoder);
         let stream_handle = self.streams.insert(stream);
         Ok(stream_handle)
@@ -63,7 +102,11 @@ impl VideoBackend for SoftwareVideoBackend {
             .get_mut(stream)
             .ok_or(Error::VideoStreamIsNotRegistered)?;
 
-        stream.decoder.preload_frame(encoded_frame)
+        match &mut stream.decoder {
+            StreamDecoder::Local(decoder) => decoder.preload_frame(encoded_frame),
+            #[cfg(not(target_family = "wasm"))]
+            StreamDecoder::Worker(worker) => worker.preload_frame(encoded_frame),
+        }
     }
 
     fn configure_video_stream_decoder(
@@ -86,7 +129,11 @@ impl VideoBackend for SoftwareVideoBackend {
             .get_mut(stream)
             .ok_or(Error::VideoStreamIsNotRegistered)?;
 
-        let frame = stream.decoder.decode_frame(encoded_frame)?;
+        let frame = match &mut stream.decoder {
+            StreamDecoder::Local(decoder) => decoder.decode_frame(encoded_frame)?,
+            #[cfg(not(target_family = "wasm"))]
+            StreamDecoder::Worker(worker) => worker.decode_frame(encoded_frame)?,
+        };
 
         let w = frame.width();
         let h = frame.height();
@@ -107,14 +154,24 @@ impl VideoBackend for SoftwareVideoBackend {
     }
 }
 
+/// Where the frames of a stream are decoded.
+enum StreamDecoder {
+    /// Frames are decoded on the calling thread, when requested.
+    Local(Box<dyn VideoDecoder>),
+
+    /// Frames are decoded ahead of time on a worker thread.
+    #[cfg(not(target_family = "wasm"))]
+    Worker(crate::decode_ahead::DecodeAheadWorker),
+}
+
 /// A single preloaded video stream.
 pub struct VideoStream {
     bitmap: Option<BitmapHandle>,
-    decoder: Box<dyn VideoDecoder>,
+    decoder: StreamDecoder,
 }
 
 impl VideoStream {
-    fn new(decoder: Box<dyn VideoDecoder>) -> Self {
+    fn new(decoder: StreamDecoder) -> Self {
         Self {
             decoder,
             bitmap: None,
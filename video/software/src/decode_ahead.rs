@@ -0,0 +1,329 @@
+//! Decoding of video frames ahead of time, on a worker thread.
+//!
+//! Decoders are stateful, so frames have to be decoded in order. While the
+//! player is busy with other work, the worker decodes the frames that follow
+//! the last requested one, so that playing them (or catching up after dropped
+//! frames) only needs the already decoded result.
+
+use crate::decoder::VideoDecoder;
+use ruffle_video::error::Error;
+use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use swf::VideoCodec;
+
+/// The number of frames decoded ahead by default.
+pub const DEFAULT_DECODE_AHEAD: usize = 4;
+
+enum Request {
+    Preload {
+        codec: VideoCodec,
+        data: Vec<u8>,
+        frame_id: u32,
+    },
+    Decode {
+        codec: VideoCodec,
+        data: Vec<u8>,
+        frame_id: u32,
+    },
+}
+
+/// Errors are passed back as strings, as not every error is `Send`.
+enum Response {
+    Started(Result<(), String>),
+    Preloaded(Result<FrameDependency, String>),
+    Decoded(Result<DecodedFrame, String>),
+}
+
+/// A video decoder that lives on its own thread.
+pub struct DecodeAheadWorker {
+    requests: Sender<Request>,
+    responses: Receiver<Response>,
+}
+
+impl DecodeAheadWorker {
+    /// Spawns a worker thread that decodes up to `depth` frames ahead.
+    ///
+    /// The decoder is created on the worker thread, so it doesn't need to be `Send`.
+    pub fn spawn(
+        make_decoder: impl FnOnce() -> Result<Box<dyn VideoDecoder>, Error> + Send + 'static,
+        depth: usize,
+    ) -> Result<Self, Error> {
+        let (requests, request_receiver) = channel();
+        let (response_sender, responses) = channel();
+
+        std::thread::Builder::new()
+            .name("video decoder".to_string())
+            .spawn(move || {
+                let decoder = match make_decoder() {
+                    Ok(decoder) => decoder,
+                    Err(e) => {
+                        let _ = response_sender.send(Response::Started(Err(e.to_string())));
+                        return;
+                    }
+                };
+                if response_sender.send(Response::Started(Ok(()))).is_ok() {
+                    WorkerState::new(decoder, depth).run(request_receiver, response_sender);
+                }
+            })
+            .map_err(|e| Error::DecoderError(Box::new(e)))?;
+
+        match responses.recv() {
+            Ok(Response::Started(Ok(()))) => Ok(Self {
+                requests,
+                responses,
+            }),
+            Ok(Response::Started(Err(e))) => Err(Error::DecoderError(e.into())),
+            _ => Err(worker_stopped()),
+        }
+    }
+
+    pub fn preload_frame(
+        &mut self,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error> {
+        self.requests
+            .send(Request::Preload {
+                codec: encoded_frame.codec,
+                data: encoded_frame.data.to_vec(),
+                frame_id: encoded_frame.frame_id,
+            })
+            .map_err(|_| worker_stopped())?;
+
+        match self.responses.recv() {
+            Ok(Response::Preloaded(result)) => result.map_err(|e| Error::DecoderError(e.into())),
+            _ => Err(worker_stopped()),
+        }
+    }
+
+    pub fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        self.requests
+            .send(Request::Decode {
+                codec: encoded_frame.codec,
+                data: encoded_frame.data.to_vec(),
+                frame_id: encoded_frame.frame_id,
+            })
+            .map_err(|_| worker_stopped())?;
+
+        match self.responses.recv() {
+            Ok(Response::Decoded(result)) => result.map_err(|e| Error::DecoderError(e.into())),
+            _ => Err(worker_stopped()),
+        }
+    }
+}
+
+fn worker_stopped() -> Error {
+    Error::DecoderError("Video decoder thread stopped".into())
+}
+
+struct WorkerState {
+    decoder: Box<dyn VideoDecoder>,
+    depth: usize,
+
+    /// The data of every preloaded frame, so that the following frames can be
+    /// decoded without waiting for the player to ask for them.
+    frames: BTreeMap<u32, (VideoCodec, Vec<u8>)>,
+
+    /// Frames that were decoded ahead of time, in order.
+    ahead: VecDeque<(u32, Result<DecodedFrame, String>)>,
+
+    /// The last frame that was passed to the decoder, if we may continue from it.
+    position: Option<u32>,
+}
+
+impl WorkerState {
+    fn new(decoder: Box<dyn VideoDecoder>, depth: usize) -> Self {
+        Self {
+            decoder,
+            depth,
+            frames: BTreeMap::new(),
+            ahead: VecDeque::new(),
+            position: None,
+        }
+    }
+
+    fn run(mut self, requests: Receiver<Request>, responses: Sender<Response>) {
+        loop {
+            let request = if let Some(next_frame) = self.next_frame_to_decode_ahead() {
+                match requests.try_recv() {
+                    Ok(request) => request,
+                    Err(TryRecvError::Empty) => {
+                        self.decode_ahead(next_frame);
+                        continue;
+                    }
+                    Err(TryRecvError::Disconnected) => return,
+                }
+            } else {
+                match requests.recv() {
+                    Ok(request) => request,
+                    Err(_) => return,
+                }
+            };
+
+            let response = match request {
+                Request::Preload {
+                    codec,
+                    data,
+                    frame_id,
+                } => Response::Preloaded(self.preload(codec, data, frame_id)),
+                Request::Decode {
+                    codec,
+                    data,
+                    frame_id,
+                } => Response::Decoded(self.decode(codec, &data, frame_id)),
+            };
+
+            if responses.send(response).is_err() {
+                return;
+            }
+        }
+    }
+
+    fn preload(
+        &mut self,
+        codec: VideoCodec,
+        data: Vec<u8>,
+        frame_id: u32,
+    ) -> Result<FrameDependency, String> {
+        // Anything decoded ahead from this frame on may be out of date now.
+        if self.ahead.iter().any(|(id, _)| *id >= frame_id) {
+            self.ahead.clear();
+            self.position = None;
+        }
+
+        let dependency = self
+            .decoder
+            .preload_frame(EncodedFrame {
+                codec,
+                data: &data,
+                frame_id,
+            })
+            .map_err(|e| e.to_string());
+        self.frames.insert(frame_id, (codec, data));
+        dependency
+    }
+
+    fn decode(
+        &mut self,
+        codec: VideoCodec,
+        data: &[u8],
+        frame_id: u32,
+    ) -> Result<DecodedFrame, String> {
+        while let Some((id, _)) = self.ahead.front() {
+            if *id == frame_id {
+                let (_, frame) = self.ahead.pop_front().expect("Front was checked");
+                return frame;
+            }
+            if *id > frame_id {
+                break;
+            }
+            self.ahead.pop_front();
+        }
+
+        // Not decoded ahead, so this must be a keyframe or the frame right
+        // after the last one we decoded; the player only seeks that way.
+        self.ahead.clear();
+        self.position = Some(frame_id);
+        self.decoder
+            .decode_frame(EncodedFrame {
+                codec,
+                data,
+                frame_id,
+            })
+            .map_err(|e| e.to_string())
+    }
+
+    fn next_frame_to_decode_ahead(&self) -> Option<u32> {
+        if self.ahead.len() >= self.depth {
+            return None;
+        }
+        let position = self.position?;
+        self.frames
+            .range(position.checked_add(1)?..)
+            .next()
+            .map(|(frame_id, _)| *frame_id)
+    }
+
+    fn decode_ahead(&mut self, frame_id: u32) {
+        let (codec, data) = &self.frames[&frame_id];
+        let frame = self
+            .decoder
+            .decode_frame(EncodedFrame {
+                codec: *codec,
+                data,
+                frame_id,
+            })
+            .map_err(|e| e.to_string());
+        self.ahead.push_back((frame_id, frame));
+        self.position = Some(frame_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::bitmap::{Bitmap, BitmapFormat};
+    use std::sync::{Arc, Mutex};
+
+    /// Produces 1x1 frames whose red channel is the frame's first data byte.
+    struct FakeDecoder(Arc<Mutex<Vec<u32>>>);
+
+    impl VideoDecoder for FakeDecoder {
+        fn preload_frame(&mut self, _frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+            Ok(FrameDependency::None)
+        }
+
+        fn decode_frame(&mut self, frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+            self.0.lock().unwrap().push(frame.frame_id);
+            Ok(Bitmap::new(
+                1,
+                1,
+                BitmapFormat::Rgba,
+                vec![frame.data[0], 0, 0, 255],
+            ))
+        }
+    }
+
+    fn frame(frame_id: u32, data: &[u8]) -> EncodedFrame<'_> {
+        EncodedFrame {
+            codec: VideoCodec::H263,
+            data,
+            frame_id,
+        }
+    }
+
+    #[test]
+    fn decodes_requested_frames() {
+        let decoded = Arc::new(Mutex::new(Vec::new()));
+        let log = decoded.clone();
+        let mut worker = DecodeAheadWorker::spawn(
+            move || Ok(Box::new(FakeDecoder(log)) as Box<dyn VideoDecoder>),
+            2,
+        )
+        .unwrap();
+
+        let data: Vec<[u8; 1]> = (0..6).map(|i| [i * 10]).collect();
+        for (i, data) in data.iter().enumerate() {
+            worker.preload_frame(frame(i as u32, data)).unwrap();
+        }
+
+        // Frames must match what was asked for, whether they were decoded
+        // ahead of time or not, including when seeking back.
+        for i in [0, 1, 2, 4, 5, 0, 1] {
+            let bitmap = worker.decode_frame(frame(i, &data[i as usize])).unwrap();
+            assert_eq!(bitmap.data()[0], i as u8 * 10);
+        }
+
+        // Every frame is decoded in order from where decoding last started.
+        let decoded = decoded.lock().unwrap();
+        for pair in decoded.windows(2) {
+            assert!(pair[1] == pair[0] + 1 || pair[1] == 0 || pair[1] == 4);
+        }
+    }
+
+    #[test]
+    fn reports_decoder_creation_errors() {
+        let result = DecodeAheadWorker::spawn(|| Err(Error::DecodingNotSupported), 2);
+        assert!(result.is_err());
+    }
+}
@@ -1,2 +1,4 @@
 pub mod backend;
+#[cfg(not(target_family = "wasm"))]
+pub mod decode_ahead;
 pub mod decoder;
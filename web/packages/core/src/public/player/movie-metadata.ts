@@ -42,4 +42,10 @@ export interface MovieMetadata {
      * Uncompressed length in bytes.
      */
     readonly uncompressedLength: number;
+
+    /**
+     * The XMP metadata of the movie, an RDF document describing e.g. its title and author.
+     * May be `null` if the movie has no metadata.
+     */
+    readonly xmpMetadata: string | null;
 }
@@ -1,4 +1,4 @@
-use ruffle_core::events::{KeyCode, TextControlCode};
+use ruffle_core::events::{KeyCode, KeyLocation, TextControlCode};
 
 /// Convert a web `KeyboardEvent.code` value into a Ruffle `KeyCode`.
 /// Return `KeyCode::Unknown` if there is no matching Flash key code.
@@ -117,6 +117,16 @@ pub fn web_to_ruffle_key_code(key_code: &str) -> KeyCode {
     }
 }
 
+/// Convert a web `KeyboardEvent.location` value into a Ruffle `KeyLocation`.
+pub fn web_to_ruffle_key_location(location: u32) -> KeyLocation {
+    match location {
+        1 => KeyLocation::Left,
+        2 => KeyLocation::Right,
+        3 => KeyLocation::NumPad,
+        _ => KeyLocation::Standard,
+    }
+}
+
 /// Convert a web `KeyboardEvent.key` value into a character codepoint.
 /// Return `None` if they input was not a printable character.
 pub fn web_key_to_codepoint(key: &str) -> Option<char> {
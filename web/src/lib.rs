@@ -14,7 +14,10 @@ mod zip;
 
 use crate::builder::RuffleInstanceBuilder;
 use external_interface::{external_to_js_value, js_to_external_value};
-use input::{web_key_to_codepoint, web_to_ruffle_key_code, web_to_ruffle_text_control};
+use input::{
+    web_key_to_codepoint, web_to_ruffle_key_code, web_to_ruffle_key_location,
+    web_to_ruffle_text_control,
+};
 use js_sys::{Error as JsError, Uint8Array};
 use ruffle_core::context::UpdateContext;
 use ruffle_core::context_menu::ContextMenuCallback;
@@ -214,6 +217,7 @@ struct MovieMetadata {
     is_action_script_3: bool,
     #[serde(rename = "uncompressedLength")]
     uncompressed_len: i32,
+    xmp_metadata: Option<String>,
 }
 
 #[wasm_bindgen]
@@ -669,17 +673,26 @@ impl RuffleHandle {
                 false,
                 move |js_event: WheelEvent| {
                     let _ = ruffle.with_instance(|instance| {
-                        let delta = match js_event.delta_mode() {
-                            WheelEvent::DOM_DELTA_LINE => {
-                                MouseWheelDelta::Lines(-js_event.delta_y())
-                            }
-                            WheelEvent::DOM_DELTA_PIXEL => {
-                                MouseWheelDelta::Pixels(-js_event.delta_y())
-                            }
+                        let (horizontal, vertical) = match js_event.delta_mode() {
+                            WheelEvent::DOM_DELTA_LINE => (
+                                MouseWheelDelta::Lines(-js_event.delta_x()),
+                                MouseWheelDelta::Lines(-js_event.delta_y()),
+                            ),
+                            WheelEvent::DOM_DELTA_PIXEL => (
+                                MouseWheelDelta::Pixels(-js_event.delta_x()),
+                                MouseWheelDelta::Pixels(-js_event.delta_y()),
+                            ),
                             _ => return,
                         };
                         let _ = instance.with_core_mut(|core| {
-                            core.handle_event(PlayerEvent::MouseWheel { delta });
+                            if vertical.lines() != 0.0 {
+                                core.handle_event(PlayerEvent::MouseWheel { delta: vertical });
+                            }
+                            if horizontal.lines() != 0.0 {
+                                core.handle_event(PlayerEvent::MouseHorizontalWheel {
+                                    delta: horizontal,
+                                });
+                            }
                             if core.should_prevent_scrolling() {
                                 js_event.prevent_default();
                             }
@@ -700,8 +713,16 @@ impl RuffleHandle {
                             let _ = instance.with_core_mut(|core| {
                                 let key_code = web_to_ruffle_key_code(&js_event.code());
                                 let key_char = web_key_to_codepoint(&js_event.key());
+                                let key_location = web_to_ruffle_key_location(js_event.location());
                                 let is_ctrl_cmd = js_event.ctrl_key() || js_event.meta_key();
-                                core.handle_event(PlayerEvent::KeyDown { key_code, key_char });
+                                // AltGr is ignored by FP, only the characters it produces matter.
+                                if js_event.key() != "AltGraph" {
+                                    core.handle_event(PlayerEvent::KeyDown {
+                                        key_code,
+                                        key_char,
+                                        key_location,
+                                    });
+                                }
 
                                 if let Some(control_code) = web_to_ruffle_text_control(
                                     &js_event.key(),
@@ -769,7 +790,14 @@ impl RuffleHandle {
                             let _ = instance.with_core_mut(|core| {
                                 let key_code = web_to_ruffle_key_code(&js_event.code());
                                 let key_char = web_key_to_codepoint(&js_event.key());
-                                core.handle_event(PlayerEvent::KeyUp { key_code, key_char });
+                                let key_location = web_to_ruffle_key_location(js_event.location());
+                                if js_event.key() != "AltGraph" {
+                                    core.handle_event(PlayerEvent::KeyUp {
+                                        key_code,
+                                        key_char,
+                                        key_location,
+                                    });
+                                }
                             });
                             js_event.prevent_default();
                         }
@@ -1101,6 +1129,7 @@ impl RuffleHandle {
                 swf_version: swf_header.version(),
                 background_color,
                 is_action_script_3: swf_header.is_action_script_3(),
+                xmp_metadata: swf_header.metadata().map(str::to_owned),
             };
 
             if let Ok(value) = serde_wasm_bindgen::to_value(&metadata) {
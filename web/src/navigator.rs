@@ -534,6 +534,19 @@ impl SuccessResponse for WebResponseWrapper {
         self.response.redirected()
     }
 
+    fn headers(&self) -> Vec<(String, String)> {
+        let Ok(Some(entries)) = js_sys::try_iter(&self.response.headers()) else {
+            return Vec::new();
+        };
+
+        entries
+            .filter_map(|entry| {
+                let entry: Array = entry.ok()?.dyn_into().ok()?;
+                Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+            })
+            .collect()
+    }
+
     #[allow(clippy::await_holding_refcell_ref)]
     fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
         if self.body_stream.is_none() {